@@ -0,0 +1,198 @@
+//! Optional encryption of cached issue and comment bodies
+//!
+//! With `[cache] encrypt = true` (or after `isq cache encrypt`), bodies are
+//! stored as AES-256-GCM ciphertext: `isq:enc:v1:` and the base64 of a random
+//! nonce followed by the sealed text. The key lives in the OS keyring, or in
+//! `ISQ_CACHE_KEY` (base64, 32 bytes) where there's no keyring.
+//!
+//! Titles, labels and other metadata stay readable so lists and filters keep
+//! working. Comment search needs plaintext, so it's unavailable while
+//! comments are encrypted.
+//!
+//! Encryption happens in SQL: `db::open` installs `isq_encrypt(text)` and
+//! `isq_decrypt(text)` on each connection. Values without the prefix pass
+//! through `isq_decrypt` untouched, so a cache can hold both while converting.
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use once_cell::sync::OnceCell;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use rusqlite::functions::FunctionFlags;
+use rusqlite::Connection;
+
+use crate::credentials;
+
+/// Marks an encrypted value
+pub const PREFIX: &str = "isq:enc:v1:";
+
+/// Keyring entry holding the cache key
+const KEYRING_ENTRY: &str = "cache-key";
+
+/// Environment variable that overrides the keyring
+const KEY_ENV: &str = "ISQ_CACHE_KEY";
+
+const KEY_LEN: usize = 32;
+
+/// Where a connection gets its cipher when it first needs one; the flag says
+/// whether a missing key may be created (writing) or not (reading)
+pub type CipherSource = fn(bool) -> Result<&'static Cipher>;
+
+pub struct Cipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl Cipher {
+    pub fn new(key: &[u8]) -> Result<Self> {
+        let key = UnboundKey::new(&AES_256_GCM, key)
+            .map_err(|_| anyhow!("Cache key must be {} bytes", KEY_LEN))?;
+        Ok(Cipher { key: LessSafeKey::new(key), rng: SystemRandom::new() })
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng.fill(&mut nonce).map_err(|_| anyhow!("No randomness available for encryption"))?;
+
+        let mut sealed = plaintext.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut sealed)
+            .map_err(|_| anyhow!("Encryption failed"))?;
+
+        let mut bytes = nonce.to_vec();
+        bytes.extend_from_slice(&sealed);
+        Ok(format!("{}{}", PREFIX, STANDARD.encode(bytes)))
+    }
+
+    pub fn decrypt(&self, value: &str) -> Result<String> {
+        let encoded = value.strip_prefix(PREFIX).ok_or_else(|| anyhow!("Not an encrypted value"))?;
+        let mut bytes = STANDARD.decode(encoded).map_err(|e| anyhow!("Corrupt encrypted value: {}", e))?;
+        if bytes.len() < NONCE_LEN {
+            anyhow::bail!("Corrupt encrypted value: too short");
+        }
+
+        let mut sealed = bytes.split_off(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(&bytes).map_err(|_| anyhow!("Corrupt encrypted value"))?;
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::empty(), &mut sealed)
+            .map_err(|_| anyhow!("Can't decrypt the cache: wrong key (check {} or the keyring)", KEY_ENV))?;
+        Ok(String::from_utf8(plaintext.to_vec())?)
+    }
+}
+
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(PREFIX)
+}
+
+/// The cache key from `ISQ_CACHE_KEY` or the keyring; with `create`, a new
+/// one is generated and stored in the keyring if there's none yet
+fn load_key(create: bool) -> Result<Vec<u8>> {
+    if let Ok(encoded) = std::env::var(KEY_ENV) {
+        return STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| anyhow!("{} isn't valid base64: {}", KEY_ENV, e));
+    }
+    if let Some(stored) = credentials::get_credential(KEYRING_ENTRY)? {
+        return STANDARD
+            .decode(&stored.access_token)
+            .map_err(|e| anyhow!("Cache key in the keyring is corrupt: {}", e));
+    }
+    if !create {
+        anyhow::bail!("The cache is encrypted but no key was found (set {} or restore the keyring entry)", KEY_ENV);
+    }
+
+    let mut key = [0u8; KEY_LEN];
+    SystemRandom::new()
+        .fill(&mut key)
+        .map_err(|_| anyhow!("No randomness available to create a cache key"))?;
+    credentials::set_credential(KEYRING_ENTRY, &STANDARD.encode(key), None, None).map_err(|_| {
+        anyhow!(
+            "No keyring to keep the cache key in. Set {} to a base64-encoded {}-byte key instead \
+             (e.g. `openssl rand -base64 32`)",
+            KEY_ENV,
+            KEY_LEN
+        )
+    })?;
+    Ok(key.to_vec())
+}
+
+/// The cache's cipher, with the key from `load_key`
+pub fn cache_cipher(create: bool) -> Result<&'static Cipher> {
+    static CIPHER: OnceCell<Cipher> = OnceCell::new();
+    CIPHER.get_or_try_init(|| Cipher::new(&load_key(create)?))
+}
+
+fn function_error(e: anyhow::Error) -> rusqlite::Error {
+    rusqlite::Error::UserFunctionError(e.into())
+}
+
+/// Install `isq_encrypt` and `isq_decrypt` on a connection. `isq_encrypt`
+/// only encrypts when `encrypt` is set; decrypting works either way.
+pub fn register(conn: &Connection, encrypt: bool, source: CipherSource) -> Result<()> {
+    // Fail now rather than on the first write
+    if encrypt {
+        source(true)?;
+    }
+
+    conn.create_scalar_function("isq_encrypt", 1, FunctionFlags::SQLITE_UTF8, move |ctx| {
+        let value: Option<String> = ctx.get(0)?;
+        match value {
+            Some(text) if encrypt && !is_encrypted(&text) => {
+                source(true).and_then(|c| c.encrypt(&text)).map(Some).map_err(function_error)
+            }
+            other => Ok(other),
+        }
+    })?;
+    conn.create_scalar_function(
+        "isq_decrypt",
+        1,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        move |ctx| {
+            let value: Option<String> = ctx.get(0)?;
+            match value {
+                Some(text) if is_encrypted(&text) => {
+                    source(false).and_then(|c| c.decrypt(&text)).map(Some).map_err(function_error)
+                }
+                other => Ok(other),
+            }
+        },
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub fn test_cipher(_create: bool) -> Result<&'static Cipher> {
+    static CIPHER: once_cell::sync::Lazy<Cipher> = once_cell::sync::Lazy::new(|| Cipher::new(&[7; KEY_LEN]).unwrap());
+    Ok(&CIPHER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let cipher = test_cipher(false).unwrap();
+        let sealed = cipher.encrypt("Secret plans").unwrap();
+        assert!(is_encrypted(&sealed));
+        assert!(!sealed.contains("Secret"));
+        assert_eq!(cipher.decrypt(&sealed).unwrap(), "Secret plans");
+
+        // Random nonces: the same text never encrypts the same way twice
+        assert_ne!(cipher.encrypt("Secret plans").unwrap(), sealed);
+    }
+
+    #[test]
+    fn test_wrong_key_or_tampering_fails() {
+        let sealed = test_cipher(false).unwrap().encrypt("Secret plans").unwrap();
+        assert!(Cipher::new(&[8; KEY_LEN]).unwrap().decrypt(&sealed).is_err());
+
+        let mut bytes = STANDARD.decode(&sealed[PREFIX.len()..]).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        let tampered = format!("{}{}", PREFIX, STANDARD.encode(bytes));
+        assert!(test_cipher(false).unwrap().decrypt(&tampered).is_err());
+
+        assert!(Cipher::new(&[1; 16]).is_err());
+    }
+}
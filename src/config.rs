@@ -0,0 +1,344 @@
+//! User configuration (~/.config/isq/config.toml)
+//!
+//! Everything is optional: a missing file or section means defaults.
+//!
+//! ```toml
+//! [github.app]     # used for the owner's repos; others keep the user token
+//! owner = "acme"
+//! app_id = 123456
+//! installation_id = 7890123
+//! private_key_path = "~/.config/isq/my-app.private-key.pem"
+//!
+//! [display]
+//! theme = "ascii"   # auto (default), unicode, ascii, nerd-font
+//! output = "json"   # plain (default); json makes --json the default off-terminal
+//! clock = "24h"     # 12h (default); see timefmt.rs for timezone and timestamps
+//!
+//! [list]            # exclusions are hidden from `isq issue list` unless --no-exclude
+//! exclude_authors = ["dependabot"]
+//! exclude_labels = ["wontfix"]
+//! maintainers = ["alice"] # their comments count as replies for --unanswered
+//!
+//! [hooks]           # see hooks.rs
+//! on_issue_created = "./notify.sh"
+//!
+//! [[labels.taxonomy]]  # see taxonomy.rs
+//! name = "bug"
+//! color = "d73a4a"
+//!
+//! [[notes.sections]]   # see notes.rs
+//! title = "Features"
+//! labels = ["enhancement"]
+//!
+//! [reminders]          # see reminders.rs
+//! days = 7
+//!
+//! [wip]                # see wip.rs
+//! limit = 3
+//!
+//! [types.labels]       # see issue_types.rs
+//! bug = "Bug"
+//!
+//! [create]             # see create_rules.rs
+//! labels = ["needs-triage"]
+//! assignee = "@me"
+//!
+//! [queue]              # queued writes that don't sync
+//! warn_days = 1        # `isq status` warns about older ones
+//! max_age_days = 7     # the daemon then gives up on them (0 = never)
+//!
+//! [digest]             # see digest.rs
+//! dir = "~/isq-digests"
+//!
+//! [network]            # see network.rs
+//! command_timeout_secs = 600
+//!
+//! [daemon]             # see metrics.rs
+//! metrics_addr = "127.0.0.1:9464"
+//!
+//! [daemon.storage]     # see storage.rs
+//! max_db_mb = 1024
+//!
+//! [cache]
+//! encrypt = true       # see cipher.rs
+//! dir = "~/isq-data"   # shared cache location (ISQ_DATA_DIR overrides)
+//! per_repo = true      # each checkout's cache in .git/isq.db
+//! ```
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::create_rules::CreateConfig;
+use crate::digest::DigestConfig;
+use crate::display::{OutputFormat, Theme};
+use crate::hooks::HooksConfig;
+use crate::issue_types::TypesConfig;
+use crate::network::NetworkConfig;
+use crate::notes::NotesConfig;
+use crate::reminders::RemindersConfig;
+use crate::storage::StorageConfig;
+use crate::taxonomy::LabelsConfig;
+use crate::timefmt::{Clock, Timestamps, Zone};
+use crate::wip::WipConfig;
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub github: GitHubConfig,
+    pub display: DisplayConfig,
+    pub list: ListConfig,
+    pub hooks: HooksConfig,
+    pub labels: LabelsConfig,
+    pub notes: NotesConfig,
+    pub reminders: RemindersConfig,
+    pub wip: WipConfig,
+    pub types: TypesConfig,
+    pub create: CreateConfig,
+    pub queue: QueueConfig,
+    pub digest: DigestConfig,
+    pub network: NetworkConfig,
+    pub daemon: DaemonConfig,
+    pub cache: CacheConfig,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// Glyph theme: auto, unicode, ascii, nerd-font
+    pub theme: Theme,
+    /// Default output: plain, or json (when stdout isn't a terminal)
+    pub output: OutputFormat,
+    /// 12h or 24h
+    pub clock: Clock,
+    /// local, UTC, or a fixed offset like "+05:30"
+    pub timezone: Zone,
+    /// Issue and comment times: relative ("5d ago") or absolute
+    pub timestamps: Timestamps,
+}
+
+/// Local cache options
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Encrypt cached issue and comment bodies at rest
+    pub encrypt: bool,
+    /// Directory for the shared cache (`~` is expanded; `ISQ_DATA_DIR` wins)
+    pub dir: Option<String>,
+    /// Keep each repo's cache in its own `.git/isq.db`
+    pub per_repo: bool,
+}
+
+impl CacheConfig {
+    pub fn dir(&self) -> Option<PathBuf> {
+        self.dir.as_deref().map(expand_home)
+    }
+}
+
+/// When queued writes count as stuck
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct QueueConfig {
+    /// `isq status` warns about writes queued longer than this
+    pub warn_days: u32,
+    /// The daemon moves writes queued longer than this to the failed list (0 = never)
+    pub max_age_days: u32,
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        QueueConfig { warn_days: 1, max_age_days: 7 }
+    }
+}
+
+/// Options for the background daemon
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct DaemonConfig {
+    /// Serve Prometheus metrics on this address (e.g. 127.0.0.1:9464)
+    pub metrics_addr: Option<String>,
+    /// Checkpoints and size limits for the cache files
+    pub storage: StorageConfig,
+}
+
+/// Default exclusions for `isq issue list`, and who answers issues
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct ListConfig {
+    /// Authors to hide; `dependabot` also hides `dependabot[bot]`
+    pub exclude_authors: Vec<String>,
+    /// Labels to hide (case-insensitive)
+    pub exclude_labels: Vec<String>,
+    /// Whose comments count as a reply for `--unanswered` and `isq report
+    /// aging` (empty: any comment for `--unanswered`, anyone but the author
+    /// for the report)
+    pub maintainers: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct GitHubConfig {
+    /// Authenticate as a GitHub App installation instead of a user token
+    pub app: Option<GitHubAppConfig>,
+}
+
+impl GitHubConfig {
+    /// The App to use for `owner`'s repos, if its installation covers them
+    pub fn app_for(&self, owner: &str) -> Option<GitHubAppConfig> {
+        self.app.clone().filter(|app| app.owner.eq_ignore_ascii_case(owner))
+    }
+}
+
+/// GitHub App credentials used to mint short-lived installation tokens
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubAppConfig {
+    /// Org or user the app is installed on; only their repos use it
+    pub owner: String,
+    pub app_id: u64,
+    pub installation_id: u64,
+    /// Path to the app's PEM private key (`~` is expanded)
+    pub private_key_path: String,
+}
+
+impl GitHubAppConfig {
+    pub fn private_key_path(&self) -> PathBuf {
+        expand_home(&self.private_key_path)
+    }
+}
+
+/// Config directory (`$XDG_CONFIG_HOME/isq` or `~/.config/isq` on every platform)
+pub fn config_dir() -> Result<PathBuf> {
+    if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME") {
+        return Ok(PathBuf::from(xdg).join("isq"));
+    }
+    let dirs = directories::BaseDirs::new()
+        .ok_or_else(|| anyhow!("Could not determine home directory"))?;
+    Ok(dirs.home_dir().join(".config").join("isq"))
+}
+
+/// Path to the user config file
+pub fn config_path() -> Result<PathBuf> {
+    Ok(config_dir()?.join("config.toml"))
+}
+
+/// Load the user config (defaults if the file doesn't exist)
+pub fn load() -> Result<Config> {
+    let path = config_path()?;
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => parse(&contents).map_err(|e| anyhow!("Invalid config {}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(e) => Err(anyhow!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+/// Parse config from TOML
+pub fn parse(contents: &str) -> Result<Config> {
+    Ok(toml::from_str(contents)?)
+}
+
+/// Expand a leading `~/` to the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/")
+        && let Some(dirs) = directories::BaseDirs::new()
+    {
+        return dirs.home_dir().join(rest);
+    }
+    PathBuf::from(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_empty_config() {
+        let config = parse("").unwrap();
+        assert!(config.github.app.is_none());
+    }
+
+    #[test]
+    fn test_parse_github_app() {
+        let config = parse(
+            r#"
+            [github.app]
+            owner = "acme"
+            app_id = 123
+            installation_id = 456
+            private_key_path = "/keys/app.pem"
+            "#,
+        )
+        .unwrap();
+
+        // Other owners' repos can't be reached through the installation
+        assert!(config.github.app_for("Acme").is_some());
+        assert!(config.github.app_for("someone-else").is_none());
+
+        let app = config.github.app.unwrap();
+        assert_eq!(app.app_id, 123);
+        assert_eq!(app.installation_id, 456);
+        assert_eq!(app.private_key_path(), PathBuf::from("/keys/app.pem"));
+    }
+
+    #[test]
+    fn test_parse_display_theme() {
+        assert_eq!(parse("").unwrap().display.theme, Theme::Auto);
+        assert_eq!(parse("[display]\ntheme = \"nerd-font\"\n").unwrap().display.theme, Theme::NerdFont);
+        assert!(parse("[display]\ntheme = \"emoji\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_display_time() {
+        let display = parse("[display]\nclock = \"24h\"\ntimezone = \"+05:30\"\ntimestamps = \"absolute\"\n")
+            .unwrap()
+            .display;
+        assert_eq!(display.clock, Clock::TwentyFourHour);
+        assert_eq!(display.timezone, Zone::parse("+05:30").unwrap());
+        assert_eq!(display.timestamps, Timestamps::Absolute);
+        assert_eq!(parse("").unwrap().display.timezone, Zone::Local);
+        assert!(parse("[display]\ntimezone = \"Mars/Olympus\"\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_cache() {
+        let cache = parse("[cache]\ndir = \"/data/isq\"\nper_repo = true\n").unwrap().cache;
+        assert_eq!(cache.dir(), Some(PathBuf::from("/data/isq")));
+        assert!(cache.per_repo);
+        assert!(!cache.encrypt);
+        assert!(parse("").unwrap().cache.dir().is_none());
+    }
+
+    #[test]
+    fn test_parse_queue() {
+        let queue = parse("").unwrap().queue;
+        assert_eq!((queue.warn_days, queue.max_age_days), (1, 7));
+        let queue = parse("[queue]\nmax_age_days = 0\n").unwrap().queue;
+        assert_eq!((queue.warn_days, queue.max_age_days), (1, 0));
+    }
+
+    #[test]
+    fn test_parse_network() {
+        let network = parse("[network]\nread_timeout_secs = 5\ncommand_timeout_secs = 0\n").unwrap().network;
+        assert_eq!((network.connect_timeout_secs, network.read_timeout_secs), (10, 5));
+        assert!(network.command_timeout().is_none());
+    }
+
+    #[test]
+    fn test_parse_hooks() {
+        let config = parse("[hooks]\non_sync_error = \"notify-send isq\"\n").unwrap();
+        assert_eq!(config.hooks.on_sync_error.as_deref(), Some("notify-send isq"));
+        assert!(config.hooks.on_issue_created.is_none());
+    }
+
+    #[test]
+    fn test_parse_github_app_missing_field() {
+        assert!(parse("[github.app]\napp_id = 123\n").is_err());
+        assert!(parse("[github.app]\napp_id = 1\ninstallation_id = 2\nprivate_key_path = \"k.pem\"\n").is_err());
+    }
+
+    #[test]
+    fn test_expand_home() {
+        assert_eq!(expand_home("/abs/key.pem"), PathBuf::from("/abs/key.pem"));
+        assert!(!expand_home("~/key.pem").starts_with("~"));
+    }
+}
@@ -0,0 +1,166 @@
+//! Flattened issue + comments markdown for LLM context windows
+//!
+//! `isq issue context <id>` renders one self-contained markdown document.
+//! With a token budget, the oldest comments are dropped first (the issue body
+//! and the latest discussion are usually what matters), then the body itself
+//! is cut if it still doesn't fit.
+
+use crate::db::Comment;
+use crate::forges::Issue;
+
+/// Rough token estimate (~4 characters per token for English text)
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Render the issue header (title + metadata)
+fn render_header(issue: &Issue) -> String {
+    let mut out = format!("# {} {}\n\n", issue.display_id(), issue.title);
+
+    let mut meta = vec![
+        format!("- State: {}", issue.state),
+        format!("- Author: @{}", issue.author),
+    ];
+    if !issue.assignees.is_empty() {
+        let assignees: Vec<_> = issue.assignees.iter().map(|a| format!("@{}", a)).collect();
+        meta.push(format!("- Assignees: {}", assignees.join(", ")));
+    }
+    if !issue.labels.is_empty() {
+        let labels: Vec<_> = issue.labels.iter().map(|l| l.name.as_str()).collect();
+        meta.push(format!("- Labels: {}", labels.join(", ")));
+    }
+    if let Some(goal) = &issue.milestone {
+        meta.push(format!("- Goal: {}", goal));
+    }
+    meta.push(format!("- Created: {}", issue.created_at));
+    meta.push(format!("- Updated: {}", issue.updated_at));
+    if let Some(url) = &issue.url {
+        meta.push(format!("- URL: {}", url));
+    }
+
+    out.push_str(&meta.join("\n"));
+    out.push('\n');
+    out
+}
+
+fn render_body(body: &str) -> String {
+    format!("\n## Description\n\n{}\n", body.trim())
+}
+
+fn render_comment(comment: &Comment) -> String {
+    format!("\n### @{} ({})\n\n{}\n", comment.author, comment.created_at, comment.body.trim())
+}
+
+/// Render an issue and its comments (oldest first) as markdown,
+/// fitting within `max_tokens` if given
+pub fn render_context(issue: &Issue, comments: &[Comment], max_tokens: Option<usize>) -> String {
+    let header = render_header(issue);
+    let mut body = issue
+        .body
+        .as_deref()
+        .filter(|b| !b.trim().is_empty())
+        .map(render_body)
+        .unwrap_or_default();
+    let rendered: Vec<String> = comments.iter().map(render_comment).collect();
+
+    // Keep the newest comments that fit; drop from the oldest end
+    let mut skip = 0;
+    if let Some(budget) = max_tokens {
+        let fixed = estimate_tokens(&header) + estimate_tokens(&body) + 20; // headings + omission note
+        let mut used: usize = rendered.iter().map(|c| estimate_tokens(c)).sum();
+        while skip < rendered.len() && fixed + used > budget {
+            used -= estimate_tokens(&rendered[skip]);
+            skip += 1;
+        }
+
+        // Still over budget with no comments: cut the description
+        let available = budget.saturating_sub(estimate_tokens(&header) + 20);
+        if skip == rendered.len() && estimate_tokens(&body) > available {
+            let keep_chars = available * 4;
+            body = body.chars().take(keep_chars).collect::<String>();
+            body.push_str("\n\n_[description truncated]_\n");
+        }
+    }
+
+    let mut out = header;
+    out.push_str(&body);
+
+    if !comments.is_empty() {
+        out.push_str(&format!("\n## Comments ({})\n", comments.len()));
+        if skip > 0 {
+            out.push_str(&format!("\n_[{} older comments omitted to fit token budget]_\n", skip));
+        }
+        for comment in &rendered[skip..] {
+            out.push_str(comment);
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_issue(body: Option<&str>) -> Issue {
+        Issue {
+            number: 42,
+            title: "Login fails".to_string(),
+            body: body.map(String::from),
+            state: "open".to_string(),
+            author: "alice".to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-02T00:00:00Z".to_string(),
+            milestone: Some("v1.0".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn make_comment(id: &str, body: &str) -> Comment {
+        Comment {
+            comment_id: id.to_string(),
+            issue_number: 42,
+            body: body.to_string(),
+            author: "bob".to_string(),
+            created_at: format!("2024-01-0{}T00:00:00Z", id),
+        }
+    }
+
+    #[test]
+    fn test_render_full_context() {
+        let comments = vec![make_comment("1", "First"), make_comment("2", "Second")];
+        let out = render_context(&make_issue(Some("Steps to reproduce")), &comments, None);
+
+        assert!(out.starts_with("# #42 Login fails"));
+        assert!(out.contains("- Goal: v1.0"));
+        assert!(out.contains("## Description\n\nSteps to reproduce"));
+        assert!(out.contains("## Comments (2)"));
+        assert!(out.find("First").unwrap() < out.find("Second").unwrap());
+        assert!(!out.contains("omitted"));
+    }
+
+    #[test]
+    fn test_truncates_oldest_comments_first() {
+        let comments: Vec<_> = (1..=5)
+            .map(|i| make_comment(&i.to_string(), &format!("comment {} {}", i, "x".repeat(400))))
+            .collect();
+        let issue = make_issue(Some("Body"));
+        let full_tokens = estimate_tokens(&render_context(&issue, &comments, None));
+
+        let out = render_context(&issue, &comments, Some(full_tokens - 150));
+
+        assert!(out.contains("older comments omitted"));
+        assert!(!out.contains("comment 1 "));
+        assert!(out.contains("comment 5 "));
+        assert!(estimate_tokens(&out) <= full_tokens - 150);
+    }
+
+    #[test]
+    fn test_truncates_body_when_still_over_budget() {
+        let issue = make_issue(Some(&"y".repeat(4000)));
+        let out = render_context(&issue, &[], Some(200));
+
+        assert!(out.contains("[description truncated]"));
+        assert!(estimate_tokens(&out) <= 220);
+    }
+}
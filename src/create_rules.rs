@@ -0,0 +1,127 @@
+//! Defaults and routing for new issues (`isq issue create`)
+//!
+//! Labels every issue gets, a default assignee (`@me` is whoever creates
+//! it), and keyword rules that send matching issues to an assignee, goal or
+//! extra labels. What's given on the command line wins; among rules, the
+//! first match sets the assignee and goal, and every match adds its labels.
+//!
+//! ```toml
+//! [create]
+//! labels = ["needs-triage"]
+//! assignee = "@me"
+//!
+//! [[create.rules]]
+//! keywords = ["billing", "invoice"]   # in the title or body, case-insensitive
+//! assignee = "alice"
+//! goal = "Payments"
+//! labels = ["billing"]
+//! ```
+
+use serde::Deserialize;
+
+use crate::suggest::contains_word;
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct CreateConfig {
+    /// Labels added to every created issue
+    pub labels: Vec<String>,
+    /// Assignee for issues no rule routes elsewhere
+    pub assignee: Option<String>,
+    pub rules: Vec<CreateRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateRule {
+    pub keywords: Vec<String>,
+    pub assignee: Option<String>,
+    pub goal: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+/// What the config adds to a new issue
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Routing {
+    pub labels: Vec<String>,
+    pub assignee: Option<String>,
+    pub goal: Option<String>,
+    /// Why, for each rule that matched (e.g. `mentions "invoice"`)
+    pub reasons: Vec<String>,
+}
+
+impl CreateConfig {
+    /// Route an issue by its title and body
+    pub fn route(&self, title: &str, body: Option<&str>) -> Routing {
+        let text = format!("{}\n{}", title, body.unwrap_or("")).to_lowercase();
+        let mut routing = Routing {
+            labels: self.labels.clone(),
+            ..Routing::default()
+        };
+
+        for rule in &self.rules {
+            let Some(keyword) = rule.keywords.iter().find(|k| contains_word(&text, &k.to_lowercase())) else {
+                continue;
+            };
+            routing.reasons.push(format!("mentions \"{}\"", keyword));
+            for label in &rule.labels {
+                if !routing.labels.iter().any(|l| l.eq_ignore_ascii_case(label)) {
+                    routing.labels.push(label.clone());
+                }
+            }
+            routing.assignee = routing.assignee.or_else(|| rule.assignee.clone());
+            routing.goal = routing.goal.or_else(|| rule.goal.clone());
+        }
+
+        routing.assignee = routing.assignee.or_else(|| self.assignee.clone());
+        routing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> CreateConfig {
+        crate::config::parse(
+            r#"
+            [create]
+            labels = ["needs-triage"]
+            assignee = "@me"
+
+            [[create.rules]]
+            keywords = ["invoice", "billing"]
+            assignee = "alice"
+            goal = "Payments"
+            labels = ["billing"]
+
+            [[create.rules]]
+            keywords = ["crash"]
+            assignee = "bob"
+            labels = ["bug", "Needs-Triage"]
+            "#,
+        )
+        .unwrap()
+        .create
+    }
+
+    #[test]
+    fn test_defaults_without_a_match() {
+        let routing = config().route("Dark mode", None);
+        assert_eq!(routing.labels, vec!["needs-triage"]);
+        assert_eq!(routing.assignee.as_deref(), Some("@me"));
+        assert_eq!(routing.goal, None);
+    }
+
+    #[test]
+    fn test_first_matching_rule_routes() {
+        let routing = config().route("Crash on checkout", Some("The invoice page crashes"));
+        assert_eq!(routing.labels, vec!["needs-triage", "billing", "bug"]);
+        assert_eq!(routing.assignee.as_deref(), Some("alice"));
+        assert_eq!(routing.goal.as_deref(), Some("Payments"));
+        assert_eq!(routing.reasons, vec!["mentions \"invoice\"", "mentions \"crash\""]);
+
+        // Whole words only
+        assert!(config().route("Invoices export", None).reasons.is_empty());
+    }
+}
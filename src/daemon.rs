@@ -1,38 +1,46 @@
 use anyhow::Result;
-use std::collections::HashMap;
-use std::fs::{self, File};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
+use crate::forges::{get_forge_in, CreateGoalRequest, CreateIssueRequest, Forge};
+use crate::hooks::{self, HookEvent, HooksConfig};
+use crate::metrics::SyncMetrics;
+use crate::network::{self, NetworkConfig};
+use crate::reminders::{self, ReminderRule, RemindersConfig};
+use crate::config::{self, QueueConfig};
 use crate::db;
-use crate::forges::{get_forge_for_repo, CreateIssueRequest, Forge};
+use crate::digest;
+use crate::error::{self, ErrorKind};
 use crate::repo::Repo;
+use crate::storage::{Maintenance, StorageConfig};
+use crate::workspace;
 
 // Sync all repos at this interval
 const SYNC_INTERVAL_SECS: u64 = 30;
+
+/// How often the daemon checks for requested syncs between cycles
+const SYNC_REQUEST_POLL: Duration = Duration::from_secs(1);
 const MAX_BACKOFF_SECS: u64 = 3600; // Max 1 hour backoff
 
+/// Starts this recent that never finished a cycle count as crashes
+const CRASH_WINDOW_SECS: i64 = 600;
+/// Crashes within the window before the daemon stops syncing (safe mode)
+const MAX_CRASHES: usize = 3;
+/// How often safe mode checks whether `isq daemon resume` cleared it
+const SAFE_MODE_POLL: Duration = Duration::from_secs(10);
+
 /// Get the daemon PID file path
 pub fn pid_path() -> Result<PathBuf> {
-    let dirs = directories::ProjectDirs::from("", "", "isq")
-        .ok_or_else(|| anyhow::anyhow!("Could not determine cache directory"))?;
-
-    let cache_dir = dirs.cache_dir();
-    fs::create_dir_all(cache_dir)?;
-
-    Ok(cache_dir.join("daemon.pid"))
+    Ok(db::data_dir()?.join("daemon.pid"))
 }
 
 /// Get the daemon lock file path
 fn lock_path() -> Result<PathBuf> {
-    let dirs = directories::ProjectDirs::from("", "", "isq")
-        .ok_or_else(|| anyhow::anyhow!("Could not determine cache directory"))?;
-
-    let cache_dir = dirs.cache_dir();
-    fs::create_dir_all(cache_dir)?;
-
-    Ok(cache_dir.join("daemon.lock"))
+    Ok(db::data_dir()?.join("daemon.lock"))
 }
 
 /// Acquire exclusive lock on the daemon lock file.
@@ -63,12 +71,145 @@ fn acquire_lock() -> Result<File> {
     Ok(File::create(&path)?)
 }
 
+/// Get the daemon health file path. It lives next to the cache rather than
+/// in it, since the cache may be what's broken.
+fn health_path() -> Result<PathBuf> {
+    Ok(db::data_dir()?.join("daemon-health.json"))
+}
+
+/// Daemon starts and safe mode, kept across restarts so a crash loop
+/// (the service manager restarting a daemon that dies on startup) is noticed
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Health {
+    /// Starts (unix seconds) that haven't finished a sync cycle yet
+    #[serde(default)]
+    starts: Vec<i64>,
+    /// Set while syncing is stopped, shown by `isq status`
+    pub safe_mode: Option<SafeMode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafeMode {
+    /// Unix seconds
+    pub since: i64,
+    pub reason: String,
+}
+
+impl Health {
+    /// Record a start at `now`; returns how many recent starts crashed
+    /// before finishing a cycle
+    fn record_start(&mut self, now: i64) -> usize {
+        self.starts.retain(|&start| now - start < CRASH_WINDOW_SECS);
+        let crashes = self.starts.len();
+        self.starts.push(now);
+        crashes
+    }
+
+    /// Forget the start at `start`: the daemon was asked to stop, which
+    /// isn't a crash
+    fn record_clean_exit(&mut self, start: i64) {
+        if let Some(i) = self.starts.iter().rposition(|&s| s == start) {
+            self.starts.remove(i);
+        }
+    }
+}
+
+/// Load the daemon's health (an unreadable file counts as healthy)
+pub fn load_health() -> Health {
+    health_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save_health(health: &Health) -> Result<()> {
+    std::fs::write(health_path()?, serde_json::to_string(health)?)?;
+    Ok(())
+}
+
+/// Leave safe mode and forget recent crashes (`isq daemon resume`).
+/// Returns whether the daemon was in safe mode.
+pub fn clear_safe_mode() -> Result<bool> {
+    let health = load_health();
+    if health.safe_mode.is_none() && health.starts.is_empty() {
+        return Ok(false);
+    }
+    save_health(&Health::default())?;
+    Ok(health.safe_mode.is_some())
+}
+
+/// Why the daemon can't sync safely, if it can't: the shared cache has to
+/// open, pass an integrity check and take a write lock
+fn startup_problem() -> Option<String> {
+    match db::open_shared().and_then(|conn| db::check_health(&conn)) {
+        Ok(()) => None,
+        // A command holding the write lock for a while isn't a broken cache
+        Err(e) if db::is_busy(&e) => {
+            eprintln!("[daemon] Cache busy at startup, carrying on: {}", e);
+            None
+        }
+        Err(e) => Some(format!("cache check failed: {}", e)),
+    }
+}
+
+/// Resolves when the daemon is asked to stop (SIGTERM from `isq daemon
+/// stop/restart` or the service manager, or Ctrl-C)
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = terminate.recv() => {}
+                _ = tokio::signal::ctrl_c() => {}
+            }
+            return;
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Stay up without syncing, so the service manager stops restarting the
+/// daemon, until `isq daemon resume` clears safe mode and the checks pass
+async fn wait_in_safe_mode(reason: String) -> Result<()> {
+    eprintln!("[daemon] Safe mode, not syncing: {}", reason);
+    let mut health = load_health();
+    health.safe_mode = Some(SafeMode { since: unix_now(), reason });
+    save_health(&health)?;
+
+    loop {
+        tokio::time::sleep(SAFE_MODE_POLL).await;
+        if load_health().safe_mode.is_some() {
+            continue;
+        }
+        match startup_problem() {
+            None => {
+                eprintln!("[daemon] Leaving safe mode");
+                return Ok(());
+            }
+            Some(reason) => {
+                eprintln!("[daemon] Still not safe to sync: {}", reason);
+                save_health(&Health { starts: Vec::new(), safe_mode: Some(SafeMode { since: unix_now(), reason }) })?;
+            }
+        }
+    }
+}
+
 /// Per-repo sync state for backoff tracking
 struct RepoSyncState {
     consecutive_failures: u32,
     next_attempt: Instant,
 }
 
+/// Current time as unix seconds
+pub fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
 /// Calculate backoff duration with exponential increase and jitter
 fn calculate_backoff(failures: u32) -> Duration {
     let base_secs = SYNC_INTERVAL_SECS;
@@ -84,12 +225,29 @@ fn calculate_backoff(failures: u32) -> Duration {
     Duration::from_secs_f64(jittered.max(1.0))
 }
 
+/// Move repos with queued writes to the front, keeping the rest in order.
+/// When the forge is reachable again, queued writes flush before read-only
+/// repos spend rate limit on refreshing.
+fn prioritize_pending(watched: &mut [db::WatchedRepo], has_pending: impl Fn(&str) -> bool) {
+    // Stable, so each group keeps its last_accessed order
+    watched.sort_by_cached_key(|repo| !has_pending(&repo.repo));
+}
+
+/// Whether a watched repo (by path) has writes waiting in the offline queue
+fn has_pending_ops(conn: &rusqlite::Connection, repo_path: &str) -> bool {
+    db::get_repo_link(conn, repo_path)
+        .ok()
+        .flatten()
+        .is_some_and(|link| db::count_pending_ops(conn, &link.forge_repo).unwrap_or(0) > 0)
+}
+
 /// Run the daemon sync loop (watches all repos)
 ///
 /// Syncs all watched repos every SYNC_INTERVAL_SECS.
-/// Repos are sorted by last_accessed (most recent first) so that if we can't
-/// finish all repos before the next cycle (due to rate limits or too many repos),
-/// the ones you're actively using get priority.
+/// Repos with queued writes go first; the rest are sorted by last_accessed
+/// (most recent first) so that if we can't finish all repos before the next
+/// cycle (due to rate limits or too many repos), the ones you're actively
+/// using get priority.
 pub async fn run_loop() -> Result<()> {
     // Acquire exclusive lock FIRST - prevents multiple instances
     let _lock = acquire_lock()?;
@@ -101,42 +259,162 @@ pub async fn run_loop() -> Result<()> {
     writeln!(f, "{}", std::process::id())?;
     drop(f);
 
+    // A daemon that keeps dying before finishing a cycle stops syncing
+    // instead of being restarted forever
+    let mut health = load_health();
+    let started_at = unix_now();
+    let crashes = health.record_start(started_at);
+    save_health(&health)?;
+
+    tokio::select! {
+        result = run_started(crashes) => result,
+        () = shutdown_signal() => {
+            eprintln!("[daemon] Stopping");
+            let mut health = load_health();
+            health.record_clean_exit(started_at);
+            save_health(&health)?;
+            Ok(())
+        }
+    }
+}
+
+/// The daemon after recording its start: safe mode if it isn't safe to
+/// sync, then the sync loop
+async fn run_started(crashes: usize) -> Result<()> {
+    let problem = startup_problem().or_else(|| {
+        (crashes >= MAX_CRASHES).then(|| {
+            format!("crashed {} times in the last {} minutes; see the daemon log", crashes, CRASH_WINDOW_SECS / 60)
+        })
+    });
+    if let Some(reason) = problem {
+        wait_in_safe_mode(reason).await?;
+    }
+
     eprintln!("[daemon] Starting sync loop (interval: {}s)", SYNC_INTERVAL_SECS);
 
     // Clean up stale repo entries on startup
-    if let Ok(conn) = db::open() {
-        if let Ok(removed) = db::cleanup_stale_repos(&conn) {
-            if removed > 0 {
-                eprintln!("[daemon] Cleaned up {} stale repo entries", removed);
-            }
+    if let Ok(conn) = db::open_shared()
+        && let Ok(cleanup) = db::cleanup_stale_repos(&conn)
+    {
+        if cleanup.removed > 0 {
+            eprintln!("[daemon] Cleaned up {} stale repo entries", cleanup.removed);
+        }
+        for (path, pending) in &cleanup.kept {
+            eprintln!(
+                "[daemon] Kept missing {} ({} unsynced ops; run `isq link --repair` from its new location)",
+                path, pending
+            );
         }
     }
 
+    // Metrics address is read once; changing it needs a restart
+    let metrics = SyncMetrics::default();
+    match config::load().map(|c| c.daemon.metrics_addr) {
+        Ok(Some(addr)) => {
+            let metrics = metrics.clone();
+            tokio::spawn(async move {
+                if let Err(e) = crate::metrics::serve(&addr, metrics).await {
+                    eprintln!("[daemon] Metrics endpoint on {} stopped: {}", addr, e);
+                }
+            });
+        }
+        Ok(None) => {}
+        Err(e) => eprintln!("[daemon] Metrics endpoint disabled: {}", e),
+    }
+
     // Track per-repo backoff state
     let mut repo_states: HashMap<String, RepoSyncState> = HashMap::new();
+    let mut maintenance = Maintenance::default();
+    let mut survived_cycle = false;
 
     loop {
-        let conn = db::open()?;
-        let watched = db::list_watched_repos(&conn)?;
-        // list_watched_repos already returns sorted by last_accessed DESC
+        let shared_path = db::shared_db_path()?;
+        let conn = db::open_at(&shared_path)?;
+        // This cycle covers whatever was requested while sleeping
+        db::clear_sync_requests(&conn)?;
+        let mut watched = db::list_watched_repos(&conn)?;
+
+        // Repos with their own cache (`[cache] per_repo`) are watched there
+        let mut repo_dbs: HashMap<String, PathBuf> = HashMap::new();
+        let repo_db_paths = db::list_repo_databases(&conn)?;
+        for path in &repo_db_paths {
+            match db::open_at(path).and_then(|c| db::list_watched_repos(&c)) {
+                Ok(repos) => {
+                    for repo in repos {
+                        repo_dbs.insert(repo.repo.clone(), path.clone());
+                        watched.push(repo);
+                    }
+                }
+                Err(e) => eprintln!("[daemon] Skipping {}: {}", path.display(), e),
+            }
+        }
+        prioritize_pending(&mut watched, |repo| match repo_dbs.get(repo) {
+            Some(path) => db::open_at(path).is_ok_and(|c| has_pending_ops(&c, repo)),
+            None => has_pending_ops(&conn, repo),
+        });
+
+        // Re-read every cycle so hook, reminder, queue, storage and network edits apply without a restart
+        let (hooks, reminders, queue, storage, digest_dir, network) = match config::load() {
+            Ok(config) => (
+                config.hooks,
+                config.reminders,
+                config.queue,
+                config.daemon.storage,
+                config.digest.dir(),
+                config.network,
+            ),
+            Err(e) => {
+                eprintln!("[daemon] Ignoring hooks, reminder, queue, storage, digest and network settings: {}", e);
+                (
+                    HooksConfig::default(),
+                    RemindersConfig::default(),
+                    QueueConfig::default(),
+                    StorageConfig::default(),
+                    None,
+                    NetworkConfig::default(),
+                )
+            }
+        };
 
-        if watched.is_empty() {
+        // Pause is re-read every cycle so `isq daemon pause/resume` apply without a restart
+        if let Some(pause) = db::get_daemon_pause(&conn, unix_now())? {
+            match pause.until {
+                Some(until) => eprintln!("[daemon] Paused ({}s remaining)", until - unix_now()),
+                None => eprintln!("[daemon] Paused until resumed"),
+            }
+        } else if watched.is_empty() {
             eprintln!("[daemon] No repos to watch, waiting...");
         } else {
             let now = Instant::now();
             let mut synced = 0;
             let mut skipped = 0;
+            let mut paused = 0;
 
             for repo in &watched {
+                if repo.paused {
+                    paused += 1;
+                    continue;
+                }
+
                 // Check if this repo is in backoff
-                if let Some(state) = repo_states.get(&repo.repo) {
-                    if now < state.next_attempt {
-                        skipped += 1;
-                        continue;
-                    }
+                if let Some(state) = repo_states.get(&repo.repo)
+                    && now < state.next_attempt
+                {
+                    skipped += 1;
+                    continue;
                 }
 
-                match sync_once(&repo.repo).await {
+                let started = Instant::now();
+                let db_path = repo_dbs.get(&repo.repo).unwrap_or(&shared_path);
+                // A hung forge call shouldn't wedge the whole cycle
+                let result = network::deadline(
+                    network.command_timeout(),
+                    &format!("Syncing {}", repo.repo),
+                    sync_once(db_path, &repo.repo, &hooks, &reminders, &queue),
+                )
+                .await;
+                metrics.record(&repo.repo, started.elapsed(), result.is_ok());
+                match result {
                     Ok(()) => {
                         // Success - reset backoff state
                         repo_states.remove(&repo.repo);
@@ -154,6 +432,16 @@ pub async fn run_loop() -> Result<()> {
                         let backoff = calculate_backoff(state.consecutive_failures);
                         state.next_attempt = now + backoff;
 
+                        hooks::fire(
+                            &hooks,
+                            HookEvent::SyncError,
+                            serde_json::json!({
+                                "repo": repo.repo,
+                                "error": e.to_string(),
+                                "consecutive_failures": state.consecutive_failures,
+                            }),
+                        );
+
                         eprintln!(
                             "[daemon] {} in backoff for {:.0}s (failures: {})",
                             repo.repo,
@@ -164,47 +452,104 @@ pub async fn run_loop() -> Result<()> {
                 }
             }
 
-            if synced > 0 || skipped > 0 {
+            if synced > 0 || skipped > 0 || paused > 0 {
                 eprintln!(
-                    "[daemon] Cycle complete: {} synced, {} in backoff",
-                    synced, skipped
+                    "[daemon] Cycle complete: {} synced, {} in backoff, {} paused",
+                    synced, skipped, paused
                 );
             }
         }
 
+        // Last week's digest, once it's over
+        if let Some(dir) = &digest_dir {
+            match db::open_all().and_then(|conns| digest::write_scheduled(&conns, dir, chrono::Utc::now())) {
+                Ok(Some(path)) => eprintln!("[daemon] Wrote weekly digest to {}", path.display()),
+                Ok(None) => {}
+                Err(e) => eprintln!("[daemon] Failed to write weekly digest: {}", e),
+            }
+        }
+
+        // Checkpoint the write-ahead logs and watch the cache sizes, paused or not
+        for path in std::iter::once(shared_path).chain(repo_db_paths) {
+            if let Err(e) = maintenance.run(&storage, &path) {
+                eprintln!("[daemon] Storage maintenance failed for {}: {}", path.display(), e);
+            }
+        }
+
+        // This start didn't crash
+        if !survived_cycle {
+            survived_cycle = true;
+            if let Err(e) = save_health(&Health::default()) {
+                eprintln!("[daemon] Failed to reset crash count: {}", e);
+            }
+        }
+
         // Add jitter to sleep interval to prevent synchronized requests
         let jitter = (rand::random::<f64>() - 0.5) * 0.2; // ±10%
         let sleep_secs = SYNC_INTERVAL_SECS as f64 * (1.0 + jitter);
-        tokio::time::sleep(Duration::from_secs_f64(sleep_secs)).await;
+        // Wake early when a command (e.g. `isq issue await`) asks for a sync
+        let next_cycle = Instant::now() + Duration::from_secs_f64(sleep_secs);
+        while let Some(left) = next_cycle.checked_duration_since(Instant::now()) {
+            if db::has_sync_requests(&conn)? {
+                break;
+            }
+            tokio::time::sleep(left.min(SYNC_REQUEST_POLL)).await;
+        }
     }
 }
 
-/// Sync a single repo by its local path.
+/// Sync a single repo by its local path, in the cache at `db_path`.
 ///
 /// Looks up the repo_link to determine which forge to use,
 /// then syncs issues from that forge.
-async fn sync_once(repo_path: &str) -> Result<()> {
+async fn sync_once(
+    db_path: &Path,
+    repo_path: &str,
+    hooks: &HooksConfig,
+    reminders: &RemindersConfig,
+    queue: &QueueConfig,
+) -> Result<()> {
+    let conn = db::open_at(db_path)?;
+
+    // Before anything that needs the forge, so writes stuck behind revoked
+    // credentials are given up on too
+    if let Some(link) = db::get_repo_link(&conn, repo_path)? {
+        expire_stuck_ops(&conn, &link.forge_repo, queue, hooks)?;
+    }
+
     // Look up the repo link to get forge info
-    let (forge, link) = get_forge_for_repo(repo_path)?;
+    let (forge, link) = get_forge_in(&conn, repo_path)?;
 
-    let conn = db::open()?;
+    // Links made before move detection existed: fingerprint the checkout now
+    if link.remote_url.is_none() && link.root_commit.is_none() && !db::is_remote_repo_path(repo_path) {
+        let fingerprint = crate::repo::fingerprint(repo_path);
+        if !fingerprint.is_empty() {
+            db::set_repo_fingerprint(&conn, repo_path, &fingerprint)?;
+        }
+    }
 
     // Check if we're rate limited for this forge
-    if db::is_rate_limited(&conn, &link.forge_type)? {
-        if let Some(state) = db::get_rate_limit_state(&conn, &link.forge_type)? {
-            if let Some(reset_at) = state.reset_at {
-                let now = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs() as i64;
-                let wait_secs = reset_at - now;
-                eprintln!(
-                    "[daemon] {} rate limited, skipping {} (resets in {}s)",
-                    link.forge_type, link.forge_repo, wait_secs
-                );
-                return Ok(());
-            }
-        }
+    if db::is_rate_limited(&conn, &link.forge_type)?
+        && let Some(state) = db::get_rate_limit_state(&conn, &link.forge_type)?
+        && let Some(reset_at) = state.reset_at
+    {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        let wait_secs = reset_at - now;
+        eprintln!(
+            "[daemon] {} rate limited, skipping {} (resets in {}s)",
+            link.forge_type, link.forge_repo, wait_secs
+        );
+        return Ok(());
+    }
+
+    // Search workspaces re-run their query instead of syncing a repo
+    if workspace::is_workspace(&link.forge_repo) {
+        let found = workspace::sync(&conn, forge.as_ref(), &link.forge_repo).await?;
+        eprintln!("[daemon] Synced {} issues for {}", found, link.forge_repo);
+        return Ok(());
     }
 
     // Parse the forge_repo (e.g., "owner/repo" for GitHub)
@@ -218,7 +563,8 @@ async fn sync_once(repo_path: &str) -> Result<()> {
         name: parts[1].to_string(),
     };
 
-    // First, process any pending operations
+    // First, process any pending operations, so the read below sees them.
+    // Any still queued afterwards are re-applied over it by save_issues.
     // Note: pending_ops are keyed by forge_repo for consistency
     let pending_ops = db::load_pending_ops(&conn, &link.forge_repo)?;
     if !pending_ops.is_empty() {
@@ -230,12 +576,13 @@ async fn sync_once(repo_path: &str) -> Result<()> {
     }
 
     // Then sync issues from remote
+    let started = db::fetch_started();
     let issues = match forge.list_issues(&repo).await {
         Ok(issues) => issues,
         Err(e) => {
             // Check if this is a rate limit error
             let err_str = e.to_string();
-            if err_str.contains("rate limit") || err_str.contains("403") {
+            if error::classify(&e) == ErrorKind::RateLimited {
                 // Try to get rate limit info from the forge
                 if let Ok(Some(rate_info)) = forge.get_rate_limit().await {
                     db::set_rate_limit_state(
@@ -263,14 +610,36 @@ async fn sync_once(repo_path: &str) -> Result<()> {
             return Err(e);
         }
     };
-    db::save_issues(&conn, &link.forge_repo, &issues)?;
+    let before = db::load_issues(&conn, &link.forge_repo)?;
+    db::save_issues(&conn, &link.forge_repo, &issues, &started)?;
+
+    let me = db::get_identity(&conn, &link.identity_key())?;
+    for (event, issue) in hooks::sync_events(&before, &issues, me.as_deref()) {
+        hooks::fire(hooks, event, serde_json::json!({ "repo": link.forge_repo, "issue": issue }));
+    }
+
+    // Comments are the most expensive query; when the complexity budget is
+    // low, keep the cached ones and catch up after the window resets
+    if let Some(budget) = forge.complexity_budget() {
+        db::update_complexity_budget(&conn, &link.forge_type, &budget)?;
+        if budget.is_low(unix_now()) {
+            eprintln!(
+                "[daemon] Synced {} issues for {}; deferring comments ({} of {} complexity left)",
+                issues.len(),
+                link.forge_repo,
+                budget.remaining,
+                budget.limit
+            );
+            return Ok(());
+        }
+    }
 
     // Sync comments
     let comments = match forge.list_all_comments(&repo).await {
         Ok(comments) => comments,
         Err(e) => {
             let err_str = e.to_string();
-            if err_str.contains("rate limit") || err_str.contains("403") {
+            if error::classify(&e) == ErrorKind::RateLimited {
                 if let Ok(Some(rate_info)) = forge.get_rate_limit().await {
                     db::set_rate_limit_state(
                         &conn,
@@ -292,6 +661,31 @@ async fn sync_once(repo_path: &str) -> Result<()> {
     };
     db::save_comments(&conn, &link.forge_repo, &comments)?;
 
+    // Goals too, so `goal list --state closed` doesn't wait for a manual sync
+    let goals = forge.list_goals(&repo).await?;
+    db::save_goals(&conn, &link.forge_repo, &goals)?;
+    remind_at_risk_goals(&conn, &link.forge_repo, &goals, reminders.rule(&link.forge_repo), hooks)?;
+
+    // Objectives are optional (they may not be enabled for the workspace), so don't fail the sync
+    match forge.list_objectives(&repo).await {
+        Ok(Some(objectives)) => db::save_objectives(&conn, &link.forge_repo, &objectives)?,
+        Ok(None) => {}
+        Err(e) => eprintln!("[daemon] Skipping objectives for {}: {}", link.forge_repo, e),
+    }
+    match forge.list_cycles(&repo).await {
+        Ok(Some(cycles)) => db::save_cycles(&conn, &link.forge_repo, &cycles)?,
+        Ok(None) => {}
+        Err(e) => eprintln!("[daemon] Skipping cycles for {}: {}", link.forge_repo, e),
+    }
+    match forge.list_labels(&repo).await {
+        Ok(labels) => db::save_labels(&conn, &link.forge_repo, &labels)?,
+        Err(e) => eprintln!("[daemon] Skipping labels for {}: {}", link.forge_repo, e),
+    }
+
+    if let Some(budget) = forge.complexity_budget() {
+        db::update_complexity_budget(&conn, &link.forge_type, &budget)?;
+    }
+
     // Sync was successful - fetch and save rate limit info
     if let Ok(Some(rate_info)) = forge.get_rate_limit().await {
         db::update_rate_limit_budget(
@@ -304,72 +698,228 @@ async fn sync_once(repo_path: &str) -> Result<()> {
     }
 
     eprintln!(
-        "[daemon] Synced {} issues and {} comments for {}",
+        "[daemon] Synced {} issues, {} comments and {} goals for {}",
         issues.len(),
         comments.len(),
+        goals.len(),
         link.forge_repo
     );
 
     Ok(())
 }
 
-/// Process pending operations and return count of successful syncs
-async fn process_pending_ops(
+/// Move writes queued longer than `[queue] max_age_days` to the failed list,
+/// leaving an inbox event and firing `on_op_failed` for each
+fn expire_stuck_ops(conn: &rusqlite::Connection, forge_repo: &str, queue: &QueueConfig, hooks: &HooksConfig) -> Result<()> {
+    if queue.max_age_days == 0 {
+        return Ok(());
+    }
+    for op in db::expire_pending_ops(conn, forge_repo, queue.max_age_days)? {
+        let mut message = format!("Gave up on queued write after {} days: {}", queue.max_age_days, op.summary());
+        if let Some(error) = &op.last_error {
+            message.push_str(&format!(" (last error: {})", error));
+        }
+        eprintln!("[daemon] {} ({})", message, forge_repo);
+        db::add_inbox_event(conn, forge_repo, "op_failed", &message)?;
+        hooks::fire(
+            hooks,
+            HookEvent::OpFailed,
+            serde_json::json!({ "repo": forge_repo, "op": op.to_json(), "message": message }),
+        );
+    }
+    Ok(())
+}
+
+/// Leave an inbox event (and fire the `on_goal_at_risk` hook) for each goal
+/// that just became at risk
+fn remind_at_risk_goals(
+    conn: &rusqlite::Connection,
+    forge_repo: &str,
+    goals: &[crate::forges::Goal],
+    rule: ReminderRule,
+    hooks: &HooksConfig,
+) -> Result<()> {
+    let today = chrono::Local::now().date_naive();
+    for goal in goals.iter().filter(|goal| reminders::is_at_risk(goal, today, rule)) {
+        let target = goal.target_date.as_deref().unwrap_or_default();
+        if !db::claim_goal_reminder(conn, forge_repo, &goal.id, target)? {
+            continue;
+        }
+        let message = reminders::message(goal, today);
+        eprintln!("[daemon] {} ({})", message, forge_repo);
+        db::add_inbox_event(conn, forge_repo, "goal_at_risk", &message)?;
+        hooks::fire(
+            hooks,
+            HookEvent::GoalAtRisk,
+            serde_json::json!({ "repo": forge_repo, "goal": goal, "message": message }),
+        );
+    }
+    Ok(())
+}
+
+/// What replaying one queued write did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Replay {
+    /// Applied on the forge
+    Applied,
+    /// Applied already (e.g. the queue came back from a backup)
+    AlreadyApplied,
+    /// The forge rejected it (404, 409 or 422): server wins, so it was dropped
+    Discarded,
+    /// Transient error; left queued for the next replay
+    Failed,
+    /// Not tried because an earlier write to the same issue is still queued
+    Held,
+    /// Another isq process (the daemon or `isq sync`) is replaying it
+    Busy,
+}
+
+impl Replay {
+    /// Whether the write left the queue, delivered or not
+    pub fn dequeued(self) -> bool {
+        matches!(self, Replay::Applied | Replay::AlreadyApplied | Replay::Discarded)
+    }
+
+    /// Whether the write reached the forge
+    pub fn delivered(self) -> bool {
+        matches!(self, Replay::Applied | Replay::AlreadyApplied)
+    }
+}
+
+/// How one queued write fared
+pub struct ReplayResult<'a> {
+    pub op: &'a db::PendingOp,
+    pub outcome: Replay,
+    pub error: Option<String>,
+}
+
+impl ReplayResult<'_> {
+    /// JSON shape used in `isq sync --json`
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut value = self.op.to_json();
+        value["summary"] = self.op.summary().into();
+        value["result"] = serde_json::to_value(self.outcome).unwrap_or_default();
+        value["error"] = self.error.clone().into();
+        value
+    }
+}
+
+/// Replay queued writes in order, removing the ones that land (or that the
+/// forge rejects) from the queue
+pub async fn replay_pending_ops<'a>(
     forge: &dyn Forge,
     repo: &Repo,
     conn: &rusqlite::Connection,
-    ops: &[db::PendingOp],
-) -> usize {
-    let mut synced = 0;
+    ops: &'a [db::PendingOp],
+) -> Vec<ReplayResult<'a>> {
+    let mut results = Vec::with_capacity(ops.len());
+    // Issues (None: creates and other issue-less ops) with an op still
+    // queued; later ops for them wait so each issue's writes land in order
+    let mut held: HashSet<Option<u64>> = HashSet::new();
 
     for op in ops {
-        let result = execute_pending_op(forge, repo, op).await;
+        let target = op.issue_number();
+        if held.contains(&target) {
+            results.push(ReplayResult { op, outcome: Replay::Held, error: None });
+            continue;
+        }
 
-        match result {
+        // Claimed before anything is sent, so a second replayer skips it.
+        // A cache we can't read is never taken to mean "not sent yet".
+        let claimed = db::claim_op(conn, op.id)
+            .and_then(|claim| Ok((claim, db::is_replayed(conn, &op.dedup_key)?)));
+        let retry = match claimed {
+            Ok((db::Claim::Busy, _)) => {
+                held.insert(target);
+                results.push(ReplayResult { op, outcome: Replay::Busy, error: None });
+                continue;
+            }
+            // Applied already (e.g. the queue came back from a backup)
+            Ok((db::Claim::Claimed { .. }, true)) => {
+                if let Err(e) = db::complete_op(conn, op.id) {
+                    eprintln!("Failed to drop replayed op {}: {}", op.id, e);
+                }
+                results.push(ReplayResult { op, outcome: Replay::AlreadyApplied, error: None });
+                continue;
+            }
+            Ok((db::Claim::Claimed { retry }, false)) => retry,
+            Err(e) => {
+                held.insert(target);
+                results.push(ReplayResult { op, outcome: Replay::Failed, error: Some(e.to_string()) });
+                continue;
+            }
+        };
+
+        let outcome = match execute_pending_op(forge, repo, op, retry).await {
             Ok(()) => {
                 // Operation succeeded, remove from queue
                 if let Err(e) = db::complete_op(conn, op.id) {
-                    eprintln!("[daemon] Failed to mark op {} complete: {}", op.id, e);
+                    eprintln!("Failed to mark op {} complete: {}", op.id, e);
                 }
-                synced += 1;
+                ReplayResult { op, outcome: Replay::Applied, error: None }
             }
             Err(e) => {
                 // Check if this is a conflict (server state changed)
                 let err_str = e.to_string();
-                if err_str.contains("404") || err_str.contains("422") || err_str.contains("409") {
+                if matches!(error::classify(&e), ErrorKind::NotFound | ErrorKind::Conflict) {
                     // Conflict or resource not found - server wins, discard operation
-                    eprintln!(
-                        "[daemon] Conflict for {} op on {}: {} (discarding)",
-                        op.op_type, repo.full_name(), e
-                    );
                     if let Err(e) = db::complete_op(conn, op.id) {
-                        eprintln!("[daemon] Failed to discard op {}: {}", op.id, e);
+                        eprintln!("Failed to discard op {}: {}", op.id, e);
                     }
-                    synced += 1; // Count as processed
+                    ReplayResult { op, outcome: Replay::Discarded, error: Some(err_str) }
                 } else {
                     // Network or other transient error - leave in queue for retry
-                    eprintln!(
-                        "[daemon] Failed {} op, will retry: {}",
-                        op.op_type, e
-                    );
+                    if let Err(e) = db::set_op_error(conn, op.id, &err_str) {
+                        eprintln!("Failed to record error for op {}: {}", op.id, e);
+                    }
+                    held.insert(target);
+                    ReplayResult { op, outcome: Replay::Failed, error: Some(err_str) }
                 }
             }
-        }
+        };
+        results.push(outcome);
     }
 
-    synced
+    results
+}
+
+/// Process pending operations and return count of successful syncs
+async fn process_pending_ops(
+    forge: &dyn Forge,
+    repo: &Repo,
+    conn: &rusqlite::Connection,
+    ops: &[db::PendingOp],
+) -> usize {
+    let results = replay_pending_ops(forge, repo, conn, ops).await;
+    for result in &results {
+        let error = result.error.as_deref().unwrap_or_default();
+        match result.outcome {
+            Replay::Discarded => eprintln!(
+                "[daemon] Conflict for {} op on {}: {} (discarding)",
+                result.op.op_type, repo.full_name(), error
+            ),
+            Replay::Failed => eprintln!("[daemon] Failed {} op, will retry: {}", result.op.op_type, error),
+            _ => {}
+        }
+    }
+    results.iter().filter(|result| result.outcome.dequeued()).count()
 }
 
-/// Execute a single pending operation
+/// Execute a single pending operation. `retry` when an earlier attempt may
+/// have landed, so creates and comments look for it before sending again.
 async fn execute_pending_op(
     forge: &dyn Forge,
     repo: &Repo,
     op: &db::PendingOp,
+    retry: bool,
 ) -> Result<()> {
     let payload: serde_json::Value = serde_json::from_str(&op.payload)?;
 
     match op.op_type.as_str() {
         "create" => {
+            // Creates queued before client keys existed use their dedup key
+            let client_key = payload["client_key"].as_str().unwrap_or(&op.dedup_key);
             let req = CreateIssueRequest {
                 title: payload["title"].as_str().unwrap_or("").to_string(),
                 body: payload["body"].as_str().map(|s| s.to_string()),
@@ -382,14 +932,35 @@ async fn execute_pending_op(
                     })
                     .unwrap_or_default(),
                 goal_id: payload["goal_id"].as_str().map(|s| s.to_string()),
+                client_key: Some(client_key.to_string()),
+                issue_type: payload["type"].as_str().map(|s| s.to_string()),
+                assignee: payload["assignee"].as_str().map(|s| s.to_string()),
             };
+
+            // An earlier attempt (a replay, or the command that queued it) may
+            // have landed with its response lost
+            if (retry || payload["client_key"].is_string())
+                && let Some(issue) = forge.find_created_issue(repo, client_key).await?
+            {
+                eprintln!("[daemon] #{} {} was already created", issue.number, issue.title);
+                return Ok(());
+            }
             let issue = forge.create_issue(repo, req).await?;
             eprintln!("[daemon] Created #{} {}", issue.number, issue.title);
         }
         "comment" => {
             let issue_number = payload["issue_number"].as_u64().unwrap_or(0);
             let body = payload["body"].as_str().unwrap_or("");
-            forge.create_comment(repo, issue_number, body).await?;
+            let client_key = payload["client_key"].as_str().unwrap_or(&op.dedup_key);
+            // A keyed comment's first attempt (the command that queued it) may
+            // have landed with its response lost
+            if (retry || payload["client_key"].is_string())
+                && forge.find_posted_comment(repo, issue_number, client_key).await?
+            {
+                eprintln!("[daemon] Comment on #{} was already posted", issue_number);
+                return Ok(());
+            }
+            forge.create_keyed_comment(repo, issue_number, body, client_key).await?;
             eprintln!("[daemon] Added comment to #{}", issue_number);
         }
         "close" => {
@@ -402,6 +973,12 @@ async fn execute_pending_op(
             forge.reopen_issue(repo, issue_number).await?;
             eprintln!("[daemon] Reopened #{}", issue_number);
         }
+        "edit_body" => {
+            let issue_number = payload["issue_number"].as_u64().unwrap_or(0);
+            let body = payload["body"].as_str().unwrap_or("");
+            forge.update_issue_body(repo, issue_number, body).await?;
+            eprintln!("[daemon] Updated the body of #{}", issue_number);
+        }
         "label_add" => {
             let issue_number = payload["issue_number"].as_u64().unwrap_or(0);
             let label = payload["label"].as_str().unwrap_or("");
@@ -420,6 +997,44 @@ async fn execute_pending_op(
             forge.assign_issue(repo, issue_number, assignee).await?;
             eprintln!("[daemon] Assigned @{} to #{}", assignee, issue_number);
         }
+        "subscribe" | "unsubscribe" => {
+            let issue_number = payload["issue_number"].as_u64().unwrap_or(0);
+            let subscribed = op.op_type == "subscribe";
+            forge.set_subscribed(repo, issue_number, subscribed).await?;
+            eprintln!("[daemon] {} #{}", if subscribed { "Subscribed to" } else { "Unsubscribed from" }, issue_number);
+        }
+        "assign_goal" => {
+            let issue_number = payload["issue_number"].as_u64().unwrap_or(0);
+            let goal_id = payload["goal_id"].as_str().unwrap_or("");
+            forge.assign_to_goal(repo, issue_number, goal_id).await?;
+            eprintln!("[daemon] Added #{} to goal {}", issue_number, goal_id);
+        }
+        "close_goal" => {
+            let goal_id = payload["goal_id"].as_str().unwrap_or("");
+            forge.close_goal(repo, goal_id).await?;
+            eprintln!("[daemon] Closed goal {}", goal_id);
+        }
+        "reopen_goal" => {
+            let goal_id = payload["goal_id"].as_str().unwrap_or("");
+            forge.reopen_goal(repo, goal_id).await?;
+            eprintln!("[daemon] Reopened goal {}", goal_id);
+        }
+        "create_objective" => {
+            let req = CreateGoalRequest {
+                name: payload["name"].as_str().unwrap_or("").to_string(),
+                description: payload["description"].as_str().map(|s| s.to_string()),
+                target_date: payload["target_date"].as_str().map(|s| s.to_string()),
+            };
+            let name = req.name.clone();
+            forge.create_objective(repo, req).await?;
+            eprintln!("[daemon] Created objective {}", name);
+        }
+        "link_objective" => {
+            let objective_id = payload["objective_id"].as_str().unwrap_or("");
+            let goal_id = payload["goal_id"].as_str().unwrap_or("");
+            forge.link_goal_to_objective(repo, objective_id, goal_id).await?;
+            eprintln!("[daemon] Linked goal {} to objective {}", goal_id, objective_id);
+        }
         _ => {
             anyhow::bail!("Unknown op type: {}", op.op_type);
         }
@@ -432,6 +1047,37 @@ async fn execute_pending_op(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_prioritize_pending() {
+        let mut watched: Vec<db::WatchedRepo> = ["/a", "/b", "/c", "/d"]
+            .iter()
+            .map(|repo| db::WatchedRepo { repo: repo.to_string(), paused: false })
+            .collect();
+        prioritize_pending(&mut watched, |repo| repo == "/c" || repo == "/d");
+        let order: Vec<_> = watched.iter().map(|w| w.repo.as_str()).collect();
+        assert_eq!(order, vec!["/c", "/d", "/a", "/b"]);
+    }
+
+    #[test]
+    fn test_record_start_counts_recent_crashes() {
+        let mut health = Health::default();
+        assert_eq!(health.record_start(1_000), 0);
+        assert_eq!(health.record_start(1_010), 1);
+        assert_eq!(health.record_start(1_020), 2);
+        // Starts outside the window are forgotten
+        assert_eq!(health.record_start(1_020 + CRASH_WINDOW_SECS), 0);
+    }
+
+    #[test]
+    fn test_clean_exits_are_not_crashes() {
+        let mut health = Health::default();
+        for start in [1_000, 1_010, 1_020] {
+            health.record_start(start);
+            health.record_clean_exit(start);
+        }
+        assert_eq!(health.record_start(1_030), 0);
+    }
+
     #[test]
     fn test_calculate_backoff_base_case() {
         // 0 failures = base interval (30s) with jitter
@@ -479,7 +1125,7 @@ mod tests {
         let secs = backoff.as_secs_f64();
 
         // Should be capped at 1920s with ±25% jitter = 1440 to 2400
-        assert!(secs >= 1440.0 && secs <= 2400.0,
+        assert!((1440.0..=2400.0).contains(&secs),
             "extreme failure backoff {} should be capped", secs);
     }
 
@@ -1,8 +1,15 @@
 use anyhow::Result;
-use rusqlite::{params, Connection};
-use std::path::PathBuf;
-
-use crate::forges::{Goal, GoalState, Issue, Label};
+use once_cell::sync::OnceCell;
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension, Transaction, TransactionBehavior, MAIN_DB};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::cipher::{self, Cipher};
+use crate::config::{self, CacheConfig};
+use crate::forges::{ComplexityBudget, Cycle, Goal, GoalState, Issue, Label, LabelDef, Objective, WhoAmI};
+use crate::references;
+use crate::repo::{self, RepoFingerprint};
 
 /// Parse labels JSON with backward compatibility.
 /// Handles both new format ([{"name": "bug", "color": "fc2929"}]) and old format (["bug"]).
@@ -18,31 +25,230 @@ fn parse_labels_json(json: &str) -> Vec<Label> {
     Vec::new()
 }
 
-/// Get the cache database path
-pub fn db_path() -> Result<PathBuf> {
-    let dirs = directories::ProjectDirs::from("", "", "isq")
-        .ok_or_else(|| anyhow::anyhow!("Could not determine cache directory"))?;
+/// Environment variable that moves the shared cache (overrides `[cache] dir`)
+pub const DATA_DIR_ENV: &str = "ISQ_DATA_DIR";
+
+/// `[cache]` settings, read once per process rather than on every open
+fn cache_config() -> Result<&'static CacheConfig> {
+    static CACHE_CONFIG: OnceCell<CacheConfig> = OnceCell::new();
+    CACHE_CONFIG.get_or_try_init(|| Ok(config::load()?.cache))
+}
+
+/// Directory holding the shared cache and the daemon's files: `ISQ_DATA_DIR`,
+/// `[cache] dir`, or the OS cache directory
+pub fn data_dir() -> Result<PathBuf> {
+    let dir = match std::env::var_os(DATA_DIR_ENV).filter(|v| !v.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => match cache_config()?.dir() {
+            Some(dir) => dir,
+            None => directories::ProjectDirs::from("", "", "isq")
+                .ok_or_else(|| anyhow::anyhow!("Could not determine cache directory"))?
+                .cache_dir()
+                .to_path_buf(),
+        },
+    };
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// The cache shared by every repo that doesn't have its own
+pub fn shared_db_path() -> Result<PathBuf> {
+    Ok(data_dir()?.join("cache.db"))
+}
 
-    let cache_dir = dirs.cache_dir();
-    std::fs::create_dir_all(cache_dir)?;
+/// A repo's own cache with `[cache] per_repo = true`, or None when the
+/// checkout has no `.git` directory to keep it in (e.g. submodules)
+pub fn repo_db_path(repo_path: &str) -> Option<PathBuf> {
+    let git_dir = Path::new(repo_path).join(".git");
+    git_dir.is_dir().then(|| git_dir.join("isq.db"))
+}
 
-    Ok(cache_dir.join("cache.db"))
+/// Get the cache database path: the current repo's own with
+/// `[cache] per_repo = true`, the shared one otherwise
+pub fn db_path() -> Result<PathBuf> {
+    if cache_config()?.per_repo
+        && let Some(path) = repo::detect_repo_path().ok().and_then(|repo| repo_db_path(&repo))
+    {
+        return Ok(path);
+    }
+    shared_db_path()
 }
 
 /// Open database connection with WAL mode
 pub fn open() -> Result<Connection> {
     let path = db_path()?;
-    let conn = Connection::open(&path)?;
+    let shared = shared_db_path()?;
+    let is_new_repo_db = path != shared && !path.exists();
+    let conn = open_at(&path)?;
+
+    // Let the daemon find the new per-repo cache
+    if is_new_repo_db {
+        add_repo_database(&open_at(&shared)?, &path)?;
+    }
+    Ok(conn)
+}
+
+/// Open the shared cache, wherever the current directory is. Daemon state
+/// (pause, sync requests) lives here.
+pub fn open_shared() -> Result<Connection> {
+    open_at(&shared_db_path()?)
+}
+
+/// Every cache: the shared one first, then each per-repo cache recorded in it.
+/// Commands covering all linked repos (warm, wip, digest) walk these; a
+/// per-repo cache that can't be opened is skipped with a warning.
+pub fn open_all() -> Result<Vec<Connection>> {
+    let shared = open_shared()?;
+    let paths = list_repo_databases(&shared)?;
+    let mut conns = vec![shared];
+    for path in paths {
+        match open_at(&path) {
+            Ok(conn) => conns.push(conn),
+            Err(e) => eprintln!("warning: skipping {}: {}", path.display(), e),
+        }
+    }
+    Ok(conns)
+}
+
+/// Open the cache at `path`
+pub fn open_at(path: &Path) -> Result<Connection> {
+    let conn = Connection::open(path)?;
 
     // Enable WAL mode for concurrent read/write
     conn.pragma_update(None, "journal_mode", "WAL")?;
+    // The CLI and daemon both write; wait for the other instead of failing with SQLITE_BUSY
+    conn.busy_timeout(std::time::Duration::from_secs(5))?;
 
     // Initialize schema
     init_schema(&conn)?;
 
+    // Bodies are encrypted once config asks for it or `isq cache encrypt` ran;
+    // a broken config doesn't turn encryption off for an encrypted cache
+    let configured = cache_config().map(|c| c.encrypt).unwrap_or(false);
+    cipher::register(&conn, configured || is_cache_encrypted(&conn)?, cipher::cache_cipher)?;
+
+    Ok(conn)
+}
+
+/// A throwaway in-memory cache (e.g. for `issue list --remote`)
+pub fn open_scratch() -> Result<Connection> {
+    let conn = Connection::open_in_memory()?;
+    init_schema(&conn)?;
+    cipher::register(&conn, false, cipher::cache_cipher)?;
     Ok(conn)
 }
 
+/// Record a per-repo cache in the shared one so the daemon syncs it
+pub fn add_repo_database(conn: &Connection, path: &Path) -> Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO repo_databases (path) VALUES (?)",
+        params![path.to_string_lossy()],
+    )?;
+    Ok(())
+}
+
+/// Per-repo caches that still exist; ones deleted along with their checkout
+/// are dropped
+pub fn list_repo_databases(conn: &Connection) -> Result<Vec<PathBuf>> {
+    let paths = conn
+        .prepare("SELECT path FROM repo_databases ORDER BY path")?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut existing = Vec::new();
+    for path in paths {
+        if Path::new(&path).exists() {
+            existing.push(PathBuf::from(path));
+        } else {
+            conn.execute("DELETE FROM repo_databases WHERE path = ?", params![path])?;
+        }
+    }
+    Ok(existing)
+}
+
+/// Counter that changes whenever another connection commits (e.g. the daemon
+/// finishing a sync). Cheap enough to poll for `--watch`.
+pub fn data_version(conn: &Connection) -> Result<i64> {
+    Ok(conn.query_row("PRAGMA data_version", [], |row| row.get(0))?)
+}
+
+/// Check the cache is intact (SQLite's quick check) and can take a write
+/// lock, i.e. no other process is stuck holding one (daemon startup)
+pub fn check_health(conn: &Connection) -> Result<()> {
+    let integrity: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+    if integrity != "ok" {
+        anyhow::bail!("database is corrupt: {}", integrity);
+    }
+    if let Err(e) = conn.execute_batch("BEGIN IMMEDIATE; ROLLBACK;") {
+        let message = format!("database can't be written: {}", e);
+        return Err(anyhow::Error::new(e).context(message));
+    }
+    Ok(())
+}
+
+/// Whether an error is SQLite finding the database busy or locked, i.e.
+/// another process held the write lock past the busy timeout. That passes on
+/// its own.
+pub fn is_busy(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<rusqlite::Error>(),
+            Some(rusqlite::Error::SqliteFailure(e, _))
+                if matches!(e.code, rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked)
+        )
+    })
+}
+
+// === Backup ===
+
+/// What a backup file contains (shown before restoring)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackupSummary {
+    pub repo_links: i64,
+    pub watched_repos: i64,
+    pub issues: i64,
+    pub pending_ops: i64,
+}
+
+/// Write a consistent snapshot of the database to `dest`.
+/// Credentials live in the OS keyring, so the snapshot holds no secrets.
+pub fn backup_to(conn: &Connection, dest: &Path) -> Result<()> {
+    conn.backup(MAIN_DB, dest, None)?;
+    Ok(())
+}
+
+/// Check that `path` is an intact isq database and summarize its contents
+pub fn inspect_backup(path: &Path) -> Result<BackupSummary> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| anyhow::anyhow!("Cannot open {}: {}", path.display(), e))?;
+
+    let integrity: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|e| anyhow::anyhow!("{} is not a valid isq backup: {}", path.display(), e))?;
+    if integrity != "ok" {
+        anyhow::bail!("{} is corrupt: {}", path.display(), integrity);
+    }
+
+    let count = |table: &str| -> Result<i64> {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+            .map_err(|_| anyhow::anyhow!("{} is not an isq backup (missing {} table)", path.display(), table))
+    };
+
+    Ok(BackupSummary {
+        repo_links: count("repo_links")?,
+        watched_repos: count("watched_repos")?,
+        issues: count("issues")?,
+        pending_ops: count("pending_ops")?,
+    })
+}
+
+/// Replace the database contents with a backup, then bring its schema up to date
+pub fn restore_from(conn: &mut Connection, src: &Path) -> Result<()> {
+    conn.restore(MAIN_DB, src, None::<fn(rusqlite::backup::Progress)>)?;
+    init_schema(conn)?;
+    Ok(())
+}
+
 pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
     conn.execute_batch(
         "
@@ -80,6 +286,22 @@ pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
 
         CREATE INDEX IF NOT EXISTS idx_pending_ops_repo ON pending_ops(repo);
 
+        CREATE TABLE IF NOT EXISTS replayed_ops (
+            dedup_key TEXT PRIMARY KEY,
+            repo TEXT NOT NULL,
+            replayed_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS failed_ops (
+            id INTEGER PRIMARY KEY,
+            repo TEXT NOT NULL,
+            op_type TEXT NOT NULL,
+            payload TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            last_error TEXT,
+            failed_at TEXT NOT NULL
+        );
+
         CREATE TABLE IF NOT EXISTS watched_repos (
             repo TEXT PRIMARY KEY,
             last_accessed TEXT NOT NULL,
@@ -126,6 +348,98 @@ pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
         CREATE INDEX IF NOT EXISTS idx_goals_repo ON goals(forge_repo);
         CREATE INDEX IF NOT EXISTS idx_goals_state ON goals(forge_repo, state);
 
+        CREATE TABLE IF NOT EXISTS goal_snapshots (
+            forge_repo TEXT NOT NULL,
+            goal_id TEXT NOT NULL,
+            day TEXT NOT NULL,
+            name TEXT NOT NULL,
+            state TEXT NOT NULL,
+            progress REAL NOT NULL,
+            open_count INTEGER,
+            closed_count INTEGER,
+            PRIMARY KEY (forge_repo, goal_id, day)
+        );
+
+        CREATE TABLE IF NOT EXISTS objectives (
+            id INTEGER PRIMARY KEY,
+            forge_repo TEXT NOT NULL,
+            objective_id TEXT NOT NULL,
+            name TEXT NOT NULL,
+            description TEXT,
+            target_date TEXT,
+            state TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            html_url TEXT,
+            UNIQUE(forge_repo, objective_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS objective_goals (
+            forge_repo TEXT NOT NULL,
+            objective_id TEXT NOT NULL,
+            goal_id TEXT NOT NULL,
+            PRIMARY KEY (forge_repo, objective_id, goal_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS daemon_pause (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            paused_until INTEGER,
+            paused_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS repo_databases (
+            path TEXT PRIMARY KEY
+        );
+
+        CREATE TABLE IF NOT EXISTS cache_encryption (
+            id INTEGER PRIMARY KEY CHECK (id = 1),
+            encrypted_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS sync_requests (
+            repo_path TEXT PRIMARY KEY,
+            requested_at INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS identities (
+            forge_type TEXT PRIMARY KEY,
+            login TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS branch_issues (
+            forge_repo TEXT NOT NULL,
+            branch TEXT NOT NULL,
+            issue_number INTEGER NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (forge_repo, branch)
+        );
+
+        CREATE TABLE IF NOT EXISTS rankings (
+            forge_repo TEXT NOT NULL,
+            issue_number INTEGER NOT NULL,
+            position INTEGER NOT NULL,
+            PRIMARY KEY (forge_repo, issue_number)
+        );
+
+        CREATE TABLE IF NOT EXISTS inbox_events (
+            id INTEGER PRIMARY KEY,
+            forge_repo TEXT NOT NULL,
+            kind TEXT NOT NULL,
+            message TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            read_at TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_inbox_events_repo ON inbox_events(forge_repo, read_at);
+
+        CREATE TABLE IF NOT EXISTS goal_reminders (
+            forge_repo TEXT NOT NULL,
+            goal_id TEXT NOT NULL,
+            target_date TEXT NOT NULL,
+            reminded_at TEXT NOT NULL,
+            PRIMARY KEY (forge_repo, goal_id)
+        );
+
         CREATE TABLE IF NOT EXISTS rate_limit_state (
             forge TEXT PRIMARY KEY,
             rate_limit INTEGER,
@@ -134,6 +448,79 @@ pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
             last_error TEXT,
             updated_at TEXT NOT NULL
         );
+
+        CREATE TABLE IF NOT EXISTS drafts (
+            id INTEGER PRIMARY KEY,
+            forge_repo TEXT NOT NULL,
+            title TEXT NOT NULL,
+            body TEXT,
+            labels TEXT NOT NULL DEFAULT '[]',
+            goal TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_drafts_repo ON drafts(forge_repo);
+
+        CREATE TABLE IF NOT EXISTS issue_references (
+            forge_repo TEXT NOT NULL,
+            source_number INTEGER NOT NULL,
+            source TEXT NOT NULL,
+            target_number INTEGER NOT NULL,
+            PRIMARY KEY (forge_repo, source_number, source, target_number)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_issue_references_target ON issue_references(forge_repo, target_number);
+
+        CREATE TABLE IF NOT EXISTS issue_subscriptions (
+            forge_repo TEXT NOT NULL,
+            number INTEGER NOT NULL,
+            subscribed INTEGER NOT NULL,
+            updated_at TEXT NOT NULL,
+            PRIMARY KEY (forge_repo, number)
+        );
+
+        CREATE TABLE IF NOT EXISTS sync_cursors (
+            repo TEXT PRIMARY KEY,
+            cursor TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS whoami (
+            identity_key TEXT PRIMARY KEY,
+            info TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS issue_tombstones (
+            repo TEXT NOT NULL,
+            number INTEGER NOT NULL,
+            deleted_at TEXT NOT NULL,
+            PRIMARY KEY (repo, number)
+        );
+
+        CREATE TABLE IF NOT EXISTS repo_labels (
+            forge_repo TEXT NOT NULL,
+            name TEXT NOT NULL COLLATE NOCASE,
+            color TEXT,
+            description TEXT,
+            PRIMARY KEY (forge_repo, name)
+        );
+
+        CREATE TABLE IF NOT EXISTS cycles (
+            forge_repo TEXT NOT NULL,
+            number INTEGER NOT NULL,
+            name TEXT,
+            starts_at TEXT NOT NULL,
+            ends_at TEXT NOT NULL,
+            PRIMARY KEY (forge_repo, number)
+        );
+
+        CREATE TABLE IF NOT EXISTS workspace_issues (
+            workspace TEXT NOT NULL,
+            forge_repo TEXT NOT NULL,
+            number INTEGER NOT NULL,
+            PRIMARY KEY (workspace, forge_repo, number)
+        );
         ",
     )?;
 
@@ -146,6 +533,57 @@ pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
         conn.execute("ALTER TABLE repo_links ADD COLUMN display_name TEXT", [])?;
     }
 
+    // Migration: add remote_url and root_commit columns to repo_links (move detection)
+    let has_root_commit: bool = conn
+        .prepare("SELECT root_commit FROM repo_links LIMIT 0")
+        .is_ok();
+    if !has_root_commit {
+        conn.execute("ALTER TABLE repo_links ADD COLUMN remote_url TEXT", [])?;
+        conn.execute("ALTER TABLE repo_links ADD COLUMN root_commit TEXT", [])?;
+    }
+
+    // Migration: add account column to repo_links (named credentials, `--as work`)
+    let has_account: bool = conn
+        .prepare("SELECT account FROM repo_links LIMIT 0")
+        .is_ok();
+    if !has_account {
+        conn.execute("ALTER TABLE repo_links ADD COLUMN account TEXT", [])?;
+    }
+
+    // Migration: add last_error column to pending_ops (why replays keep failing)
+    let has_last_error: bool = conn
+        .prepare("SELECT last_error FROM pending_ops LIMIT 0")
+        .is_ok();
+    if !has_last_error {
+        conn.execute("ALTER TABLE pending_ops ADD COLUMN last_error TEXT", [])?;
+    }
+
+    // Migration: add claimed_at/claimed_by to pending_ops (one replayer per op)
+    let has_claimed_at: bool = conn
+        .prepare("SELECT claimed_at FROM pending_ops LIMIT 0")
+        .is_ok();
+    if !has_claimed_at {
+        conn.execute_batch(
+            "ALTER TABLE pending_ops ADD COLUMN claimed_at TEXT;
+             ALTER TABLE pending_ops ADD COLUMN claimed_by INTEGER;",
+        )?;
+    }
+
+    // Migration: add dedup_key to queued and failed ops (idempotent replay),
+    // giving ops queued before it a key of their own
+    for table in ["pending_ops", "failed_ops"] {
+        let has_dedup_key: bool = conn
+            .prepare(&format!("SELECT dedup_key FROM {} LIMIT 0", table))
+            .is_ok();
+        if !has_dedup_key {
+            conn.execute_batch(&format!(
+                "ALTER TABLE {0} ADD COLUMN dedup_key TEXT;
+                 UPDATE {0} SET dedup_key = {1};",
+                table, NEW_DEDUP_KEY
+            ))?;
+        }
+    }
+
     // Migration: add html_url column to issues if it doesn't exist
     let has_html_url: bool = conn
         .prepare("SELECT html_url FROM issues LIMIT 0")
@@ -162,6 +600,185 @@ pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
         conn.execute("ALTER TABLE issues ADD COLUMN milestone TEXT", [])?;
     }
 
+    // Migration: add assignees and priority columns to issues if they don't exist
+    let has_assignees: bool = conn
+        .prepare("SELECT assignees FROM issues LIMIT 0")
+        .is_ok();
+    if !has_assignees {
+        conn.execute("ALTER TABLE issues ADD COLUMN assignees TEXT NOT NULL DEFAULT '[]'", [])?;
+    }
+    let has_priority: bool = conn
+        .prepare("SELECT priority FROM issues LIMIT 0")
+        .is_ok();
+    if !has_priority {
+        conn.execute("ALTER TABLE issues ADD COLUMN priority INTEGER", [])?;
+    }
+
+    // Migration: add Linear SLA and triage columns to issues
+    let has_sla: bool = conn
+        .prepare("SELECT sla_breaches_at FROM issues LIMIT 0")
+        .is_ok();
+    if !has_sla {
+        conn.execute_batch(
+            "ALTER TABLE issues ADD COLUMN sla_breaches_at TEXT;
+             ALTER TABLE issues ADD COLUMN triage INTEGER NOT NULL DEFAULT 0;",
+        )?;
+    }
+
+    // Migration: add issue_type to issues (GitHub issue types)
+    let has_issue_type: bool = conn
+        .prepare("SELECT issue_type FROM issues LIMIT 0")
+        .is_ok();
+    if !has_issue_type {
+        conn.execute("ALTER TABLE issues ADD COLUMN issue_type TEXT", [])?;
+    }
+
+    // Migration: add issue_number to drafts (comment drafts kept while posting)
+    let has_draft_issue: bool = conn
+        .prepare("SELECT issue_number FROM drafts LIMIT 0")
+        .is_ok();
+    if !has_draft_issue {
+        conn.execute("ALTER TABLE drafts ADD COLUMN issue_number INTEGER", [])?;
+    }
+
+    // Migration: add cached_at to issues (local clock, for ordering syncs
+    // against local writes)
+    let has_cached_at: bool = conn
+        .prepare("SELECT cached_at FROM issues LIMIT 0")
+        .is_ok();
+    if !has_cached_at {
+        conn.execute("ALTER TABLE issues ADD COLUMN cached_at TEXT", [])?;
+    }
+
+    // Migration: add estimate and cycle columns to issues (Linear)
+    let has_estimate: bool = conn
+        .prepare("SELECT estimate FROM issues LIMIT 0")
+        .is_ok();
+    if !has_estimate {
+        conn.execute_batch(
+            "ALTER TABLE issues ADD COLUMN estimate REAL;
+             ALTER TABLE issues ADD COLUMN cycle INTEGER;",
+        )?;
+    }
+
+    // Migration: add identifier/team_key columns to issues and pull Linear
+    // identifiers back out of titles cached as "ABC-123 Title"
+    let has_identifier: bool = conn
+        .prepare("SELECT identifier FROM issues LIMIT 0")
+        .is_ok();
+    if !has_identifier {
+        conn.execute_batch(
+            "ALTER TABLE issues ADD COLUMN identifier TEXT;
+             ALTER TABLE issues ADD COLUMN team_key TEXT;",
+        )?;
+        split_cached_identifiers(conn)?;
+    }
+
+    // Migration: add issue_labels (one row per issue label, for filtering),
+    // filled from the labels JSON of cached issues
+    let has_issue_labels: bool = conn
+        .prepare("SELECT label FROM issue_labels LIMIT 0")
+        .is_ok();
+    if !has_issue_labels {
+        conn.execute_batch(
+            "CREATE TABLE issue_labels (
+                repo TEXT NOT NULL,
+                issue_number INTEGER NOT NULL,
+                label TEXT NOT NULL COLLATE NOCASE,
+                PRIMARY KEY (repo, issue_number, label)
+            );",
+        )?;
+        conn.execute(&format!("INSERT OR IGNORE INTO issue_labels {}", ISSUE_LABELS_SQL), [])?;
+    }
+    // Covering index for label filters; (repo, label) alone lost to the
+    // primary key, which only narrows by repo
+    conn.execute_batch(
+        "DROP INDEX IF EXISTS idx_issue_labels_label;
+         CREATE INDEX IF NOT EXISTS idx_issue_labels_lookup ON issue_labels(repo, label, issue_number);",
+    )?;
+
+    // Migration: full-text index over comment bodies (`isq comment search`),
+    // kept in step with the comments table by triggers
+    let has_comments_fts: bool = conn
+        .prepare("SELECT rowid FROM comments_fts LIMIT 0")
+        .is_ok();
+    if !has_comments_fts {
+        conn.execute_batch(
+            "CREATE VIRTUAL TABLE comments_fts USING fts5(
+                body, content = 'comments', content_rowid = 'id', tokenize = 'porter unicode61'
+            );
+            CREATE TRIGGER comments_fts_insert AFTER INSERT ON comments BEGIN
+                INSERT INTO comments_fts (rowid, body) VALUES (new.id, new.body);
+            END;
+            CREATE TRIGGER comments_fts_delete AFTER DELETE ON comments BEGIN
+                INSERT INTO comments_fts (comments_fts, rowid, body) VALUES ('delete', old.id, old.body);
+            END;
+            CREATE TRIGGER comments_fts_update AFTER UPDATE ON comments BEGIN
+                INSERT INTO comments_fts (comments_fts, rowid, body) VALUES ('delete', old.id, old.body);
+                INSERT INTO comments_fts (rowid, body) VALUES (new.id, new.body);
+            END;
+            INSERT INTO comments_fts (comments_fts) VALUES ('rebuild');",
+        )?;
+    }
+
+    // Migration: full-text indexes over issue titles and bodies and goal names
+    // and descriptions (`isq search`). Only text changes touch them.
+    let has_issues_fts: bool = conn
+        .prepare("SELECT rowid FROM issues_fts LIMIT 0")
+        .is_ok();
+    if !has_issues_fts {
+        conn.execute_batch(
+            "CREATE VIRTUAL TABLE issues_fts USING fts5(
+                title, body, content = 'issues', content_rowid = 'id', tokenize = 'porter unicode61'
+            );
+            CREATE TRIGGER issues_fts_insert AFTER INSERT ON issues BEGIN
+                INSERT INTO issues_fts (rowid, title, body) VALUES (new.id, new.title, new.body);
+            END;
+            CREATE TRIGGER issues_fts_delete AFTER DELETE ON issues BEGIN
+                INSERT INTO issues_fts (issues_fts, rowid, title, body) VALUES ('delete', old.id, old.title, old.body);
+            END;
+            CREATE TRIGGER issues_fts_update AFTER UPDATE OF title, body ON issues BEGIN
+                INSERT INTO issues_fts (issues_fts, rowid, title, body) VALUES ('delete', old.id, old.title, old.body);
+                INSERT INTO issues_fts (rowid, title, body) VALUES (new.id, new.title, new.body);
+            END;
+            INSERT INTO issues_fts (issues_fts) VALUES ('rebuild');
+
+            CREATE VIRTUAL TABLE goals_fts USING fts5(
+                name, description, content = 'goals', content_rowid = 'id', tokenize = 'porter unicode61'
+            );
+            CREATE TRIGGER goals_fts_insert AFTER INSERT ON goals BEGIN
+                INSERT INTO goals_fts (rowid, name, description) VALUES (new.id, new.name, new.description);
+            END;
+            CREATE TRIGGER goals_fts_delete AFTER DELETE ON goals BEGIN
+                INSERT INTO goals_fts (goals_fts, rowid, name, description)
+                    VALUES ('delete', old.id, old.name, old.description);
+            END;
+            CREATE TRIGGER goals_fts_update AFTER UPDATE OF name, description ON goals BEGIN
+                INSERT INTO goals_fts (goals_fts, rowid, name, description)
+                    VALUES ('delete', old.id, old.name, old.description);
+                INSERT INTO goals_fts (rowid, name, description) VALUES (new.id, new.name, new.description);
+            END;
+            INSERT INTO goals_fts (goals_fts) VALUES ('rebuild');",
+        )?;
+    }
+
+    // Migration: add comment_count column to issues, filled from cached comments
+    let has_comment_count: bool = conn
+        .prepare("SELECT comment_count FROM issues LIMIT 0")
+        .is_ok();
+    if !has_comment_count {
+        conn.execute("ALTER TABLE issues ADD COLUMN comment_count INTEGER NOT NULL DEFAULT 0", [])?;
+        conn.execute(&format!("UPDATE issues SET comment_count = {}", COMMENT_COUNT_SQL), [])?;
+    }
+
+    // Migration: add paused column to watched_repos if it doesn't exist
+    let has_paused: bool = conn
+        .prepare("SELECT paused FROM watched_repos LIMIT 0")
+        .is_ok();
+    if !has_paused {
+        conn.execute("ALTER TABLE watched_repos ADD COLUMN paused INTEGER NOT NULL DEFAULT 0", [])?;
+    }
+
     // Migration: add progress column to goals if it doesn't exist
     let has_progress: bool = conn
         .prepare("SELECT progress FROM goals LIMIT 0")
@@ -184,83 +801,452 @@ pub(crate) fn init_schema(conn: &Connection) -> Result<()> {
         conn.execute("ALTER TABLE rate_limit_state ADD COLUMN remaining INTEGER", [])?;
     }
 
+    // Migration: add query-complexity budget columns (Linear) to rate_limit_state
+    let has_complexity: bool = conn
+        .prepare("SELECT complexity_limit FROM rate_limit_state LIMIT 0")
+        .is_ok();
+    if !has_complexity {
+        conn.execute_batch(
+            "ALTER TABLE rate_limit_state ADD COLUMN complexity_limit INTEGER;
+             ALTER TABLE rate_limit_state ADD COLUMN complexity_remaining INTEGER;
+             ALTER TABLE rate_limit_state ADD COLUMN complexity_reset_at INTEGER;
+             ALTER TABLE rate_limit_state ADD COLUMN complexity_last_cost INTEGER;",
+        )?;
+    }
+
     Ok(())
 }
 
-/// Save issues to database (full replace for a repo)
-pub fn save_issues(conn: &Connection, repo: &str, issues: &[Issue]) -> Result<()> {
-    let tx = conn.unchecked_transaction()?;
+/// Move identifiers baked into cached titles ("ABC-123 Title", from before
+/// identifiers had their own column) into the identifier column
+fn split_cached_identifiers(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("SELECT id, number, title FROM issues")?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, String>(2)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
 
-    // Delete existing issues for this repo
-    tx.execute("DELETE FROM issues WHERE repo = ?", params![repo])?;
+    for (id, number, title) in rows {
+        let Some((first, rest)) = title.split_once(' ') else {
+            continue;
+        };
+        let Some((key, n)) = crate::forges::split_identifier(first) else {
+            continue;
+        };
+        if n == number as u64 {
+            conn.execute(
+                "UPDATE issues SET title = ?, identifier = ?, team_key = ? WHERE id = ?",
+                params![rest, first, key, id],
+            )?;
+        }
+    }
+    Ok(())
+}
 
-    // Insert new issues
-    let mut stmt = tx.prepare(
-        "INSERT INTO issues (repo, number, title, body, state, author, labels, created_at, updated_at, html_url, milestone)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-    )?;
+/// Team key of an identifier (`ABC` for `ABC-123`)
+fn team_key(issue: &Issue) -> Option<&str> {
+    issue.identifier.as_deref().and_then(crate::forges::split_identifier).map(|(key, _)| key)
+}
 
-    for issue in issues {
-        let labels_json = serde_json::to_string(&issue.labels)?;
-        stmt.execute(params![
-            repo,
-            issue.number as i64,
-            issue.title,
-            issue.body,
-            issue.state,
-            issue.author,
-            labels_json,
-            issue.created_at,
-            issue.updated_at,
-            issue.url,
-            issue.milestone,
-        ])?;
+/// Insert or update an issue row, keyed on (repo, number). Leaves
+/// `comment_count` alone.
+const UPSERT_ISSUE_SQL: &str =
+    "INSERT INTO issues (repo, number, title, body, state, author, labels, created_at, updated_at, html_url, milestone, assignees, priority, identifier, team_key, sla_breaches_at, triage, issue_type, estimate, cycle, cached_at)
+     VALUES (?, ?, ?, isq_encrypt(?), ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+     ON CONFLICT(repo, number) DO UPDATE SET
+        title = excluded.title, body = excluded.body, state = excluded.state,
+        author = excluded.author, labels = excluded.labels, created_at = excluded.created_at,
+        updated_at = excluded.updated_at, html_url = excluded.html_url,
+        milestone = excluded.milestone, assignees = excluded.assignees, priority = excluded.priority,
+        identifier = excluded.identifier, team_key = excluded.team_key,
+        sla_breaches_at = excluded.sla_breaches_at, triage = excluded.triage, issue_type = excluded.issue_type,
+        estimate = excluded.estimate, cycle = excluded.cycle, cached_at = excluded.cached_at";
+
+fn upsert_issue_row(stmt: &mut rusqlite::Statement, repo: &str, issue: &Issue) -> Result<()> {
+    stmt.execute(params![
+        repo,
+        issue.number as i64,
+        issue.title,
+        issue.body,
+        issue.state,
+        issue.author,
+        serde_json::to_string(&issue.labels)?,
+        issue.created_at,
+        issue.updated_at,
+        issue.url,
+        issue.milestone,
+        serde_json::to_string(&issue.assignees)?,
+        issue.priority,
+        issue.identifier,
+        team_key(issue),
+        issue.sla_breaches_at,
+        issue.triage,
+        issue.issue_type,
+        issue.estimate,
+        issue.cycle,
+    ])?;
+    Ok(())
+}
+
+/// (repo, issue_number, label) for each label in the issues' labels JSON.
+/// Labels are objects; caches from before label colors hold plain strings.
+const ISSUE_LABELS_SQL: &str =
+    "SELECT issues.repo, issues.number, CASE j.type WHEN 'object' THEN json_extract(j.value, '$.name') ELSE j.value END AS name
+     FROM issues, json_each(issues.labels) j
+     WHERE json_valid(issues.labels) AND name IS NOT NULL";
+
+/// Rebuild `issue_labels` for a repo, or one issue in it, from the labels JSON
+fn refresh_issue_labels(conn: &Connection, repo: &str, number: Option<u64>) -> Result<()> {
+    match number {
+        Some(number) => {
+            conn.execute(
+                "DELETE FROM issue_labels WHERE repo = ? AND issue_number = ?",
+                params![repo, number as i64],
+            )?;
+            conn.execute(
+                &format!("INSERT OR IGNORE INTO issue_labels {} AND issues.repo = ? AND issues.number = ?", ISSUE_LABELS_SQL),
+                params![repo, number as i64],
+            )?;
+        }
+        None => {
+            conn.execute("DELETE FROM issue_labels WHERE repo = ?", params![repo])?;
+            conn.execute(
+                &format!("INSERT OR IGNORE INTO issue_labels {} AND issues.repo = ?", ISSUE_LABELS_SQL),
+                params![repo],
+            )?;
+        }
     }
+    Ok(())
+}
 
-    // Drop statement before committing
-    drop(stmt);
+/// The local clock, for `save_issues`: take it just before fetching
+pub fn fetch_started() -> String {
+    chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string()
+}
+
+/// Save a full fetch of a repo's issues, begun at `started`
+/// (`fetch_started`). Fetched issues are merged in by number. Cached issues
+/// missing from the fetch are deleted and leave a tombstone, unless they were
+/// written after the fetch began (e.g. by `isq issue create`). A tombstone
+/// keeps out issues from any fetch that began before the deletion, so a
+/// slower sync can't bring them back. Only local-clock times are compared.
+/// Takes the write lock up front, so the CLI and daemon syncing at once apply
+/// one after the other instead of interleaving.
+pub fn save_issues(conn: &Connection, repo: &str, issues: &[Issue], started: &str) -> Result<()> {
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+
+    let tombstones: HashMap<u64, String> = {
+        let mut stmt = tx.prepare("SELECT number, deleted_at FROM issue_tombstones WHERE repo = ?")?;
+        let rows = stmt.query_map(params![repo], |row| Ok((row.get::<_, i64>(0)? as u64, row.get::<_, String>(1)?)))?;
+        rows.collect::<Result<_, _>>()?
+    };
+    // Rows written since the fetch began may be missing from it only because
+    // they're newer
+    let cached: Vec<u64> = {
+        let mut stmt = tx.prepare("SELECT number FROM issues WHERE repo = ? AND (cached_at IS NULL OR cached_at < ?)")?;
+        let rows = stmt.query_map(params![repo, started], |row| row.get::<_, i64>(0))?;
+        rows.map(|n| n.map(|n| n as u64)).collect::<Result<_, _>>()?
+    };
+
+    // Both are local-clock UTC timestamps in the same format, so they compare as strings
+    let live: Vec<&Issue> = issues
+        .iter()
+        .filter(|issue| tombstones.get(&issue.number).is_none_or(|deleted_at| started > deleted_at.as_str()))
+        .collect();
+
+    let mut upsert = tx.prepare(UPSERT_ISSUE_SQL)?;
+    let mut revive = tx.prepare("DELETE FROM issue_tombstones WHERE repo = ? AND number = ?")?;
+    for issue in &live {
+        upsert_issue_row(&mut upsert, repo, issue)?;
+        revive.execute(params![repo, issue.number as i64])?;
+    }
+
+    let fetched: std::collections::HashSet<u64> = issues.iter().map(|issue| issue.number).collect();
+    let mut bury = tx.prepare(&format!(
+        "INSERT OR REPLACE INTO issue_tombstones (repo, number, deleted_at) VALUES (?1, ?2, {})",
+        NOW_MS
+    ))?;
+    let mut delete = tx.prepare("DELETE FROM issues WHERE repo = ? AND number = ?")?;
+    for &number in cached.iter().filter(|n| !fetched.contains(n)) {
+        bury.execute(params![repo, number as i64])?;
+        delete.execute(params![repo, number as i64])?;
+    }
+
+    // Drop statements before committing
+    drop((upsert, revive, bury, delete));
+
+    // Queued writes win over what was just read
+    reapply_pending_ops(&tx, repo)?;
+
+    // New rows start at zero; comments already cached still count
+    refresh_comment_counts(&tx, repo, None)?;
+    refresh_references(&tx, repo)?;
+    refresh_issue_labels(&tx, repo, None)?;
 
-    // Update sync state
+    // Update sync state; a complete fetch supersedes any interrupted one
     tx.execute(
         "INSERT OR REPLACE INTO sync_state (repo, last_sync, issue_count)
          VALUES (?, datetime('now'), ?)",
-        params![repo, issues.len() as i64],
+        params![repo, live.len() as i64],
     )?;
+    tx.execute("DELETE FROM sync_cursors WHERE repo = ?", params![repo])?;
 
     tx.commit()?;
     Ok(())
 }
 
-/// Load all issues for a repo from cache
-#[allow(dead_code)] // Used in tests
-pub fn load_issues(conn: &Connection, repo: &str) -> Result<Vec<Issue>> {
-    load_issues_filtered(conn, repo, None, None)
-}
+// === Resumable syncs ===
+//
+// A large initial sync saves each page as it arrives, along with a cursor
+// (forge-specific: GitHub's finished page numbers, Linear's end cursor), so
+// an interrupted `isq link` picks up where it stopped.
 
-/// Load issues with optional filters
+/// Where an interrupted sync of a repo stopped, if one did
+pub fn get_sync_cursor(conn: &Connection, repo: &str) -> Result<Option<String>> {
+    let mut stmt = conn.prepare("SELECT cursor FROM sync_cursors WHERE repo = ?")?;
+    let mut rows = stmt.query(params![repo])?;
+
+    match rows.next()? {
+        Some(row) => Ok(Some(row.get(0)?)),
+        None => Ok(None),
+    }
+}
+
+/// Merge one page of a sync into the cache and move its cursor past it
+pub fn save_issue_page(conn: &Connection, repo: &str, issues: &[Issue], cursor: &str) -> Result<()> {
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+
+    let mut upsert = tx.prepare(UPSERT_ISSUE_SQL)?;
+    for issue in issues {
+        upsert_issue_row(&mut upsert, repo, issue)?;
+        refresh_issue_labels(&tx, repo, Some(issue.number))?;
+    }
+    drop(upsert);
+    reapply_pending_ops(&tx, repo)?;
+
+    tx.execute(
+        "INSERT OR REPLACE INTO sync_cursors (repo, cursor, updated_at) VALUES (?, ?, datetime('now'))",
+        params![repo, cursor],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Finish a sync that was resumed from a cursor. Its pages are already
+/// cached; what was fetched before the interruption can't be told apart from
+/// older rows, so deletions are left for the next full sync.
+pub fn finish_resumed_sync(conn: &Connection, repo: &str) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    refresh_comment_counts(&tx, repo, None)?;
+    refresh_references(&tx, repo)?;
+    tx.execute(
+        "INSERT OR REPLACE INTO sync_state (repo, last_sync, issue_count)
+         VALUES (?1, datetime('now'), (SELECT COUNT(*) FROM issues WHERE repo = ?1))",
+        params![repo],
+    )?;
+    tx.execute("DELETE FROM sync_cursors WHERE repo = ?", params![repo])?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Load all issues for a repo from cache
+pub fn load_issues(conn: &Connection, repo: &str) -> Result<Vec<Issue>> {
+    load_issues_filtered(conn, repo, None, None)
+}
+
+/// Columns read by `issue_from_row`, in order
+const ISSUE_COLUMNS: &str =
+    "number, title, isq_decrypt(body), state, author, labels, created_at, updated_at, html_url, milestone, assignees, priority, identifier, sla_breaches_at, triage, issue_type, estimate, cycle";
+
+/// Map a row selected with `ISSUE_COLUMNS` to an Issue
+fn issue_from_row(row: &rusqlite::Row) -> rusqlite::Result<Issue> {
+    let number: i64 = row.get(0)?;
+    let labels_json: String = row.get(5)?;
+    let assignees_json: String = row.get(10)?;
+
+    Ok(Issue {
+        number: number as u64,
+        title: row.get(1)?,
+        body: row.get(2)?,
+        state: row.get(3)?,
+        author: row.get(4)?,
+        labels: parse_labels_json(&labels_json),
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+        url: row.get(8)?,
+        milestone: row.get(9)?,
+        assignees: serde_json::from_str(&assignees_json).unwrap_or_default(),
+        priority: row.get(11)?,
+        identifier: row.get(12)?,
+        sla_breaches_at: row.get(13)?,
+        triage: row.get(14)?,
+        issue_type: row.get(15)?,
+        estimate: row.get(16)?,
+        cycle: row.get(17)?,
+    })
+}
+
+/// Filters for listing cached issues (combined with AND)
+#[derive(Debug, Default, Clone)]
+pub struct IssueFilter {
+    /// Only issues with all of these labels (case-insensitive)
+    pub labels: Vec<String>,
+    /// Only issues with at least one of these labels
+    pub any_labels: Vec<String>,
+    /// Only issues with none of these labels
+    pub not_labels: Vec<String>,
+    pub state: Option<String>,
+    /// Goal name (milestone/project), matched case-insensitively
+    pub goal: Option<String>,
+    pub assignee: Option<String>,
+    /// Only issues this user opened, is assigned to or commented on
+    pub participant: Option<String>,
+    /// Only issues last updated before this timestamp (RFC 3339)
+    pub updated_before: Option<String>,
+    /// Only open issues whose SLA breaches before this timestamp (RFC 3339)
+    pub sla_breaches_before: Option<String>,
+    /// Only open issues awaiting a first reply: no comments, or (given a
+    /// maintainer list) none by these users
+    pub unanswered: Option<Vec<String>>,
+    /// Only issues of this type (case-insensitive)
+    pub issue_type: Option<String>,
+    /// Labels that stand for `issue_type` on issues the forge gave no type
+    pub type_labels: Vec<String>,
+    /// Hide issues by these authors (case-insensitive; `name` also covers `name[bot]`)
+    pub exclude_authors: Vec<String>,
+    /// Hide issues with any of these labels (the `[list]` config exclusions)
+    pub exclude_labels: Vec<String>,
+}
+
+impl IssueFilter {
+    pub fn has_exclusions(&self) -> bool {
+        !self.exclude_authors.is_empty() || !self.exclude_labels.is_empty()
+    }
+}
+
+/// Load issues with optional label/state filters
 pub fn load_issues_filtered(
     conn: &Connection,
     repo: &str,
     label: Option<&str>,
     state: Option<&str>,
 ) -> Result<Vec<Issue>> {
+    let filter = IssueFilter {
+        labels: label.map(String::from).into_iter().collect(),
+        state: state.map(String::from),
+        ..Default::default()
+    };
+    load_issues_matching(conn, repo, &filter)
+}
+
+/// Matches issues with `?` among their assignees (a JSON array of names),
+/// exactly: no wildcards, no case folding
+const ASSIGNED_TO: &str = "EXISTS (SELECT 1 FROM json_each(issues.assignees) WHERE value = ?)";
+
+/// Load issues matching a filter
+pub fn load_issues_matching(conn: &Connection, repo: &str, filter: &IssueFilter) -> Result<Vec<Issue>> {
     // Build query dynamically based on filters
-    let mut sql = String::from(
-        "SELECT number, title, body, state, author, labels, created_at, updated_at, html_url, milestone
-         FROM issues WHERE repo = ?",
-    );
+    let mut sql = format!("SELECT {} FROM issues WHERE repo = ?", ISSUE_COLUMNS);
 
     let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(repo.to_string())];
 
-    if let Some(s) = state {
+    if let Some(s) = &filter.state {
         sql.push_str(" AND state = ?");
-        params_vec.push(Box::new(s.to_string()));
+        params_vec.push(Box::new(s.clone()));
+    }
+
+    // Labels are matched against issue_labels, one row per issue label
+    let mut label_match = |sql: &mut String, negate: bool, labels: &[String]| {
+        let placeholders = vec!["?"; labels.len()].join(", ");
+        sql.push_str(&format!(
+            " AND number {}IN (SELECT issue_number FROM issue_labels WHERE repo = ? AND label IN ({}))",
+            if negate { "NOT " } else { "" },
+            placeholders
+        ));
+        params_vec.push(Box::new(repo.to_string()));
+        for label in labels {
+            params_vec.push(Box::new(label.clone()));
+        }
+    };
+    for label in &filter.labels {
+        label_match(&mut sql, false, std::slice::from_ref(label));
+    }
+    if !filter.any_labels.is_empty() {
+        label_match(&mut sql, false, &filter.any_labels);
+    }
+    let excluded: Vec<String> = filter.not_labels.iter().chain(&filter.exclude_labels).cloned().collect();
+    if !excluded.is_empty() {
+        label_match(&mut sql, true, &excluded);
+    }
+
+    if let Some(g) = &filter.goal {
+        sql.push_str(" AND milestone = ? COLLATE NOCASE");
+        params_vec.push(Box::new(g.clone()));
+    }
+
+    if let Some(a) = &filter.assignee {
+        sql.push_str(&format!(" AND {}", ASSIGNED_TO));
+        params_vec.push(Box::new(a.clone()));
+    }
+
+    if let Some(p) = &filter.participant {
+        sql.push_str(&format!(
+            " AND (author = ? COLLATE NOCASE OR {} OR EXISTS (SELECT 1 FROM comments c
+                WHERE c.forge_repo = issues.repo AND c.issue_number = issues.number AND c.author = ? COLLATE NOCASE))",
+            ASSIGNED_TO
+        ));
+        params_vec.push(Box::new(p.clone()));
+        params_vec.push(Box::new(p.clone()));
+        params_vec.push(Box::new(p.clone()));
+    }
+
+    if let Some(before) = &filter.updated_before {
+        sql.push_str(" AND updated_at < ?");
+        params_vec.push(Box::new(before.clone()));
+    }
+
+    if let Some(issue_type) = &filter.issue_type {
+        sql.push_str(" AND (issue_type = ? COLLATE NOCASE");
+        params_vec.push(Box::new(issue_type.clone()));
+        if !filter.type_labels.is_empty() {
+            let placeholders = vec!["?"; filter.type_labels.len()].join(", ");
+            sql.push_str(&format!(
+                " OR (issue_type IS NULL AND number IN (SELECT issue_number FROM issue_labels WHERE repo = ? AND label IN ({})))",
+                placeholders
+            ));
+            params_vec.push(Box::new(repo.to_string()));
+            for label in &filter.type_labels {
+                params_vec.push(Box::new(label.clone()));
+            }
+        }
+        sql.push(')');
+    }
+
+    if let Some(before) = &filter.sla_breaches_before {
+        sql.push_str(" AND state = 'open' AND sla_breaches_at < ?");
+        params_vec.push(Box::new(before.clone()));
+    }
+
+    if let Some(maintainers) = &filter.unanswered {
+        sql.push_str(" AND state = 'open' AND (comment_count = 0");
+        if !maintainers.is_empty() {
+            let placeholders = vec!["?"; maintainers.len()].join(", ");
+            sql.push_str(&format!(
+                " OR NOT EXISTS (SELECT 1 FROM comments c WHERE c.forge_repo = issues.repo
+                    AND c.issue_number = issues.number AND LOWER(c.author) IN ({}))",
+                placeholders
+            ));
+            for maintainer in maintainers {
+                params_vec.push(Box::new(maintainer.to_lowercase()));
+            }
+        }
+        sql.push(')');
     }
 
-    if let Some(l) = label {
-        // Labels are stored as JSON array of strings, e.g. ["bug","enhancement"]
-        sql.push_str(" AND labels LIKE ?");
-        params_vec.push(Box::new(format!("%\"{}\"%", l)));
+    for author in &filter.exclude_authors {
+        let author = author.to_lowercase();
+        sql.push_str(" AND LOWER(author) NOT IN (?, ?)");
+        params_vec.push(Box::new(format!("{}[bot]", author)));
+        params_vec.push(Box::new(author));
     }
 
     sql.push_str(" ORDER BY number DESC");
@@ -270,24 +1256,7 @@ pub fn load_issues_filtered(
     let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
 
     let issues = stmt
-        .query_map(params_refs.as_slice(), |row| {
-            let number: i64 = row.get(0)?;
-            let labels_json: String = row.get(5)?;
-            let labels = parse_labels_json(&labels_json);
-
-            Ok(Issue {
-                number: number as u64,
-                title: row.get(1)?,
-                body: row.get(2)?,
-                state: row.get(3)?,
-                author: row.get(4)?,
-                labels,
-                created_at: row.get(6)?,
-                updated_at: row.get(7)?,
-                url: row.get(8)?,
-                milestone: row.get(9)?,
-            })
-        })?
+        .query_map(params_refs.as_slice(), issue_from_row)?
         .collect::<Result<Vec<_>, _>>()?;
 
     Ok(issues)
@@ -295,35 +1264,143 @@ pub fn load_issues_filtered(
 
 /// Load a single issue from cache
 pub fn load_issue(conn: &Connection, repo: &str, number: u64) -> Result<Option<Issue>> {
-    let mut stmt = conn.prepare(
-        "SELECT number, title, body, state, author, labels, created_at, updated_at, html_url, milestone
-         FROM issues WHERE repo = ? AND number = ?",
-    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM issues WHERE repo = ? AND number = ?",
+        ISSUE_COLUMNS
+    ))?;
 
     let mut rows = stmt.query(params![repo, number as i64])?;
 
     if let Some(row) = rows.next()? {
-        let num: i64 = row.get(0)?;
-        let labels_json: String = row.get(5)?;
-        let labels = parse_labels_json(&labels_json);
-
-        Ok(Some(Issue {
-            number: num as u64,
-            title: row.get(1)?,
-            body: row.get(2)?,
-            state: row.get(3)?,
-            author: row.get(4)?,
-            labels,
-            created_at: row.get(6)?,
-            updated_at: row.get(7)?,
-            url: row.get(8)?,
-            milestone: row.get(9)?,
-        }))
+        Ok(Some(issue_from_row(row)?))
     } else {
         Ok(None)
     }
 }
 
+// === Local Updates ===
+//
+// Applied right after a write succeeds (or is queued) so follow-up commands see
+// it before the next sync. The next full sync replaces these rows with the
+// forge's copy.
+
+/// Timestamp format the forges use for `updated_at`
+const NOW_ISO: &str = "strftime('%Y-%m-%dT%H:%M:%SZ', 'now')";
+
+/// `fetch_started`'s format, for local-clock times compared against it
+const NOW_MS: &str = "strftime('%Y-%m-%dT%H:%M:%fZ', 'now')";
+
+/// Insert or replace a single issue without touching sync state. A fresh
+/// fetch of the issue clears any tombstone for it.
+pub fn upsert_issue(conn: &Connection, repo: &str, issue: &Issue) -> Result<()> {
+    upsert_issue_row(&mut conn.prepare(UPSERT_ISSUE_SQL)?, repo, issue)?;
+    conn.execute(
+        "DELETE FROM issue_tombstones WHERE repo = ? AND number = ?",
+        params![repo, issue.number as i64],
+    )?;
+    refresh_issue_labels(conn, repo, Some(issue.number))?;
+    index_references(conn, repo, issue.number, BODY_SOURCE, issue.body.as_deref().unwrap_or_default())
+}
+
+/// Set an issue's state ("open" or "closed"). Returns false if it isn't cached.
+pub fn set_issue_state(conn: &Connection, repo: &str, number: u64, state: &str) -> Result<bool> {
+    let changed = conn.execute(
+        &format!("UPDATE issues SET state = ?, updated_at = {} WHERE repo = ? AND number = ?", NOW_ISO),
+        params![state, repo, number as i64],
+    )?;
+    Ok(changed > 0)
+}
+
+/// Replace an issue's cached body. Returns false if it isn't cached.
+pub fn set_issue_body(conn: &Connection, repo: &str, number: u64, body: &str) -> Result<bool> {
+    let changed = conn.execute(
+        &format!("UPDATE issues SET body = isq_encrypt(?), updated_at = {} WHERE repo = ? AND number = ?", NOW_ISO),
+        params![body, repo, number as i64],
+    )?;
+    if changed > 0 {
+        index_references(conn, repo, number, BODY_SOURCE, body)?;
+    }
+    Ok(changed > 0)
+}
+
+/// Load, modify and store one issue. Returns false if it isn't cached.
+fn update_cached_issue(conn: &Connection, repo: &str, number: u64, f: impl FnOnce(&mut Issue)) -> Result<bool> {
+    let Some(mut issue) = load_issue(conn, repo, number)? else {
+        return Ok(false);
+    };
+    f(&mut issue);
+    conn.execute(
+        &format!(
+            "UPDATE issues SET labels = ?, assignees = ?, milestone = ?, updated_at = {} WHERE repo = ? AND number = ?",
+            NOW_ISO
+        ),
+        params![
+            serde_json::to_string(&issue.labels)?,
+            serde_json::to_string(&issue.assignees)?,
+            issue.milestone,
+            repo,
+            number as i64,
+        ],
+    )?;
+    refresh_issue_labels(conn, repo, Some(number))?;
+    Ok(true)
+}
+
+/// Add a label to a cached issue, reusing the label's color from other issues when known
+pub fn add_issue_label(conn: &Connection, repo: &str, number: u64, name: &str) -> Result<bool> {
+    // The repo's label catalog knows the color, else another issue carrying the label
+    let mut color = load_labels(conn, repo)?
+        .into_iter()
+        .find(|label| label.name.eq_ignore_ascii_case(name))
+        .and_then(|label| label.color);
+    if color.is_none() {
+        color = load_issues_matching(
+            conn,
+            repo,
+            &IssueFilter {
+                labels: vec![name.to_string()],
+                ..Default::default()
+            },
+        )?
+        .into_iter()
+        .flat_map(|issue| issue.labels)
+        .find(|label| label.name == name)
+        .and_then(|label| label.color);
+    }
+
+    update_cached_issue(conn, repo, number, |issue| {
+        if !issue.labels.iter().any(|l| l.name == name) {
+            issue.labels.push(Label {
+                name: name.to_string(),
+                color,
+            });
+        }
+    })
+}
+
+/// Remove a label from a cached issue
+pub fn remove_issue_label(conn: &Connection, repo: &str, number: u64, name: &str) -> Result<bool> {
+    update_cached_issue(conn, repo, number, |issue| issue.labels.retain(|l| l.name != name))
+}
+
+/// Move a cached issue into a goal
+pub fn set_issue_goal(conn: &Connection, repo: &str, number: u64, goal: &str) -> Result<bool> {
+    update_cached_issue(conn, repo, number, |issue| issue.milestone = Some(goal.to_string()))
+}
+
+/// Add an assignee to a cached issue. `exclusive` replaces existing assignees
+/// (Linear issues have a single assignee).
+pub fn add_issue_assignee(conn: &Connection, repo: &str, number: u64, user: &str, exclusive: bool) -> Result<bool> {
+    update_cached_issue(conn, repo, number, |issue| {
+        if exclusive {
+            issue.assignees.clear();
+        }
+        if !issue.assignees.iter().any(|a| a == user) {
+            issue.assignees.push(user.to_string());
+        }
+    })
+}
+
 /// Get sync state for a repo
 pub fn get_sync_state(conn: &Connection, repo: &str) -> Result<Option<(String, i64)>> {
     let mut stmt = conn.prepare(
@@ -339,6 +1416,17 @@ pub fn get_sync_state(conn: &Connection, repo: &str) -> Result<Option<(String, i
     }
 }
 
+/// When a repo was last synced (None if never)
+pub fn last_synced_at(conn: &Connection, repo: &str) -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+    let Some((last_sync, _)) = get_sync_state(conn, repo)? else {
+        return Ok(None);
+    };
+    // Written by SQLite's datetime('now'): UTC without a zone
+    let at = chrono::NaiveDateTime::parse_from_str(&last_sync, "%Y-%m-%d %H:%M:%S")
+        .map_err(|e| anyhow::anyhow!("Invalid last_sync '{}': {}", last_sync, e))?;
+    Ok(Some(at.and_utc()))
+}
+
 /// A pending operation queued for later sync
 #[derive(Debug, Clone)]
 #[allow(dead_code)] // Fields used for status display and debugging
@@ -348,43 +1436,300 @@ pub struct PendingOp {
     pub op_type: String,
     pub payload: String,
     pub created_at: String,
+    /// Why the last replay failed, if one did
+    pub last_error: Option<String>,
+    /// Generated when queued; a replayed op's key is remembered so the op is
+    /// never applied twice
+    pub dedup_key: String,
+}
+
+impl PendingOp {
+    fn payload_json(&self) -> serde_json::Value {
+        serde_json::from_str(&self.payload).unwrap_or_default()
+    }
+
+    /// Issue this op touches (None for creates and goal-level ops)
+    pub fn issue_number(&self) -> Option<u64> {
+        self.payload_json()["issue_number"].as_u64()
+    }
+
+    /// What the op does to its issue, e.g. `close` or `add label bug`
+    pub fn action(&self) -> String {
+        let payload = self.payload_json();
+        let text = |key: &str| payload[key].as_str().unwrap_or("").to_string();
+
+        match self.op_type.as_str() {
+            "label_add" => format!("add label {}", text("label")),
+            "label_remove" => format!("remove label {}", text("label")),
+            "assign" => format!("assign @{}", text("assignee")),
+            "assign_goal" => "add to goal".to_string(),
+            "edit_body" => "edit body".to_string(),
+            other => other.to_string(),
+        }
+    }
+
+    /// JSON shape used in `--json` output
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "id": self.id,
+            "op": self.op_type,
+            "action": self.action(),
+            "queued_at": self.created_at,
+            "last_error": self.last_error,
+        })
+    }
+
+    /// One-line description, e.g. `comment on #12` or `create "Fix login"`
+    pub fn summary(&self) -> String {
+        let payload = self.payload_json();
+        let issue = payload["issue_number"].as_u64().map(|n| format!("#{}", n)).unwrap_or_default();
+        let text = |key: &str| payload[key].as_str().unwrap_or("").to_string();
+
+        match self.op_type.as_str() {
+            "create" => format!("create \"{}\"", text("title")),
+            "comment" => format!("comment on {}", issue),
+            "close" => format!("close {}", issue),
+            "reopen" => format!("reopen {}", issue),
+            "label_add" => format!("add label {} to {}", text("label"), issue),
+            "label_remove" => format!("remove label {} from {}", text("label"), issue),
+            "assign" => format!("assign {} to {}", issue, text("assignee")),
+            "create_goal" => format!("create goal \"{}\"", text("name")),
+            "assign_goal" => format!("add {} to a goal", issue),
+            "edit_body" => format!("edit the body of {}", issue),
+            "subscribe" => format!("subscribe to {}", issue),
+            "unsubscribe" => format!("unsubscribe from {}", issue),
+            "close_goal" => "close a goal".to_string(),
+            "reopen_goal" => format!("reopen goal \"{}\"", text("name")),
+            "create_objective" => format!("create objective \"{}\"", text("name")),
+            "link_objective" => format!("put goal \"{}\" under an objective", text("name")),
+            other => other.to_string(),
+        }
+    }
 }
 
+/// SQL for a fresh random dedup key
+const NEW_DEDUP_KEY: &str = "lower(hex(randomblob(16)))";
+
 /// Queue a write operation for later sync (used when offline)
 pub fn queue_op(conn: &Connection, repo: &str, op_type: &str, payload: &str) -> Result<i64> {
     conn.execute(
-        "INSERT INTO pending_ops (repo, op_type, payload, created_at)
-         VALUES (?, ?, ?, datetime('now'))",
+        &format!(
+            "INSERT INTO pending_ops (repo, op_type, payload, created_at, dedup_key)
+             VALUES (?, ?, isq_encrypt(?), datetime('now'), {})",
+            NEW_DEDUP_KEY
+        ),
         params![repo, op_type, payload],
     )?;
     Ok(conn.last_insert_rowid())
 }
 
+const PENDING_OP_COLUMNS: &str = "id, repo, op_type, isq_decrypt(payload), created_at, last_error, dedup_key";
+
+fn pending_op_from_row(row: &rusqlite::Row) -> rusqlite::Result<PendingOp> {
+    Ok(PendingOp {
+        id: row.get(0)?,
+        repo: row.get(1)?,
+        op_type: row.get(2)?,
+        payload: row.get(3)?,
+        created_at: row.get(4)?,
+        last_error: row.get(5)?,
+        dedup_key: row.get(6)?,
+    })
+}
+
 /// Load all pending operations for a repo
 pub fn load_pending_ops(conn: &Connection, repo: &str) -> Result<Vec<PendingOp>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, repo, op_type, payload, created_at
-         FROM pending_ops WHERE repo = ? ORDER BY id ASC",
-    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM pending_ops WHERE repo = ? ORDER BY id ASC",
+        PENDING_OP_COLUMNS
+    ))?;
 
     let ops = stmt
-        .query_map(params![repo], |row| {
-            Ok(PendingOp {
-                id: row.get(0)?,
-                repo: row.get(1)?,
-                op_type: row.get(2)?,
-                payload: row.get(3)?,
-                created_at: row.get(4)?,
-            })
-        })?
+        .query_map(params![repo], pending_op_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ops)
+}
+
+/// Pending ops queued more than `days` ago, oldest first
+pub fn pending_ops_older_than(conn: &Connection, repo: &str, days: u32) -> Result<Vec<PendingOp>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM pending_ops
+         WHERE repo = ? AND created_at < datetime('now', '-' || ? || ' days') ORDER BY id ASC",
+        PENDING_OP_COLUMNS
+    ))?;
+    let ops = stmt
+        .query_map(params![repo, days], pending_op_from_row)?
         .collect::<Result<Vec<_>, _>>()?;
+    Ok(ops)
+}
+
+/// How long a replayer's claim on an op keeps others away; a claim older
+/// than this belongs to a process that died mid-replay
+const CLAIM_MINUTES: u32 = 10;
+
+/// What `claim_op` found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Claim {
+    /// Claimed; `retry` when an earlier replay was claimed or failed, so its
+    /// write may have landed already
+    Claimed { retry: bool },
+    /// Another process claimed it within the last `CLAIM_MINUTES`
+    Busy,
+}
+
+/// Claim a queued op before replaying it, so the daemon and `isq sync`
+/// replaying at once don't both send it. The claim is written before
+/// anything is sent and stays until the op completes or fails, so a replay
+/// that died in between is retried as one that may have landed.
+pub fn claim_op(conn: &Connection, id: i64) -> Result<Claim> {
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+    let retry: bool = tx.query_row(
+        "SELECT claimed_at IS NOT NULL OR last_error IS NOT NULL FROM pending_ops WHERE id = ?",
+        params![id],
+        |row| row.get(0),
+    )?;
+    let claimed = tx.execute(
+        "UPDATE pending_ops SET claimed_at = datetime('now'), claimed_by = ?1
+         WHERE id = ?2 AND (claimed_at IS NULL OR claimed_by = ?1
+                            OR claimed_at < datetime('now', '-' || ?3 || ' minutes'))",
+        params![std::process::id(), id, CLAIM_MINUTES],
+    )?;
+    tx.commit()?;
+    Ok(if claimed == 1 { Claim::Claimed { retry } } else { Claim::Busy })
+}
+
+/// Remember why replaying an op failed (it stays queued) and release the claim
+pub fn set_op_error(conn: &Connection, id: i64, error: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE pending_ops SET last_error = ?, claimed_at = NULL, claimed_by = NULL WHERE id = ?",
+        params![error, id],
+    )?;
+    Ok(())
+}
 
+/// A queued op that never synced and was given up on
+#[derive(Debug, Clone)]
+pub struct FailedOp {
+    pub op: PendingOp,
+    pub failed_at: String,
+}
+
+/// Move ops queued more than `days` ago to `failed_ops`; returns them
+pub fn expire_pending_ops(conn: &Connection, repo: &str, days: u32) -> Result<Vec<PendingOp>> {
+    let tx = conn.unchecked_transaction()?;
+    let expired = pending_ops_older_than(&tx, repo, days)?;
+    for op in &expired {
+        tx.execute(
+            "INSERT INTO failed_ops (repo, op_type, payload, created_at, last_error, dedup_key, failed_at)
+             VALUES (?, ?, isq_encrypt(?), ?, ?, ?, datetime('now'))",
+            params![op.repo, op.op_type, op.payload, op.created_at, op.last_error, op.dedup_key],
+        )?;
+        tx.execute("DELETE FROM pending_ops WHERE id = ?", params![op.id])?;
+    }
+    tx.commit()?;
+    Ok(expired)
+}
+
+/// Failed ops for a repo, oldest first
+pub fn load_failed_ops(conn: &Connection, repo: &str) -> Result<Vec<FailedOp>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}, failed_at FROM failed_ops WHERE repo = ? ORDER BY id ASC",
+        PENDING_OP_COLUMNS
+    ))?;
+    let ops = stmt
+        .query_map(params![repo], |row| Ok(FailedOp { op: pending_op_from_row(row)?, failed_at: row.get(7)? }))?
+        .collect::<Result<Vec<_>, _>>()?;
     Ok(ops)
 }
 
+/// Queue a repo's failed ops again, as if just queued; returns how many
+pub fn retry_failed_ops(conn: &Connection, repo: &str) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT INTO pending_ops (repo, op_type, payload, created_at, dedup_key)
+         SELECT repo, op_type, payload, datetime('now'), dedup_key FROM failed_ops WHERE repo = ? ORDER BY id",
+        params![repo],
+    )?;
+    let moved = tx.execute("DELETE FROM failed_ops WHERE repo = ?", params![repo])?;
+    tx.commit()?;
+    Ok(moved)
+}
+
+/// Pending ops grouped by the issue they touch (creates and goal ops are left out)
+pub fn pending_ops_by_issue(conn: &Connection, repo: &str) -> Result<HashMap<u64, Vec<PendingOp>>> {
+    let mut by_issue: HashMap<u64, Vec<PendingOp>> = HashMap::new();
+    for op in load_pending_ops(conn, repo)? {
+        if let Some(number) = op.issue_number() {
+            by_issue.entry(number).or_default().push(op);
+        }
+    }
+    Ok(by_issue)
+}
+
 /// Delete a pending operation after successful sync
 pub fn complete_op(conn: &Connection, id: i64) -> Result<()> {
-    conn.execute("DELETE FROM pending_ops WHERE id = ?", params![id])?;
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT OR IGNORE INTO replayed_ops (dedup_key, repo, replayed_at)
+         SELECT dedup_key, repo, datetime('now') FROM pending_ops WHERE id = ?",
+        params![id],
+    )?;
+    tx.execute("DELETE FROM pending_ops WHERE id = ?", params![id])?;
+    // Keys only need to outlive any copy of the op (e.g. a restored backup)
+    tx.execute("DELETE FROM replayed_ops WHERE replayed_at < datetime('now', '-90 days')", [])?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Whether an op with this key was already replayed (or discarded)
+pub fn is_replayed(conn: &Connection, dedup_key: &str) -> Result<bool> {
+    let found: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM replayed_ops WHERE dedup_key = ?)",
+        params![dedup_key],
+        |row| row.get(0),
+    )?;
+    Ok(found)
+}
+
+/// Re-apply the local effect of writes still queued for a repo, so a sync
+/// that read the forge before they were replayed doesn't undo them in the
+/// cache. Creates and comments have no cached row to fix up yet. The forge's
+/// `updated_at` is kept, since the forge hasn't seen these writes.
+fn reapply_pending_ops(conn: &Connection, repo: &str) -> Result<()> {
+    for op in load_pending_ops(conn, repo)? {
+        let payload = op.payload_json();
+        let Some(number) = op.issue_number() else {
+            continue;
+        };
+        let updated_at: Option<String> = conn
+            .query_row(
+                "SELECT updated_at FROM issues WHERE repo = ? AND number = ?",
+                params![repo, number as i64],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(updated_at) = updated_at else {
+            continue;
+        };
+        let text = |key: &str| payload[key].as_str().unwrap_or_default().to_string();
+        match op.op_type.as_str() {
+            "close" => set_issue_state(conn, repo, number, "closed")?,
+            "reopen" => set_issue_state(conn, repo, number, "open")?,
+            "edit_body" => set_issue_body(conn, repo, number, &text("body"))?,
+            "label_add" => add_issue_label(conn, repo, number, &text("label"))?,
+            "label_remove" => remove_issue_label(conn, repo, number, &text("label"))?,
+            "assign" => {
+                let exclusive = payload["exclusive"].as_bool().unwrap_or(false);
+                add_issue_assignee(conn, repo, number, &text("assignee"), exclusive)?
+            }
+            _ => continue,
+        };
+        conn.execute(
+            "UPDATE issues SET updated_at = ? WHERE repo = ? AND number = ?",
+            params![updated_at, repo, number as i64],
+        )?;
+    }
     Ok(())
 }
 
@@ -398,12 +1743,23 @@ pub fn count_pending_ops(conn: &Connection, repo: &str) -> Result<i64> {
     Ok(count)
 }
 
+/// Pending op counts per forge repo (repos with none are left out)
+pub fn pending_op_counts(conn: &Connection) -> Result<Vec<(String, i64)>> {
+    let mut stmt = conn.prepare("SELECT repo, COUNT(*) FROM pending_ops GROUP BY repo ORDER BY repo")?;
+    let counts = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(counts)
+}
+
 // === Watched Repos ===
 
 /// A repo being watched by the daemon
 #[derive(Debug, Clone)]
 pub struct WatchedRepo {
     pub repo: String,
+    /// Sync suspended for this repo (`isq daemon watch --paused`)
+    pub paused: bool,
 }
 
 /// Add a repo to the watch list (or update if exists)
@@ -433,13 +1789,14 @@ pub fn touch_repo(conn: &Connection, repo: &str) -> Result<()> {
 /// List all watched repos ordered by last_accessed (most recent first)
 pub fn list_watched_repos(conn: &Connection) -> Result<Vec<WatchedRepo>> {
     let mut stmt = conn.prepare(
-        "SELECT repo, last_accessed, added_at FROM watched_repos ORDER BY last_accessed DESC",
+        "SELECT repo, last_accessed, added_at, paused FROM watched_repos ORDER BY last_accessed DESC",
     )?;
 
     let repos = stmt
         .query_map([], |row| {
             Ok(WatchedRepo {
                 repo: row.get(0)?,
+                paused: row.get(3)?,
             })
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -453,661 +1810,3733 @@ pub fn remove_watched_repo(conn: &Connection, repo: &str) -> Result<()> {
     Ok(())
 }
 
-/// Clean up stale entries - removes watched_repos and repo_links for paths that no longer exist
-pub fn cleanup_stale_repos(conn: &Connection) -> Result<usize> {
+/// Suspend or resume daemon sync for a single repo (adds it to the watch list if needed)
+pub fn set_repo_paused(conn: &Connection, repo: &str, paused: bool) -> Result<()> {
+    add_watched_repo(conn, repo)?;
+    conn.execute(
+        "UPDATE watched_repos SET paused = ? WHERE repo = ?",
+        params![paused, repo],
+    )?;
+    Ok(())
+}
+
+/// Pending ops that would be stranded if the link at `repo_path` went away.
+/// Empty when another link to the same forge repo will still replay them.
+pub fn ops_at_risk(conn: &Connection, repo_path: &str) -> Result<Vec<PendingOp>> {
+    let Some(link) = get_repo_link(conn, repo_path)? else {
+        return Ok(Vec::new());
+    };
+
+    let shared: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM repo_links WHERE forge_repo = ? AND repo_path != ?",
+        params![link.forge_repo, repo_path],
+        |row| row.get(0),
+    )?;
+    if shared > 0 {
+        return Ok(Vec::new());
+    }
+
+    load_pending_ops(conn, &link.forge_repo)
+}
+
+/// Outcome of `cleanup_stale_repos`
+#[derive(Debug, Default)]
+pub struct CleanupResult {
+    pub removed: usize,
+    /// Missing checkouts kept because of unsynced ops: (path, pending op count)
+    pub kept: Vec<(String, usize)>,
+}
+
+/// Whether a linked path no longer points at a checkout (moved or deleted)
+pub fn is_missing_checkout(repo_path: &str) -> bool {
+    !is_remote_repo_path(repo_path) && !Path::new(repo_path).is_dir()
+}
+
+/// Clean up stale entries - removes watched_repos and repo_links for paths that no longer exist.
+/// Links with pending ops are kept so queued writes survive a moved checkout
+/// (rebind them with `isq link --repair`).
+pub fn cleanup_stale_repos(conn: &Connection) -> Result<CleanupResult> {
     let watched = list_watched_repos(conn)?;
-    let mut removed = 0;
+    let mut result = CleanupResult::default();
 
     for repo in watched {
-        let path = std::path::Path::new(&repo.repo);
-        // Remove if path doesn't exist or isn't a directory (valid git repo path)
-        if !path.exists() || !path.is_dir() {
+        if is_missing_checkout(&repo.repo) {
+            let at_risk = ops_at_risk(conn, &repo.repo)?;
+            if !at_risk.is_empty() {
+                result.kept.push((repo.repo.clone(), at_risk.len()));
+                continue;
+            }
+
             conn.execute("DELETE FROM watched_repos WHERE repo = ?", params![repo.repo])?;
             conn.execute("DELETE FROM repo_links WHERE repo_path = ?", params![repo.repo])?;
-            removed += 1;
+            result.removed += 1;
         }
     }
 
-    Ok(removed)
+    Ok(result)
 }
 
-// === Repo Links ===
+// === Daemon Pause ===
 
-/// A link between a local git repo and its issue tracker (forge)
+/// Global daemon pause, persisted so it survives daemon restarts
 #[derive(Debug, Clone)]
-pub struct RepoLink {
-    pub forge_type: String,
-    pub forge_repo: String,
-    pub display_name: Option<String>,
+pub struct DaemonPause {
+    /// Unix timestamp when the pause ends (None = until `isq daemon resume`)
+    pub until: Option<i64>,
 }
 
-/// Get the link for a repo path
-pub fn get_repo_link(conn: &Connection, repo_path: &str) -> Result<Option<RepoLink>> {
-    let mut stmt = conn.prepare(
-        "SELECT repo_path, forge_type, forge_repo, display_name, created_at FROM repo_links WHERE repo_path = ?",
+impl DaemonPause {
+    /// Whether the pause is still in effect at `now` (unix seconds)
+    pub fn is_active(&self, now: i64) -> bool {
+        self.until.is_none_or(|until| now < until)
+    }
+}
+
+/// Pause the daemon, optionally until a unix timestamp
+pub fn set_daemon_pause(conn: &Connection, until: Option<i64>) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO daemon_pause (id, paused_until, paused_at)
+         VALUES (1, ?, datetime('now'))",
+        params![until],
     )?;
+    Ok(())
+}
 
-    let mut rows = stmt.query(params![repo_path])?;
+/// Resume the daemon
+pub fn clear_daemon_pause(conn: &Connection) -> Result<()> {
+    conn.execute("DELETE FROM daemon_pause", [])?;
+    Ok(())
+}
+
+/// Get the active daemon pause, if any (expired pauses are ignored)
+pub fn get_daemon_pause(conn: &Connection, now: i64) -> Result<Option<DaemonPause>> {
+    let mut stmt = conn.prepare("SELECT paused_until FROM daemon_pause WHERE id = 1")?;
+    let mut rows = stmt.query([])?;
 
     if let Some(row) = rows.next()? {
-        Ok(Some(RepoLink {
-            forge_type: row.get(1)?,
-            forge_repo: row.get(2)?,
-            display_name: row.get(3)?,
-        }))
+        let pause = DaemonPause { until: row.get(0)? };
+        Ok(pause.is_active(now).then_some(pause))
     } else {
         Ok(None)
     }
 }
 
-/// Link a repo to a forge (insert or update)
-pub fn set_repo_link(
-    conn: &Connection,
-    repo_path: &str,
-    forge_type: &str,
-    forge_repo: &str,
-    display_name: Option<&str>,
-) -> Result<()> {
+/// Ask the daemon to sync a repo now instead of at its next cycle
+pub fn request_sync(conn: &Connection, repo_path: &str) -> Result<()> {
     conn.execute(
-        "INSERT INTO repo_links (repo_path, forge_type, forge_repo, display_name, created_at)
-         VALUES (?, ?, ?, ?, datetime('now'))
-         ON CONFLICT(repo_path) DO UPDATE SET forge_type = ?, forge_repo = ?, display_name = ?",
-        params![repo_path, forge_type, forge_repo, display_name, forge_type, forge_repo, display_name],
+        "INSERT OR REPLACE INTO sync_requests (repo_path, requested_at) VALUES (?, strftime('%s', 'now'))",
+        params![repo_path],
     )?;
     Ok(())
 }
 
-/// Remove the link for a repo
-pub fn remove_repo_link(conn: &Connection, repo_path: &str) -> Result<()> {
-    conn.execute("DELETE FROM repo_links WHERE repo_path = ?", params![repo_path])?;
+/// Whether any sync has been requested since the daemon's last cycle
+pub fn has_sync_requests(conn: &Connection) -> Result<bool> {
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM sync_requests", [], |row| row.get(0))?;
+    Ok(count > 0)
+}
+
+/// Mark requested syncs as handled (the daemon is starting a cycle)
+pub fn clear_sync_requests(conn: &Connection) -> Result<()> {
+    conn.execute("DELETE FROM sync_requests", [])?;
     Ok(())
 }
 
-// ============================================================================
-// Comments
-// ============================================================================
+// === Repo Links ===
 
-/// A comment on an issue
+/// A link between a local git repo and its issue tracker (forge)
 #[derive(Debug, Clone)]
-pub struct Comment {
-    pub comment_id: String,
-    pub issue_number: u64,
-    pub body: String,
-    pub author: String,
-    pub created_at: String,
+pub struct RepoLink {
+    pub forge_type: String,
+    pub forge_repo: String,
+    pub display_name: Option<String>,
+    /// `origin` URL when linked, for recognizing the repo after a move
+    pub remote_url: Option<String>,
+    /// Root commit hash when linked, for recognizing the repo after a move
+    pub root_commit: Option<String>,
+    /// Named credential used for this link (`--as work`); None for the default one
+    pub account: Option<String>,
 }
 
-/// Save comments for a repo (replaces all existing comments)
-pub fn save_comments(conn: &Connection, forge_repo: &str, comments: &[Comment]) -> Result<()> {
-    let tx = conn.unchecked_transaction()?;
+impl RepoLink {
+    /// Key the link's forge identity is stored under (see `forges::account_key`)
+    pub fn identity_key(&self) -> String {
+        crate::forges::account_key(&self.forge_type, self.account.as_deref())
+    }
+}
 
-    // Delete existing comments for this repo
-    tx.execute("DELETE FROM comments WHERE forge_repo = ?", params![forge_repo])?;
+const REPO_LINK_COLUMNS: &str = "repo_path, forge_type, forge_repo, display_name, remote_url, root_commit, account";
 
-    // Insert new comments
-    let mut stmt = tx.prepare(
-        "INSERT INTO comments (forge_repo, issue_number, comment_id, body, author, created_at)
-         VALUES (?, ?, ?, ?, ?, ?)",
-    )?;
+fn repo_link_from_row(row: &rusqlite::Row) -> rusqlite::Result<(String, RepoLink)> {
+    Ok((
+        row.get(0)?,
+        RepoLink {
+            forge_type: row.get(1)?,
+            forge_repo: row.get(2)?,
+            display_name: row.get(3)?,
+            remote_url: row.get(4)?,
+            root_commit: row.get(5)?,
+            account: row.get(6)?,
+        },
+    ))
+}
 
-    for comment in comments {
-        stmt.execute(params![
-            forge_repo,
-            comment.issue_number as i64,
-            comment.comment_id,
-            comment.body,
-            comment.author,
-            comment.created_at,
-        ])?;
+/// Get the link for a repo path
+pub fn get_repo_link(conn: &Connection, repo_path: &str) -> Result<Option<RepoLink>> {
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM repo_links WHERE repo_path = ?", REPO_LINK_COLUMNS))?;
+
+    let mut rows = stmt.query(params![repo_path])?;
+
+    if let Some(row) = rows.next()? {
+        Ok(Some(repo_link_from_row(row)?.1))
+    } else {
+        Ok(None)
     }
+}
 
-    drop(stmt);
-    tx.commit()?;
-    Ok(())
+/// Key for a repo linked without a local checkout (e.g. "github:owner/repo").
+/// Used in place of a path in repo_links and watched_repos.
+pub fn remote_repo_path(forge_type: &str, forge_repo: &str) -> String {
+    format!("{}:{}", forge_type, forge_repo)
 }
 
-/// Load comments for a specific issue
-pub fn load_comments(conn: &Connection, forge_repo: &str, issue_number: u64) -> Result<Vec<Comment>> {
-    let mut stmt = conn.prepare(
-        "SELECT comment_id, issue_number, body, author, created_at
-         FROM comments WHERE forge_repo = ? AND issue_number = ?
-         ORDER BY created_at ASC",
-    )?;
+/// Whether a repo key is a remote-only link rather than a local path
+pub fn is_remote_repo_path(repo_path: &str) -> bool {
+    crate::forges::ALL_FORGE_TYPES
+        .iter()
+        .any(|f| repo_path.starts_with(&format!("{}:", f.as_str())))
+}
 
-    let comments = stmt
-        .query_map(params![forge_repo, issue_number as i64], |row| {
-            let num: i64 = row.get(1)?;
-            Ok(Comment {
-                comment_id: row.get(0)?,
-                issue_number: num as u64,
-                body: row.get(2)?,
-                author: row.get(3)?,
-                created_at: row.get(4)?,
-            })
-        })?
+/// All linked repos as (repo_path, link), ordered by path
+pub fn list_repo_links(conn: &Connection) -> Result<Vec<(String, RepoLink)>> {
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM repo_links ORDER BY repo_path", REPO_LINK_COLUMNS))?;
+
+    let links = stmt
+        .query_map([], repo_link_from_row)?
         .collect::<Result<Vec<_>, _>>()?;
 
-    Ok(comments)
+    Ok(links)
 }
 
-/// Count comments for each issue in a repo (returns map of issue_number -> count)
-pub fn count_comments_by_issue(conn: &Connection, forge_repo: &str) -> Result<std::collections::HashMap<u64, usize>> {
+/// Record how to recognize a linked checkout if it moves
+pub fn set_repo_fingerprint(conn: &Connection, repo_path: &str, fingerprint: &RepoFingerprint) -> Result<()> {
+    conn.execute(
+        "UPDATE repo_links SET remote_url = ?, root_commit = ? WHERE repo_path = ?",
+        params![fingerprint.remote_url, fingerprint.root_commit, repo_path],
+    )?;
+    Ok(())
+}
+
+/// Links whose checkout is gone and that look like the repo with `fingerprint`
+pub fn find_orphaned_links(conn: &Connection, fingerprint: &RepoFingerprint) -> Result<Vec<(String, RepoLink)>> {
+    Ok(list_repo_links(conn)?
+        .into_iter()
+        .filter(|(path, link)| {
+            is_missing_checkout(path)
+                && fingerprint.matches(link.remote_url.as_deref(), link.root_commit.as_deref())
+        })
+        .collect())
+}
+
+/// Move a link (and its watch entry) from an old path to a new one
+pub fn rebind_repo_link(conn: &Connection, old_path: &str, new_path: &str) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("UPDATE repo_links SET repo_path = ? WHERE repo_path = ?", params![new_path, old_path])?;
+    tx.execute("UPDATE watched_repos SET repo = ? WHERE repo = ?", params![new_path, old_path])?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Find the local path of a linked repo by forge repo or display name
+pub fn find_repo_path(conn: &Connection, name: &str) -> Result<Option<String>> {
     let mut stmt = conn.prepare(
-        "SELECT issue_number, COUNT(*) FROM comments WHERE forge_repo = ? GROUP BY issue_number",
+        "SELECT repo_path FROM repo_links
+         WHERE forge_repo = ?1 COLLATE NOCASE OR display_name = ?1 COLLATE NOCASE
+         LIMIT 1",
     )?;
 
-    let mut counts = std::collections::HashMap::new();
-    let rows = stmt.query_map(params![forge_repo], |row| {
-        let num: i64 = row.get(0)?;
-        let count: i64 = row.get(1)?;
-        Ok((num as u64, count as usize))
-    })?;
+    let mut rows = stmt.query(params![name])?;
 
-    for row in rows {
-        let (num, count) = row?;
-        counts.insert(num, count);
+    if let Some(row) = rows.next()? {
+        Ok(Some(row.get(0)?))
+    } else {
+        Ok(None)
     }
-
-    Ok(counts)
 }
 
-// ============================================================================
-// Goals
-// ============================================================================
+/// Link a repo to a forge (insert or update)
+pub fn set_repo_link(
+    conn: &Connection,
+    repo_path: &str,
+    forge_type: &str,
+    forge_repo: &str,
+    display_name: Option<&str>,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO repo_links (repo_path, forge_type, forge_repo, display_name, created_at)
+         VALUES (?, ?, ?, ?, datetime('now'))
+         ON CONFLICT(repo_path) DO UPDATE SET forge_type = ?, forge_repo = ?, display_name = ?",
+        params![repo_path, forge_type, forge_repo, display_name, forge_type, forge_repo, display_name],
+    )?;
+    Ok(())
+}
 
-/// Save goals for a repo (replaces all existing goals)
-pub fn save_goals(conn: &Connection, forge_repo: &str, goals: &[Goal]) -> Result<()> {
-    let tx = conn.unchecked_transaction()?;
+/// Record which named credential a link uses (None for the default one)
+pub fn set_repo_account(conn: &Connection, repo_path: &str, account: Option<&str>) -> Result<()> {
+    conn.execute("UPDATE repo_links SET account = ? WHERE repo_path = ?", params![account, repo_path])?;
+    Ok(())
+}
 
-    // Delete existing goals for this repo
-    tx.execute("DELETE FROM goals WHERE forge_repo = ?", params![forge_repo])?;
+/// Remove the link for a repo
+pub fn remove_repo_link(conn: &Connection, repo_path: &str) -> Result<()> {
+    conn.execute("DELETE FROM repo_links WHERE repo_path = ?", params![repo_path])?;
+    Ok(())
+}
 
-    // Insert new goals
-    let mut stmt = tx.prepare(
-        "INSERT INTO goals (forge_repo, goal_id, name, description, target_date, state, progress, open_count, closed_count, created_at, updated_at, html_url)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+/// Whether any link still points at a forge repo
+pub fn is_forge_repo_linked(conn: &Connection, forge_repo: &str) -> Result<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM repo_links WHERE forge_repo = ?",
+        params![forge_repo],
+        |row| row.get(0),
     )?;
+    Ok(count > 0)
+}
 
-    for goal in goals {
-        stmt.execute(params![
-            forge_repo,
-            goal.id,
-            goal.name,
-            goal.description,
-            goal.target_date,
-            goal.state.as_str(),
-            goal.progress,
-            goal.open_count.map(|c| c as i64),
-            goal.closed_count.map(|c| c as i64),
-            goal.created_at,
-            goal.updated_at,
-            goal.html_url,
-        ])?;
+// === Cache Scope ===
+
+/// Rows cached for one forge repo
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheSize {
+    pub issues: i64,
+    pub comments: i64,
+    pub goals: i64,
+}
+
+impl CacheSize {
+    pub fn is_empty(&self) -> bool {
+        self.issues == 0 && self.comments == 0 && self.goals == 0
     }
+}
 
-    drop(stmt);
-    tx.commit()?;
-    Ok(())
+/// Count cached rows for a forge repo
+pub fn cache_size(conn: &Connection, forge_repo: &str) -> Result<CacheSize> {
+    let count = |sql: &str| -> Result<i64> { Ok(conn.query_row(sql, params![forge_repo], |row| row.get(0))?) };
+    Ok(CacheSize {
+        issues: count("SELECT COUNT(*) FROM issues WHERE repo = ?")?,
+        comments: count("SELECT COUNT(*) FROM comments WHERE forge_repo = ?")?,
+        goals: count("SELECT COUNT(*) FROM goals WHERE forge_repo = ?")?,
+    })
 }
 
-/// Save a single goal (insert or update)
-pub fn save_goal(conn: &Connection, forge_repo: &str, goal: &Goal) -> Result<()> {
-    conn.execute(
-        "INSERT INTO goals (forge_repo, goal_id, name, description, target_date, state, progress, open_count, closed_count, created_at, updated_at, html_url)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-         ON CONFLICT(forge_repo, goal_id) DO UPDATE SET
-            name = excluded.name,
-            description = excluded.description,
-            target_date = excluded.target_date,
-            state = excluded.state,
-            progress = excluded.progress,
-            open_count = excluded.open_count,
-            closed_count = excluded.closed_count,
-            updated_at = excluded.updated_at,
-            html_url = excluded.html_url",
-        params![
-            forge_repo,
-            goal.id,
-            goal.name,
-            goal.description,
-            goal.target_date,
-            goal.state.as_str(),
-            goal.progress,
-            goal.open_count.map(|c| c as i64),
-            goal.closed_count.map(|c| c as i64),
-            goal.created_at,
-            goal.updated_at,
-            goal.html_url,
-        ],
-    )?;
-    Ok(())
+/// An issue named in a `SyncDiff`
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangedIssue {
+    pub number: u64,
+    pub id: String,
+    pub title: String,
 }
 
-/// Load all goals for a repo from cache
-pub fn load_goals(conn: &Connection, forge_repo: &str, state: Option<&str>) -> Result<Vec<Goal>> {
-    let mut sql = String::from(
-        "SELECT goal_id, name, description, target_date, state, progress, open_count, closed_count, created_at, updated_at, html_url
-         FROM goals WHERE forge_repo = ?",
-    );
+impl ChangedIssue {
+    fn new(issue: &Issue) -> Self {
+        ChangedIssue { number: issue.number, id: issue.display_id(), title: issue.title.clone() }
+    }
+}
 
-    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(forge_repo.to_string())];
+/// What saving freshly fetched data would change in the cache (`isq sync --dry-run`)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncDiff {
+    pub new_issues: Vec<ChangedIssue>,
+    /// Changed upstream, other than being closed or reopened
+    pub updated: Vec<ChangedIssue>,
+    pub closed: Vec<ChangedIssue>,
+    pub reopened: Vec<ChangedIssue>,
+    /// Cached but no longer returned by the forge
+    pub removed: Vec<ChangedIssue>,
+    pub new_comments: usize,
+    pub new_goals: Vec<String>,
+}
 
-    if let Some(s) = state {
-        sql.push_str(" AND state = ?");
-        params_vec.push(Box::new(s.to_string()));
+impl SyncDiff {
+    pub fn is_empty(&self) -> bool {
+        self.new_issues.is_empty()
+            && self.updated.is_empty()
+            && self.closed.is_empty()
+            && self.reopened.is_empty()
+            && self.removed.is_empty()
+            && self.new_comments == 0
+            && self.new_goals.is_empty()
     }
+}
 
-    sql.push_str(" ORDER BY target_date ASC NULLS LAST, name ASC");
+/// Compare fetched issues, comments and goals with the cache, writing nothing
+pub fn diff_sync(
+    conn: &Connection,
+    forge_repo: &str,
+    issues: &[Issue],
+    comments: &[Comment],
+    goals: &[Goal],
+) -> Result<SyncDiff> {
+    let cached: HashMap<u64, Issue> = load_issues(conn, forge_repo)?.into_iter().map(|i| (i.number, i)).collect();
+    let fetched: HashSet<u64> = issues.iter().map(|i| i.number).collect();
+    let mut diff = SyncDiff::default();
 
-    let mut stmt = conn.prepare(&sql)?;
-    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+    for issue in issues {
+        let Some(old) = cached.get(&issue.number) else {
+            diff.new_issues.push(ChangedIssue::new(issue));
+            continue;
+        };
+        if old.state == "open" && issue.state != "open" {
+            diff.closed.push(ChangedIssue::new(issue));
+        } else if old.state != "open" && issue.state == "open" {
+            diff.reopened.push(ChangedIssue::new(issue));
+        } else if old.updated_at != issue.updated_at {
+            diff.updated.push(ChangedIssue::new(issue));
+        }
+    }
+    let mut removed: Vec<&Issue> = cached.values().filter(|i| !fetched.contains(&i.number)).collect();
+    removed.sort_by_key(|i| i.number);
+    diff.removed = removed.into_iter().map(ChangedIssue::new).collect();
 
-    let goals = stmt
-        .query_map(params_refs.as_slice(), |row| {
-            let state_str: String = row.get(4)?;
-            let progress: f64 = row.get::<_, Option<f64>>(5)?.unwrap_or(0.0);
-            let open: Option<i64> = row.get(6)?;
-            let closed: Option<i64> = row.get(7)?;
+    let cached_comments: HashSet<String> = load_all_comments(conn, forge_repo)?
+        .into_values()
+        .flatten()
+        .map(|c| c.comment_id)
+        .collect();
+    diff.new_comments = comments.iter().filter(|c| !cached_comments.contains(&c.comment_id)).count();
 
-            Ok(Goal {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                description: row.get(2)?,
-                target_date: row.get(3)?,
-                state: GoalState::from_str(&state_str),
-                progress,
-                open_count: open.map(|c| c as u64),
-                closed_count: closed.map(|c| c as u64),
-                created_at: row.get(8)?,
-                updated_at: row.get(9)?,
-                html_url: row.get(10)?,
-            })
-        })?
-        .collect::<Result<Vec<_>, _>>()?;
+    let cached_goals: HashSet<String> = load_goals(conn, forge_repo, None)?.into_iter().map(|g| g.id).collect();
+    diff.new_goals = goals.iter().filter(|g| !cached_goals.contains(&g.id)).map(|g| g.name.clone()).collect();
 
-    Ok(goals)
+    Ok(diff)
 }
 
-/// Load a single goal by name or ID
-pub fn load_goal_by_name(conn: &Connection, forge_repo: &str, name: &str) -> Result<Option<Goal>> {
-    let mut stmt = conn.prepare(
-        "SELECT goal_id, name, description, target_date, state, progress, open_count, closed_count, created_at, updated_at, html_url
-         FROM goals WHERE forge_repo = ? AND (name = ? OR goal_id = ?)",
-    )?;
+/// Delete everything cached for a forge repo (issues, comments, goals and their history, objectives, sync state, inbox)
+pub fn purge_repo_cache(conn: &Connection, forge_repo: &str) -> Result<CacheSize> {
+    let size = cache_size(conn, forge_repo)?;
 
-    let mut rows = stmt.query(params![forge_repo, name, name])?;
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM issues WHERE repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM comments WHERE forge_repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM goals WHERE forge_repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM objectives WHERE forge_repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM objective_goals WHERE forge_repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM goal_snapshots WHERE forge_repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM sync_state WHERE repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM inbox_events WHERE forge_repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM goal_reminders WHERE forge_repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM issue_references WHERE forge_repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM issue_tombstones WHERE repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM issue_labels WHERE repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM issue_subscriptions WHERE forge_repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM sync_cursors WHERE repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM workspace_issues WHERE workspace = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM repo_labels WHERE forge_repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM cycles WHERE forge_repo = ?", params![forge_repo])?;
+    tx.commit()?;
 
-    if let Some(row) = rows.next()? {
-        let state_str: String = row.get(4)?;
-        let progress: f64 = row.get::<_, Option<f64>>(5)?.unwrap_or(0.0);
-        let open: Option<i64> = row.get(6)?;
-        let closed: Option<i64> = row.get(7)?;
+    Ok(size)
+}
 
-        Ok(Some(Goal {
-            id: row.get(0)?,
-            name: row.get(1)?,
-            description: row.get(2)?,
-            target_date: row.get(3)?,
-            state: GoalState::from_str(&state_str),
-            progress,
-            open_count: open.map(|c| c as u64),
-            closed_count: closed.map(|c| c as u64),
-            created_at: row.get(8)?,
-            updated_at: row.get(9)?,
-            html_url: row.get(10)?,
-        }))
+// === Cycles ===
+
+/// Replace a repo's cached cycles with what the forge has
+pub fn save_cycles(conn: &Connection, forge_repo: &str, cycles: &[Cycle]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM cycles WHERE forge_repo = ?", params![forge_repo])?;
+    {
+        let mut insert = tx.prepare(
+            "INSERT OR REPLACE INTO cycles (forge_repo, number, name, starts_at, ends_at) VALUES (?, ?, ?, ?, ?)",
+        )?;
+        for cycle in cycles {
+            insert.execute(params![forge_repo, cycle.number, cycle.name, cycle.starts_at, cycle.ends_at])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// A repo's cached cycles, oldest first
+pub fn load_cycles(conn: &Connection, forge_repo: &str) -> Result<Vec<Cycle>> {
+    let mut stmt =
+        conn.prepare("SELECT number, name, starts_at, ends_at FROM cycles WHERE forge_repo = ? ORDER BY number")?;
+    let cycles = stmt
+        .query_map(params![forge_repo], |row| {
+            Ok(Cycle { number: row.get(0)?, name: row.get(1)?, starts_at: row.get(2)?, ends_at: row.get(3)? })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(cycles)
+}
+
+// === Label catalog ===
+
+/// Replace a repo's cached label definitions with what the forge has
+pub fn save_labels(conn: &Connection, forge_repo: &str, labels: &[LabelDef]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM repo_labels WHERE forge_repo = ?", params![forge_repo])?;
+    {
+        let mut insert = tx.prepare(
+            "INSERT OR REPLACE INTO repo_labels (forge_repo, name, color, description) VALUES (?, ?, ?, ?)",
+        )?;
+        for label in labels {
+            insert.execute(params![forge_repo, label.name, label.color, label.description])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// A repo's cached label definitions, by name
+pub fn load_labels(conn: &Connection, forge_repo: &str) -> Result<Vec<LabelDef>> {
+    let mut stmt = conn.prepare("SELECT name, color, description FROM repo_labels WHERE forge_repo = ? ORDER BY name")?;
+    let labels = stmt
+        .query_map(params![forge_repo], |row| {
+            Ok(LabelDef { name: row.get(0)?, color: row.get(1)?, description: row.get(2)? })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(labels)
+}
+
+/// Open issues per label (lowercased names)
+pub fn count_open_issues_by_label(conn: &Connection, forge_repo: &str) -> Result<HashMap<String, usize>> {
+    let mut stmt = conn.prepare(
+        "SELECT LOWER(l.label), COUNT(*) FROM issue_labels l
+         JOIN issues i ON i.repo = l.repo AND i.number = l.issue_number
+         WHERE l.repo = ? AND i.state = 'open' GROUP BY LOWER(l.label)",
+    )?;
+    let counts = stmt
+        .query_map(params![forge_repo], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as usize)))?
+        .collect::<Result<_, _>>()?;
+    Ok(counts)
+}
+
+// === Search workspaces ===
+
+/// Record what a search workspace's query found (see workspace.rs). Each
+/// issue is cached under its own repo, leaving that repo's other issues
+/// alone; the workspace remembers which ones it found.
+pub fn save_workspace_issues(conn: &Connection, workspace: &str, hits: &[(String, Issue)]) -> Result<()> {
+    let tx = Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+    tx.execute("DELETE FROM workspace_issues WHERE workspace = ?", params![workspace])?;
+    for (forge_repo, issue) in hits {
+        upsert_issue(&tx, forge_repo, issue)?;
+        refresh_comment_counts(&tx, forge_repo, Some(issue.number))?;
+        tx.execute(
+            "INSERT OR IGNORE INTO workspace_issues (workspace, forge_repo, number) VALUES (?, ?, ?)",
+            params![workspace, forge_repo, issue.number as i64],
+        )?;
+    }
+    tx.execute(
+        "INSERT OR REPLACE INTO sync_state (repo, last_sync, issue_count)
+         VALUES (?, datetime('now'), ?)",
+        params![workspace, hits.len() as i64],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// The issues a search workspace last found, with their repos, newest first
+pub fn load_workspace_issues(conn: &Connection, workspace: &str) -> Result<Vec<(String, Issue)>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}, repo FROM issues
+         WHERE (repo, number) IN (SELECT forge_repo, number FROM workspace_issues WHERE workspace = ?)
+         ORDER BY updated_at DESC",
+        ISSUE_COLUMNS
+    ))?;
+    let hits = stmt
+        .query_map(params![workspace], |row| Ok((row.get("repo")?, issue_from_row(row)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(hits)
+}
+
+// === Identities ===
+
+/// Remember the authenticated user's login for a forge (for "assigned to me")
+pub fn set_identity(conn: &Connection, forge_type: &str, login: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO identities (forge_type, login, updated_at) VALUES (?, ?, datetime('now'))
+         ON CONFLICT(forge_type) DO UPDATE SET login = excluded.login, updated_at = excluded.updated_at",
+        params![forge_type, login],
+    )?;
+    Ok(())
+}
+
+/// Get the cached login for a forge
+pub fn get_identity(conn: &Connection, forge_type: &str) -> Result<Option<String>> {
+    let mut stmt = conn.prepare("SELECT login FROM identities WHERE forge_type = ?")?;
+    let mut rows = stmt.query(params![forge_type])?;
+
+    if let Some(row) = rows.next()? {
+        Ok(Some(row.get(0)?))
     } else {
         Ok(None)
     }
 }
 
-/// Count goals for a repo
-pub fn count_goals(conn: &Connection, forge_repo: &str) -> Result<i64> {
-    let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM goals WHERE forge_repo = ?",
-        params![forge_repo],
-        |row| row.get(0),
+/// Cache the result of `isq whoami` for a forge account (see `forges::account_key`)
+pub fn save_whoami(conn: &Connection, identity_key: &str, info: &WhoAmI) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO whoami (identity_key, info) VALUES (?, ?)",
+        params![identity_key, serde_json::to_string(info)?],
     )?;
-    Ok(count)
+    Ok(())
 }
 
-// ============================================================================
-// Rate Limit State
-// ============================================================================
+/// The cached `isq whoami` result for a forge account
+pub fn load_whoami(conn: &Connection, identity_key: &str) -> Result<Option<WhoAmI>> {
+    let mut stmt = conn.prepare("SELECT info FROM whoami WHERE identity_key = ?")?;
+    let mut rows = stmt.query(params![identity_key])?;
 
-/// Rate limit state for a forge
-#[derive(Debug, Clone)]
-pub struct RateLimitState {
-    /// Total requests allowed per hour
-    pub limit: Option<u32>,
-    /// Requests remaining this hour
-    pub remaining: Option<u32>,
-    /// Unix timestamp when the limit resets
-    pub reset_at: Option<i64>,
-    pub last_error: Option<String>,
+    match rows.next()? {
+        Some(row) => Ok(serde_json::from_str(&row.get::<_, String>(0)?).ok()),
+        None => Ok(None),
+    }
 }
 
-impl RateLimitState {
-    /// Calculate how many requests have been used this hour
-    pub fn used(&self) -> Option<u32> {
-        match (self.limit, self.remaining) {
-            (Some(limit), Some(remaining)) => Some(limit.saturating_sub(remaining)),
-            _ => None,
-        }
+/// Drop the cached `isq whoami` result (e.g. after logging in again)
+pub fn forget_whoami(conn: &Connection, identity_key: &str) -> Result<()> {
+    conn.execute("DELETE FROM whoami WHERE identity_key = ?", params![identity_key])?;
+    Ok(())
+}
+
+// === Subscriptions ===
+
+/// Remember whether you're subscribed to an issue (set by `isq issue subscribe/unsubscribe`)
+pub fn set_subscription(conn: &Connection, forge_repo: &str, number: u64, subscribed: bool) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO issue_subscriptions (forge_repo, number, subscribed, updated_at)
+         VALUES (?, ?, ?, datetime('now'))",
+        params![forge_repo, number as i64, subscribed],
+    )?;
+    Ok(())
+}
+
+/// Whether you're subscribed to an issue, if isq has seen you change it
+pub fn get_subscription(conn: &Connection, forge_repo: &str, number: u64) -> Result<Option<bool>> {
+    let mut stmt = conn.prepare("SELECT subscribed FROM issue_subscriptions WHERE forge_repo = ? AND number = ?")?;
+    let mut rows = stmt.query(params![forge_repo, number as i64])?;
+
+    match rows.next()? {
+        Some(row) => Ok(Some(row.get(0)?)),
+        None => Ok(None),
     }
 }
 
-/// Get rate limit state for a forge
-pub fn get_rate_limit_state(conn: &Connection, forge: &str) -> Result<Option<RateLimitState>> {
-    let mut stmt = conn.prepare(
-        "SELECT forge, rate_limit, remaining, reset_at, last_error, updated_at FROM rate_limit_state WHERE forge = ?",
+// === Branch issues ===
+
+/// Tie a branch to the issue being worked on there (`isq issue current <id>`)
+pub fn set_branch_issue(conn: &Connection, forge_repo: &str, branch: &str, number: u64) -> Result<()> {
+    conn.execute(
+        "INSERT INTO branch_issues (forge_repo, branch, issue_number, updated_at) VALUES (?, ?, ?, datetime('now'))
+         ON CONFLICT(forge_repo, branch) DO UPDATE SET issue_number = excluded.issue_number, updated_at = excluded.updated_at",
+        params![forge_repo, branch, number as i64],
     )?;
+    Ok(())
+}
 
-    let mut rows = stmt.query(params![forge])?;
+/// The issue tied to a branch, if one was set
+pub fn get_branch_issue(conn: &Connection, forge_repo: &str, branch: &str) -> Result<Option<u64>> {
+    let mut stmt = conn.prepare("SELECT issue_number FROM branch_issues WHERE forge_repo = ? AND branch = ?")?;
+    let mut rows = stmt.query(params![forge_repo, branch])?;
 
     if let Some(row) = rows.next()? {
-        let limit: Option<i64> = row.get(1)?;
-        let remaining: Option<i64> = row.get(2)?;
-        Ok(Some(RateLimitState {
-            limit: limit.map(|v| v as u32),
-            remaining: remaining.map(|v| v as u32),
-            reset_at: row.get(3)?,
-            last_error: row.get(4)?,
-        }))
+        Ok(Some(row.get::<_, i64>(0)? as u64))
     } else {
         Ok(None)
     }
 }
 
-/// Set rate limit state for a forge (error case - only reset_at and last_error)
-pub fn set_rate_limit_state(
-    conn: &Connection,
-    forge: &str,
-    reset_at: Option<i64>,
-    last_error: Option<&str>,
-) -> Result<()> {
+/// Issues tied to a branch, with the branch (the latest, if several are)
+pub fn load_branch_issues(conn: &Connection, forge_repo: &str) -> Result<HashMap<u64, String>> {
+    let mut stmt = conn.prepare(
+        "SELECT issue_number, branch FROM branch_issues WHERE forge_repo = ? ORDER BY updated_at",
+    )?;
+    let branches = stmt
+        .query_map(params![forge_repo], |row| Ok((row.get::<_, i64>(0)? as u64, row.get(1)?)))?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+    Ok(branches)
+}
+
+/// Forget a branch's issue. Returns whether one was set.
+pub fn clear_branch_issue(conn: &Connection, forge_repo: &str, branch: &str) -> Result<bool> {
+    let deleted = conn.execute(
+        "DELETE FROM branch_issues WHERE forge_repo = ? AND branch = ?",
+        params![forge_repo, branch],
+    )?;
+    Ok(deleted > 0)
+}
+
+// === Rankings ===
+
+/// How `isq issue rank` moves an issue in the local ranking
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RankMove {
+    /// Leave a ranked issue where it is; add an unranked one at the bottom
+    Keep,
+    Up,
+    Down,
+    /// To this position, 1 being the top
+    To(usize),
+    Remove,
+}
+
+/// Ranked issue numbers, top first
+pub fn load_ranking(conn: &Connection, forge_repo: &str) -> Result<Vec<u64>> {
+    let mut stmt = conn.prepare("SELECT issue_number FROM rankings WHERE forge_repo = ? ORDER BY position")?;
+    let numbers = stmt
+        .query_map(params![forge_repo], |row| Ok(row.get::<_, i64>(0)? as u64))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(numbers)
+}
+
+/// Move an issue in the ranking, adding it at the bottom first if it isn't
+/// ranked yet. Returns its new position (1-based), or None once removed.
+pub fn rank_issue(conn: &Connection, forge_repo: &str, number: u64, mv: RankMove) -> Result<Option<usize>> {
+    let mut order = load_ranking(conn, forge_repo)?;
+    let from = match order.iter().position(|&n| n == number) {
+        Some(i) => {
+            order.remove(i);
+            i
+        }
+        None => order.len(),
+    };
+
+    let position = match mv {
+        RankMove::Remove => None,
+        RankMove::Keep => Some(from),
+        RankMove::Up => Some(from.saturating_sub(1)),
+        RankMove::Down => Some(from + 1),
+        RankMove::To(n) => Some(n.saturating_sub(1)),
+    }
+    .map(|i| i.min(order.len()));
+    if let Some(i) = position {
+        order.insert(i, number);
+    }
+
+    // Positions stay dense (1..=n), so rewrite them all
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM rankings WHERE forge_repo = ?", params![forge_repo])?;
+    {
+        let mut stmt = tx.prepare("INSERT INTO rankings (forge_repo, issue_number, position) VALUES (?, ?, ?)")?;
+        for (i, n) in order.iter().enumerate() {
+            stmt.execute(params![forge_repo, *n as i64, i as i64 + 1])?;
+        }
+    }
+    tx.commit()?;
+
+    Ok(position.map(|i| i + 1))
+}
+
+// === Inbox ===
+
+/// Record an inbox event for a repo
+pub fn add_inbox_event(conn: &Connection, forge_repo: &str, kind: &str, message: &str) -> Result<i64> {
     conn.execute(
-        "INSERT INTO rate_limit_state (forge, reset_at, last_error, updated_at)
-         VALUES (?, ?, ?, datetime('now'))
-         ON CONFLICT(forge) DO UPDATE SET
-            reset_at = excluded.reset_at,
-            last_error = excluded.last_error,
-            updated_at = excluded.updated_at",
-        params![forge, reset_at, last_error],
+        "INSERT INTO inbox_events (forge_repo, kind, message, created_at) VALUES (?, ?, ?, datetime('now'))",
+        params![forge_repo, kind, message],
     )?;
-    Ok(())
+    Ok(conn.last_insert_rowid())
 }
 
-/// Update rate limit budget for a forge (called after successful API calls)
-pub fn update_rate_limit_budget(
-    conn: &Connection,
-    forge: &str,
-    limit: u32,
-    remaining: u32,
-    reset_at: i64,
-) -> Result<()> {
+/// Note that an at-risk reminder went out for a goal's target date. Returns
+/// false if one already had, so each goal is reminded once per target date
+/// (moving the date re-arms it).
+pub fn claim_goal_reminder(conn: &Connection, forge_repo: &str, goal_id: &str, target_date: &str) -> Result<bool> {
+    let changed = conn.execute(
+        "INSERT INTO goal_reminders (forge_repo, goal_id, target_date, reminded_at) VALUES (?1, ?2, ?3, datetime('now'))
+         ON CONFLICT(forge_repo, goal_id) DO UPDATE SET target_date = ?3, reminded_at = datetime('now')
+         WHERE target_date != ?3",
+        params![forge_repo, goal_id, target_date],
+    )?;
+    Ok(changed > 0)
+}
+
+/// Count inbox events not yet marked read
+pub fn count_unread_inbox(conn: &Connection, forge_repo: &str) -> Result<i64> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM inbox_events WHERE forge_repo = ? AND read_at IS NULL",
+        params![forge_repo],
+        |row| row.get(0),
+    )?;
+    Ok(count)
+}
+
+/// Something the daemon left for a repo (a write it gave up on, a goal at risk)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InboxEvent {
+    pub id: i64,
+    pub kind: String,
+    pub message: String,
+    pub created_at: String,
+    /// None until `isq inbox` shows it
+    pub read_at: Option<String>,
+}
+
+/// A repo's inbox events, newest first: the unread ones, or with `all` every one
+pub fn load_inbox_events(conn: &Connection, forge_repo: &str, all: bool) -> Result<Vec<InboxEvent>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, kind, message, created_at, read_at FROM inbox_events
+         WHERE forge_repo = ? AND (? OR read_at IS NULL) ORDER BY id DESC",
+    )?;
+    let events = stmt
+        .query_map(params![forge_repo, all], |row| {
+            Ok(InboxEvent {
+                id: row.get(0)?,
+                kind: row.get(1)?,
+                message: row.get(2)?,
+                created_at: row.get(3)?,
+                read_at: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(events)
+}
+
+/// Mark a repo's inbox events read. Returns how many were unread.
+pub fn mark_inbox_read(conn: &Connection, forge_repo: &str) -> Result<usize> {
+    let marked = conn.execute(
+        "UPDATE inbox_events SET read_at = datetime('now') WHERE forge_repo = ? AND read_at IS NULL",
+        params![forge_repo],
+    )?;
+    Ok(marked)
+}
+
+// === References ===
+
+/// `issue_references.source` for an issue's own body (otherwise a comment ID)
+const BODY_SOURCE: &str = "body";
+
+/// An issue that mentions another one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Backlink {
+    pub number: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    pub title: String,
+    pub state: String,
+}
+
+impl Backlink {
+    /// `ABC-123` when the forge has identifiers, `#123` otherwise
+    pub fn display_id(&self) -> String {
+        match &self.identifier {
+            Some(identifier) => identifier.clone(),
+            None => format!("#{}", self.number),
+        }
+    }
+}
+
+/// Team keys of a repo's cached issues (for telling `ABC-123` from `UTF-8`)
+pub fn known_team_keys(conn: &Connection, repo: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("SELECT DISTINCT team_key FROM issues WHERE repo = ? AND team_key IS NOT NULL")?;
+    let keys = stmt
+        .query_map(params![repo], |row| row.get(0))?
+        .collect::<Result<Vec<String>, _>>()?;
+    Ok(keys)
+}
+
+/// Store the references `text` makes from issue `number`, replacing what
+/// the same source (body or comment) referenced before
+fn index_references(conn: &Connection, repo: &str, number: u64, source: &str, text: &str) -> Result<()> {
     conn.execute(
-        "INSERT INTO rate_limit_state (forge, rate_limit, remaining, reset_at, updated_at)
-         VALUES (?, ?, ?, ?, datetime('now'))
-         ON CONFLICT(forge) DO UPDATE SET
-            rate_limit = excluded.rate_limit,
-            remaining = excluded.remaining,
-            reset_at = excluded.reset_at,
-            last_error = NULL,
-            updated_at = excluded.updated_at",
-        params![forge, limit as i64, remaining as i64, reset_at],
+        "DELETE FROM issue_references WHERE forge_repo = ? AND source_number = ? AND source = ?",
+        params![repo, number as i64, source],
     )?;
-    Ok(())
+    let keys = known_team_keys(conn, repo)?;
+    insert_references(conn, repo, &keys, number, source, text)
 }
 
-/// Check if a forge is currently rate limited
-pub fn is_rate_limited(conn: &Connection, forge: &str) -> Result<bool> {
-    if let Some(state) = get_rate_limit_state(conn, forge)? {
-        if let Some(reset_at) = state.reset_at {
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs() as i64;
-            return Ok(now < reset_at);
+fn insert_references(conn: &Connection, repo: &str, keys: &[String], number: u64, source: &str, text: &str) -> Result<()> {
+    let mut stmt = conn.prepare_cached(
+        "INSERT OR IGNORE INTO issue_references (forge_repo, source_number, source, target_number) VALUES (?, ?, ?, ?)",
+    )?;
+    for reference in references::extract(text) {
+        let known = reference.key.as_ref().is_none_or(|key| keys.contains(key));
+        if known && reference.number != number {
+            stmt.execute(params![repo, number as i64, source, reference.number as i64])?;
         }
     }
-    Ok(false)
+    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::forges::Issue;
+/// Rebuild a repo's references from its cached bodies and comments
+fn refresh_references(conn: &Connection, repo: &str) -> Result<()> {
+    conn.execute("DELETE FROM issue_references WHERE forge_repo = ?", params![repo])?;
+    let keys = known_team_keys(conn, repo)?;
 
-    /// Create an in-memory database for testing
-    fn test_db() -> Connection {
-        let conn = Connection::open_in_memory().unwrap();
-        init_schema(&conn).unwrap();
-        conn
+    let mut stmt = conn.prepare(
+        "SELECT number, ?2, isq_decrypt(body) FROM issues WHERE repo = ?1 AND body IS NOT NULL
+         UNION ALL
+         SELECT issue_number, comment_id, isq_decrypt(body) FROM comments WHERE forge_repo = ?1",
+    )?;
+    let sources = stmt
+        .query_map(params![repo, BODY_SOURCE], |row| {
+            Ok((row.get::<_, i64>(0)? as u64, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for (number, source, text) in sources {
+        insert_references(conn, repo, &keys, number, &source, &text)?;
     }
+    Ok(())
+}
 
-    // === Schema Tests ===
+/// Cached issues whose body or comments mention `number`, lowest number first
+pub fn load_backlinks(conn: &Connection, repo: &str, number: u64) -> Result<Vec<Backlink>> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT i.number, i.identifier, i.title, i.state
+         FROM issue_references r
+         JOIN issues i ON i.repo = r.forge_repo AND i.number = r.source_number
+         WHERE r.forge_repo = ? AND r.target_number = ?
+         ORDER BY i.number ASC",
+    )?;
+    let backlinks = stmt
+        .query_map(params![repo, number as i64], |row| {
+            Ok(Backlink {
+                number: row.get::<_, i64>(0)? as u64,
+                identifier: row.get(1)?,
+                title: row.get(2)?,
+                state: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(backlinks)
+}
 
-    #[test]
-    fn test_schema_creates_all_tables() {
-        let conn = test_db();
+// === Drafts ===
 
-        // Verify all tables exist by querying sqlite_master
-        let tables: Vec<String> = conn
-            .prepare("SELECT name FROM sqlite_master WHERE type='table' ORDER BY name")
-            .unwrap()
-            .query_map([], |row| row.get(0))
-            .unwrap()
-            .collect::<Result<Vec<_>, _>>()
-            .unwrap();
+/// An unsubmitted issue kept locally until `isq draft submit`, or a comment
+/// kept until the forge (or the offline queue) has it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Draft {
+    pub id: i64,
+    /// The issue a comment draft is for (None for issue drafts)
+    pub issue_number: Option<u64>,
+    /// Empty for comment drafts
+    pub title: String,
+    pub body: Option<String>,
+    pub labels: Vec<String>,
+    /// Goal name (resolved to an ID on submit)
+    pub goal: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
 
-        assert!(tables.contains(&"issues".to_string()));
-        assert!(tables.contains(&"sync_state".to_string()));
-        assert!(tables.contains(&"pending_ops".to_string()));
-        assert!(tables.contains(&"watched_repos".to_string()));
-        assert!(tables.contains(&"repo_links".to_string()));
-        assert!(tables.contains(&"comments".to_string()));
+/// Store a new draft. Returns its ID.
+pub fn create_draft(
+    conn: &Connection,
+    forge_repo: &str,
+    title: &str,
+    body: Option<&str>,
+    labels: &[String],
+    goal: Option<&str>,
+) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO drafts (forge_repo, title, body, labels, goal, created_at, updated_at)
+         VALUES (?, ?, isq_encrypt(?), ?, ?, datetime('now'), datetime('now'))",
+        params![forge_repo, title, body, serde_json::to_string(labels)?, goal],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Store a comment before posting it. Returns the draft's ID.
+pub fn create_comment_draft(conn: &Connection, forge_repo: &str, issue_number: u64, body: &str) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO drafts (forge_repo, issue_number, title, body, created_at, updated_at)
+         VALUES (?, ?, '', isq_encrypt(?), datetime('now'), datetime('now'))",
+        params![forge_repo, issue_number as i64, body],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Replace a draft's contents. Returns whether it exists.
+pub fn update_draft(conn: &Connection, forge_repo: &str, draft: &Draft) -> Result<bool> {
+    let updated = conn.execute(
+        "UPDATE drafts SET title = ?, body = isq_encrypt(?), labels = ?, goal = ?, updated_at = datetime('now')
+         WHERE forge_repo = ? AND id = ?",
+        params![
+            draft.title,
+            draft.body,
+            serde_json::to_string(&draft.labels)?,
+            draft.goal,
+            forge_repo,
+            draft.id,
+        ],
+    )?;
+    Ok(updated > 0)
+}
+
+/// Columns read by `draft_from_row`, in order
+const DRAFT_COLUMNS: &str = "id, title, isq_decrypt(body), labels, goal, created_at, updated_at, issue_number";
+
+fn draft_from_row(row: &rusqlite::Row) -> rusqlite::Result<Draft> {
+    let labels: String = row.get(3)?;
+    let issue_number: Option<i64> = row.get(7)?;
+    Ok(Draft {
+        id: row.get(0)?,
+        issue_number: issue_number.map(|n| n as u64),
+        title: row.get(1)?,
+        body: row.get(2)?,
+        labels: serde_json::from_str(&labels).unwrap_or_default(),
+        goal: row.get(4)?,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
+
+/// Load one draft
+pub fn load_draft(conn: &Connection, forge_repo: &str, id: i64) -> Result<Option<Draft>> {
+    let mut stmt = conn.prepare(&format!("SELECT {} FROM drafts WHERE forge_repo = ? AND id = ?", DRAFT_COLUMNS))?;
+    let mut rows = stmt.query(params![forge_repo, id])?;
+
+    if let Some(row) = rows.next()? {
+        Ok(Some(draft_from_row(row)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// A repo's issue drafts, or its comment drafts, oldest first
+pub fn list_drafts(conn: &Connection, forge_repo: &str, comments: bool) -> Result<Vec<Draft>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM drafts WHERE forge_repo = ? AND (issue_number IS NOT NULL) = ? ORDER BY id ASC",
+        DRAFT_COLUMNS
+    ))?;
+    let drafts = stmt
+        .query_map(params![forge_repo, comments], draft_from_row)?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(drafts)
+}
+
+/// Delete a draft (once submitted). Returns whether it existed.
+pub fn delete_draft(conn: &Connection, forge_repo: &str, id: i64) -> Result<bool> {
+    let deleted = conn.execute("DELETE FROM drafts WHERE forge_repo = ? AND id = ?", params![forge_repo, id])?;
+    Ok(deleted > 0)
+}
+
+// === Status Counts ===
+
+/// Cached issue counts for a repo, as shown by `isq status`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct IssueCounts {
+    pub open: i64,
+    /// None when the forge identity isn't known yet
+    pub assigned_to_me: Option<i64>,
+    /// Open issues whose goal's target date is before `today`
+    pub overdue: i64,
+    pub unread_inbox: i64,
+}
+
+/// Count open, mine, overdue and unread for a repo. `today` is YYYY-MM-DD.
+pub fn issue_counts(conn: &Connection, forge_repo: &str, me: Option<&str>, today: &str) -> Result<IssueCounts> {
+    let open: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM issues WHERE repo = ? AND state = 'open'",
+        params![forge_repo],
+        |row| row.get(0),
+    )?;
+
+    let assigned_to_me = match me {
+        Some(login) => Some(conn.query_row(
+            &format!("SELECT COUNT(*) FROM issues WHERE repo = ? AND state = 'open' AND {}", ASSIGNED_TO),
+            params![forge_repo, login],
+            |row| row.get(0),
+        )?),
+        None => None,
+    };
+
+    // Target dates are YYYY-MM-DD (Linear) or RFC 3339 (GitHub); compare the date part
+    let overdue: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM issues i
+         JOIN goals g ON g.forge_repo = i.repo AND g.name = i.milestone
+         WHERE i.repo = ? AND i.state = 'open' AND g.state = 'open'
+           AND g.target_date IS NOT NULL AND substr(g.target_date, 1, 10) < ?",
+        params![forge_repo, today],
+        |row| row.get(0),
+    )?;
+
+    Ok(IssueCounts {
+        open,
+        assigned_to_me,
+        overdue,
+        unread_inbox: count_unread_inbox(conn, forge_repo)?,
+    })
+}
+
+// ============================================================================
+// Comments
+// ============================================================================
+
+/// A comment on an issue
+#[derive(Debug, Clone)]
+pub struct Comment {
+    pub comment_id: String,
+    pub issue_number: u64,
+    pub body: String,
+    pub author: String,
+    pub created_at: String,
+}
+
+/// Cached comments on the `issues` row being updated (correlated subquery)
+const COMMENT_COUNT_SQL: &str = "(SELECT COUNT(*) FROM comments c WHERE c.forge_repo = issues.repo AND c.issue_number = issues.number)";
+
+/// Recount `issues.comment_count` for a repo, or for one of its issues
+fn refresh_comment_counts(conn: &Connection, forge_repo: &str, number: Option<u64>) -> Result<()> {
+    match number {
+        Some(number) => conn.execute(
+            &format!("UPDATE issues SET comment_count = {} WHERE repo = ? AND number = ?", COMMENT_COUNT_SQL),
+            params![forge_repo, number as i64],
+        )?,
+        None => conn.execute(
+            &format!("UPDATE issues SET comment_count = {} WHERE repo = ?", COMMENT_COUNT_SQL),
+            params![forge_repo],
+        )?,
+    };
+    Ok(())
+}
+
+/// Save comments for a repo (replaces all existing comments)
+pub fn save_comments(conn: &Connection, forge_repo: &str, comments: &[Comment]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+
+    // Delete existing comments for this repo
+    tx.execute("DELETE FROM comments WHERE forge_repo = ?", params![forge_repo])?;
+
+    // Insert new comments
+    let mut stmt = tx.prepare(
+        "INSERT INTO comments (forge_repo, issue_number, comment_id, body, author, created_at)
+         VALUES (?, ?, ?, isq_encrypt(?), ?, ?)",
+    )?;
+
+    for comment in comments {
+        stmt.execute(params![
+            forge_repo,
+            comment.issue_number as i64,
+            comment.comment_id,
+            comment.body,
+            comment.author,
+            comment.created_at,
+        ])?;
+    }
+
+    drop(stmt);
+    refresh_comment_counts(&tx, forge_repo, None)?;
+    refresh_references(&tx, forge_repo)?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Cache one freshly fetched issue and its comments (`sync --issue`,
+/// `show --fresh`), leaving the rest of the repo alone. Writes still queued
+/// are re-applied on top, as after a full sync.
+pub fn save_fetched_issue(conn: &Connection, forge_repo: &str, issue: &Issue, comments: &[Comment]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    upsert_issue(&tx, forge_repo, issue)?;
+    replace_issue_comments(&tx, forge_repo, issue.number, comments)?;
+    reapply_pending_ops(&tx, forge_repo)?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Replace one issue's cached comments
+fn replace_issue_comments(tx: &Connection, forge_repo: &str, number: u64, comments: &[Comment]) -> Result<()> {
+    tx.execute(
+        "DELETE FROM comments WHERE forge_repo = ? AND issue_number = ?",
+        params![forge_repo, number as i64],
+    )?;
+    tx.execute(
+        "DELETE FROM issue_references WHERE forge_repo = ? AND source_number = ? AND source != ?",
+        params![forge_repo, number as i64, BODY_SOURCE],
+    )?;
+
+    let keys = known_team_keys(tx, forge_repo)?;
+    let mut stmt = tx.prepare(
+        "INSERT INTO comments (forge_repo, issue_number, comment_id, body, author, created_at)
+         VALUES (?, ?, ?, isq_encrypt(?), ?, ?)",
+    )?;
+    for comment in comments.iter().filter(|c| c.issue_number == number) {
+        stmt.execute(params![
+            forge_repo,
+            number as i64,
+            comment.comment_id,
+            comment.body,
+            comment.author,
+            comment.created_at,
+        ])?;
+        insert_references(tx, forge_repo, &keys, number, &comment.comment_id, &comment.body)?;
+    }
+
+    drop(stmt);
+    refresh_comment_counts(tx, forge_repo, Some(number))
+}
+
+/// Add a single comment (e.g. one just posted) without replacing the rest.
+/// An upsert rather than INSERT OR REPLACE, whose implicit delete would skip
+/// the search index triggers.
+pub fn add_comment(conn: &Connection, forge_repo: &str, comment: &Comment) -> Result<()> {
+    conn.execute(
+        "INSERT INTO comments (forge_repo, issue_number, comment_id, body, author, created_at)
+         VALUES (?, ?, ?, isq_encrypt(?), ?, ?)
+         ON CONFLICT(forge_repo, comment_id) DO UPDATE SET
+            issue_number = excluded.issue_number, body = excluded.body,
+            author = excluded.author, created_at = excluded.created_at",
+        params![
+            forge_repo,
+            comment.issue_number as i64,
+            comment.comment_id,
+            comment.body,
+            comment.author,
+            comment.created_at,
+        ],
+    )?;
+    refresh_comment_counts(conn, forge_repo, Some(comment.issue_number))?;
+    index_references(conn, forge_repo, comment.issue_number, &comment.comment_id, &comment.body)
+}
+
+// === Encryption ===
+
+/// Whether `isq cache encrypt` was run (and not undone with `decrypt`)
+pub fn is_cache_encrypted(conn: &Connection) -> Result<bool> {
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM cache_encryption", [], |row| row.get(0))?;
+    Ok(count > 0)
+}
+
+/// Whether any of a repo's cached comments are encrypted (and so unsearchable)
+pub fn has_encrypted_comments(conn: &Connection, forge_repo: &str) -> Result<bool> {
+    let found: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM comments WHERE forge_repo = ? AND substr(body, 1, ?) = ?)",
+        params![forge_repo, cipher::PREFIX.len() as i64, cipher::PREFIX],
+        |row| row.get(0),
+    )?;
+    Ok(found)
+}
+
+/// Columns holding issue or comment text: cached bodies, drafts, and the
+/// payloads of queued writes
+const ENCRYPTED_COLUMNS: [(&str, &str); 5] = [
+    ("issues", "body"),
+    ("comments", "body"),
+    ("drafts", "body"),
+    ("pending_ops", "payload"),
+    ("failed_ops", "payload"),
+];
+
+/// Encrypt every cached body, draft and queued write in place (or with
+/// `encrypt` false, decrypt them) and record the choice for later writes.
+/// Returns how many values changed.
+pub fn convert_bodies(conn: &Connection, cipher: &Cipher, encrypt: bool) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let mut changed = 0;
+    for (table, column) in ENCRYPTED_COLUMNS {
+        let bodies = tx
+            .prepare(&format!("SELECT id, {1} FROM {0} WHERE {1} IS NOT NULL", table, column))?
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut update = tx.prepare(&format!("UPDATE {} SET {} = ? WHERE id = ?", table, column))?;
+        for (id, body) in bodies {
+            let converted = match (encrypt, cipher::is_encrypted(&body)) {
+                (true, false) => cipher.encrypt(&body)?,
+                (false, true) => cipher.decrypt(&body)?,
+                _ => continue,
+            };
+            update.execute(params![converted, id])?;
+            changed += 1;
+        }
+    }
+
+    if encrypt {
+        tx.execute(
+            "INSERT OR REPLACE INTO cache_encryption (id, encrypted_at) VALUES (1, datetime('now'))",
+            [],
+        )?;
+    } else {
+        tx.execute("DELETE FROM cache_encryption", [])?;
+    }
+    tx.commit()?;
+    Ok(changed)
+}
+
+/// Move the write-ahead log into the database and truncate it. Returns false
+/// if readers kept part of it from being moved.
+pub fn checkpoint(conn: &Connection) -> Result<bool> {
+    let busy: i64 = conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |row| row.get(0))?;
+    Ok(busy == 0)
+}
+
+/// Rebuild the database file to give free pages back to the filesystem
+pub fn vacuum(conn: &Connection) -> Result<()> {
+    conn.execute_batch("VACUUM; PRAGMA wal_checkpoint(TRUNCATE);")?;
+    Ok(())
+}
+
+/// Rewrite the database file so replaced bodies, drafts and queued writes
+/// don't linger in free pages, the write-ahead log or the search indexes
+pub fn scrub(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "INSERT INTO comments_fts (comments_fts) VALUES ('optimize');
+         INSERT INTO issues_fts (issues_fts) VALUES ('optimize');
+         VACUUM;
+         PRAGMA wal_checkpoint(TRUNCATE);",
+    )?;
+    Ok(())
+}
+
+// === Full-text search ===
+
+/// Marks the start and end of each match in a `CommentHit` snippet
+pub const MATCH_START: char = '\u{E000}';
+pub const MATCH_END: char = '\u{E001}';
+
+/// A cached comment matching a search, with the issue it's on
+#[derive(Debug, Clone, Serialize)]
+pub struct CommentHit {
+    pub issue_number: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    /// None when the issue itself isn't cached
+    pub issue_title: Option<String>,
+    pub comment_id: String,
+    pub author: String,
+    pub created_at: String,
+    pub body: String,
+    /// The matching part of the body, matches wrapped in MATCH_START/MATCH_END
+    #[serde(skip)]
+    pub snippet: String,
+}
+
+impl CommentHit {
+    /// `ABC-123` when the forge has identifiers, `#123` otherwise
+    pub fn display_id(&self) -> String {
+        match &self.identifier {
+            Some(identifier) => identifier.clone(),
+            None => format!("#{}", self.issue_number),
+        }
+    }
+}
+
+/// Turn free text into an FTS5 query matching all of its words. Each word is
+/// quoted, so punctuation (`foo-bar`, `C++`) can't be read as query syntax.
+fn fts_query(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Search a repo's cached comments, best matches first
+pub fn search_comments(
+    conn: &Connection,
+    forge_repo: &str,
+    query: &str,
+    author: Option<&str>,
+    issue_number: Option<u64>,
+    limit: usize,
+) -> Result<Vec<CommentHit>> {
+    let query = fts_query(query);
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut sql = format!(
+        "SELECT c.issue_number, i.identifier, i.title, c.comment_id, c.author, c.created_at, isq_decrypt(c.body),
+                snippet(comments_fts, 0, '{}', '{}', '…', 16)
+         FROM comments_fts
+         JOIN comments c ON c.id = comments_fts.rowid
+         LEFT JOIN issues i ON i.repo = c.forge_repo AND i.number = c.issue_number
+         WHERE comments_fts MATCH ? AND c.forge_repo = ?",
+        MATCH_START, MATCH_END
+    );
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(query), Box::new(forge_repo.to_string())];
+
+    if let Some(author) = author {
+        sql.push_str(" AND c.author = ? COLLATE NOCASE");
+        params_vec.push(Box::new(author.to_string()));
+    }
+    if let Some(number) = issue_number {
+        sql.push_str(" AND c.issue_number = ?");
+        params_vec.push(Box::new(number as i64));
+    }
+    sql.push_str(" ORDER BY bm25(comments_fts), c.created_at DESC LIMIT ?");
+    params_vec.push(Box::new(limit as i64));
+
+    let mut stmt = conn.prepare(&sql)?;
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+    let hits = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            Ok(CommentHit {
+                issue_number: row.get::<_, i64>(0)? as u64,
+                identifier: row.get(1)?,
+                issue_title: row.get(2)?,
+                comment_id: row.get(3)?,
+                author: row.get(4)?,
+                created_at: row.get(5)?,
+                body: row.get(6)?,
+                snippet: row.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(hits)
+}
+
+/// A cached issue whose title or body matches a search
+#[derive(Debug, Clone, Serialize)]
+pub struct IssueHit {
+    pub number: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    pub title: String,
+    pub state: String,
+    pub updated_at: String,
+    /// The matching part of the title or body, matches wrapped in MATCH_START/MATCH_END
+    #[serde(skip)]
+    pub snippet: String,
+}
+
+impl IssueHit {
+    /// `ABC-123` when the forge has identifiers, `#123` otherwise
+    pub fn display_id(&self) -> String {
+        match &self.identifier {
+            Some(identifier) => identifier.clone(),
+            None => format!("#{}", self.number),
+        }
+    }
+}
+
+/// A cached goal whose name or description matches a search
+#[derive(Debug, Clone, Serialize)]
+pub struct GoalHit {
+    pub goal_id: String,
+    pub name: String,
+    pub state: String,
+    pub target_date: Option<String>,
+    /// The matching part of the name or description, matches wrapped in MATCH_START/MATCH_END
+    #[serde(skip)]
+    pub snippet: String,
+}
+
+/// An `isq search` result, tagged with what it matched
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SearchHit {
+    Issue(IssueHit),
+    Comment(CommentHit),
+    Goal(GoalHit),
+}
+
+/// Search a repo's cached issue titles and bodies, best matches first.
+/// Encrypted bodies can't match, so only titles do while the cache is encrypted.
+pub fn search_issues_text(conn: &Connection, forge_repo: &str, query: &str, limit: usize) -> Result<Vec<IssueHit>> {
+    let query = fts_query(query);
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT i.number, i.identifier, i.title, i.state, i.updated_at,
+                snippet(issues_fts, -1, '{}', '{}', '…', 16)
+         FROM issues_fts
+         JOIN issues i ON i.id = issues_fts.rowid
+         WHERE issues_fts MATCH ? AND i.repo = ?
+         ORDER BY bm25(issues_fts), i.updated_at DESC LIMIT ?",
+        MATCH_START, MATCH_END
+    ))?;
+    let hits = stmt
+        .query_map(params![query, forge_repo, limit as i64], |row| {
+            Ok(IssueHit {
+                number: row.get::<_, i64>(0)? as u64,
+                identifier: row.get(1)?,
+                title: row.get(2)?,
+                state: row.get(3)?,
+                updated_at: row.get(4)?,
+                snippet: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(hits)
+}
+
+/// Search a repo's cached goal names and descriptions, best matches first
+pub fn search_goals(conn: &Connection, forge_repo: &str, query: &str, limit: usize) -> Result<Vec<GoalHit>> {
+    let query = fts_query(query);
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT g.goal_id, g.name, g.state, g.target_date,
+                snippet(goals_fts, -1, '{}', '{}', '…', 16)
+         FROM goals_fts
+         JOIN goals g ON g.id = goals_fts.rowid
+         WHERE goals_fts MATCH ? AND g.forge_repo = ?
+         ORDER BY bm25(goals_fts), g.updated_at DESC LIMIT ?",
+        MATCH_START, MATCH_END
+    ))?;
+    let hits = stmt
+        .query_map(params![query, forge_repo, limit as i64], |row| {
+            Ok(GoalHit {
+                goal_id: row.get(0)?,
+                name: row.get(1)?,
+                state: row.get(2)?,
+                target_date: row.get(3)?,
+                snippet: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(hits)
+}
+
+/// Load every cached comment for a repo, grouped by issue (oldest first)
+pub fn load_all_comments(conn: &Connection, forge_repo: &str) -> Result<HashMap<u64, Vec<Comment>>> {
+    let mut stmt = conn.prepare(
+        "SELECT comment_id, issue_number, isq_decrypt(body), author, created_at
+         FROM comments WHERE forge_repo = ?
+         ORDER BY issue_number ASC, created_at ASC",
+    )?;
+
+    let mut by_issue: HashMap<u64, Vec<Comment>> = HashMap::new();
+    let rows = stmt.query_map(params![forge_repo], |row| {
+        let num: i64 = row.get(1)?;
+        Ok(Comment {
+            comment_id: row.get(0)?,
+            issue_number: num as u64,
+            body: row.get(2)?,
+            author: row.get(3)?,
+            created_at: row.get(4)?,
+        })
+    })?;
+    for comment in rows {
+        let comment = comment?;
+        by_issue.entry(comment.issue_number).or_default().push(comment);
+    }
+
+    Ok(by_issue)
+}
+
+/// Load comments for a specific issue
+pub fn load_comments(conn: &Connection, forge_repo: &str, issue_number: u64) -> Result<Vec<Comment>> {
+    let mut stmt = conn.prepare(
+        "SELECT comment_id, issue_number, isq_decrypt(body), author, created_at
+         FROM comments WHERE forge_repo = ? AND issue_number = ?
+         ORDER BY created_at ASC",
+    )?;
+
+    let comments = stmt
+        .query_map(params![forge_repo, issue_number as i64], |row| {
+            let num: i64 = row.get(1)?;
+            Ok(Comment {
+                comment_id: row.get(0)?,
+                issue_number: num as u64,
+                body: row.get(2)?,
+                author: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(comments)
+}
+
+/// Load the most recent `limit` comments for an issue (all if `None`), oldest
+/// first, along with the issue's total comment count
+pub fn load_latest_comments(
+    conn: &Connection,
+    forge_repo: &str,
+    issue_number: u64,
+    limit: Option<usize>,
+) -> Result<(Vec<Comment>, usize)> {
+    let total: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM comments WHERE forge_repo = ? AND issue_number = ?",
+        params![forge_repo, issue_number as i64],
+        |row| row.get(0),
+    )?;
+
+    // LIMIT -1 means no limit in SQLite
+    let limit = limit.map(|n| n as i64).unwrap_or(-1);
+    let mut stmt = conn.prepare(
+        "SELECT comment_id, issue_number, isq_decrypt(body), author, created_at FROM (
+            SELECT * FROM comments WHERE forge_repo = ? AND issue_number = ?
+            ORDER BY created_at DESC LIMIT ?
+         ) ORDER BY created_at ASC",
+    )?;
+
+    let comments = stmt
+        .query_map(params![forge_repo, issue_number as i64, limit], |row| {
+            let num: i64 = row.get(1)?;
+            Ok(Comment {
+                comment_id: row.get(0)?,
+                issue_number: num as u64,
+                body: row.get(2)?,
+                author: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((comments, total as usize))
+}
+
+/// Comment counts for a repo's cached issues (issue_number -> count, issues
+/// without comments omitted). Reads the `comment_count` column kept up to date
+/// by `save_comments`/`add_comment`, so it costs one row per issue.
+pub fn count_comments_by_issue(conn: &Connection, forge_repo: &str) -> Result<std::collections::HashMap<u64, usize>> {
+    let mut stmt = conn.prepare("SELECT number, comment_count FROM issues WHERE repo = ? AND comment_count > 0")?;
+
+    let mut counts = std::collections::HashMap::new();
+    let rows = stmt.query_map(params![forge_repo], |row| {
+        let num: i64 = row.get(0)?;
+        let count: i64 = row.get(1)?;
+        Ok((num as u64, count as usize))
+    })?;
+
+    for row in rows {
+        let (num, count) = row?;
+        counts.insert(num, count);
+    }
+
+    Ok(counts)
+}
+
+/// Everyone on an issue: its author, assignees, then commenters by their
+/// first comment (each once, case-insensitively)
+pub fn load_participants(conn: &Connection, forge_repo: &str, number: u64) -> Result<Vec<String>> {
+    let Some(issue) = load_issue(conn, forge_repo, number)? else {
+        return Ok(Vec::new());
+    };
+    let mut stmt = conn.prepare(
+        "SELECT author FROM comments WHERE forge_repo = ? AND issue_number = ? ORDER BY created_at",
+    )?;
+    let commenters = stmt
+        .query_map(params![forge_repo, number as i64], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+
+    let mut participants: Vec<String> = Vec::new();
+    for user in std::iter::once(issue.author).chain(issue.assignees).chain(commenters) {
+        if !participants.iter().any(|p| p.eq_ignore_ascii_case(&user)) {
+            participants.push(user);
+        }
+    }
+    Ok(participants)
+}
+
+/// Issues with a cached comment by `author` (case-insensitive)
+pub fn issues_commented_by(conn: &Connection, forge_repo: &str, author: &str) -> Result<HashSet<u64>> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT issue_number FROM comments WHERE forge_repo = ? AND author = ? COLLATE NOCASE",
+    )?;
+    let numbers = stmt
+        .query_map(params![forge_repo, author], |row| row.get::<_, i64>(0).map(|n| n as u64))?
+        .collect::<rusqlite::Result<HashSet<u64>>>()?;
+    Ok(numbers)
+}
+
+// ============================================================================
+// Goals
+// ============================================================================
+
+/// Save goals for a repo (replaces all existing goals) and record today's progress snapshot
+pub fn save_goals(conn: &Connection, forge_repo: &str, goals: &[Goal]) -> Result<()> {
+    let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
+    record_goal_snapshots(conn, forge_repo, goals, &today)?;
+
+    let tx = conn.unchecked_transaction()?;
+
+    // Delete existing goals for this repo
+    tx.execute("DELETE FROM goals WHERE forge_repo = ?", params![forge_repo])?;
+
+    // Insert new goals
+    let mut stmt = tx.prepare(
+        "INSERT INTO goals (forge_repo, goal_id, name, description, target_date, state, progress, open_count, closed_count, created_at, updated_at, html_url)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )?;
+
+    for goal in goals {
+        stmt.execute(params![
+            forge_repo,
+            goal.id,
+            goal.name,
+            goal.description,
+            goal.target_date,
+            goal.state.as_str(),
+            goal.progress,
+            goal.open_count.map(|c| c as i64),
+            goal.closed_count.map(|c| c as i64),
+            goal.created_at,
+            goal.updated_at,
+            goal.html_url,
+        ])?;
+    }
+
+    drop(stmt);
+    tx.commit()?;
+    Ok(())
+}
+
+/// Progress of one goal on one day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GoalSnapshot {
+    /// YYYY-MM-DD (UTC)
+    pub day: String,
+    pub goal_id: String,
+    pub name: String,
+    pub state: GoalState,
+    pub progress: f64,
+    pub open_count: Option<u64>,
+    pub closed_count: Option<u64>,
+}
+
+/// Record each goal's progress for `day`. Later syncs the same day overwrite
+/// earlier ones, so there's one row per goal per day.
+pub fn record_goal_snapshots(conn: &Connection, forge_repo: &str, goals: &[Goal], day: &str) -> Result<()> {
+    let mut stmt = conn.prepare(
+        "INSERT INTO goal_snapshots (forge_repo, goal_id, day, name, state, progress, open_count, closed_count)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(forge_repo, goal_id, day) DO UPDATE SET
+            name = excluded.name,
+            state = excluded.state,
+            progress = excluded.progress,
+            open_count = excluded.open_count,
+            closed_count = excluded.closed_count",
+    )?;
+    for goal in goals {
+        stmt.execute(params![
+            forge_repo,
+            goal.id,
+            day,
+            goal.name,
+            goal.state.as_str(),
+            goal.progress,
+            goal.open_count.map(|c| c as i64),
+            goal.closed_count.map(|c| c as i64),
+        ])?;
+    }
+    Ok(())
+}
+
+/// Load goal snapshots on or after `since` (YYYY-MM-DD), optionally for one goal
+/// (name or ID), ordered by goal name then day
+pub fn load_goal_snapshots(
+    conn: &Connection,
+    forge_repo: &str,
+    since: Option<&str>,
+    goal: Option<&str>,
+) -> Result<Vec<GoalSnapshot>> {
+    let mut sql = String::from(
+        "SELECT day, goal_id, name, state, progress, open_count, closed_count
+         FROM goal_snapshots WHERE forge_repo = ?",
+    );
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(forge_repo.to_string())];
+
+    if let Some(since) = since {
+        sql.push_str(" AND day >= ?");
+        params_vec.push(Box::new(since.to_string()));
+    }
+    if let Some(goal) = goal {
+        sql.push_str(" AND (goal_id = ? OR name = ? COLLATE NOCASE)");
+        params_vec.push(Box::new(goal.to_string()));
+        params_vec.push(Box::new(goal.to_string()));
+    }
+    sql.push_str(" ORDER BY name COLLATE NOCASE, goal_id, day");
+
+    let mut stmt = conn.prepare(&sql)?;
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+    let snapshots = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            let state: String = row.get(3)?;
+            let open: Option<i64> = row.get(5)?;
+            let closed: Option<i64> = row.get(6)?;
+            Ok(GoalSnapshot {
+                day: row.get(0)?,
+                goal_id: row.get(1)?,
+                name: row.get(2)?,
+                state: GoalState::from_str(&state),
+                progress: row.get(4)?,
+                open_count: open.map(|c| c as u64),
+                closed_count: closed.map(|c| c as u64),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(snapshots)
+}
+
+/// Save a single goal (insert or update)
+pub fn save_goal(conn: &Connection, forge_repo: &str, goal: &Goal) -> Result<()> {
+    conn.execute(
+        "INSERT INTO goals (forge_repo, goal_id, name, description, target_date, state, progress, open_count, closed_count, created_at, updated_at, html_url)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(forge_repo, goal_id) DO UPDATE SET
+            name = excluded.name,
+            description = excluded.description,
+            target_date = excluded.target_date,
+            state = excluded.state,
+            progress = excluded.progress,
+            open_count = excluded.open_count,
+            closed_count = excluded.closed_count,
+            updated_at = excluded.updated_at,
+            html_url = excluded.html_url",
+        params![
+            forge_repo,
+            goal.id,
+            goal.name,
+            goal.description,
+            goal.target_date,
+            goal.state.as_str(),
+            goal.progress,
+            goal.open_count.map(|c| c as i64),
+            goal.closed_count.map(|c| c as i64),
+            goal.created_at,
+            goal.updated_at,
+            goal.html_url,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Load all goals for a repo from cache
+pub fn load_goals(conn: &Connection, forge_repo: &str, state: Option<&str>) -> Result<Vec<Goal>> {
+    let mut sql = String::from(
+        "SELECT goal_id, name, description, target_date, state, progress, open_count, closed_count, created_at, updated_at, html_url
+         FROM goals WHERE forge_repo = ?",
+    );
+
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(forge_repo.to_string())];
+
+    if let Some(s) = state {
+        sql.push_str(" AND state = ?");
+        params_vec.push(Box::new(s.to_string()));
+    }
+
+    sql.push_str(" ORDER BY target_date ASC NULLS LAST, name ASC");
+
+    let mut stmt = conn.prepare(&sql)?;
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+
+    let goals = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            let state_str: String = row.get(4)?;
+            let progress: f64 = row.get::<_, Option<f64>>(5)?.unwrap_or(0.0);
+            let open: Option<i64> = row.get(6)?;
+            let closed: Option<i64> = row.get(7)?;
+
+            Ok(Goal {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                description: row.get(2)?,
+                target_date: row.get(3)?,
+                state: GoalState::from_str(&state_str),
+                progress,
+                open_count: open.map(|c| c as u64),
+                closed_count: closed.map(|c| c as u64),
+                created_at: row.get(8)?,
+                updated_at: row.get(9)?,
+                html_url: row.get(10)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(goals)
+}
+
+/// Update a cached goal's state right after closing or reopening it
+pub fn set_goal_state(conn: &Connection, forge_repo: &str, goal_id: &str, state: GoalState) -> Result<bool> {
+    let changed = conn.execute(
+        "UPDATE goals SET state = ? WHERE forge_repo = ? AND goal_id = ?",
+        params![state.as_str(), forge_repo, goal_id],
+    )?;
+    Ok(changed > 0)
+}
+
+/// Load a single goal by name or ID
+pub fn load_goal_by_name(conn: &Connection, forge_repo: &str, name: &str) -> Result<Option<Goal>> {
+    let mut stmt = conn.prepare(
+        "SELECT goal_id, name, description, target_date, state, progress, open_count, closed_count, created_at, updated_at, html_url
+         FROM goals WHERE forge_repo = ? AND (name = ? OR goal_id = ?)",
+    )?;
+
+    let mut rows = stmt.query(params![forge_repo, name, name])?;
+
+    if let Some(row) = rows.next()? {
+        let state_str: String = row.get(4)?;
+        let progress: f64 = row.get::<_, Option<f64>>(5)?.unwrap_or(0.0);
+        let open: Option<i64> = row.get(6)?;
+        let closed: Option<i64> = row.get(7)?;
+
+        Ok(Some(Goal {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            description: row.get(2)?,
+            target_date: row.get(3)?,
+            state: GoalState::from_str(&state_str),
+            progress,
+            open_count: open.map(|c| c as u64),
+            closed_count: closed.map(|c| c as u64),
+            created_at: row.get(8)?,
+            updated_at: row.get(9)?,
+            html_url: row.get(10)?,
+        }))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Resolve a goal name case-insensitively to its cached spelling
+pub fn resolve_goal_name(conn: &Connection, forge_repo: &str, name: &str) -> Result<Option<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT name FROM goals WHERE forge_repo = ? AND name = ? COLLATE NOCASE LIMIT 1",
+    )?;
+
+    let mut rows = stmt.query(params![forge_repo, name])?;
+
+    if let Some(row) = rows.next()? {
+        Ok(Some(row.get(0)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Count goals for a repo
+pub fn count_goals(conn: &Connection, forge_repo: &str) -> Result<i64> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM goals WHERE forge_repo = ?",
+        params![forge_repo],
+        |row| row.get(0),
+    )?;
+    Ok(count)
+}
+
+// ============================================================================
+// Objectives
+// ============================================================================
+
+/// Save objectives for a repo (replaces all existing objectives and their goal links)
+pub fn save_objectives(conn: &Connection, forge_repo: &str, objectives: &[Objective]) -> Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute("DELETE FROM objectives WHERE forge_repo = ?", params![forge_repo])?;
+    tx.execute("DELETE FROM objective_goals WHERE forge_repo = ?", params![forge_repo])?;
+    for objective in objectives {
+        save_objective(&tx, forge_repo, objective)?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Save a single objective and its goal links (insert or update)
+pub fn save_objective(conn: &Connection, forge_repo: &str, objective: &Objective) -> Result<()> {
+    conn.execute(
+        "INSERT INTO objectives (forge_repo, objective_id, name, description, target_date, state, updated_at, html_url)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(forge_repo, objective_id) DO UPDATE SET
+            name = excluded.name,
+            description = excluded.description,
+            target_date = excluded.target_date,
+            state = excluded.state,
+            updated_at = excluded.updated_at,
+            html_url = excluded.html_url",
+        params![
+            forge_repo,
+            objective.id,
+            objective.name,
+            objective.description,
+            objective.target_date,
+            objective.state.as_str(),
+            objective.updated_at,
+            objective.html_url,
+        ],
+    )?;
+    for goal_id in &objective.goal_ids {
+        link_objective_goal(conn, forge_repo, &objective.id, goal_id)?;
+    }
+    Ok(())
+}
+
+/// Record that a goal sits under an objective
+pub fn link_objective_goal(conn: &Connection, forge_repo: &str, objective_id: &str, goal_id: &str) -> Result<()> {
+    conn.execute(
+        "INSERT OR IGNORE INTO objective_goals (forge_repo, objective_id, goal_id) VALUES (?, ?, ?)",
+        params![forge_repo, objective_id, goal_id],
+    )?;
+    Ok(())
+}
+
+fn objective_from_row(conn: &Connection, forge_repo: &str, row: &rusqlite::Row) -> rusqlite::Result<Objective> {
+    let id: String = row.get(0)?;
+    let state: String = row.get(4)?;
+    let mut stmt = conn.prepare_cached(
+        "SELECT goal_id FROM objective_goals WHERE forge_repo = ? AND objective_id = ? ORDER BY goal_id",
+    )?;
+    let goal_ids = stmt
+        .query_map(params![forge_repo, id], |r| r.get(0))?
+        .collect::<Result<Vec<String>, _>>()?;
+
+    Ok(Objective {
+        id,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        target_date: row.get(3)?,
+        state: GoalState::from_str(&state),
+        goal_ids,
+        updated_at: row.get(5)?,
+        html_url: row.get(6)?,
+    })
+}
+
+/// Load all objectives for a repo, soonest target first
+pub fn load_objectives(conn: &Connection, forge_repo: &str) -> Result<Vec<Objective>> {
+    let mut stmt = conn.prepare(
+        "SELECT objective_id, name, description, target_date, state, updated_at, html_url
+         FROM objectives WHERE forge_repo = ?
+         ORDER BY target_date ASC NULLS LAST, name ASC",
+    )?;
+    let objectives = stmt
+        .query_map(params![forge_repo], |row| objective_from_row(conn, forge_repo, row))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(objectives)
+}
+
+/// Load a single objective by ID or case-insensitive name
+pub fn load_objective_by_name(conn: &Connection, forge_repo: &str, name: &str) -> Result<Option<Objective>> {
+    let mut stmt = conn.prepare(
+        "SELECT objective_id, name, description, target_date, state, updated_at, html_url
+         FROM objectives WHERE forge_repo = ? AND (objective_id = ? OR name = ? COLLATE NOCASE)",
+    )?;
+    let mut rows = stmt.query(params![forge_repo, name, name])?;
+    match rows.next()? {
+        Some(row) => Ok(Some(objective_from_row(conn, forge_repo, row)?)),
+        None => Ok(None),
+    }
+}
+
+// ============================================================================
+// Rate Limit State
+// ============================================================================
+
+/// Rate limit state for a forge
+#[derive(Debug, Clone)]
+pub struct RateLimitState {
+    /// Total requests allowed per hour
+    pub limit: Option<u32>,
+    /// Requests remaining this hour
+    pub remaining: Option<u32>,
+    /// Unix timestamp when the limit resets
+    pub reset_at: Option<i64>,
+    pub last_error: Option<String>,
+}
+
+impl RateLimitState {
+    /// Calculate how many requests have been used this hour
+    pub fn used(&self) -> Option<u32> {
+        match (self.limit, self.remaining) {
+            (Some(limit), Some(remaining)) => Some(limit.saturating_sub(remaining)),
+            _ => None,
+        }
+    }
+
+    /// Whether less than 5% of the hourly budget is left at `now` (never once it has reset)
+    pub fn is_low(&self, now: i64) -> bool {
+        match (self.limit, self.remaining, self.reset_at) {
+            (Some(limit), Some(remaining), Some(reset_at)) => now < reset_at && (remaining as u64) * 20 < limit as u64,
+            _ => false,
+        }
+    }
+}
+
+/// Get rate limit state for a forge
+pub fn get_rate_limit_state(conn: &Connection, forge: &str) -> Result<Option<RateLimitState>> {
+    let mut stmt = conn.prepare(
+        "SELECT forge, rate_limit, remaining, reset_at, last_error, updated_at FROM rate_limit_state WHERE forge = ?",
+    )?;
+
+    let mut rows = stmt.query(params![forge])?;
+
+    if let Some(row) = rows.next()? {
+        let limit: Option<i64> = row.get(1)?;
+        let remaining: Option<i64> = row.get(2)?;
+        Ok(Some(RateLimitState {
+            limit: limit.map(|v| v as u32),
+            remaining: remaining.map(|v| v as u32),
+            reset_at: row.get(3)?,
+            last_error: row.get(4)?,
+        }))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Set rate limit state for a forge (error case - only reset_at and last_error)
+pub fn set_rate_limit_state(
+    conn: &Connection,
+    forge: &str,
+    reset_at: Option<i64>,
+    last_error: Option<&str>,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO rate_limit_state (forge, reset_at, last_error, updated_at)
+         VALUES (?, ?, ?, datetime('now'))
+         ON CONFLICT(forge) DO UPDATE SET
+            reset_at = excluded.reset_at,
+            last_error = excluded.last_error,
+            updated_at = excluded.updated_at",
+        params![forge, reset_at, last_error],
+    )?;
+    Ok(())
+}
+
+/// Update rate limit budget for a forge (called after successful API calls)
+pub fn update_rate_limit_budget(
+    conn: &Connection,
+    forge: &str,
+    limit: u32,
+    remaining: u32,
+    reset_at: i64,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO rate_limit_state (forge, rate_limit, remaining, reset_at, updated_at)
+         VALUES (?, ?, ?, ?, datetime('now'))
+         ON CONFLICT(forge) DO UPDATE SET
+            rate_limit = excluded.rate_limit,
+            remaining = excluded.remaining,
+            reset_at = excluded.reset_at,
+            last_error = NULL,
+            updated_at = excluded.updated_at",
+        params![forge, limit as i64, remaining as i64, reset_at],
+    )?;
+    Ok(())
+}
+
+/// Budget left for a forge, as last seen by sync
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForgeBudget {
+    pub forge: String,
+    pub remaining: Option<i64>,
+    /// Linear's query-complexity points
+    pub complexity_remaining: Option<i64>,
+}
+
+/// Last known budgets of every forge that has reported one, taking the most
+/// recent report when several caches have one
+pub fn forge_budgets(conns: &[Connection]) -> Result<Vec<ForgeBudget>> {
+    let mut latest: BTreeMap<String, (String, ForgeBudget)> = BTreeMap::new();
+    for conn in conns {
+        let mut stmt =
+            conn.prepare("SELECT forge, remaining, complexity_remaining, updated_at FROM rate_limit_state")?;
+        let rows = stmt
+            .query_map([], |row| {
+                let budget =
+                    ForgeBudget { forge: row.get(0)?, remaining: row.get(1)?, complexity_remaining: row.get(2)? };
+                Ok((row.get::<_, String>(3)?, budget))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        for (updated_at, budget) in rows {
+            if latest.get(&budget.forge).is_none_or(|(seen, _)| *seen < updated_at) {
+                latest.insert(budget.forge.clone(), (updated_at, budget));
+            }
+        }
+    }
+    Ok(latest.into_values().map(|(_, budget)| budget).collect())
+}
+
+/// Get the last known query-complexity budget for a forge
+pub fn get_complexity_budget(conn: &Connection, forge: &str) -> Result<Option<ComplexityBudget>> {
+    let row = conn.query_row(
+        "SELECT complexity_limit, complexity_remaining, complexity_reset_at, complexity_last_cost
+         FROM rate_limit_state WHERE forge = ? AND complexity_limit IS NOT NULL",
+        params![forge],
+        |row| {
+            Ok(ComplexityBudget {
+                limit: row.get::<_, i64>(0)? as u32,
+                remaining: row.get::<_, Option<i64>>(1)?.unwrap_or(0) as u32,
+                reset_at: row.get::<_, Option<i64>>(2)?.unwrap_or(0),
+                last_cost: row.get::<_, Option<i64>>(3)?.unwrap_or(0) as u32,
+            })
+        },
+    );
+    match row {
+        Ok(budget) => Ok(Some(budget)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Persist a forge's query-complexity budget (called after sync)
+pub fn update_complexity_budget(conn: &Connection, forge: &str, budget: &ComplexityBudget) -> Result<()> {
+    conn.execute(
+        "INSERT INTO rate_limit_state (forge, complexity_limit, complexity_remaining, complexity_reset_at, complexity_last_cost, updated_at)
+         VALUES (?, ?, ?, ?, ?, datetime('now'))
+         ON CONFLICT(forge) DO UPDATE SET
+            complexity_limit = excluded.complexity_limit,
+            complexity_remaining = excluded.complexity_remaining,
+            complexity_reset_at = excluded.complexity_reset_at,
+            complexity_last_cost = excluded.complexity_last_cost,
+            updated_at = excluded.updated_at",
+        params![
+            forge,
+            budget.limit as i64,
+            budget.remaining as i64,
+            budget.reset_at,
+            budget.last_cost as i64,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Check if a forge is currently rate limited
+pub fn is_rate_limited(conn: &Connection, forge: &str) -> Result<bool> {
+    if let Some(state) = get_rate_limit_state(conn, forge)?
+        && let Some(reset_at) = state.reset_at
+    {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        return Ok(now < reset_at);
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forges::Issue;
+
+    /// Create an in-memory database for testing
+    fn test_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        cipher::register(&conn, false, cipher::test_cipher).unwrap();
+        conn
+    }
+
+    /// Save a fetch begun after every local write so far
+    fn save_issues(conn: &Connection, repo: &str, issues: &[Issue]) -> Result<()> {
+        super::save_issues(conn, repo, issues, "9999-12-31T23:59:59.999Z")
+    }
+
+    // === Schema Tests ===
+
+    #[test]
+    fn test_data_version_sees_other_connections() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.db");
+        let watcher = Connection::open(&path).unwrap();
+        init_schema(&watcher).unwrap();
+        let writer = Connection::open(&path).unwrap();
+        cipher::register(&writer, false, cipher::test_cipher).unwrap();
+
+        let before = data_version(&watcher).unwrap();
+        touch_repo(&watcher, "/own/write").unwrap();
+        assert_eq!(data_version(&watcher).unwrap(), before);
+
+        save_issues(&writer, "acme/api", &[make_issue(1, "New", "open", vec![])]).unwrap();
+        assert_ne!(data_version(&watcher).unwrap(), before);
+    }
+
+    #[test]
+    fn test_check_health_sees_a_held_write_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.db");
+        let conn = Connection::open(&path).unwrap();
+        init_schema(&conn).unwrap();
+        assert!(check_health(&conn).is_ok());
+
+        conn.busy_timeout(std::time::Duration::ZERO).unwrap();
+        let holder = Connection::open(&path).unwrap();
+        holder.execute_batch("BEGIN IMMEDIATE").unwrap();
+        let e = check_health(&conn).unwrap_err();
+        assert!(e.to_string().contains("can't be written"));
+        // Only busy, which passes on its own
+        assert!(is_busy(&e));
+        assert!(!is_busy(&anyhow::anyhow!("database is corrupt")));
+    }
+
+    #[test]
+    fn test_schema_creates_all_tables() {
+        let conn = test_db();
+
+        // Verify all tables exist by querying sqlite_master
+        let tables: Vec<String> = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type='table' ORDER BY name")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert!(tables.contains(&"issues".to_string()));
+        assert!(tables.contains(&"sync_state".to_string()));
+        assert!(tables.contains(&"pending_ops".to_string()));
+        assert!(tables.contains(&"watched_repos".to_string()));
+        assert!(tables.contains(&"repo_links".to_string()));
+        assert!(tables.contains(&"comments".to_string()));
+    }
+
+    #[test]
+    fn test_schema_is_idempotent() {
+        let conn = test_db();
+        // Running init_schema again should not error
+        init_schema(&conn).unwrap();
+        init_schema(&conn).unwrap();
+    }
+
+    // === Pending Ops Tests ===
+
+    #[test]
+    fn test_queue_and_load_pending_ops() {
+        let conn = test_db();
+
+        let id = queue_op(&conn, "owner/repo", "create", r#"{"title":"test"}"#).unwrap();
+        assert!(id > 0);
+
+        let ops = load_pending_ops(&conn, "owner/repo").unwrap();
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].op_type, "create");
+        assert_eq!(ops[0].payload, r#"{"title":"test"}"#);
+    }
+
+    #[test]
+    fn test_pending_ops_ordered_by_id() {
+        let conn = test_db();
+
+        queue_op(&conn, "owner/repo", "create", "first").unwrap();
+        queue_op(&conn, "owner/repo", "comment", "second").unwrap();
+        queue_op(&conn, "owner/repo", "close", "third").unwrap();
+
+        let ops = load_pending_ops(&conn, "owner/repo").unwrap();
+        assert_eq!(ops.len(), 3);
+        assert_eq!(ops[0].op_type, "create");
+        assert_eq!(ops[1].op_type, "comment");
+        assert_eq!(ops[2].op_type, "close");
+    }
+
+    #[test]
+    fn test_pending_ops_isolated_by_repo() {
+        let conn = test_db();
+
+        queue_op(&conn, "repo-a", "create", "a").unwrap();
+        queue_op(&conn, "repo-b", "create", "b").unwrap();
+
+        let ops_a = load_pending_ops(&conn, "repo-a").unwrap();
+        let ops_b = load_pending_ops(&conn, "repo-b").unwrap();
+
+        assert_eq!(ops_a.len(), 1);
+        assert_eq!(ops_b.len(), 1);
+        assert_eq!(ops_a[0].payload, "a");
+        assert_eq!(ops_b[0].payload, "b");
+    }
+
+    #[test]
+    fn test_complete_op_removes_from_queue() {
+        let conn = test_db();
+
+        let id1 = queue_op(&conn, "owner/repo", "create", "first").unwrap();
+        let id2 = queue_op(&conn, "owner/repo", "comment", "second").unwrap();
+
+        complete_op(&conn, id1).unwrap();
+
+        let ops = load_pending_ops(&conn, "owner/repo").unwrap();
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].id, id2);
+    }
+
+    #[test]
+    fn test_expire_and_retry_failed_ops() {
+        let conn = test_db();
+        let old = queue_op(&conn, "owner/repo", "close", r#"{"issue_number":1}"#).unwrap();
+        queue_op(&conn, "owner/repo", "reopen", r#"{"issue_number":2}"#).unwrap();
+        conn.execute("UPDATE pending_ops SET created_at = datetime('now', '-10 days') WHERE id = ?", params![old])
+            .unwrap();
+        set_op_error(&conn, old, "401 Bad credentials").unwrap();
+
+        assert_eq!(pending_ops_older_than(&conn, "owner/repo", 1).unwrap().len(), 1);
+        let expired = expire_pending_ops(&conn, "owner/repo", 7).unwrap();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(count_pending_ops(&conn, "owner/repo").unwrap(), 1);
+
+        let failed = load_failed_ops(&conn, "owner/repo").unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].op.op_type, "close");
+        assert_eq!(failed[0].op.last_error.as_deref(), Some("401 Bad credentials"));
+
+        assert_eq!(retry_failed_ops(&conn, "owner/repo").unwrap(), 1);
+        assert!(load_failed_ops(&conn, "owner/repo").unwrap().is_empty());
+        let pending = load_pending_ops(&conn, "owner/repo").unwrap();
+        assert_eq!(pending.len(), 2);
+        // Requeued fresh, so it isn't expired again right away
+        assert!(pending_ops_older_than(&conn, "owner/repo", 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pending_ops_survive_stale_sync() {
+        let conn = test_db();
+        save_issues(&conn, "owner/repo", &[make_issue(1, "Bug", "open", vec![])]).unwrap();
+        queue_op(&conn, "owner/repo", "close", r#"{"issue_number":1}"#).unwrap();
+        queue_op(&conn, "owner/repo", "label_add", r#"{"issue_number":1,"label":"urgent"}"#).unwrap();
+
+        // A sync that read the forge before the ops replayed
+        save_issues(&conn, "owner/repo", &[make_issue(1, "Bug", "open", vec![])]).unwrap();
+        let issue = load_issue(&conn, "owner/repo", 1).unwrap().unwrap();
+        assert_eq!(issue.state, "closed");
+        assert!(issue.labels.iter().any(|l| l.name == "urgent"));
+        // Still the forge's time; the forge hasn't seen the writes
+        assert_eq!(issue.updated_at, make_issue(1, "Bug", "open", vec![]).updated_at);
+    }
+
+    #[test]
+    fn test_replayed_op_keys_are_remembered() {
+        let conn = test_db();
+        let id = queue_op(&conn, "owner/repo", "close", r#"{"issue_number":1}"#).unwrap();
+        queue_op(&conn, "owner/repo", "close", r#"{"issue_number":2}"#).unwrap();
+        let ops = load_pending_ops(&conn, "owner/repo").unwrap();
+        assert_eq!(ops[0].dedup_key.len(), 32);
+        assert_ne!(ops[0].dedup_key, ops[1].dedup_key);
+
+        assert!(!is_replayed(&conn, &ops[0].dedup_key).unwrap());
+        complete_op(&conn, id).unwrap();
+        assert!(is_replayed(&conn, &ops[0].dedup_key).unwrap());
+        assert!(!is_replayed(&conn, &ops[1].dedup_key).unwrap());
+    }
+
+    #[test]
+    fn test_claim_op_keeps_other_replayers_away() {
+        let conn = test_db();
+        let id = queue_op(&conn, "owner/repo", "comment", r#"{"issue_number":1}"#).unwrap();
+        assert_eq!(claim_op(&conn, id).unwrap(), Claim::Claimed { retry: false });
+        // Claimed and never finished: it may have landed
+        assert_eq!(claim_op(&conn, id).unwrap(), Claim::Claimed { retry: true });
+
+        // Another process's recent claim wins; a stale one doesn't
+        conn.execute("UPDATE pending_ops SET claimed_by = 0 WHERE id = ?", params![id]).unwrap();
+        assert_eq!(claim_op(&conn, id).unwrap(), Claim::Busy);
+        conn.execute("UPDATE pending_ops SET claimed_at = datetime('now', '-1 hour') WHERE id = ?", params![id])
+            .unwrap();
+        assert_eq!(claim_op(&conn, id).unwrap(), Claim::Claimed { retry: true });
+
+        // A failed replay releases it
+        conn.execute("UPDATE pending_ops SET claimed_by = 0 WHERE id = ?", params![id]).unwrap();
+        set_op_error(&conn, id, "timed out").unwrap();
+        assert_eq!(claim_op(&conn, id).unwrap(), Claim::Claimed { retry: true });
+    }
+
+    #[test]
+    fn test_count_pending_ops() {
+        let conn = test_db();
+
+        assert_eq!(count_pending_ops(&conn, "owner/repo").unwrap(), 0);
+
+        queue_op(&conn, "owner/repo", "create", "1").unwrap();
+        assert_eq!(count_pending_ops(&conn, "owner/repo").unwrap(), 1);
+
+        queue_op(&conn, "owner/repo", "create", "2").unwrap();
+        assert_eq!(count_pending_ops(&conn, "owner/repo").unwrap(), 2);
+
+        queue_op(&conn, "other/repo", "create", "3").unwrap();
+        assert_eq!(count_pending_ops(&conn, "owner/repo").unwrap(), 2);
+        assert_eq!(count_pending_ops(&conn, "other/repo").unwrap(), 1);
+    }
+
+    // === Label Parsing Tests ===
+
+    #[test]
+    fn test_parse_labels_json_new_format() {
+        let json = r##"[{"name":"bug","color":"#fc2929"},{"name":"feature","color":"#4EA7FC"}]"##;
+        let labels = parse_labels_json(json);
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels[0].name, "bug");
+        assert_eq!(labels[0].color, Some("#fc2929".to_string()));
+        assert_eq!(labels[1].name, "feature");
+        assert_eq!(labels[1].color, Some("#4EA7FC".to_string()));
+    }
+
+    #[test]
+    fn test_parse_labels_json_old_format() {
+        let json = r#"["bug","enhancement"]"#;
+        let labels = parse_labels_json(json);
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels[0].name, "bug");
+        assert_eq!(labels[0].color, None);
+        assert_eq!(labels[1].name, "enhancement");
+        assert_eq!(labels[1].color, None);
+    }
+
+    #[test]
+    fn test_parse_labels_json_empty() {
+        assert!(parse_labels_json("[]").is_empty());
+        assert!(parse_labels_json("").is_empty());
+        assert!(parse_labels_json("invalid").is_empty());
+    }
+
+    // === Issues Tests ===
+
+    fn make_issue(number: u64, title: &str, state: &str, labels: Vec<&str>) -> Issue {
+        Issue {
+            number,
+            title: title.to_string(),
+            state: state.to_string(),
+            author: "testuser".to_string(),
+            labels: labels.into_iter().map(|s| Label::name_only(s.to_string())).collect(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_identifier_kept_out_of_title() {
+        let conn = test_db();
+        let mut issue = make_issue(42, "Fix login", "open", vec![]);
+        issue.identifier = Some("ENG-42".to_string());
+        save_issues(&conn, "ENG/team-id", &[issue]).unwrap();
+
+        // A title cached before identifiers had their own column
+        conn.execute(
+            "INSERT INTO issues (repo, number, title, state, author, labels, created_at, updated_at)
+             VALUES ('ENG/team-id', 7, 'ENG-7 Old style', 'open', 'a', '[]', '', '')",
+            [],
+        )
+        .unwrap();
+        split_cached_identifiers(&conn).unwrap();
+
+        let loaded = load_issue(&conn, "ENG/team-id", 42).unwrap().unwrap();
+        assert_eq!(loaded.title, "Fix login");
+        assert_eq!(loaded.display_id(), "ENG-42");
+        let old = load_issue(&conn, "ENG/team-id", 7).unwrap().unwrap();
+        assert_eq!(old.title, "Old style");
+        assert_eq!(old.identifier.as_deref(), Some("ENG-7"));
+        let key: String = conn
+            .query_row("SELECT team_key FROM issues WHERE number = 7", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(key, "ENG");
+    }
+
+    #[test]
+    fn test_save_and_load_issues() {
+        let conn = test_db();
+
+        let issues = vec![
+            make_issue(1, "First", "open", vec![]),
+            make_issue(2, "Second", "open", vec!["bug"]),
+        ];
+
+        save_issues(&conn, "owner/repo", &issues).unwrap();
+
+        let loaded = load_issues(&conn, "owner/repo").unwrap();
+        assert_eq!(loaded.len(), 2);
+        // Ordered by number DESC
+        assert_eq!(loaded[0].number, 2);
+        assert_eq!(loaded[1].number, 1);
+    }
+
+    #[test]
+    fn test_save_issues_replaces_existing() {
+        let conn = test_db();
+
+        save_issues(&conn, "owner/repo", &[make_issue(1, "Old", "open", vec![])]).unwrap();
+        save_issues(&conn, "owner/repo", &[make_issue(2, "New", "open", vec![])]).unwrap();
+
+        let loaded = load_issues(&conn, "owner/repo").unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].title, "New");
+    }
+
+    #[test]
+    fn test_save_issues_tombstones_deleted_issues() {
+        let conn = test_db();
+        let before = "2000-01-01T00:00:00.000Z";
+
+        let gone = make_issue(1, "Gone", "open", vec![]);
+        save_issues(&conn, "owner/repo", &[gone.clone(), make_issue(2, "Kept", "open", vec![])]).unwrap();
+        save_issues(&conn, "owner/repo", &[make_issue(2, "Kept", "open", vec![])]).unwrap();
+        assert!(load_issue(&conn, "owner/repo", 1).unwrap().is_none());
+
+        // A stale fetch from before the deletion doesn't bring it back
+        super::save_issues(&conn, "owner/repo", &[gone.clone(), make_issue(2, "Kept", "open", vec![])], before).unwrap();
+        assert!(load_issue(&conn, "owner/repo", 1).unwrap().is_none());
+        assert_eq!(get_sync_state(&conn, "owner/repo").unwrap().unwrap().1, 1);
+
+        // A fetch begun after it (restored), does
+        save_issues(&conn, "owner/repo", std::slice::from_ref(&gone)).unwrap();
+        assert!(load_issue(&conn, "owner/repo", 1).unwrap().is_some());
+
+        // So does fetching it directly
+        save_issues(&conn, "owner/repo", &[]).unwrap();
+        upsert_issue(&conn, "owner/repo", &gone).unwrap();
+        super::save_issues(&conn, "owner/repo", &[gone], before).unwrap();
+        assert!(load_issue(&conn, "owner/repo", 1).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_save_issues_keeps_issues_written_after_the_fetch_began() {
+        let conn = test_db();
+        save_issues(&conn, "owner/repo", &[make_issue(1, "Old", "open", vec![])]).unwrap();
+
+        // Created while a sync was fetching; its forge time is older than any local one
+        let started = fetch_started();
+        let created = Issue { updated_at: "2001-01-01T00:00:00Z".to_string(), ..make_issue(2, "New", "open", vec![]) };
+        upsert_issue(&conn, "owner/repo", &created).unwrap();
+
+        let stale = [make_issue(1, "Old", "open", vec![])];
+        super::save_issues(&conn, "owner/repo", &stale, &started).unwrap();
+        super::save_issues(&conn, "owner/repo", &stale, &started).unwrap();
+        assert!(load_issue(&conn, "owner/repo", 2).unwrap().is_some());
+
+        // Later syncs that include it keep it too
+        super::save_issues(&conn, "owner/repo", &[stale[0].clone(), created.clone()], &fetch_started()).unwrap();
+        save_issues(&conn, "owner/repo", &[stale[0].clone(), created]).unwrap();
+        assert!(load_issue(&conn, "owner/repo", 2).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_save_issues_merges_in_place() {
+        let conn = test_db();
+
+        save_issues(&conn, "owner/repo", &[make_issue(1, "Old", "open", vec![])]).unwrap();
+        let id: i64 = conn.query_row("SELECT id FROM issues WHERE number = 1", [], |row| row.get(0)).unwrap();
+        save_issues(&conn, "owner/repo", &[make_issue(1, "New", "closed", vec![])]).unwrap();
+
+        let (same_id, title): (i64, String) =
+            conn.query_row("SELECT id, title FROM issues WHERE number = 1", [], |row| Ok((row.get(0)?, row.get(1)?))).unwrap();
+        assert_eq!(same_id, id);
+        assert_eq!(title, "New");
+    }
+
+    #[test]
+    fn test_resumable_sync() {
+        let conn = test_db();
+        assert_eq!(get_sync_cursor(&conn, "owner/repo").unwrap(), None);
+
+        save_issue_page(&conn, "owner/repo", &[make_issue(1, "One", "open", vec![])], "[1]").unwrap();
+        save_issue_page(&conn, "owner/repo", &[make_issue(2, "Two", "open", vec![])], "[1,2]").unwrap();
+        assert_eq!(get_sync_cursor(&conn, "owner/repo").unwrap().as_deref(), Some("[1,2]"));
+        assert_eq!(load_issues(&conn, "owner/repo").unwrap().len(), 2);
+        assert!(get_sync_state(&conn, "owner/repo").unwrap().is_none());
+
+        finish_resumed_sync(&conn, "owner/repo").unwrap();
+        assert_eq!(get_sync_cursor(&conn, "owner/repo").unwrap(), None);
+        assert_eq!(get_sync_state(&conn, "owner/repo").unwrap().unwrap().1, 2);
+
+        // A complete sync also drops a leftover cursor
+        save_issue_page(&conn, "owner/repo", &[], "[1]").unwrap();
+        save_issues(&conn, "owner/repo", &[make_issue(1, "One", "open", vec![])]).unwrap();
+        assert_eq!(get_sync_cursor(&conn, "owner/repo").unwrap(), None);
+    }
+
+    #[test]
+    fn test_filter_by_state() {
+        let conn = test_db();
+
+        let issues = vec![
+            make_issue(1, "Open issue", "open", vec![]),
+            make_issue(2, "Closed issue", "closed", vec![]),
+        ];
+        save_issues(&conn, "owner/repo", &issues).unwrap();
+
+        let open = load_issues_filtered(&conn, "owner/repo", None, Some("open")).unwrap();
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].title, "Open issue");
+
+        let closed = load_issues_filtered(&conn, "owner/repo", None, Some("closed")).unwrap();
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].title, "Closed issue");
+    }
+
+    #[test]
+    fn test_filter_by_label() {
+        let conn = test_db();
+
+        let issues = vec![
+            make_issue(1, "Bug", "open", vec!["bug"]),
+            make_issue(2, "Feature", "open", vec!["enhancement"]),
+            make_issue(3, "Bug and feature", "open", vec!["bug", "enhancement"]),
+        ];
+        save_issues(&conn, "owner/repo", &issues).unwrap();
+
+        let bugs = load_issues_filtered(&conn, "owner/repo", Some("bug"), None).unwrap();
+        assert_eq!(bugs.len(), 2);
+
+        let enhancements =
+            load_issues_filtered(&conn, "owner/repo", Some("enhancement"), None).unwrap();
+        assert_eq!(enhancements.len(), 2);
+    }
+
+    #[test]
+    fn test_filter_by_label_is_exact() {
+        let conn = test_db();
+
+        // Names a LIKE scan over the labels JSON got wrong: wildcards, quotes
+        let issues = vec![
+            make_issue(1, "Wildcard", "open", vec!["good_first"]),
+            make_issue(2, "Lookalike", "open", vec!["goodXfirst"]),
+            make_issue(3, "Quoted", "open", vec!["say \"hi\""]),
+        ];
+        save_issues(&conn, "owner/repo", &issues).unwrap();
+
+        let numbers = |label: &str| -> Vec<u64> {
+            let issues = load_issues_filtered(&conn, "owner/repo", Some(label), None).unwrap();
+            issues.iter().map(|i| i.number).collect()
+        };
+        assert_eq!(numbers("good_first"), vec![1]);
+        assert_eq!(numbers("good%"), Vec::<u64>::new());
+        assert_eq!(numbers("say \"hi\""), vec![3]);
+    }
+
+    #[test]
+    fn test_label_filter_uses_index() {
+        let conn = test_db();
+        let plan: Vec<String> = conn
+            .prepare(
+                "EXPLAIN QUERY PLAN SELECT issue_number FROM issue_labels WHERE repo = 'owner/repo' AND label IN ('bug')",
+            )
+            .unwrap()
+            .query_map([], |row| row.get(3))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(plan.iter().any(|step| step.contains("idx_issue_labels_lookup")), "{:?}", plan);
+    }
+
+    #[test]
+    fn test_filter_exclusions() {
+        let conn = test_db();
+
+        let mut bot = make_issue(1, "Bump serde", "open", vec!["dependencies"]);
+        bot.author = "dependabot[bot]".to_string();
+        let wontfix = make_issue(2, "Rewrite in Go", "open", vec!["WontFix"]);
+        let kept = make_issue(3, "Real bug", "open", vec!["bug"]);
+        save_issues(&conn, "owner/repo", &[bot, wontfix, kept]).unwrap();
+
+        let filter = IssueFilter {
+            exclude_authors: vec!["Dependabot".to_string()],
+            exclude_labels: vec!["wontfix".to_string()],
+            ..Default::default()
+        };
+        let issues = load_issues_matching(&conn, "owner/repo", &filter).unwrap();
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![3]);
+        assert_eq!(load_issues_matching(&conn, "owner/repo", &IssueFilter::default()).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_filter_by_labels() {
+        let conn = test_db();
+
+        let both = make_issue(1, "Both", "open", vec!["bug", "ui"]);
+        let bug = make_issue(2, "Bug", "open", vec!["Bug"]);
+        let ui = make_issue(3, "UI", "open", vec!["ui", "wontfix"]);
+        // A label that contains another's name as a substring
+        let debug = make_issue(4, "Debugging", "open", vec!["debug-bug"]);
+        save_issues(&conn, "owner/repo", &[both, bug, ui, debug]).unwrap();
+
+        let numbers = |filter: IssueFilter| -> Vec<u64> {
+            load_issues_matching(&conn, "owner/repo", &filter).unwrap().iter().map(|i| i.number).collect()
+        };
+        let labels = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(numbers(IssueFilter { labels: labels(&["bug"]), ..Default::default() }), vec![2, 1]);
+        assert_eq!(numbers(IssueFilter { labels: labels(&["bug", "ui"]), ..Default::default() }), vec![1]);
+        assert_eq!(numbers(IssueFilter { any_labels: labels(&["bug", "ui"]), ..Default::default() }), vec![3, 2, 1]);
+        assert_eq!(
+            numbers(IssueFilter { any_labels: labels(&["ui"]), not_labels: labels(&["WONTFIX"]), ..Default::default() }),
+            vec![1]
+        );
+
+        // Label edits keep the join table in step
+        remove_issue_label(&conn, "owner/repo", 1, "bug").unwrap();
+        add_issue_label(&conn, "owner/repo", 4, "bug").unwrap();
+        assert_eq!(numbers(IssueFilter { labels: labels(&["bug"]), ..Default::default() }), vec![4, 2]);
+    }
+
+    #[test]
+    fn test_issue_labels_backfilled_from_json() {
+        let conn = test_db();
+        // Rows cached before issue_labels, in both label JSON formats
+        conn.execute_batch(
+            r#"DROP TABLE issue_labels;
+             INSERT INTO issues (repo, number, title, state, author, labels, created_at, updated_at)
+             VALUES ('owner/repo', 1, 'Old', 'open', 'a', '["bug"]', '', ''),
+                    ('owner/repo', 2, 'New', 'open', 'a', '[{"name":"bug","color":"fc2929"}]', '', ''),
+                    ('owner/repo', 3, 'None', 'open', 'a', '[]', '', '');"#,
+        )
+        .unwrap();
+        init_schema(&conn).unwrap();
+
+        let issues = load_issues_filtered(&conn, "owner/repo", Some("bug"), None).unwrap();
+        assert_eq!(issues.iter().map(|i| i.number).collect::<Vec<_>>(), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_filter_by_goal_case_insensitive() {
+        let conn = test_db();
+
+        let mut in_release = make_issue(1, "In release", "open", vec![]);
+        in_release.milestone = Some("v1.0".to_string());
+        let mut other = make_issue(2, "Other goal", "open", vec![]);
+        other.milestone = Some("v2.0".to_string());
+        let no_goal = make_issue(3, "No goal", "open", vec![]);
+        save_issues(&conn, "owner/repo", &[in_release, other, no_goal]).unwrap();
+
+        let filter = IssueFilter {
+            goal: Some("V1.0".to_string()),
+            ..Default::default()
+        };
+        let loaded = load_issues_matching(&conn, "owner/repo", &filter).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].title, "In release");
+    }
+
+    #[test]
+    fn test_filter_by_updated_before() {
+        let conn = test_db();
+
+        let old = make_issue(1, "Old", "open", vec![]);
+        let mut recent = make_issue(2, "Recent", "open", vec![]);
+        recent.updated_at = "2024-03-01T12:00:00Z".to_string();
+        save_issues(&conn, "owner/repo", &[old, recent]).unwrap();
+
+        let filter = IssueFilter {
+            updated_before: Some("2024-02-01T00:00:00Z".to_string()),
+            ..Default::default()
+        };
+        let loaded = load_issues_matching(&conn, "owner/repo", &filter).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].title, "Old");
+    }
+
+    // === Goals Tests ===
+
+    // === Backup Tests ===
+
+    #[test]
+    fn test_backup_and_restore_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("isq-backup.db");
+
+        let conn = test_db();
+        set_repo_link(&conn, "/work/api", "github", "acme/api", None).unwrap();
+        add_watched_repo(&conn, "/work/api").unwrap();
+        save_issues(&conn, "acme/api", &[make_issue(1, "Bug", "open", vec!["bug"])]).unwrap();
+        queue_op(&conn, "acme/api", "comment", "{}").unwrap();
+        backup_to(&conn, &path).unwrap();
+
+        assert_eq!(
+            inspect_backup(&path).unwrap(),
+            BackupSummary {
+                repo_links: 1,
+                watched_repos: 1,
+                issues: 1,
+                pending_ops: 1,
+            }
+        );
+
+        let mut restored = test_db();
+        set_repo_link(&restored, "/old", "linear", "ENG/1", None).unwrap();
+        restore_from(&mut restored, &path).unwrap();
+
+        assert!(get_repo_link(&restored, "/old").unwrap().is_none());
+        assert_eq!(get_repo_link(&restored, "/work/api").unwrap().unwrap().forge_repo, "acme/api");
+        assert_eq!(load_issues(&restored, "acme/api").unwrap().len(), 1);
+        assert_eq!(count_pending_ops(&restored, "acme/api").unwrap(), 1);
+    }
+
+    #[test]
+    fn test_inspect_backup_rejects_non_isq_files() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let text = dir.path().join("notes.txt");
+        std::fs::write(&text, "not a database at all, just some text padding it out").unwrap();
+        assert!(inspect_backup(&text).is_err());
+
+        let other = dir.path().join("other.db");
+        Connection::open(&other).unwrap().execute_batch("CREATE TABLE t (x INTEGER)").unwrap();
+        assert!(inspect_backup(&other).is_err());
+    }
+
+    // === Cache Scope Tests ===
+
+    #[test]
+    fn test_backlinks_from_bodies_and_comments() {
+        let conn = test_db();
+        let mut target = make_issue(1, "Login broken", "open", vec![]);
+        target.identifier = Some("ENG-1".to_string());
+        let mut from_body = make_issue(2, "Session refactor", "open", vec![]);
+        from_body.identifier = Some("ENG-2".to_string());
+        from_body.body = Some("Follow-up to ENG-1; unrelated to UTF-8 or #2".to_string());
+        let mut from_comment = make_issue(3, "Cookie expiry", "closed", vec![]);
+        from_comment.identifier = Some("ENG-3".to_string());
+        save_issues(&conn, "ENG/team", &[target, from_body, from_comment]).unwrap();
+        save_comments(
+            &conn,
+            "ENG/team",
+            &[Comment {
+                comment_id: "c1".to_string(),
+                issue_number: 3,
+                body: "Same root cause as #1".to_string(),
+                author: "alice".to_string(),
+                created_at: "2024-01-02T00:00:00Z".to_string(),
+            }],
+        )
+        .unwrap();
+
+        let backlinks = load_backlinks(&conn, "ENG/team", 1).unwrap();
+        let numbers: Vec<u64> = backlinks.iter().map(|b| b.number).collect();
+        assert_eq!(numbers, vec![2, 3]);
+        assert_eq!(backlinks[1].display_id(), "ENG-3");
+        // Self-references and unknown keys are ignored
+        assert!(load_backlinks(&conn, "ENG/team", 2).unwrap().is_empty());
+        assert!(load_backlinks(&conn, "ENG/team", 8).unwrap().is_empty());
+
+        // Editing the body drops what it no longer mentions
+        set_issue_body(&conn, "ENG/team", 2, "Nothing here").unwrap();
+        assert_eq!(load_backlinks(&conn, "ENG/team", 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_drafts_round_trip_per_repo() {
+        let conn = test_db();
+        let labels = vec!["bug".to_string(), "ui".to_string()];
+        let id = create_draft(&conn, "acme/api", "Button misaligned", None, &labels, Some("v1")).unwrap();
+        create_draft(&conn, "acme/web", "Other repo", None, &[], None).unwrap();
+
+        let mut draft = load_draft(&conn, "acme/api", id).unwrap().unwrap();
+        assert_eq!(draft.title, "Button misaligned");
+        assert_eq!(draft.labels, labels);
+        assert_eq!(draft.goal.as_deref(), Some("v1"));
+        assert!(load_draft(&conn, "acme/web", id).unwrap().is_none());
+
+        draft.body = Some("Off by 2px".to_string());
+        draft.labels = vec![];
+        assert!(update_draft(&conn, "acme/api", &draft).unwrap());
+        let drafts = list_drafts(&conn, "acme/api", false).unwrap();
+        assert_eq!(drafts.len(), 1);
+        assert_eq!(drafts[0].body.as_deref(), Some("Off by 2px"));
+        assert!(drafts[0].labels.is_empty());
+
+        assert!(delete_draft(&conn, "acme/api", id).unwrap());
+        assert!(!delete_draft(&conn, "acme/api", id).unwrap());
+        assert_eq!(list_drafts(&conn, "acme/web", false).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_comment_drafts_listed_apart() {
+        let conn = test_db();
+        create_draft(&conn, "acme/api", "An issue", None, &[], None).unwrap();
+        let id = create_comment_draft(&conn, "acme/api", 42, "Can't reproduce on main").unwrap();
+
+        let comments = list_drafts(&conn, "acme/api", true).unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].issue_number, Some(42));
+        assert_eq!(comments[0].body.as_deref(), Some("Can't reproduce on main"));
+        let issues = list_drafts(&conn, "acme/api", false).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].issue_number, None);
+
+        assert!(delete_draft(&conn, "acme/api", id).unwrap());
+        assert!(list_drafts(&conn, "acme/api", true).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_purge_repo_cache_only_touches_one_repo() {
+        let conn = test_db();
+        save_issues(&conn, "acme/api", &[make_issue(1, "A", "open", vec![])]).unwrap();
+        save_issues(&conn, "acme/web", &[make_issue(1, "B", "open", vec![])]).unwrap();
+        save_goals(&conn, "acme/api", &[make_goal("1", "v1")]).unwrap();
+        save_comments(
+            &conn,
+            "acme/api",
+            &[Comment {
+                comment_id: "c1".to_string(),
+                issue_number: 1,
+                body: "hi".to_string(),
+                author: "bob".to_string(),
+                created_at: "2024-01-01T00:00:00Z".to_string(),
+            }],
+        )
+        .unwrap();
+
+        let purged = purge_repo_cache(&conn, "acme/api").unwrap();
+        assert_eq!(purged, CacheSize { issues: 1, comments: 1, goals: 1 });
+        assert!(cache_size(&conn, "acme/api").unwrap().is_empty());
+        assert!(get_sync_state(&conn, "acme/api").unwrap().is_none());
+        assert_eq!(cache_size(&conn, "acme/web").unwrap().issues, 1);
+    }
+
+    #[test]
+    fn test_label_catalog() {
+        let conn = test_db();
+        let def = |name: &str, color: &str| LabelDef {
+            name: name.to_string(),
+            color: Some(color.to_string()),
+            description: Some(format!("About {}", name)),
+        };
+        save_labels(&conn, "acme/api", &[def("bug", "d73a4a"), def("docs", "0075ca")]).unwrap();
+        save_labels(&conn, "acme/api", &[def("bug", "d73a4a"), def("Urgent", "b60205")]).unwrap();
+        let names: Vec<String> = load_labels(&conn, "acme/api").unwrap().into_iter().map(|l| l.name).collect();
+        assert_eq!(names, vec!["bug", "Urgent"]);
+        assert!(load_labels(&conn, "acme/web").unwrap().is_empty());
+
+        // A label no cached issue carries yet still gets its color
+        save_issues(&conn, "acme/api", &[make_issue(1, "One", "open", vec![]), make_issue(2, "Two", "closed", vec![])]).unwrap();
+        add_issue_label(&conn, "acme/api", 1, "urgent").unwrap();
+        add_issue_label(&conn, "acme/api", 2, "urgent").unwrap();
+        let issue = load_issue(&conn, "acme/api", 1).unwrap().unwrap();
+        assert_eq!(issue.labels[0].color.as_deref(), Some("b60205"));
+        assert_eq!(count_open_issues_by_label(&conn, "acme/api").unwrap(), HashMap::from([("urgent".to_string(), 1)]));
+    }
+
+    #[test]
+    fn test_estimates_and_cycles() {
+        let conn = test_db();
+        let mut estimated = make_issue(1, "Estimated", "open", vec![]);
+        estimated.estimate = Some(2.5);
+        estimated.cycle = Some(7);
+        save_issues(&conn, "team", &[estimated, make_issue(2, "Unestimated", "open", vec![])]).unwrap();
+        let loaded = load_issue(&conn, "team", 1).unwrap().unwrap();
+        assert_eq!((loaded.estimate, loaded.cycle), (Some(2.5), Some(7)));
+        assert_eq!(load_issue(&conn, "team", 2).unwrap().unwrap().estimate, None);
+
+        let cycle = |number: u32| Cycle {
+            number,
+            name: None,
+            starts_at: "2025-06-02T00:00:00Z".to_string(),
+            ends_at: "2025-06-16T00:00:00Z".to_string(),
+        };
+        save_cycles(&conn, "team", &[cycle(8), cycle(7)]).unwrap();
+        let numbers: Vec<u32> = load_cycles(&conn, "team").unwrap().iter().map(|c| c.number).collect();
+        assert_eq!(numbers, vec![7, 8]);
+        purge_repo_cache(&conn, "team").unwrap();
+        assert!(load_cycles(&conn, "team").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_workspace_issues_keep_repo_caches() {
+        let conn = test_db();
+        let workspace = "search:user:acme assignee:@me";
+        save_issues(&conn, "acme/api", &[make_issue(1, "Old", "open", vec![]), make_issue(2, "Other", "open", vec![])]).unwrap();
+
+        let hits = vec![
+            ("acme/api".to_string(), make_issue(1, "Renamed", "open", vec![])),
+            ("acme/web".to_string(), make_issue(1, "Web", "open", vec![])),
+        ];
+        save_workspace_issues(&conn, workspace, &hits).unwrap();
+        let found = load_workspace_issues(&conn, workspace).unwrap();
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().any(|(repo, issue)| repo == "acme/api" && issue.title == "Renamed"));
+        // The rest of acme/api's cache is untouched
+        assert_eq!(cache_size(&conn, "acme/api").unwrap().issues, 2);
+        assert!(get_sync_state(&conn, workspace).unwrap().is_some());
+
+        // Issues the search no longer finds leave the workspace
+        save_workspace_issues(&conn, workspace, &hits[1..]).unwrap();
+        let found = load_workspace_issues(&conn, workspace).unwrap();
+        assert_eq!(found.iter().map(|(repo, _)| repo.as_str()).collect::<Vec<_>>(), vec!["acme/web"]);
+
+        purge_repo_cache(&conn, workspace).unwrap();
+        assert!(load_workspace_issues(&conn, workspace).unwrap().is_empty());
+    }
+
+    // === Status Counts Tests ===
+
+    #[test]
+    fn test_identity_roundtrip() {
+        let conn = test_db();
+        assert_eq!(get_identity(&conn, "github").unwrap(), None);
+
+        set_identity(&conn, "github", "alice").unwrap();
+        set_identity(&conn, "github", "alice2").unwrap();
+        assert_eq!(get_identity(&conn, "github").unwrap(), Some("alice2".to_string()));
+    }
+
+    #[test]
+    fn test_branch_issue_round_trip() {
+        let conn = test_db();
+        assert_eq!(get_branch_issue(&conn, "acme/api", "fix-login").unwrap(), None);
+
+        set_branch_issue(&conn, "acme/api", "fix-login", 7).unwrap();
+        set_branch_issue(&conn, "acme/api", "fix-login", 8).unwrap();
+        assert_eq!(get_branch_issue(&conn, "acme/api", "fix-login").unwrap(), Some(8));
+        assert_eq!(get_branch_issue(&conn, "acme/web", "fix-login").unwrap(), None);
+
+        assert!(clear_branch_issue(&conn, "acme/api", "fix-login").unwrap());
+        assert!(!clear_branch_issue(&conn, "acme/api", "fix-login").unwrap());
+        assert_eq!(get_branch_issue(&conn, "acme/api", "fix-login").unwrap(), None);
+    }
+
+    #[test]
+    fn test_issue_counts() {
+        let conn = test_db();
+
+        let mut mine = make_issue(1, "Mine", "open", vec![]);
+        mine.assignees = vec!["alice".to_string()];
+        mine.milestone = Some("Past".to_string());
+        let mut late = make_issue(2, "Late", "open", vec![]);
+        late.milestone = Some("Past".to_string());
+        let mut future = make_issue(3, "Future", "open", vec![]);
+        future.milestone = Some("Later".to_string());
+        let mut closed = make_issue(4, "Done", "closed", vec![]);
+        closed.assignees = vec!["alice".to_string()];
+        closed.milestone = Some("Past".to_string());
+        save_issues(&conn, "owner/repo", &[mine, late, future, closed]).unwrap();
+
+        let mut past = make_goal("1", "Past");
+        past.target_date = Some("2024-01-01T08:00:00Z".to_string());
+        let mut later = make_goal("2", "Later");
+        later.target_date = Some("2099-01-01".to_string());
+        save_goals(&conn, "owner/repo", &[past, later]).unwrap();
+
+        add_inbox_event(&conn, "owner/repo", "mention", "You were mentioned in #1").unwrap();
+        add_inbox_event(&conn, "other/repo", "mention", "Elsewhere").unwrap();
+
+        let counts = issue_counts(&conn, "owner/repo", Some("alice"), "2024-06-01").unwrap();
+        assert_eq!(
+            counts,
+            IssueCounts {
+                open: 3,
+                assigned_to_me: Some(1),
+                overdue: 2,
+                unread_inbox: 1,
+            }
+        );
+
+        let anonymous = issue_counts(&conn, "owner/repo", None, "2024-06-01").unwrap();
+        assert_eq!(anonymous.assigned_to_me, None);
+    }
+
+    #[test]
+    fn test_inbox_mark_read() {
+        let conn = test_db();
+        add_inbox_event(&conn, "owner/repo", "op_failed", "Gave up on a comment on #1").unwrap();
+        add_inbox_event(&conn, "owner/repo", "goal_at_risk", "v2 is at risk").unwrap();
+        add_inbox_event(&conn, "other/repo", "goal_at_risk", "Elsewhere").unwrap();
+
+        let unread = load_inbox_events(&conn, "owner/repo", false).unwrap();
+        assert_eq!(unread.iter().map(|e| e.kind.as_str()).collect::<Vec<_>>(), vec!["goal_at_risk", "op_failed"]);
+
+        assert_eq!(mark_inbox_read(&conn, "owner/repo").unwrap(), 2);
+        assert_eq!(count_unread_inbox(&conn, "owner/repo").unwrap(), 0);
+        assert!(load_inbox_events(&conn, "owner/repo", false).unwrap().is_empty());
+        let all = load_inbox_events(&conn, "owner/repo", true).unwrap();
+        assert!(all.iter().all(|e| e.read_at.is_some()));
+        assert_eq!(mark_inbox_read(&conn, "owner/repo").unwrap(), 0);
+        // Other repos' events stay unread
+        assert_eq!(count_unread_inbox(&conn, "other/repo").unwrap(), 1);
+    }
+
+    #[test]
+    fn test_claim_goal_reminder() {
+        let conn = test_db();
+        assert!(claim_goal_reminder(&conn, "owner/repo", "g1", "2024-03-01").unwrap());
+        assert!(!claim_goal_reminder(&conn, "owner/repo", "g1", "2024-03-01").unwrap());
+        // Another repo's goal with the same ID is separate
+        assert!(claim_goal_reminder(&conn, "other/repo", "g1", "2024-03-01").unwrap());
+        // Moving the target date re-arms the reminder
+        assert!(claim_goal_reminder(&conn, "owner/repo", "g1", "2024-04-01").unwrap());
+        assert!(!claim_goal_reminder(&conn, "owner/repo", "g1", "2024-04-01").unwrap());
+    }
+
+    #[test]
+    fn test_cleanup_keeps_remote_links() {
+        let conn = test_db();
+        let remote = remote_repo_path("github", "acme/api");
+        assert!(is_remote_repo_path(&remote));
+        assert!(!is_remote_repo_path("/home/me/api"));
+
+        add_watched_repo(&conn, &remote).unwrap();
+        add_watched_repo(&conn, "/nonexistent/isq-test-path").unwrap();
+
+        assert_eq!(cleanup_stale_repos(&conn).unwrap().removed, 1);
+        let watched = list_watched_repos(&conn).unwrap();
+        assert_eq!(watched.len(), 1);
+        assert_eq!(watched[0].repo, remote);
+    }
+
+    #[test]
+    fn test_cleanup_keeps_links_with_pending_ops() {
+        let conn = test_db();
+        set_repo_link(&conn, "/gone/api", "github", "acme/api", None).unwrap();
+        add_watched_repo(&conn, "/gone/api").unwrap();
+        set_repo_link(&conn, "/gone/web", "github", "acme/web", None).unwrap();
+        add_watched_repo(&conn, "/gone/web").unwrap();
+        queue_op(&conn, "acme/api", "comment", "{}").unwrap();
+
+        let result = cleanup_stale_repos(&conn).unwrap();
+        assert_eq!(result.removed, 1);
+        assert_eq!(result.kept, vec![("/gone/api".to_string(), 1)]);
+        assert!(get_repo_link(&conn, "/gone/api").unwrap().is_some());
+        assert!(get_repo_link(&conn, "/gone/web").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_ops_at_risk_ignores_shared_forge_repo() {
+        let conn = test_db();
+        set_repo_link(&conn, "/a/api", "github", "acme/api", None).unwrap();
+        queue_op(&conn, "acme/api", "close", r#"{"issue_number":7}"#).unwrap();
+
+        let at_risk = ops_at_risk(&conn, "/a/api").unwrap();
+        assert_eq!(at_risk.len(), 1);
+        assert_eq!(at_risk[0].summary(), "close #7");
+
+        // A second checkout of the same repo will still replay the queue
+        set_repo_link(&conn, "/b/api", "github", "acme/api", None).unwrap();
+        assert!(ops_at_risk(&conn, "/a/api").unwrap().is_empty());
+        assert!(ops_at_risk(&conn, "/not/linked").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pending_ops_by_issue() {
+        let conn = test_db();
+        queue_op(&conn, "acme/api", "close", r#"{"issue_number":7}"#).unwrap();
+        queue_op(&conn, "acme/api", "label_add", r#"{"issue_number":7,"label":"bug"}"#).unwrap();
+        queue_op(&conn, "acme/api", "create", r#"{"title":"New"}"#).unwrap();
+        queue_op(&conn, "acme/web", "close", r#"{"issue_number":7}"#).unwrap();
+
+        let by_issue = pending_ops_by_issue(&conn, "acme/api").unwrap();
+        assert_eq!(by_issue.len(), 1);
+        let actions: Vec<_> = by_issue[&7].iter().map(|op| op.action()).collect();
+        assert_eq!(actions, vec!["close", "add label bug"]);
+        assert_eq!(by_issue[&7][0].to_json()["op"], "close");
     }
 
     #[test]
-    fn test_schema_is_idempotent() {
+    fn test_local_updates_apply_to_cached_issue() {
         let conn = test_db();
-        // Running init_schema again should not error
-        init_schema(&conn).unwrap();
-        init_schema(&conn).unwrap();
+        let mut bug = make_issue(2, "Colored", "open", vec![]);
+        bug.labels = vec![Label {
+            name: "bug".to_string(),
+            color: Some("fc2929".to_string()),
+        }];
+        save_issues(&conn, "acme/api", &[make_issue(1, "Target", "open", vec!["docs"]), bug]).unwrap();
+
+        assert!(set_issue_state(&conn, "acme/api", 1, "closed").unwrap());
+        assert!(add_issue_label(&conn, "acme/api", 1, "bug").unwrap());
+        assert!(remove_issue_label(&conn, "acme/api", 1, "docs").unwrap());
+        assert!(add_issue_assignee(&conn, "acme/api", 1, "alice", false).unwrap());
+        assert!(add_issue_assignee(&conn, "acme/api", 1, "bob", false).unwrap());
+
+        let issue = load_issue(&conn, "acme/api", 1).unwrap().unwrap();
+        assert_eq!(issue.state, "closed");
+        assert_eq!(issue.labels.len(), 1);
+        assert_eq!(issue.labels[0].color.as_deref(), Some("fc2929"));
+        assert_eq!(issue.assignees, vec!["alice", "bob"]);
+        assert!(set_issue_body(&conn, "acme/api", 1, "- [x] done").unwrap());
+        assert_eq!(load_issue(&conn, "acme/api", 1).unwrap().unwrap().body.as_deref(), Some("- [x] done"));
+        assert!(set_issue_goal(&conn, "acme/api", 1, "v2.0").unwrap());
+        assert_eq!(load_issue(&conn, "acme/api", 1).unwrap().unwrap().milestone.as_deref(), Some("v2.0"));
+        assert_ne!(issue.updated_at, "2024-01-01T00:00:00Z");
+
+        add_issue_assignee(&conn, "acme/api", 1, "carol", true).unwrap();
+        assert_eq!(load_issue(&conn, "acme/api", 1).unwrap().unwrap().assignees, vec!["carol"]);
+
+        // Uncached issues are left alone
+        assert!(!set_issue_state(&conn, "acme/api", 99, "closed").unwrap());
+        assert!(!add_issue_label(&conn, "acme/api", 99, "bug").unwrap());
     }
 
-    // === Pending Ops Tests ===
-
     #[test]
-    fn test_queue_and_load_pending_ops() {
+    fn test_upsert_issue_and_add_comment() {
         let conn = test_db();
+        save_issues(&conn, "acme/api", &[make_issue(1, "Old", "open", vec![])]).unwrap();
+        upsert_issue(&conn, "acme/api", &make_issue(1, "Renamed", "open", vec![])).unwrap();
+        upsert_issue(&conn, "acme/api", &make_issue(2, "New", "open", vec![])).unwrap();
 
-        let id = queue_op(&conn, "owner/repo", "create", r#"{"title":"test"}"#).unwrap();
-        assert!(id > 0);
+        let issues = load_issues(&conn, "acme/api").unwrap();
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[1].title, "Renamed");
 
-        let ops = load_pending_ops(&conn, "owner/repo").unwrap();
-        assert_eq!(ops.len(), 1);
-        assert_eq!(ops[0].op_type, "create");
-        assert_eq!(ops[0].payload, r#"{"title":"test"}"#);
+        add_comment(
+            &conn,
+            "acme/api",
+            &Comment {
+                comment_id: "local-1".to_string(),
+                issue_number: 2,
+                body: "Looks good".to_string(),
+                author: "alice".to_string(),
+                created_at: "2024-01-02T00:00:00Z".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(load_comments(&conn, "acme/api", 2).unwrap()[0].body, "Looks good");
+        assert_eq!(count_comments_by_issue(&conn, "acme/api").unwrap(), HashMap::from([(2, 1)]));
     }
 
     #[test]
-    fn test_pending_ops_ordered_by_id() {
+    fn test_last_synced_at() {
         let conn = test_db();
+        assert!(last_synced_at(&conn, "acme/api").unwrap().is_none());
 
-        queue_op(&conn, "owner/repo", "create", "first").unwrap();
-        queue_op(&conn, "owner/repo", "comment", "second").unwrap();
-        queue_op(&conn, "owner/repo", "close", "third").unwrap();
-
-        let ops = load_pending_ops(&conn, "owner/repo").unwrap();
-        assert_eq!(ops.len(), 3);
-        assert_eq!(ops[0].op_type, "create");
-        assert_eq!(ops[1].op_type, "comment");
-        assert_eq!(ops[2].op_type, "close");
+        save_issues(&conn, "acme/api", &[]).unwrap();
+        let at = last_synced_at(&conn, "acme/api").unwrap().unwrap();
+        assert!((chrono::Utc::now() - at).num_seconds().abs() < 60);
     }
 
     #[test]
-    fn test_pending_ops_isolated_by_repo() {
+    fn test_diff_sync() {
         let conn = test_db();
+        let comment = |id: &str| Comment {
+            comment_id: id.to_string(),
+            issue_number: 1,
+            body: "hi".to_string(),
+            author: "alice".to_string(),
+            created_at: "2024-01-02T00:00:00Z".to_string(),
+        };
+        let cached = [
+            make_issue(1, "Same", "open", vec![]),
+            make_issue(2, "Edited", "open", vec![]),
+            make_issue(3, "Closing", "open", vec![]),
+            make_issue(4, "Deleted", "open", vec![]),
+        ];
+        save_issues(&conn, "acme/api", &cached).unwrap();
+        save_comments(&conn, "acme/api", &[comment("a")]).unwrap();
+        save_goals(&conn, "acme/api", &[make_goal("g1", "v1")]).unwrap();
+
+        let mut edited = make_issue(2, "Edited", "open", vec![]);
+        edited.updated_at = "2024-02-01T00:00:00Z".to_string();
+        let fetched = [
+            make_issue(1, "Same", "open", vec![]),
+            edited,
+            make_issue(3, "Closing", "closed", vec![]),
+            make_issue(5, "New", "open", vec![]),
+        ];
+        let diff = diff_sync(
+            &conn,
+            "acme/api",
+            &fetched,
+            &[comment("a"), comment("b")],
+            &[make_goal("g1", "v1"), make_goal("g2", "v2")],
+        )
+        .unwrap();
 
-        queue_op(&conn, "repo-a", "create", "a").unwrap();
-        queue_op(&conn, "repo-b", "create", "b").unwrap();
-
-        let ops_a = load_pending_ops(&conn, "repo-a").unwrap();
-        let ops_b = load_pending_ops(&conn, "repo-b").unwrap();
-
-        assert_eq!(ops_a.len(), 1);
-        assert_eq!(ops_b.len(), 1);
-        assert_eq!(ops_a[0].payload, "a");
-        assert_eq!(ops_b[0].payload, "b");
+        let numbers = |issues: &[ChangedIssue]| issues.iter().map(|i| i.number).collect::<Vec<_>>();
+        assert_eq!(numbers(&diff.new_issues), vec![5]);
+        assert_eq!(numbers(&diff.updated), vec![2]);
+        assert_eq!(numbers(&diff.closed), vec![3]);
+        assert_eq!(numbers(&diff.removed), vec![4]);
+        assert_eq!(diff.new_comments, 1);
+        assert_eq!(diff.new_goals, vec!["v2"]);
+
+        // Nothing was written
+        assert_eq!(load_issues(&conn, "acme/api").unwrap().len(), 4);
+        assert!(diff_sync(&conn, "acme/api", &cached, &[comment("a")], &[make_goal("g1", "v1")]).unwrap().is_empty());
     }
 
     #[test]
-    fn test_complete_op_removes_from_queue() {
+    fn test_comment_counts_survive_resync() {
         let conn = test_db();
+        let comment = |id: &str, number: u64| Comment {
+            comment_id: id.to_string(),
+            issue_number: number,
+            body: "hi".to_string(),
+            author: "alice".to_string(),
+            created_at: "2024-01-02T00:00:00Z".to_string(),
+        };
+
+        // Comments can arrive before their issues (sync order) and vice versa
+        save_comments(&conn, "acme/api", &[comment("a", 1), comment("b", 1), comment("c", 3)]).unwrap();
+        save_issues(&conn, "acme/api", &[make_issue(1, "One", "open", vec![]), make_issue(2, "Two", "open", vec![])]).unwrap();
+        assert_eq!(count_comments_by_issue(&conn, "acme/api").unwrap(), HashMap::from([(1, 2)]));
+
+        save_comments(&conn, "acme/api", &[comment("d", 2)]).unwrap();
+        assert_eq!(count_comments_by_issue(&conn, "acme/api").unwrap(), HashMap::from([(2, 1)]));
+
+        // Re-adding a known comment doesn't double count
+        add_comment(&conn, "acme/api", &comment("d", 2)).unwrap();
+        assert_eq!(count_comments_by_issue(&conn, "acme/api").unwrap(), HashMap::from([(2, 1)]));
+
+        assert_eq!(issues_commented_by(&conn, "acme/api", "Alice").unwrap(), HashSet::from([2]));
+        assert!(issues_commented_by(&conn, "acme/api", "bob").unwrap().is_empty());
+
+        // Participants: author, assignees, commenters
+        let mut assigned = make_issue(1, "One", "open", vec![]);
+        assigned.assignees = vec!["carol".to_string()];
+        upsert_issue(&conn, "acme/api", &assigned).unwrap();
+        let participating = |user: &str| {
+            let filter = IssueFilter { participant: Some(user.to_string()), ..Default::default() };
+            load_issues_matching(&conn, "acme/api", &filter).unwrap().into_iter().map(|i| i.number).collect::<Vec<_>>()
+        };
+        assert_eq!(participating("carol"), vec![1]);
+        assert_eq!(participating("ALICE"), vec![2]);
+        assert!(participating("dave").is_empty());
+        assert_eq!(load_participants(&conn, "acme/api", 2).unwrap(), vec!["testuser", "alice"]);
+
+        // Unanswered: no comments, or none by a maintainer
+        let unanswered = |maintainers: &[&str]| {
+            let maintainers = maintainers.iter().map(|m| m.to_string()).collect();
+            let filter = IssueFilter { unanswered: Some(maintainers), ..Default::default() };
+            load_issues_matching(&conn, "acme/api", &filter).unwrap().into_iter().map(|i| i.number).collect::<Vec<_>>()
+        };
+        assert_eq!(unanswered(&[]), vec![1]);
+        assert_eq!(unanswered(&["Alice"]), vec![1]);
+        assert_eq!(unanswered(&["bob"]), vec![2, 1]);
+    }
 
-        let id1 = queue_op(&conn, "owner/repo", "create", "first").unwrap();
-        let id2 = queue_op(&conn, "owner/repo", "comment", "second").unwrap();
-
-        complete_op(&conn, id1).unwrap();
-
-        let ops = load_pending_ops(&conn, "owner/repo").unwrap();
-        assert_eq!(ops.len(), 1);
-        assert_eq!(ops[0].id, id2);
+    #[test]
+    fn test_save_fetched_issue_leaves_other_issues() {
+        let conn = test_db();
+        let comment = |id: &str, number: u64, body: &str| Comment {
+            comment_id: id.to_string(),
+            issue_number: number,
+            body: body.to_string(),
+            author: "alice".to_string(),
+            created_at: "2024-01-02T00:00:00Z".to_string(),
+        };
+        save_issues(&conn, "acme/api", &[make_issue(1, "One", "open", vec![]), make_issue(2, "Two", "open", vec![])]).unwrap();
+        save_comments(&conn, "acme/api", &[comment("a", 1, "see #2"), comment("b", 2, "hi")]).unwrap();
+
+        queue_op(&conn, "acme/api", "close", r#"{"issue_number":1}"#).unwrap();
+
+        save_fetched_issue(&conn, "acme/api", &make_issue(1, "One", "open", vec![]), &[comment("c", 1, "fixed")]).unwrap();
+        assert_eq!(load_issue(&conn, "acme/api", 1).unwrap().unwrap().state, "closed");
+        let ids = |number| load_comments(&conn, "acme/api", number).unwrap().into_iter().map(|c| c.comment_id).collect::<Vec<_>>();
+        assert_eq!(ids(1), vec!["c"]);
+        assert_eq!(ids(2), vec!["b"]);
+        assert!(load_backlinks(&conn, "acme/api", 2).unwrap().is_empty());
+        assert_eq!(count_comments_by_issue(&conn, "acme/api").unwrap(), HashMap::from([(1, 1), (2, 1)]));
     }
 
     #[test]
-    fn test_count_pending_ops() {
+    fn test_search_comments() {
         let conn = test_db();
+        let comment = |id: &str, number: u64, author: &str, body: &str| Comment {
+            comment_id: id.to_string(),
+            issue_number: number,
+            body: body.to_string(),
+            author: author.to_string(),
+            created_at: "2024-01-02T00:00:00Z".to_string(),
+        };
+        save_issues(&conn, "acme/api", &[make_issue(1, "Pick a database", "open", vec![])]).unwrap();
+        save_comments(
+            &conn,
+            "acme/api",
+            &[
+                comment("a", 1, "alice", "We decided to use SQLite for the cache."),
+                comment("b", 2, "bob", "Still deciding on C++ vs Rust."),
+                comment("c", 1, "bob", "Unrelated note"),
+            ],
+        )
+        .unwrap();
+        save_comments(&conn, "acme/web", &[comment("z", 1, "alice", "decide later")]).unwrap();
+
+        let ids = |hits: Vec<CommentHit>| hits.into_iter().map(|h| h.comment_id).collect::<Vec<_>>();
+        let search = |query: &str, author: Option<&str>, issue: Option<u64>| {
+            ids(search_comments(&conn, "acme/api", query, author, issue, 10).unwrap())
+        };
+
+        // Stemmed, all words required, scoped to the repo
+        assert_eq!(search("decide", None, None).len(), 2);
+        assert_eq!(search("decided sqlite", None, None), vec!["a"]);
+        assert_eq!(search("decide", Some("BOB"), None), vec!["b"]);
+        assert_eq!(search("decide", None, Some(1)), vec!["a"]);
+        // Punctuation is searched for, not parsed as query syntax
+        assert_eq!(search("C++ \"Rust", None, None), vec!["b"]);
+        assert!(search("   ", None, None).is_empty());
+
+        let hit = &search_comments(&conn, "acme/api", "sqlite", None, None, 10).unwrap()[0];
+        assert_eq!(hit.issue_title.as_deref(), Some("Pick a database"));
+        assert!(hit.snippet.contains(&format!("{}SQLite{}", MATCH_START, MATCH_END)));
+
+        // The index follows edits and full replacements
+        add_comment(&conn, "acme/api", &comment("a", 1, "alice", "Switched to Postgres")).unwrap();
+        assert!(search("sqlite", None, None).is_empty());
+        assert_eq!(search("postgres", None, None), vec!["a"]);
+        save_comments(&conn, "acme/api", &[comment("d", 3, "carol", "nothing relevant")]).unwrap();
+        assert!(search("postgres", None, None).is_empty());
+    }
 
-        assert_eq!(count_pending_ops(&conn, "owner/repo").unwrap(), 0);
+    #[test]
+    fn test_search_issues_and_goals() {
+        let conn = test_db();
+        let mut crash = make_issue(1, "Crash on save", "open", vec![]);
+        crash.body = Some("Saving a large file panics".to_string());
+        save_issues(&conn, "acme/api", &[crash, make_issue(2, "Dark mode", "open", vec![])]).unwrap();
+        save_issues(&conn, "acme/web", &[make_issue(1, "Crash on load", "open", vec![])]).unwrap();
+
+        let numbers = |query: &str| {
+            search_issues_text(&conn, "acme/api", query, 10).unwrap().into_iter().map(|h| h.number).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers("crash"), vec![1]);
+        // Bodies are searched too, stemmed
+        assert_eq!(numbers("panic"), vec![1]);
+        let hit = &search_issues_text(&conn, "acme/api", "dark", 10).unwrap()[0];
+        assert!(hit.snippet.contains(&format!("{}Dark{}", MATCH_START, MATCH_END)));
+
+        // Title edits reach the index; other updates leave it alone
+        set_issue_state(&conn, "acme/api", 2, "closed").unwrap();
+        upsert_issue(&conn, "acme/api", &make_issue(2, "Light theme", "closed", vec![])).unwrap();
+        assert!(numbers("dark").is_empty());
+        assert_eq!(numbers("light"), vec![2]);
+
+        let mut goal = make_goal("g1", "Q3 Release");
+        goal.description = Some("Ship the offline sync work".to_string());
+        save_goals(&conn, "acme/api", &[goal, make_goal("g2", "Docs")]).unwrap();
+        let goals = |query: &str| {
+            search_goals(&conn, "acme/api", query, 10).unwrap().into_iter().map(|h| h.goal_id).collect::<Vec<_>>()
+        };
+        assert_eq!(goals("offline"), vec!["g1"]);
+        assert_eq!(goals("docs"), vec!["g2"]);
+        save_goals(&conn, "acme/api", &[make_goal("g2", "Docs")]).unwrap();
+        assert!(goals("offline").is_empty());
+    }
 
-        queue_op(&conn, "owner/repo", "create", "1").unwrap();
-        assert_eq!(count_pending_ops(&conn, "owner/repo").unwrap(), 1);
+    #[test]
+    fn test_load_latest_comments() {
+        let conn = test_db();
+        let comments: Vec<Comment> = (1..=5)
+            .map(|i| Comment {
+                comment_id: format!("c{}", i),
+                issue_number: 1,
+                body: format!("comment {}", i),
+                author: "alice".to_string(),
+                created_at: format!("2024-01-0{}T00:00:00Z", i),
+            })
+            .collect();
+        save_comments(&conn, "acme/api", &comments).unwrap();
 
-        queue_op(&conn, "owner/repo", "create", "2").unwrap();
-        assert_eq!(count_pending_ops(&conn, "owner/repo").unwrap(), 2);
+        let (latest, total) = load_latest_comments(&conn, "acme/api", 1, Some(2)).unwrap();
+        assert_eq!(total, 5);
+        let bodies: Vec<_> = latest.iter().map(|c| c.body.as_str()).collect();
+        assert_eq!(bodies, vec!["comment 4", "comment 5"]);
 
-        queue_op(&conn, "other/repo", "create", "3").unwrap();
-        assert_eq!(count_pending_ops(&conn, "owner/repo").unwrap(), 2);
-        assert_eq!(count_pending_ops(&conn, "other/repo").unwrap(), 1);
+        let (all, _) = load_latest_comments(&conn, "acme/api", 1, None).unwrap();
+        assert_eq!(all.len(), 5);
+        assert_eq!(all[0].body, "comment 1");
     }
 
-    // === Label Parsing Tests ===
-
     #[test]
-    fn test_parse_labels_json_new_format() {
-        let json = r##"[{"name":"bug","color":"#fc2929"},{"name":"feature","color":"#4EA7FC"}]"##;
-        let labels = parse_labels_json(json);
-        assert_eq!(labels.len(), 2);
-        assert_eq!(labels[0].name, "bug");
-        assert_eq!(labels[0].color, Some("#fc2929".to_string()));
-        assert_eq!(labels[1].name, "feature");
-        assert_eq!(labels[1].color, Some("#4EA7FC".to_string()));
+    fn test_pending_op_summary() {
+        let op = |op_type: &str, payload: &str| PendingOp {
+            id: 1,
+            repo: "acme/api".to_string(),
+            op_type: op_type.to_string(),
+            payload: payload.to_string(),
+            created_at: String::new(),
+            last_error: None,
+            dedup_key: String::new(),
+        };
+        assert_eq!(op("create", r#"{"title":"Fix login"}"#).summary(), "create \"Fix login\"");
+        assert_eq!(op("comment", r#"{"issue_number":12,"body":"hi"}"#).summary(), "comment on #12");
+        assert_eq!(op("label_add", r#"{"issue_number":3,"label":"bug"}"#).summary(), "add label bug to #3");
+        assert_eq!(op("unsubscribe", r#"{"issue_number":5}"#).summary(), "unsubscribe from #5");
+        assert_eq!(op("mystery", "not json").summary(), "mystery");
     }
 
     #[test]
-    fn test_parse_labels_json_old_format() {
-        let json = r#"["bug","enhancement"]"#;
-        let labels = parse_labels_json(json);
-        assert_eq!(labels.len(), 2);
-        assert_eq!(labels[0].name, "bug");
-        assert_eq!(labels[0].color, None);
-        assert_eq!(labels[1].name, "enhancement");
-        assert_eq!(labels[1].color, None);
+    fn test_find_and_rebind_orphaned_link() {
+        let conn = test_db();
+        let fingerprint = RepoFingerprint {
+            remote_url: Some("git@github.com:acme/api.git".to_string()),
+            root_commit: Some("abc123".to_string()),
+        };
+        set_repo_link(&conn, "/old/api", "github", "acme/api", None).unwrap();
+        set_repo_fingerprint(&conn, "/old/api", &fingerprint).unwrap();
+        add_watched_repo(&conn, "/old/api").unwrap();
+        set_repo_link(&conn, "/old/web", "github", "acme/web", None).unwrap();
+
+        let orphans = find_orphaned_links(&conn, &fingerprint).unwrap();
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].0, "/old/api");
+
+        rebind_repo_link(&conn, "/old/api", "/new/api").unwrap();
+        let link = get_repo_link(&conn, "/new/api").unwrap().unwrap();
+        assert_eq!(link.root_commit.as_deref(), Some("abc123"));
+        assert_eq!(list_watched_repos(&conn).unwrap()[0].repo, "/new/api");
+        assert!(get_repo_link(&conn, "/old/api").unwrap().is_none());
     }
 
     #[test]
-    fn test_parse_labels_json_empty() {
-        assert!(parse_labels_json("[]").is_empty());
-        assert!(parse_labels_json("").is_empty());
-        assert!(parse_labels_json("invalid").is_empty());
-    }
+    fn test_list_repo_links() {
+        let conn = test_db();
+        set_repo_link(&conn, "/b", "linear", "ENG/123", Some("ENG")).unwrap();
+        set_repo_link(&conn, "/a", "github", "owner/repo", None).unwrap();
 
-    // === Issues Tests ===
+        let links = list_repo_links(&conn).unwrap();
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].0, "/a");
+        assert_eq!(links[1].1.display_name.as_deref(), Some("ENG"));
+    }
 
-    fn make_issue(number: u64, title: &str, state: &str, labels: Vec<&str>) -> Issue {
-        Issue {
-            number,
-            title: title.to_string(),
-            body: None,
-            state: state.to_string(),
-            author: "testuser".to_string(),
-            labels: labels.into_iter().map(|s| Label::name_only(s.to_string())).collect(),
+    fn make_goal(id: &str, name: &str) -> Goal {
+        Goal {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: None,
+            target_date: None,
+            state: GoalState::Open,
+            progress: 0.0,
+            open_count: None,
+            closed_count: None,
             created_at: "2024-01-01T00:00:00Z".to_string(),
             updated_at: "2024-01-01T00:00:00Z".to_string(),
-            url: None,
-            milestone: None,
+            html_url: None,
         }
     }
 
     #[test]
-    fn test_save_and_load_issues() {
+    fn test_resolve_goal_name() {
         let conn = test_db();
 
-        let issues = vec![
-            make_issue(1, "First", "open", vec![]),
-            make_issue(2, "Second", "open", vec!["bug"]),
-        ];
+        save_goals(&conn, "owner/repo", &[make_goal("1", "Q1 Release")]).unwrap();
 
-        save_issues(&conn, "owner/repo", &issues).unwrap();
+        assert_eq!(
+            resolve_goal_name(&conn, "owner/repo", "q1 release").unwrap(),
+            Some("Q1 Release".to_string())
+        );
+        assert_eq!(resolve_goal_name(&conn, "owner/repo", "Q2").unwrap(), None);
+        assert_eq!(resolve_goal_name(&conn, "other/repo", "Q1 Release").unwrap(), None);
+    }
 
-        let loaded = load_issues(&conn, "owner/repo").unwrap();
-        assert_eq!(loaded.len(), 2);
-        // Ordered by number DESC
-        assert_eq!(loaded[0].number, 2);
-        assert_eq!(loaded[1].number, 1);
+    #[test]
+    fn test_objectives_round_trip() {
+        let conn = test_db();
+
+        let objective = Objective {
+            id: "init-1".to_string(),
+            name: "Q3".to_string(),
+            description: None,
+            target_date: Some("2024-09-30".to_string()),
+            state: GoalState::Open,
+            goal_ids: vec!["2".to_string(), "1".to_string()],
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            html_url: None,
+        };
+        save_objectives(&conn, "owner/repo", std::slice::from_ref(&objective)).unwrap();
+        link_objective_goal(&conn, "owner/repo", "init-1", "3").unwrap();
+        link_objective_goal(&conn, "owner/repo", "init-1", "3").unwrap();
+
+        let loaded = load_objective_by_name(&conn, "owner/repo", "q3").unwrap().unwrap();
+        assert_eq!(loaded.id, "init-1");
+        assert_eq!(loaded.goal_ids, vec!["1", "2", "3"]);
+        assert!(load_objective_by_name(&conn, "other/repo", "Q3").unwrap().is_none());
+
+        // A full save replaces the links too
+        save_objectives(&conn, "owner/repo", &[objective]).unwrap();
+        assert_eq!(load_objectives(&conn, "owner/repo").unwrap()[0].goal_ids, vec!["1", "2"]);
     }
 
     #[test]
-    fn test_save_issues_replaces_existing() {
+    fn test_goal_snapshots() {
         let conn = test_db();
 
-        save_issues(&conn, "owner/repo", &[make_issue(1, "Old", "open", vec![])]).unwrap();
-        save_issues(&conn, "owner/repo", &[make_issue(2, "New", "open", vec![])]).unwrap();
+        let mut goal = make_goal("1", "v1");
+        record_goal_snapshots(&conn, "owner/repo", std::slice::from_ref(&goal), "2024-01-01").unwrap();
+        goal.progress = 0.25;
+        record_goal_snapshots(&conn, "owner/repo", std::slice::from_ref(&goal), "2024-01-02").unwrap();
+        goal.progress = 0.5;
+        record_goal_snapshots(&conn, "owner/repo", &[goal, make_goal("2", "v2")], "2024-01-02").unwrap();
+
+        let all = load_goal_snapshots(&conn, "owner/repo", None, None).unwrap();
+        assert_eq!(all.len(), 3);
+        // Same day overwrites
+        assert_eq!(all[1].day, "2024-01-02");
+        assert_eq!(all[1].progress, 0.5);
+
+        let since = load_goal_snapshots(&conn, "owner/repo", Some("2024-01-02"), Some("V1")).unwrap();
+        assert_eq!(since.len(), 1);
+        assert_eq!(since[0].goal_id, "1");
+    }
 
-        let loaded = load_issues(&conn, "owner/repo").unwrap();
-        assert_eq!(loaded.len(), 1);
-        assert_eq!(loaded[0].title, "New");
+    #[test]
+    fn test_set_goal_state() {
+        let conn = test_db();
+
+        save_goals(&conn, "owner/repo", &[make_goal("1", "v1"), make_goal("2", "v2")]).unwrap();
+
+        assert!(set_goal_state(&conn, "owner/repo", "1", GoalState::Closed).unwrap());
+        let closed = load_goals(&conn, "owner/repo", Some("closed")).unwrap();
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].name, "v1");
+        assert_eq!(load_goals(&conn, "owner/repo", Some("open")).unwrap().len(), 1);
+
+        assert!(set_goal_state(&conn, "owner/repo", "1", GoalState::Open).unwrap());
+        assert!(load_goals(&conn, "owner/repo", Some("closed")).unwrap().is_empty());
+
+        assert!(!set_goal_state(&conn, "owner/repo", "missing", GoalState::Closed).unwrap());
     }
 
     #[test]
-    fn test_filter_by_state() {
+    fn test_assignees_and_priority_round_trip() {
         let conn = test_db();
 
-        let issues = vec![
-            make_issue(1, "Open issue", "open", vec![]),
-            make_issue(2, "Closed issue", "closed", vec![]),
-        ];
-        save_issues(&conn, "owner/repo", &issues).unwrap();
+        let mut assigned = make_issue(1, "Assigned", "open", vec![]);
+        assigned.assignees = vec!["alice".to_string(), "bob".to_string()];
+        assigned.priority = Some(2);
+        save_issues(&conn, "owner/repo", &[assigned, make_issue(2, "Unassigned", "open", vec![])]).unwrap();
+
+        let loaded = load_issue(&conn, "owner/repo", 1).unwrap().unwrap();
+        assert_eq!(loaded.assignees, vec!["alice", "bob"]);
+        assert_eq!(loaded.priority, Some(2));
+
+        let filter = IssueFilter {
+            assignee: Some("bob".to_string()),
+            ..Default::default()
+        };
+        let bobs = load_issues_matching(&conn, "owner/repo", &filter).unwrap();
+        assert_eq!(bobs.len(), 1);
+        assert_eq!(bobs[0].title, "Assigned");
+    }
 
-        let open = load_issues_filtered(&conn, "owner/repo", None, Some("open")).unwrap();
-        assert_eq!(open.len(), 1);
-        assert_eq!(open[0].title, "Open issue");
+    #[test]
+    fn test_assignee_filter_is_exact() {
+        let conn = test_db();
 
-        let closed = load_issues_filtered(&conn, "owner/repo", None, Some("closed")).unwrap();
-        assert_eq!(closed.len(), 1);
-        assert_eq!(closed[0].title, "Closed issue");
+        let mut bob = make_issue(1, "Bob's", "open", vec![]);
+        bob.assignees = vec!["bob".to_string()];
+        let mut underscored = make_issue(2, "Underscored", "open", vec![]);
+        underscored.assignees = vec!["bo_".to_string()];
+        save_issues(&conn, "owner/repo", &[bob, underscored]).unwrap();
+
+        let numbers = |filter: IssueFilter| -> Vec<u64> {
+            load_issues_matching(&conn, "owner/repo", &filter).unwrap().iter().map(|i| i.number).collect()
+        };
+        let assignee = |name: &str| IssueFilter { assignee: Some(name.to_string()), ..Default::default() };
+        let participant = |name: &str| IssueFilter { participant: Some(name.to_string()), ..Default::default() };
+        assert_eq!(numbers(assignee("bo_")), vec![2]);
+        assert_eq!(numbers(assignee("b%")), Vec::<u64>::new());
+        assert_eq!(numbers(assignee("BOB")), Vec::<u64>::new());
+        assert_eq!(numbers(participant("bo_")), vec![2]);
+
+        let counts = issue_counts(&conn, "owner/repo", Some("bo_"), "2024-01-01").unwrap();
+        assert_eq!(counts.assigned_to_me, Some(1));
     }
 
     #[test]
-    fn test_filter_by_label() {
+    fn test_type_filter() {
         let conn = test_db();
 
-        let issues = vec![
-            make_issue(1, "Bug", "open", vec!["bug"]),
-            make_issue(2, "Feature", "open", vec!["enhancement"]),
-            make_issue(3, "Bug and feature", "open", vec!["bug", "enhancement"]),
-        ];
-        save_issues(&conn, "owner/repo", &issues).unwrap();
+        let mut typed = make_issue(1, "Typed bug", "open", vec![]);
+        typed.issue_type = Some("Bug".to_string());
+        let mut typed_feature = make_issue(2, "Feature with a bug label", "open", vec!["bug"]);
+        typed_feature.issue_type = Some("Feature".to_string());
+        let labelled = make_issue(3, "Labelled bug", "open", vec!["Bug"]);
+        save_issues(&conn, "owner/repo", &[typed, typed_feature, labelled, make_issue(4, "Other", "open", vec![])]).unwrap();
+
+        let filter = IssueFilter {
+            issue_type: Some("bug".to_string()),
+            type_labels: vec!["bug".to_string()],
+            ..Default::default()
+        };
+        let bugs = load_issues_matching(&conn, "owner/repo", &filter).unwrap();
+        assert_eq!(bugs.iter().map(|i| i.number).collect::<Vec<_>>(), vec![3, 1]);
+        assert_eq!(load_issue(&conn, "owner/repo", 1).unwrap().unwrap().issue_type.as_deref(), Some("Bug"));
+    }
 
-        let bugs = load_issues_filtered(&conn, "owner/repo", Some("bug"), None).unwrap();
-        assert_eq!(bugs.len(), 2);
+    #[test]
+    fn test_sla_filter() {
+        let conn = test_db();
 
-        let enhancements =
-            load_issues_filtered(&conn, "owner/repo", Some("enhancement"), None).unwrap();
-        assert_eq!(enhancements.len(), 2);
+        let mut due = make_issue(1, "Due soon", "open", vec![]);
+        due.sla_breaches_at = Some("2024-03-01T12:00:00.000Z".to_string());
+        due.triage = true;
+        let mut later = make_issue(2, "Due later", "open", vec![]);
+        later.sla_breaches_at = Some("2024-03-09T00:00:00.000Z".to_string());
+        let mut closed = make_issue(3, "Done", "closed", vec![]);
+        closed.sla_breaches_at = Some("2024-02-01T00:00:00.000Z".to_string());
+        save_issues(&conn, "ENG/team-id", &[due, later, closed, make_issue(4, "No SLA", "open", vec![])]).unwrap();
+
+        let loaded = load_issue(&conn, "ENG/team-id", 1).unwrap().unwrap();
+        assert!(loaded.triage);
+        assert_eq!(loaded.sla_breaches_at.as_deref(), Some("2024-03-01T12:00:00.000Z"));
+
+        let filter = IssueFilter {
+            sla_breaches_before: Some("2024-03-02T00:00:00Z".to_string()),
+            ..Default::default()
+        };
+        let breaching = load_issues_matching(&conn, "ENG/team-id", &filter).unwrap();
+        assert_eq!(breaching.iter().map(|i| i.number).collect::<Vec<_>>(), vec![1]);
     }
 
     #[test]
@@ -1239,6 +5668,50 @@ mod tests {
         remove_watched_repo(&conn, "nonexistent/repo").unwrap();
     }
 
+    #[test]
+    fn test_set_repo_paused() {
+        let conn = test_db();
+
+        add_watched_repo(&conn, "/path/a").unwrap();
+        set_repo_paused(&conn, "/path/a", true).unwrap();
+
+        let repos = list_watched_repos(&conn).unwrap();
+        assert_eq!(repos.len(), 1);
+        assert!(repos[0].paused);
+
+        // Re-watching keeps the pause until explicitly cleared
+        add_watched_repo(&conn, "/path/a").unwrap();
+        assert!(list_watched_repos(&conn).unwrap()[0].paused);
+
+        set_repo_paused(&conn, "/path/a", false).unwrap();
+        assert!(!list_watched_repos(&conn).unwrap()[0].paused);
+    }
+
+    // === Daemon Pause Tests ===
+
+    #[test]
+    fn test_daemon_pause_indefinite() {
+        let conn = test_db();
+
+        assert!(get_daemon_pause(&conn, 1000).unwrap().is_none());
+
+        set_daemon_pause(&conn, None).unwrap();
+        let pause = get_daemon_pause(&conn, 1000).unwrap().unwrap();
+        assert_eq!(pause.until, None);
+
+        clear_daemon_pause(&conn).unwrap();
+        assert!(get_daemon_pause(&conn, 1000).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_daemon_pause_expires() {
+        let conn = test_db();
+
+        set_daemon_pause(&conn, Some(2000)).unwrap();
+        assert!(get_daemon_pause(&conn, 1999).unwrap().is_some());
+        assert!(get_daemon_pause(&conn, 2000).unwrap().is_none());
+    }
+
     // === Repo Links Tests ===
 
     #[test]
@@ -1254,6 +5727,70 @@ mod tests {
         assert_eq!(link.forge_repo, "owner/repo");
     }
 
+    #[test]
+    fn test_find_repo_path() {
+        let conn = test_db();
+
+        set_repo_link(&conn, "/path/a", "github", "owner/repo", None).unwrap();
+        set_repo_link(&conn, "/path/b", "linear", "ENG/team-id", Some("Engineering")).unwrap();
+
+        assert_eq!(find_repo_path(&conn, "Owner/Repo").unwrap(), Some("/path/a".to_string()));
+        assert_eq!(find_repo_path(&conn, "engineering").unwrap(), Some("/path/b".to_string()));
+        assert_eq!(find_repo_path(&conn, "other/repo").unwrap(), None);
+    }
+
+    #[test]
+    fn test_repo_link_account() {
+        let conn = test_db();
+
+        set_repo_link(&conn, "/path/to/repo", "github", "owner/repo", None).unwrap();
+        let link = get_repo_link(&conn, "/path/to/repo").unwrap().unwrap();
+        assert_eq!(link.account, None);
+        assert_eq!(link.identity_key(), "github");
+
+        set_repo_account(&conn, "/path/to/repo", Some("work")).unwrap();
+        let link = get_repo_link(&conn, "/path/to/repo").unwrap().unwrap();
+        assert_eq!(link.account.as_deref(), Some("work"));
+        assert_eq!(link.identity_key(), "github:work");
+    }
+
+    #[test]
+    fn test_whoami_cache() {
+        let conn = test_db();
+        assert!(load_whoami(&conn, "github:work").unwrap().is_none());
+
+        let info = WhoAmI {
+            forge: "github".to_string(),
+            account: Some("work".to_string()),
+            login: "octocat".to_string(),
+            source: "keyring".to_string(),
+            token_kind: Some("classic PAT".to_string()),
+            scopes: Some(vec!["repo".to_string()]),
+            expires_at: None,
+            verified_at: "2024-01-01T00:00:00+00:00".to_string(),
+        };
+        save_whoami(&conn, "github:work", &info).unwrap();
+        let cached = load_whoami(&conn, "github:work").unwrap().unwrap();
+        assert_eq!(cached.login, "octocat");
+        assert_eq!(cached.scopes, Some(vec!["repo".to_string()]));
+        assert!(load_whoami(&conn, "github").unwrap().is_none());
+
+        forget_whoami(&conn, "github:work").unwrap();
+        assert!(load_whoami(&conn, "github:work").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_issue_subscription() {
+        let conn = test_db();
+        assert_eq!(get_subscription(&conn, "acme/api", 7).unwrap(), None);
+
+        set_subscription(&conn, "acme/api", 7, false).unwrap();
+        assert_eq!(get_subscription(&conn, "acme/api", 7).unwrap(), Some(false));
+        set_subscription(&conn, "acme/api", 7, true).unwrap();
+        assert_eq!(get_subscription(&conn, "acme/api", 7).unwrap(), Some(true));
+        assert_eq!(get_subscription(&conn, "acme/web", 7).unwrap(), None);
+    }
+
     #[test]
     fn test_get_repo_link_not_found() {
         let conn = test_db();
@@ -1295,6 +5832,170 @@ mod tests {
 
     // === Rate Limit Budget Tests ===
 
+    #[test]
+    fn test_sync_requests() {
+        let conn = test_db();
+        assert!(!has_sync_requests(&conn).unwrap());
+
+        request_sync(&conn, "/src/api").unwrap();
+        request_sync(&conn, "/src/api").unwrap();
+        assert!(has_sync_requests(&conn).unwrap());
+
+        clear_sync_requests(&conn).unwrap();
+        assert!(!has_sync_requests(&conn).unwrap());
+    }
+
+    #[test]
+    fn test_repo_databases() {
+        let conn = test_db();
+        let dir = tempfile::tempdir().unwrap();
+        let checkout = dir.path().join("api");
+        std::fs::create_dir_all(checkout.join(".git")).unwrap();
+
+        let path = repo_db_path(checkout.to_str().unwrap()).unwrap();
+        assert_eq!(path, checkout.join(".git").join("isq.db"));
+        // Submodules have a .git file, not a directory
+        assert!(repo_db_path(dir.path().to_str().unwrap()).is_none());
+
+        open_at(&path).unwrap();
+        add_repo_database(&conn, &path).unwrap();
+        add_repo_database(&conn, &path).unwrap();
+        add_repo_database(&conn, &dir.path().join("gone").join(".git").join("isq.db")).unwrap();
+        // Caches deleted with their checkout drop out
+        assert_eq!(list_repo_databases(&conn).unwrap(), vec![path]);
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM repo_databases", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_encrypted_bodies() {
+        let conn = test_db();
+        let repo = "acme/api";
+        let raw = |table: &str| -> String {
+            conn.query_row(&format!("SELECT body FROM {} LIMIT 1", table), [], |row| row.get(0)).unwrap()
+        };
+
+        let mut issue = make_issue(1, "Leak", "open", vec![]);
+        issue.body = Some("Token in logs".to_string());
+        save_issues(&conn, repo, &[issue]).unwrap();
+        let comment = Comment {
+            comment_id: "c1".to_string(),
+            issue_number: 1,
+            body: "Rotated it".to_string(),
+            author: "alice".to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+        };
+        save_comments(&conn, repo, &[comment]).unwrap();
+
+        // Existing plaintext gets converted in place
+        let cipher = cipher::test_cipher(true).unwrap();
+        assert_eq!(convert_bodies(&conn, cipher, true).unwrap(), 2);
+        assert!(is_cache_encrypted(&conn).unwrap());
+        assert!(has_encrypted_comments(&conn, repo).unwrap());
+        assert!(cipher::is_encrypted(&raw("issues")));
+        assert!(cipher::is_encrypted(&raw("comments")));
+        assert_eq!(load_issue(&conn, repo, 1).unwrap().unwrap().body.as_deref(), Some("Token in logs"));
+        assert_eq!(load_comments(&conn, repo, 1).unwrap()[0].body, "Rotated it");
+        scrub(&conn).unwrap();
+
+        // New writes are encrypted once the connection is set up for it
+        cipher::register(&conn, true, cipher::test_cipher).unwrap();
+        set_issue_body(&conn, repo, 1, "Token in logs (fixed)").unwrap();
+        assert!(cipher::is_encrypted(&raw("issues")));
+        assert_eq!(load_issue(&conn, repo, 1).unwrap().unwrap().body.as_deref(), Some("Token in logs (fixed)"));
+
+        assert_eq!(convert_bodies(&conn, cipher, false).unwrap(), 2);
+        assert!(!is_cache_encrypted(&conn).unwrap());
+        assert_eq!(raw("issues"), "Token in logs (fixed)");
+        assert_eq!(raw("comments"), "Rotated it");
+    }
+
+    #[test]
+    fn test_encrypted_drafts_and_queued_writes() {
+        let conn = test_db();
+        let repo = "acme/api";
+        let raw = |table: &str, column: &str| -> String {
+            conn.query_row(&format!("SELECT {} FROM {} LIMIT 1", column, table), [], |row| row.get(0)).unwrap()
+        };
+        let payload = r#"{"issue_number":1,"body":"The key is hunter2"}"#;
+
+        // Written before encryption was turned on
+        create_comment_draft(&conn, repo, 1, "The key is hunter2").unwrap();
+        queue_op(&conn, repo, "comment", payload).unwrap();
+        let cipher = cipher::test_cipher(true).unwrap();
+        assert_eq!(convert_bodies(&conn, cipher, true).unwrap(), 2);
+        assert!(cipher::is_encrypted(&raw("drafts", "body")));
+        assert!(cipher::is_encrypted(&raw("pending_ops", "payload")));
+
+        // Written after
+        cipher::register(&conn, true, cipher::test_cipher).unwrap();
+        conn.execute_batch("DELETE FROM drafts; DELETE FROM pending_ops;").unwrap();
+        let draft = create_comment_draft(&conn, repo, 1, "The key is hunter2").unwrap();
+        queue_op(&conn, repo, "comment", payload).unwrap();
+        assert!(cipher::is_encrypted(&raw("drafts", "body")));
+        assert!(cipher::is_encrypted(&raw("pending_ops", "payload")));
+        assert_eq!(load_draft(&conn, repo, draft).unwrap().unwrap().body.as_deref(), Some("The key is hunter2"));
+        assert_eq!(load_pending_ops(&conn, repo).unwrap()[0].payload, payload);
+
+        // Given up on, and still encrypted
+        conn.execute("UPDATE pending_ops SET created_at = datetime('now', '-30 days')", []).unwrap();
+        assert_eq!(expire_pending_ops(&conn, repo, 7).unwrap().len(), 1);
+        assert!(cipher::is_encrypted(&raw("failed_ops", "payload")));
+        assert_eq!(load_failed_ops(&conn, repo).unwrap()[0].op.payload, payload);
+
+        assert_eq!(convert_bodies(&conn, cipher, false).unwrap(), 2);
+        assert_eq!(raw("drafts", "body"), "The key is hunter2");
+        assert_eq!(raw("failed_ops", "payload"), payload);
+    }
+
+    #[test]
+    fn test_rank_issue() {
+        let conn = test_db();
+        let repo = "owner/repo";
+
+        assert_eq!(rank_issue(&conn, repo, 1, RankMove::Keep).unwrap(), Some(1));
+        assert_eq!(rank_issue(&conn, repo, 2, RankMove::Keep).unwrap(), Some(2));
+        assert_eq!(rank_issue(&conn, repo, 3, RankMove::To(1)).unwrap(), Some(1));
+        assert_eq!(load_ranking(&conn, repo).unwrap(), vec![3, 1, 2]);
+
+        assert_eq!(rank_issue(&conn, repo, 2, RankMove::Up).unwrap(), Some(2));
+        assert_eq!(load_ranking(&conn, repo).unwrap(), vec![3, 2, 1]);
+        // Moves past either end stop there
+        assert_eq!(rank_issue(&conn, repo, 3, RankMove::Up).unwrap(), Some(1));
+        assert_eq!(rank_issue(&conn, repo, 1, RankMove::Down).unwrap(), Some(3));
+        assert_eq!(rank_issue(&conn, repo, 2, RankMove::To(99)).unwrap(), Some(3));
+        assert_eq!(load_ranking(&conn, repo).unwrap(), vec![3, 1, 2]);
+
+        // Keep leaves a ranked issue in place
+        assert_eq!(rank_issue(&conn, repo, 1, RankMove::Keep).unwrap(), Some(2));
+
+        assert_eq!(rank_issue(&conn, repo, 1, RankMove::Remove).unwrap(), None);
+        assert_eq!(load_ranking(&conn, repo).unwrap(), vec![3, 2]);
+        assert!(load_ranking(&conn, "other/repo").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_complexity_budget_round_trip() {
+        let conn = test_db();
+        assert!(get_complexity_budget(&conn, "linear").unwrap().is_none());
+
+        // Request-based limits alone don't count as a complexity budget
+        update_rate_limit_budget(&conn, "linear", 1500, 1400, 1700000000).unwrap();
+        assert!(get_complexity_budget(&conn, "linear").unwrap().is_none());
+
+        let budget = ComplexityBudget {
+            limit: 250000,
+            remaining: 12000,
+            reset_at: 1700000000,
+            last_cost: 3100,
+        };
+        update_complexity_budget(&conn, "linear", &budget).unwrap();
+        assert_eq!(get_complexity_budget(&conn, "linear").unwrap(), Some(budget));
+
+        // Request budget is kept alongside
+        assert_eq!(get_rate_limit_state(&conn, "linear").unwrap().unwrap().remaining, Some(1400));
+    }
+
     #[test]
     fn test_update_rate_limit_budget() {
         let conn = test_db();
@@ -1326,6 +6027,20 @@ mod tests {
         assert_eq!(state.used(), Some(847)); // 5000 - 4153 = 847
     }
 
+    #[test]
+    fn test_rate_limit_budget_is_low_until_reset() {
+        let conn = test_db();
+
+        update_rate_limit_budget(&conn, "github", 5000, 12, 1700000000).unwrap();
+        let state = get_rate_limit_state(&conn, "github").unwrap().unwrap();
+        assert!(state.is_low(1699999000));
+        assert!(!state.is_low(1700000000));
+
+        update_rate_limit_budget(&conn, "github", 5000, 250, 1700000000).unwrap();
+        let state = get_rate_limit_state(&conn, "github").unwrap().unwrap();
+        assert!(!state.is_low(1699999000));
+    }
+
     #[test]
     fn test_rate_limit_budget_per_forge() {
         let conn = test_db();
@@ -1348,6 +6063,21 @@ mod tests {
         assert_eq!(linear.used(), Some(100));
     }
 
+    #[test]
+    fn test_forge_budgets_take_latest_report_across_caches() {
+        let shared = test_db();
+        let repo = test_db();
+        update_rate_limit_budget(&shared, "github", 5000, 4500, 1700000000).unwrap();
+        update_rate_limit_budget(&repo, "github", 5000, 3000, 1700000000).unwrap();
+        update_rate_limit_budget(&repo, "linear", 1500, 1400, 1700000000).unwrap();
+        shared.execute("UPDATE rate_limit_state SET updated_at = '2025-01-01 00:00:00'", []).unwrap();
+        repo.execute("UPDATE rate_limit_state SET updated_at = '2025-01-02 00:00:00'", []).unwrap();
+
+        let budgets = forge_budgets(&[shared, repo]).unwrap();
+        let remaining: Vec<_> = budgets.iter().map(|b| (b.forge.as_str(), b.remaining)).collect();
+        assert_eq!(remaining, vec![("github", Some(3000)), ("linear", Some(1400))]);
+    }
+
     #[test]
     fn test_rate_limit_budget_updates_existing() {
         let conn = test_db();
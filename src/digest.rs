@@ -0,0 +1,394 @@
+//! Weekly digest of the watched repos (`isq digest`)
+//!
+//! Summarizes what happened in a window across every watched repo: issues
+//! opened and closed, how far each goal moved, and the busiest discussions.
+//! The markdown is meant for pasting into a team update.
+//!
+//! With a digest directory configured, the daemon writes last week's digest
+//! there once it's over (`digest-2025-06-23.md`, named for the Monday):
+//!
+//! ```toml
+//! [digest]
+//! dir = "~/isq-digests"
+//! ```
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::db::{self, Comment, GoalSnapshot};
+use crate::forges::Issue;
+use crate::workspace;
+
+/// Most discussions listed per repo
+const MAX_DISCUSSIONS: usize = 5;
+
+/// Comments in the window before a discussion counts as notable
+const NOTABLE_COMMENTS: usize = 3;
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct DigestConfig {
+    /// Where the daemon writes weekly digests (`~` is expanded; unset: never)
+    pub dir: Option<String>,
+}
+
+impl DigestConfig {
+    pub fn dir(&self) -> Option<PathBuf> {
+        self.dir.as_deref().map(crate::config::expand_home)
+    }
+}
+
+/// An issue opened or closed in the window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IssueRef {
+    pub number: u64,
+    pub id: String,
+    pub title: String,
+}
+
+/// How a goal moved over the window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GoalDelta {
+    pub name: String,
+    pub from: f64,
+    pub to: f64,
+    pub open_count: Option<u64>,
+    pub closed_count: Option<u64>,
+}
+
+/// An issue with a lot of comments in the window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Discussion {
+    pub number: u64,
+    pub id: String,
+    pub title: String,
+    pub comments: usize,
+    pub participants: usize,
+}
+
+/// One repo's part of the digest
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoDigest {
+    pub repo: String,
+    pub opened: Vec<IssueRef>,
+    pub closed: Vec<IssueRef>,
+    pub goals: Vec<GoalDelta>,
+    /// Most comments first
+    pub discussions: Vec<Discussion>,
+}
+
+impl RepoDigest {
+    fn is_quiet(&self) -> bool {
+        self.opened.is_empty() && self.closed.is_empty() && self.goals.is_empty() && self.discussions.is_empty()
+    }
+}
+
+/// Everything `isq digest` shows
+#[derive(Debug, Clone, Serialize)]
+pub struct Digest {
+    #[serde(serialize_with = "rfc3339")]
+    pub since: DateTime<Utc>,
+    #[serde(serialize_with = "rfc3339")]
+    pub until: DateTime<Utc>,
+    /// Repos with activity, in watch order
+    pub repos: Vec<RepoDigest>,
+}
+
+/// Times in JSON are RFC 3339, like the forge's
+fn rfc3339<S: serde::Serializer>(at: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&at.to_rfc3339())
+}
+
+/// The last full Monday-to-Monday week (UTC) before `now`
+pub fn last_week(now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let today = now.date_naive();
+    let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let until = start_of(monday);
+    (until - Duration::days(7), until)
+}
+
+fn start_of(day: NaiveDate) -> DateTime<Utc> {
+    day.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+fn within(at: &str, since: DateTime<Utc>, until: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(at).is_ok_and(|at| at >= since && at < until)
+}
+
+fn issue_ref(issue: &Issue) -> IssueRef {
+    IssueRef { number: issue.number, id: issue.display_id(), title: issue.title.clone() }
+}
+
+/// Summarize one repo's window. A closed issue's last update counts as when
+/// it closed, as in `isq stats`. Goal deltas compare each goal's first and
+/// last snapshot in the window (snapshots from `since` on), so goals that
+/// didn't move are left out.
+pub fn summarize(
+    repo: &str,
+    issues: &[Issue],
+    comments: &HashMap<u64, Vec<Comment>>,
+    snapshots: &[GoalSnapshot],
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> RepoDigest {
+    let mut opened: Vec<IssueRef> =
+        issues.iter().filter(|i| within(&i.created_at, since, until)).map(issue_ref).collect();
+    let mut closed: Vec<IssueRef> = issues
+        .iter()
+        .filter(|i| i.state == "closed" && within(&i.updated_at, since, until))
+        .map(issue_ref)
+        .collect();
+    opened.sort_by_key(|i| i.number);
+    closed.sort_by_key(|i| i.number);
+
+    let until_day = until.format("%Y-%m-%d").to_string();
+    let mut by_goal: BTreeMap<&str, Vec<&GoalSnapshot>> = BTreeMap::new();
+    for snapshot in snapshots.iter().filter(|s| s.day < until_day) {
+        by_goal.entry(&snapshot.goal_id).or_default().push(snapshot);
+    }
+    let mut goals: Vec<GoalDelta> = by_goal
+        .values()
+        .filter_map(|days| {
+            let (first, last) = (days.iter().min_by(|a, b| a.day.cmp(&b.day))?, days.iter().max_by(|a, b| a.day.cmp(&b.day))?);
+            (last.progress != first.progress || last.closed_count != first.closed_count).then(|| GoalDelta {
+                name: last.name.clone(),
+                from: first.progress,
+                to: last.progress,
+                open_count: last.open_count,
+                closed_count: last.closed_count,
+            })
+        })
+        .collect();
+    goals.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut discussions: Vec<Discussion> = issues
+        .iter()
+        .filter_map(|issue| {
+            let recent: Vec<&Comment> = comments
+                .get(&issue.number)?
+                .iter()
+                .filter(|c| within(&c.created_at, since, until))
+                .collect();
+            (recent.len() >= NOTABLE_COMMENTS).then(|| Discussion {
+                number: issue.number,
+                id: issue.display_id(),
+                title: issue.title.clone(),
+                comments: recent.len(),
+                participants: recent.iter().map(|c| c.author.to_lowercase()).collect::<HashSet<_>>().len(),
+            })
+        })
+        .collect();
+    discussions.sort_by_key(|d| (std::cmp::Reverse(d.comments), d.number));
+    discussions.truncate(MAX_DISCUSSIONS);
+
+    RepoDigest { repo: repo.to_string(), opened, closed, goals, discussions }
+}
+
+/// Build the digest for every watched repo from the caches (`db::open_all`).
+/// Checkouts sharing a forge repo count once; search workspaces have no
+/// history and are skipped.
+pub fn collect(conns: &[rusqlite::Connection], since: DateTime<Utc>, until: DateTime<Utc>) -> Result<Digest> {
+    let since_day = since.format("%Y-%m-%d").to_string();
+    let mut seen = HashSet::new();
+    let mut repos = Vec::new();
+    for conn in conns {
+        for watched in db::list_watched_repos(conn)? {
+            let Some(link) = db::get_repo_link(conn, &watched.repo)? else {
+                continue;
+            };
+            if workspace::is_workspace(&link.forge_repo) || !seen.insert(link.forge_repo.clone()) {
+                continue;
+            }
+            let issues = db::load_issues(conn, &link.forge_repo)?;
+            let comments = db::load_all_comments(conn, &link.forge_repo)?;
+            let snapshots = db::load_goal_snapshots(conn, &link.forge_repo, Some(&since_day), None)?;
+            let name = link.display_name.as_deref().unwrap_or(&link.forge_repo);
+            let digest = summarize(name, &issues, &comments, &snapshots, since, until);
+            if !digest.is_quiet() {
+                repos.push(digest);
+            }
+        }
+    }
+    Ok(Digest { since, until, repos })
+}
+
+fn percent(progress: f64) -> String {
+    format!("{:.0}%", progress * 100.0)
+}
+
+/// Render a digest as markdown
+pub fn markdown(digest: &Digest) -> String {
+    let last_day = digest.until - Duration::seconds(1);
+    let mut out = format!(
+        "# Digest: {} – {}\n\n",
+        digest.since.format("%b %-d"),
+        last_day.format("%b %-d, %Y")
+    );
+    if digest.repos.is_empty() {
+        out.push_str("Nothing was opened, closed or discussed.\n");
+        return out;
+    }
+
+    for repo in &digest.repos {
+        out.push_str(&format!(
+            "## {}\n\n{} opened, {} closed\n",
+            repo.repo,
+            repo.opened.len(),
+            repo.closed.len()
+        ));
+        for (title, issues) in [("Closed", &repo.closed), ("Opened", &repo.opened)] {
+            if issues.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n### {}\n\n", title));
+            for issue in issues {
+                out.push_str(&format!("- {} {}\n", issue.id, issue.title));
+            }
+        }
+        if !repo.goals.is_empty() {
+            out.push_str("\n### Goals\n\n");
+            for goal in &repo.goals {
+                out.push_str(&format!("- {}: {} → {}", goal.name, percent(goal.from), percent(goal.to)));
+                if let (Some(open), Some(closed)) = (goal.open_count, goal.closed_count) {
+                    out.push_str(&format!(" ({}/{} closed)", closed, open + closed));
+                }
+                out.push('\n');
+            }
+        }
+        if !repo.discussions.is_empty() {
+            out.push_str("\n### Discussions\n\n");
+            for discussion in &repo.discussions {
+                out.push_str(&format!(
+                    "- {} {} ({} comments, {} people)\n",
+                    discussion.id, discussion.title, discussion.comments, discussion.participants
+                ));
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Where the scheduled digest for the week starting `since` goes
+pub fn scheduled_path(dir: &Path, since: DateTime<Utc>) -> PathBuf {
+    dir.join(format!("digest-{}.md", since.format("%Y-%m-%d")))
+}
+
+/// Write last week's digest into `dir` unless it's already there. Returns the
+/// path when a new digest was written.
+pub fn write_scheduled(conns: &[rusqlite::Connection], dir: &Path, now: DateTime<Utc>) -> Result<Option<PathBuf>> {
+    let (since, until) = last_week(now);
+    let path = scheduled_path(dir, since);
+    if path.exists() {
+        return Ok(None);
+    }
+    let digest = collect(conns, since, until)?;
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(&path, markdown(&digest))?;
+    Ok(Some(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forges::GoalState;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn issue(number: u64, state: &str, created_at: &str, updated_at: &str) -> Issue {
+        Issue {
+            number,
+            title: format!("Issue {}", number),
+            state: state.to_string(),
+            author: "reporter".to_string(),
+            created_at: created_at.to_string(),
+            updated_at: updated_at.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn comment(issue_number: u64, author: &str, created_at: &str) -> Comment {
+        Comment {
+            comment_id: format!("{}-{}-{}", issue_number, author, created_at),
+            issue_number,
+            body: "+1".to_string(),
+            author: author.to_string(),
+            created_at: created_at.to_string(),
+        }
+    }
+
+    fn snapshot(day: &str, progress: f64, closed: u64) -> GoalSnapshot {
+        GoalSnapshot {
+            day: day.to_string(),
+            goal_id: "1".to_string(),
+            name: "v1.2".to_string(),
+            state: GoalState::Open,
+            progress,
+            open_count: Some(4 - closed),
+            closed_count: Some(closed),
+        }
+    }
+
+    #[test]
+    fn test_last_week() {
+        // A Wednesday: last week ran from the Monday before to this Monday
+        let (since, until) = last_week(at("2025-07-02T15:00:00Z"));
+        assert_eq!((since, until), (at("2025-06-23T00:00:00Z"), at("2025-06-30T00:00:00Z")));
+        // On a Monday, the week that just ended
+        assert_eq!(last_week(at("2025-06-30T00:00:00Z")).1, at("2025-06-30T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_summarize_and_markdown() {
+        let (since, until) = (at("2025-06-23T00:00:00Z"), at("2025-06-30T00:00:00Z"));
+        let issues = vec![
+            issue(1, "closed", "2025-05-01T00:00:00Z", "2025-06-25T00:00:00Z"),
+            issue(2, "open", "2025-06-24T00:00:00Z", "2025-06-24T00:00:00Z"),
+            issue(3, "open", "2025-05-01T00:00:00Z", "2025-06-29T00:00:00Z"),
+            // Closed before the window
+            issue(4, "closed", "2025-05-01T00:00:00Z", "2025-06-01T00:00:00Z"),
+        ];
+        let comments = HashMap::from([
+            (
+                3,
+                vec![
+                    comment(3, "alice", "2025-06-24T00:00:00Z"),
+                    comment(3, "Bob", "2025-06-25T00:00:00Z"),
+                    comment(3, "bob", "2025-06-26T00:00:00Z"),
+                ],
+            ),
+            // Two in the window isn't notable
+            (2, vec![comment(2, "alice", "2025-06-24T00:00:00Z"), comment(2, "bob", "2025-06-24T00:00:00Z")]),
+        ]);
+        let snapshots = vec![
+            snapshot("2025-06-23", 0.25, 1),
+            snapshot("2025-06-27", 0.5, 2),
+            // After the window
+            snapshot("2025-06-30", 1.0, 4),
+        ];
+
+        let repo = summarize("acme/app", &issues, &comments, &snapshots, since, until);
+        let numbers = |issues: &[IssueRef]| issues.iter().map(|i| i.number).collect::<Vec<_>>();
+        assert_eq!(numbers(&repo.opened), vec![2]);
+        assert_eq!(numbers(&repo.closed), vec![1]);
+        assert_eq!(repo.goals.len(), 1);
+        assert_eq!((repo.goals[0].from, repo.goals[0].to), (0.25, 0.5));
+        assert_eq!(repo.discussions.len(), 1);
+        assert_eq!((repo.discussions[0].comments, repo.discussions[0].participants), (3, 2));
+
+        let text = markdown(&Digest { since, until, repos: vec![repo] });
+        assert!(text.starts_with("# Digest: Jun 23 – Jun 29, 2025\n\n## acme/app\n\n1 opened, 1 closed\n"));
+        assert!(text.contains("### Closed\n\n- #1 Issue 1\n"));
+        assert!(text.contains("### Goals\n\n- v1.2: 25% → 50% (2/4 closed)\n"));
+        assert!(text.contains("### Discussions\n\n- #3 Issue 3 (3 comments, 2 people)\n"));
+
+        let quiet = Digest { since, until, repos: Vec::new() };
+        assert!(markdown(&quiet).ends_with("Nothing was opened, closed or discussed.\n"));
+    }
+}
@@ -3,17 +3,70 @@
 //! Design principles:
 //! - Visual hierarchy: title prominent, metadata dimmed
 //! - Semantic colors: green=open, red=closed
-//! - Relative timestamps: "5d ago" vs ISO format
+//! - Relative timestamps: "5d ago" vs ISO format (or absolute, via timefmt.rs)
 //! - Graceful degradation: plain text when not a TTY
+//! - Glyph themes: unicode (default), ascii, nerd-font; auto-detects terminals
+//!   that can't render unicode
 
+use std::collections::{HashMap, HashSet};
 use std::io::IsTerminal;
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use colored::{ColoredString, Colorize};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use textwrap::core::display_width;
 use textwrap::{wrap, Options};
 
-use crate::db::Comment;
-use crate::forges::{Goal, GoalState, Issue, Label};
+use crate::db::{self, Backlink, Comment, CommentHit, PendingOp, SearchHit};
+use crate::forges::{Goal, GoalState, Issue, Label, LabelDef, Objective, ObjectiveProgress};
+use crate::issue_types::{self, TypeKind};
+use crate::reminders;
+use crate::tasks;
+use crate::timefmt::{self, Timestamps};
+
+/// Open issues untouched for this long are dimmed in the issue table
+pub const STALE_AFTER_DAYS: i64 = 30;
+
+/// Whether an open issue hasn't been updated since `now - after`
+pub fn is_stale(issue: &Issue, now: DateTime<Utc>, after: chrono::Duration) -> bool {
+    if issue.state != "open" {
+        return false;
+    }
+    DateTime::parse_from_rfc3339(&issue.updated_at)
+        .map(|updated| now.signed_duration_since(updated.with_timezone(&Utc)) > after)
+        .unwrap_or(false)
+}
+
+/// Whether an open issue is past its SLA
+fn sla_breached(issue: &Issue, now: DateTime<Utc>) -> bool {
+    issue.state == "open"
+        && issue
+            .sla_breaches_at
+            .as_deref()
+            .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+            .is_some_and(|at| at.with_timezone(&Utc) <= now)
+}
+
+/// Time left on an open issue's SLA ("3h left", "breached 2d ago"); None
+/// without one
+fn sla_countdown(issue: &Issue, now: DateTime<Utc>) -> Option<String> {
+    if issue.state != "open" {
+        return None;
+    }
+    let at = DateTime::parse_from_rfc3339(issue.sla_breaches_at.as_deref()?).ok()?.with_timezone(&Utc);
+    let left = at.signed_duration_since(now);
+    let span = |d: chrono::TimeDelta| match (d.num_days(), d.num_hours(), d.num_minutes()) {
+        (days, _, _) if days > 0 => format!("{}d", days),
+        (_, hours, _) if hours > 0 => format!("{}h", hours),
+        (_, _, minutes) => format!("{}m", minutes.max(1)),
+    };
+    Some(if left > chrono::TimeDelta::zero() {
+        format!("{} left", span(left))
+    } else {
+        format!("breached {} ago", span(-left))
+    })
+}
 
 /// Format a timestamp as relative time (e.g., "5d ago", "2h ago", "just now")
 fn relative_time(timestamp: &str) -> String {
@@ -53,6 +106,219 @@ fn relative_time(timestamp: &str) -> String {
     }
 }
 
+/// Format an issue or comment timestamp as configured: relative, or the
+/// date and time (see timefmt.rs)
+fn timestamp(ts: &str) -> String {
+    match (timefmt::FORMAT.timestamps, DateTime::parse_from_rfc3339(ts)) {
+        (Timestamps::Absolute, Ok(at)) => timefmt::FORMAT.absolute(at.with_timezone(&Utc)),
+        _ => relative_time(ts),
+    }
+}
+
+// ============================================================================
+// Themes
+// ============================================================================
+
+/// Glyph theme, set via `[display] theme` in config.toml or `ISQ_THEME`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    /// Unicode unless the terminal looks like it can't render it
+    #[default]
+    Auto,
+    Unicode,
+    Ascii,
+    NerdFont,
+}
+
+impl Theme {
+    pub fn from_str(s: &str) -> Option<Theme> {
+        match s.to_lowercase().as_str() {
+            "auto" => Some(Theme::Auto),
+            "unicode" => Some(Theme::Unicode),
+            "ascii" => Some(Theme::Ascii),
+            "nerd-font" | "nerdfont" => Some(Theme::NerdFont),
+            _ => None,
+        }
+    }
+}
+
+/// Glyphs used by the styled output
+struct Glyphs {
+    open: &'static str,
+    closed: &'static str,
+    goal_open: &'static str,
+    goal_done: &'static str,
+    goal: &'static str,
+    link: &'static str,
+    dot: &'static str,
+    heavy_rule: &'static str,
+    light_rule: &'static str,
+    ellipsis: &'static str,
+    /// Marks issues with writes queued for sync
+    pending: &'static str,
+    /// Issue types (see issue_types.rs)
+    type_bug: &'static str,
+    type_feature: &'static str,
+    type_task: &'static str,
+    type_other: &'static str,
+    /// Sparkline levels, lowest first, and the bar chart block (see stats.rs)
+    sparks: [&'static str; 8],
+    bar: &'static str,
+}
+
+const UNICODE_GLYPHS: Glyphs = Glyphs {
+    open: "●",
+    closed: "○",
+    goal_open: "●",
+    goal_done: "✓",
+    goal: "→",
+    link: "↗",
+    dot: "·",
+    heavy_rule: "━",
+    light_rule: "─",
+    ellipsis: "…",
+    pending: "⇡",
+    type_bug: "✖",
+    type_feature: "★",
+    type_task: "☐",
+    type_other: "◇",
+    sparks: ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"],
+    bar: "█",
+};
+
+const ASCII_GLYPHS: Glyphs = Glyphs {
+    open: "o",
+    closed: "x",
+    goal_open: "o",
+    goal_done: "+",
+    goal: "->",
+    link: ">",
+    dot: "-",
+    heavy_rule: "=",
+    light_rule: "-",
+    ellipsis: "...",
+    pending: "^",
+    type_bug: "!",
+    type_feature: "+",
+    type_task: "-",
+    type_other: "*",
+    sparks: [" ", ".", ":", "-", "=", "+", "*", "#"],
+    bar: "#",
+};
+
+// Nerd Font codepoints (Octicons set)
+const NERD_FONT_GLYPHS: Glyphs = Glyphs {
+    open: "\u{f41b}",
+    closed: "\u{f41d}",
+    goal_open: "\u{f45d}",
+    goal_done: "\u{f42e}",
+    goal: "\u{f45d}",
+    link: "\u{f465}",
+    dot: "·",
+    heavy_rule: "━",
+    light_rule: "─",
+    ellipsis: "…",
+    pending: "⇡",
+    type_bug: "\u{f46f}",
+    type_feature: "\u{f400}",
+    type_task: "\u{f45e}",
+    type_other: "\u{f412}",
+    sparks: ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"],
+    bar: "█",
+};
+
+/// Resolve `Auto` from the terminal environment: ASCII for dumb terminals or
+/// non-UTF-8 locales, unicode otherwise
+fn detect_theme(term: Option<&str>, locale: Option<&str>) -> Theme {
+    if term == Some("dumb") {
+        return Theme::Ascii;
+    }
+    match locale {
+        Some(l) => {
+            let l = l.to_lowercase();
+            if l.contains("utf-8") || l.contains("utf8") {
+                Theme::Unicode
+            } else {
+                Theme::Ascii
+            }
+        }
+        // No locale set: Windows terminals handle unicode, bare unix consoles often don't
+        None => {
+            if cfg!(windows) {
+                Theme::Unicode
+            } else {
+                Theme::Ascii
+            }
+        }
+    }
+}
+
+/// Effective locale (LC_ALL > LC_CTYPE > LANG, first non-empty)
+fn current_locale() -> Option<String> {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|v| std::env::var(v).ok())
+        .find(|v| !v.is_empty())
+}
+
+fn configured_theme() -> Theme {
+    if let Some(theme) = std::env::var("ISQ_THEME").ok().and_then(|t| Theme::from_str(&t)) {
+        return theme;
+    }
+    crate::config::load().map(|c| c.display.theme).unwrap_or_default()
+}
+
+/// Default output, set via `[display] output` in config.toml or `ISQ_OUTPUT`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    /// Human-readable unless `--json` is given
+    #[default]
+    Plain,
+    /// JSON when stdout isn't a terminal (agents, pipes); `--plain` overrides
+    Json,
+}
+
+impl OutputFormat {
+    pub fn from_str(s: &str) -> Option<OutputFormat> {
+        match s.to_lowercase().as_str() {
+            "plain" => Some(OutputFormat::Plain),
+            "json" => Some(OutputFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+pub fn configured_output() -> OutputFormat {
+    if let Some(format) = std::env::var("ISQ_OUTPUT").ok().and_then(|f| OutputFormat::from_str(&f)) {
+        return format;
+    }
+    crate::config::load().map(|c| c.display.output).unwrap_or_default()
+}
+
+static GLYPHS: Lazy<&'static Glyphs> = Lazy::new(|| {
+    let theme = match configured_theme() {
+        Theme::Auto => detect_theme(std::env::var("TERM").ok().as_deref(), current_locale().as_deref()),
+        theme => theme,
+    };
+    match theme {
+        Theme::Ascii => &ASCII_GLYPHS,
+        Theme::NerdFont => &NERD_FONT_GLYPHS,
+        Theme::Unicode | Theme::Auto => &UNICODE_GLYPHS,
+    }
+});
+
+/// Sparkline levels of the active theme, lowest first
+pub fn spark_glyphs() -> &'static [&'static str] {
+    &GLYPHS.sparks
+}
+
+/// Bar chart block of the active theme
+pub fn bar_glyph() -> &'static str {
+    GLYPHS.bar
+}
+
 /// Check if stdout is a terminal (for color support)
 fn is_tty() -> bool {
     std::io::stdout().is_terminal()
@@ -140,6 +406,47 @@ fn format_labels(labels: &[Label], tty: bool) -> String {
     }
 }
 
+/// The repo's labels in their colors, with open issue counts (keyed by
+/// lowercased name) and descriptions
+pub fn print_label_catalog(labels: &[LabelDef], open_counts: &HashMap<String, usize>) {
+    let tty = is_tty();
+    let width = labels.iter().map(|l| display_width(&l.name)).max().unwrap_or(0);
+    for def in labels {
+        let label = Label::new(def.name.clone(), def.color.clone());
+        let padding = " ".repeat(width - display_width(&def.name));
+        let open = format!("{:>4} open", open_counts.get(&def.name.to_lowercase()).copied().unwrap_or(0));
+        let open = if tty { open.dimmed().to_string() } else { open };
+        let line = format!(
+            "  {}{}  {}  {}",
+            render_label(&label, tty),
+            padding,
+            open,
+            def.description.as_deref().unwrap_or_default()
+        );
+        println!("{}", line.trim_end());
+    }
+}
+
+/// Human-readable priority (Linear scale), None for "no priority"
+fn type_glyph(issue_type: &str, g: &Glyphs) -> &'static str {
+    match issue_types::kind(issue_type) {
+        TypeKind::Bug => g.type_bug,
+        TypeKind::Feature => g.type_feature,
+        TypeKind::Task => g.type_task,
+        TypeKind::Other => g.type_other,
+    }
+}
+
+fn priority_name(priority: u8) -> Option<&'static str> {
+    match priority {
+        1 => Some("Urgent"),
+        2 => Some("High"),
+        3 => Some("Medium"),
+        4 => Some("Low"),
+        _ => None,
+    }
+}
+
 /// Wrap text with consistent indentation
 fn wrap_indented(text: &str, indent: &str, width: usize) -> String {
     let effective_width = width.saturating_sub(indent.len());
@@ -160,12 +467,26 @@ fn wrap_indented(text: &str, indent: &str, width: usize) -> String {
     result
 }
 
+/// What the issue detail view shows besides the issue and its comments
+#[derive(Clone, Copy)]
+pub struct IssueExtras<'a> {
+    /// Queued writes the cached state doesn't reflect yet
+    pub pending: &'a [PendingOp],
+    pub backlinks: &'a [Backlink],
+    /// Only known once you've (un)subscribed through isq
+    pub subscribed: Option<bool>,
+    /// Author, assignees and commenters
+    pub participants: &'a [String],
+}
+
 /// Print a styled issue detail view
-pub fn print_issue(issue: &Issue, comments: &[Comment], elapsed_ms: u64) {
+pub fn print_issue(issue: &Issue, comments: &[Comment], total_comments: usize, extras: &IssueExtras, elapsed_ms: u64) {
+    let IssueExtras { pending, backlinks, subscribed, participants } = *extras;
     let tty = is_tty();
+    let g = *GLYPHS;
 
     // Title line
-    let title_line = format!("  #{} {}", issue.number, issue.title);
+    let title_line = format!("  {} {}", issue.display_id(), issue.title);
     if tty {
         println!("{}", title_line.bold());
     } else {
@@ -173,7 +494,7 @@ pub fn print_issue(issue: &Issue, comments: &[Comment], elapsed_ms: u64) {
     }
 
     // Heavy separator
-    let separator = "━".repeat(60);
+    let separator = g.heavy_rule.repeat(60);
     if tty {
         println!(" {}", separator.dimmed());
     } else {
@@ -183,15 +504,15 @@ pub fn print_issue(issue: &Issue, comments: &[Comment], elapsed_ms: u64) {
     // State + author + labels line
     let state_indicator = if issue.state == "open" {
         if tty {
-            "●".green().to_string()
+            g.open.green().to_string()
         } else {
-            "●".to_string()
+            g.open.to_string()
         }
     } else {
         if tty {
-            "●".red().to_string()
+            g.closed.red().to_string()
         } else {
-            "○".to_string()
+            g.closed.to_string()
         }
     };
 
@@ -213,9 +534,52 @@ pub fn print_issue(issue: &Issue, comments: &[Comment], elapsed_ms: u64) {
         meta_parts.push(labels_str);
     }
 
+    if let Some((done, total)) = issue.body.as_deref().and_then(tasks::progress) {
+        meta_parts.push(format!("{}/{} tasks", done, total));
+    }
+
+    if !issue.assignees.is_empty() {
+        let assigned = format!(
+            "assigned {}",
+            issue.assignees.iter().map(|a| format!("@{}", a)).collect::<Vec<_>>().join(", ")
+        );
+        if tty {
+            meta_parts.push(assigned.cyan().to_string());
+        } else {
+            meta_parts.push(assigned);
+        }
+    }
+
+    if let Some(priority) = issue.priority.and_then(priority_name) {
+        if tty {
+            meta_parts.push(priority.yellow().to_string());
+        } else {
+            meta_parts.push(priority.to_string());
+        }
+    }
+
+    if let Some(issue_type) = &issue.issue_type {
+        meta_parts.push(format!("{} {}", type_glyph(issue_type, g), issue_type));
+    }
+
+    if issue.triage {
+        meta_parts.push(if tty { "in triage".yellow().to_string() } else { "in triage".to_string() });
+    }
+
+    if let Some(countdown) = sla_countdown(issue, Utc::now()) {
+        let sla = format!("SLA {}", countdown);
+        if !tty {
+            meta_parts.push(sla);
+        } else if sla_breached(issue, Utc::now()) {
+            meta_parts.push(sla.red().to_string());
+        } else {
+            meta_parts.push(sla.yellow().to_string());
+        }
+    }
+
     // Add milestone/goal if present
     if let Some(milestone) = &issue.milestone {
-        let goal_str = format!("→ {}", milestone);
+        let goal_str = format!("{} {}", g.goal, milestone);
         if tty {
             meta_parts.push(goal_str.cyan().to_string());
         } else {
@@ -223,13 +587,45 @@ pub fn print_issue(issue: &Issue, comments: &[Comment], elapsed_ms: u64) {
         }
     }
 
+    // Only known once you've (un)subscribed through isq
+    if let Some(subscribed) = subscribed {
+        let subscription = if subscribed { "subscribed" } else { "unsubscribed" };
+        if tty {
+            meta_parts.push(subscription.dimmed().to_string());
+        } else {
+            meta_parts.push(subscription.to_string());
+        }
+    }
+
     let meta_line = format!("  {}", meta_parts.join("   "));
     println!("{}", meta_line);
 
+    // Queued writes the cached state doesn't reflect yet
+    if !pending.is_empty() {
+        let actions: Vec<String> = pending.iter().map(|op| op.action()).collect();
+        let pending_line = format!("  {} {} pending sync", g.pending, actions.join(", "));
+        if tty {
+            println!("{}", pending_line.yellow());
+        } else {
+            println!("{}", pending_line);
+        }
+    }
+
+    // Everyone else on the issue, when there's more than its author
+    if participants.len() > 1 {
+        let names: Vec<String> = participants.iter().map(|p| format!("@{}", p)).collect();
+        let participants_line = format!("  participants {}", names.join(", "));
+        if tty {
+            println!("{}", participants_line.dimmed());
+        } else {
+            println!("{}", participants_line);
+        }
+    }
+
     // Timestamps line
-    let created = relative_time(&issue.created_at);
-    let updated = relative_time(&issue.updated_at);
-    let time_line = format!("  {} · updated {}", created, updated);
+    let created = timestamp(&issue.created_at);
+    let updated = timestamp(&issue.updated_at);
+    let time_line = format!("  {} {} updated {}", created, g.dot, updated);
     if tty {
         println!("{}", time_line.dimmed());
     } else {
@@ -239,32 +635,51 @@ pub fn print_issue(issue: &Issue, comments: &[Comment], elapsed_ms: u64) {
     // URL line (in header, not footer) - keep https:// for terminal clickability
     if let Some(url) = &issue.url {
         if tty {
-            println!("  {} {}", "↗".dimmed(), url.dimmed().underline());
+            println!("  {} {}", g.link.dimmed(), url.dimmed().underline());
         } else {
             println!("  {}", url);
         }
     }
 
     // Body (wrapped to terminal width with indent)
-    if let Some(body) = &issue.body {
-        if !body.trim().is_empty() {
-            println!();
-            let width = term_width();
-            print!("{}", wrap_indented(body, "  ", width));
+    if let Some(body) = &issue.body
+        && !body.trim().is_empty()
+    {
+        println!();
+        let width = term_width();
+        print!("{}", wrap_indented(body, "  ", width));
+    }
+
+    // Issues that mention this one
+    if !backlinks.is_empty() {
+        println!();
+        if tty {
+            println!("{}", "  Referenced by".bold());
+        } else {
+            println!("  Referenced by");
+        }
+        for backlink in backlinks {
+            let glyph = if backlink.state == "open" { g.open } else { g.closed };
+            let line = format!("  {} {} {}", glyph, backlink.display_id(), backlink.title);
+            if tty {
+                println!("{}", line.dimmed());
+            } else {
+                println!("{}", line);
+            }
         }
     }
 
     // Comments section
-    if !comments.is_empty() {
+    if total_comments > 0 {
         println!();
-        let light_separator = "─".repeat(60);
+        let light_separator = g.light_rule.repeat(60);
         if tty {
             println!(" {}", light_separator.dimmed());
         } else {
             println!(" {}", light_separator);
         }
 
-        let comments_header = format!("  {} comment{}", comments.len(), if comments.len() == 1 { "" } else { "s" });
+        let comments_header = format!("  {} comment{}", total_comments, if total_comments == 1 { "" } else { "s" });
         if tty {
             println!("{}", comments_header.bold());
         } else {
@@ -272,14 +687,30 @@ pub fn print_issue(issue: &Issue, comments: &[Comment], elapsed_ms: u64) {
         }
         println!();
 
+        let hidden = total_comments.saturating_sub(comments.len());
+        if hidden > 0 {
+            let hidden_line = format!(
+                "  {} {} earlier comment{} (use --comments all)",
+                g.ellipsis,
+                hidden,
+                if hidden == 1 { "" } else { "s" }
+            );
+            if tty {
+                println!("{}", hidden_line.dimmed());
+            } else {
+                println!("{}", hidden_line);
+            }
+            println!();
+        }
+
         for c in comments {
             let comment_author = format!("@{}", c.author);
-            let comment_time = relative_time(&c.created_at);
+            let comment_time = timestamp(&c.created_at);
 
             if tty {
-                println!("  {} · {}", comment_author.cyan(), comment_time.dimmed());
+                println!("  {} {} {}", comment_author.cyan(), g.dot, comment_time.dimmed());
             } else {
-                println!("  {} · {}", comment_author, comment_time);
+                println!("  {} {} {}", comment_author, g.dot, comment_time);
             }
 
             // Indent comment body (wrapped)
@@ -299,119 +730,575 @@ pub fn print_issue(issue: &Issue, comments: &[Comment], elapsed_ms: u64) {
     }
 }
 
-/// Print a compact issue list row with optional comment count
-pub fn print_issue_row(issue: &Issue, comment_count: Option<usize>) {
+// ============================================================================
+// Issue table
+// ============================================================================
+
+/// A column in the `issue list` table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    State,
+    Id,
+    Title,
+    Assignee,
+    Author,
+    Labels,
+    Goal,
+    Type,
+    Priority,
+    Sla,
+    Comments,
+    Tasks,
+    Created,
+    Updated,
+}
+
+/// Columns shown when `--columns` isn't given (empty ones are hidden)
+const DEFAULT_COLUMNS: &[Column] = &[
+    Column::State,
+    Column::Id,
+    Column::Type,
+    Column::Title,
+    Column::Labels,
+    Column::Goal,
+    Column::Sla,
+    Column::Comments,
+    Column::Tasks,
+];
+
+impl Column {
+    fn header(&self) -> &'static str {
+        match self {
+            Column::State => "",
+            Column::Id => "ID",
+            Column::Title => "TITLE",
+            Column::Assignee => "ASSIGNEE",
+            Column::Author => "AUTHOR",
+            Column::Labels => "LABELS",
+            Column::Goal => "GOAL",
+            Column::Type => "TYPE",
+            Column::Priority => "PRIORITY",
+            Column::Sla => "SLA",
+            Column::Comments => "COMMENTS",
+            Column::Tasks => "TASKS",
+            Column::Created => "CREATED",
+            Column::Updated => "UPDATED",
+        }
+    }
+
+    fn right_aligned(&self) -> bool {
+        matches!(self, Column::Id | Column::Comments | Column::Tasks)
+    }
+
+    /// Narrowest a column may be truncated to, None if it never truncates
+    fn min_width(&self) -> Option<usize> {
+        match self {
+            Column::Title => Some(20),
+            Column::Labels | Column::Goal | Column::Assignee | Column::Author => Some(8),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a column name (clap value parser for `--columns`)
+pub fn parse_column(s: &str) -> Result<Column, String> {
+    match s.trim().to_lowercase().as_str() {
+        "state" => Ok(Column::State),
+        "id" | "number" => Ok(Column::Id),
+        "title" => Ok(Column::Title),
+        "assignee" | "assignees" => Ok(Column::Assignee),
+        "author" => Ok(Column::Author),
+        "labels" | "label" => Ok(Column::Labels),
+        "goal" | "milestone" => Ok(Column::Goal),
+        "type" => Ok(Column::Type),
+        "priority" => Ok(Column::Priority),
+        "sla" | "triage" => Ok(Column::Sla),
+        "comments" => Ok(Column::Comments),
+        "tasks" => Ok(Column::Tasks),
+        "created" => Ok(Column::Created),
+        "updated" => Ok(Column::Updated),
+        other => Err(format!(
+            "unknown column '{}' (expected: state, id, title, assignee, author, labels, goal, type, priority, sla, comments, tasks, created, updated)",
+            other
+        )),
+    }
+}
+
+/// Plain-text cell content (styling is applied after padding)
+fn cell_text(column: Column, issue: &Issue, comment_count: usize, pending: bool, g: &Glyphs) -> String {
+    match column {
+        Column::State => {
+            let state = if issue.state == "open" { g.open } else { g.closed };
+            if pending {
+                format!("{}{}", state, g.pending)
+            } else {
+                state.to_string()
+            }
+        }
+        Column::Id => issue.display_id(),
+        Column::Title => issue.title.clone(),
+        Column::Assignee => issue.assignees.iter().map(|a| format!("@{}", a)).collect::<Vec<_>>().join(", "),
+        Column::Author => format!("@{}", issue.author),
+        Column::Labels => issue.labels.iter().map(|l| l.name.as_str()).collect::<Vec<_>>().join(", "),
+        Column::Goal => issue.milestone.clone().unwrap_or_default(),
+        Column::Type => issue.issue_type.as_deref().map(|t| format!("{} {}", type_glyph(t, g), t)).unwrap_or_default(),
+        Column::Priority => issue.priority.and_then(priority_name).unwrap_or_default().to_string(),
+        Column::Sla => {
+            let countdown = sla_countdown(issue, Utc::now());
+            match (issue.triage, countdown) {
+                (true, Some(countdown)) => format!("triage {} {}", g.dot, countdown),
+                (true, None) => "triage".to_string(),
+                (false, countdown) => countdown.unwrap_or_default(),
+            }
+        }
+        Column::Comments => {
+            if comment_count > 0 {
+                comment_count.to_string()
+            } else {
+                String::new()
+            }
+        }
+        Column::Tasks => match issue.body.as_deref().and_then(tasks::progress) {
+            Some((done, total)) => format!("{}/{}", done, total),
+            None => String::new(),
+        },
+        Column::Created => timestamp(&issue.created_at),
+        Column::Updated => timestamp(&issue.updated_at),
+    }
+}
+
+fn style_cell(column: Column, issue: &Issue, stale: bool, padded: String) -> String {
+    match column {
+        Column::Title | Column::Updated if stale => padded.dimmed().to_string(),
+        Column::State if issue.state == "open" => padded.green().to_string(),
+        Column::State => padded.red().to_string(),
+        Column::Id | Column::Comments | Column::Tasks | Column::Created | Column::Updated => {
+            padded.dimmed().to_string()
+        }
+        Column::Labels => padded.yellow().to_string(),
+        Column::Goal => padded.cyan().to_string(),
+        Column::Type => match issue.issue_type.as_deref().map(issue_types::kind) {
+            Some(TypeKind::Bug) => padded.red().to_string(),
+            Some(TypeKind::Feature) => padded.blue().to_string(),
+            _ => padded,
+        },
+        Column::Sla if sla_breached(issue, Utc::now()) => padded.red().to_string(),
+        Column::Sla => padded.yellow().to_string(),
+        _ => padded,
+    }
+}
+
+/// Cut `text` to `width` display columns, ending with an ellipsis if shortened
+fn truncate(text: &str, width: usize, ellipsis: &str) -> String {
+    if display_width(text) <= width {
+        return text.to_string();
+    }
+    let budget = width.saturating_sub(display_width(ellipsis));
+    let mut out = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = display_width(c.encode_utf8(&mut [0; 4]));
+        if used + w > budget {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    out.push_str(ellipsis);
+    out
+}
+
+fn pad(text: &str, width: usize, right: bool) -> String {
+    let fill = " ".repeat(width.saturating_sub(display_width(text)));
+    if right {
+        format!("{}{}", fill, text)
+    } else {
+        format!("{}{}", text, fill)
+    }
+}
+
+/// Lay out the issue table as lines.
+///
+/// With `max_width`, truncatable columns (title first) shrink until the row
+/// fits. Without it (piped output) nothing is cut, but columns stay aligned.
+fn render_issue_table(
+    issues: &[Issue],
+    comment_counts: &HashMap<u64, usize>,
+    pending: &HashSet<u64>,
+    columns: &[Column],
+    max_width: Option<usize>,
+    styled: bool,
+    g: &Glyphs,
+) -> Vec<String> {
+    let rows: Vec<Vec<String>> = issues
+        .iter()
+        .map(|issue| {
+            let count = comment_counts.get(&issue.number).copied().unwrap_or(0);
+            let is_pending = pending.contains(&issue.number);
+            columns.iter().map(|&c| cell_text(c, issue, count, is_pending, g)).collect()
+        })
+        .collect();
+
+    // Header only for humans; scripts get bare rows
+    let mut widths: Vec<usize> = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let header = if styled { display_width(c.header()) } else { 0 };
+            rows.iter().map(|r| display_width(&r[i])).fold(header, usize::max)
+        })
+        .collect();
+
+    if let Some(max_width) = max_width {
+        let gaps = 2 * columns.len().saturating_sub(1);
+        let mut total: usize = widths.iter().sum::<usize>() + gaps;
+        for target in [Column::Title, Column::Labels, Column::Goal, Column::Assignee, Column::Author] {
+            let Some(i) = columns.iter().position(|&c| c == target) else {
+                continue;
+            };
+            let Some(min) = target.min_width() else {
+                continue;
+            };
+            if total <= max_width {
+                break;
+            }
+            let cut = (total - max_width).min(widths[i].saturating_sub(min));
+            widths[i] -= cut;
+            total -= cut;
+        }
+    }
+
+    let now = Utc::now();
+    let stale_after = chrono::Duration::days(STALE_AFTER_DAYS);
+    let render_line = |cells: Vec<String>, issue: Option<&Issue>| -> String {
+        let stale = issue.is_some_and(|issue| is_stale(issue, now, stale_after));
+        let last = cells.len().saturating_sub(1);
+        let parts: Vec<String> = cells
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let column = columns[i];
+                let text = truncate(text, widths[i], g.ellipsis);
+                // No trailing padding on the last column
+                let padded = if i == last && !column.right_aligned() {
+                    text
+                } else {
+                    pad(&text, widths[i], column.right_aligned())
+                };
+                match (styled, issue) {
+                    (true, Some(issue)) => style_cell(column, issue, stale, padded),
+                    (true, None) => padded.dimmed().to_string(),
+                    _ => padded,
+                }
+            })
+            .collect();
+        parts.join("  ").trim_end().to_string()
+    };
+
+    let mut lines = Vec::new();
+    if styled {
+        lines.push(render_line(columns.iter().map(|c| c.header().to_string()).collect(), None));
+    }
+    for (issue, cells) in issues.iter().zip(rows) {
+        lines.push(render_line(cells, Some(issue)));
+    }
+    lines
+}
+
+/// Print comment search results: the issue, who commented when, and the
+/// matching part of the comment with matches in bold
+pub fn print_comment_hits(hits: &[CommentHit]) {
     let tty = is_tty();
+    let g = *GLYPHS;
 
-    let state_char = if issue.state == "open" {
+    for (i, hit) in hits.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let title = hit.issue_title.as_deref().unwrap_or("(issue not cached)");
+        let header = format!("{} {}", hit.display_id(), title);
+        let meta = format!("  {} {} {}", hit.author, g.dot, timestamp(&hit.created_at));
         if tty {
-            "●".green().to_string()
+            println!("{}", header.bold());
+            println!("{}", meta.dimmed());
         } else {
-            "●".to_string()
+            println!("{}", header);
+            println!("{}", meta);
         }
-    } else {
+        println!("  {}", highlight(&hit.snippet, tty));
+    }
+}
+
+/// A search snippet on one line, with matches in bold on a terminal
+fn highlight(snippet: &str, tty: bool) -> String {
+    let snippet = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut line = String::new();
+    for (j, part) in snippet.split(db::MATCH_START).enumerate() {
+        // Every part after the first opens with a match
+        let (matched, rest) = match part.split_once(db::MATCH_END) {
+            Some((matched, rest)) if j > 0 => (matched, rest),
+            _ => ("", part),
+        };
         if tty {
-            "○".red().to_string()
+            line.push_str(&matched.bold().to_string());
         } else {
-            "○".to_string()
+            line.push_str(matched);
         }
-    };
+        line.push_str(rest);
+    }
+    line
+}
 
-    let labels_str = format_labels(&issue.labels, tty);
+/// Print `isq search` results, each tagged with what it matched
+pub fn print_search_hits(hits: &[SearchHit]) {
+    let tty = is_tty();
+    let g = *GLYPHS;
 
-    // Format goal/milestone (if present)
-    let goal_str = issue
-        .milestone
-        .as_ref()
-        .map(|m| format!(" → {}", m))
-        .unwrap_or_default();
-
-    // Format comment count
-    let comment_str = match comment_count {
-        Some(0) | None => String::new(),
-        Some(count) => format!(" 💬{}", count),
-    };
+    for (i, hit) in hits.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let (tag, header, meta, snippet) = match hit {
+            SearchHit::Issue(hit) => (
+                "issue",
+                format!("{} {}", hit.display_id(), hit.title),
+                format!("{} {} updated {}", hit.state, g.dot, timestamp(&hit.updated_at)),
+                &hit.snippet,
+            ),
+            SearchHit::Comment(hit) => (
+                "comment",
+                format!("{} {}", hit.display_id(), hit.issue_title.as_deref().unwrap_or("(issue not cached)")),
+                format!("{} {} {}", hit.author, g.dot, timestamp(&hit.created_at)),
+                &hit.snippet,
+            ),
+            SearchHit::Goal(hit) => (
+                "goal",
+                hit.name.clone(),
+                match &hit.target_date {
+                    Some(date) => format!("{} {} due {}", hit.state, g.dot, date),
+                    None => hit.state.clone(),
+                },
+                &hit.snippet,
+            ),
+        };
+        let tag = format!("{:<8}", tag);
+        if tty {
+            println!("{}{}", tag.dimmed(), header.bold());
+            println!("{}{}", " ".repeat(8), meta.dimmed());
+        } else {
+            println!("{}{}", tag, header);
+            println!("{}{}", " ".repeat(8), meta);
+        }
+        println!("{}{}", " ".repeat(8), highlight(snippet, tty));
+    }
+}
 
-    if tty {
-        println!(
-            "{} {:>5}  {}{}{}{}",
-            state_char,
-            format!("#{}", issue.number).dimmed(),
-            issue.title,
-            labels_str,
-            goal_str.cyan(),
-            comment_str.dimmed()
-        );
+/// Print issues as an aligned table. Empty `columns` means the default set,
+/// with columns that are blank for every issue left out. Issues in `pending`
+/// have queued writes and get a marker next to their state.
+pub fn print_issue_table(
+    issues: &[Issue],
+    comment_counts: &HashMap<u64, usize>,
+    pending: &HashSet<u64>,
+    columns: &[Column],
+) {
+    let tty = is_tty();
+    let g = *GLYPHS;
+
+    let columns: Vec<Column> = if columns.is_empty() {
+        DEFAULT_COLUMNS
+            .iter()
+            .copied()
+            .filter(|&c| {
+                matches!(c, Column::State | Column::Id | Column::Title)
+                    || issues.iter().any(|issue| {
+                        let count = comment_counts.get(&issue.number).copied().unwrap_or(0);
+                        !cell_text(c, issue, count, false, g).is_empty()
+                    })
+            })
+            .collect()
     } else {
-        println!(
-            "{} #{:<5}  {}{}{}{}",
-            state_char,
-            issue.number,
-            issue.title,
-            labels_str,
-            goal_str,
-            comment_str
-        );
+        columns.to_vec()
+    };
+
+    let max_width = if tty { Some(term_width()) } else { None };
+    for line in render_issue_table(issues, comment_counts, pending, &columns, max_width, tty, g) {
+        println!("{}", line);
     }
 }
 
-/// Print a list of goals
-pub fn print_goals(goals: &[Goal]) {
+/// Print a list of goals, flagging those in `at_risk` (goal IDs)
+pub fn print_goals(goals: &[Goal], at_risk: &HashSet<&str>, today: NaiveDate) {
     if goals.is_empty() {
         println!("No goals found.");
         return;
     }
 
+    let g = *GLYPHS;
     let tty = is_tty();
+    let name_width = goals.iter().map(|goal| display_width(&goal.name)).max().unwrap_or(0);
 
     for goal in goals {
-        let status_char = match goal.state {
-            GoalState::Open => {
-                if tty {
-                    "●".yellow().to_string()
-                } else {
-                    "●".to_string()
-                }
-            }
-            GoalState::Closed => {
-                if tty {
-                    "✓".green().to_string()
-                } else {
-                    "✓".to_string()
-                }
-            }
+        let status_char = goal_state_glyph(goal.state);
+
+        // Counts when the forge gives them; the bar shows the percentage either way
+        let counts = match (goal.open_count, goal.closed_count) {
+            (Some(open), Some(closed)) => format!("{}/{}", closed, open + closed),
+            _ => String::new(),
         };
 
-        // Show counts if available, otherwise show percentage
-        let progress_str = match (goal.open_count, goal.closed_count) {
-            (Some(open), Some(closed)) => {
-                let total = open + closed;
-                if total > 0 {
-                    format!("{}/{}", closed, total)
-                } else {
-                    "0/0".to_string()
-                }
+        let days = reminders::days_left(goal, today);
+        let overdue = goal.state == GoalState::Open && days.is_some_and(|d| d < 0);
+        let target = match (&goal.target_date, days) {
+            (Some(date), Some(days)) if goal.state == GoalState::Open => {
+                format!("{} {} ({})", g.goal, date, due_in(days))
             }
-            _ => format!("{}%", (goal.progress * 100.0).round() as u32),
+            (Some(date), _) => format!("{} {}", g.goal, date),
+            (None, _) => String::new(),
         };
+        let target = if overdue && tty { target.red().to_string() } else { target };
 
-        let target = goal
-            .target_date
-            .as_ref()
-            .map(|d| format!("→ {}", d))
-            .unwrap_or_default();
+        let risk = if !at_risk.contains(goal.id.as_str()) {
+            String::new()
+        } else if tty {
+            format!("  {}", "at risk".red())
+        } else {
+            "  at risk".to_string()
+        };
 
         // Avoid dimmed colors - they're unreadable on light terminals
         println!(
-            "{} {:>8}  {}  {}",
+            "{} {:>7} {:>16}  {}{}  {}{}",
             status_char,
-            progress_str,
+            counts,
+            progress_bar(goal.progress),
             goal.name,
+            " ".repeat(name_width - display_width(&goal.name)),
+            target,
+            risk
+        );
+    }
+}
+
+/// `in 5d`, `due today`, `3d overdue`
+pub fn due_in(days: i64) -> String {
+    match days {
+        0 => "due today".to_string(),
+        d if d > 0 => format!("in {}d", d),
+        d => format!("{}d overdue", -d),
+    }
+}
+
+/// Sort goals for `goal list --sort`: `progress` (least done first), `target`
+/// (soonest first, undated last) or `name`
+pub fn sort_goals(goals: &mut [Goal], by: &str) {
+    match by {
+        "progress" => goals.sort_by(|a, b| a.progress.total_cmp(&b.progress)),
+        "target" => goals.sort_by(|a, b| match (&a.target_date, &b.target_date) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }),
+        "name" => goals.sort_by_key(|goal| goal.name.to_lowercase()),
+        _ => {}
+    }
+}
+
+/// Open/done marker for a goal or objective
+fn goal_state_glyph(state: GoalState) -> String {
+    let g = *GLYPHS;
+    match (state, is_tty()) {
+        (GoalState::Open, true) => g.goal_open.yellow().to_string(),
+        (GoalState::Open, false) => g.goal_open.to_string(),
+        (GoalState::Closed, true) => g.goal_done.green().to_string(),
+        (GoalState::Closed, false) => g.goal_done.to_string(),
+    }
+}
+
+/// `[======----] 60%`
+fn progress_bar(progress: f64) -> String {
+    let pct = (progress * 100.0).round() as usize;
+    let filled = (pct / 10).min(10);
+    format!("[{}{}] {}%", "=".repeat(filled), "-".repeat(10 - filled), pct)
+}
+
+/// Print objectives with their goal roll-up
+pub fn print_objectives(objectives: &[(Objective, ObjectiveProgress)]) {
+    if objectives.is_empty() {
+        println!("No objectives found.");
+        return;
+    }
+
+    let g = *GLYPHS;
+
+    for (objective, progress) in objectives {
+        let goals_str = format!("{}/{} goals", progress.goals_closed, progress.goals);
+        let target = objective
+            .target_date
+            .as_ref()
+            .map(|d| format!("{} {}", g.goal, d))
+            .unwrap_or_default();
+
+        println!(
+            "{} {:>10} {:>4}%  {}  {}",
+            goal_state_glyph(objective.state),
+            goals_str,
+            (progress.progress * 100.0).round() as u32,
+            objective.name,
             target
         );
     }
 }
 
+/// Print objective detail view: roll-up, then its goals
+pub fn print_objective_detail(objective: &Objective, goals: &[Goal], progress: &ObjectiveProgress, elapsed_ms: u64) {
+    let tty = is_tty();
+    let width = term_width();
+
+    if tty {
+        println!("{}", objective.name.bold());
+    } else {
+        println!("{}", objective.name);
+    }
+
+    if let Some(target) = &objective.target_date {
+        println!("Target: {}", target);
+    }
+
+    if let Some(desc) = &objective.description
+        && !desc.trim().is_empty()
+    {
+        println!();
+        print!("{}", wrap_indented(desc, "", width));
+    }
+
+    println!();
+    println!("{}", progress_bar(progress.progress));
+    println!("Goals: {}/{} closed", progress.goals_closed, progress.goals);
+    let issues = progress.open_issues + progress.closed_issues;
+    if issues > 0 {
+        println!("Issues: {}/{} closed", progress.closed_issues, issues);
+    }
+
+    if !goals.is_empty() {
+        println!();
+        print_goals(goals, &HashSet::new(), chrono::Local::now().date_naive());
+    }
+
+    if let Some(url) = &objective.html_url {
+        println!();
+        if tty {
+            println!("{}", url.underline());
+        } else {
+            println!("{}", url);
+        }
+    }
+
+    eprintln!();
+    eprintln!("Loaded in {}ms", elapsed_ms);
+}
+
 /// Print goal detail view
 pub fn print_goal_detail(goal: &Goal, elapsed_ms: u64) {
     let tty = is_tty();
@@ -430,11 +1317,11 @@ pub fn print_goal_detail(goal: &Goal, elapsed_ms: u64) {
     }
 
     // Description
-    if let Some(desc) = &goal.description {
-        if !desc.trim().is_empty() {
-            println!();
-            print!("{}", wrap_indented(desc, "", width));
-        }
+    if let Some(desc) = &goal.description
+        && !desc.trim().is_empty()
+    {
+        println!();
+        print!("{}", wrap_indented(desc, "", width));
     }
 
     // Progress bar - use filled/empty that work on both dark and light
@@ -498,6 +1385,88 @@ pub fn print_goal_detail(goal: &Goal, elapsed_ms: u64) {
     eprintln!("Loaded in {}ms", elapsed_ms);
 }
 
+// ============================================================================
+// JSON shaping
+// ============================================================================
+
+/// Keys of an issue in `--json` output
+pub const ISSUE_JSON_FIELDS: &[&str] = &[
+    "number", "title", "body", "state", "author", "labels", "created_at", "updated_at", "url", "milestone",
+    "assignees", "priority", "identifier", "pending_ops",
+];
+
+/// Titles longer than this are cut in `--compact` output
+const COMPACT_TITLE_WIDTH: usize = 80;
+
+/// Extra keys `issue show --fields` accepts besides the issue's own
+pub const SHOW_EXTRA_FIELDS: &[&str] = &["comments", "total_comments", "referenced_by", "subscribed", "participants"];
+
+/// Resolve a `--fields` entry against `allowed`, accepting `id` and `goal` as aliases
+fn parse_field(s: &str, allowed: &[&str]) -> Result<String, String> {
+    let field = match s.trim().to_lowercase().as_str() {
+        "id" => "number".to_string(),
+        "goal" => "milestone".to_string(),
+        other => other.to_string(),
+    };
+    if allowed.contains(&field.as_str()) {
+        Ok(field)
+    } else {
+        Err(format!("unknown field '{}' (expected: {})", s.trim(), allowed.join(", ")))
+    }
+}
+
+/// Extra keys `issue list --fields` accepts (`rank` comes with `--ranked`)
+pub const LIST_EXTRA_FIELDS: &[&str] = &["rank"];
+
+/// Parse an `issue list --fields` entry (clap value parser)
+pub fn parse_issue_field(s: &str) -> Result<String, String> {
+    let allowed: Vec<&str> = ISSUE_JSON_FIELDS.iter().chain(LIST_EXTRA_FIELDS).copied().collect();
+    parse_field(s, &allowed)
+}
+
+/// Parse an `issue show --fields` entry: an issue field, `comments` or `total_comments`
+pub fn parse_show_field(s: &str) -> Result<String, String> {
+    let allowed: Vec<&str> = ISSUE_JSON_FIELDS.iter().chain(SHOW_EXTRA_FIELDS).copied().collect();
+    parse_field(s, &allowed)
+}
+
+/// Trim an issue's JSON for agents: keep only `fields` (all if empty), and in
+/// compact mode drop the body (unless asked for) and empty values, cut long
+/// titles and reduce labels to their names.
+pub fn shape_issue_json(value: serde_json::Value, fields: &[String], compact: bool) -> serde_json::Value {
+    let serde_json::Value::Object(mut map) = value else {
+        return value;
+    };
+
+    if compact {
+        if !fields.iter().any(|f| f == "body") {
+            map.remove("body");
+        }
+        if let Some(serde_json::Value::String(title)) = map.get_mut("title") {
+            *title = truncate(title, COMPACT_TITLE_WIDTH, "…");
+        }
+        if let Some(serde_json::Value::Array(labels)) = map.get_mut("labels") {
+            for label in labels.iter_mut() {
+                if let Some(name) = label.get("name").cloned() {
+                    *label = name;
+                }
+            }
+        }
+        map.retain(|_, v| match v {
+            serde_json::Value::Null => false,
+            serde_json::Value::Array(items) => !items.is_empty(),
+            serde_json::Value::String(s) => !s.is_empty(),
+            _ => true,
+        });
+    }
+
+    if !fields.is_empty() {
+        map.retain(|key, _| fields.iter().any(|f| f == key));
+    }
+
+    serde_json::Value::Object(map)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -509,6 +1478,43 @@ mod tests {
         assert!(!relative_time("2024-01-01T00:00:00Z").is_empty());
     }
 
+    #[test]
+    fn test_priority_name() {
+        assert_eq!(priority_name(1), Some("Urgent"));
+        assert_eq!(priority_name(4), Some("Low"));
+        assert_eq!(priority_name(0), None);
+    }
+
+    #[test]
+    fn test_detect_theme() {
+        assert_eq!(detect_theme(Some("dumb"), Some("en_US.UTF-8")), Theme::Ascii);
+        assert_eq!(detect_theme(Some("xterm-256color"), Some("en_US.UTF-8")), Theme::Unicode);
+        assert_eq!(detect_theme(Some("xterm"), Some("C.utf8")), Theme::Unicode);
+        assert_eq!(detect_theme(Some("xterm"), Some("C")), Theme::Ascii);
+        assert_eq!(detect_theme(Some("xterm"), Some("POSIX")), Theme::Ascii);
+    }
+
+    #[test]
+    fn test_theme_from_str() {
+        assert_eq!(Theme::from_str("ASCII"), Some(Theme::Ascii));
+        assert_eq!(Theme::from_str("nerd-font"), Some(Theme::NerdFont));
+        assert_eq!(Theme::from_str("emoji"), None);
+    }
+
+    #[test]
+    fn test_ascii_glyphs_are_ascii() {
+        let g = ASCII_GLYPHS;
+        for glyph in [
+            g.open, g.closed, g.goal_open, g.goal_done, g.goal, g.link, g.dot, g.heavy_rule, g.light_rule, g.ellipsis,
+            g.pending, g.type_bug, g.type_feature, g.type_task, g.type_other, g.bar,
+        ]
+        .into_iter()
+        .chain(g.sparks)
+        {
+            assert!(glyph.is_ascii(), "{:?}", glyph);
+        }
+    }
+
     #[test]
     fn test_parse_hex_color_valid() {
         assert_eq!(parse_hex_color("ff0000"), Some((255, 0, 0)));
@@ -540,4 +1546,195 @@ mod tests {
         // Pure red (0.299 * 255 = 76.245)
         assert!((luminance(255, 0, 0) - 76.245).abs() < 0.1);
     }
+
+    fn make_issue(number: u64, title: &str, labels: &[&str]) -> Issue {
+        Issue {
+            number,
+            title: title.to_string(),
+            state: "open".to_string(),
+            author: "alice".to_string(),
+            labels: labels
+                .iter()
+                .map(|name| Label { name: name.to_string(), color: None })
+                .collect(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let now = DateTime::parse_from_rfc3339("2024-03-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let month = chrono::Duration::days(30);
+
+        let old = make_issue(1, "Old", &[]);
+        assert!(is_stale(&old, now, month));
+        assert!(!is_stale(&old, now, chrono::Duration::days(90)));
+
+        let mut closed = make_issue(2, "Closed", &[]);
+        closed.state = "closed".to_string();
+        assert!(!is_stale(&closed, now, month));
+
+        let mut unparsable = make_issue(3, "Odd", &[]);
+        unparsable.updated_at = "yesterday".to_string();
+        assert!(!is_stale(&unparsable, now, month));
+    }
+
+    #[test]
+    fn test_sla_countdown() {
+        let now = DateTime::parse_from_rfc3339("2024-03-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let mut issue = make_issue(1, "Outage", &[]);
+        assert_eq!(sla_countdown(&issue, now), None);
+
+        issue.sla_breaches_at = Some("2024-03-01T05:30:00.000Z".to_string());
+        assert_eq!(sla_countdown(&issue, now).as_deref(), Some("5h left"));
+        assert!(!sla_breached(&issue, now));
+
+        issue.sla_breaches_at = Some("2024-02-27T00:00:00Z".to_string());
+        assert_eq!(sla_countdown(&issue, now).as_deref(), Some("breached 3d ago"));
+        assert!(sla_breached(&issue, now));
+
+        issue.state = "closed".to_string();
+        assert_eq!(sla_countdown(&issue, now), None);
+        assert!(!sla_breached(&issue, now));
+    }
+
+    #[test]
+    fn test_parse_column() {
+        assert_eq!(parse_column("Title"), Ok(Column::Title));
+        assert_eq!(parse_column("milestone"), Ok(Column::Goal));
+        assert!(parse_column("body").is_err());
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 10, "..."), "short");
+        assert_eq!(truncate("a long title", 8, "..."), "a lon...");
+        assert_eq!(truncate("日本語のタイトル", 7, "…"), "日本語…");
+    }
+
+    #[test]
+    fn test_issue_table_aligns_plain_output() {
+        let issues = vec![
+            make_issue(7, "Short", &["bug"]),
+            make_issue(1234, "A much longer title", &[]),
+        ];
+        let counts = HashMap::from([(1234, 3)]);
+        let columns = [Column::Id, Column::Title, Column::Comments, Column::Labels];
+        let lines = render_issue_table(&issues, &counts, &HashSet::new(), &columns, None, false, &ASCII_GLYPHS);
+
+        assert_eq!(
+            lines,
+            vec![
+                "   #7  Short                   bug",
+                "#1234  A much longer title  3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_issue_table_marks_pending_issues() {
+        let issues = vec![make_issue(1, "Queued", &[]), make_issue(2, "Synced", &[])];
+        let columns = [Column::State, Column::Id, Column::Title];
+        let pending = HashSet::from([1]);
+        let lines = render_issue_table(&issues, &HashMap::new(), &pending, &columns, None, false, &ASCII_GLYPHS);
+
+        assert_eq!(lines, vec!["o^  #1  Queued", "o   #2  Synced"]);
+    }
+
+    #[test]
+    fn test_issue_table_shows_task_progress() {
+        let mut tracked = make_issue(1, "Tracked", &[]);
+        tracked.body = Some("- [x] one\n- [ ] two\n- [ ] three".to_string());
+        let issues = vec![tracked, make_issue(2, "Plain", &[])];
+        let columns = [Column::Id, Column::Title, Column::Tasks];
+        let lines = render_issue_table(&issues, &HashMap::new(), &HashSet::new(), &columns, None, false, &ASCII_GLYPHS);
+
+        assert_eq!(lines, vec!["#1  Tracked  1/3", "#2  Plain"]);
+    }
+
+    #[test]
+    fn test_issue_table_truncates_title_to_width() {
+        let issues = vec![make_issue(1, &"x".repeat(100), &["enhancement"])];
+        let columns = [Column::Id, Column::Title, Column::Labels];
+        let lines = render_issue_table(&issues, &HashMap::new(), &HashSet::new(), &columns, Some(50), false, &ASCII_GLYPHS);
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(display_width(&lines[0]), 50);
+        assert!(lines[0].contains("x..."));
+        assert!(lines[0].ends_with("enhancement"));
+    }
+
+    #[test]
+    fn test_parse_issue_field() {
+        assert_eq!(parse_issue_field("id").unwrap(), "number");
+        assert_eq!(parse_issue_field(" Title ").unwrap(), "title");
+        assert!(parse_issue_field("bodyy").unwrap_err().contains("expected: number, title"));
+        assert!(parse_issue_field("comments").is_err());
+        assert_eq!(parse_show_field("Comments").unwrap(), "comments");
+        assert_eq!(parse_show_field("goal").unwrap(), "milestone");
+    }
+
+    #[test]
+    fn test_shape_issue_json() {
+        let issue = serde_json::json!({
+            "number": 7,
+            "title": "x".repeat(100),
+            "body": "long body",
+            "labels": [{"name": "bug", "color": "fc2929"}],
+            "milestone": null,
+            "assignees": [],
+            "pending_ops": [],
+        });
+
+        let compact = shape_issue_json(issue.clone(), &[], true);
+        assert_eq!(
+            compact.as_object().unwrap().keys().collect::<Vec<_>>(),
+            vec!["labels", "number", "title"]
+        );
+        assert_eq!(compact["labels"], serde_json::json!(["bug"]));
+        assert_eq!(display_width(compact["title"].as_str().unwrap()), 80);
+
+        let fields = vec!["number".to_string(), "body".to_string()];
+        assert_eq!(
+            shape_issue_json(issue.clone(), &fields, true),
+            serde_json::json!({"number": 7, "body": "long body"})
+        );
+        assert_eq!(shape_issue_json(issue.clone(), &[], false), issue);
+    }
+
+    #[test]
+    fn test_sort_goals_and_due_in() {
+        let goal = |name: &str, progress: f64, target: Option<&str>| Goal {
+            id: name.to_string(),
+            name: name.to_string(),
+            description: None,
+            target_date: target.map(String::from),
+            state: GoalState::Open,
+            progress,
+            open_count: None,
+            closed_count: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            html_url: None,
+        };
+        let mut goals = vec![
+            goal("beta", 0.9, None),
+            goal("Alpha", 0.5, Some("2025-09-01")),
+            goal("gamma", 0.1, Some("2025-07-01")),
+        ];
+        let names = |goals: &[Goal]| goals.iter().map(|g| g.name.clone()).collect::<Vec<_>>();
+
+        sort_goals(&mut goals, "progress");
+        assert_eq!(names(&goals), vec!["gamma", "Alpha", "beta"]);
+        sort_goals(&mut goals, "target");
+        assert_eq!(names(&goals), vec!["gamma", "Alpha", "beta"]);
+        sort_goals(&mut goals, "name");
+        assert_eq!(names(&goals), vec!["Alpha", "beta", "gamma"]);
+
+        assert_eq!(due_in(5), "in 5d");
+        assert_eq!(due_in(0), "due today");
+        assert_eq!(due_in(-3), "3d overdue");
+    }
 }
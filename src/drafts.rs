@@ -0,0 +1,174 @@
+//! Issue drafts edited in `$EDITOR` (`isq draft new/edit`), and quoted
+//! replies (`isq comment quote`)
+//!
+//! A draft is edited as a small text file: header lines, a blank line, then
+//! the markdown body.
+//!
+//! ```text
+//! Title: Button misaligned on settings page
+//! Labels: bug, ui
+//! Goal: v1.2
+//!
+//! Off by 2px in Safari.
+//! ```
+
+use std::process::Command;
+
+use anyhow::{anyhow, Result};
+
+use crate::error::{self, ErrorKind};
+use crate::network;
+
+/// The editable parts of a draft
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DraftText {
+    pub title: String,
+    pub body: Option<String>,
+    pub labels: Vec<String>,
+    pub goal: Option<String>,
+}
+
+/// Render a draft for editing
+pub fn render(draft: &DraftText) -> String {
+    let mut text = format!("Title: {}\n", draft.title);
+    text.push_str(&format!("Labels: {}\n", draft.labels.join(", ")));
+    text.push_str(&format!("Goal: {}\n", draft.goal.as_deref().unwrap_or("")));
+    text.push('\n');
+    if let Some(body) = &draft.body {
+        text.push_str(body);
+        if !body.ends_with('\n') {
+            text.push('\n');
+        }
+    }
+    text
+}
+
+/// Parse an edited draft. Headers run until the first blank line; everything
+/// after it is the body.
+pub fn parse(text: &str) -> Result<DraftText> {
+    let mut draft = DraftText::default();
+    let mut lines = text.lines();
+
+    for line in lines.by_ref() {
+        if line.trim().is_empty() {
+            break;
+        }
+        let (key, value) = line
+            .split_once(':')
+            .ok_or_else(|| error::err(ErrorKind::Usage, format!("Expected 'Key: value' header, got '{}'", line)))?;
+        let value = value.trim();
+        match key.trim().to_lowercase().as_str() {
+            "title" => draft.title = value.to_string(),
+            "labels" => {
+                draft.labels = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty())
+                    .map(String::from)
+                    .collect()
+            }
+            "goal" => draft.goal = (!value.is_empty()).then(|| value.to_string()),
+            other => {
+                return Err(error::err(
+                    ErrorKind::Usage,
+                    format!("Unknown draft header '{}' (use Title, Labels or Goal)", other),
+                ))
+            }
+        }
+    }
+
+    let body = lines.collect::<Vec<_>>().join("\n");
+    let body = body.trim();
+    draft.body = (!body.is_empty()).then(|| body.to_string());
+
+    if draft.title.is_empty() {
+        return Err(error::err(ErrorKind::Usage, "Draft needs a 'Title:' line"));
+    }
+    Ok(draft)
+}
+
+/// Open `draft` in `$VISUAL`/`$EDITOR` (falling back to `vi`) and parse the result
+pub fn edit(draft: &DraftText) -> Result<DraftText> {
+    parse(&edit_text("draft", &render(draft))?)
+}
+
+/// A markdown blockquote of a comment, with a line for the reply below it
+pub fn quote(author: &str, body: &str) -> String {
+    let quoted: Vec<String> = body
+        .trim_end()
+        .lines()
+        .map(|line| if line.is_empty() { ">".to_string() } else { format!("> {}", line) })
+        .collect();
+    format!("@{} wrote:\n\n{}\n\n", author, quoted.join("\n"))
+}
+
+/// Open `text` in `$VISUAL`/`$EDITOR` (falling back to `vi`) and return what was saved
+pub fn edit_text(name: &str, text: &str) -> Result<String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+
+    let path = std::env::temp_dir().join(format!("isq-{}-{}.md", name, std::process::id()));
+    std::fs::write(&path, text)?;
+
+    // Run through the shell so editors with flags ("code --wait") work
+    let _waiting = network::interactive();
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(&path)
+        .status()
+        .map_err(|e| anyhow!("Failed to run editor '{}': {}", editor, e));
+    let text = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+
+    let status = status?;
+    if !status.success() {
+        anyhow::bail!("Editor '{}' exited with {}; {} left unchanged", editor, status, name);
+    }
+    Ok(text?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(
+            quote("octocat", "Agreed.\n\n- ship it\n"),
+            "@octocat wrote:\n\n> Agreed.\n>\n> - ship it\n\n"
+        );
+    }
+
+    #[test]
+    fn test_render_parse_round_trip() {
+        let draft = DraftText {
+            title: "Button misaligned".to_string(),
+            body: Some("Off by 2px.\n\nOnly in Safari.".to_string()),
+            labels: vec!["bug".to_string(), "ui".to_string()],
+            goal: Some("v1.2".to_string()),
+        };
+        assert_eq!(parse(&render(&draft)).unwrap(), draft);
+
+        let bare = DraftText { title: "Idea".to_string(), ..Default::default() };
+        assert_eq!(parse(&render(&bare)).unwrap(), bare);
+    }
+
+    #[test]
+    fn test_parse_headers_are_case_insensitive_and_optional() {
+        let draft = parse("title: Crash on save\n\nStack trace below").unwrap();
+        assert_eq!(draft.title, "Crash on save");
+        assert_eq!(draft.body.as_deref(), Some("Stack trace below"));
+        assert!(draft.labels.is_empty());
+        assert_eq!(draft.goal, None);
+    }
+
+    #[test]
+    fn test_parse_rejects_missing_title_and_unknown_headers() {
+        assert!(parse("Labels: bug\n\nbody").is_err());
+        assert!(parse("Title: x\nAssignee: me\n").is_err());
+        assert!(parse("just some text\n").is_err());
+    }
+}
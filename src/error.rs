@@ -0,0 +1,233 @@
+//! Error taxonomy and exit codes for scripting
+//!
+//! Every failure maps to an `ErrorKind` with a stable exit code, so scripts and
+//! agents can branch on the code instead of parsing messages. When `--json` is
+//! passed, errors are also written to stderr as a JSON envelope:
+//!
+//! ```json
+//! {"error": {"kind": "not-found", "message": "...", "exit_code": 5}}
+//! ```
+
+use std::fmt;
+
+/// Category of failure (stable, part of the CLI contract)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Anything not covered below
+    General,
+    /// Invalid arguments (clap parse errors)
+    Usage,
+    /// Current repo has no forge link
+    NotLinked,
+    /// No credentials available for the forge
+    AuthMissing,
+    /// Issue, goal or other resource does not exist
+    NotFound,
+    /// Forge rate limit hit
+    RateLimited,
+    /// Server state diverged (HTTP 409/422)
+    Conflict,
+    /// Write accepted locally but queued for the daemon (offline)
+    OfflineQueued,
+    /// Gave up waiting (`isq issue await --timeout`, `[network]` deadlines)
+    Timeout,
+}
+
+/// All kinds (for docs and tests)
+#[allow(dead_code)] // Used in tests
+pub const ALL_ERROR_KINDS: &[ErrorKind] = &[
+    ErrorKind::General,
+    ErrorKind::Usage,
+    ErrorKind::NotLinked,
+    ErrorKind::AuthMissing,
+    ErrorKind::NotFound,
+    ErrorKind::RateLimited,
+    ErrorKind::Conflict,
+    ErrorKind::OfflineQueued,
+    ErrorKind::Timeout,
+];
+
+impl ErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::General => "error",
+            ErrorKind::Usage => "usage",
+            ErrorKind::NotLinked => "not-linked",
+            ErrorKind::AuthMissing => "auth-missing",
+            ErrorKind::NotFound => "not-found",
+            ErrorKind::RateLimited => "rate-limited",
+            ErrorKind::Conflict => "conflict",
+            ErrorKind::OfflineQueued => "offline-queued",
+            ErrorKind::Timeout => "timeout",
+        }
+    }
+
+    /// Process exit code for this kind (2 matches clap's usage errors)
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorKind::General => 1,
+            ErrorKind::Usage => 2,
+            ErrorKind::NotLinked => 3,
+            ErrorKind::AuthMissing => 4,
+            ErrorKind::NotFound => 5,
+            ErrorKind::RateLimited => 6,
+            ErrorKind::Conflict => 7,
+            ErrorKind::OfflineQueued => 8,
+            ErrorKind::Timeout => 9,
+        }
+    }
+}
+
+/// An error with a known kind. Wrap in `anyhow::Error` and `classify` recovers the kind.
+#[derive(Debug)]
+pub struct IsqError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl IsqError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for IsqError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for IsqError {}
+
+/// Build an `anyhow::Error` of the given kind
+pub fn err(kind: ErrorKind, message: impl Into<String>) -> anyhow::Error {
+    anyhow::Error::new(IsqError::new(kind, message))
+}
+
+/// The kind of a forge's HTTP error status
+pub fn status_kind(status: u16) -> ErrorKind {
+    match status {
+        401 => ErrorKind::AuthMissing,
+        404 => ErrorKind::NotFound,
+        409 | 422 => ErrorKind::Conflict,
+        429 => ErrorKind::RateLimited,
+        _ => ErrorKind::General,
+    }
+}
+
+/// Determine the kind of an error from its chain: a typed `IsqError` (the
+/// forges build these from HTTP statuses), or a reqwest error's status.
+/// Messages are never searched, so "Issue #401 not found" isn't an auth error.
+pub fn classify(err: &anyhow::Error) -> ErrorKind {
+    for cause in err.chain() {
+        if let Some(e) = cause.downcast_ref::<IsqError>() {
+            return e.kind;
+        }
+        if let Some(e) = cause.downcast_ref::<reqwest::Error>() {
+            if let Some(status) = e.status() {
+                return status_kind(status.as_u16());
+            }
+            if e.is_timeout() {
+                return ErrorKind::Timeout;
+            }
+        }
+    }
+    ErrorKind::General
+}
+
+/// Build the JSON error envelope
+fn envelope(kind: ErrorKind, message: &str) -> serde_json::Value {
+    serde_json::json!({
+        "error": {
+            "kind": kind.as_str(),
+            "message": message,
+            "exit_code": kind.exit_code(),
+        }
+    })
+}
+
+/// Print an error to stderr (text or JSON envelope) and return its exit code
+pub fn report(err: &anyhow::Error, json: bool) -> i32 {
+    let kind = classify(err);
+
+    // Queued writes already printed their result to stdout
+    if kind == ErrorKind::OfflineQueued {
+        return kind.exit_code();
+    }
+
+    if json {
+        eprintln!("{}", envelope(kind, &err.to_string()));
+    } else {
+        eprintln!("Error: {:#}", err);
+    }
+
+    kind.exit_code()
+}
+
+/// Report a clap parse error as a usage error envelope
+pub fn report_usage(message: &str) -> i32 {
+    let kind = ErrorKind::Usage;
+    eprintln!("{}", envelope(kind, message.trim()));
+    kind.exit_code()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let mut codes: Vec<i32> = ALL_ERROR_KINDS.iter().map(|k| k.exit_code()).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), ALL_ERROR_KINDS.len());
+        assert!(!codes.contains(&0));
+    }
+
+    #[test]
+    fn test_classify_typed_error() {
+        let e = err(ErrorKind::NotLinked, "This repo is not linked");
+        assert_eq!(classify(&e), ErrorKind::NotLinked);
+        assert_eq!(e.to_string(), "This repo is not linked");
+    }
+
+    #[test]
+    fn test_classify_ignores_numbers_in_messages() {
+        let cases = [
+            "Issue #401 not found in team",
+            "Issue #404 was closed",
+            "GitHub API error 422 Unprocessable Entity",
+            "something else broke",
+        ];
+        for msg in cases {
+            assert_eq!(classify(&anyhow::anyhow!(msg)), ErrorKind::General, "{}", msg);
+        }
+    }
+
+    #[test]
+    fn test_classify_statuses() {
+        let cases = [
+            (401, ErrorKind::AuthMissing),
+            (404, ErrorKind::NotFound),
+            (409, ErrorKind::Conflict),
+            (422, ErrorKind::Conflict),
+            (429, ErrorKind::RateLimited),
+            (500, ErrorKind::General),
+        ];
+        for (status, kind) in cases {
+            let e = err(status_kind(status), format!("GitHub API error {}", status)).context("Sync failed");
+            assert_eq!(classify(&e), kind, "{}", status);
+        }
+    }
+
+    #[test]
+    fn test_envelope_shape() {
+        let value = envelope(ErrorKind::NotFound, "Issue #1 not found");
+        assert_eq!(value["error"]["kind"], "not-found");
+        assert_eq!(value["error"]["message"], "Issue #1 not found");
+        assert_eq!(value["error"]["exit_code"], 5);
+    }
+}
@@ -0,0 +1,325 @@
+//! Static HTML snapshot of the cache
+//!
+//! `isq export --format html --out site/` writes a self-contained site:
+//!
+//! ```text
+//! site/
+//!   index.html          issue table with client-side search
+//!   goals.html          goals with their issues
+//!   issues/<n>.html     one page per issue, comments included
+//!   search-index.js     prebuilt search index (a script, so it loads from file://)
+//!   style.css
+//! ```
+//!
+//! Bodies are shown as preformatted text, not rendered markdown.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::db::Comment;
+use crate::forges::{Goal, Issue};
+
+/// Characters of body and comment text kept per issue in the search index
+const SEARCH_TEXT_LIMIT: usize = 2000;
+
+const STYLE: &str = "\
+body { font: 15px/1.5 -apple-system, BlinkMacSystemFont, \"Segoe UI\", Helvetica, Arial, sans-serif; max-width: 960px; margin: 2em auto; padding: 0 1em; color: #1f2328; }
+a { color: #0969da; text-decoration: none; }
+a:hover { text-decoration: underline; }
+nav { margin-bottom: 1.5em; }
+nav a { margin-right: 1em; }
+table { border-collapse: collapse; width: 100%; }
+td, th { text-align: left; padding: 0.35em 0.5em; border-bottom: 1px solid #d0d7de; vertical-align: top; }
+.state-open { color: #1a7f37; }
+.state-closed { color: #8250df; }
+.label { display: inline-block; padding: 0 0.5em; margin-right: 0.25em; border-radius: 1em; background: #eaeef2; font-size: 0.85em; }
+.meta { color: #59636e; font-size: 0.9em; }
+.text { white-space: pre-wrap; word-wrap: break-word; font-family: inherit; }
+.comment { border: 1px solid #d0d7de; border-radius: 6px; margin: 1em 0; }
+.comment header { background: #f6f8fa; padding: 0.4em 0.8em; border-bottom: 1px solid #d0d7de; }
+.comment .text { padding: 0 0.8em; }
+#search { width: 100%; padding: 0.5em; font-size: 1em; margin-bottom: 1em; box-sizing: border-box; }
+";
+
+/// Filters the index table against `ISQ_INDEX` as you type
+const SEARCH_SCRIPT: &str = r#"
+const input = document.getElementById("search");
+const rows = new Map([...document.querySelectorAll("tr[data-number]")].map(r => [r.dataset.number, r]));
+input.addEventListener("input", () => {
+  const terms = input.value.toLowerCase().split(/\s+/).filter(Boolean);
+  for (const entry of window.ISQ_INDEX) {
+    const row = rows.get(String(entry.number));
+    if (row) row.hidden = !terms.every(t => entry.text.includes(t));
+  }
+});
+"#;
+
+/// One search index entry; `text` is lowercased for matching
+#[derive(Debug, Serialize)]
+struct SearchEntry {
+    number: u64,
+    text: String,
+}
+
+/// Escape text for HTML element content and attribute values
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Anchor for a goal on goals.html
+fn goal_anchor(name: &str) -> String {
+    let slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    format!("goal-{}", slug.trim_matches('-'))
+}
+
+/// Wrap page content in the shared layout. `root` is the relative path back to the site root.
+fn page(title: &str, root: &str, repo: &str, content: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<link rel=\"stylesheet\" href=\"{root}style.css\">\n</head>\n<body>\n\
+         <nav><strong>{repo}</strong> <a href=\"{root}index.html\">Issues</a> <a href=\"{root}goals.html\">Goals</a></nav>\n\
+         {content}\n</body>\n</html>\n",
+        title = escape(title),
+        root = root,
+        repo = escape(repo),
+        content = content,
+    )
+}
+
+fn state_badge(state: &str) -> String {
+    let state = escape(state);
+    format!("<span class=\"state-{state}\">{state}</span>")
+}
+
+fn labels_html(issue: &Issue) -> String {
+    issue
+        .labels
+        .iter()
+        .map(|l| format!("<span class=\"label\">{}</span>", escape(&l.name)))
+        .collect()
+}
+
+/// Table of issues, linking to their pages (from a page at the site root)
+fn issue_table(issues: &[&Issue]) -> String {
+    let mut out = String::from("<table>\n<tr><th>State</th><th>#</th><th>Title</th><th>Labels</th><th>Updated</th></tr>\n");
+    for issue in issues {
+        out.push_str(&format!(
+            "<tr data-number=\"{n}\"><td>{state}</td><td>{id}</td><td><a href=\"issues/{n}.html\">{title}</a></td><td>{labels}</td><td class=\"meta\">{updated}</td></tr>\n",
+            n = issue.number,
+            id = escape(&issue.display_id()),
+            state = state_badge(&issue.state),
+            title = escape(&issue.title),
+            labels = labels_html(issue),
+            updated = escape(&issue.updated_at),
+        ));
+    }
+    out.push_str("</table>");
+    out
+}
+
+fn render_index(repo: &str, issues: &[Issue]) -> String {
+    let refs: Vec<&Issue> = issues.iter().collect();
+    let open = issues.iter().filter(|i| i.state == "open").count();
+    let content = format!(
+        "<h1>Issues</h1>\n<p class=\"meta\">{} open, {} closed</p>\n\
+         <input id=\"search\" type=\"search\" placeholder=\"Search titles, bodies and comments\" autofocus>\n\
+         {}\n<script src=\"search-index.js\"></script>\n<script>{}</script>",
+        open,
+        issues.len() - open,
+        issue_table(&refs),
+        SEARCH_SCRIPT,
+    );
+    page(&format!("{} issues", repo), "", repo, &content)
+}
+
+fn render_goals(repo: &str, goals: &[Goal], issues: &[Issue]) -> String {
+    let mut content = String::from("<h1>Goals</h1>\n");
+    if goals.is_empty() {
+        content.push_str("<p>No goals.</p>\n");
+    }
+    for goal in goals {
+        content.push_str(&format!(
+            "<h2 id=\"{}\">{}</h2>\n<p class=\"meta\">{} · {}% done",
+            goal_anchor(&goal.name),
+            escape(&goal.name),
+            state_badge(goal.state.as_str()),
+            (goal.progress * 100.0).round() as u32,
+        ));
+        if let Some(target) = &goal.target_date {
+            content.push_str(&format!(" · target {}", escape(target)));
+        }
+        content.push_str("</p>\n");
+        if let Some(desc) = goal.description.as_deref().filter(|d| !d.trim().is_empty()) {
+            content.push_str(&format!("<div class=\"text\">{}</div>\n", escape(desc.trim())));
+        }
+        let in_goal: Vec<&Issue> = issues.iter().filter(|i| i.milestone.as_deref() == Some(goal.name.as_str())).collect();
+        if !in_goal.is_empty() {
+            content.push_str(&issue_table(&in_goal));
+            content.push('\n');
+        }
+    }
+    page(&format!("{} goals", repo), "", repo, &content)
+}
+
+fn render_issue(repo: &str, issue: &Issue, comments: &[Comment]) -> String {
+    let mut meta = vec![state_badge(&issue.state), format!("opened by @{}", escape(&issue.author))];
+    if !issue.assignees.is_empty() {
+        let assignees: Vec<_> = issue.assignees.iter().map(|a| format!("@{}", escape(a))).collect();
+        meta.push(format!("assigned to {}", assignees.join(", ")));
+    }
+    if let Some(goal) = &issue.milestone {
+        meta.push(format!(
+            "goal <a href=\"../goals.html#{}\">{}</a>",
+            goal_anchor(goal),
+            escape(goal)
+        ));
+    }
+    meta.push(format!("updated {}", escape(&issue.updated_at)));
+    if let Some(url) = &issue.url {
+        meta.push(format!("<a href=\"{}\">view on the tracker</a>", escape(url)));
+    }
+
+    let mut content = format!(
+        "<h1>{} <span class=\"meta\">{}</span></h1>\n<p class=\"meta\">{}</p>\n<p>{}</p>\n",
+        escape(&issue.title),
+        escape(&issue.display_id()),
+        meta.join(" · "),
+        labels_html(issue),
+    );
+    match issue.body.as_deref().filter(|b| !b.trim().is_empty()) {
+        Some(body) => content.push_str(&format!("<div class=\"text\">{}</div>\n", escape(body.trim()))),
+        None => content.push_str("<p class=\"meta\">No description.</p>\n"),
+    }
+
+    if !comments.is_empty() {
+        content.push_str(&format!("<h2>{} comments</h2>\n", comments.len()));
+    }
+    for comment in comments {
+        content.push_str(&format!(
+            "<div class=\"comment\"><header><strong>@{}</strong> <span class=\"meta\">{}</span></header><div class=\"text\">{}</div></div>\n",
+            escape(&comment.author),
+            escape(&comment.created_at),
+            escape(comment.body.trim()),
+        ));
+    }
+
+    page(&format!("{} {}", issue.display_id(), issue.title), "../", repo, &content)
+}
+
+/// `window.ISQ_INDEX = [...]`: titles, labels, bodies and comments, lowercased
+fn render_search_index(issues: &[Issue], comments: &HashMap<u64, Vec<Comment>>) -> String {
+    let entries: Vec<SearchEntry> = issues
+        .iter()
+        .map(|issue| {
+            let mut text = format!("{} {}", issue.display_id(), issue.title);
+            for label in &issue.labels {
+                text.push(' ');
+                text.push_str(&label.name);
+            }
+            let mut prose = issue.body.clone().unwrap_or_default();
+            for comment in comments.get(&issue.number).into_iter().flatten() {
+                prose.push(' ');
+                prose.push_str(&comment.body);
+            }
+            text.push(' ');
+            text.extend(prose.chars().take(SEARCH_TEXT_LIMIT));
+            SearchEntry {
+                number: issue.number,
+                text: text.to_lowercase(),
+            }
+        })
+        .collect();
+    format!("window.ISQ_INDEX = {};\n", serde_json::to_string(&entries).unwrap_or_else(|_| "[]".to_string()))
+}
+
+/// Render the whole site as (relative path, contents) pairs
+pub fn render_site(
+    repo: &str,
+    issues: &[Issue],
+    comments: &HashMap<u64, Vec<Comment>>,
+    goals: &[Goal],
+) -> Vec<(String, String)> {
+    let mut files = vec![
+        ("style.css".to_string(), STYLE.to_string()),
+        ("index.html".to_string(), render_index(repo, issues)),
+        ("goals.html".to_string(), render_goals(repo, goals, issues)),
+        ("search-index.js".to_string(), render_search_index(issues, comments)),
+    ];
+    for issue in issues {
+        let issue_comments = comments.get(&issue.number).map(Vec::as_slice).unwrap_or_default();
+        files.push((format!("issues/{}.html", issue.number), render_issue(repo, issue, issue_comments)));
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forges::Label;
+
+    fn issue(number: u64, title: &str) -> Issue {
+        Issue {
+            number,
+            title: title.to_string(),
+            body: Some("Steps: <click>".to_string()),
+            state: "open".to_string(),
+            author: "alice".to_string(),
+            labels: vec![Label::name_only("bug".to_string())],
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: "2024-01-02T00:00:00Z".to_string(),
+            milestone: Some("Q1 Release".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape(r#"<a href="x">&'"#), "&lt;a href=&quot;x&quot;&gt;&amp;&#39;");
+        assert_eq!(goal_anchor("Q1 Release!"), "goal-q1-release");
+    }
+
+    #[test]
+    fn test_render_site() {
+        let issues = vec![issue(1, "Crash on <save>"), issue(2, "Slow")];
+        let mut comments = HashMap::new();
+        comments.insert(
+            1,
+            vec![Comment {
+                comment_id: "c1".to_string(),
+                issue_number: 1,
+                body: "Also happens on LOAD".to_string(),
+                author: "bob".to_string(),
+                created_at: "2024-01-03T00:00:00Z".to_string(),
+            }],
+        );
+
+        let files: HashMap<String, String> = render_site("owner/repo", &issues, &comments, &[]).into_iter().collect();
+        assert_eq!(files.len(), 6);
+
+        let page = &files["issues/1.html"];
+        assert!(page.contains("Crash on &lt;save&gt;"));
+        assert!(page.contains("Steps: &lt;click&gt;"));
+        assert!(page.contains("@bob"));
+        assert!(page.contains("../goals.html#goal-q1-release"));
+
+        assert!(files["index.html"].contains("href=\"issues/2.html\""));
+        assert!(files["search-index.js"].contains("also happens on load"));
+    }
+}
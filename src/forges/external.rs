@@ -0,0 +1,427 @@
+//! External forge adapters: `isq-forge-<name>` executables on PATH
+//!
+//! Lets trackers isq doesn't know about (Jira Server, YouTrack, Redmine, ...)
+//! be supported without recompiling. Each call runs the adapter once with a
+//! JSON request on stdin and reads a JSON response from stdout:
+//!
+//! ```json
+//! {"version": 1, "method": "close_issue", "repo": "acme/api", "params": {"issue": 42}}
+//! {"result": null}
+//! {"error": {"kind": "not-found", "message": "Issue 42 not found"}}
+//! ```
+//!
+//! The adapter's stderr is passed through, so it can prompt or log. See
+//! `src/forges/README.md` for the methods and their params.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::io::AsyncWriteExt;
+
+use super::{CreateGoalRequest, CreateIssueRequest, Forge, ForgeType, Goal, Issue, LabelDef, LinkArgs, LinkResult, RateLimitInfo};
+use crate::db;
+use crate::error::{self, ErrorKind, ALL_ERROR_KINDS};
+use crate::repo::Repo;
+
+/// Executable name prefix; `isq-forge-redmine` provides the `redmine` forge
+pub const ADAPTER_PREFIX: &str = "isq-forge-";
+
+/// Protocol version sent with every request
+const PROTOCOL_VERSION: u32 = 1;
+
+/// Whether `name` can name an adapter (lowercase letters, digits and dashes)
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata().is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+fn path_dirs() -> Vec<PathBuf> {
+    std::env::var_os("PATH").map(|p| std::env::split_paths(&p).collect()).unwrap_or_default()
+}
+
+/// Adapter names found in `dirs`, in order, first occurrence winning
+fn discover_in(dirs: &[PathBuf]) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        let mut found: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| is_executable(&e.path()))
+            .filter_map(|e| e.file_name().to_str()?.strip_prefix(ADAPTER_PREFIX).map(String::from))
+            .filter(|name| is_valid_name(name) && ForgeType::builtin(name).is_none())
+            .collect();
+        found.sort();
+        for name in found {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// Names of the adapters on PATH
+pub fn discover() -> Vec<String> {
+    discover_in(&path_dirs())
+}
+
+/// Path of the adapter for `name`, if one is on PATH
+pub fn find_adapter(name: &str) -> Option<PathBuf> {
+    if !is_valid_name(name) {
+        return None;
+    }
+    path_dirs()
+        .into_iter()
+        .map(|dir| dir.join(format!("{}{}", ADAPTER_PREFIX, name)))
+        .find(|path| is_executable(path))
+}
+
+#[derive(Deserialize)]
+struct AdapterError {
+    #[serde(default)]
+    kind: Option<String>,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    #[serde(default)]
+    result: Value,
+    #[serde(default)]
+    error: Option<AdapterError>,
+}
+
+/// Comment as adapters report it (`db::Comment` on the wire)
+#[derive(Deserialize)]
+struct AdapterComment {
+    comment_id: String,
+    issue_number: u64,
+    body: String,
+    author: String,
+    created_at: String,
+}
+
+#[derive(Deserialize)]
+struct LinkResponse {
+    forge_repo: String,
+    display_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct LoginResponse {
+    user: String,
+}
+
+/// Turn an adapter's response into its result or a typed error
+fn parse_response<T: DeserializeOwned>(adapter: &str, stdout: &[u8]) -> Result<T> {
+    let response: Response = serde_json::from_slice(stdout)
+        .map_err(|e| anyhow!("{}{} returned invalid JSON: {}", ADAPTER_PREFIX, adapter, e))?;
+
+    if let Some(err) = response.error {
+        let kind = err
+            .kind
+            .and_then(|k| ALL_ERROR_KINDS.iter().find(|known| known.as_str() == k).copied())
+            .unwrap_or(ErrorKind::General);
+        return Err(error::err(kind, err.message));
+    }
+    serde_json::from_value(response.result)
+        .map_err(|e| anyhow!("{}{} returned an unexpected result: {}", ADAPTER_PREFIX, adapter, e))
+}
+
+/// Client for one external adapter
+pub struct ExternalClient {
+    name: String,
+    path: PathBuf,
+}
+
+impl ExternalClient {
+    /// Client for the adapter named `name` on PATH
+    pub fn new(name: &str) -> Result<Self> {
+        let path = find_adapter(name).ok_or_else(|| {
+            error::err(
+                ErrorKind::NotFound,
+                format!("No adapter for forge '{}': {}{} not found on PATH", name, ADAPTER_PREFIX, name),
+            )
+        })?;
+        Ok(Self::at(name, path))
+    }
+
+    fn at(name: &str, path: PathBuf) -> Self {
+        Self { name: name.to_string(), path }
+    }
+
+    /// Run one request through the adapter
+    async fn call<T: DeserializeOwned>(&self, method: &str, repo: Option<&Repo>, params: Value) -> Result<T> {
+        let request = json!({
+            "version": PROTOCOL_VERSION,
+            "method": method,
+            "repo": repo.map(|r| r.full_name()),
+            "params": params,
+        });
+
+        let mut child = tokio::process::Command::new(&self.path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            // A command past its deadline drops the call; don't leave the adapter running
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| anyhow!("Failed to run {}: {}", self.path.display(), e))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            // An adapter that doesn't read its request closes the pipe early; that's fine
+            let _ = stdin.write_all(request.to_string().as_bytes()).await;
+        }
+
+        let output = child.wait_with_output().await?;
+        if output.stdout.iter().all(u8::is_ascii_whitespace) && !output.status.success() {
+            return Err(anyhow!("{}{} {} failed ({})", ADAPTER_PREFIX, self.name, method, output.status));
+        }
+        parse_response(&self.name, &output.stdout)
+    }
+
+    async fn call_unit(&self, method: &str, repo: &Repo, params: Value) -> Result<()> {
+        self.call::<Value>(method, Some(repo), params).await.map(|_| ())
+    }
+}
+
+#[async_trait]
+impl Forge for ExternalClient {
+    async fn list_issues(&self, repo: &Repo) -> Result<Vec<Issue>> {
+        self.call("list_issues", Some(repo), json!({})).await
+    }
+
+    async fn create_issue(&self, repo: &Repo, req: CreateIssueRequest) -> Result<Issue> {
+        let params = json!({
+            "title": req.title,
+            "body": req.body,
+            "labels": req.labels,
+            "goal_id": req.goal_id,
+            "client_key": req.client_key,
+            "type": req.issue_type,
+            "assignee": req.assignee,
+        });
+        self.call("create_issue", Some(repo), params).await
+    }
+
+    async fn create_comment(&self, repo: &Repo, issue_number: u64, body: &str) -> Result<()> {
+        self.call_unit("create_comment", repo, json!({"issue": issue_number, "body": body})).await
+    }
+
+    async fn close_issue(&self, repo: &Repo, issue_number: u64) -> Result<()> {
+        self.call_unit("close_issue", repo, json!({"issue": issue_number})).await
+    }
+
+    async fn reopen_issue(&self, repo: &Repo, issue_number: u64) -> Result<()> {
+        self.call_unit("reopen_issue", repo, json!({"issue": issue_number})).await
+    }
+
+    async fn update_issue_body(&self, repo: &Repo, issue_number: u64, body: &str) -> Result<()> {
+        self.call_unit("update_issue_body", repo, json!({"issue": issue_number, "body": body})).await
+    }
+
+    async fn add_label(&self, repo: &Repo, issue_number: u64, label: &str) -> Result<()> {
+        self.call_unit("add_label", repo, json!({"issue": issue_number, "label": label})).await
+    }
+
+    async fn remove_label(&self, repo: &Repo, issue_number: u64, label: &str) -> Result<()> {
+        self.call_unit("remove_label", repo, json!({"issue": issue_number, "label": label})).await
+    }
+
+    async fn assign_issue(&self, repo: &Repo, issue_number: u64, assignee: &str) -> Result<()> {
+        self.call_unit("assign_issue", repo, json!({"issue": issue_number, "assignee": assignee})).await
+    }
+
+    async fn set_subscribed(&self, repo: &Repo, issue_number: u64, subscribed: bool) -> Result<()> {
+        self.call_unit("set_subscribed", repo, json!({"issue": issue_number, "subscribed": subscribed})).await
+    }
+
+    async fn list_labels(&self, repo: &Repo) -> Result<Vec<LabelDef>> {
+        self.call("list_labels", Some(repo), json!({})).await
+    }
+
+    async fn create_label(&self, repo: &Repo, label: &LabelDef) -> Result<()> {
+        self.call_unit("create_label", repo, json!({"label": label})).await
+    }
+
+    async fn update_label(&self, repo: &Repo, name: &str, label: &LabelDef) -> Result<()> {
+        self.call_unit("update_label", repo, json!({"name": name, "label": label})).await
+    }
+
+    async fn list_all_comments(&self, repo: &Repo) -> Result<Vec<db::Comment>> {
+        let comments: Vec<AdapterComment> = self.call("list_all_comments", Some(repo), json!({})).await?;
+        Ok(comments
+            .into_iter()
+            .map(|c| db::Comment {
+                comment_id: c.comment_id,
+                issue_number: c.issue_number,
+                body: c.body,
+                author: c.author,
+                created_at: c.created_at,
+            })
+            .collect())
+    }
+
+    async fn list_goals(&self, repo: &Repo) -> Result<Vec<Goal>> {
+        self.call("list_goals", Some(repo), json!({})).await
+    }
+
+    async fn create_goal(&self, repo: &Repo, req: CreateGoalRequest) -> Result<Goal> {
+        let params = json!({
+            "name": req.name,
+            "description": req.description,
+            "target_date": req.target_date,
+        });
+        self.call("create_goal", Some(repo), params).await
+    }
+
+    async fn close_goal(&self, repo: &Repo, goal_id: &str) -> Result<()> {
+        self.call_unit("close_goal", repo, json!({"goal_id": goal_id})).await
+    }
+
+    async fn reopen_goal(&self, repo: &Repo, goal_id: &str) -> Result<()> {
+        self.call_unit("reopen_goal", repo, json!({"goal_id": goal_id})).await
+    }
+
+    async fn assign_to_goal(&self, repo: &Repo, issue_number: u64, goal_id: &str) -> Result<()> {
+        self.call_unit("assign_to_goal", repo, json!({"issue": issue_number, "goal_id": goal_id})).await
+    }
+
+    async fn get_rate_limit(&self) -> Result<Option<RateLimitInfo>> {
+        // Adapters handle their tracker's limits themselves
+        Ok(None)
+    }
+
+    async fn get_user(&self) -> Result<String> {
+        self.call("get_user", None, json!({})).await
+    }
+}
+
+/// Hand credentials to the adapter (it stores them itself), returning the username
+pub async fn login(name: &str, token: Option<String>) -> Result<String> {
+    let client = ExternalClient::new(name)?;
+    let response: LoginResponse = client.call("login", None, json!({"token": token})).await?;
+    db::set_identity(&db::open()?, name, &response.user)?;
+    Ok(response.user)
+}
+
+/// Link a checkout through the adapter: it names the remote project, then the
+/// first sync runs as for any other forge
+pub async fn link(name: &str, repo_path: &str, args: &LinkArgs) -> Result<LinkResult> {
+    if args.org.is_some() {
+        return Err(error::err(ErrorKind::Usage, "--org is only supported for GitHub"));
+    }
+    let client = ExternalClient::new(name)?;
+    let mut options = args.extra.clone();
+    if let Some(team) = &args.team {
+        options.insert("team".to_string(), team.clone());
+    }
+
+    let params = json!({"repo_path": repo_path, "options": options});
+    let response: LinkResponse = client.call("link", None, params).await?;
+    let repo = match response.forge_repo.split_once('/') {
+        Some((owner, project)) if !owner.is_empty() && !project.is_empty() => Repo {
+            owner: owner.to_string(),
+            name: project.to_string(),
+        },
+        _ => {
+            return Err(anyhow!(
+                "{}{} link returned forge_repo '{}' (expected owner/name)",
+                ADAPTER_PREFIX,
+                name,
+                response.forge_repo
+            ))
+        }
+    };
+    let display_name = response.display_name.unwrap_or_else(|| repo.full_name());
+
+    println!("Syncing {}...", display_name);
+    let started = db::fetch_started();
+    let issues = client.list_issues(&repo).await?;
+
+    let conn = db::open()?;
+    db::set_repo_link(&conn, repo_path, name, &repo.full_name(), Some(&display_name))?;
+    db::save_issues(&conn, &repo.full_name(), &issues, &started)?;
+    db::add_watched_repo(&conn, repo_path)?;
+
+    println!("✓ Cached {} issues", issues.len());
+
+    Ok(LinkResult { display_name })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write an executable adapter script into `dir`
+    fn write_adapter(dir: &Path, name: &str, script: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let path = dir.join(format!("{}{}", ADAPTER_PREFIX, name));
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_discover_in() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        write_adapter(first.path(), "redmine", "true");
+        write_adapter(second.path(), "redmine", "true");
+        write_adapter(second.path(), "jira-server", "true");
+        write_adapter(second.path(), "github", "true");
+        std::fs::write(second.path().join("isq-forge-notexec"), "").unwrap();
+
+        let dirs = vec![first.path().to_path_buf(), second.path().to_path_buf()];
+        assert_eq!(discover_in(&dirs), vec!["redmine", "jira-server"]);
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let labels: Vec<LabelDef> =
+            parse_response("x", br#"{"result": [{"name": "bug", "color": null, "description": null}]}"#).unwrap();
+        assert_eq!(labels[0].name, "bug");
+
+        let err = parse_response::<Value>("x", br#"{"error": {"kind": "not-found", "message": "no such issue"}}"#)
+            .unwrap_err();
+        assert_eq!(error::classify(&err), ErrorKind::NotFound);
+        assert_eq!(err.to_string(), "no such issue");
+
+        assert!(parse_response::<Value>("x", b"not json").is_err());
+        assert!(parse_response::<Value>("x", b"{}").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_call_sends_request_on_stdin() {
+        let dir = tempfile::tempdir().unwrap();
+        // Echo the request back as the result
+        let path = write_adapter(dir.path(), "echo", r#"printf '{"result": %s}' "$(cat)""#);
+        let client = ExternalClient::at("echo", path);
+        let repo = Repo { owner: "acme".to_string(), name: "api".to_string() };
+
+        let request: Value = client.call("close_issue", Some(&repo), json!({"issue": 42})).await.unwrap();
+        assert_eq!(request["version"], 1);
+        assert_eq!(request["method"], "close_issue");
+        assert_eq!(request["repo"], "acme/api");
+        assert_eq!(request["params"]["issue"], 42);
+    }
+}
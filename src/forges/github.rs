@@ -1,17 +1,21 @@
-use std::io::Write;
+use std::collections::BTreeSet;
+use std::io::{IsTerminal, Write};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 use futures::future::join_all;
+use futures::StreamExt;
 use once_cell::sync::Lazy;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tokio::sync::{Mutex, Semaphore};
 
-use super::{AuthConfig, CreateGoalRequest, CreateIssueRequest, Forge, ForgeType, Goal, GoalState, Issue, Label, LinkArgs, LinkResult, RateLimitInfo};
+use super::{body_with_client_key, has_client_key, strip_client_key, AuthConfig, CreateGoalRequest, CreateIssueRequest, Forge, ForgeType, Goal, GoalState, Issue, Label, LabelDef, LinkArgs, LinkResult, RateLimitInfo, WhoAmI};
+use crate::config::{self, GitHubAppConfig};
+use crate::error::{self, ErrorKind};
 use crate::repo::Repo;
-use crate::{db, repo};
+use crate::{db, network, progress, repo, workspace};
 
 // ============================================================================
 // Auth Configuration
@@ -24,8 +28,158 @@ pub const AUTH: AuthConfig = AuthConfig {
     cli_command: Some(&["gh", "auth", "token"]),
     display_name: "GitHub",
     link_command: "isq link github",
+    login_command: "isq auth login github --with-token",
 };
 
+/// Kind of GitHub token, detected from its prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    ClassicPat,
+    FineGrainedPat,
+    OAuth,
+    UserToServer,
+    Installation,
+    Unknown,
+}
+
+impl TokenKind {
+    /// Detect the token kind (see GitHub's token prefix conventions)
+    pub fn detect(token: &str) -> Self {
+        if token.starts_with("github_pat_") {
+            TokenKind::FineGrainedPat
+        } else if token.starts_with("ghp_") {
+            TokenKind::ClassicPat
+        } else if token.starts_with("gho_") {
+            TokenKind::OAuth
+        } else if token.starts_with("ghu_") {
+            TokenKind::UserToServer
+        } else if token.starts_with("ghs_") {
+            TokenKind::Installation
+        } else {
+            TokenKind::Unknown
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            TokenKind::ClassicPat => "classic PAT",
+            TokenKind::FineGrainedPat => "fine-grained PAT",
+            TokenKind::OAuth => "OAuth token",
+            TokenKind::UserToServer => "GitHub App user token",
+            TokenKind::Installation => "GitHub App installation token",
+            TokenKind::Unknown => "token",
+        }
+    }
+}
+
+// ============================================================================
+// GitHub App Installation Tokens
+// ============================================================================
+
+/// Refresh installation tokens this long before GitHub expires them (they last 1 hour)
+const APP_TOKEN_REFRESH_MARGIN_SECS: i64 = 300;
+
+/// Installation token shared by all clients in this process (the daemon creates
+/// a client per sync, so caching here avoids minting a token every cycle)
+static APP_TOKEN: Lazy<Mutex<Option<CachedAppToken>>> = Lazy::new(|| Mutex::new(None));
+
+struct CachedAppToken {
+    installation_id: u64,
+    token: String,
+    expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// JWT claims for authenticating as the app
+#[derive(Serialize)]
+struct AppClaims {
+    iat: i64,
+    exp: i64,
+    iss: String,
+}
+
+#[derive(Deserialize)]
+struct InstallationTokenResponse {
+    token: String,
+    /// RFC 3339 timestamp
+    expires_at: String,
+}
+
+/// Mint a new installation token using a JWT signed with the app's private key
+async fn mint_installation_token(client: &reqwest::Client, app: &GitHubAppConfig) -> Result<InstallationTokenResponse> {
+    let key_path = app.private_key_path();
+    let pem = std::fs::read(&key_path)
+        .map_err(|e| anyhow!("Failed to read GitHub App private key {}: {}", key_path.display(), e))?;
+    let key = jsonwebtoken::EncodingKey::from_rsa_pem(&pem)
+        .map_err(|e| anyhow!("Invalid GitHub App private key {}: {}", key_path.display(), e))?;
+
+    // Backdate iat for clock drift; GitHub caps JWT lifetime at 10 minutes
+    let now = chrono::Utc::now().timestamp();
+    let claims = AppClaims {
+        iat: now - 60,
+        exp: now + 540,
+        iss: app.app_id.to_string(),
+    };
+    let jwt = jsonwebtoken::encode(&jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256), &claims, &key)?;
+
+    let url = format!("https://api.github.com/app/installations/{}/access_tokens", app.installation_id);
+    let response = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", jwt))
+        .header("User-Agent", "isq")
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await?;
+        return Err(error::err(
+            ErrorKind::AuthMissing,
+            format!("GitHub App installation token request failed ({}): {}", status, body),
+        ));
+    }
+
+    Ok(response.json().await?)
+}
+
+/// Get a valid installation token, minting a new one when missing or about to expire
+async fn app_token(client: &reqwest::Client, app: &GitHubAppConfig) -> Result<String> {
+    let mut cached = APP_TOKEN.lock().await;
+
+    if let Some(c) = cached.as_ref() {
+        let refresh_at = c.expires_at - chrono::Duration::seconds(APP_TOKEN_REFRESH_MARGIN_SECS);
+        if c.installation_id == app.installation_id && chrono::Utc::now() < refresh_at {
+            return Ok(c.token.clone());
+        }
+    }
+
+    let minted = mint_installation_token(client, app).await?;
+    let token = minted.token.clone();
+    // If the expiry can't be parsed, cache it for the minimum lifetime we rely on
+    let expires_at = chrono::DateTime::parse_from_rfc3339(&minted.expires_at)
+        .map(|dt| dt.with_timezone(&chrono::Utc))
+        .unwrap_or_else(|_| chrono::Utc::now() + chrono::Duration::seconds(APP_TOKEN_REFRESH_MARGIN_SECS * 2));
+    *cached = Some(CachedAppToken {
+        installation_id: app.installation_id,
+        token: minted.token,
+        expires_at,
+    });
+    Ok(token)
+}
+
+/// Build a client for `owner`'s repos from configured credentials: a named
+/// account's token, else the GitHub App from config.toml if it's installed on
+/// `owner`, otherwise the user token fallback chain (CLI → keyring → env)
+pub fn client_from_config(account: Option<&str>, owner: &str) -> Result<GitHubClient> {
+    if account.is_some() {
+        return Ok(GitHubClient::new(AUTH.get_account_token(account)?));
+    }
+    match config::load()?.github.app_for(owner) {
+        Some(app) => Ok(GitHubClient::from_app(app)),
+        None => Ok(GitHubClient::new(AUTH.get_token()?)),
+    }
+}
+
 // ============================================================================
 // OAuth Device Flow
 // ============================================================================
@@ -59,7 +213,7 @@ struct DeviceCodeResponse {
 /// Run the GitHub Device Flow for authentication
 /// Shows a code for the user to enter at github.com/login/device
 pub async fn oauth_flow() -> Result<TokenResponse> {
-    let client = reqwest::Client::new();
+    let client = network::client();
 
     // Step 1: Request device code
     let params = [
@@ -89,6 +243,7 @@ pub async fn oauth_flow() -> Result<TokenResponse> {
 
     print!("Waiting for authorization...");
     std::io::stdout().flush()?;
+    let _waiting = network::interactive();
 
     // Step 3: Poll for token
     let interval = std::time::Duration::from_secs(device.interval.max(5));
@@ -151,56 +306,287 @@ pub async fn oauth_flow() -> Result<TokenResponse> {
     Err(anyhow!("Authorization timed out. Please try again."))
 }
 
+/// Store credentials for `isq auth login github`.
+/// A provided token (PAT) is verified before it's saved; otherwise runs the device flow.
+pub async fn login(token: Option<String>, account: Option<&str>) -> Result<String> {
+    let token = match token {
+        Some(token) => token,
+        None => oauth_flow().await?.access_token,
+    };
+
+    let (username, scopes, _) = GitHubClient::new(token.clone())
+        .token_info()
+        .await
+        .map_err(|e| error::err(ErrorKind::AuthMissing, format!("GitHub rejected the token: {}", e)))?;
+    if let Some(scopes) = scopes {
+        check_scopes(&scopes)?;
+    }
+    AUTH.store_credential(account, &token, None, None)?;
+    db::set_identity(&db::open()?, &super::account_key(ForgeType::GitHub.as_str(), account), &username)?;
+    Ok(format!("{} ({})", username, TokenKind::detect(&token).description()))
+}
+
+/// Verify the GitHub credential (the App, or a token) for `isq whoami`
+pub async fn whoami(account: Option<&str>) -> Result<WhoAmI> {
+    let verified_at = chrono::Utc::now().to_rfc3339();
+    let app = if account.is_none() { config::load()?.github.app } else { None };
+    if let Some(app) = app {
+        // Installation tokens can't call /user, so minting one is the check
+        let login = format!("GitHub App installation {} ({} repos)", app.installation_id, app.owner);
+        GitHubClient::from_app(app).auth_header().await?;
+        return Ok(WhoAmI {
+            forge: ForgeType::GitHub.as_str().to_string(),
+            account: None,
+            login,
+            source: "config.toml".to_string(),
+            token_kind: Some(TokenKind::Installation.description().to_string()),
+            scopes: None,
+            expires_at: None,
+            verified_at,
+        });
+    }
+
+    let (token, source) = AUTH.account_token_source(account)?;
+    let kind = TokenKind::detect(&token);
+    let (login, scopes, expires_at) = GitHubClient::new(token)
+        .token_info()
+        .await
+        .map_err(|e| error::err(ErrorKind::AuthMissing, format!("GitHub rejected the token from {}: {}", source, e)))?;
+    db::set_identity(&db::open()?, &super::account_key(ForgeType::GitHub.as_str(), account), &login)?;
+
+    Ok(WhoAmI {
+        forge: ForgeType::GitHub.as_str().to_string(),
+        account: account.map(String::from),
+        login,
+        source,
+        token_kind: Some(kind.description().to_string()),
+        scopes,
+        expires_at,
+        verified_at,
+    })
+}
+
 // ============================================================================
 // Link Flow
 // ============================================================================
 
+/// Initial syncs running at once during `link --org`
+const BULK_SYNC_CONCURRENCY: usize = 4;
+
 /// Run the complete GitHub link flow.
 /// Handles auth, verifies credentials, syncs issues, and returns the result.
-pub async fn link(repo_path: &str, _args: &LinkArgs) -> Result<LinkResult> {
-    let forge_type = ForgeType::GitHub;
+pub async fn link(repo_path: &str, args: &LinkArgs) -> Result<LinkResult> {
     let conn = db::open()?;
 
-    // Detect GitHub repo from git remote
-    let repo = repo::detect_repo()?;
-
-    // Try existing auth first, fall back to OAuth
-    let (token, auth_method) = match AUTH.get_token() {
-        Ok(t) => (t, "stored"),
-        Err(_) => {
-            let oauth_token = oauth_flow().await?;
-            AUTH.store_credential(
-                &oauth_token.access_token,
-                oauth_token.refresh_token.as_deref(),
-                None, // GitHub tokens don't expire by default
-            )?;
-            (oauth_token.access_token, "OAuth")
+    if let Some(org) = &args.org {
+        let client = authenticate(&conn, args.account.as_deref(), org).await?;
+        if let Some(query) = &args.query {
+            return link_search(&conn, &client, org, query, args).await;
         }
-    };
-
-    let client = GitHubClient::new(token);
+        return link_org(&conn, &client, org, args).await;
+    }
 
-    // Verify authentication
-    let username = client.get_user().await?;
-    println!("✓ Authenticated as {} (via {})", username, auth_method);
+    // Detect GitHub repo from git remote
+    let repo = repo::detect_repo()?;
+    let client = authenticate(&conn, args.account.as_deref(), &repo.owner).await?;
 
     // Sync issues
     let display_name = repo.full_name();
     println!("Syncing {}...", display_name);
-    let issues = client.list_issues(&repo).await?;
+    let cached = client.sync_issues_resumable(&conn, &repo).await?;
 
     // Save to database
-    db::set_repo_link(&conn, repo_path, forge_type.as_str(), &repo.full_name(), Some(&display_name))?;
-    db::save_issues(&conn, &repo.full_name(), &issues)?;
+    db::set_repo_link(&conn, repo_path, ForgeType::GitHub.as_str(), &repo.full_name(), Some(&display_name))?;
+    db::set_repo_account(&conn, repo_path, args.account.as_deref())?;
     db::add_watched_repo(&conn, repo_path)?;
 
-    println!("✓ Cached {} issues", issues.len());
+    println!("✓ Cached {} issues", cached);
 
     Ok(LinkResult {
         display_name,
     })
 }
 
+/// Link every selected repo of an org (or user) in one pass.
+///
+/// Repos are linked without a local checkout (see `db::remote_repo_path`) so the
+/// daemon keeps their caches warm. Initial syncs run with bounded concurrency.
+async fn link_org(conn: &rusqlite::Connection, client: &GitHubClient, org: &str, args: &LinkArgs) -> Result<LinkResult> {
+    println!("Listing repos for {}...", org);
+    let mut repos = client.list_owner_repos(org).await?;
+    if let Some(pattern) = &args.pattern {
+        repos.retain(|r| super::glob_match(pattern, &r.name));
+    }
+    if repos.is_empty() {
+        return Err(error::err(ErrorKind::NotFound, format!("No matching repos found for {}", org)));
+    }
+
+    let selected: Vec<GitHubRepo> = if args.all || args.pattern.is_some() {
+        repos
+    } else {
+        select_repos(repos)?
+    };
+
+    // Repos already linked (locally or remotely) keep their existing link
+    let mut to_sync = Vec::new();
+    for r in selected {
+        if db::find_repo_path(conn, &r.full_name)?.is_some() {
+            println!("  {} already linked, skipping", r.full_name);
+        } else {
+            to_sync.push(r);
+        }
+    }
+
+    println!("Syncing {} repos...", to_sync.len());
+    let results: Vec<(GitHubRepo, String, Result<Vec<Issue>>)> = futures::stream::iter(to_sync)
+        .map(|r| async move {
+            let repo = Repo {
+                owner: r.owner.login.clone(),
+                name: r.name.clone(),
+            };
+            let started = db::fetch_started();
+            let issues = client.list_issues(&repo).await;
+            (r, started, issues)
+        })
+        .buffer_unordered(BULK_SYNC_CONCURRENCY)
+        .collect()
+        .await;
+
+    // Link even when the first sync failed; the daemon retries with backoff
+    let mut linked = 0;
+    for (r, started, issues) in &results {
+        let path = db::remote_repo_path(ForgeType::GitHub.as_str(), &r.full_name);
+        db::set_repo_link(conn, &path, ForgeType::GitHub.as_str(), &r.full_name, Some(&r.full_name))?;
+        db::set_repo_account(conn, &path, args.account.as_deref())?;
+        db::add_watched_repo(conn, &path)?;
+        linked += 1;
+
+        match issues {
+            Ok(issues) => {
+                db::save_issues(conn, &r.full_name, issues, started)?;
+                println!("  ✓ {} ({} issues)", r.full_name, issues.len());
+            }
+            Err(e) => println!("  ✗ {} (will retry in background: {})", r.full_name, e),
+        }
+    }
+
+    Ok(LinkResult {
+        display_name: format!("{} repos in {}", linked, org),
+    })
+}
+
+/// Link a search workspace: the issues in an org's (or user's) repos that
+/// match `query`, cached under their own repos (see workspace.rs)
+async fn link_search(
+    conn: &rusqlite::Connection,
+    client: &GitHubClient,
+    org: &str,
+    query: &str,
+    args: &LinkArgs,
+) -> Result<LinkResult> {
+    let forge_repo = workspace::forge_repo(org, query);
+    let display_name = format!("{} ({})", org, query.trim());
+    println!("Searching {}...", display_name);
+    let found = workspace::sync(conn, client, &forge_repo).await?;
+
+    let path = db::remote_repo_path(ForgeType::GitHub.as_str(), &forge_repo);
+    db::set_repo_link(conn, &path, ForgeType::GitHub.as_str(), &forge_repo, Some(&display_name))?;
+    db::set_repo_account(conn, &path, args.account.as_deref())?;
+    db::add_watched_repo(conn, &path)?;
+    println!("✓ Cached {} issues", found);
+
+    Ok(LinkResult { display_name })
+}
+
+/// Prompt for which repos to link
+fn select_repos(repos: Vec<GitHubRepo>) -> Result<Vec<GitHubRepo>> {
+    if !std::io::stdin().is_terminal() {
+        return Err(error::err(
+            ErrorKind::Usage,
+            "Pass --all or --match <pattern> to pick repos non-interactively",
+        ));
+    }
+
+    for (i, r) in repos.iter().enumerate() {
+        match &r.description {
+            Some(desc) if !desc.is_empty() => println!("  {:>3}. {}  {}", i + 1, r.full_name, desc),
+            _ => println!("  {:>3}. {}", i + 1, r.full_name),
+        }
+    }
+    print!("\nRepos to link (e.g. 1,3,5-7 or all): ");
+    std::io::stdout().flush()?;
+
+    let mut input = String::new();
+    {
+        let _waiting = network::interactive();
+        std::io::stdin().read_line(&mut input)?;
+    }
+    let indices = super::parse_selection(&input, repos.len()).map_err(|e| error::err(ErrorKind::Usage, e))?;
+
+    Ok(repos
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| indices.contains(i))
+        .map(|(_, r)| r)
+        .collect())
+}
+
+/// Authenticate for `owner`'s repos with the configured GitHub App (if it's
+/// installed on `owner`), a stored token, or OAuth. A named account skips the
+/// App and keeps its token under its own name.
+async fn authenticate(conn: &rusqlite::Connection, account: Option<&str>, owner: &str) -> Result<GitHubClient> {
+    let app = if account.is_none() { config::load()?.github.app_for(owner) } else { None };
+    let client = if let Some(app) = app {
+        // Installation tokens can't call /user, so minting one is the auth check
+        let installation_id = app.installation_id;
+        let client = GitHubClient::from_app(app);
+        client.auth_header().await?;
+        println!("✓ Authenticated as GitHub App installation {}", installation_id);
+        client
+    } else {
+        // Try existing auth first, fall back to OAuth
+        let (token, auth_method) = match AUTH.get_account_token(account) {
+            Ok(t) => (t, "stored"),
+            Err(_) => {
+                let oauth_token = oauth_flow().await?;
+                AUTH.store_credential(
+                    account,
+                    &oauth_token.access_token,
+                    oauth_token.refresh_token.as_deref(),
+                    None, // GitHub tokens don't expire by default
+                )?;
+                (oauth_token.access_token, "OAuth")
+            }
+        };
+
+        let kind = TokenKind::detect(&token);
+        let client = GitHubClient::new(token);
+
+        // Verify authentication, and that the token can see private repos
+        let (username, scopes, _) = client.token_info().await?;
+        println!("✓ Authenticated as {} (via {} {})", username, auth_method, kind.description());
+        if let Some(scopes) = scopes {
+            check_scopes(&scopes)?;
+        }
+        db::set_identity(conn, &super::account_key(ForgeType::GitHub.as_str(), account), &username)?;
+        client
+    };
+    Ok(client)
+}
+
+/// Refuse a token that can't write issues at all. One with only
+/// `public_repo` still works for public repos, so it just gets a warning.
+fn check_scopes(scopes: &[String]) -> Result<()> {
+    let Some(scope) = missing_scope(scopes) else {
+        return Ok(());
+    };
+    if scopes.iter().any(|s| s == "public_repo") {
+        eprintln!("⚠ {}", missing_scope_message(scope, scopes));
+        return Ok(());
+    }
+    Err(error::err(ErrorKind::AuthMissing, missing_scope_message(scope, scopes)))
+}
+
 // ============================================================================
 // GitHub API Client
 // ============================================================================
@@ -241,15 +627,67 @@ fn is_rate_limited(status: u16, body: &str) -> bool {
 /// Parse retry-after header or use exponential backoff
 fn get_retry_delay(response: &reqwest::Response, attempt: u32) -> Duration {
     // Check retry-after header first
-    if let Some(retry_after) = response.headers().get("retry-after") {
-        if let Ok(secs) = retry_after.to_str().unwrap_or("").parse::<u64>() {
-            return Duration::from_secs(secs);
-        }
+    if let Some(retry_after) = response.headers().get("retry-after")
+        && let Ok(secs) = retry_after.to_str().unwrap_or("").parse::<u64>()
+    {
+        return Duration::from_secs(secs);
     }
     // Exponential backoff: 1s, 2s, 4s
     Duration::from_secs(1 << attempt)
 }
 
+/// The scope isq needs on classic PATs and OAuth tokens. Without it GitHub
+/// answers requests for private repos with 404, as if they didn't exist.
+const REQUIRED_SCOPE: &str = "repo";
+
+/// The token's OAuth scopes from the X-OAuth-Scopes header. None for tokens
+/// that don't have scopes (fine-grained PATs, GitHub App tokens).
+fn oauth_scopes(response: &reqwest::Response) -> Option<Vec<String>> {
+    let header = response.headers().get("x-oauth-scopes")?.to_str().ok()?;
+    Some(header.split(',').map(str::trim).filter(|s| !s.is_empty()).map(String::from).collect())
+}
+
+/// The scope isq needs that `scopes` lacks, if any
+fn missing_scope(scopes: &[String]) -> Option<&'static str> {
+    (!scopes.iter().any(|s| s == REQUIRED_SCOPE)).then_some(REQUIRED_SCOPE)
+}
+
+/// What the user should do about a token that lacks `scope`
+fn missing_scope_message(scope: &str, scopes: &[String]) -> String {
+    let has = if scopes.is_empty() { "none".to_string() } else { scopes.join(", ") };
+    format!(
+        "Your GitHub token is missing the `{scope}` scope (it has: {has}), so private repos look empty and writes fail.\n\
+         Fix: `gh auth refresh -s {scope}` if the token comes from gh, or create a token with `{scope}` at \
+         https://github.com/settings/tokens/new?scopes={scope} and run `{}`",
+        AUTH.login_command
+    )
+}
+
+/// The error for a failed API response, naming the missing scope when that's
+/// the likely cause
+async fn api_error(response: reqwest::Response) -> anyhow::Error {
+    let status = response.status();
+    let scopes = oauth_scopes(&response);
+    match response.text().await {
+        Ok(body) => explain_api_error(status, scopes.as_deref(), &body),
+        Err(e) => e.into(),
+    }
+}
+
+pub(super) fn explain_api_error(status: reqwest::StatusCode, scopes: Option<&[String]>, body: &str) -> anyhow::Error {
+    let forbidden = matches!(status.as_u16(), 403 | 404) && !is_rate_limited(status.as_u16(), body);
+    match scopes.filter(|_| forbidden).and_then(|s| missing_scope(s).map(|scope| (scope, s))) {
+        Some((scope, scopes)) => error::err(
+            ErrorKind::AuthMissing,
+            format!("GitHub API error {}: {}", status, missing_scope_message(scope, scopes)),
+        ),
+        None if is_rate_limited(status.as_u16(), body) => {
+            error::err(ErrorKind::RateLimited, format!("GitHub API error {}: {}", status, body))
+        }
+        None => error::err(error::status_kind(status.as_u16()), format!("GitHub API error {}: {}", status, body)),
+    }
+}
+
 /// GitHub API issue response (for deserializing)
 #[derive(Debug, Clone, Deserialize)]
 struct GitHubIssue {
@@ -260,10 +698,20 @@ struct GitHubIssue {
     user: GitHubUser,
     labels: Vec<GitHubLabel>,
     milestone: Option<GitHubMilestoneRef>,
+    #[serde(default)]
+    assignees: Vec<GitHubUser>,
     created_at: String,
     updated_at: String,
     #[serde(default)]
     html_url: Option<String>,
+    /// Set on plans with issue types
+    #[serde(rename = "type", default)]
+    issue_type: Option<GitHubIssueType>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GitHubIssueType {
+    name: String,
 }
 
 impl GitHubIssue {
@@ -271,7 +719,7 @@ impl GitHubIssue {
         Issue {
             number: self.number,
             title: self.title,
-            body: self.body,
+            body: self.body.map(|b| strip_client_key(&b)).filter(|b| !b.is_empty()),
             state: self.state,
             author: self.user.login,
             labels: self.labels.into_iter().map(|l| Label::new(l.name, Some(l.color))).collect(),
@@ -279,6 +727,14 @@ impl GitHubIssue {
             updated_at: self.updated_at,
             url: self.html_url,
             milestone: self.milestone.map(|m| m.title),
+            assignees: self.assignees.into_iter().map(|u| u.login).collect(),
+            priority: None,
+            identifier: None,
+            sla_breaches_at: None,
+            triage: false,
+            issue_type: self.issue_type.map(|t| t.name),
+            estimate: None,
+            cycle: None,
         }
     }
 }
@@ -294,6 +750,14 @@ struct GitHubLabel {
     color: String,
 }
 
+/// A repo label from the labels API
+#[derive(Debug, Clone, Deserialize)]
+struct GitHubRepoLabel {
+    name: String,
+    color: String,
+    description: Option<String>,
+}
+
 /// Minimal milestone info embedded in issue responses
 #[derive(Debug, Clone, Deserialize)]
 struct GitHubMilestoneRef {
@@ -362,10 +826,23 @@ impl From<GitHubMilestone> for Goal {
     }
 }
 
+/// A repo from the org/user repo listing
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHubRepo {
+    pub name: String,
+    pub full_name: String,
+    pub owner: GitHubUser,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+}
+
 #[derive(Clone)]
 pub struct GitHubClient {
     client: reqwest::Client,
     token: String,
+    /// When set, `token` is unused and installation tokens are minted on demand
+    app: Option<GitHubAppConfig>,
 }
 
 #[derive(Deserialize)]
@@ -373,14 +850,60 @@ struct SearchResult {
     total_count: usize,
 }
 
+/// Most results the search API returns for one query
+const SEARCH_RESULT_LIMIT: usize = 1000;
+
+/// One page of issue search results
+#[derive(Debug, Deserialize)]
+struct IssueSearchPage {
+    total_count: usize,
+    items: Vec<SearchedIssue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchedIssue {
+    /// API URL of the issue's repo (".../repos/owner/name")
+    repository_url: String,
+    #[serde(flatten)]
+    issue: GitHubIssue,
+}
+
+impl SearchedIssue {
+    /// The issue's repo as "owner/name"
+    fn forge_repo(&self) -> Option<String> {
+        let mut parts = self.repository_url.rsplit('/');
+        let (name, owner) = (parts.next()?, parts.next()?);
+        Some(format!("{}/{}", owner, name))
+    }
+}
+
 impl GitHubClient {
     pub fn new(token: String) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: network::client(),
             token,
+            app: None,
         }
     }
 
+    /// Client authenticating as a GitHub App installation
+    pub fn from_app(app: GitHubAppConfig) -> Self {
+        Self {
+            client: network::client(),
+            token: String::new(),
+            app: Some(app),
+        }
+    }
+
+    /// Authorization header value, refreshing the installation token if needed
+    async fn auth_header(&self) -> Result<String> {
+        let token = match &self.app {
+            Some(app) => app_token(&self.client, app).await?,
+            None => self.token.clone(),
+        };
+        Ok(format!("Bearer {}", token))
+    }
+
     /// Fetch all open issues for a repo (parallel pagination with rate limiting)
     pub async fn list_issues(&self, repo: &Repo) -> Result<Vec<Issue>> {
         // Get total count from search API
@@ -390,7 +913,7 @@ impl GitHubClient {
             return Ok(Vec::new());
         }
 
-        let total_pages = (total + PER_PAGE - 1) / PER_PAGE;
+        let total_pages = total.div_ceil(PER_PAGE);
         eprintln!("Fetching {} issues across {} pages...", total, total_pages);
 
         // Fetch all pages in parallel with semaphore-bounded concurrency
@@ -417,7 +940,7 @@ impl GitHubClient {
                 Err(e) => {
                     let err_str = e.to_string();
                     eprintln!("Warning: page fetch failed: {}", err_str);
-                    if err_str.contains("rate limit") || err_str.contains("403") {
+                    if error::classify(&e) == ErrorKind::RateLimited {
                         rate_limit_errors += 1;
                     }
                     error_count += 1;
@@ -427,13 +950,15 @@ impl GitHubClient {
 
         // If we expected issues but got none, sync failed completely
         if all_issues.is_empty() && total > 0 {
-            let reason = if rate_limit_errors > 0 { "rate limit" } else { "network error" };
-            anyhow::bail!(
-                "Sync failed ({}): all {} page fetches failed (expected {} issues)",
-                reason,
-                total_pages,
-                total
-            );
+            let (kind, reason) = if rate_limit_errors > 0 {
+                (ErrorKind::RateLimited, "rate limit")
+            } else {
+                (ErrorKind::General, "network error")
+            };
+            return Err(error::err(
+                kind,
+                format!("Sync failed ({}): all {} page fetches failed (expected {} issues)", reason, total_pages, total),
+            ));
         }
 
         // Warn if we got partial results
@@ -447,6 +972,96 @@ impl GitHubClient {
         Ok(all_issues)
     }
 
+    /// Fetch every issue into the cache page by page, with a progress bar.
+    /// Finished pages are recorded, so if this is interrupted the next call
+    /// only fetches the rest. Returns the number of issues cached.
+    pub async fn sync_issues_resumable(&self, conn: &rusqlite::Connection, repo: &Repo) -> Result<usize> {
+        let forge_repo = repo.full_name();
+        let started = db::fetch_started();
+        let mut done: BTreeSet<usize> = match db::get_sync_cursor(conn, &forge_repo)? {
+            Some(cursor) => serde_json::from_str(&cursor).unwrap_or_default(),
+            None => BTreeSet::new(),
+        };
+        let resumed = !done.is_empty();
+
+        let total_pages = self.get_issue_count(repo).await?.div_ceil(PER_PAGE);
+        let remaining: Vec<usize> = (1..=total_pages).filter(|page| !done.contains(page)).collect();
+        if resumed {
+            eprintln!("Resuming: {} of {} pages already cached", total_pages - remaining.len(), total_pages);
+        }
+
+        let pb = progress::bar(total_pages as u64, "Fetching issues");
+        pb.set_position((total_pages - remaining.len()) as u64);
+
+        let mut pages = futures::stream::iter(remaining)
+            .map(|page| async move {
+                let _permit = REQUEST_SEMAPHORE.acquire().await.unwrap();
+                (page, self.fetch_page_with_retry(repo, page).await)
+            })
+            .buffer_unordered(MAX_CONCURRENT_REQUESTS);
+
+        let mut fetched = Vec::new();
+        let mut failed = 0;
+        while let Some((page, result)) = pages.next().await {
+            match result {
+                Ok(issues) => {
+                    done.insert(page);
+                    db::save_issue_page(conn, &forge_repo, &issues, &serde_json::to_string(&done)?)?;
+                    fetched.extend(issues);
+                    pb.inc(1);
+                }
+                Err(e) => {
+                    pb.suspend(|| eprintln!("Warning: page {} fetch failed: {}", page, e));
+                    failed += 1;
+                }
+            }
+        }
+        pb.finish_and_clear();
+
+        if failed > 0 {
+            eprintln!(
+                "Warning: {} of {} pages failed; run the command again to fetch the rest",
+                failed, total_pages
+            );
+        } else if resumed {
+            db::finish_resumed_sync(conn, &forge_repo)?;
+        } else {
+            db::save_issues(conn, &forge_repo, &fetched, &started)?;
+        }
+        Ok(db::cache_size(conn, &forge_repo)?.issues as usize)
+    }
+
+    /// Every comment on one issue, with client key markers left in
+    async fn fetch_issue_comments(&self, repo: &Repo, issue_number: u64) -> Result<Vec<GitHubComment>> {
+        let mut comments = Vec::new();
+        for page in 1.. {
+            let url = format!(
+                "https://api.github.com/repos/{}/{}/issues/{}/comments?per_page={}&page={}",
+                repo.owner, repo.name, issue_number, PER_PAGE, page
+            );
+            let response = self
+                .client
+                .get(&url)
+                .header("Authorization", self.auth_header().await?)
+                .header("User-Agent", "isq")
+                .header("Accept", "application/vnd.github+json")
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(api_error(response).await);
+            }
+
+            let page_comments: Vec<GitHubComment> = response.json().await?;
+            let last_page = page_comments.len() < PER_PAGE;
+            comments.extend(page_comments);
+            if last_page {
+                break;
+            }
+        }
+        Ok(comments)
+    }
+
     /// Get total issue count via search API
     async fn get_issue_count(&self, repo: &Repo) -> Result<usize> {
         let url = format!(
@@ -457,7 +1072,7 @@ impl GitHubClient {
         let response = self
             .client
             .get(&url)
-            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Authorization", self.auth_header().await?)
             .header("User-Agent", "isq")
             .header("Accept", "application/vnd.github+json")
             .send()
@@ -466,13 +1081,57 @@ impl GitHubClient {
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await?;
-            anyhow::bail!("GitHub search API error {}: {}", status, body);
+            return Err(error::err(
+                error::status_kind(status.as_u16()),
+                format!("GitHub search API error {}: {}", status, body),
+            ));
         }
 
         let result: SearchResult = response.json().await?;
         Ok(result.total_count)
     }
 
+    /// Every issue matching a search query, with its repo. GitHub stops at
+    /// 1000 results, so a broader query is cut short (with a warning).
+    pub async fn search_issues(&self, query: &str) -> Result<Vec<(String, Issue)>> {
+        let query = format!("is:issue {}", query);
+        let mut hits = Vec::new();
+        let mut total = 0;
+        for page in 1.. {
+            let response = self
+                .client
+                .get("https://api.github.com/search/issues")
+                .query(&[("q", query.as_str()), ("per_page", &PER_PAGE.to_string()), ("page", &page.to_string())])
+                .header("Authorization", self.auth_header().await?)
+                .header("User-Agent", "isq")
+                .header("Accept", "application/vnd.github+json")
+                .send()
+                .await?;
+            if !response.status().is_success() {
+                return Err(api_error(response).await);
+            }
+
+            let result: IssueSearchPage = response.json().await?;
+            total = result.total_count;
+            let last_page = result.items.len() < PER_PAGE;
+            for item in result.items {
+                if let Some(forge_repo) = item.forge_repo() {
+                    hits.push((forge_repo, item.issue.into_issue()));
+                }
+            }
+            if last_page || hits.len() >= total.min(SEARCH_RESULT_LIMIT) {
+                break;
+            }
+        }
+        if total > SEARCH_RESULT_LIMIT {
+            eprintln!(
+                "warning: '{}' matches {} issues; GitHub search returns the first {}",
+                query, total, SEARCH_RESULT_LIMIT
+            );
+        }
+        Ok(hits)
+    }
+
     /// Fetch a single page of issues with retry on rate limit or network errors
     async fn fetch_page_with_retry(&self, repo: &Repo, page: usize) -> Result<Vec<Issue>> {
         let url = format!(
@@ -487,7 +1146,7 @@ impl GitHubClient {
             let response = match self
                 .client
                 .get(&url)
-                .header("Authorization", format!("Bearer {}", self.token))
+                .header("Authorization", self.auth_header().await?)
                 .header("User-Agent", "isq")
                 .header("Accept", "application/vnd.github+json")
                 .send()
@@ -533,11 +1192,12 @@ impl GitHubClient {
                 }
             }
 
-            let status = response.status().as_u16();
+            let status = response.status();
             let delay = get_retry_delay(&response, attempt);
+            let scopes = oauth_scopes(&response);
             let body = response.text().await?;
 
-            if is_rate_limited(status, &body) && attempt < MAX_RETRIES - 1 {
+            if is_rate_limited(status.as_u16(), &body) && attempt < MAX_RETRIES - 1 {
                 eprintln!(
                     "Rate limited on page {}, retrying in {:?} (attempt {}/{})",
                     page,
@@ -549,7 +1209,7 @@ impl GitHubClient {
                 continue;
             }
 
-            anyhow::bail!("GitHub API error {}: {}", status, body);
+            return Err(explain_api_error(status, scopes.as_deref(), &body));
         }
 
         anyhow::bail!(
@@ -559,25 +1219,116 @@ impl GitHubClient {
         )
     }
 
+    /// List an org's non-archived repos, falling back to a user's public repos
+    pub async fn list_owner_repos(&self, owner: &str) -> Result<Vec<GitHubRepo>> {
+        let mut base = format!("https://api.github.com/orgs/{}/repos?type=all", owner);
+        let mut repos: Vec<GitHubRepo> = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let url = format!("{}&per_page={}&page={}", base, PER_PAGE, page);
+            let response = self
+                .client
+                .get(&url)
+                .header("Authorization", self.auth_header().await?)
+                .header("User-Agent", "isq")
+                .header("Accept", "application/vnd.github+json")
+                .send()
+                .await?;
+
+            // Not an org: try it as a user. /users/{owner}/repos only lists
+            // public repos, so our own come from /user/repos instead.
+            if response.status() == reqwest::StatusCode::NOT_FOUND && page == 1 && base.contains("/orgs/") {
+                let me = self.get_user().await.ok();
+                base = if me.is_some_and(|me| me.eq_ignore_ascii_case(owner)) {
+                    "https://api.github.com/user/repos?affiliation=owner".to_string()
+                } else {
+                    format!("https://api.github.com/users/{}/repos?type=all", owner)
+                };
+                continue;
+            }
+
+            if !response.status().is_success() {
+                return Err(api_error(response).await);
+            }
+
+            let batch: Vec<GitHubRepo> = response.json().await?;
+            let done = batch.len() < PER_PAGE;
+            repos.extend(batch);
+            if done {
+                break;
+            }
+            page += 1;
+        }
+
+        repos.retain(|r| !r.archived);
+        Ok(repos)
+    }
+
     /// Get authenticated user's login
     pub async fn get_user(&self) -> Result<String> {
+        Ok(self.token_info().await?.0)
+    }
+
+    /// The authenticated login, the token's OAuth scopes (classic PATs and
+    /// OAuth tokens only) and its expiry (PATs created with one)
+    pub async fn token_info(&self) -> Result<(String, Option<Vec<String>>, Option<String>)> {
+        // GitHub answers /user from an installation token with a bare 403
+        if let Some(app) = &self.app {
+            return Err(error::err(
+                ErrorKind::AuthMissing,
+                format!(
+                    "GitHub App installation {} has no user (needed for @me); use `--as` with a user token for this",
+                    app.installation_id
+                ),
+            ));
+        }
         let response = self
             .client
             .get("https://api.github.com/user")
-            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Authorization", self.auth_header().await?)
             .header("User-Agent", "isq")
             .header("Accept", "application/vnd.github+json")
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            anyhow::bail!("GitHub API error {}: {}", status, body);
+            return Err(api_error(response).await);
         }
 
+        let scopes = oauth_scopes(&response);
+        let expires_at = response
+            .headers()
+            .get("github-authentication-token-expiration")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+
         let user: GitHubUser = response.json().await?;
-        Ok(user.login)
+        Ok((user.login, scopes, expires_at))
+    }
+
+    /// GraphQL node ID of an issue
+    async fn get_issue_node_id(&self, repo: &Repo, number: u64) -> Result<String> {
+        let url = format!("https://api.github.com/repos/{}/{}/issues/{}", repo.owner, repo.name, number);
+
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.auth_header().await?)
+            .header("User-Agent", "isq")
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        let issue: serde_json::Value = response.json().await?;
+        issue["node_id"]
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| anyhow!("GitHub returned issue #{} without a node_id", number))
     }
 
     /// Helper for PATCH requests to update issue state
@@ -592,7 +1343,7 @@ impl GitHubClient {
         let response = self
             .client
             .patch(&url)
-            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Authorization", self.auth_header().await?)
             .header("User-Agent", "isq")
             .header("Accept", "application/vnd.github+json")
             .json(body)
@@ -600,9 +1351,7 @@ impl GitHubClient {
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            anyhow::bail!("GitHub API error {}: {}", status, body);
+            return Err(api_error(response).await);
         }
 
         Ok(())
@@ -645,7 +1394,7 @@ impl GitHubClient {
             let response = match self
                 .client
                 .get(&url)
-                .header("Authorization", format!("Bearer {}", self.token))
+                .header("Authorization", self.auth_header().await?)
                 .header("User-Agent", "isq")
                 .header("Accept", "application/vnd.github+json")
                 .send()
@@ -679,17 +1428,18 @@ impl GitHubClient {
                 }
             }
 
-            let status = response.status().as_u16();
+            let status = response.status();
             let delay = get_retry_delay(&response, attempt);
+            let scopes = oauth_scopes(&response);
             let body = response.text().await?;
 
-            if is_rate_limited(status, &body) && attempt < MAX_RETRIES - 1 {
+            if is_rate_limited(status.as_u16(), &body) && attempt < MAX_RETRIES - 1 {
                 eprintln!("Rate limited on comments page {}, retrying in {:?}", page, delay);
                 tokio::time::sleep(delay).await;
                 continue;
             }
 
-            anyhow::bail!("GitHub API error {}: {}", status, body);
+            return Err(explain_api_error(status, scopes.as_deref(), &body));
         }
 
         anyhow::bail!(
@@ -711,16 +1461,14 @@ impl GitHubClient {
         let response = self
             .client
             .get(&url)
-            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Authorization", self.auth_header().await?)
             .header("User-Agent", "isq")
             .header("Accept", "application/vnd.github+json")
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            anyhow::bail!("GitHub API error {}: {}", status, body);
+            return Err(api_error(response).await);
         }
 
         let milestones: Vec<GitHubMilestone> = response.json().await?;
@@ -752,7 +1500,7 @@ impl GitHubClient {
         let response = self
             .client
             .post(&url)
-            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Authorization", self.auth_header().await?)
             .header("User-Agent", "isq")
             .header("Accept", "application/vnd.github+json")
             .json(&body)
@@ -760,17 +1508,15 @@ impl GitHubClient {
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            anyhow::bail!("GitHub API error {}: {}", status, body);
+            return Err(api_error(response).await);
         }
 
         let milestone: GitHubMilestone = response.json().await?;
         Ok(milestone)
     }
 
-    /// Close a milestone
-    pub async fn close_milestone(&self, repo: &Repo, number: u64) -> Result<()> {
+    /// Open or close a milestone
+    pub async fn set_milestone_state(&self, repo: &Repo, number: u64, state: GoalState) -> Result<()> {
         throttle_write().await;
 
         let url = format!(
@@ -778,12 +1524,12 @@ impl GitHubClient {
             repo.owner, repo.name, number
         );
 
-        let body = serde_json::json!({ "state": "closed" });
+        let body = serde_json::json!({ "state": state.as_str() });
 
         let response = self
             .client
             .patch(&url)
-            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Authorization", self.auth_header().await?)
             .header("User-Agent", "isq")
             .header("Accept", "application/vnd.github+json")
             .json(&body)
@@ -791,9 +1537,92 @@ impl GitHubClient {
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            anyhow::bail!("GitHub API error {}: {}", status, body);
+            return Err(api_error(response).await);
+        }
+
+        Ok(())
+    }
+
+    /// List a repo's labels (all pages)
+    pub async fn list_repo_labels(&self, repo: &Repo) -> Result<Vec<LabelDef>> {
+        let mut labels = Vec::new();
+        for page in 1.. {
+            let url = format!(
+                "https://api.github.com/repos/{}/{}/labels?per_page=100&page={}",
+                repo.owner, repo.name, page
+            );
+
+            let _permit = REQUEST_SEMAPHORE.acquire().await.unwrap();
+
+            let response = self
+                .client
+                .get(&url)
+                .header("Authorization", self.auth_header().await?)
+                .header("User-Agent", "isq")
+                .header("Accept", "application/vnd.github+json")
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(api_error(response).await);
+            }
+
+            let batch: Vec<GitHubRepoLabel> = response.json().await?;
+            let done = batch.len() < 100;
+            labels.extend(batch.into_iter().map(|l| LabelDef {
+                name: l.name,
+                color: Some(l.color),
+                description: l.description.filter(|d| !d.is_empty()),
+            }));
+            if done {
+                break;
+            }
+        }
+        Ok(labels)
+    }
+
+    /// Create a label, or (with `current`) update the label of that name
+    async fn write_label(&self, repo: &Repo, current: Option<&str>, label: &LabelDef) -> Result<()> {
+        throttle_write().await;
+
+        let mut body = serde_json::json!({});
+        if let Some(color) = &label.color {
+            body["color"] = serde_json::json!(color);
+        }
+        if let Some(desc) = &label.description {
+            body["description"] = serde_json::json!(desc);
+        }
+
+        let request = match current {
+            Some(name) => {
+                body["new_name"] = serde_json::json!(label.name);
+                // Label names can contain spaces and slashes
+                let mut url = reqwest::Url::parse(&format!(
+                    "https://api.github.com/repos/{}/{}/labels",
+                    repo.owner, repo.name
+                ))?;
+                url.path_segments_mut()
+                    .map_err(|_| anyhow!("Invalid labels URL"))?
+                    .push(name);
+                self.client.patch(url)
+            }
+            None => {
+                body["name"] = serde_json::json!(label.name);
+                let url = format!("https://api.github.com/repos/{}/{}/labels", repo.owner, repo.name);
+                self.client.post(url)
+            }
+        };
+
+        let response = request
+            .header("Authorization", self.auth_header().await?)
+            .header("User-Agent", "isq")
+            .header("Accept", "application/vnd.github+json")
+            .json(&body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
         }
 
         Ok(())
@@ -824,7 +1653,9 @@ impl Forge for GitHubClient {
             "title": req.title,
         });
 
-        if let Some(b) = &req.body {
+        if let Some(key) = &req.client_key {
+            body["body"] = serde_json::json!(body_with_client_key(req.body.as_deref(), key));
+        } else if let Some(b) = &req.body {
             body["body"] = serde_json::json!(b);
         }
 
@@ -832,16 +1663,24 @@ impl Forge for GitHubClient {
             body["labels"] = serde_json::json!(req.labels);
         }
 
-        if let Some(goal_id) = &req.goal_id {
-            if let Ok(milestone_num) = goal_id.parse::<u64>() {
-                body["milestone"] = serde_json::json!(milestone_num);
-            }
+        if let Some(issue_type) = &req.issue_type {
+            body["type"] = serde_json::json!(issue_type);
+        }
+
+        if let Some(assignee) = &req.assignee {
+            body["assignees"] = serde_json::json!([assignee]);
+        }
+
+        if let Some(goal_id) = &req.goal_id
+            && let Ok(milestone_num) = goal_id.parse::<u64>()
+        {
+            body["milestone"] = serde_json::json!(milestone_num);
         }
 
         let response = self
             .client
             .post(&url)
-            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Authorization", self.auth_header().await?)
             .header("User-Agent", "isq")
             .header("Accept", "application/vnd.github+json")
             .json(&body)
@@ -849,15 +1688,39 @@ impl Forge for GitHubClient {
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            anyhow::bail!("GitHub API error {}: {}", status, body);
+            return Err(api_error(response).await);
         }
 
         let issue: GitHubIssue = response.json().await?;
         Ok(issue.into_issue())
     }
 
+    async fn find_created_issue(&self, repo: &Repo, client_key: &str) -> Result<Option<Issue>> {
+        // A lost create is recent, so the newest issues are enough
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/issues?state=all&sort=created&direction=desc&per_page={}",
+            repo.owner, repo.name, PER_PAGE
+        );
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.auth_header().await?)
+            .header("User-Agent", "isq")
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        let issues: Vec<GitHubIssue> = response.json().await?;
+        Ok(issues
+            .into_iter()
+            .find(|i| i.body.as_deref().is_some_and(|b| has_client_key(b, client_key)))
+            .map(GitHubIssue::into_issue))
+    }
+
     async fn create_comment(&self, repo: &Repo, issue_number: u64, body: &str) -> Result<()> {
         throttle_write().await;
 
@@ -871,7 +1734,7 @@ impl Forge for GitHubClient {
         let response = self
             .client
             .post(&url)
-            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Authorization", self.auth_header().await?)
             .header("User-Agent", "isq")
             .header("Accept", "application/vnd.github+json")
             .json(&payload)
@@ -879,14 +1742,21 @@ impl Forge for GitHubClient {
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            anyhow::bail!("GitHub API error {}: {}", status, body);
+            return Err(api_error(response).await);
         }
 
         Ok(())
     }
 
+    async fn create_keyed_comment(&self, repo: &Repo, issue_number: u64, body: &str, client_key: &str) -> Result<()> {
+        self.create_comment(repo, issue_number, &body_with_client_key(Some(body), client_key)).await
+    }
+
+    async fn find_posted_comment(&self, repo: &Repo, issue_number: u64, client_key: &str) -> Result<bool> {
+        let comments = self.fetch_issue_comments(repo, issue_number).await?;
+        Ok(comments.iter().any(|c| has_client_key(&c.body, client_key)))
+    }
+
     async fn close_issue(&self, repo: &Repo, issue_number: u64) -> Result<()> {
         self.patch_issue(repo, issue_number, &serde_json::json!({ "state": "closed" }))
             .await
@@ -897,6 +1767,11 @@ impl Forge for GitHubClient {
             .await
     }
 
+    async fn update_issue_body(&self, repo: &Repo, issue_number: u64, body: &str) -> Result<()> {
+        self.patch_issue(repo, issue_number, &serde_json::json!({ "body": body }))
+            .await
+    }
+
     async fn add_label(&self, repo: &Repo, issue_number: u64, label: &str) -> Result<()> {
         throttle_write().await;
 
@@ -910,7 +1785,7 @@ impl Forge for GitHubClient {
         let response = self
             .client
             .post(&url)
-            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Authorization", self.auth_header().await?)
             .header("User-Agent", "isq")
             .header("Accept", "application/vnd.github+json")
             .json(&payload)
@@ -918,9 +1793,7 @@ impl Forge for GitHubClient {
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            anyhow::bail!("GitHub API error {}: {}", status, body);
+            return Err(api_error(response).await);
         }
 
         Ok(())
@@ -937,7 +1810,7 @@ impl Forge for GitHubClient {
         let response = self
             .client
             .delete(&url)
-            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Authorization", self.auth_header().await?)
             .header("User-Agent", "isq")
             .header("Accept", "application/vnd.github+json")
             .send()
@@ -945,9 +1818,7 @@ impl Forge for GitHubClient {
 
         // 404 is ok - label might not exist
         if !response.status().is_success() && response.status().as_u16() != 404 {
-            let status = response.status();
-            let body = response.text().await?;
-            anyhow::bail!("GitHub API error {}: {}", status, body);
+            return Err(api_error(response).await);
         }
 
         Ok(())
@@ -966,7 +1837,7 @@ impl Forge for GitHubClient {
         let response = self
             .client
             .post(&url)
-            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Authorization", self.auth_header().await?)
             .header("User-Agent", "isq")
             .header("Accept", "application/vnd.github+json")
             .json(&payload)
@@ -974,14 +1845,62 @@ impl Forge for GitHubClient {
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            anyhow::bail!("GitHub API error {}: {}", status, body);
+            return Err(api_error(response).await);
         }
 
         Ok(())
     }
 
+    async fn set_subscribed(&self, repo: &Repo, issue_number: u64, subscribed: bool) -> Result<()> {
+        // REST only has thread subscriptions (which need a notification to exist),
+        // so go through GraphQL with the issue's node ID
+        let node_id = self.get_issue_node_id(repo, issue_number).await?;
+        throttle_write().await;
+
+        let query = r#"
+            mutation($id: ID!, $state: SubscriptionState!) {
+                updateSubscription(input: { subscribableId: $id, state: $state }) {
+                    subscribable { viewerSubscription }
+                }
+            }
+        "#;
+        let variables = serde_json::json!({
+            "id": node_id,
+            "state": if subscribed { "SUBSCRIBED" } else { "UNSUBSCRIBED" },
+        });
+
+        let response = self
+            .client
+            .post("https://api.github.com/graphql")
+            .header("Authorization", self.auth_header().await?)
+            .header("User-Agent", "isq")
+            .json(&serde_json::json!({ "query": query, "variables": variables }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        if let Some(message) = body["errors"][0]["message"].as_str() {
+            anyhow::bail!("GitHub API error: {}", message);
+        }
+        Ok(())
+    }
+
+    async fn list_labels(&self, repo: &Repo) -> Result<Vec<LabelDef>> {
+        self.list_repo_labels(repo).await
+    }
+
+    async fn create_label(&self, repo: &Repo, label: &LabelDef) -> Result<()> {
+        self.write_label(repo, None, label).await
+    }
+
+    async fn update_label(&self, repo: &Repo, name: &str, label: &LabelDef) -> Result<()> {
+        self.write_label(repo, Some(name), label).await
+    }
+
     async fn list_all_comments(&self, repo: &Repo) -> Result<Vec<crate::db::Comment>> {
         let github_comments = GitHubClient::list_all_comments(self, repo).await?;
 
@@ -992,7 +1911,7 @@ impl Forge for GitHubClient {
                 Some(crate::db::Comment {
                     comment_id: c.id.to_string(),
                     issue_number: c.issue_number()?,
-                    body: c.body,
+                    body: strip_client_key(&c.body),
                     author: c.user.login,
                     created_at: c.created_at,
                 })
@@ -1002,6 +1921,39 @@ impl Forge for GitHubClient {
         Ok(comments)
     }
 
+    async fn get_issue(&self, repo: &Repo, issue_number: u64) -> Result<Issue> {
+        let url = format!("https://api.github.com/repos/{}/{}/issues/{}", repo.owner, repo.name, issue_number);
+        let response = self
+            .client
+            .get(&url)
+            .header("Authorization", self.auth_header().await?)
+            .header("User-Agent", "isq")
+            .header("Accept", "application/vnd.github+json")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(api_error(response).await);
+        }
+
+        let issue: GitHubIssue = response.json().await?;
+        Ok(issue.into_issue())
+    }
+
+    async fn list_issue_comments(&self, repo: &Repo, issue_number: u64) -> Result<Vec<crate::db::Comment>> {
+        let comments = self.fetch_issue_comments(repo, issue_number).await?;
+        Ok(comments
+            .into_iter()
+            .map(|c| crate::db::Comment {
+                comment_id: c.id.to_string(),
+                issue_number,
+                body: strip_client_key(&c.body),
+                author: c.user.login,
+                created_at: c.created_at,
+            })
+            .collect())
+    }
+
     async fn list_goals(&self, repo: &Repo) -> Result<Vec<Goal>> {
         let milestones = self.list_milestones(repo).await?;
         Ok(milestones.into_iter().map(Goal::from).collect())
@@ -1016,7 +1968,14 @@ impl Forge for GitHubClient {
         let number: u64 = goal_id
             .parse()
             .map_err(|_| anyhow::anyhow!("Invalid milestone number: {}", goal_id))?;
-        self.close_milestone(repo, number).await
+        self.set_milestone_state(repo, number, GoalState::Closed).await
+    }
+
+    async fn reopen_goal(&self, repo: &Repo, goal_id: &str) -> Result<()> {
+        let number: u64 = goal_id
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Invalid milestone number: {}", goal_id))?;
+        self.set_milestone_state(repo, number, GoalState::Open).await
     }
 
     async fn assign_to_goal(&self, repo: &Repo, issue_number: u64, goal_id: &str) -> Result<()> {
@@ -1026,20 +1985,26 @@ impl Forge for GitHubClient {
         self.set_issue_milestone(repo, issue_number, milestone_number).await
     }
 
+    async fn get_user(&self) -> Result<String> {
+        GitHubClient::get_user(self).await
+    }
+
+    async fn search_issues(&self, query: &str) -> Result<Vec<(String, Issue)>> {
+        self.search_issues(query).await
+    }
+
     async fn get_rate_limit(&self) -> Result<Option<RateLimitInfo>> {
         let response = self
             .client
             .get("https://api.github.com/rate_limit")
-            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Authorization", self.auth_header().await?)
             .header("User-Agent", "isq")
             .header("Accept", "application/vnd.github+json")
             .send()
             .await?;
 
         if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await?;
-            anyhow::bail!("GitHub API error {}: {}", status, body);
+            return Err(api_error(response).await);
         }
 
         #[derive(Deserialize)]
@@ -1,6 +1,7 @@
 use std::io::{BufRead, BufReader, Write};
 use std::net::TcpListener;
-use std::sync::RwLock;
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
 
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
@@ -9,9 +10,11 @@ use rand::Rng;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
-use super::{AuthConfig, CreateGoalRequest, CreateIssueRequest, Forge, ForgeType, Goal, GoalState, Issue, Label, LinkArgs, LinkResult, RateLimitInfo};
+use super::{AuthConfig, ComplexityBudget, CreateGoalRequest, CreateIssueRequest, Cycle, Forge, ForgeType, Goal, GoalState, Issue, Label, LabelDef, LinkArgs, LinkResult, Objective, RateLimitInfo, WhoAmI};
+use crate::error::{self, ErrorKind};
 use crate::repo::Repo;
-use crate::{db, repo};
+use crate::team_config::StatesConfig;
+use crate::{db, network, progress};
 
 // ============================================================================
 // Auth Configuration
@@ -24,6 +27,7 @@ pub const AUTH: AuthConfig = AuthConfig {
     cli_command: None, // Linear has no CLI
     display_name: "Linear",
     link_command: "isq link linear",
+    login_command: "isq auth login linear --with-token",
 };
 
 // ============================================================================
@@ -32,6 +36,10 @@ pub const AUTH: AuthConfig = AuthConfig {
 
 const GRAPHQL_URL: &str = "https://api.linear.app/graphql";
 
+/// Largest page sizes we request; shrunk when the complexity budget runs low
+const ISSUES_PAGE_SIZE: u32 = 250;
+const COMMENTS_PAGE_SIZE: u32 = 100;
+
 // OAuth configuration
 const LINEAR_CLIENT_ID: &str = "a6c010f01947bd3b847cb3c1707366e5";
 const LINEAR_AUTH_URL: &str = "https://linear.app/oauth/authorize";
@@ -63,7 +71,7 @@ fn generate_code_challenge(verifier: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(verifier.as_bytes());
     let hash = hasher.finalize();
-    URL_SAFE_NO_PAD.encode(&hash)
+    URL_SAFE_NO_PAD.encode(hash)
 }
 
 /// Build the authorization URL with PKCE
@@ -94,33 +102,33 @@ fn wait_for_callback(expected_state: &str) -> Result<String> {
         let mut request_line = String::new();
         reader.read_line(&mut request_line)?;
 
-        if let Some(path) = request_line.split_whitespace().nth(1) {
-            if path.starts_with("/callback") {
-                let query = path.strip_prefix("/callback?").unwrap_or("");
-                let params: std::collections::HashMap<_, _> = query
-                    .split('&')
-                    .filter_map(|p| {
-                        let mut parts = p.splitn(2, '=');
-                        Some((parts.next()?, parts.next()?))
-                    })
-                    .collect();
-
-                if let Some(error) = params.get("error") {
-                    let description = params.get("error_description").unwrap_or(&"Unknown error");
-                    send_response(&mut stream, false, &format!("Authorization failed: {}", description))?;
-                    return Err(anyhow!("OAuth error: {} - {}", error, description));
-                }
-
-                let state = params.get("state").ok_or_else(|| anyhow!("Missing state parameter"))?;
-                if *state != expected_state {
-                    send_response(&mut stream, false, "State mismatch - possible CSRF attack")?;
-                    return Err(anyhow!("State mismatch"));
-                }
+        if let Some(path) = request_line.split_whitespace().nth(1)
+            && path.starts_with("/callback")
+        {
+            let query = path.strip_prefix("/callback?").unwrap_or("");
+            let params: std::collections::HashMap<_, _> = query
+                .split('&')
+                .filter_map(|p| {
+                    let mut parts = p.splitn(2, '=');
+                    Some((parts.next()?, parts.next()?))
+                })
+                .collect();
+
+            if let Some(error) = params.get("error") {
+                let description = params.get("error_description").unwrap_or(&"Unknown error");
+                send_response(&mut stream, false, &format!("Authorization failed: {}", description))?;
+                return Err(anyhow!("OAuth error: {} - {}", error, description));
+            }
 
-                let code = params.get("code").ok_or_else(|| anyhow!("Missing code parameter"))?;
-                send_response(&mut stream, true, "Authorization successful! You can close this tab.")?;
-                return Ok(code.to_string());
+            let state = params.get("state").ok_or_else(|| anyhow!("Missing state parameter"))?;
+            if *state != expected_state {
+                send_response(&mut stream, false, "State mismatch - possible CSRF attack")?;
+                return Err(anyhow!("State mismatch"));
             }
+
+            let code = params.get("code").ok_or_else(|| anyhow!("Missing code parameter"))?;
+            send_response(&mut stream, true, "Authorization successful! You can close this tab.")?;
+            return Ok(code.to_string());
         }
     }
 
@@ -164,7 +172,7 @@ fn send_response(stream: &mut std::net::TcpStream, success: bool, message: &str)
 
 /// Exchange authorization code for access token
 async fn exchange_code(code: &str, code_verifier: &str) -> Result<TokenResponse> {
-    let client = reqwest::Client::new();
+    let client = network::client();
 
     let params = [
         ("grant_type", "authorization_code"),
@@ -202,7 +210,10 @@ pub async fn oauth_flow() -> Result<TokenResponse> {
     println!("Opening browser to authorize...");
     open::that(&auth_url).map_err(|e| anyhow!("Failed to open browser: {}", e))?;
 
-    let code = wait_for_callback(&state)?;
+    let code = {
+        let _waiting = network::interactive();
+        wait_for_callback(&state)?
+    };
 
     println!("Exchanging authorization code...");
     let token = exchange_code(&code, &code_verifier).await?;
@@ -212,7 +223,7 @@ pub async fn oauth_flow() -> Result<TokenResponse> {
 
 /// Refresh a Linear access token using a refresh token
 pub async fn refresh_token(refresh_token: &str) -> Result<TokenResponse> {
-    let client = reqwest::Client::new();
+    let client = network::client();
 
     let params = [
         ("grant_type", "refresh_token"),
@@ -236,6 +247,106 @@ pub async fn refresh_token(refresh_token: &str) -> Result<TokenResponse> {
     Ok(token)
 }
 
+/// Run OAuth and store the resulting credentials (under `account`, if named).
+/// Refuses on headless machines, where the browser redirect can't complete.
+async fn oauth_login(account: Option<&str>) -> Result<String> {
+    if super::is_headless() {
+        return Err(error::err(
+            ErrorKind::AuthMissing,
+            "No valid Linear credentials found, and no browser is available for OAuth.\n\n\
+             Create a personal API key at https://linear.app/settings/api, then either:\n  \
+             isq auth login linear --with-token < key.txt\n  \
+             export LINEAR_API_KEY=<key>",
+        ));
+    }
+
+    let oauth_token = oauth_flow().await?;
+    let expires_at = oauth_token.expires_in.map(|secs| {
+        (chrono::Utc::now() + chrono::Duration::seconds(secs as i64)).to_rfc3339()
+    });
+    AUTH.store_credential(
+        account,
+        &oauth_token.access_token,
+        oauth_token.refresh_token.as_deref(),
+        expires_at.as_deref(),
+    )?;
+    Ok(oauth_token.access_token)
+}
+
+/// Find the first existing token (keyring, then LINEAR_API_KEY) that Linear
+/// accepts. A named account only has its own.
+async fn existing_valid_token(account: Option<&str>) -> Option<(String, String)> {
+    let tokens = match account {
+        Some(_) => AUTH.get_account_token(account).into_iter().collect(),
+        None => AUTH.candidate_tokens().into_iter().map(|(token, _source)| token).collect::<Vec<_>>(),
+    };
+    // verify_token, not get_viewer: a rejected token must not be "fixed" by
+    // refreshing into the stored credential
+    first_accepted(tokens, |token| async move { LinearClient::new(token).verify_token().await }).await
+}
+
+/// The first token `verify` accepts, with the username it returned
+async fn first_accepted<F, Fut>(tokens: Vec<String>, verify: F) -> Option<(String, String)>
+where
+    F: Fn(String) -> Fut,
+    Fut: std::future::Future<Output = Result<String>>,
+{
+    for token in tokens {
+        if let Ok(username) = verify(token.clone()).await {
+            return Some((token, username));
+        }
+    }
+    None
+}
+
+/// Store credentials for `isq auth login linear`.
+/// A provided token (personal API key) is verified before it's saved.
+pub async fn login(token: Option<String>, account: Option<&str>) -> Result<String> {
+    let username = match token {
+        Some(token) => {
+            let username = LinearClient::new(token.clone())
+                .verify_token()
+                .await
+                .map_err(|e| error::err(ErrorKind::AuthMissing, format!("Linear rejected the token: {}", e)))?;
+            AUTH.store_credential(account, &token, None, None)?;
+            username
+        }
+        None => {
+            let token = oauth_login(account).await?;
+            LinearClient::new(token).get_viewer().await?
+        }
+    };
+
+    db::set_identity(&db::open()?, &super::account_key(ForgeType::Linear.as_str(), account), &username)?;
+    Ok(username)
+}
+
+/// Verify the Linear credential for `isq whoami`. Linear doesn't expose a
+/// token's scopes; OAuth tokens' expiry comes from the keyring.
+pub async fn whoami(account: Option<&str>) -> Result<WhoAmI> {
+    let (token, source) = AUTH.account_token_source(account)?;
+    let login = LinearClient::new(token.clone())
+        .verify_token()
+        .await
+        .map_err(|e| error::err(ErrorKind::AuthMissing, format!("Linear rejected the token from {}: {}", source, e)))?;
+    db::set_identity(&db::open()?, &super::account_key(ForgeType::Linear.as_str(), account), &login)?;
+
+    let expires_at = match AUTH.get_credential(account) {
+        Ok(Some(cred)) if cred.access_token == token => cred.expires_at,
+        _ => None,
+    };
+    Ok(WhoAmI {
+        forge: ForgeType::Linear.as_str().to_string(),
+        account: account.map(String::from),
+        login,
+        source,
+        token_kind: None,
+        scopes: None,
+        expires_at,
+        verified_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
 // ============================================================================
 // Link Flow
 // ============================================================================
@@ -244,32 +355,23 @@ pub async fn refresh_token(refresh_token: &str) -> Result<TokenResponse> {
 /// Handles auth, team selection, syncs issues, and returns the result.
 pub async fn link(repo_path: &str, args: &LinkArgs) -> Result<LinkResult> {
     let forge_type = ForgeType::Linear;
+    if args.org.is_some() {
+        return Err(error::err(ErrorKind::Usage, "--org is only supported for GitHub. Use -o team=KEY for Linear."));
+    }
     let conn = db::open()?;
 
-    // Try existing auth first, fall back to OAuth
-    let (token, is_new_auth) = match AUTH.get_token() {
-        Ok(t) => (t, false),
-        Err(_) => {
-            let oauth_token = oauth_flow().await?;
-            let expires_at = oauth_token.expires_in.map(|secs| {
-                (chrono::Utc::now() + chrono::Duration::seconds(secs as i64)).to_rfc3339()
-            });
-            AUTH.store_credential(
-                &oauth_token.access_token,
-                oauth_token.refresh_token.as_deref(),
-                expires_at.as_deref(),
-            )?;
-            (oauth_token.access_token, true)
+    // Use any existing token that works (keyring or LINEAR_API_KEY), fall back to OAuth
+    let account = args.account.as_deref();
+    let (client, username) = match existing_valid_token(account).await {
+        Some((token, username)) => (LinearClient::new(token).for_account(account), username),
+        None => {
+            let client = LinearClient::new(oauth_login(account).await?).for_account(account);
+            let username = client.get_viewer().await?;
+            println!("✓ Authenticated as {}", username);
+            (client, username)
         }
     };
-
-    let client = LinearClient::new(token);
-
-    // Verify authentication
-    let username = client.get_viewer().await?;
-    if is_new_auth {
-        println!("✓ Authenticated as {}", username);
-    }
+    db::set_identity(&conn, &super::account_key(forge_type.as_str(), account), &username)?;
 
     // List teams
     let teams = client.list_teams().await?;
@@ -289,17 +391,7 @@ pub async fn link(repo_path: &str, args: &LinkArgs) -> Result<LinkResult> {
 
     // Resolve team from --team argument or auto-select if only one
     let team = if let Some(ref team_query) = args.team {
-        let query_lower = team_query.to_lowercase();
-        teams.iter().find(|t| {
-            t.name.to_lowercase() == query_lower || t.key.to_lowercase() == query_lower
-        }).ok_or_else(|| {
-            let available: Vec<_> = teams.iter().map(|t| format!("{} ({})", t.name, t.key)).collect();
-            anyhow!(
-                "Team '{}' not found.\n\nAvailable teams:\n  {}",
-                team_query,
-                available.join("\n  ")
-            )
-        })?
+        find_team(&teams, team_query)?
     } else if teams.len() == 1 {
         println!("Using team: {} ({})", teams[0].name, teams[0].key);
         &teams[0]
@@ -315,30 +407,39 @@ pub async fn link(repo_path: &str, args: &LinkArgs) -> Result<LinkResult> {
     // Get organization info for display name
     let org = client.get_organization().await?;
     let display_name = format!("{}/{}", org.url_key, team.key);
-    let forge_repo = format!("{}/{}", team.key, team.id);
-
-    // Create pseudo-repo for syncing
-    let pseudo_repo = repo::Repo {
-        owner: team.key.clone(),
-        name: team.id.clone(),
-    };
+    let forge_repo = team.forge_repo();
 
     // Sync issues
     println!("Syncing {}...", team.name);
-    let issues = client.list_issues(&pseudo_repo).await?;
+    let cached = client.sync_team_issues_resumable(&conn, &team.id, &forge_repo).await?;
 
     // Save to database
     db::set_repo_link(&conn, repo_path, forge_type.as_str(), &forge_repo, Some(&display_name))?;
-    db::save_issues(&conn, &forge_repo, &issues)?;
+    db::set_repo_account(&conn, repo_path, account)?;
     db::add_watched_repo(&conn, repo_path)?;
 
-    println!("✓ Cached {} issues", issues.len());
+    println!("✓ Cached {} issues", cached);
 
     Ok(LinkResult {
         display_name: team.name.clone(),
     })
 }
 
+/// Find a team by key or name (case-insensitive)
+pub fn find_team<'a>(teams: &'a [LinearTeam], query: &str) -> Result<&'a LinearTeam> {
+    let query_lower = query.to_lowercase();
+    teams
+        .iter()
+        .find(|t| t.name.to_lowercase() == query_lower || t.key.to_lowercase() == query_lower)
+        .ok_or_else(|| {
+            let available: Vec<_> = teams.iter().map(|t| format!("{} ({})", t.name, t.key)).collect();
+            error::err(
+                ErrorKind::NotFound,
+                format!("Team '{}' not found.\n\nAvailable teams:\n  {}", query, available.join("\n  ")),
+            )
+        })
+}
+
 mod urlencoding {
     pub fn encode(s: &str) -> String {
         let mut result = String::new();
@@ -364,6 +465,14 @@ mod urlencoding {
 pub struct LinearClient {
     client: reqwest::Client,
     token: RwLock<String>,
+    /// Complexity budget from the most recent response
+    budget: Mutex<Option<ComplexityBudget>>,
+    /// Issues resolved by (team ID, number) during this process
+    resolved: Mutex<HashMap<(String, u64), LinearIssueWithDetails>>,
+    /// Named account whose stored credential refreshes the token (None: default)
+    account: Option<String>,
+    /// Workflow states close and reopen move to, from the repo's team config
+    states: StatesConfig,
 }
 
 // GraphQL response types
@@ -394,13 +503,20 @@ struct TeamConnection {
     nodes: Vec<LinearTeam>,
 }
 
-#[derive(Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct LinearTeam {
     pub id: String,
     pub name: String,
     pub key: String,
 }
 
+impl LinearTeam {
+    /// The `forge_repo` a link to this team uses ("KEY/team-id")
+    pub fn forge_repo(&self) -> String {
+        format!("{}/{}", self.key, self.id)
+    }
+}
+
 #[derive(Deserialize)]
 struct OrganizationResponse {
     organization: LinearOrganization,
@@ -422,6 +538,13 @@ struct IssuesResponse {
     issues: IssueConnection,
 }
 
+/// One issue plus the org URL key its link needs
+#[derive(Deserialize)]
+struct SingleIssueResponse {
+    organization: LinearOrganization,
+    issues: IssueConnection,
+}
+
 #[derive(Deserialize)]
 struct IssueConnection {
     nodes: Vec<LinearIssue>,
@@ -437,6 +560,12 @@ struct PageInfo {
     end_cursor: Option<String>,
 }
 
+/// Minimal cycle info embedded in issue responses
+#[derive(Deserialize)]
+struct LinearCycleRef {
+    number: u32,
+}
+
 /// Minimal project info embedded in issue responses
 #[derive(Deserialize)]
 struct LinearProjectRef {
@@ -453,12 +582,52 @@ struct LinearIssue {
     creator: Option<LinearCreator>,
     labels: LabelConnection,
     project: Option<LinearProjectRef>,
+    assignee: Option<LinearCreator>,
+    /// 0 = no priority, 1 = urgent .. 4 = low (a Float in Linear's schema)
+    #[serde(default)]
+    priority: f64,
+    #[serde(rename = "slaBreachesAt", default)]
+    sla_breaches_at: Option<String>,
+    #[serde(default)]
+    estimate: Option<f64>,
+    #[serde(default)]
+    cycle: Option<LinearCycleRef>,
     #[serde(rename = "createdAt")]
     created_at: String,
     #[serde(rename = "updatedAt")]
     updated_at: String,
 }
 
+impl LinearIssue {
+    fn into_issue(self, url_key: &str) -> Issue {
+        let url = format!("https://linear.app/{}/issue/{}", url_key, self.identifier);
+        Issue {
+            number: self.number,
+            title: self.title,
+            body: self.description,
+            state: if self.state.state_type == "completed" || self.state.state_type == "canceled" {
+                "closed".to_string()
+            } else {
+                "open".to_string()
+            },
+            author: self.creator.map(|c| c.name).unwrap_or_else(|| "unknown".to_string()),
+            labels: self.labels.nodes.into_iter().map(|l| Label::new(l.name, Some(l.color))).collect(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            url: Some(url),
+            milestone: self.project.map(|p| p.name),
+            assignees: self.assignee.map(|a| a.name).into_iter().collect(),
+            priority: (self.priority >= 1.0).then_some(self.priority as u8),
+            identifier: Some(self.identifier),
+            sla_breaches_at: self.sla_breaches_at,
+            triage: self.state.state_type == "triage",
+            issue_type: None,
+            estimate: self.estimate,
+            cycle: self.cycle.map(|c| c.number),
+        }
+    }
+}
+
 #[derive(Deserialize)]
 struct LinearState {
     #[serde(rename = "type")]
@@ -505,6 +674,41 @@ struct CreatedIssue {
     identifier: String,
     number: u64,
     title: String,
+    #[serde(default)]
+    url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CreatedIssueListResponse {
+    issues: CreatedIssueNodes,
+}
+
+#[derive(Deserialize)]
+struct CreatedIssueNodes {
+    nodes: Vec<CreatedIssue>,
+}
+
+#[derive(Deserialize)]
+struct PostedCommentListResponse {
+    comments: PostedCommentNodes,
+}
+
+#[derive(Deserialize)]
+struct PostedCommentNodes {
+    nodes: Vec<serde_json::Value>,
+}
+
+/// A client key as the UUID Linear expects for a new issue's or comment's id: the key's
+/// 32 hex digits with the version and variant bits of a v4 UUID
+fn client_key_uuid(key: &str) -> Option<String> {
+    if key.len() != 32 || !key.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let mut hex = key.to_ascii_lowercase().into_bytes();
+    hex[12] = b'4';
+    hex[16] = b"89ab"[(hex[16] as char).to_digit(16)? as usize % 4];
+    let hex = String::from_utf8(hex).ok()?;
+    Some(format!("{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..]))
 }
 
 #[derive(Deserialize)]
@@ -575,18 +779,24 @@ struct IssueConnectionWithDetails {
     nodes: Vec<LinearIssueWithDetails>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone)]
 struct LinearIssueWithDetails {
     id: String,
     labels: LabelConnectionWithIds,
+    assignee: Option<LinearAssigneeRef>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone)]
 struct LabelConnectionWithIds {
     nodes: Vec<LinearLabelWithId>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone)]
+struct LinearAssigneeRef {
+    id: String,
+}
+
+#[derive(Deserialize, Clone)]
 struct LinearLabelWithId {
     id: String,
     name: String,
@@ -607,6 +817,7 @@ struct WorkflowStateConnection {
 #[derive(Deserialize)]
 struct WorkflowState {
     id: String,
+    name: String,
     #[serde(rename = "type")]
     state_type: String,
 }
@@ -643,6 +854,42 @@ struct TeamLabelConnection {
     nodes: Vec<LinearLabelWithId>,
 }
 
+/// A team label with everything `isq label sync` compares
+#[derive(Deserialize)]
+struct LinearLabelDetails {
+    id: String,
+    name: String,
+    color: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TeamLabelDetailsResponse {
+    team: TeamWithLabelDetails,
+}
+
+#[derive(Deserialize)]
+struct TeamWithLabelDetails {
+    labels: TeamLabelDetailsConnection,
+}
+
+#[derive(Deserialize)]
+struct TeamLabelDetailsConnection {
+    nodes: Vec<LinearLabelDetails>,
+}
+
+#[derive(Deserialize)]
+struct LabelCreateResponse {
+    #[serde(rename = "issueLabelCreate")]
+    issue_label_create: ProjectUpdatePayload,
+}
+
+#[derive(Deserialize)]
+struct LabelUpdateResponse {
+    #[serde(rename = "issueLabelUpdate")]
+    issue_label_update: ProjectUpdatePayload,
+}
+
 // Project response types
 
 #[derive(Deserialize)]
@@ -715,14 +962,212 @@ struct ProjectUpdatePayload {
     success: bool,
 }
 
+#[derive(Deserialize)]
+struct TeamCyclesResponse {
+    team: TeamCycles,
+}
+
+#[derive(Deserialize)]
+struct TeamCycles {
+    cycles: CycleConnection,
+}
+
+#[derive(Deserialize)]
+struct CycleConnection {
+    nodes: Vec<LinearCycle>,
+}
+
+#[derive(Deserialize)]
+struct LinearCycle {
+    number: u32,
+    name: Option<String>,
+    #[serde(rename = "startsAt")]
+    starts_at: String,
+    #[serde(rename = "endsAt")]
+    ends_at: String,
+}
+
+impl From<LinearCycle> for Cycle {
+    fn from(c: LinearCycle) -> Self {
+        Cycle { number: c.number, name: c.name, starts_at: c.starts_at, ends_at: c.ends_at }
+    }
+}
+
+/// Fields requested for every initiative
+const INITIATIVE_FIELDS: &str = "id name description status targetDate updatedAt url projects { nodes { id } }";
+
+#[derive(Deserialize)]
+struct InitiativesResponse {
+    initiatives: InitiativeConnection,
+}
+
+#[derive(Deserialize)]
+struct InitiativeConnection {
+    nodes: Vec<LinearInitiative>,
+}
+
+#[derive(Deserialize)]
+struct LinearInitiative {
+    id: String,
+    name: String,
+    description: Option<String>,
+    /// Planned, Active or Completed
+    status: String,
+    #[serde(rename = "targetDate")]
+    target_date: Option<String>,
+    #[serde(rename = "updatedAt")]
+    updated_at: String,
+    url: String,
+    projects: ProjectIdConnection,
+}
+
+#[derive(Deserialize)]
+struct ProjectIdConnection {
+    nodes: Vec<ProjectId>,
+}
+
+#[derive(Deserialize)]
+struct ProjectId {
+    id: String,
+}
+
+impl From<LinearInitiative> for Objective {
+    fn from(i: LinearInitiative) -> Self {
+        Objective {
+            id: i.id,
+            name: i.name,
+            description: i.description,
+            target_date: i.target_date,
+            state: GoalState::from_str(&i.status),
+            goal_ids: i.projects.nodes.into_iter().map(|p| p.id).collect(),
+            updated_at: i.updated_at,
+            html_url: Some(i.url),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct InitiativeCreateResponse {
+    #[serde(rename = "initiativeCreate")]
+    initiative_create: InitiativeCreatePayload,
+}
+
+#[derive(Deserialize)]
+struct InitiativeCreatePayload {
+    success: bool,
+    initiative: Option<LinearInitiative>,
+}
+
+#[derive(Deserialize)]
+struct InitiativeToProjectResponse {
+    #[serde(rename = "initiativeToProjectCreate")]
+    initiative_to_project_create: ProjectUpdatePayload,
+}
+
+/// Read Linear's complexity headers into a budget.
+///
+/// Linear reports the window (`X-RateLimit-Complexity-*`, reset in epoch ms)
+/// and the cost of the query itself (`X-Complexity`). When only the cost is
+/// present, it's deducted from the previous budget.
+fn budget_from_headers(
+    headers: &reqwest::header::HeaderMap,
+    previous: Option<ComplexityBudget>,
+    now: i64,
+) -> Option<ComplexityBudget> {
+    let header = |name: &str| -> Option<i64> { headers.get(name)?.to_str().ok()?.trim().parse().ok() };
+
+    let cost = header("x-complexity").map(|c| c.max(0) as u32);
+    let limit = header("x-ratelimit-complexity-limit");
+    let remaining = header("x-ratelimit-complexity-remaining");
+    // Reset is epoch milliseconds; tolerate seconds too
+    let reset_at = header("x-ratelimit-complexity-reset").map(|r| if r > 10_000_000_000 { r / 1000 } else { r });
+
+    match (limit, remaining) {
+        (Some(limit), Some(remaining)) => Some(ComplexityBudget {
+            limit: limit.max(0) as u32,
+            remaining: remaining.max(0) as u32,
+            reset_at: reset_at.or(previous.map(|p| p.reset_at)).unwrap_or(now + 3600),
+            last_cost: cost.unwrap_or(0),
+        }),
+        _ => {
+            let mut budget = previous?;
+            if let Some(cost) = cost {
+                budget.charge(cost, now);
+            }
+            Some(budget)
+        }
+    }
+}
+
+/// The scope a Linear error says the token lacks, e.g. "write" from
+/// "Invalid scope: `write` required"
+pub(super) fn missing_scope(message: &str) -> Option<String> {
+    let lower = message.to_lowercase();
+    let rest = &message[lower.find("scope")? + "scope".len()..];
+    let name = match rest.split('`').nth(1) {
+        Some(quoted) => quoted,
+        None => rest.trim_start_matches([':', ' ']).split_whitespace().next()?,
+    };
+    let name = name.trim_matches(|c: char| !c.is_alphanumeric() && c != ':' && c != '_');
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// What the user should do about a token that lacks `scope`
+pub(super) fn scope_error(scope: &str) -> anyhow::Error {
+    error::err(
+        ErrorKind::AuthMissing,
+        format!(
+            "Your Linear token is missing the `{scope}` scope.\n\
+             Fix: run `isq auth login linear` again (it asks for read and write), or create an API key with \
+             {scope} access at https://linear.app/settings/account/security and run `{}`",
+            AUTH.login_command
+        ),
+    )
+}
+
 impl LinearClient {
     pub fn new(token: String) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: network::client(),
             token: RwLock::new(token),
+            budget: Mutex::new(None),
+            resolved: Mutex::new(HashMap::new()),
+            account: None,
+            states: StatesConfig::default(),
         }
     }
 
+    /// Refresh through a named account's stored credential instead of the default one
+    pub fn for_account(mut self, account: Option<&str>) -> Self {
+        self.account = account.map(String::from);
+        self
+    }
+
+    /// Close and reopen into these workflow states instead of the defaults
+    pub fn with_states(mut self, states: StatesConfig) -> Self {
+        self.states = states;
+        self
+    }
+
+    /// Start from a budget persisted by an earlier run
+    pub fn seed_complexity_budget(&self, budget: ComplexityBudget) {
+        *self.budget.lock().unwrap() = Some(budget);
+    }
+
+    /// Page size for a paginated query given the current budget
+    fn page_size(&self, max: u32) -> u32 {
+        match *self.budget.lock().unwrap() {
+            Some(budget) => budget.page_size(max, chrono::Utc::now().timestamp()),
+            None => max,
+        }
+    }
+
+    /// Update the budget from a response's complexity headers
+    fn record_complexity(&self, headers: &reqwest::header::HeaderMap) {
+        let mut budget = self.budget.lock().unwrap();
+        *budget = budget_from_headers(headers, *budget, chrono::Utc::now().timestamp());
+    }
+
     /// Execute a GraphQL query (internal, no retry)
     async fn query_internal<T: for<'de> Deserialize<'de>>(
         &self,
@@ -744,17 +1189,33 @@ impl LinearClient {
             .send()
             .await?;
 
+        self.record_complexity(response.headers());
+
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await?;
-            anyhow::bail!("Linear API error {} Unauthorized: {}", status.as_u16(), body);
+            if let Some(scope) = missing_scope(&body) {
+                return Err(scope_error(&scope));
+            }
+            return Err(error::err(
+                error::status_kind(status.as_u16()),
+                format!("Linear API error {}: {}", status.as_u16(), body),
+            ));
         }
 
         let result: GraphQLResponse<T> = response.json().await?;
 
         if let Some(errors) = result.errors {
+            if let Some(scope) = errors.iter().find_map(|e| missing_scope(&e.message)) {
+                return Err(scope_error(&scope));
+            }
             let messages: Vec<_> = errors.iter().map(|e| e.message.as_str()).collect();
-            anyhow::bail!("Linear GraphQL errors: {}", messages.join(", "));
+            let kind = if messages.iter().any(|m| m.to_lowercase().contains("rate limit")) {
+                ErrorKind::RateLimited
+            } else {
+                ErrorKind::General
+            };
+            return Err(error::err(kind, format!("Linear GraphQL errors: {}", messages.join(", "))));
         }
 
         result.data.ok_or_else(|| anyhow::anyhow!("No data in response"))
@@ -762,7 +1223,8 @@ impl LinearClient {
 
     /// Refresh the access token using the stored refresh token
     async fn do_refresh_token(&self) -> Result<()> {
-        let cred = AUTH.get_credential()?
+        let account = self.account.as_deref();
+        let cred = AUTH.get_credential(account)?
             .ok_or_else(|| anyhow!("No Linear credentials found"))?;
 
         let stored_refresh_token = cred.refresh_token
@@ -776,6 +1238,7 @@ impl LinearClient {
                 .to_rfc3339()
         });
         AUTH.store_credential(
+            account,
             &new_tokens.access_token,
             new_tokens.refresh_token.as_deref(),
             expires_at.as_deref(),
@@ -796,8 +1259,7 @@ impl LinearClient {
         match self.query_internal(query, variables.clone()).await {
             Ok(result) => Ok(result),
             Err(e) => {
-                let err_str = e.to_string();
-                if err_str.contains("401") || err_str.contains("Unauthorized") {
+                if error::classify(&e) == ErrorKind::AuthMissing {
                     // Try to refresh and retry once
                     self.do_refresh_token().await?;
                     self.query_internal(query, variables).await
@@ -824,6 +1286,23 @@ impl LinearClient {
         Ok(response.viewer.name)
     }
 
+    /// Get the authenticated user without the refresh-on-401 fallback,
+    /// so a bad token isn't masked by previously stored credentials
+    pub async fn verify_token(&self) -> Result<String> {
+        let query = r#"
+            query {
+                viewer {
+                    id
+                    name
+                    email
+                }
+            }
+        "#;
+
+        let response: ViewerResponse = self.query_internal(query, None).await?;
+        Ok(response.viewer.name)
+    }
+
     /// List all teams
     pub async fn list_teams(&self) -> Result<Vec<LinearTeam>> {
         let query = r#"
@@ -857,14 +1336,23 @@ impl LinearClient {
         Ok(response.organization)
     }
 
-    /// Get issue by number within a team (returns id and label IDs for mutations)
+    /// Get issue by number within a team (returns id, label IDs and assignee for mutations).
+    ///
+    /// One round trip per issue per process: results are memoized until a
+    /// mutation through this client invalidates them.
     async fn get_issue_by_number(&self, team_id: &str, number: u64) -> Result<LinearIssueWithDetails> {
+        let key = (team_id.to_string(), number);
+        if let Some(issue) = self.resolved.lock().unwrap().get(&key) {
+            return Ok(issue.clone());
+        }
+
         let query = r#"
             query($teamId: ID!, $number: Float!) {
                 issues(filter: { team: { id: { eq: $teamId } }, number: { eq: $number } }, first: 1) {
                     nodes {
                         id
                         labels { nodes { id name } }
+                        assignee { id }
                     }
                 }
             }
@@ -877,17 +1365,26 @@ impl LinearClient {
 
         let response: SingleIssueListResponse = self.query(query, Some(variables)).await?;
 
-        response.issues.nodes.into_iter().next()
-            .ok_or_else(|| anyhow::anyhow!("Issue #{} not found in team", number))
+        let issue = response.issues.nodes.into_iter().next()
+            .ok_or_else(|| error::err(ErrorKind::NotFound, format!("Issue #{} not found in team", number)))?;
+        self.resolved.lock().unwrap().insert(key, issue.clone());
+        Ok(issue)
+    }
+
+    /// Drop a memoized issue after a mutation changed its labels or assignee
+    fn forget_issue(&self, team_id: &str, number: u64) {
+        self.resolved.lock().unwrap().remove(&(team_id.to_string(), number));
     }
 
-    /// Get workflow state by type (completed, started, backlog, etc.)
-    async fn get_state_by_type(&self, team_id: &str, state_type: &str) -> Result<WorkflowState> {
+    /// The workflow state named `name` if set (from the team config), else the
+    /// team's first state of the first of `types` it has
+    async fn pick_state(&self, team_id: &str, name: Option<&str>, types: &[&str]) -> Result<WorkflowState> {
         let query = r#"
             query($teamId: ID!) {
                 workflowStates(filter: { team: { id: { eq: $teamId } } }) {
                     nodes {
                         id
+                        name
                         type
                     }
                 }
@@ -896,11 +1393,19 @@ impl LinearClient {
 
         let variables = serde_json::json!({ "teamId": team_id });
         let response: WorkflowStatesResponse = self.query(query, Some(variables)).await?;
+        let states = response.workflow_states.nodes;
 
-        response.workflow_states.nodes
+        if let Some(name) = name {
+            return states.into_iter().find(|s| s.name.eq_ignore_ascii_case(name)).ok_or_else(|| {
+                error::err(ErrorKind::NotFound, format!("No workflow state named '{}' (set in [states] of .isq/config.toml)", name))
+            });
+        }
+        let position = |s: &WorkflowState| types.iter().position(|t| *t == s.state_type);
+        states
             .into_iter()
-            .find(|s| s.state_type == state_type)
-            .ok_or_else(|| anyhow::anyhow!("No workflow state of type '{}' found", state_type))
+            .filter(|s| position(s).is_some())
+            .min_by_key(position)
+            .ok_or_else(|| anyhow::anyhow!("No workflow state of type '{}' found", types.join("' or '")))
     }
 
     /// Get user by name or email
@@ -924,7 +1429,7 @@ impl LinearClient {
         response.users.nodes
             .into_iter()
             .find(|u| u.name.to_lowercase() == name_lower || u.email.to_lowercase() == name_lower)
-            .ok_or_else(|| anyhow::anyhow!("User '{}' not found", name))
+            .ok_or_else(|| error::err(ErrorKind::NotFound, format!("User '{}' not found", name)))
     }
 
     /// Get labels by name for a team
@@ -959,6 +1464,40 @@ impl LinearClient {
         Ok(label_ids)
     }
 
+    /// List a team's labels with colors and descriptions
+    async fn team_labels(&self, team_id: &str) -> Result<Vec<LinearLabelDetails>> {
+        let query = r#"
+            query($teamId: String!) {
+                team(id: $teamId) {
+                    labels(first: 250) {
+                        nodes {
+                            id
+                            name
+                            color
+                            description
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({ "teamId": team_id });
+        let response: TeamLabelDetailsResponse = self.query(query, Some(variables)).await?;
+        Ok(response.team.labels.nodes)
+    }
+
+    /// Label fields for create/update input (Linear colors carry a leading #)
+    fn label_input(label: &LabelDef) -> serde_json::Value {
+        let mut input = serde_json::json!({ "name": label.name });
+        if let Some(color) = &label.color {
+            input["color"] = serde_json::json!(format!("#{}", color));
+        }
+        if let Some(desc) = &label.description {
+            input["description"] = serde_json::json!(desc);
+        }
+        input
+    }
+
     /// List issues for a team (with pagination)
     pub async fn list_team_issues(&self, team_id: &str) -> Result<Vec<Issue>> {
         // Fetch org URL key for constructing issue URLs
@@ -981,11 +1520,51 @@ impl LinearClient {
         Ok(all_issues)
     }
 
+    /// Fetch every issue of a team into the cache page by page, with a
+    /// running count. Linear's page cursor is saved after each page, so if
+    /// this is interrupted the next call continues from there. Returns the
+    /// number of issues cached.
+    pub async fn sync_team_issues_resumable(&self, conn: &rusqlite::Connection, team_id: &str, forge_repo: &str) -> Result<usize> {
+        let started = db::fetch_started();
+        let url_key = self.get_organization().await?.url_key;
+        let mut cursor = db::get_sync_cursor(conn, forge_repo)?;
+        let resumed = cursor.is_some();
+
+        let pb = progress::counter("Fetching issues");
+        if resumed {
+            let cached = db::cache_size(conn, forge_repo)?.issues;
+            pb.suspend(|| eprintln!("Resuming after {} cached issues", cached));
+            pb.set_position(cached as u64);
+        }
+
+        let mut fetched = Vec::new();
+        loop {
+            let (issues, page_info) = self.fetch_issues_page(team_id, &url_key, cursor.as_deref()).await?;
+            pb.inc(issues.len() as u64);
+            let next = page_info.end_cursor.unwrap_or_default();
+            db::save_issue_page(conn, forge_repo, &issues, &next)?;
+            fetched.extend(issues);
+
+            if !page_info.has_next_page {
+                break;
+            }
+            cursor = Some(next);
+        }
+        pb.finish_and_clear();
+
+        if resumed {
+            db::finish_resumed_sync(conn, forge_repo)?;
+        } else {
+            db::save_issues(conn, forge_repo, &fetched, &started)?;
+        }
+        Ok(db::cache_size(conn, forge_repo)?.issues as usize)
+    }
+
     /// Fetch a single page of issues
     async fn fetch_issues_page(&self, team_id: &str, url_key: &str, after: Option<&str>) -> Result<(Vec<Issue>, PageInfo)> {
         let query = r#"
-            query($teamId: ID!, $after: String) {
-                issues(filter: { team: { id: { eq: $teamId } } }, first: 250, after: $after) {
+            query($teamId: ID!, $first: Int!, $after: String) {
+                issues(filter: { team: { id: { eq: $teamId } } }, first: $first, after: $after) {
                     pageInfo {
                         hasNextPage
                         endCursor
@@ -1012,6 +1591,15 @@ impl LinearClient {
                         project {
                             name
                         }
+                        assignee {
+                            name
+                        }
+                        priority
+                        slaBreachesAt
+                        estimate
+                        cycle {
+                            number
+                        }
                         createdAt
                         updatedAt
                     }
@@ -1021,6 +1609,7 @@ impl LinearClient {
 
         let variables = serde_json::json!({
             "teamId": team_id,
+            "first": self.page_size(ISSUES_PAGE_SIZE),
             "after": after
         });
 
@@ -1030,25 +1619,7 @@ impl LinearClient {
             .unwrap_or(PageInfo { has_next_page: false, end_cursor: None });
 
         // Convert Linear issues to our Issue format
-        let issues = response.issues.nodes.into_iter().map(|i| {
-            let url = format!("https://linear.app/{}/issue/{}", url_key, i.identifier);
-            Issue {
-                number: i.number,
-                title: format!("{} {}", i.identifier, i.title),
-                body: i.description,
-                state: if i.state.state_type == "completed" || i.state.state_type == "canceled" {
-                    "closed".to_string()
-                } else {
-                    "open".to_string()
-                },
-                author: i.creator.map(|c| c.name).unwrap_or_else(|| "unknown".to_string()),
-                labels: i.labels.nodes.into_iter().map(|l| Label::new(l.name, Some(l.color))).collect(),
-                created_at: i.created_at,
-                updated_at: i.updated_at,
-                url: Some(url),
-                milestone: i.project.map(|p| p.name),
-            }
-        }).collect();
+        let issues = response.issues.nodes.into_iter().map(|i| i.into_issue(url_key)).collect();
 
         Ok((issues, page_info))
     }
@@ -1123,8 +1694,8 @@ impl LinearClient {
             .ok_or_else(|| anyhow::anyhow!("Project created but not returned"))
     }
 
-    /// Update project state to completed
-    pub async fn complete_project(&self, project_id: &str) -> Result<()> {
+    /// Update project state ("completed" to close, "started" to reopen)
+    pub async fn set_project_state(&self, project_id: &str, state: &str) -> Result<()> {
         let query = r#"
             mutation($id: String!, $input: ProjectUpdateInput!) {
                 projectUpdate(id: $id, input: $input) {
@@ -1135,13 +1706,92 @@ impl LinearClient {
 
         let variables = serde_json::json!({
             "id": project_id,
-            "input": { "state": "completed" }
+            "input": { "state": state }
         });
 
         let response: ProjectUpdateResponse = self.query(query, Some(variables)).await?;
 
         if !response.project_update.success {
-            anyhow::bail!("Failed to complete project");
+            anyhow::bail!("Failed to set project state to {}", state);
+        }
+
+        Ok(())
+    }
+
+    /// List the workspace's initiatives with the projects under each
+    pub async fn list_initiatives(&self) -> Result<Vec<Objective>> {
+        let query = format!("query {{ initiatives(first: 100) {{ nodes {{ {} }} }} }}", INITIATIVE_FIELDS);
+        let response: InitiativesResponse = self.query(&query, None).await?;
+        Ok(response.initiatives.nodes.into_iter().map(Objective::from).collect())
+    }
+
+    /// List a team's cycles
+    pub async fn list_team_cycles(&self, team_id: &str) -> Result<Vec<Cycle>> {
+        let query = r#"
+            query($teamId: String!) {
+                team(id: $teamId) {
+                    cycles(first: 100) {
+                        nodes {
+                            number
+                            name
+                            startsAt
+                            endsAt
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({ "teamId": team_id });
+        let response: TeamCyclesResponse = self.query(query, Some(variables)).await?;
+        Ok(response.team.cycles.nodes.into_iter().map(Cycle::from).collect())
+    }
+
+    /// Create a new initiative
+    pub async fn create_initiative(&self, req: &CreateGoalRequest) -> Result<Objective> {
+        let query = format!(
+            "mutation($input: InitiativeCreateInput!) {{ initiativeCreate(input: $input) {{ success initiative {{ {} }} }} }}",
+            INITIATIVE_FIELDS
+        );
+
+        let mut input = serde_json::json!({ "name": req.name });
+        if let Some(desc) = &req.description {
+            input["description"] = serde_json::json!(desc);
+        }
+        if let Some(date) = &req.target_date {
+            input["targetDate"] = serde_json::json!(date);
+        }
+
+        let variables = serde_json::json!({ "input": input });
+        let response: InitiativeCreateResponse = self.query(&query, Some(variables)).await?;
+
+        if !response.initiative_create.success {
+            anyhow::bail!("Failed to create initiative");
+        }
+
+        response.initiative_create.initiative
+            .map(Objective::from)
+            .ok_or_else(|| anyhow::anyhow!("Initiative created but not returned"))
+    }
+
+    /// Add a project to an initiative
+    pub async fn add_project_to_initiative(&self, initiative_id: &str, project_id: &str) -> Result<()> {
+        let query = r#"
+            mutation($input: InitiativeToProjectCreateInput!) {
+                initiativeToProjectCreate(input: $input) {
+                    success
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "input": { "initiativeId": initiative_id, "projectId": project_id }
+        });
+
+        let response: InitiativeToProjectResponse = self.query(query, Some(variables)).await?;
+
+        if !response.initiative_to_project_create.success {
+            anyhow::bail!("Failed to add project to initiative");
         }
 
         Ok(())
@@ -1181,6 +1831,12 @@ impl Forge for LinearClient {
 
     async fn create_issue(&self, repo: &Repo, req: CreateIssueRequest) -> Result<Issue> {
         let team_id = &repo.name;
+        if let Some(issue_type) = &req.issue_type {
+            return Err(error::err(
+                ErrorKind::Usage,
+                format!("Linear has no issue types; map a label to '{}' under [types.labels] instead", issue_type),
+            ));
+        }
         let org = self.get_organization().await?;
 
         // Get label IDs if any labels specified (Linear requires empty array, not null)
@@ -1190,49 +1846,40 @@ impl Forge for LinearClient {
             Vec::new()
         };
 
-        // Build mutation dynamically based on whether projectId is provided
-        let (query, variables) = if let Some(project_id) = &req.goal_id {
-            let q = r#"
-                mutation($teamId: String!, $title: String!, $description: String, $labelIds: [String!], $projectId: String!) {
-                    issueCreate(input: { teamId: $teamId, title: $title, description: $description, labelIds: $labelIds, projectId: $projectId }) {
-                        issue {
-                            id
-                            identifier
-                            number
-                            title
-                        }
-                    }
-                }
-            "#;
-            let v = serde_json::json!({
-                "teamId": team_id,
-                "title": req.title,
-                "description": req.body,
-                "labelIds": label_ids,
-                "projectId": project_id
-            });
-            (q, v)
-        } else {
-            let q = r#"
-                mutation($teamId: String!, $title: String!, $description: String, $labelIds: [String!]) {
-                    issueCreate(input: { teamId: $teamId, title: $title, description: $description, labelIds: $labelIds }) {
-                        issue {
-                            id
-                            identifier
-                            number
-                            title
-                        }
+        let mut input = serde_json::json!({
+            "teamId": team_id,
+            "title": req.title,
+            "description": req.body,
+            "labelIds": label_ids,
+        });
+        if let Some(project_id) = &req.goal_id {
+            input["projectId"] = serde_json::json!(project_id);
+        }
+        let assignee = match &req.assignee {
+            Some(name) => Some(self.get_user_by_name(name).await?),
+            None => None,
+        };
+        if let Some(user) = &assignee {
+            input["assigneeId"] = serde_json::json!(user.id);
+        }
+        // Linear takes a client-chosen id, which is all a retry needs to find the issue
+        if let Some(id) = req.client_key.as_deref().and_then(client_key_uuid) {
+            input["id"] = serde_json::json!(id);
+        }
+
+        let query = r#"
+            mutation($input: IssueCreateInput!) {
+                issueCreate(input: $input) {
+                    issue {
+                        id
+                        identifier
+                        number
+                        title
                     }
                 }
-            "#;
-            let v = serde_json::json!({
-                "teamId": team_id,
-                "title": req.title,
-                "description": req.body,
-                "labelIds": label_ids
-            });
-            (q, v)
-        };
+            }
+        "#;
+        let variables = serde_json::json!({ "input": input });
 
         let response: IssueCreateResponse = self.query(query, Some(variables)).await?;
         let created = response.issue_create.issue;
@@ -1240,7 +1887,7 @@ impl Forge for LinearClient {
 
         Ok(Issue {
             number: created.number,
-            title: format!("{} {}", created.identifier, created.title),
+            title: created.title,
             body: req.body,
             state: "open".to_string(),
             author: "me".to_string(),
@@ -1249,9 +1896,57 @@ impl Forge for LinearClient {
             updated_at: String::new(),
             url: Some(url),
             milestone: req.goal_id.clone(),
+            assignees: assignee.into_iter().map(|user| user.name).collect(),
+            priority: None,
+            identifier: Some(created.identifier),
+            sla_breaches_at: None,
+            triage: false,
+            issue_type: None,
+            estimate: None,
+            cycle: None,
         })
     }
 
+    async fn find_created_issue(&self, _repo: &Repo, client_key: &str) -> Result<Option<Issue>> {
+        let Some(id) = client_key_uuid(client_key) else {
+            return Ok(None);
+        };
+        let query = r#"
+            query($id: ID!) {
+                issues(filter: { id: { eq: $id } }, first: 1) {
+                    nodes {
+                        identifier
+                        number
+                        title
+                        url
+                    }
+                }
+            }
+        "#;
+
+        let response: CreatedIssueListResponse = self.query(query, Some(serde_json::json!({ "id": id }))).await?;
+        Ok(response.issues.nodes.into_iter().next().map(|found| Issue {
+            number: found.number,
+            title: found.title,
+            body: None,
+            state: "open".to_string(),
+            author: "me".to_string(),
+            labels: Vec::new(),
+            created_at: String::new(),
+            updated_at: String::new(),
+            url: found.url,
+            milestone: None,
+            assignees: Vec::new(),
+            priority: None,
+            identifier: Some(found.identifier),
+            sla_breaches_at: None,
+            triage: false,
+            issue_type: None,
+            estimate: None,
+            cycle: None,
+        }))
+    }
+
     async fn create_comment(&self, repo: &Repo, issue_number: u64, body: &str) -> Result<()> {
         let issue = self.get_issue_by_number(&repo.name, issue_number).await?;
 
@@ -1275,9 +1970,54 @@ impl Forge for LinearClient {
         Ok(())
     }
 
+    async fn create_keyed_comment(&self, repo: &Repo, issue_number: u64, body: &str, client_key: &str) -> Result<()> {
+        let Some(id) = client_key_uuid(client_key) else {
+            return self.create_comment(repo, issue_number, body).await;
+        };
+        let issue = self.get_issue_by_number(&repo.name, issue_number).await?;
+
+        let query = r#"
+            mutation($id: String!, $issueId: String!, $body: String!) {
+                commentCreate(input: { id: $id, issueId: $issueId, body: $body }) {
+                    success
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "id": id,
+            "issueId": issue.id,
+            "body": body
+        });
+
+        let response: CommentCreateResponse = self.query(query, Some(variables)).await?;
+        if !response.comment_create.success {
+            anyhow::bail!("Failed to create comment");
+        }
+        Ok(())
+    }
+
+    async fn find_posted_comment(&self, _repo: &Repo, _issue_number: u64, client_key: &str) -> Result<bool> {
+        let Some(id) = client_key_uuid(client_key) else {
+            return Ok(false);
+        };
+        let query = r#"
+            query($id: ID!) {
+                comments(filter: { id: { eq: $id } }, first: 1) {
+                    nodes {
+                        id
+                    }
+                }
+            }
+        "#;
+
+        let response: PostedCommentListResponse = self.query(query, Some(serde_json::json!({ "id": id }))).await?;
+        Ok(!response.comments.nodes.is_empty())
+    }
+
     async fn close_issue(&self, repo: &Repo, issue_number: u64) -> Result<()> {
         let issue = self.get_issue_by_number(&repo.name, issue_number).await?;
-        let done_state = self.get_state_by_type(&repo.name, "completed").await?;
+        let done_state = self.pick_state(&repo.name, self.states.closed.as_deref(), &["completed"]).await?;
 
         let query = r#"
             mutation($issueId: String!, $stateId: String!) {
@@ -1301,14 +2041,8 @@ impl Forge for LinearClient {
 
     async fn reopen_issue(&self, repo: &Repo, issue_number: u64) -> Result<()> {
         let issue = self.get_issue_by_number(&repo.name, issue_number).await?;
-        // Try "backlog" first, fall back to "unstarted" or "started"
-        let backlog_state = match self.get_state_by_type(&repo.name, "backlog").await {
-            Ok(state) => state,
-            Err(_) => match self.get_state_by_type(&repo.name, "unstarted").await {
-                Ok(state) => state,
-                Err(_) => self.get_state_by_type(&repo.name, "started").await?,
-            }
-        };
+        // Backlog by default, else "unstarted" or "started"
+        let backlog_state = self.pick_state(&repo.name, self.states.open.as_deref(), &["backlog", "unstarted", "started"]).await?;
 
         let query = r#"
             mutation($issueId: String!, $stateId: String!) {
@@ -1335,7 +2069,7 @@ impl Forge for LinearClient {
         let label_ids = self.get_label_ids(&repo.name, &[label.to_string()]).await?;
 
         if label_ids.is_empty() {
-            anyhow::bail!("Label '{}' not found", label);
+            return Err(error::err(ErrorKind::NotFound, format!("Label '{}' not found", label)));
         }
 
         // Get current label IDs and add the new one
@@ -1361,6 +2095,7 @@ impl Forge for LinearClient {
         if !response.issue_update.success {
             anyhow::bail!("Failed to add label");
         }
+        self.forget_issue(&repo.name, issue_number);
         Ok(())
     }
 
@@ -1391,12 +2126,39 @@ impl Forge for LinearClient {
         if !response.issue_update.success {
             anyhow::bail!("Failed to remove label");
         }
+        self.forget_issue(&repo.name, issue_number);
+        Ok(())
+    }
+
+    async fn update_issue_body(&self, repo: &Repo, issue_number: u64, body: &str) -> Result<()> {
+        let issue = self.get_issue_by_number(&repo.name, issue_number).await?;
+
+        let query = r#"
+            mutation($issueId: String!, $description: String!) {
+                issueUpdate(id: $issueId, input: { description: $description }) {
+                    success
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "issueId": issue.id,
+            "description": body
+        });
+
+        let response: IssueUpdateResponse = self.query(query, Some(variables)).await?;
+        if !response.issue_update.success {
+            anyhow::bail!("Failed to update issue");
+        }
         Ok(())
     }
 
     async fn assign_issue(&self, repo: &Repo, issue_number: u64, assignee: &str) -> Result<()> {
         let issue = self.get_issue_by_number(&repo.name, issue_number).await?;
         let user = self.get_user_by_name(assignee).await?;
+        if issue.assignee.as_ref().is_some_and(|a| a.id == user.id) {
+            return Ok(());
+        }
 
         let query = r#"
             mutation($issueId: String!, $assigneeId: String!) {
@@ -1415,14 +2177,93 @@ impl Forge for LinearClient {
         if !response.issue_update.success {
             anyhow::bail!("Failed to assign issue");
         }
+        self.forget_issue(&repo.name, issue_number);
+        Ok(())
+    }
+
+    async fn set_subscribed(&self, repo: &Repo, issue_number: u64, subscribed: bool) -> Result<()> {
+        let issue = self.get_issue_by_number(&repo.name, issue_number).await?;
+        let mutation = if subscribed { "issueSubscribe" } else { "issueUnsubscribe" };
+        let query = format!(
+            r#"
+            mutation($id: String!) {{
+                {}(id: $id) {{
+                    success
+                }}
+            }}
+        "#,
+            mutation
+        );
+
+        let response: serde_json::Value = self.query(&query, Some(serde_json::json!({ "id": issue.id }))).await?;
+        if response[mutation]["success"].as_bool() != Some(true) {
+            anyhow::bail!("Failed to {} issue", if subscribed { "subscribe to" } else { "unsubscribe from" });
+        }
+        Ok(())
+    }
+
+    async fn list_labels(&self, repo: &Repo) -> Result<Vec<LabelDef>> {
+        let labels = self.team_labels(&repo.name).await?;
+        Ok(labels
+            .into_iter()
+            .map(|l| LabelDef {
+                name: l.name,
+                color: Some(l.color.trim_start_matches('#').to_lowercase()),
+                description: l.description.filter(|d| !d.is_empty()),
+            })
+            .collect())
+    }
+
+    async fn create_label(&self, repo: &Repo, label: &LabelDef) -> Result<()> {
+        let query = r#"
+            mutation($input: IssueLabelCreateInput!) {
+                issueLabelCreate(input: $input) {
+                    success
+                }
+            }
+        "#;
+
+        let mut input = Self::label_input(label);
+        input["teamId"] = serde_json::json!(repo.name);
+        let variables = serde_json::json!({ "input": input });
+
+        let response: LabelCreateResponse = self.query(query, Some(variables)).await?;
+        if !response.issue_label_create.success {
+            anyhow::bail!("Failed to create label '{}'", label.name);
+        }
+        Ok(())
+    }
+
+    async fn update_label(&self, repo: &Repo, name: &str, label: &LabelDef) -> Result<()> {
+        let current = self
+            .team_labels(&repo.name)
+            .await?
+            .into_iter()
+            .find(|l| l.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| error::err(ErrorKind::NotFound, format!("Label '{}' not found", name)))?;
+
+        let query = r#"
+            mutation($id: String!, $input: IssueLabelUpdateInput!) {
+                issueLabelUpdate(id: $id, input: $input) {
+                    success
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({ "id": current.id, "input": Self::label_input(label) });
+
+        let response: LabelUpdateResponse = self.query(query, Some(variables)).await?;
+        if !response.issue_label_update.success {
+            anyhow::bail!("Failed to update label '{}'", name);
+        }
         Ok(())
     }
 
     async fn list_all_comments(&self, repo: &Repo) -> Result<Vec<crate::db::Comment>> {
         // Fetch all issues with their comments in a single query
         let query = r#"
-            query($teamId: ID!) {
-                issues(filter: { team: { id: { eq: $teamId } } }, first: 100) {
+            query($teamId: ID!, $first: Int!) {
+                issues(filter: { team: { id: { eq: $teamId } } }, first: $first) {
                     nodes {
                         number
                         comments {
@@ -1441,7 +2282,8 @@ impl Forge for LinearClient {
         "#;
 
         let variables = serde_json::json!({
-            "teamId": repo.name
+            "teamId": repo.name,
+            "first": self.page_size(COMMENTS_PAGE_SIZE)
         });
 
         let response: IssuesWithCommentsResponse = self.query(query, Some(variables)).await?;
@@ -1463,6 +2305,104 @@ impl Forge for LinearClient {
         Ok(comments)
     }
 
+    async fn get_issue(&self, repo: &Repo, issue_number: u64) -> Result<Issue> {
+        let query = r#"
+            query($teamId: ID!, $number: Float!) {
+                organization {
+                    urlKey
+                }
+                issues(filter: { team: { id: { eq: $teamId } }, number: { eq: $number } }, first: 1) {
+                    nodes {
+                        id
+                        identifier
+                        number
+                        title
+                        description
+                        state {
+                            name
+                            type
+                        }
+                        creator {
+                            name
+                        }
+                        labels {
+                            nodes {
+                                name
+                                color
+                            }
+                        }
+                        project {
+                            name
+                        }
+                        assignee {
+                            name
+                        }
+                        priority
+                        slaBreachesAt
+                        estimate
+                        cycle {
+                            number
+                        }
+                        createdAt
+                        updatedAt
+                    }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "teamId": repo.name,
+            "number": issue_number as f64
+        });
+
+        let response: SingleIssueResponse = self.query(query, Some(variables)).await?;
+        let url_key = response.organization.url_key;
+        response.issues.nodes.into_iter().next()
+            .map(|i| i.into_issue(&url_key))
+            .ok_or_else(|| error::err(ErrorKind::NotFound, format!("Issue #{} not found in team", issue_number)))
+    }
+
+    async fn list_issue_comments(&self, repo: &Repo, issue_number: u64) -> Result<Vec<crate::db::Comment>> {
+        let query = r#"
+            query($teamId: ID!, $number: Float!) {
+                issues(filter: { team: { id: { eq: $teamId } }, number: { eq: $number } }, first: 1) {
+                    nodes {
+                        number
+                        comments(first: 250) {
+                            nodes {
+                                id
+                                body
+                                user {
+                                    name
+                                }
+                                createdAt
+                            }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = serde_json::json!({
+            "teamId": repo.name,
+            "number": issue_number as f64
+        });
+
+        let response: IssuesWithCommentsResponse = self.query(query, Some(variables)).await?;
+        let mut comments: Vec<crate::db::Comment> = response.issues.nodes.into_iter()
+            .flat_map(|issue| issue.comments.nodes)
+            .map(|comment| crate::db::Comment {
+                comment_id: comment.id,
+                issue_number,
+                body: comment.body,
+                author: comment.user.map(|u| u.name).unwrap_or_else(|| "unknown".to_string()),
+                created_at: comment.created_at,
+            })
+            .collect();
+        comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(comments)
+    }
+
     async fn list_goals(&self, repo: &Repo) -> Result<Vec<Goal>> {
         let projects = self.list_projects(&repo.name).await?;
         Ok(projects.into_iter().map(Goal::from).collect())
@@ -1474,7 +2414,11 @@ impl Forge for LinearClient {
     }
 
     async fn close_goal(&self, _repo: &Repo, goal_id: &str) -> Result<()> {
-        self.complete_project(goal_id).await
+        self.set_project_state(goal_id, "completed").await
+    }
+
+    async fn reopen_goal(&self, _repo: &Repo, goal_id: &str) -> Result<()> {
+        self.set_project_state(goal_id, "started").await
     }
 
     async fn assign_to_goal(&self, repo: &Repo, issue_number: u64, goal_id: &str) -> Result<()> {
@@ -1483,6 +2427,22 @@ impl Forge for LinearClient {
         self.set_issue_project(&issue.id, goal_id).await
     }
 
+    async fn list_objectives(&self, _repo: &Repo) -> Result<Option<Vec<Objective>>> {
+        Ok(Some(self.list_initiatives().await?))
+    }
+
+    async fn list_cycles(&self, repo: &Repo) -> Result<Option<Vec<Cycle>>> {
+        Ok(Some(self.list_team_cycles(&repo.name).await?))
+    }
+
+    async fn create_objective(&self, _repo: &Repo, req: CreateGoalRequest) -> Result<Option<Objective>> {
+        Ok(Some(self.create_initiative(&req).await?))
+    }
+
+    async fn link_goal_to_objective(&self, _repo: &Repo, objective_id: &str, goal_id: &str) -> Result<()> {
+        self.add_project_to_initiative(objective_id, goal_id).await
+    }
+
     async fn get_rate_limit(&self) -> Result<Option<RateLimitInfo>> {
         // Linear returns rate limit info in response headers
         // Make a minimal query to get the headers
@@ -1527,4 +2487,35 @@ impl Forge for LinearClient {
             _ => Ok(None), // Headers not present, Linear may not always send them
         }
     }
+
+    async fn get_user(&self) -> Result<String> {
+        self.get_viewer().await
+    }
+
+    fn complexity_budget(&self) -> Option<ComplexityBudget> {
+        *self.budget.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rejected_tokens_are_skipped() {
+        let verify = |token: String| async move {
+            match token.as_str() {
+                "lin_good" => Ok("alice".to_string()),
+                _ => Err(error::err(ErrorKind::AuthMissing, "Linear API error 401")),
+            }
+        };
+
+        // A stale LINEAR_API_KEY is passed over for the next token
+        let tokens = vec!["lin_stale".to_string(), "lin_good".to_string()];
+        assert_eq!(
+            first_accepted(tokens, verify).await,
+            Some(("lin_good".to_string(), "alice".to_string()))
+        );
+        assert_eq!(first_accepted(vec!["lin_stale".to_string()], verify).await, None);
+    }
 }
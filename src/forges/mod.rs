@@ -1,6 +1,8 @@
+mod external;
 mod github;
 mod linear;
 
+use std::collections::BTreeMap;
 use std::process::Command;
 
 use anyhow::{anyhow, Result};
@@ -9,10 +11,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::credentials;
 use crate::db;
+use crate::error::{self, ErrorKind};
 use crate::repo::Repo;
+use crate::team_config;
 
-pub use github::GitHubClient;
-pub use linear::LinearClient;
+pub use linear::{find_team, LinearClient};
 
 // ============================================================================
 // Auth Configuration
@@ -34,16 +37,18 @@ pub struct AuthConfig {
     pub display_name: &'static str,
     /// Command to authenticate (shown in error messages)
     pub link_command: &'static str,
+    /// Command to store a token non-interactively (shown in error messages)
+    pub login_command: &'static str,
 }
 
 impl AuthConfig {
     /// Get a token using the fallback chain: CLI → keyring → env var
     pub fn get_token(&self) -> Result<String> {
         // 1. Try CLI command if configured
-        if let Some(cmd) = self.cli_command {
-            if let Ok(token) = self.try_cli_token(cmd) {
-                return Ok(token);
-            }
+        if let Some(cmd) = self.cli_command
+            && let Ok(token) = self.try_cli_token(cmd)
+        {
+            return Ok(token);
         }
 
         // 2. Try stored credentials from OS keyring
@@ -60,13 +65,38 @@ impl AuthConfig {
         Err(self.auth_error())
     }
 
+    /// All available tokens in fallback order, labelled by source.
+    /// Lets callers skip a stale token (e.g. an expired keyring entry) and try the next.
+    pub fn candidate_tokens(&self) -> Vec<(String, &'static str)> {
+        let mut tokens = Vec::new();
+
+        if let Some(cmd) = self.cli_command
+            && let Ok(token) = self.try_cli_token(cmd)
+        {
+            tokens.push((token, "CLI"));
+        }
+
+        if let Ok(Some(cred)) = credentials::get_credential(self.keyring_service) {
+            tokens.push((cred.access_token, "keyring"));
+        }
+
+        if let Ok(token) = std::env::var(self.env_var)
+            && !token.trim().is_empty()
+        {
+            tokens.push((token.trim().to_string(), self.env_var));
+        }
+
+        tokens
+    }
+
     /// Check if credentials are available (without detailed errors)
+    #[allow(dead_code)] // Used in tests
     pub fn has_credentials(&self) -> bool {
         // Check CLI
-        if let Some(cmd) = self.cli_command {
-            if self.try_cli_token(cmd).is_ok() {
-                return true;
-            }
+        if let Some(cmd) = self.cli_command
+            && self.try_cli_token(cmd).is_ok()
+        {
+            return true;
         }
 
         // Check keyring
@@ -78,19 +108,71 @@ impl AuthConfig {
         std::env::var(self.env_var).is_ok()
     }
 
-    /// Store a credential in the OS keyring
+    /// Get a token for a named account (`--as work`): its keyring entry, then
+    /// an env var like `GITHUB_TOKEN_WORK`. Without a name, the usual chain.
+    pub fn get_account_token(&self, account: Option<&str>) -> Result<String> {
+        let Some(account) = account else {
+            return self.get_token();
+        };
+
+        let service = account_key(self.keyring_service, Some(account));
+        if let Ok(Some(cred)) = credentials::get_credential(&service) {
+            return Ok(cred.access_token);
+        }
+
+        let env_var = self.account_env_var(account);
+        if let Ok(token) = std::env::var(&env_var)
+            && !token.trim().is_empty()
+        {
+            return Ok(token.trim().to_string());
+        }
+
+        Err(error::err(
+            ErrorKind::AuthMissing,
+            format!(
+                "{} account '{}' not authenticated.\n\nRun: {} --as {} < token.txt\nOr set {}",
+                self.display_name, account, self.login_command, account, env_var
+            ),
+        ))
+    }
+
+    /// The token `get_account_token` would use, with where it came from
+    /// ("gh CLI", "keyring" or an env var name)
+    pub fn account_token_source(&self, account: Option<&str>) -> Result<(String, String)> {
+        let Some(name) = account else {
+            let (token, source) = self.candidate_tokens().into_iter().next().ok_or_else(|| self.auth_error())?;
+            let source = match (source, self.cli_command) {
+                ("CLI", Some(cmd)) => format!("{} CLI", cmd[0]),
+                _ => source.to_string(),
+            };
+            return Ok((token, source));
+        };
+
+        let token = self.get_account_token(account)?;
+        let in_keyring = matches!(self.get_credential(account), Ok(Some(cred)) if cred.access_token == token);
+        let source = if in_keyring { "keyring".to_string() } else { self.account_env_var(name) };
+        Ok((token, source))
+    }
+
+    /// Env var holding a named account's token (`GITHUB_TOKEN_WORK`)
+    fn account_env_var(&self, account: &str) -> String {
+        format!("{}_{}", self.env_var, account.to_uppercase().replace('-', "_"))
+    }
+
+    /// Store a credential in the OS keyring (for a named account, or the default one)
     pub fn store_credential(
         &self,
+        account: Option<&str>,
         access_token: &str,
         refresh_token: Option<&str>,
         expires_at: Option<&str>,
     ) -> Result<()> {
-        credentials::set_credential(self.keyring_service, access_token, refresh_token, expires_at)
+        credentials::set_credential(&account_key(self.keyring_service, account), access_token, refresh_token, expires_at)
     }
 
     /// Get the full credential (including refresh token) from keyring
-    pub fn get_credential(&self) -> Result<Option<credentials::Credential>> {
-        credentials::get_credential(self.keyring_service)
+    pub fn get_credential(&self, account: Option<&str>) -> Result<Option<credentials::Credential>> {
+        credentials::get_credential(&account_key(self.keyring_service, account))
     }
 
     /// Try to get a token from a CLI command
@@ -101,7 +183,7 @@ impl AuthConfig {
             .map_err(|_| anyhow!("{} CLI not found", self.display_name))?;
 
         if !output.status.success() {
-            return Err(anyhow!("{} CLI not authenticated", self.display_name));
+            return Err(error::err(ErrorKind::AuthMissing, format!("{} CLI not authenticated", self.display_name)));
         }
 
         let token = String::from_utf8(output.stdout)?.trim().to_string();
@@ -131,13 +213,29 @@ impl AuthConfig {
         msg.push_str(&format!("Option {}: Run: {}\n", option, self.link_command));
         option += 1;
 
+        // Token on stdin (headless)
+        msg.push_str(&format!(
+            "Option {}: Run: {} < token.txt\n",
+            option, self.login_command
+        ));
+        option += 1;
+
         // Env var option
         msg.push_str(&format!(
             "Option {}: Set {} environment variable",
             option, self.env_var
         ));
 
-        anyhow!(msg)
+        error::err(ErrorKind::AuthMissing, msg)
+    }
+}
+
+/// Keyring entry and identity key for a forge account: `github` for the
+/// default account, `github:work` for one added with `--as work`
+pub fn account_key(forge: &str, account: Option<&str>) -> String {
+    match account {
+        Some(account) => format!("{}:{}", forge, account),
+        None => forge.to_string(),
     }
 }
 
@@ -165,7 +263,7 @@ impl Label {
 }
 
 /// Forge-agnostic issue representation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Issue {
     pub number: u64,
     pub title: String,
@@ -178,16 +276,126 @@ pub struct Issue {
     pub url: Option<String>,
     /// Goal name (GitHub: milestone title, Linear: project name)
     pub milestone: Option<String>,
+    /// Assignee usernames (GitHub: logins, Linear: user name)
+    #[serde(default)]
+    pub assignees: Vec<String>,
+    /// Priority on Linear's scale: 1 urgent, 2 high, 3 medium, 4 low (GitHub has none)
+    #[serde(default)]
+    pub priority: Option<u8>,
+    /// Forge-assigned key, e.g. Linear's `ABC-123` (GitHub has none; the number is the ID)
+    #[serde(default)]
+    pub identifier: Option<String>,
+    /// When the issue breaches its SLA (Linear, RFC 3339; GitHub has none)
+    #[serde(default)]
+    pub sla_breaches_at: Option<String>,
+    /// Waiting in the team's triage queue (Linear)
+    #[serde(default)]
+    pub triage: bool,
+    /// Issue type, e.g. Bug or Feature: GitHub's own, else from `[types]` labels
+    #[serde(rename = "type", default)]
+    pub issue_type: Option<String>,
+    /// Estimate in points (Linear; GitHub has none)
+    #[serde(default)]
+    pub estimate: Option<f64>,
+    /// Number of the cycle the issue is planned in (Linear; GitHub has none)
+    #[serde(default)]
+    pub cycle: Option<u32>,
+}
+
+impl Issue {
+    /// How to refer to the issue: its identifier if it has one, else `#number`
+    pub fn display_id(&self) -> String {
+        match &self.identifier {
+            Some(identifier) => identifier.clone(),
+            None => format!("#{}", self.number),
+        }
+    }
+}
+
+/// Split a Linear-style identifier (`ABC-123`) into team key and number
+pub fn split_identifier(s: &str) -> Option<(&str, u64)> {
+    let (key, number) = s.rsplit_once('-')?;
+    let valid_key = key.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && key.chars().all(|c| c.is_ascii_alphanumeric());
+    if !valid_key {
+        return None;
+    }
+    Some((key, number.parse().ok()?))
+}
+
+/// An issue ID argument, with the team key if it was given as an identifier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueId {
+    pub key: Option<String>,
+    pub number: u64,
+}
+
+impl IssueId {
+    /// The issue number in the linked repo. An identifier's key has to be one
+    /// of the link's teams (`team_keys`: the keys its cached issues carry), so
+    /// `OPS-42` never acts on another team's #42.
+    pub fn number_in(&self, link: &db::RepoLink, team_keys: &[String]) -> Result<u64> {
+        let Some(key) = &self.key else {
+            return Ok(self.number);
+        };
+        if link.forge_type == ForgeType::GitHub.as_str() {
+            return Err(error::err(
+                ErrorKind::Usage,
+                format!("{} is an identifier, but GitHub issues only have numbers (e.g. {})", self, self.number),
+            ));
+        }
+
+        // A Linear link's forge_repo starts with its team key ("KEY/team-id")
+        let mut keys: Vec<&str> = team_keys.iter().map(String::as_str).collect();
+        if link.forge_type == ForgeType::Linear.as_str()
+            && let Some((linked, _)) = link.forge_repo.split_once('/')
+        {
+            keys.push(linked);
+        }
+        if keys.iter().any(|k| k.eq_ignore_ascii_case(key)) {
+            return Ok(self.number);
+        }
+        let message = if keys.is_empty() {
+            format!("{} not found: this repo's issues have no identifiers; use the issue number", self)
+        } else {
+            keys.sort_unstable();
+            keys.dedup();
+            format!("{} not found: this repo is linked to team {}", self, keys.join(", "))
+        };
+        Err(error::err(ErrorKind::NotFound, message))
+    }
+}
+
+impl std::fmt::Display for IssueId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.key {
+            Some(key) => write!(f, "{}-{}", key, self.number),
+            None => write!(f, "#{}", self.number),
+        }
+    }
+}
+
+/// Parse an issue ID argument: `123`, `#123` or an identifier like `ABC-123`
+pub fn parse_issue_id(s: &str) -> Result<IssueId, String> {
+    let s = s.trim();
+    if let Ok(number) = s.trim_start_matches('#').parse() {
+        return Ok(IssueId { key: None, number });
+    }
+    split_identifier(s)
+        .map(|(key, number)| IssueId { key: Some(key.to_string()), number })
+        .ok_or_else(|| format!("invalid issue ID '{}' (expected a number like 123 or an identifier like ABC-123)", s))
 }
 
 /// Supported forge types
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ForgeType {
     GitHub,
     Linear,
+    /// A tracker served by an `isq-forge-<name>` adapter on PATH
+    External(String),
 }
 
-/// All supported forge types (for iteration)
+/// Built-in forge types (for iteration; see `available_forge_types` for adapters too)
 pub const ALL_FORGE_TYPES: &[ForgeType] = &[ForgeType::GitHub, ForgeType::Linear];
 
 // ============================================================================
@@ -197,8 +405,21 @@ pub const ALL_FORGE_TYPES: &[ForgeType] = &[ForgeType::GitHub, ForgeType::Linear
 /// Arguments for the link command, parsed from CLI options
 #[derive(Debug, Clone, Default)]
 pub struct LinkArgs {
+    /// Options the built-in forges don't know, passed through to external adapters
+    pub extra: BTreeMap<String, String>,
     pub team: Option<String>,
     pub list_teams: bool,
+    /// Link repos of this org/user instead of the current repo (GitHub)
+    pub org: Option<String>,
+    /// With `org`: link every repo without prompting
+    pub all: bool,
+    /// With `org`: only repos whose name matches this glob
+    pub pattern: Option<String>,
+    /// With `org`: link a search workspace of the issues matching this
+    /// GitHub search query instead of repos
+    pub query: Option<String>,
+    /// Named credential to link with (`--as work`); None for the default one
+    pub account: Option<String>,
 }
 
 impl LinkArgs {
@@ -211,7 +432,9 @@ impl LinkArgs {
             } else if let Some((key, value)) = opt.split_once('=') {
                 match key {
                     "team" => args.team = Some(value.to_string()),
-                    _ => return Err(anyhow!("Unknown option: {}", key)),
+                    _ => {
+                        args.extra.insert(key.to_string(), value.to_string());
+                    }
                 }
             } else {
                 return Err(anyhow!("Invalid option format: {}. Use key=value or flag name.", opt));
@@ -221,27 +444,117 @@ impl LinkArgs {
     }
 }
 
+/// Match a name against a glob pattern (`*` any run, `?` one character)
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let n: Vec<char> = name.chars().collect();
+    let (mut pi, mut ni) = (0, 0);
+    // Position after the last `*` and the name index it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while ni < n.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == n[ni]) {
+            pi += 1;
+            ni += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            backtrack = Some((pi + 1, ni));
+            pi += 1;
+        } else if let Some((star_pi, star_ni)) = backtrack {
+            pi = star_pi;
+            ni = star_ni + 1;
+            backtrack = Some((star_pi, star_ni + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+/// Parse an interactive selection like "1,3,5-7" or "all" into 0-based indices
+pub fn parse_selection(input: &str, len: usize) -> Result<Vec<usize>, String> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("all") || input == "*" {
+        return Ok((0..len).collect());
+    }
+
+    let parse = |s: &str| -> Result<usize, String> {
+        let n: usize = s.trim().parse().map_err(|_| format!("Invalid selection: '{}'", s.trim()))?;
+        if n == 0 || n > len {
+            return Err(format!("Selection {} out of range (1-{})", n, len));
+        }
+        Ok(n - 1)
+    };
+
+    let mut indices = Vec::new();
+    for part in input.split(',').filter(|p| !p.trim().is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse(start)?, parse(end)?);
+                if start > end {
+                    return Err(format!("Invalid range '{}': it runs backwards", part.trim()));
+                }
+                indices.extend(start..=end);
+            }
+            None => indices.push(parse(part)?),
+        }
+    }
+    if indices.is_empty() {
+        return Err("Nothing selected".to_string());
+    }
+    indices.sort_unstable();
+    indices.dedup();
+    Ok(indices)
+}
+
 /// Result of a successful link operation
 #[derive(Debug, Clone)]
 pub struct LinkResult {
     pub display_name: String,
 }
 
+/// Whether a browser can't be opened for OAuth (SSH session or no display server)
+pub fn is_headless() -> bool {
+    if std::env::var_os("SSH_CONNECTION").is_some() {
+        return true;
+    }
+    cfg!(target_os = "linux")
+        && std::env::var_os("DISPLAY").is_none()
+        && std::env::var_os("WAYLAND_DISPLAY").is_none()
+}
+
 /// Generate error message for repos not linked to a forge
 pub fn not_linked_error() -> anyhow::Error {
-    let forges: Vec<_> = ALL_FORGE_TYPES.iter().map(|f| format!("  isq link {}", f.as_str())).collect();
-    anyhow!("This repo is not linked to an issue tracker.\n\nRun one of:\n{}", forges.join("\n"))
+    let forges: Vec<_> = available_forge_types()
+        .iter().map(|f| format!("  isq link {}", f.as_str())).collect();
+    error::err(
+        ErrorKind::NotLinked,
+        format!(
+            "This repo is not linked to an issue tracker.\n\nRun one of:\n{}\n\nMoved this repo? Run: isq link --repair",
+            forges.join("\n")
+        ),
+    )
+}
+
+/// Built-in forges followed by the adapters found on PATH
+pub fn available_forge_types() -> Vec<ForgeType> {
+    ALL_FORGE_TYPES
+        .iter()
+        .cloned()
+        .chain(external::discover().into_iter().map(ForgeType::External))
+        .collect()
 }
 
 impl ForgeType {
-    pub fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &str {
         match self {
             ForgeType::GitHub => "github",
             ForgeType::Linear => "linear",
+            ForgeType::External(name) => name,
         }
     }
 
-    pub fn from_str(s: &str) -> Option<ForgeType> {
+    /// A built-in forge by name
+    pub fn builtin(s: &str) -> Option<ForgeType> {
         match s.to_lowercase().as_str() {
             "github" => Some(ForgeType::GitHub),
             "linear" => Some(ForgeType::Linear),
@@ -249,29 +562,177 @@ impl ForgeType {
         }
     }
 
-    /// Get auth configuration for this forge
-    pub fn auth(&self) -> &'static AuthConfig {
+    /// A built-in forge, or an external one if its adapter is on PATH
+    pub fn from_str(s: &str) -> Option<ForgeType> {
+        let name = s.to_lowercase();
+        ForgeType::builtin(&name)
+            .or_else(|| external::find_adapter(&name).map(|_| ForgeType::External(name)))
+    }
+
+    /// Get auth configuration for this forge (None for external adapters,
+    /// which keep their own credentials)
+    pub fn auth(&self) -> Option<&'static AuthConfig> {
         match self {
-            ForgeType::GitHub => &github::AUTH,
-            ForgeType::Linear => &linear::AUTH,
+            ForgeType::GitHub => Some(&github::AUTH),
+            ForgeType::Linear => Some(&linear::AUTH),
+            ForgeType::External(_) => None,
+        }
+    }
+
+    /// Human-readable forge name (an adapter's name as given)
+    pub fn display_name(&self) -> &str {
+        match self.auth() {
+            Some(auth) => auth.display_name,
+            None => self.as_str(),
+        }
+    }
+
+    /// Describe the credential that would be used (e.g. "fine-grained PAT via keyring"),
+    /// or None if nothing is configured
+    pub fn credential_summary(&self) -> Option<String> {
+        if let ForgeType::External(name) = self {
+            return Some(format!("managed by {}{}", external::ADAPTER_PREFIX, name));
+        }
+
+        let token = self.auth()?.candidate_tokens().into_iter().next().map(|(token, source)| match self {
+            ForgeType::GitHub => format!("{} via {}", github::TokenKind::detect(&token).description(), source),
+            _ => format!("via {}", source),
+        });
+
+        // The App only covers its owner's repos; the rest use the token
+        if *self == ForgeType::GitHub
+            && let Ok(Some(app)) = crate::config::load().map(|c| c.github.app)
+        {
+            let app = format!("GitHub App installation {} for {} repos", app.installation_id, app.owner);
+            return Some(match token {
+                Some(token) => format!("{}, else {}", app, token),
+                None => app,
+            });
+        }
+        token
+    }
+
+    /// Authenticate and store credentials, returning the username.
+    /// With a token (e.g. a personal API key from stdin) it is validated first;
+    /// without one the forge's interactive OAuth flow runs.
+    /// `account` stores it under a name (`--as work`) alongside the default one.
+    pub async fn login(&self, token: Option<String>, account: Option<&str>) -> Result<String> {
+        match self {
+            ForgeType::GitHub => github::login(token, account).await,
+            ForgeType::Linear => linear::login(token, account).await,
+            ForgeType::External(_) if account.is_some() => {
+                Err(error::err(ErrorKind::Usage, "--as isn't supported for adapters; they keep their own credentials"))
+            }
+            ForgeType::External(name) => external::login(name, token).await,
+        }
+    }
+
+    /// Check the credential for this forge (or a named account) against the
+    /// forge and describe it. Adapters keep their own credentials, so for them
+    /// this reports the login recorded at `isq auth login`.
+    pub async fn whoami(&self, account: Option<&str>) -> Result<WhoAmI> {
+        match self {
+            ForgeType::GitHub => github::whoami(account).await,
+            ForgeType::Linear => linear::whoami(account).await,
+            ForgeType::External(name) => {
+                let login = db::get_identity(&db::open()?, name)?.ok_or_else(|| {
+                    error::err(ErrorKind::AuthMissing, format!("No login recorded for {}. Run: isq auth login {}", name, name))
+                })?;
+                Ok(WhoAmI {
+                    forge: name.clone(),
+                    account: None,
+                    login,
+                    source: format!("{}{}", external::ADAPTER_PREFIX, name),
+                    token_kind: None,
+                    scopes: None,
+                    expires_at: None,
+                    verified_at: chrono::Utc::now().to_rfc3339(),
+                })
+            }
         }
     }
 
     /// Run the complete link flow for this forge
     pub async fn link(&self, repo_path: &str, args: &LinkArgs) -> Result<LinkResult> {
+        // Only adapters take options beyond the built-in ones
+        let is_external = matches!(self, ForgeType::External(_));
+        if let Some(key) = args.extra.keys().next().filter(|_| !is_external) {
+            return Err(anyhow!("Unknown option: {}", key));
+        }
+        if args.account.is_some() && is_external {
+            return Err(error::err(ErrorKind::Usage, "--as isn't supported for adapters; they keep their own credentials"));
+        }
         match self {
             ForgeType::GitHub => github::link(repo_path, args).await,
             ForgeType::Linear => linear::link(repo_path, args).await,
+            ForgeType::External(name) => external::link(name, repo_path, args).await,
         }
     }
 }
 
+/// Who a forge credential belongs to (`isq whoami`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhoAmI {
+    pub forge: String,
+    pub account: Option<String>,
+    pub login: String,
+    /// Where the credential came from: "gh CLI", "keyring", an env var, ...
+    pub source: String,
+    /// GitHub token kind, e.g. "classic PAT"
+    pub token_kind: Option<String>,
+    /// OAuth scopes, when the forge reports them (GitHub classic PATs and OAuth tokens)
+    pub scopes: Option<Vec<String>>,
+    /// When the token expires, if the forge or keyring knows
+    pub expires_at: Option<String>,
+    /// When this was checked against the forge (RFC 3339)
+    pub verified_at: String,
+}
+
 /// Request to create an issue
 pub struct CreateIssueRequest {
     pub title: String,
     pub body: Option<String>,
     pub labels: Vec<String>,
     pub goal_id: Option<String>,
+    /// Stored with the issue so a retried create can find it (see `new_client_key`)
+    pub client_key: Option<String>,
+    /// GitHub issue type name (forges without types use `[types]` labels instead)
+    pub issue_type: Option<String>,
+    /// Login (GitHub) or name or email (Linear) to assign on creation
+    pub assignee: Option<String>,
+}
+
+/// A random key for one create, kept in the queued op's payload. If the
+/// create's response is lost, replay looks the key up before creating again.
+pub fn new_client_key() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+/// The hidden body line that carries a client key on forges without an
+/// idempotency field of their own
+fn client_key_marker(key: &str) -> String {
+    format!("<!-- isq:client-key {} -->", key)
+}
+
+/// An issue or comment body with the client key marker appended
+pub fn body_with_client_key(body: Option<&str>, key: &str) -> String {
+    match body {
+        Some(body) if !body.is_empty() => format!("{}\n\n{}", body, client_key_marker(key)),
+        _ => client_key_marker(key),
+    }
+}
+
+/// Whether a body carries this client key
+pub fn has_client_key(body: &str, key: &str) -> bool {
+    body.contains(&client_key_marker(key))
+}
+
+/// A body without its client key marker, as shown and cached
+pub fn strip_client_key(body: &str) -> String {
+    match body.rfind("<!-- isq:client-key ") {
+        Some(start) if body[start..].trim_end().ends_with("-->") => body[..start].trim_end().to_string(),
+        _ => body.to_string(),
+    }
 }
 
 /// Goal state (normalized across forges)
@@ -297,6 +758,15 @@ impl GoalState {
     }
 }
 
+/// A label as defined on the forge
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelDef {
+    pub name: String,
+    /// Hex color without #, e.g., "d73a4a"
+    pub color: Option<String>,
+    pub description: Option<String>,
+}
+
 /// A time-bound container for issues (GitHub: Milestone, Linear: Project)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Goal {
@@ -316,7 +786,62 @@ pub struct Goal {
     pub html_url: Option<String>,
 }
 
-/// Request to create a goal
+/// A group of goals rolled up together (Linear: Initiative; GitHub: local only)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Objective {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub target_date: Option<String>,
+    pub state: GoalState,
+    /// IDs of the goals under this objective
+    pub goal_ids: Vec<String>,
+    pub updated_at: String,
+    pub html_url: Option<String>,
+}
+
+/// A fixed-length planning period issues are scheduled into (Linear cycles)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cycle {
+    pub number: u32,
+    pub name: Option<String>,
+    /// RFC 3339
+    pub starts_at: String,
+    /// RFC 3339
+    pub ends_at: String,
+}
+
+/// Roll-up of an objective's goals
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ObjectiveProgress {
+    pub goals: usize,
+    pub goals_closed: usize,
+    /// Open issues across goals that report counts
+    pub open_issues: u64,
+    /// Closed issues across goals that report counts
+    pub closed_issues: u64,
+    /// Mean goal progress (0.0 to 1.0)
+    pub progress: f64,
+}
+
+impl ObjectiveProgress {
+    pub fn from_goals(goals: &[Goal]) -> Self {
+        let progress = if goals.is_empty() {
+            0.0
+        } else {
+            goals.iter().map(|g| g.progress).sum::<f64>() / goals.len() as f64
+        };
+        ObjectiveProgress {
+            goals: goals.len(),
+            goals_closed: goals.iter().filter(|g| g.state == GoalState::Closed).count(),
+            open_issues: goals.iter().filter_map(|g| g.open_count).sum(),
+            closed_issues: goals.iter().filter_map(|g| g.closed_count).sum(),
+            progress,
+        }
+    }
+}
+
+/// Request to create a goal (or an objective)
 pub struct CreateGoalRequest {
     pub name: String,
     pub description: Option<String>,
@@ -334,6 +859,68 @@ pub struct RateLimitInfo {
     pub reset_at: i64,
 }
 
+/// Query-complexity budget for forges that rate limit by cost (Linear)
+///
+/// Refreshed from response headers after every query and persisted between
+/// daemon runs, so each new client starts from the last known budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComplexityBudget {
+    /// Complexity points allowed per window
+    pub limit: u32,
+    /// Points remaining in the current window
+    pub remaining: u32,
+    /// Unix timestamp when the window resets
+    pub reset_at: i64,
+    /// Complexity of the most recent query
+    pub last_cost: u32,
+}
+
+impl ComplexityBudget {
+    /// Below this share of the budget, page sizes shrink
+    const SHRINK_BELOW: f64 = 0.5;
+    /// Below this share of the budget, comment sync is deferred
+    const LOW_BELOW: f64 = 0.2;
+    /// Smallest page size we shrink to
+    const MIN_PAGE_SIZE: u32 = 25;
+
+    /// Points remaining at `now` (the full limit once the window has reset)
+    pub fn remaining_at(&self, now: i64) -> u32 {
+        if now >= self.reset_at { self.limit } else { self.remaining }
+    }
+
+    fn fraction_at(&self, now: i64) -> f64 {
+        if self.limit == 0 {
+            return 1.0;
+        }
+        self.remaining_at(now) as f64 / self.limit as f64
+    }
+
+    /// Whether the budget is low enough to skip non-essential queries
+    pub fn is_low(&self, now: i64) -> bool {
+        self.fraction_at(now) < Self::LOW_BELOW
+    }
+
+    /// Page size to request: `max` while the budget is healthy, shrinking in
+    /// proportion once it drops below half
+    pub fn page_size(&self, max: u32, now: i64) -> u32 {
+        let fraction = self.fraction_at(now);
+        if fraction >= Self::SHRINK_BELOW {
+            return max;
+        }
+        let scaled = (max as f64 * fraction / Self::SHRINK_BELOW) as u32;
+        scaled.clamp(Self::MIN_PAGE_SIZE.min(max), max)
+    }
+
+    /// Deduct a query's cost when the response didn't report the remaining budget
+    pub fn charge(&mut self, cost: u32, now: i64) {
+        if now >= self.reset_at {
+            return;
+        }
+        self.remaining = self.remaining.saturating_sub(cost);
+        self.last_cost = cost;
+    }
+}
+
 /// Abstraction over GitHub/GitLab/Forgejo APIs
 ///
 /// CLI code should use this trait, not forge-specific implementations directly.
@@ -346,15 +933,37 @@ pub trait Forge: Send + Sync {
     /// Create a new issue
     async fn create_issue(&self, repo: &Repo, req: CreateIssueRequest) -> Result<Issue>;
 
+    /// The issue an earlier create with this client key made, if any.
+    /// None also when the forge can't tell.
+    async fn find_created_issue(&self, _repo: &Repo, _client_key: &str) -> Result<Option<Issue>> {
+        Ok(None)
+    }
+
     /// Add a comment to an issue
     async fn create_comment(&self, repo: &Repo, issue_number: u64, body: &str) -> Result<()>;
 
+    /// Add a comment carrying a client key, so a replay whose response was
+    /// lost can find it (`find_posted_comment`). Forges that can't store a
+    /// key post it plain.
+    async fn create_keyed_comment(&self, repo: &Repo, issue_number: u64, body: &str, _client_key: &str) -> Result<()> {
+        self.create_comment(repo, issue_number, body).await
+    }
+
+    /// Whether a comment made with this client key exists. False also when
+    /// the forge can't tell.
+    async fn find_posted_comment(&self, _repo: &Repo, _issue_number: u64, _client_key: &str) -> Result<bool> {
+        Ok(false)
+    }
+
     /// Close an issue
     async fn close_issue(&self, repo: &Repo, issue_number: u64) -> Result<()>;
 
     /// Reopen an issue
     async fn reopen_issue(&self, repo: &Repo, issue_number: u64) -> Result<()>;
 
+    /// Replace an issue's body (Linear: description)
+    async fn update_issue_body(&self, repo: &Repo, issue_number: u64, body: &str) -> Result<()>;
+
     /// Add a label to an issue
     async fn add_label(&self, repo: &Repo, issue_number: u64, label: &str) -> Result<()>;
 
@@ -364,9 +973,36 @@ pub trait Forge: Send + Sync {
     /// Assign a user to an issue
     async fn assign_issue(&self, repo: &Repo, issue_number: u64, assignee: &str) -> Result<()>;
 
+    /// List the labels defined for a repo (Linear: the team's labels)
+    async fn list_labels(&self, repo: &Repo) -> Result<Vec<LabelDef>>;
+
+    /// Create a label
+    async fn create_label(&self, repo: &Repo, label: &LabelDef) -> Result<()>;
+
+    /// Rename, recolor or re-describe the label currently named `name`
+    async fn update_label(&self, repo: &Repo, name: &str, label: &LabelDef) -> Result<()>;
+
     /// List all comments for a repo (batch operation for sync)
     async fn list_all_comments(&self, repo: &Repo) -> Result<Vec<db::Comment>>;
 
+    /// Fetch a single issue. The default lists every issue, so forges that
+    /// can fetch one directly should override it.
+    async fn get_issue(&self, repo: &Repo, issue_number: u64) -> Result<Issue> {
+        self.list_issues(repo)
+            .await?
+            .into_iter()
+            .find(|i| i.number == issue_number)
+            .ok_or_else(|| error::err(ErrorKind::NotFound, format!("Issue #{} not found", issue_number)))
+    }
+
+    /// List one issue's comments, oldest first. The default lists every
+    /// comment in the repo.
+    async fn list_issue_comments(&self, repo: &Repo, issue_number: u64) -> Result<Vec<db::Comment>> {
+        let mut comments = self.list_all_comments(repo).await?;
+        comments.retain(|c| c.issue_number == issue_number);
+        Ok(comments)
+    }
+
     /// List all goals (GitHub: milestones, Linear: projects)
     async fn list_goals(&self, repo: &Repo) -> Result<Vec<Goal>>;
 
@@ -376,38 +1012,103 @@ pub trait Forge: Send + Sync {
     /// Close a goal
     async fn close_goal(&self, repo: &Repo, goal_id: &str) -> Result<()>;
 
+    /// Reopen a closed goal
+    async fn reopen_goal(&self, repo: &Repo, goal_id: &str) -> Result<()>;
+
     /// Assign an issue to a goal
     async fn assign_to_goal(&self, repo: &Repo, issue_number: u64, goal_id: &str) -> Result<()>;
 
+    /// Subscribe to an issue's notifications, or unsubscribe to mute it
+    async fn set_subscribed(&self, repo: &Repo, issue_number: u64, subscribed: bool) -> Result<()>;
+
+    /// List objectives (Linear: initiatives). None if the forge has no
+    /// equivalent, in which case objectives only live in the local cache.
+    async fn list_objectives(&self, _repo: &Repo) -> Result<Option<Vec<Objective>>> {
+        Ok(None)
+    }
+
+    /// Create an objective (None if the forge has no equivalent)
+    async fn create_objective(&self, _repo: &Repo, _req: CreateGoalRequest) -> Result<Option<Objective>> {
+        Ok(None)
+    }
+
+    /// Put a goal under an objective (no-op if the forge has no equivalent)
+    async fn link_goal_to_objective(&self, _repo: &Repo, _objective_id: &str, _goal_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    /// List planning cycles (Linear: the team's cycles). None if the forge
+    /// has no equivalent.
+    async fn list_cycles(&self, _repo: &Repo) -> Result<Option<Vec<Cycle>>> {
+        Ok(None)
+    }
+
+    /// Issues matching a search query across repos, each with the repo it's
+    /// in ("owner/name"); for search workspaces (see workspace.rs)
+    async fn search_issues(&self, _query: &str) -> Result<Vec<(String, Issue)>> {
+        Err(error::err(ErrorKind::Usage, "This forge can't search issues across repos"))
+    }
+
     /// Get rate limit status (returns None if forge doesn't have rate limits)
     async fn get_rate_limit(&self) -> Result<Option<RateLimitInfo>>;
+
+    /// Get the authenticated user's username (GitHub: login, Linear: user name)
+    async fn get_user(&self) -> Result<String>;
+
+    /// Query-complexity budget as of the last response (None if the forge doesn't report one)
+    fn complexity_budget(&self) -> Option<ComplexityBudget> {
+        None
+    }
 }
 
 /// Get the forge for a specific repo path, looking up the link in the database.
 ///
 /// Returns an error if the repo is not linked to a forge.
 pub fn get_forge_for_repo(repo_path: &str) -> Result<(Box<dyn Forge>, db::RepoLink)> {
-    let conn = db::open()?;
-    let link = db::get_repo_link(&conn, repo_path)?
+    get_forge_in(&db::open()?, repo_path)
+}
+
+/// Like `get_forge_for_repo`, looking the link up in a given cache (e.g. a
+/// per-repo one that isn't the current directory's)
+pub fn get_forge_in(conn: &rusqlite::Connection, repo_path: &str) -> Result<(Box<dyn Forge>, db::RepoLink)> {
+    let link = db::get_repo_link(conn, repo_path)?
         .ok_or_else(not_linked_error)?;
 
-    let forge_type = ForgeType::from_str(&link.forge_type)
-        .ok_or_else(|| anyhow!("Unknown forge type: {}", link.forge_type))?;
+    let forge_type = ForgeType::from_str(&link.forge_type).ok_or_else(|| {
+        anyhow!(
+            "Unknown forge type: {} (no {}{} on PATH)",
+            link.forge_type,
+            external::ADAPTER_PREFIX,
+            link.forge_type
+        )
+    })?;
 
+    let account = link.account.as_deref();
     let forge: Box<dyn Forge> = match forge_type {
         ForgeType::GitHub => {
-            let token = github::AUTH.get_token()?;
-            Box::new(GitHubClient::new(token))
-        }
-        ForgeType::Linear => {
-            let token = linear::AUTH.get_token()?;
-            Box::new(LinearClient::new(token))
+            let owner = link.forge_repo.split_once('/').map_or(link.forge_repo.as_str(), |(owner, _)| owner);
+            Box::new(github::client_from_config(account, owner)?)
         }
+        // A broken team config shouldn't block every write
+        ForgeType::Linear => Box::new(
+            linear_client(conn, account)?.with_states(team_config::load(repo_path).unwrap_or_default().states),
+        ),
+        ForgeType::External(name) => Box::new(external::ExternalClient::new(&name)?),
     };
 
     Ok((forge, link))
 }
 
+/// Linear client for the stored credentials (of `account`, if named), starting
+/// from the last known complexity budget
+pub fn linear_client(conn: &rusqlite::Connection, account: Option<&str>) -> Result<LinearClient> {
+    let client = LinearClient::new(linear::AUTH.get_account_token(account)?).for_account(account);
+    if let Some(budget) = db::get_complexity_budget(conn, ForgeType::Linear.as_str())? {
+        client.seed_complexity_budget(budget);
+    }
+    Ok(client)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -453,6 +1154,7 @@ mod tests {
         cli_command: None,
         display_name: "Test",
         link_command: "isq link test",
+        login_command: "isq auth login test --with-token",
     };
 
     #[test]
@@ -496,6 +1198,91 @@ mod tests {
         }
     }
 
+    #[test]
+    #[serial]
+    fn test_candidate_tokens_includes_env_var() {
+        let _guard = EnvGuard::set("_ISQ_TEST_TOKEN", "  test_token_123\n");
+
+        let tokens = TEST_AUTH.candidate_tokens();
+        assert_eq!(tokens.last(), Some(&("test_token_123".to_string(), "_ISQ_TEST_TOKEN")));
+    }
+
+    #[test]
+    fn test_objective_progress() {
+        let goal = |state, progress, counts: Option<(u64, u64)>| Goal {
+            id: String::new(),
+            name: String::new(),
+            description: None,
+            target_date: None,
+            state,
+            progress,
+            open_count: counts.map(|c| c.0),
+            closed_count: counts.map(|c| c.1),
+            created_at: String::new(),
+            updated_at: String::new(),
+            html_url: None,
+        };
+
+        let rollup = ObjectiveProgress::from_goals(&[
+            goal(GoalState::Closed, 1.0, Some((0, 4))),
+            goal(GoalState::Open, 0.5, Some((3, 3))),
+            goal(GoalState::Open, 0.0, None),
+        ]);
+        assert_eq!(rollup.goals, 3);
+        assert_eq!(rollup.goals_closed, 1);
+        assert_eq!((rollup.open_issues, rollup.closed_issues), (3, 7));
+        assert!((rollup.progress - 0.5).abs() < 1e-9);
+
+        assert_eq!(ObjectiveProgress::from_goals(&[]).progress, 0.0);
+    }
+
+    #[test]
+    fn test_client_key_marker() {
+        let key = new_client_key();
+        assert_eq!(key.len(), 32);
+
+        let body = body_with_client_key(Some("Steps to reproduce"), &key);
+        assert!(has_client_key(&body, &key));
+        assert!(!has_client_key(&body, &new_client_key()));
+        assert_eq!(strip_client_key(&body), "Steps to reproduce");
+        assert_eq!(strip_client_key(&body_with_client_key(None, &key)), "");
+        assert_eq!(strip_client_key("No marker"), "No marker");
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("api-*", "api-gateway"));
+        assert!(glob_match("api-*", "api-"));
+        assert!(!glob_match("api-*", "web-api"));
+        assert!(glob_match("*-svc", "billing-svc"));
+        assert!(glob_match("a*b*c", "axxbyyc"));
+        assert!(!glob_match("a*b*c", "axxbyy"));
+        assert!(glob_match("web?", "web2"));
+        assert!(glob_match("*", "anything"));
+    }
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!(parse_selection("1,3,5-7", 8), Ok(vec![0, 2, 4, 5, 6]));
+        assert_eq!(parse_selection(" all \n", 3), Ok(vec![0, 1, 2]));
+        assert_eq!(parse_selection("2,2,1", 3), Ok(vec![0, 1]));
+        assert!(parse_selection("4", 3).is_err());
+        assert!(parse_selection("x", 3).is_err());
+        assert!(parse_selection("", 3).is_err());
+        assert!(parse_selection("3-2", 3).is_err());
+        assert_eq!(parse_selection("2-2", 3), Ok(vec![1]));
+    }
+
+    #[test]
+    fn test_github_token_kind_detection() {
+        use github::TokenKind;
+        assert_eq!(TokenKind::detect("github_pat_11ABC"), TokenKind::FineGrainedPat);
+        assert_eq!(TokenKind::detect("ghp_abc"), TokenKind::ClassicPat);
+        assert_eq!(TokenKind::detect("gho_abc"), TokenKind::OAuth);
+        assert_eq!(TokenKind::detect("ghs_abc"), TokenKind::Installation);
+        assert_eq!(TokenKind::detect("something"), TokenKind::Unknown);
+    }
+
     #[test]
     fn test_github_auth_config() {
         // Verify GitHub AUTH is properly configured
@@ -535,4 +1322,119 @@ mod tests {
         assert!(result.is_ok());
         assert!(!result.unwrap().is_empty());
     }
+
+    #[test]
+    fn test_missing_scope_errors() {
+        let not_found = reqwest::StatusCode::NOT_FOUND;
+        let scopes = vec!["read:user".to_string()];
+        let err = github::explain_api_error(not_found, Some(&scopes), "Not Found");
+        assert_eq!(error::classify(&err), ErrorKind::AuthMissing);
+        assert!(err.to_string().contains("missing the `repo` scope (it has: read:user)"));
+
+        // A token with `repo` (or one without scopes) gets the plain error
+        let scopes = vec!["repo".to_string()];
+        let err = github::explain_api_error(not_found, Some(&scopes), "Not Found");
+        assert_eq!(err.to_string(), "GitHub API error 404 Not Found: Not Found");
+        assert_eq!(error::classify(&github::explain_api_error(not_found, None, "")), ErrorKind::NotFound);
+
+        assert_eq!(linear::missing_scope("Invalid scope: `write` required").as_deref(), Some("write"));
+        assert_eq!(linear::missing_scope("Invalid scope: issues:create required").as_deref(), Some("issues:create"));
+        assert_eq!(linear::missing_scope("Entity not found"), None);
+        assert!(linear::scope_error("write").to_string().contains("missing the `write` scope"));
+    }
+
+    #[test]
+    fn test_find_team_by_key_or_name() {
+        let team = |key: &str, name: &str| linear::LinearTeam {
+            id: format!("id-{}", key),
+            name: name.to_string(),
+            key: key.to_string(),
+        };
+        let teams = vec![team("ENG", "Engineering"), team("OPS", "Operations")];
+
+        assert_eq!(find_team(&teams, "ops").unwrap().key, "OPS");
+        assert_eq!(find_team(&teams, "engineering").unwrap().forge_repo(), "ENG/id-ENG");
+        let err = find_team(&teams, "design").unwrap_err();
+        assert!(err.to_string().contains("Engineering (ENG)"));
+    }
+
+    #[test]
+    fn test_complexity_budget_shrinks_pages_when_low() {
+        let budget = |remaining| ComplexityBudget {
+            limit: 1000,
+            remaining,
+            reset_at: 100,
+            last_cost: 0,
+        };
+
+        assert_eq!(budget(800).page_size(250, 0), 250);
+        assert_eq!(budget(250).page_size(250, 0), 125);
+        assert_eq!(budget(10).page_size(250, 0), 25);
+        assert!(!budget(250).is_low(0));
+        assert!(budget(150).is_low(0));
+
+        // Once the window resets the full budget is available again
+        assert_eq!(budget(10).page_size(250, 100), 250);
+        assert!(!budget(10).is_low(100));
+    }
+
+    #[test]
+    fn test_complexity_budget_charge() {
+        let mut budget = ComplexityBudget {
+            limit: 1000,
+            remaining: 300,
+            reset_at: 100,
+            last_cost: 0,
+        };
+        budget.charge(120, 0);
+        assert_eq!(budget.remaining, 180);
+        assert_eq!(budget.last_cost, 120);
+        budget.charge(500, 0);
+        assert_eq!(budget.remaining, 0);
+    }
+
+    #[test]
+    fn test_parse_issue_id() {
+        let number = |s| parse_issue_id(s).map(|id| id.number);
+        assert_eq!(number("123"), Ok(123));
+        assert_eq!(number("#7"), Ok(7));
+        assert_eq!(parse_issue_id("ENG-42"), Ok(IssueId { key: Some("ENG".to_string()), number: 42 }));
+        assert_eq!(split_identifier("ENG2-42"), Some(("ENG2", 42)));
+        assert!(parse_issue_id("42-ENG").is_err());
+        assert!(parse_issue_id("-42").is_err());
+        assert!(parse_issue_id("abc").is_err());
+    }
+
+    fn link(forge_type: &str, forge_repo: &str) -> db::RepoLink {
+        db::RepoLink {
+            forge_type: forge_type.to_string(),
+            forge_repo: forge_repo.to_string(),
+            display_name: None,
+            remote_url: None,
+            root_commit: None,
+            account: None,
+        }
+    }
+
+    #[test]
+    fn test_identifier_must_match_linked_team() {
+        let linear = link("linear", "ENG/team-1");
+        let id = |s| parse_issue_id(s).unwrap();
+        assert_eq!(id("ENG-42").number_in(&linear, &[]).unwrap(), 42);
+        assert_eq!(id("eng-42").number_in(&linear, &[]).unwrap(), 42);
+        assert_eq!(id("42").number_in(&linear, &[]).unwrap(), 42);
+        // Issues moved in from another team keep their old identifiers
+        assert_eq!(id("OLD-7").number_in(&linear, &["OLD".to_string()]).unwrap(), 7);
+
+        let err = id("OPS-42").number_in(&linear, &["ENG".to_string()]).unwrap_err();
+        assert_eq!(error::classify(&err), ErrorKind::NotFound);
+        assert!(err.to_string().contains("OPS-42"), "{}", err);
+
+        let err = id("ABC-123").number_in(&link("github", "acme/api"), &[]).unwrap_err();
+        assert_eq!(error::classify(&err), ErrorKind::Usage);
+        assert_eq!(id("#123").number_in(&link("github", "acme/api"), &[]).unwrap(), 123);
+
+        let err = id("ABC-1").number_in(&link("jira", "acme/api"), &[]).unwrap_err();
+        assert_eq!(error::classify(&err), ErrorKind::NotFound);
+    }
 }
@@ -0,0 +1,180 @@
+//! User hooks: shell commands run on local events
+//!
+//! Configured in `config.toml`; each command runs via `sh -c` with a JSON
+//! payload on stdin and `ISQ_EVENT` set to the event name.
+//!
+//! ```toml
+//! [hooks]
+//! on_issue_created = "./notify.sh"
+//! on_sync_error = "terminal-notifier -message 'isq sync failed'"
+//! on_assigned_to_me = "~/bin/post-to-slack"
+//! on_goal_at_risk = "notify-send 'isq goal at risk'"
+//! on_op_failed = "notify-send 'isq gave up on a queued write'"
+//! ```
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use serde::Deserialize;
+
+use crate::forges::Issue;
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// A new issue appeared (created here or found by the daemon)
+    pub on_issue_created: Option<String>,
+    /// The daemon failed to sync a repo
+    pub on_sync_error: Option<String>,
+    /// You were added as an assignee
+    pub on_assigned_to_me: Option<String>,
+    /// A goal's target date is near and it's behind (see reminders.rs)
+    pub on_goal_at_risk: Option<String>,
+    /// A queued write passed `[queue] max_age_days` without syncing
+    pub on_op_failed: Option<String>,
+}
+
+/// A local event hooks can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    IssueCreated,
+    SyncError,
+    AssignedToMe,
+    GoalAtRisk,
+    OpFailed,
+}
+
+impl HookEvent {
+    pub fn name(self) -> &'static str {
+        match self {
+            HookEvent::IssueCreated => "issue_created",
+            HookEvent::SyncError => "sync_error",
+            HookEvent::AssignedToMe => "assigned_to_me",
+            HookEvent::GoalAtRisk => "goal_at_risk",
+            HookEvent::OpFailed => "op_failed",
+        }
+    }
+
+    fn command(self, config: &HooksConfig) -> Option<&str> {
+        match self {
+            HookEvent::IssueCreated => config.on_issue_created.as_deref(),
+            HookEvent::SyncError => config.on_sync_error.as_deref(),
+            HookEvent::AssignedToMe => config.on_assigned_to_me.as_deref(),
+            HookEvent::GoalAtRisk => config.on_goal_at_risk.as_deref(),
+            HookEvent::OpFailed => config.on_op_failed.as_deref(),
+        }
+    }
+}
+
+/// Run the hook for `event`, if one is configured.
+///
+/// The command gets `{"event": ..., ...payload}` on stdin. It runs in the
+/// background; failures are logged, never returned, so a broken hook can't
+/// stop a sync or a write.
+pub fn fire(config: &HooksConfig, event: HookEvent, payload: serde_json::Value) {
+    let Some(command) = event.command(config) else {
+        return;
+    };
+
+    let mut body = serde_json::json!({ "event": event.name() });
+    if let (Some(body), serde_json::Value::Object(fields)) = (body.as_object_mut(), payload) {
+        body.extend(fields);
+    }
+
+    let child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("ISQ_EVENT", event.name())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn();
+
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            eprintln!("[hooks] Failed to run {} hook: {}", event.name(), e);
+            return;
+        }
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that ignores stdin closes the pipe early; that's fine
+        let _ = stdin.write_all(body.to_string().as_bytes());
+    }
+
+    let name = event.name();
+    std::thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => eprintln!("[hooks] {} hook exited with {}", name, status),
+        Err(e) => eprintln!("[hooks] {} hook failed: {}", name, e),
+        _ => {}
+    });
+}
+
+/// Events implied by a sync replacing `before` with `after`.
+///
+/// Nothing is reported on a repo's first sync (`before` empty), otherwise
+/// every existing issue would look new.
+pub fn sync_events<'a>(before: &[Issue], after: &'a [Issue], me: Option<&str>) -> Vec<(HookEvent, &'a Issue)> {
+    if before.is_empty() {
+        return Vec::new();
+    }
+    let previous: HashMap<u64, &Issue> = before.iter().map(|i| (i.number, i)).collect();
+
+    let mut events = Vec::new();
+    for issue in after {
+        let old = previous.get(&issue.number);
+        if old.is_none() {
+            events.push((HookEvent::IssueCreated, issue));
+        }
+        if let Some(me) = me {
+            let assigned_now = issue.assignees.iter().any(|a| a == me);
+            let assigned_before = old.is_some_and(|o| o.assignees.iter().any(|a| a == me));
+            if assigned_now && !assigned_before {
+                events.push((HookEvent::AssignedToMe, issue));
+            }
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(number: u64, assignees: &[&str]) -> Issue {
+        Issue {
+            number,
+            title: format!("Issue {}", number),
+            state: "open".to_string(),
+            author: "someone".to_string(),
+            assignees: assignees.iter().map(|a| a.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sync_events() {
+        let before = vec![issue(1, &[]), issue(2, &["me"])];
+        let after = vec![issue(1, &["me"]), issue(2, &["me"]), issue(3, &[]), issue(4, &["me"])];
+
+        let events: Vec<_> = sync_events(&before, &after, Some("me"))
+            .into_iter()
+            .map(|(event, issue)| (event.name(), issue.number))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                ("assigned_to_me", 1),
+                ("issue_created", 3),
+                ("issue_created", 4),
+                ("assigned_to_me", 4),
+            ]
+        );
+
+        // Unknown identity: only new issues
+        assert_eq!(sync_events(&before, &after, None).len(), 2);
+        // First sync: nothing
+        assert!(sync_events(&[], &after, Some("me")).is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+//! Issue types: Bug, Feature, Task and so on (`--type`, the TYPE column)
+//!
+//! GitHub issue types (on plans that have them) come from the forge. Labels
+//! can stand for a type too, for forges or repos without them; an issue the
+//! forge gave a type keeps it.
+//!
+//! ```toml
+//! [types.labels]        # label = type (labels are case-insensitive)
+//! bug = "Bug"
+//! enhancement = "Feature"
+//! chore = "Task"
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::forges::Issue;
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct TypesConfig {
+    /// Label name to the type it stands for
+    pub labels: BTreeMap<String, String>,
+}
+
+impl TypesConfig {
+    /// The type a label stands for
+    fn type_for_label(&self, label: &str) -> Option<&str> {
+        self.labels
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(label))
+            .map(|(_, issue_type)| issue_type.as_str())
+    }
+
+    /// Labels that stand for a type (case-insensitive)
+    pub fn labels_for(&self, issue_type: &str) -> Vec<String> {
+        self.labels
+            .iter()
+            .filter(|(_, t)| t.eq_ignore_ascii_case(issue_type))
+            .map(|(label, _)| label.clone())
+            .collect()
+    }
+
+    /// Give issues without a forge type the type of their first mapped label
+    pub fn apply(&self, issues: &mut [Issue]) {
+        if self.labels.is_empty() {
+            return;
+        }
+        for issue in issues.iter_mut().filter(|i| i.issue_type.is_none()) {
+            issue.issue_type = issue.labels.iter().find_map(|l| self.type_for_label(&l.name)).map(String::from);
+        }
+    }
+}
+
+/// What a type looks like in tables: bugs, features and tasks have their
+/// own glyph, anything else the generic one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeKind {
+    Bug,
+    Feature,
+    Task,
+    Other,
+}
+
+pub fn kind(issue_type: &str) -> TypeKind {
+    match issue_type.to_lowercase().as_str() {
+        "bug" | "defect" => TypeKind::Bug,
+        "feature" | "enhancement" | "story" => TypeKind::Feature,
+        "task" | "chore" => TypeKind::Task,
+        _ => TypeKind::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forges::Label;
+
+    fn config() -> TypesConfig {
+        crate::config::parse("[types.labels]\nbug = \"Bug\"\nregression = \"Bug\"\nenhancement = \"Feature\"\n")
+            .unwrap()
+            .types
+    }
+
+    #[test]
+    fn test_labels_for() {
+        assert_eq!(config().labels_for("bug"), vec!["bug", "regression"]);
+        assert!(config().labels_for("Task").is_empty());
+    }
+
+    #[test]
+    fn test_apply() {
+        let issue = |labels: &[&str], issue_type: Option<&str>| Issue {
+            number: 1,
+            title: "Crash".to_string(),
+            state: "open".to_string(),
+            author: "alice".to_string(),
+            labels: labels.iter().map(|l| Label::name_only(l.to_string())).collect(),
+            issue_type: issue_type.map(String::from),
+            ..Default::default()
+        };
+        let mut issues = vec![issue(&["P1", "Regression"], None), issue(&["bug"], Some("Task")), issue(&["docs"], None)];
+        config().apply(&mut issues);
+
+        let types: Vec<_> = issues.iter().map(|i| i.issue_type.as_deref()).collect();
+        assert_eq!(types, vec![Some("Bug"), Some("Task"), None]);
+    }
+
+    #[test]
+    fn test_kind() {
+        assert_eq!(kind("Bug"), TypeKind::Bug);
+        assert_eq!(kind("enhancement"), TypeKind::Feature);
+        assert_eq!(kind("Epic"), TypeKind::Other);
+    }
+}
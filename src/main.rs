@@ -1,18 +1,52 @@
+mod cipher;
+mod config;
+mod context;
+mod create_rules;
 mod credentials;
 mod daemon;
 mod db;
+mod digest;
 mod display;
+mod drafts;
+mod error;
+mod export;
 mod forges;
+mod hooks;
+mod issue_types;
+mod metrics;
+mod network;
+mod notes;
+mod picker;
+mod progress;
+mod references;
+mod relevance;
+mod reminders;
+mod report;
 mod repo;
 mod service;
-
-use std::time::Instant;
+mod similar;
+mod stats;
+mod storage;
+mod suggest;
+mod taxonomy;
+mod team_config;
+mod tasks;
+mod template;
+mod timefmt;
+mod wip;
+mod workspace;
+
+use std::collections::HashSet;
+use std::io::{IsTerminal, Read};
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
+use futures::StreamExt;
 use serde::Serialize;
 
-use crate::forges::{get_forge_for_repo, not_linked_error, CreateGoalRequest, CreateIssueRequest, ForgeType, Issue, LinkArgs, ALL_FORGE_TYPES};
+use crate::error::ErrorKind;
+use crate::forges::{get_forge_for_repo, not_linked_error, CreateGoalRequest, CreateIssueRequest, ForgeType, GoalState, Issue, LinkArgs, ObjectiveProgress, ALL_FORGE_TYPES};
 
 /// JSON response for write operations
 #[derive(Serialize)]
@@ -25,8 +59,36 @@ struct WriteResult {
     elapsed_ms: u64,
 }
 
-/// Check if an error is a network/connectivity error (offline)
+/// Issue JSON with the writes still queued for it (the cached fields don't reflect them yet)
+#[derive(Serialize)]
+struct IssueJson<'a> {
+    #[serde(flatten)]
+    issue: &'a Issue,
+    pending_ops: Vec<serde_json::Value>,
+}
+
+impl<'a> IssueJson<'a> {
+    fn new(issue: &'a Issue, pending: &std::collections::HashMap<u64, Vec<db::PendingOp>>) -> Self {
+        let pending_ops = pending
+            .get(&issue.number)
+            .map(|ops| ops.iter().map(|op| op.to_json()).collect())
+            .unwrap_or_default();
+        Self { issue, pending_ops }
+    }
+}
+
+/// Signal that a write was queued for the daemon (exit code, not an error message)
+fn queued_error() -> anyhow::Error {
+    error::err(ErrorKind::OfflineQueued, "Write queued for sync (offline)")
+}
+
+/// Check if an error is a network/connectivity error (offline), or a write deferred with `--queue`
 fn is_offline_error(err: &anyhow::Error) -> bool {
+    // A request that timed out may or may not have landed; the queued
+    // replay checks before sending it again
+    if matches!(error::classify(err), ErrorKind::OfflineQueued | ErrorKind::Timeout) {
+        return true;
+    }
     let err_str = err.to_string().to_lowercase();
     err_str.contains("connection refused")
         || err_str.contains("network is unreachable")
@@ -37,6 +99,108 @@ fn is_offline_error(err: &anyhow::Error) -> bool {
         || err_str.contains("could not resolve")
 }
 
+/// Run a forge write, or with `--queue` skip it and fail as if offline so the
+/// caller takes its queueing path
+async fn unless_queued<T>(queue: bool, write: impl std::future::Future<Output = Result<T>>) -> Result<T> {
+    if queue {
+        return Err(error::err(ErrorKind::OfflineQueued, "Write deferred to the daemon (--queue)"));
+    }
+    network::write(write).await
+}
+
+/// Before a write, warn when the forge's stored rate limit or complexity
+/// budget is nearly spent (not with `--queue`, which spends none of it)
+fn warn_low_budget(conn: &rusqlite::Connection, forge_type: &str, queue: bool) -> Result<()> {
+    if queue {
+        return Ok(());
+    }
+    let now = daemon::unix_now();
+    let low = match db::get_rate_limit_state(conn, forge_type)? {
+        Some(state) if state.is_low(now) => state.remaining.zip(state.reset_at).map(|(n, at)| (n, "requests", at)),
+        _ => None,
+    };
+    let low = low.or(match db::get_complexity_budget(conn, forge_type)? {
+        Some(budget) if budget.is_low(now) => Some((budget.remaining, "complexity points", budget.reset_at)),
+        _ => None,
+    });
+    if let Some((remaining, unit, reset_at)) = low {
+        eprintln!(
+            "warning: {} {} remaining; resets at {} (use --queue to let the daemon write it later)",
+            remaining,
+            unit,
+            timefmt::local_time(reset_at)
+        );
+    }
+    Ok(())
+}
+
+/// Parse a human duration like "90s", "30m", "2h" or "1d"
+fn parse_duration(s: &str) -> std::result::Result<Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (num, unit) = s.split_at(split);
+    let n: u64 = num
+        .parse()
+        .map_err(|_| format!("invalid duration '{}' (expected e.g. 30m, 2h, 1d)", s))?;
+    let scale = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return Err(format!("invalid duration unit '{}' (use s, m, h or d)", unit)),
+    };
+    let secs = n
+        .checked_mul(scale)
+        .ok_or_else(|| format!("duration '{}' is too large", s))?;
+    Ok(Duration::from_secs(secs))
+}
+
+/// The number an issue ID argument refers to in this repo's link; an
+/// identifier from another team (or on a GitHub link) is an error
+fn issue_number(id: &forges::IssueId) -> Result<u64> {
+    if id.key.is_none() {
+        return Ok(id.number);
+    }
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+    id.number_in(&link, &db::known_team_keys(&conn, &link.forge_repo)?)
+}
+
+/// Validate a YYYY-MM-DD date
+fn parse_date(s: &str) -> std::result::Result<String, String> {
+    chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .map_err(|_| format!("invalid date '{}' (expected YYYY-MM-DD)", s))
+}
+
+/// How many comments `issue show` prints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CommentLimit {
+    Last(usize),
+    All,
+}
+
+impl CommentLimit {
+    fn max(self) -> Option<usize> {
+        match self {
+            CommentLimit::Last(n) => Some(n),
+            CommentLimit::All => None,
+        }
+    }
+}
+
+/// Parse `--comments`: a count or "all"
+fn parse_comment_limit(s: &str) -> std::result::Result<CommentLimit, String> {
+    if s.eq_ignore_ascii_case("all") {
+        return Ok(CommentLimit::All);
+    }
+    s.parse()
+        .map(CommentLimit::Last)
+        .map_err(|_| format!("invalid comment count '{}' (expected a number or 'all')", s))
+}
+
 #[derive(Parser)]
 #[command(name = "isq")]
 #[command(about = "Instant issue tracking. Offline-first. AI-agent native.")]
@@ -44,24 +208,79 @@ fn is_offline_error(err: &anyhow::Error) -> bool {
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Human-readable output even when ISQ_OUTPUT=json makes JSON the default
+    #[arg(long, global = true)]
+    plain: bool,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Link this repo to an issue tracker
     Link {
-        /// Forge name
+        /// Forge name (github, linear, or NAME for an isq-forge-NAME adapter on PATH)
         forge: Option<String>,
         /// Forge-specific options (e.g., -o team=Engineering)
         #[arg(short = 'o', long = "opt")]
         opt: Vec<String>,
+        /// Link repos of a GitHub org or user instead of this repo
+        #[arg(long)]
+        org: Option<String>,
+        /// With --org: link all repos without prompting
+        #[arg(long, requires = "org")]
+        all: bool,
+        /// With --org: link repos whose name matches a glob (e.g. 'api-*')
+        #[arg(long = "match", requires = "org")]
+        pattern: Option<String>,
+        /// With --org: link the issues matching a GitHub search (e.g.
+        /// 'assignee:@me') across its repos instead of whole repos
+        #[arg(long, requires = "org", conflicts_with_all = ["all", "pattern"])]
+        query: Option<String>,
+        /// Use a named credential (from `isq auth login <forge> --as NAME`)
+        #[arg(long = "as", value_name = "NAME")]
+        account: Option<String>,
+        /// Rebind the link of a moved checkout to this directory
+        #[arg(long, conflicts_with_all = ["forge", "org"])]
+        repair: bool,
+        /// Link as `[link]` in the repo's .isq/config.toml says
+        #[arg(long, conflicts_with_all = ["forge", "org", "repair"])]
+        from_config: bool,
     },
 
     /// Unlink this repo from its issue tracker
-    Unlink,
+    Unlink {
+        /// Unlink even if unsynced operations would be discarded
+        #[arg(long)]
+        force: bool,
 
-    /// Show status (auth, link, daemon)
-    Status,
+        /// Also delete the cached issues, comments and goals
+        #[arg(long)]
+        purge: bool,
+    },
+
+    /// Manage forge credentials
+    Auth {
+        #[command(subcommand)]
+        command: AuthCommands,
+    },
+
+    /// Show status (auth, link, issue counts, daemon)
+    Status {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show who you're authenticated as on each linked forge
+    Whoami {
+        /// Re-verify the credentials against the forges instead of using the cached answer
+        #[arg(long)]
+        refresh: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Issue operations
     Issue {
@@ -69,64 +288,306 @@ enum Commands {
         command: IssueCommands,
     },
 
+    /// Search cached comments, or reply quoting one
+    Comment {
+        #[command(subcommand)]
+        command: CommentCommands,
+    },
+
+    /// Full-text search over cached issues, comments and goals
+    Search {
+        /// Words to find (all of them, in any order; "decide" also matches "decided")
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// Only search these (repeatable; default: all three)
+        #[arg(long = "in", value_parser = ["issues", "comments", "goals"])]
+        scopes: Vec<String>,
+
+        /// Maximum number of results of each type
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Unsubmitted issue drafts, edited locally and created with `submit`
+    Draft {
+        #[command(subcommand)]
+        command: DraftCommands,
+    },
+
     /// Daemon operations
     Daemon {
         #[command(subcommand)]
         command: DaemonCommands,
     },
 
-    /// Sync issues from remote
-    Sync,
+    /// Replay queued writes, then sync issues from remote
+    Sync {
+        /// Fetch and report what would change, without writing to the cache
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Only fetch issues
+        #[arg(long, conflicts_with_all = ["dry_run", "comments_only", "goals_only", "issue"])]
+        issues_only: bool,
+
+        /// Only fetch comments
+        #[arg(long, conflicts_with_all = ["dry_run", "goals_only", "issue"])]
+        comments_only: bool,
+
+        /// Only fetch goals (and objectives)
+        #[arg(long, conflicts_with_all = ["dry_run", "issue"])]
+        goals_only: bool,
+
+        /// Refresh just this issue and its comments
+        #[arg(long, value_parser = forges::parse_issue_id, conflicts_with = "dry_run")]
+        issue: Option<forges::IssueId>,
+    },
+
+    /// Sync every watched repo whose cache is older than --max-age (for login scripts)
+    Warm {
+        /// Repos synced more recently than this are left alone
+        #[arg(long, default_value = "1h", value_parser = parse_duration)]
+        max_age: Duration,
+
+        /// How many repos to sync at once
+        #[arg(long, default_value = "4", value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: u16,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Goal operations (milestones/projects)
     Goal {
         #[command(subcommand)]
         command: GoalCommands,
     },
+
+    /// Objectives: goals rolled up together (Linear: initiatives)
+    Objective {
+        #[command(subcommand)]
+        command: ObjectiveCommands,
+    },
+
+    /// Keep the forge's labels in line with the configured taxonomy
+    Label {
+        #[command(subcommand)]
+        command: LabelCommands,
+    },
+
+    /// Reports over cached history
+    Report {
+        #[command(subcommand)]
+        command: ReportCommands,
+    },
+
+    /// Issue analytics from the cache: weekly activity, time to close, top labels and authors
+    Stats {
+        /// Number of weeks of activity to chart
+        #[arg(long, default_value_t = 12, value_parser = clap::value_parser!(u16).range(1..=104))]
+        weeks: u16,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Digest of issues opened and closed, goal progress and busy discussions across watched repos
+    Digest {
+        /// Cover last week (Monday to Monday, UTC) instead of the last few days
+        #[arg(long, conflicts_with = "days")]
+        week: bool,
+
+        /// Number of days up to now to cover
+        #[arg(long, default_value_t = 7, value_parser = clap::value_parser!(u16).range(1..=365))]
+        days: u16,
+
+        /// Write the markdown to a file instead of stdout
+        #[arg(long)]
+        out: Option<std::path::PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Export the cached issues, comments and goals as a static site
+    Export {
+        /// Output format
+        #[arg(long, default_value = "html", value_parser = ["html"])]
+        format: String,
+
+        /// Directory to write the site to
+        #[arg(long, default_value = "site")]
+        out: std::path::PathBuf,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Back up or restore all isq state (links, watch list, queue, cache)
+    Backup {
+        #[command(subcommand)]
+        command: BackupCommands,
+    },
+
+    /// Events the daemon left for this repo (writes it gave up on, goals at
+    /// risk); listing them marks them read
+    Inbox {
+        /// Include events already read
+        #[arg(long)]
+        all: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Writes queued for the daemon, and ones it gave up on
+    Queue {
+        #[command(subcommand)]
+        command: QueueCommands,
+    },
+
+    /// Encrypt or decrypt cached issue and comment bodies at rest
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommands,
+    },
+
+    /// List Linear teams or point this repo at another one
+    Team {
+        #[command(subcommand)]
+        command: TeamCommands,
+    },
+
+    /// Every linked checkout, from anywhere
+    Repo {
+        #[command(subcommand)]
+        command: RepoCommands,
+    },
+
+    /// Your in-progress issues across linked repos, checked against `[wip] limit`
+    Wip {
+        #[command(subcommand)]
+        command: Option<WipCommands>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
-enum IssueCommands {
-    /// List issues
+enum WipCommands {
+    /// Suggest the unstarted assigned issue to pick up next
+    Next {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum RepoCommands {
+    /// List linked repos with their last sync, queued writes and watch state
     List {
-        /// Filter by label
+        /// Output as JSON
         #[arg(long)]
-        label: Option<String>,
+        json: bool,
+    },
 
-        /// Filter by state (open, closed)
+    /// Unlink a checkout by path (e.g. one that was deleted)
+    Forget {
+        /// Path of the linked checkout, as shown by `isq repo list`
+        path: String,
+
+        /// Forget it even if unsynced operations would be discarded
         #[arg(long)]
-        state: Option<String>,
+        force: bool,
+
+        /// Also delete the cached issues, comments and goals
+        #[arg(long)]
+        purge: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CommentCommands {
+    /// Full-text search over cached comments ("where did we decide X")
+    Search {
+        /// Words to find (all of them, in any order; "decide" also matches "decided")
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// Only comments by this author
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Only comments on this issue (e.g. 123 or ABC-123)
+        #[arg(long, value_parser = forges::parse_issue_id)]
+        issue: Option<forges::IssueId>,
+
+        /// Maximum number of results
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
 
         /// Output as JSON
         #[arg(long)]
         json: bool,
     },
 
-    /// Show a single issue
-    Show {
-        /// Issue number
-        id: u64,
+    /// Reply to a comment: opens $EDITOR on a blockquote of it, then posts
+    Quote {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Comment ID (from `issue show --json`) or position (1 = first comment)
+        comment: String,
+
+        /// Reply text to post below the quote, without opening $EDITOR
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Queue for the daemon instead of writing now (saves rate limit budget)
+        #[arg(long)]
+        queue: bool,
 
         /// Output as JSON
         #[arg(long)]
         json: bool,
     },
+}
 
-    /// Create a new issue
-    Create {
-        /// Issue title
+#[derive(Subcommand)]
+enum DraftCommands {
+    /// Start a draft (opens $EDITOR unless --title is given)
+    New {
+        /// Draft title
         #[arg(long)]
-        title: String,
+        title: Option<String>,
 
-        /// Issue body
+        /// Draft body
         #[arg(long)]
         body: Option<String>,
 
-        /// Labels to add
+        /// Labels to add on submit
         #[arg(long)]
         label: Vec<String>,
 
-        /// Goal to assign the issue to
+        /// Goal to assign the issue to on submit
         #[arg(long)]
         goal: Option<String>,
 
@@ -135,62 +596,60 @@ enum IssueCommands {
         json: bool,
     },
 
-    /// Add a comment to an issue
-    Comment {
-        /// Issue number
-        id: u64,
-
-        /// Comment body
-        message: String,
+    /// Edit a draft (issue or comment) in $EDITOR
+    Edit {
+        /// Draft ID (from `isq draft list`)
+        id: i64,
 
         /// Output as JSON
         #[arg(long)]
         json: bool,
     },
 
-    /// Close an issue
-    Close {
-        /// Issue number
-        id: u64,
+    /// List this repo's drafts
+    List {
+        /// Comments kept from posts that failed, instead of issue drafts
+        #[arg(long)]
+        comments: bool,
 
         /// Output as JSON
         #[arg(long)]
         json: bool,
     },
 
-    /// Reopen an issue
-    Reopen {
-        /// Issue number
-        id: u64,
+    /// Create the issue or post the comment (or queue it offline) and delete the draft
+    Submit {
+        /// Draft ID (from `isq draft list`)
+        id: i64,
+
+        /// Submit even if open issues with similar titles exist
+        #[arg(long)]
+        force: bool,
 
         /// Output as JSON
         #[arg(long)]
         json: bool,
     },
+}
 
-    /// Manage labels on an issue
-    Label {
-        /// Issue number
-        id: u64,
-
-        /// Action: add or remove
-        action: String,
-
-        /// Label name
-        label: String,
+#[derive(Subcommand)]
+enum LabelCommands {
+    /// The repo's labels from the cache, with colors, descriptions and open issue counts
+    List {
+        /// Only the names, one per line (for shell completion)
+        #[arg(long, conflicts_with = "json")]
+        names: bool,
 
         /// Output as JSON
         #[arg(long)]
         json: bool,
     },
 
-    /// Assign a user to an issue
-    Assign {
-        /// Issue number
-        id: u64,
-
-        /// Username to assign
-        user: String,
+    /// Create, rename and recolor labels to match `[[labels.taxonomy]]` in config.toml
+    Sync {
+        /// Make the changes (default: only show them)
+        #[arg(long)]
+        apply: bool,
 
         /// Output as JSON
         #[arg(long)]
@@ -199,21 +658,47 @@ enum IssueCommands {
 }
 
 #[derive(Subcommand)]
-enum GoalCommands {
-    /// List goals
-    List {
-        /// Filter by state (open, closed, all)
-        #[arg(long, default_value = "open")]
-        state: String,
+enum ReportCommands {
+    /// Goal progress over time, one row per goal per day (recorded at each sync)
+    Goals {
+        /// First day to include (YYYY-MM-DD)
+        #[arg(long, value_parser = parse_date)]
+        since: Option<String>,
+
+        /// Only this goal (name or ID)
+        #[arg(long)]
+        goal: Option<String>,
+
+        /// Output format
+        #[arg(long, default_value = "csv", value_parser = ["csv", "json"])]
+        format: String,
+    },
+
+    /// Open issues by age (0-7d, 7-30d, 30-90d, 90d+) and label, and the ones with no response
+    Aging {
+        /// Only comments from this login count as a response (repeatable; default:
+        /// the `[list] maintainers`, else anyone but the author)
+        #[arg(long = "maintainer")]
+        maintainers: Vec<String>,
+
+        /// Output format
+        #[arg(long, default_value = "markdown", value_parser = ["markdown", "json"])]
+        format: String,
+    },
+}
 
+#[derive(Subcommand)]
+enum ObjectiveCommands {
+    /// List objectives with their goal roll-up
+    List {
         /// Output as JSON
         #[arg(long)]
         json: bool,
     },
 
-    /// Show a goal with its issues
+    /// Show an objective's roll-up and goals
     Show {
-        /// Goal name or ID
+        /// Objective name or ID
         name: String,
 
         /// Output as JSON
@@ -221,9 +706,9 @@ enum GoalCommands {
         json: bool,
     },
 
-    /// Create a new goal
+    /// Create a new objective (GitHub: kept locally)
     Create {
-        /// Goal name
+        /// Objective name
         name: String,
 
         /// Target date (YYYY-MM-DD)
@@ -239,10 +724,10 @@ enum GoalCommands {
         json: bool,
     },
 
-    /// Assign an issue to a goal
-    Assign {
-        /// Issue number
-        issue: u64,
+    /// Put a goal under an objective
+    Link {
+        /// Objective name or ID
+        objective: String,
 
         /// Goal name or ID
         goal: String,
@@ -251,11 +736,21 @@ enum GoalCommands {
         #[arg(long)]
         json: bool,
     },
+}
 
-    /// Close a goal
-    Close {
-        /// Goal name or ID
-        name: String,
+#[derive(Subcommand)]
+enum TeamCommands {
+    /// List teams in the Linear workspace
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Re-point this repo's Linear link at another team (keeps the old team's cache)
+    Switch {
+        /// Team key or name
+        team: String,
 
         /// Output as JSON
         #[arg(long)]
@@ -264,332 +759,2948 @@ enum GoalCommands {
 }
 
 #[derive(Subcommand)]
-enum DaemonCommands {
-    /// Show daemon status and watched repos
+enum BackupCommands {
+    /// Write a snapshot of the database to a file (no credentials included)
+    Create {
+        /// Destination file
+        file: std::path::PathBuf,
+
+        /// Overwrite the file if it exists
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Replace all isq state with a snapshot
+    Restore {
+        /// Backup file
+        file: std::path::PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum QueueCommands {
+    /// List this repo's queued writes, with the last error of each
+    List {
+        /// List the writes that expired after `[queue] max_age_days` instead
+        #[arg(long)]
+        failed: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Ask the daemon to replay queued writes now
+    Retry {
+        /// Queue the writes that expired again first
+        #[arg(long)]
+        failed: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommands {
+    /// Encrypt the bodies already cached; new ones stay encrypted from then on
+    Encrypt {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Turn encryption off and store bodies as plain text again
+    Decrypt {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum IssueCommands {
+    /// List issues
+    List {
+        /// Only issues with this label (repeat to require several)
+        #[arg(long)]
+        label: Vec<String>,
+
+        /// Only issues with at least one of these labels (repeatable)
+        #[arg(long)]
+        any_label: Vec<String>,
+
+        /// Hide issues with this label (repeatable)
+        #[arg(long)]
+        not_label: Vec<String>,
+
+        /// Filter by state (open, closed)
+        #[arg(long)]
+        state: Option<String>,
+
+        /// Filter by goal (milestone/project name, case-insensitive)
+        #[arg(long, alias = "milestone")]
+        goal: Option<String>,
+
+        /// Filter by assignee username (`@me` for yourself)
+        #[arg(long)]
+        assignee: Option<String>,
+
+        /// Only issues this user opened, is assigned to or commented on (`@me` for yourself)
+        #[arg(long)]
+        participant: Option<String>,
+
+        /// Only issues untouched for at least this long (e.g. 30d)
+        #[arg(long, value_parser = parse_duration)]
+        stale: Option<Duration>,
+
+        /// Only issues of this type (GitHub issue type, or a `[types.labels]` label for it)
+        #[arg(long = "type", value_name = "TYPE")]
+        issue_type: Option<String>,
+
+        /// Only open issues past their SLA or breaching within this long
+        /// (default 1d), soonest first (Linear)
+        #[arg(long, value_name = "WITHIN", value_parser = parse_duration, num_args = 0..=1, default_missing_value = "1d")]
+        sla_breaching: Option<Duration>,
+
+        /// Only open issues awaiting a first reply: no comments, or none by
+        /// the `[list] maintainers`
+        #[arg(long)]
+        unanswered: bool,
+
+        /// Table columns, comma-separated (state, id, title, assignee, author,
+        /// labels, goal, priority, sla, comments, created, updated)
+        #[arg(long, value_delimiter = ',', value_parser = display::parse_column)]
+        columns: Vec<display::Column>,
+
+        /// Keep the list on screen, redrawing it whenever the cache changes
+        #[arg(long, conflicts_with = "json")]
+        watch: bool,
+
+        /// Ranked issues first, in `issue rank` order
+        #[arg(long, conflicts_with = "watch")]
+        ranked: bool,
+
+        /// Most relevant to you first: assigned to you, commented on by you,
+        /// priority and recent updates
+        #[arg(long, conflicts_with_all = ["watch", "ranked"])]
+        smart: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// JSON fields to include, comma-separated (e.g. number,title,state)
+        #[arg(long, value_delimiter = ',', value_parser = display::parse_issue_field, requires = "json")]
+        fields: Vec<String>,
+
+        /// Smaller JSON: no bodies or empty values, short titles, label names only
+        #[arg(long, requires = "json")]
+        compact: bool,
+
+        /// Include issues hidden by the `[list]` exclusions in config
+        #[arg(long)]
+        no_exclude: bool,
+
+        /// List another repo on the same forge, fetched live without linking or caching it
+        #[arg(long, value_name = "OWNER/NAME", conflicts_with_all = ["watch", "ranked"])]
+        remote: Option<String>,
+    },
+
+    /// Find open issues untouched for a while, optionally commenting on or closing them
+    Stale {
+        /// Untouched for at least this long
+        #[arg(long, default_value = "30d", value_parser = parse_duration)]
+        older_than: Duration,
+
+        /// Only issues with this label (repeat to require several)
+        #[arg(long)]
+        label: Vec<String>,
+
+        /// Comment on each stale issue (before closing, with --close)
+        #[arg(long)]
+        comment: Option<String>,
+
+        /// Close each stale issue
+        #[arg(long)]
+        close: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show a single issue
+    Show {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Number of most recent comments to show, or "all"
+        #[arg(long, default_value = "10", value_parser = parse_comment_limit)]
+        comments: CommentLimit,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Only these JSON fields, as one flat object (e.g. body or labels,comments)
+        #[arg(long, value_delimiter = ',', value_parser = display::parse_show_field, requires = "json")]
+        fields: Vec<String>,
+
+        /// Fetch the issue and its comments from the forge first, not just the cache
+        #[arg(long)]
+        fresh: bool,
+    },
+
+    /// Print an issue's web URL
+    Url {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Copy the URL to the clipboard instead of printing it
+        #[arg(long)]
+        copy: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show or set the issue being worked on in this branch (per worktree)
+    Current {
+        /// Issue to tie to the current branch
+        #[arg(conflicts_with = "clear", value_parser = forges::parse_issue_id)]
+        id: Option<forges::IssueId>,
+
+        /// Forget the current branch's issue
+        #[arg(long)]
+        clear: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Pick an issue by fuzzy search over the cache and print its number
+    /// (`isq issue show $(isq issue pick)`)
+    Pick {
+        /// Start with this query; without a terminal, take its best match
+        query: Option<String>,
+
+        /// Issues in this state
+        #[arg(long, default_value = "open", value_parser = ["open", "closed", "all"])]
+        state: String,
+
+        /// Only issues with this label (repeat to require several)
+        #[arg(long)]
+        label: Vec<String>,
+
+        /// Run this on the picked issue instead of printing its number
+        #[arg(long, value_parser = ["show", "context", "url", "current", "close", "reopen"])]
+        then: Option<String>,
+    },
+
+    /// Wait until an issue reaches a state or gets a new comment (for scripts
+    /// that need a human's go-ahead). Exits 9 on timeout.
+    Await {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Wait for this state
+        #[arg(long, value_parser = ["open", "closed"], required_unless_present = "comment")]
+        state: Option<String>,
+
+        /// Wait for a new comment (with --state, whichever comes first)
+        #[arg(long)]
+        comment: bool,
+
+        /// Give up after this long
+        #[arg(long, default_value = "10m", value_parser = parse_duration)]
+        timeout: Duration,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Print an issue and its comments as one markdown document (for LLM context)
+    Context {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Approximate token budget; oldest comments are dropped first to fit
+        #[arg(long)]
+        max_tokens: Option<usize>,
+    },
+
+    /// Create a new issue
+    Create {
+        /// Issue title
+        #[arg(long)]
+        title: String,
+
+        /// Issue body
+        #[arg(long)]
+        body: Option<String>,
+
+        /// Labels to add
+        #[arg(long)]
+        label: Vec<String>,
+
+        /// Goal to assign the issue to
+        #[arg(long)]
+        goal: Option<String>,
+
+        /// Issue type (e.g. Bug): a `[types.labels]` label for it, else GitHub's issue type
+        #[arg(long = "type", value_name = "TYPE")]
+        issue_type: Option<String>,
+
+        /// Queue for the daemon instead of writing now (saves rate limit budget)
+        #[arg(long)]
+        queue: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// Print only the new issue's number (nothing if queued offline)
+        #[arg(long, conflicts_with = "json")]
+        quiet: bool,
+
+        /// Create even if open issues with similar titles exist
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Add a comment to an issue
+    Comment {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Comment body (goes above the commits with --from-commit)
+        #[arg(required_unless_present = "from_commit")]
+        message: Option<String>,
+
+        /// Post a commit (`HEAD`) or range (`main..HEAD`) with its diffstat
+        #[arg(long, value_name = "REV")]
+        from_commit: Option<String>,
+
+        /// Queue for the daemon instead of writing now (saves rate limit budget)
+        #[arg(long)]
+        queue: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Close an issue
+    Close {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Post this comment first (queued together with the close when offline)
+        #[arg(short = 'm', long)]
+        comment: Option<String>,
+
+        /// Queue for the daemon instead of writing now (saves rate limit budget)
+        #[arg(long)]
+        queue: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Reopen an issue
+    Reopen {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Post this comment first (queued together with the reopen when offline)
+        #[arg(short = 'm', long)]
+        comment: Option<String>,
+
+        /// Queue for the daemon instead of writing now (saves rate limit budget)
+        #[arg(long)]
+        queue: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Manage labels on an issue
+    Label {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Action: add or remove
+        action: String,
+
+        /// Label name
+        label: String,
+
+        /// Queue for the daemon instead of writing now (saves rate limit budget)
+        #[arg(long)]
+        queue: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Turn an issue into a goal, optionally splitting its task list into issues
+    ConvertToGoal {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Goal name (defaults to the issue title)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Target date (YYYY-MM-DD)
+        #[arg(long)]
+        target: Option<String>,
+
+        /// Create an issue under the goal for each unchecked task-list item
+        #[arg(long)]
+        tasks: bool,
+
+        /// Close the original issue (it is added to the goal either way)
+        #[arg(long)]
+        close: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Create an issue for each unchecked task-list item, then comment on the
+    /// original with links to them
+    Split {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Put the new issues under the original's goal
+        #[arg(long)]
+        same_goal: bool,
+
+        /// Start each new issue's body with a reference back to the original
+        #[arg(long)]
+        link: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Suggest labels from the issue's content using the configured rules
+    SuggestLabels {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Add the suggested labels to the issue
+        #[arg(long)]
+        apply: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Tick or untick a task-list checkbox in an issue's body
+    Check {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Task number, counting from 1 at the top of the body
+        task: usize,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Assign a user to an issue
+    /// Subscribe to an issue's notifications on the forge
+    Subscribe {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Queue for the daemon instead of writing now (saves rate limit budget)
+        #[arg(long)]
+        queue: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Unsubscribe from an issue to mute a noisy thread
+    Unsubscribe {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Queue for the daemon instead of writing now (saves rate limit budget)
+        #[arg(long)]
+        queue: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    Assign {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Username to assign (`@me` for yourself)
+        #[arg(required_unless_present = "me", conflicts_with = "me")]
+        user: Option<String>,
+
+        /// Assign yourself
+        #[arg(long)]
+        me: bool,
+
+        /// Queue for the daemon instead of writing now (saves rate limit budget)
+        #[arg(long)]
+        queue: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Rank an issue in this repo's local backlog order (`issue list --ranked`).
+    /// Without a move, an unranked issue goes to the bottom.
+    Rank {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        id: forges::IssueId,
+
+        /// Move one place up
+        #[arg(long, conflicts_with_all = ["down", "position", "remove"])]
+        up: bool,
+
+        /// Move one place down
+        #[arg(long, conflicts_with_all = ["position", "remove"])]
+        down: bool,
+
+        /// Move to this position (1 is the top)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..), conflicts_with = "remove")]
+        position: Option<u64>,
+
+        /// Take the issue out of the ranking
+        #[arg(long)]
+        remove: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Story points from cached estimates (Linear)
+    Estimate {
+        #[command(subcommand)]
+        command: EstimateCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum EstimateCommands {
+    /// Points open and closed per goal or cycle, recent velocity, and when each goal should be done
+    Report {
+        /// Group by goal or by cycle
+        #[arg(long, default_value = "goal", value_parser = ["goal", "cycle"])]
+        by: String,
+
+        /// Number of finished cycles to average velocity over
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u16).range(1..=52))]
+        cycles: u16,
+
+        /// Output format
+        #[arg(long, default_value = "markdown", value_parser = ["markdown", "json"])]
+        format: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum GoalCommands {
+    /// List goals
+    List {
+        /// Filter by state (open, closed, all)
+        #[arg(long, default_value = "open", value_parser = ["open", "closed", "all"])]
+        state: String,
+
+        /// Sort by progress (least done first), target (soonest first) or name
+        #[arg(long, value_parser = ["progress", "target", "name"])]
+        sort: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show a goal with its issues
+    Show {
+        /// Goal name or ID
+        name: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Release notes from the goal's closed issues, grouped by label
+    Notes {
+        /// Goal name or ID
+        name: String,
+
+        /// Output format
+        #[arg(long, default_value = "markdown", value_parser = ["markdown", "json"])]
+        format: String,
+    },
+
+    /// Print a goal's web URL
+    Url {
+        /// Goal name or ID
+        name: String,
+
+        /// Copy the URL to the clipboard instead of printing it
+        #[arg(long)]
+        copy: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Create a new goal
+    Create {
+        /// Goal name
+        name: String,
+
+        /// Target date (YYYY-MM-DD)
+        #[arg(long)]
+        target: Option<String>,
+
+        /// Description
+        #[arg(long)]
+        body: Option<String>,
+
+        /// Also create the template's issues under the goal
+        /// (~/.config/isq/templates/<name>.toml, or a path)
+        #[arg(long)]
+        template: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Assign an issue to a goal
+    Assign {
+        /// Issue number or identifier (e.g. 123 or ABC-123)
+        #[arg(value_parser = forges::parse_issue_id)]
+        issue: forges::IssueId,
+
+        /// Goal name or ID
+        goal: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Close a goal
+    Close {
+        /// Goal name or ID
+        name: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Reopen a closed goal
+    Reopen {
+        /// Goal name or ID
+        name: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuthCommands {
+    /// Authenticate with a forge and store the credential in the OS keyring
+    Login {
+        /// Forge name (github, linear)
+        forge: String,
+
+        /// Read a token (PAT / personal API key) from stdin instead of using OAuth
+        #[arg(long)]
+        with_token: bool,
+
+        /// Store it as a named account (e.g. work) next to the default one
+        #[arg(long = "as", value_name = "NAME")]
+        account: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DaemonCommands {
+    /// Show daemon status and watched repos
     Status,
 
-    /// Start the daemon
-    Start,
+    /// Start the daemon
+    Start,
+
+    /// Stop the daemon
+    Stop,
+
+    /// Add a repo to the watch list (defaults to current repo)
+    Watch {
+        /// Repo path or linked name (e.g. owner/repo)
+        repo: Option<String>,
+
+        /// Keep the repo watched but suspend its sync
+        #[arg(long)]
+        paused: bool,
+    },
+
+    /// Remove current repo from watch list
+    Unwatch,
+
+    /// Pause all syncing (persists across daemon restarts)
+    Pause {
+        /// Resume automatically after this long (e.g. 30m, 2h, 1d)
+        #[arg(long = "for", value_parser = parse_duration)]
+        duration: Option<Duration>,
+    },
+
+    /// Resume syncing after `pause`, or after safe mode once its cause is fixed
+    Resume,
+
+    /// Run the sync loop (internal, called by spawn)
+    #[command(hide = true)]
+    Run,
+}
+
+/// With JSON as the configured default (`ISQ_OUTPUT=json`) and stdout not a
+/// terminal, the command line with `--json` added, if the command takes it
+/// and neither `--json` nor `--plain` was given
+fn with_default_json(args: &[String]) -> Option<Vec<String>> {
+    if args.iter().any(|a| a == "--json" || a == "--plain")
+        || std::io::stdout().is_terminal()
+        || display::configured_output() != display::OutputFormat::Json
+    {
+        return None;
+    }
+
+    let cli = Cli::command();
+    let mut command = &cli;
+    for arg in args.iter().skip(1).filter(|a| !a.starts_with('-')) {
+        match command.find_subcommand(arg) {
+            Some(sub) => command = sub,
+            None => break,
+        }
+    }
+    command.get_arguments().any(|a| a.get_id() == "json").then(|| {
+        let mut args = args.to_vec();
+        args.push("--json".to_string());
+        args
+    })
+}
+
+#[tokio::main]
+async fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    // Flags that rule out --json (e.g. `issue list --watch`) keep plain output
+    let args = match with_default_json(&args) {
+        Some(json_args) if Cli::try_parse_from(&json_args).is_ok() => json_args,
+        _ => args,
+    };
+
+    // Scan raw args so parse errors also get the JSON envelope
+    let json_errors = args.iter().any(|a| a == "--json");
+
+    let cli = match Cli::try_parse_from(&args) {
+        Ok(cli) => cli,
+        Err(e) if json_errors && e.use_stderr() => std::process::exit(error::report_usage(&e.to_string())),
+        Err(e) => e.exit(),
+    };
+
+    // Commands that wait on purpose run without the deadline
+    let timeout = if waits(&cli.command) {
+        None
+    } else {
+        config::load().map(|c| c.network).unwrap_or_default().command_timeout()
+    };
+    if let Err(e) = network::deadline(timeout, "The command", run(cli)).await {
+        std::process::exit(error::report(&e, json_errors));
+    }
+}
+
+/// Whether a command runs until interrupted or sets its own deadlines (the
+/// daemon and `isq warm` give each repo one, `issue await` has --timeout)
+fn waits(command: &Commands) -> bool {
+    matches!(
+        command,
+        Commands::Daemon { command: DaemonCommands::Run }
+            | Commands::Issue { command: IssueCommands::Await { .. } | IssueCommands::List { watch: true, .. } }
+            | Commands::Warm { .. }
+    )
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    match cli.command {
+        Commands::Link { repair: true, .. } => cmd_link_repair()?,
+        Commands::Link { from_config: true, opt, account, .. } => cmd_link_from_config(opt, account).await?,
+        Commands::Link { forge, opt, org, all, pattern, query, account, .. } => {
+            cmd_link(forge.as_deref(), opt, org, all, pattern, query, account).await?
+        }
+        Commands::Unlink { force, purge } => cmd_unlink(force, purge)?,
+        Commands::Auth { command } => match command {
+            AuthCommands::Login { forge, with_token, account } => {
+                cmd_auth_login(&forge, with_token, account.as_deref()).await?
+            }
+        },
+        Commands::Status { json } => cmd_status(json)?,
+        Commands::Whoami { refresh, json } => cmd_whoami(refresh, json).await?,
+        Commands::Issue { command } => match command {
+            IssueCommands::List {
+                label,
+                any_label,
+                not_label,
+                state,
+                goal,
+                assignee,
+                participant,
+                stale,
+                issue_type,
+                sla_breaching,
+                unanswered,
+                columns,
+                watch,
+                ranked,
+                smart,
+                json,
+                fields,
+                compact,
+                no_exclude,
+                remote,
+            } => {
+                let labels = LabelFilters { all: label, any: any_label, not: not_label };
+                let filters = ListFilters { labels, state, goal, assignee, participant, stale, issue_type, sla_breaching, unanswered, no_exclude };
+                let output = ListOutput { columns, watch, ranked, smart, json, fields, compact };
+                cmd_issue_list(remote, filters, output).await?
+            }
+            IssueCommands::Stale { older_than, label, comment, close, json } => {
+                cmd_issue_stale(older_than, label, comment, close, json).await?
+            }
+            IssueCommands::Show { id, comments, json, fields, fresh } => {
+                let id = issue_number(&id)?;
+                if fresh {
+                    refresh_issue(id).await?;
+                }
+                cmd_issue_show(id, comments, json, &fields)?
+            }
+            IssueCommands::Url { id, copy, json } => cmd_issue_url(issue_number(&id)?, copy, json)?,
+            IssueCommands::Current { id, clear, json } => cmd_issue_current(id.as_ref().map(issue_number).transpose()?, clear, json)?,
+            IssueCommands::Context { id, max_tokens } => cmd_issue_context(issue_number(&id)?, max_tokens)?,
+            IssueCommands::Pick { query, state, label, then } => {
+                let id = cmd_issue_pick(query, state, label)?;
+                match then.as_deref() {
+                    Some("show") => cmd_issue_show(id, CommentLimit::Last(10), false, &[])?,
+                    Some("context") => cmd_issue_context(id, None)?,
+                    Some("url") => cmd_issue_url(id, false, false)?,
+                    Some("current") => cmd_issue_current(Some(id), false, false)?,
+                    Some("close") => cmd_issue_set_state(id, true, None, false, false).await?,
+                    Some("reopen") => cmd_issue_set_state(id, false, None, false, false).await?,
+                    _ => println!("{}", id),
+                }
+            }
+            IssueCommands::Await { id, state, comment, timeout, json } => {
+                cmd_issue_await(issue_number(&id)?, state, comment, timeout, json).await?
+            }
+            IssueCommands::Create { title, body, label, goal, issue_type, queue, json, quiet, force } => {
+                let new = NewIssue { title, body, labels: label, goal, issue_type };
+                cmd_issue_create(new, queue, json, quiet, force).await?
+            }
+            IssueCommands::Comment { id, message, from_commit, queue, json } => {
+                let message = match from_commit {
+                    Some(rev) => {
+                        let (commits, stat) = repo::read_commits(&rev)?;
+                        let commits = repo::commits_markdown(&commits, &stat);
+                        match message {
+                            Some(message) => format!("{}\n\n{}", message, commits),
+                            None => commits,
+                        }
+                    }
+                    None => message.unwrap_or_default(),
+                };
+                cmd_issue_comment(issue_number(&id)?, message, queue, json, None).await?
+            }
+            IssueCommands::Close { id, comment, queue, json } => cmd_issue_set_state(issue_number(&id)?, true, comment, queue, json).await?,
+            IssueCommands::Reopen { id, comment, queue, json } => cmd_issue_set_state(issue_number(&id)?, false, comment, queue, json).await?,
+            IssueCommands::Label { id, action, label, queue, json } => {
+                cmd_issue_label(issue_number(&id)?, action, label, queue, json).await?
+            }
+            IssueCommands::ConvertToGoal { id, name, target, tasks, close, json } => {
+                cmd_issue_convert_to_goal(issue_number(&id)?, name, target, tasks, close, json).await?
+            }
+            IssueCommands::Split { id, same_goal, link, json } => cmd_issue_split(issue_number(&id)?, same_goal, link, json).await?,
+            IssueCommands::SuggestLabels { id, apply, json } => {
+                cmd_issue_suggest_labels(issue_number(&id)?, apply, json).await?
+            }
+            IssueCommands::Check { id, task, json } => cmd_issue_check(issue_number(&id)?, task, json).await?,
+            IssueCommands::Subscribe { id, queue, json } => cmd_issue_subscribe(issue_number(&id)?, true, queue, json).await?,
+            IssueCommands::Unsubscribe { id, queue, json } => cmd_issue_subscribe(issue_number(&id)?, false, queue, json).await?,
+            IssueCommands::Assign { id, user, me, queue, json } => {
+                let user = if me { ME.to_string() } else { user.unwrap_or_default() };
+                cmd_issue_assign(issue_number(&id)?, user, queue, json).await?
+            }
+            IssueCommands::Rank { id, up, down, position, remove, json } => {
+                let mv = match (up, down, position, remove) {
+                    (_, _, _, true) => db::RankMove::Remove,
+                    (_, _, Some(n), _) => db::RankMove::To(n as usize),
+                    (true, _, _, _) => db::RankMove::Up,
+                    (_, true, _, _) => db::RankMove::Down,
+                    _ => db::RankMove::Keep,
+                };
+                cmd_issue_rank(issue_number(&id)?, mv, json)?
+            }
+            IssueCommands::Estimate { command } => match command {
+                EstimateCommands::Report { by, cycles, format } => cmd_estimate_report(&by, cycles, &format)?,
+            },
+        },
+        Commands::Search { query, scopes, limit, json } => cmd_search(&query.join(" "), &scopes, limit, json)?,
+        Commands::Comment { command } => match command {
+            CommentCommands::Search { query, author, issue, limit, json } => {
+                cmd_comment_search(&query.join(" "), author, issue.as_ref().map(issue_number).transpose()?, limit, json)?
+            }
+            CommentCommands::Quote { id, comment, message, queue, json } => {
+                cmd_comment_quote(issue_number(&id)?, &comment, message, queue, json).await?
+            }
+        },
+        Commands::Draft { command } => match command {
+            DraftCommands::New { title, body, label, goal, json } => cmd_draft_new(title, body, label, goal, json)?,
+            DraftCommands::Edit { id, json } => cmd_draft_edit(id, json)?,
+            DraftCommands::List { comments, json } => cmd_draft_list(comments, json)?,
+            DraftCommands::Submit { id, force, json } => cmd_draft_submit(id, force, json).await?,
+        },
+        Commands::Daemon { command } => match command {
+            DaemonCommands::Status => cmd_daemon_status()?,
+            DaemonCommands::Start => cmd_daemon_start()?,
+            DaemonCommands::Stop => cmd_daemon_stop()?,
+            DaemonCommands::Watch { repo, paused } => cmd_daemon_watch(repo, paused)?,
+            DaemonCommands::Unwatch => cmd_daemon_unwatch()?,
+            DaemonCommands::Pause { duration } => cmd_daemon_pause(duration)?,
+            DaemonCommands::Resume => cmd_daemon_resume()?,
+            DaemonCommands::Run => daemon::run_loop().await?,
+        },
+        Commands::Sync { dry_run, json, issues_only, comments_only, goals_only, issue } => {
+            let scope = if issues_only {
+                SyncScope::Issues
+            } else if comments_only {
+                SyncScope::Comments
+            } else if goals_only {
+                SyncScope::Goals
+            } else {
+                SyncScope::All
+            };
+            match issue {
+                Some(id) => cmd_sync_issue(issue_number(&id)?).await?,
+                None => cmd_sync(dry_run, json, scope).await?,
+            }
+        }
+        Commands::Warm { max_age, jobs, json } => cmd_warm(max_age, jobs as usize, json).await?,
+        Commands::Goal { command } => match command {
+            GoalCommands::List { state, sort, json } => cmd_goal_list(state, sort, json).await?,
+            GoalCommands::Show { name, json } => cmd_goal_show(name, json)?,
+            GoalCommands::Notes { name, format } => cmd_goal_notes(name, format)?,
+            GoalCommands::Url { name, copy, json } => cmd_goal_url(name, copy, json)?,
+            GoalCommands::Create { name, target, body, template, json } => {
+                cmd_goal_create(name, target, body, template, json).await?
+            }
+            GoalCommands::Assign { issue, goal, json } => {
+                cmd_goal_assign(issue_number(&issue)?, goal, json).await?
+            }
+            GoalCommands::Close { name, json } => cmd_goal_set_state(name, GoalState::Closed, json).await?,
+            GoalCommands::Reopen { name, json } => cmd_goal_set_state(name, GoalState::Open, json).await?,
+        },
+        Commands::Objective { command } => match command {
+            ObjectiveCommands::List { json } => cmd_objective_list(json)?,
+            ObjectiveCommands::Show { name, json } => cmd_objective_show(name, json)?,
+            ObjectiveCommands::Create { name, target, body, json } => {
+                cmd_objective_create(name, target, body, json).await?
+            }
+            ObjectiveCommands::Link { objective, goal, json } => {
+                cmd_objective_link(objective, goal, json).await?
+            }
+        },
+        Commands::Label { command } => match command {
+            LabelCommands::List { names, json } => cmd_label_list(names, json)?,
+            LabelCommands::Sync { apply, json } => cmd_label_sync(apply, json).await?,
+        },
+        Commands::Report { command } => match command {
+            ReportCommands::Goals { since, goal, format } => cmd_report_goals(since, goal, &format)?,
+            ReportCommands::Aging { maintainers, format } => cmd_report_aging(&maintainers, &format)?,
+        },
+        Commands::Stats { weeks, json } => cmd_stats(weeks, json)?,
+        Commands::Digest { week, days, out, json } => cmd_digest(week, days, out.as_deref(), json)?,
+        Commands::Export { format: _, out, json } => cmd_export(&out, json)?,
+        Commands::Backup { command } => match command {
+            BackupCommands::Create { file, force } => cmd_backup_create(&file, force)?,
+            BackupCommands::Restore { file } => cmd_backup_restore(&file)?,
+        },
+        Commands::Inbox { all, json } => cmd_inbox(all, json)?,
+        Commands::Queue { command } => match command {
+            QueueCommands::List { failed, json } => cmd_queue_list(failed, json)?,
+            QueueCommands::Retry { failed, json } => cmd_queue_retry(failed, json)?,
+        },
+        Commands::Cache { command } => match command {
+            CacheCommands::Encrypt { json } => cmd_cache_convert(true, json)?,
+            CacheCommands::Decrypt { json } => cmd_cache_convert(false, json)?,
+        },
+        Commands::Team { command } => match command {
+            TeamCommands::List { json } => cmd_team_list(json).await?,
+            TeamCommands::Switch { team, json } => cmd_team_switch(team, json).await?,
+        },
+        Commands::Repo { command } => match command {
+            RepoCommands::List { json } => cmd_repo_list(json)?,
+            RepoCommands::Forget { path, force, purge } => cmd_repo_forget(&path, force, purge)?,
+        },
+        Commands::Wip { command, json } => match command {
+            None => cmd_wip(json).await?,
+            Some(WipCommands::Next { json }) => cmd_wip_next(json).await?,
+        },
+    }
+
+    Ok(())
+}
+
+async fn cmd_link(
+    forge_name: Option<&str>,
+    opts: Vec<String>,
+    org: Option<String>,
+    all: bool,
+    pattern: Option<String>,
+    query: Option<String>,
+    account: Option<String>,
+) -> Result<()> {
+    // Bulk links don't need to run inside a checkout
+    let repo_path = match org {
+        Some(_) => String::new(),
+        None => repo::detect_repo_path()?,
+    };
+
+    // Require forge name
+    let forge_name = forge_name.ok_or_else(|| {
+        let forges: Vec<_> = forges::available_forge_types().iter().map(|f| format!("  isq link {}", f.as_str())).collect();
+        anyhow::anyhow!("Missing forge name.\n\nRun one of:\n{}", forges.join("\n"))
+    })?;
+
+    // Parse forge type
+    let forge_type = ForgeType::from_str(forge_name).ok_or_else(|| {
+        let forges: Vec<_> = forges::available_forge_types().iter().map(|f| format!("  isq link {}", f.as_str())).collect();
+        anyhow::anyhow!(
+            "Unknown forge: {}\n\nRun one of:\n{}\n\nOther trackers need an isq-forge-{} adapter on PATH",
+            forge_name,
+            forges.join("\n"),
+            forge_name
+        )
+    })?;
+
+    // Parse options
+    let mut args = LinkArgs::parse(&opts)?;
+    args.org = org;
+    args.all = all;
+    args.pattern = pattern;
+    args.query = query;
+    args.account = account;
+
+    // Run forge-specific link flow
+    let is_bulk = args.org.is_some();
+    let result = forge_type.link(&repo_path, &args).await?;
+
+    // Remember how to recognize this checkout if it moves
+    if !is_bulk {
+        let conn = db::open()?;
+        db::set_repo_fingerprint(&conn, &repo_path, &repo::fingerprint(&repo_path))?;
+    }
+
+    // Start background service
+    println!();
+    ensure_service_running()?;
+    println!("\n✓ Linked to {} ({})", forge_type.display_name(), result.display_name);
+
+    Ok(())
+}
+
+/// Rebind an orphaned link (checkout moved) to the current directory
+/// Link the way the repo's team config says, so every checkout gets the same setup
+async fn cmd_link_from_config(opts: Vec<String>, account: Option<String>) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let team = team_config::load(&repo_path)?;
+    let (forge, mut config_opts) = team.link_args().ok_or_else(|| {
+        error::err(
+            ErrorKind::NotFound,
+            format!("No [link] forge in {}. Run `isq link <forge>` instead.", team_config::path(&repo_path).display()),
+        )
+    })?;
+    // Options given on the command line win
+    config_opts.retain(|o| !opts.iter().any(|cli| cli.split('=').next() == o.split('=').next()));
+    config_opts.extend(opts);
+    cmd_link(Some(&forge), config_opts, None, false, None, None, account).await
+}
+
+fn cmd_link_repair() -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+
+    if let Some(link) = db::get_repo_link(&conn, &repo_path)? {
+        println!("Already linked to {} ({})", link.display_name.as_deref().unwrap_or(&link.forge_repo), link.forge_type);
+        return Ok(());
+    }
+
+    let fingerprint = repo::fingerprint(&repo_path);
+    if fingerprint.is_empty() {
+        anyhow::bail!("Can't identify this repo (no 'origin' remote and no commits)");
+    }
+
+    let orphans = db::find_orphaned_links(&conn, &fingerprint)?;
+    let (old_path, link) = match orphans.as_slice() {
+        [] => {
+            return Err(error::err(
+                ErrorKind::NotFound,
+                "No link from a moved copy of this repo found. Run `isq link <forge>` to link it.",
+            ));
+        }
+        [orphan] => orphan,
+        _ => {
+            let paths: Vec<_> = orphans.iter().map(|(path, _)| format!("  {}", path)).collect();
+            return Err(error::err(
+                ErrorKind::Conflict,
+                format!(
+                    "Several links from moved copies of this repo exist:\n{}\n\nRun `isq link <forge>` here to link it fresh.",
+                    paths.join("\n")
+                ),
+            ));
+        }
+    };
+
+    db::rebind_repo_link(&conn, old_path, &repo_path)?;
+    db::set_repo_fingerprint(&conn, &repo_path, &fingerprint)?;
+
+    let pending = db::count_pending_ops(&conn, &link.forge_repo)?;
+    println!(
+        "✓ Moved link to {} ({}) from {}",
+        link.display_name.as_deref().unwrap_or(&link.forge_repo),
+        link.forge_type,
+        old_path
+    );
+    if pending > 0 {
+        println!("  {} pending operations kept", pending);
+    }
+    Ok(())
+}
+
+async fn cmd_whoami(refresh: bool, json: bool) -> Result<()> {
+    let conns = db::open_all()?;
+    // Accounts aren't tied to one repo, so their details are kept in the shared cache
+    let conn = &conns[0];
+
+    // Each forge account some repo is linked with; without links, whatever is configured
+    let mut targets: Vec<(ForgeType, Option<String>)> = Vec::new();
+    for repo_conn in &conns {
+        for (_, link) in db::list_repo_links(repo_conn)? {
+            let Some(forge_type) = ForgeType::from_str(&link.forge_type) else {
+                continue;
+            };
+            let target = (forge_type, link.account);
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+    }
+    if targets.is_empty() {
+        targets = ALL_FORGE_TYPES
+            .iter()
+            .filter(|forge_type| forge_type.credential_summary().is_some())
+            .map(|forge_type| (forge_type.clone(), None))
+            .collect();
+    }
+    if targets.is_empty() {
+        return Err(error::err(ErrorKind::AuthMissing, "Not authenticated with any forge. Run: isq auth login <forge>"));
+    }
+
+    let mut results = Vec::new();
+    for (forge_type, account) in &targets {
+        let key = forges::account_key(forge_type.as_str(), account.as_deref());
+        let cached = if refresh { None } else { db::load_whoami(conn, &key)? };
+        let result = match cached {
+            Some(info) => Ok((info, true)),
+            None => match forge_type.whoami(account.as_deref()).await {
+                Ok(info) => {
+                    db::save_whoami(conn, &key, &info)?;
+                    Ok((info, false))
+                }
+                Err(e) => Err(e),
+            },
+        };
+        results.push((forge_type, account, result));
+    }
+
+    if json {
+        let output = results
+            .iter()
+            .map(|(forge_type, account, result)| -> Result<serde_json::Value> {
+                Ok(match result {
+                    Ok((info, cached)) => {
+                        let mut value = serde_json::to_value(info)?;
+                        value["cached"] = serde_json::json!(cached);
+                        value
+                    }
+                    Err(e) => serde_json::json!({
+                        "forge": forge_type.as_str(),
+                        "account": account,
+                        "error": e.to_string(),
+                    }),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    for (i, (forge_type, account, result)) in results.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let name = match account {
+            Some(account) => format!("{} ({})", forge_type.display_name(), account),
+            None => forge_type.display_name().to_string(),
+        };
+        let (info, cached) = match result {
+            Ok(found) => found,
+            Err(e) => {
+                let reason = e.to_string();
+                println!("{}: not authenticated ({})", name, reason.lines().next().unwrap_or_default());
+                continue;
+            }
+        };
+
+        println!("{}: {}", name, info.login);
+        match &info.token_kind {
+            Some(kind) => println!("  token:    {} via {}", kind, info.source),
+            None => println!("  token:    via {}", info.source),
+        }
+        if let Some(scopes) = &info.scopes {
+            let scopes = if scopes.is_empty() { "(none)".to_string() } else { scopes.join(", ") };
+            println!("  scopes:   {}", scopes);
+        }
+        if let Some(expires_at) = &info.expires_at {
+            println!("  expires:  {}", expires_at);
+        }
+        let verified = chrono::DateTime::parse_from_rfc3339(&info.verified_at)
+            .map(|t| timefmt::local_time(t.timestamp()))
+            .unwrap_or_else(|_| info.verified_at.clone());
+        if *cached {
+            println!("  verified: {} (cached; --refresh to re-check)", verified);
+        } else {
+            println!("  verified: {}", verified);
+        }
+    }
+
+    Ok(())
+}
+
+async fn cmd_auth_login(forge_name: &str, with_token: bool, account: Option<&str>) -> Result<()> {
+    let forge_type = ForgeType::from_str(forge_name).ok_or_else(|| {
+        let forges: Vec<_> = forges::available_forge_types().iter().map(|f| format!("  isq auth login {}", f.as_str())).collect();
+        anyhow::anyhow!(
+            "Unknown forge: {}\n\nRun one of:\n{}\n\nOther trackers need an isq-forge-{} adapter on PATH",
+            forge_name,
+            forges.join("\n"),
+            forge_name
+        )
+    })?;
+
+    let token = if with_token {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        let token = input.trim().to_string();
+        if token.is_empty() {
+            anyhow::bail!("No token on stdin. Usage: isq auth login {} --with-token < token.txt", forge_type.as_str());
+        }
+        Some(token)
+    } else {
+        None
+    };
+
+    let username = forge_type.login(token, account).await?;
+    db::forget_whoami(&db::open()?, &forges::account_key(forge_type.as_str(), account))?;
+    match account {
+        Some(account) => println!(
+            "✓ Authenticated with {} as {} (account '{}'; link with `isq link {} --as {}`)",
+            forge_type.display_name(),
+            username,
+            account,
+            forge_type.as_str(),
+            account
+        ),
+        None => println!("✓ Authenticated with {} as {}", forge_type.display_name(), username),
+    }
+    Ok(())
+}
+
+/// Ensure the system service is installed and running
+fn ensure_service_running() -> Result<()> {
+    let status = service::status()?;
+
+    if !status.installed {
+        println!("Installing system service...");
+        service::install()?;
+        println!("✓ System service installed");
+    } else if !status.running {
+        service::start()?;
+        println!("✓ System service started");
+    } else if let Some(pid) = status.pid {
+        println!("System service running (PID {})", pid);
+    }
+
+    Ok(())
+}
+
+fn cmd_unlink(force: bool, purge: bool) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+
+    // Check if linked
+    let Some(link) = db::get_repo_link(&conn, &repo_path)? else {
+        println!("This repo is not linked to any issue tracker.");
+        return Ok(());
+    };
+    unlink_repo_path(&conn, &repo_path, link, force, purge)
+}
+
+/// Remove a checkout's link and stop watching it (`isq unlink`, `isq repo forget`)
+fn unlink_repo_path(conn: &rusqlite::Connection, repo_path: &str, link: db::RepoLink, force: bool, purge: bool) -> Result<()> {
+    // Unlinking stops the daemon from replaying the queue
+    let at_risk = db::ops_at_risk(conn, repo_path)?;
+    if !at_risk.is_empty() {
+        let ops: Vec<_> = at_risk.iter().map(|op| format!("  {} (queued {})", op.summary(), op.created_at)).collect();
+        if !force {
+            return Err(error::err(
+                ErrorKind::Conflict,
+                format!(
+                    "{} unsynced operations would be lost:\n{}\n\nLet the daemon sync them once online (see `isq status`), or rerun with --force to discard them.",
+                    at_risk.len(),
+                    ops.join("\n")
+                ),
+            ));
+        }
+        eprintln!("Discarding {} unsynced operations:\n{}", at_risk.len(), ops.join("\n"));
+        for op in &at_risk {
+            db::complete_op(conn, op.id)?;
+        }
+    }
+
+    db::remove_repo_link(conn, repo_path)?;
+    db::remove_watched_repo(conn, repo_path)?;
+
+    println!("✓ Unlinked from {} ({})", link.forge_type, link.forge_repo);
+
+    // The cache is keyed by forge repo, so another checkout may still use it
+    if db::is_forge_repo_linked(conn, &link.forge_repo)? {
+        if purge {
+            println!("  Cache kept: {} is still linked from another directory", link.forge_repo);
+        }
+    } else if purge {
+        let purged = db::purge_repo_cache(conn, &link.forge_repo)?;
+        println!(
+            "✓ Purged {} issues, {} comments and {} goals from the cache",
+            purged.issues, purged.comments, purged.goals
+        );
+    } else {
+        let size = db::cache_size(conn, &link.forge_repo)?;
+        if !size.is_empty() {
+            println!(
+                "  {} issues, {} comments and {} goals remain cached. Re-linking reuses them; `isq unlink --purge` deletes them.",
+                size.issues, size.comments, size.goals
+            );
+        }
+    }
+
+    // Check if any repos left - if not, uninstall service
+    let remaining = db::list_watched_repos(conn)?;
+    if remaining.is_empty() {
+        println!();
+        service::uninstall()?;
+        println!("✓ System service removed (no repos to watch)");
+    }
+
+    Ok(())
+}
+
+/// A linked checkout in `isq repo list --json`
+#[derive(Serialize)]
+struct RepoListEntry {
+    path: String,
+    forge: String,
+    forge_repo: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account: Option<String>,
+    last_sync: Option<String>,
+    pending_ops: i64,
+    watched: bool,
+    paused: bool,
+    /// The checkout's directory no longer exists (moved or deleted)
+    missing: bool,
+}
+
+fn cmd_repo_list(json: bool) -> Result<()> {
+    let mut entries = Vec::new();
+    for conn in db::open_all()? {
+        let watched: std::collections::HashMap<String, bool> =
+            db::list_watched_repos(&conn)?.into_iter().map(|w| (w.repo, w.paused)).collect();
+        for (path, link) in db::list_repo_links(&conn)? {
+            entries.push(RepoListEntry {
+                last_sync: db::get_sync_state(&conn, &link.forge_repo)?.map(|(last_sync, _)| last_sync),
+                pending_ops: db::count_pending_ops(&conn, &link.forge_repo)?,
+                watched: watched.contains_key(&path),
+                paused: watched.get(&path).copied().unwrap_or(false),
+                missing: db::is_missing_checkout(&path),
+                forge: link.forge_type,
+                forge_repo: link.forge_repo,
+                display_name: link.display_name,
+                account: link.account,
+                path,
+            });
+        }
+    }
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+    if entries.is_empty() {
+        println!("No linked repos. Run `isq link <forge>` in a git repo.");
+        return Ok(());
+    }
+
+    let path_width = entries.iter().map(|e| e.path.chars().count()).max().unwrap_or(0);
+    for entry in &entries {
+        let name = entry.display_name.as_deref().unwrap_or(&entry.forge_repo);
+        let mut notes = vec![match &entry.last_sync {
+            Some(at) => format!("synced {}", at),
+            None => "not synced".to_string(),
+        }];
+        if entry.pending_ops > 0 {
+            notes.push(format!("{} pending", entry.pending_ops));
+        }
+        if entry.paused {
+            notes.push("paused".to_string());
+        } else if !entry.watched {
+            notes.push("not watched".to_string());
+        }
+        if entry.missing {
+            notes.push("missing; `isq repo forget` or `isq link --repair`".to_string());
+        }
+        println!("{:path_width$}  {} {}  {}", entry.path, entry.forge, name, notes.join(", "));
+    }
+    Ok(())
+}
+
+fn cmd_repo_forget(path: &str, force: bool, purge: bool) -> Result<()> {
+    let conn = db::open()?;
+
+    // Links are stored by absolute path; a checkout that still exists can be
+    // named relative to the current directory
+    let mut repo_path = path.trim_end_matches('/').to_string();
+    if db::get_repo_link(&conn, &repo_path)?.is_none()
+        && !db::is_remote_repo_path(path)
+        && let Ok(resolved) = std::fs::canonicalize(path).or_else(|_| std::path::absolute(path))
+    {
+        repo_path = resolved.to_string_lossy().trim_end_matches('/').to_string();
+    }
+
+    let link = db::get_repo_link(&conn, &repo_path)?.ok_or_else(|| {
+        error::err(ErrorKind::NotFound, format!("No repo linked at {}. See `isq repo list`.", path))
+    })?;
+    unlink_repo_path(&conn, &repo_path, link, force, purge)
+}
+
+fn cmd_status(json_output: bool) -> Result<()> {
+    let safe_mode = daemon::load_health().safe_mode;
+    let conn = db::open().inspect_err(|_| {
+        // Likely why the daemon stopped syncing too
+        if let Some(safe_mode) = &safe_mode {
+            eprintln!("⚠️  Daemon in safe mode: {}", safe_mode.reason);
+        }
+    })?;
+    let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+    let svc_status = service::status()?;
+    let queue = config::load().unwrap_or_default().queue;
+
+    // Counts for every linked repo, straight from the cache
+    let mut repos = Vec::new();
+    for (path, link) in db::list_repo_links(&conn)? {
+        let me = db::get_identity(&conn, &link.identity_key())?;
+        let counts = db::issue_counts(&conn, &link.forge_repo, me.as_deref(), &today)?;
+        repos.push((path, link, counts));
+    }
+    let current_path = repo::detect_repo_path().ok();
+    let current = current_path
+        .as_deref()
+        .and_then(|p| repos.iter().find(|(path, _, _)| path == p));
+
+    if json_output {
+        let repo_json = |(path, link, counts): &(String, db::RepoLink, db::IssueCounts)| -> Result<serde_json::Value> {
+            let sync = db::get_sync_state(&conn, &link.forge_repo)?;
+            Ok(serde_json::json!({
+                "path": path,
+                "forge_type": link.forge_type,
+                "forge_repo": link.forge_repo,
+                "display_name": link.display_name,
+                "account": link.account,
+                "last_sync": sync.as_ref().map(|(last_sync, _)| last_sync),
+                "cached_issues": sync.as_ref().map(|(_, count)| count),
+                "pending_ops": db::count_pending_ops(&conn, &link.forge_repo)?,
+                "stuck_ops": db::pending_ops_older_than(&conn, &link.forge_repo, queue.warn_days)?.len(),
+                "failed_ops": db::load_failed_ops(&conn, &link.forge_repo)?.len(),
+                "counts": counts,
+            }))
+        };
+
+        let auth: Vec<_> = forges::available_forge_types()
+            .iter()
+            .map(|forge_type| {
+                let summary = forge_type.credential_summary();
+                serde_json::json!({
+                    "forge": forge_type.as_str(),
+                    "ready": summary.is_some(),
+                    "summary": summary,
+                })
+            })
+            .collect();
+
+        let output = serde_json::json!({
+            "auth": auth,
+            "repo": current.map(repo_json).transpose()?,
+            "repos": repos.iter().map(repo_json).collect::<Result<Vec<_>>>()?,
+            "service": {
+                "installed": svc_status.installed,
+                "running": svc_status.running,
+                "pid": svc_status.pid,
+                "safe_mode": safe_mode,
+            },
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    // Auth status
+    println!("Authentication:");
+
+    for forge_type in forges::available_forge_types() {
+        print!("  {:10}", forge_type.display_name());
+        match (forge_type.credential_summary(), forge_type.auth()) {
+            (Some(summary), _) => println!("ready ({})", summary),
+            (None, Some(auth)) => println!("not configured (run: {})", auth.link_command),
+            (None, None) => println!("not configured"),
+        }
+    }
+
+    // Current repo link (if in a git repo)
+    println!();
+    match current_path {
+        Some(_) => {
+            match current {
+                Some((_, link, counts)) => {
+                    let display = link.display_name.as_deref().unwrap_or(&link.forge_repo);
+                    println!("This repo:");
+                    match &link.account {
+                        Some(account) => println!("  Linked to {} ({}, account '{}')", display, link.forge_type, account),
+                        None => println!("  Linked to {} ({})", display, link.forge_type),
+                    }
+                    println!("  {}", format_issue_counts(counts));
+
+                    // Show sync state
+                    if let Some((last_sync, count)) = db::get_sync_state(&conn, &link.forge_repo)? {
+                        println!("  {} issues cached ({})", count, last_sync);
+                    }
+
+                    // Show pending ops
+                    let pending = db::count_pending_ops(&conn, &link.forge_repo)?;
+                    if pending > 0 {
+                        println!("  {} pending operations", pending);
+                    }
+                    let stuck = db::pending_ops_older_than(&conn, &link.forge_repo, queue.warn_days)?;
+                    if let Some(oldest) = stuck.first() {
+                        println!(
+                            "  ⚠️  {} queued over {}d ago without syncing; oldest: {} ({})",
+                            stuck.len(),
+                            queue.warn_days,
+                            oldest.summary(),
+                            oldest.last_error.as_deref().unwrap_or("is the daemon running?")
+                        );
+                    }
+                    let failed = db::load_failed_ops(&conn, &link.forge_repo)?.len();
+                    if failed > 0 {
+                        println!("  ⚠️  {} failed writes (see `isq queue list --failed`)", failed);
+                    }
+
+                    // Show rate limit status
+                    if let Some(state) = db::get_rate_limit_state(&conn, &link.forge_type)?
+                        && let Some(reset_at) = state.reset_at
+                    {
+                        let now = std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .unwrap()
+                            .as_secs() as i64;
+                        if now < reset_at {
+                            let wait_secs = reset_at - now;
+                            let reset_time = chrono::DateTime::from_timestamp(reset_at, 0)
+                                .map(|at| timefmt::FORMAT.time(at))
+                                .unwrap_or_else(|| format!("{}s", wait_secs));
+                            println!("  ⚠️  Rate limited until {}", reset_time);
+                        }
+                    }
+                }
+                None => {
+                    println!("This repo:");
+                    println!("  Not linked (run: isq link <forge>)");
+                }
+            }
+        }
+        None => {
+            println!("Not in a git repository");
+        }
+    }
+
+    // Every linked repo, when there's more than the current one
+    if repos.len() > 1 || (current.is_none() && !repos.is_empty()) {
+        println!();
+        println!("Repos:");
+        let width = repos
+            .iter()
+            .map(|(_, link, _)| link.display_name.as_deref().unwrap_or(&link.forge_repo).chars().count())
+            .max()
+            .unwrap_or(0);
+        for (_, link, counts) in &repos {
+            let display = link.display_name.as_deref().unwrap_or(&link.forge_repo);
+            println!("  {:width$}  {}", display, format_issue_counts(counts), width = width);
+        }
+    }
 
-    /// Stop the daemon
-    Stop,
+    // Service status
+    println!();
+    print!("Service:    ");
+    if !svc_status.installed {
+        println!("not installed");
+    } else if let Some(pid) = svc_status.pid {
+        println!("running (PID {})", pid);
+    } else {
+        println!("installed but not running");
+    }
+    if let Some(safe_mode) = safe_mode {
+        println!(
+            "  ⚠️  Safe mode since {}, not syncing: {}. Fix the cause, then run `isq daemon resume`",
+            timefmt::local_time(safe_mode.since),
+            safe_mode.reason
+        );
+    }
 
-    /// Add current repo to watch list
-    Watch,
+    Ok(())
+}
 
-    /// Remove current repo from watch list
-    Unwatch,
+/// Summary line for status, e.g. "12 open · 3 assigned to you · 1 overdue"
+fn format_issue_counts(counts: &db::IssueCounts) -> String {
+    let mut parts = vec![format!("{} open", counts.open)];
+    if let Some(mine) = counts.assigned_to_me.filter(|&n| n > 0) {
+        parts.push(format!("{} assigned to you", mine));
+    }
+    if counts.overdue > 0 {
+        parts.push(format!("{} overdue", counts.overdue));
+    }
+    if counts.unread_inbox > 0 {
+        parts.push(format!("{} unread", counts.unread_inbox));
+    }
+    parts.join(" · ")
+}
 
-    /// Run the sync loop (internal, called by spawn)
-    #[command(hide = true)]
-    Run,
+/// What `isq sync` fetches
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SyncScope {
+    All,
+    Issues,
+    Comments,
+    Goals,
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
+impl SyncScope {
+    fn includes(self, part: SyncScope) -> bool {
+        self == SyncScope::All || self == part
+    }
+}
 
-    match cli.command {
-        Commands::Link { forge, opt } => cmd_link(forge.as_deref(), opt).await?,
-        Commands::Unlink => cmd_unlink()?,
-        Commands::Status => cmd_status()?,
-        Commands::Issue { command } => match command {
-            IssueCommands::List { label, state, json } => cmd_issue_list(label, state, json).await?,
-            IssueCommands::Show { id, json } => cmd_issue_show(id, json)?,
-            IssueCommands::Create { title, body, label, goal, json } => {
-                cmd_issue_create(title, body, label, goal, json).await?
-            }
-            IssueCommands::Comment { id, message, json } => cmd_issue_comment(id, message, json).await?,
-            IssueCommands::Close { id, json } => cmd_issue_close(id, json).await?,
-            IssueCommands::Reopen { id, json } => cmd_issue_reopen(id, json).await?,
-            IssueCommands::Label { id, action, label, json } => {
-                cmd_issue_label(id, action, label, json).await?
-            }
-            IssueCommands::Assign { id, user, json } => cmd_issue_assign(id, user, json).await?,
-        },
-        Commands::Daemon { command } => match command {
-            DaemonCommands::Status => cmd_daemon_status()?,
-            DaemonCommands::Start => cmd_daemon_start()?,
-            DaemonCommands::Stop => cmd_daemon_stop()?,
-            DaemonCommands::Watch => cmd_daemon_watch()?,
-            DaemonCommands::Unwatch => cmd_daemon_unwatch()?,
-            DaemonCommands::Run => daemon::run_loop().await?,
-        },
-        Commands::Sync => cmd_sync().await?,
-        Commands::Goal { command } => match command {
-            GoalCommands::List { state, json } => cmd_goal_list(state, json).await?,
-            GoalCommands::Show { name, json } => cmd_goal_show(name, json)?,
-            GoalCommands::Create { name, target, body, json } => {
-                cmd_goal_create(name, target, body, json).await?
+async fn cmd_sync(dry_run: bool, json: bool, scope: SyncScope) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let (forge, link) = get_forge_for_repo(&repo_path)?;
+
+    // Parse forge_repo to create Repo struct
+    let parts: Vec<&str> = link.forge_repo.split('/').collect();
+    if parts.len() != 2 {
+        anyhow::bail!("Invalid forge_repo format: {}", link.forge_repo);
+    }
+    let repo = repo::Repo {
+        owner: parts[0].to_string(),
+        name: parts[1].to_string(),
+    };
+
+    if dry_run {
+        return sync_dry_run(forge.as_ref(), &link, &repo, json).await;
+    }
+
+    eprintln!("Syncing {}...", link.forge_repo);
+    let start = Instant::now();
+
+    let conn = db::open()?;
+    // Replay queued writes first, like the daemon, so the fetch sees them
+    let ops = db::load_pending_ops(&conn, &link.forge_repo)?;
+    let replayed = daemon::replay_pending_ops(forge.as_ref(), &repo, &conn, &ops).await;
+    let synced = sync_forge_repo(forge.as_ref(), &conn, &link, &repo, scope).await?;
+    let fetch_time = start.elapsed();
+
+    // Touch repo to update last_accessed
+    db::touch_repo(&conn, &repo_path)?;
+
+    let undelivered = replayed.iter().filter(|result| !result.outcome.delivered()).count();
+    if json {
+        let result = serde_json::json!({
+            "issues": synced.issues,
+            "comments": synced.comments,
+            "goals": synced.goals,
+            "ops": replayed.iter().map(|result| result.to_json()).collect::<Vec<_>>(),
+            "elapsed_ms": fetch_time.as_millis() as u64,
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        for result in &replayed {
+            let summary = result.op.summary();
+            let error = result.error.as_deref().unwrap_or_default();
+            match result.outcome {
+                daemon::Replay::Applied => println!("✓ {}", summary),
+                daemon::Replay::AlreadyApplied => println!("✓ {} (already applied)", summary),
+                daemon::Replay::Discarded => println!("✗ {}: {} (discarded)", summary, error),
+                daemon::Replay::Failed => println!("✗ {}: {} (still queued)", summary, error),
+                daemon::Replay::Held => println!("· {} (waits for an earlier write)", summary),
+                daemon::Replay::Busy => println!("· {} (another isq process is sending it)", summary),
             }
-            GoalCommands::Assign { issue, goal, json } => {
-                cmd_goal_assign(issue, goal, json).await?
+        }
+        let synced = match scope {
+            SyncScope::All => format!("{} issues, {} comments, and {} goals", synced.issues, synced.comments, synced.goals),
+            SyncScope::Issues => format!("{} issues", synced.issues),
+            SyncScope::Comments => format!("{} comments", synced.comments),
+            SyncScope::Goals => format!("{} goals", synced.goals),
+        };
+        println!("✓ Synced {} in {:.2}s", synced, fetch_time.as_secs_f64());
+    }
+
+    if undelivered > 0 {
+        return Err(error::err(
+            ErrorKind::General,
+            format!("{} of {} queued writes weren't delivered", undelivered, replayed.len()),
+        ));
+    }
+    Ok(())
+}
+
+async fn cmd_sync_issue(number: u64) -> Result<()> {
+    let start = Instant::now();
+    let (issue, comments) = refresh_issue(number).await?;
+    println!(
+        "✓ Synced {} and {} comments in {:.2}s",
+        issue.display_id(),
+        comments,
+        start.elapsed().as_secs_f64()
+    );
+    Ok(())
+}
+
+/// Fetch one issue and its comments live and cache them: two requests
+/// instead of a full sync. Returns the issue and its comment count.
+async fn refresh_issue(number: u64) -> Result<(forges::Issue, usize)> {
+    let repo_path = repo::detect_repo_path()?;
+    let (forge, link) = get_forge_for_repo(&repo_path)?;
+    let parts: Vec<&str> = link.forge_repo.split('/').collect();
+    if parts.len() != 2 {
+        anyhow::bail!("Invalid forge_repo format: {}", link.forge_repo);
+    }
+    let repo = repo::Repo {
+        owner: parts[0].to_string(),
+        name: parts[1].to_string(),
+    };
+
+    let (issue, comments) = tokio::try_join!(forge.get_issue(&repo, number), forge.list_issue_comments(&repo, number))?;
+
+    let conn = db::open()?;
+    db::save_fetched_issue(&conn, &link.forge_repo, &issue, &comments)?;
+    db::touch_repo(&conn, &repo_path)?;
+    Ok((issue, comments.len()))
+}
+
+/// Fetch what a sync would and report how it differs from the cache
+async fn sync_dry_run(forge: &dyn forges::Forge, link: &db::RepoLink, repo: &repo::Repo, json: bool) -> Result<()> {
+    let start = Instant::now();
+    eprintln!("Fetching {} (dry run, the cache is left alone)...", link.forge_repo);
+    let issues = forge.list_issues(repo).await?;
+    let comments = forge.list_all_comments(repo).await?;
+    let goals = forge.list_goals(repo).await?;
+
+    let conn = db::open()?;
+    let diff = db::diff_sync(&conn, &link.forge_repo, &issues, &comments, &goals)?;
+    let elapsed = start.elapsed();
+
+    if json {
+        let mut output = serde_json::to_value(&diff)?;
+        output["elapsed_ms"] = (elapsed.as_millis() as u64).into();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+    if diff.is_empty() {
+        println!("✓ The cache is up to date ({:.2}s)", elapsed.as_secs_f64());
+        return Ok(());
+    }
+
+    // A few examples per kind; the JSON report has them all
+    const SHOWN: usize = 5;
+    let list = |label: &str, issues: &[db::ChangedIssue]| {
+        if issues.is_empty() {
+            return;
+        }
+        println!("{} {}", issues.len(), label);
+        for issue in issues.iter().take(SHOWN) {
+            println!("  {} {}", issue.id, issue.title);
+        }
+        if issues.len() > SHOWN {
+            println!("  … and {} more", issues.len() - SHOWN);
+        }
+    };
+    println!("A sync of {} would change:", link.forge_repo);
+    list("new issues", &diff.new_issues);
+    list("updated", &diff.updated);
+    list("closed upstream", &diff.closed);
+    list("reopened upstream", &diff.reopened);
+    list("removed upstream", &diff.removed);
+    if diff.new_comments > 0 {
+        println!("{} new comments", diff.new_comments);
+    }
+    if !diff.new_goals.is_empty() {
+        println!("{} new goals: {}", diff.new_goals.len(), diff.new_goals.join(", "));
+    }
+    eprintln!("\nNothing written ({:.2}s); run `isq sync` to apply", elapsed.as_secs_f64());
+    Ok(())
+}
+
+async fn cmd_warm(max_age: Duration, jobs: usize, json: bool) -> Result<()> {
+    let start = Instant::now();
+    let now = chrono::Utc::now();
+    let max_age = chrono::Duration::from_std(max_age)?;
+
+    // Several checkouts can share a forge repo; sync each one once, into the
+    // cache it's linked in
+    let mut seen = HashSet::new();
+    let mut fresh = 0;
+    let mut stale = Vec::new();
+    for conn in db::open_all()? {
+        let db_path = std::path::PathBuf::from(conn.path().unwrap_or_default());
+        for watched in db::list_watched_repos(&conn)? {
+            if watched.paused {
+                continue;
             }
-            GoalCommands::Close { name, json } => cmd_goal_close(name, json).await?,
-        },
+            let Some(link) = db::get_repo_link(&conn, &watched.repo)? else {
+                continue;
+            };
+            if !seen.insert(link.forge_repo.clone()) {
+                continue;
+            }
+            match db::last_synced_at(&conn, &link.forge_repo)? {
+                Some(at) if now - at < max_age => fresh += 1,
+                _ => stale.push((db_path.clone(), watched.repo, link)),
+            }
+        }
+    }
+
+    let timeout = config::load()?.network.command_timeout();
+    let results: Vec<(String, Result<db::CacheSize>)> = futures::stream::iter(stale)
+        .map(|(db_path, repo_path, link)| async move {
+            let sync = async {
+                // Each sync gets its own connection; they run interleaved
+                let conn = db::open_at(&db_path)?;
+                let (forge, _) = forges::get_forge_in(&conn, &repo_path)?;
+                if workspace::is_workspace(&link.forge_repo) {
+                    let found = workspace::sync(&conn, forge.as_ref(), &link.forge_repo).await?;
+                    return Ok(db::CacheSize { issues: found as i64, ..Default::default() });
+                }
+                let parts: Vec<&str> = link.forge_repo.split('/').collect();
+                if parts.len() != 2 {
+                    anyhow::bail!("Invalid forge_repo format: {}", link.forge_repo);
+                }
+                let repo = repo::Repo {
+                    owner: parts[0].to_string(),
+                    name: parts[1].to_string(),
+                };
+                sync_forge_repo(forge.as_ref(), &conn, &link, &repo, SyncScope::All).await
+            };
+            let result = network::deadline(timeout, &format!("Syncing {}", link.forge_repo), sync).await;
+            (link.forge_repo, result)
+        })
+        .buffer_unordered(jobs)
+        .collect()
+        .await;
+
+    let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+    if json {
+        let repos: Vec<_> = results
+            .iter()
+            .map(|(repo, result)| match result {
+                Ok(size) => serde_json::json!({
+                    "repo": repo,
+                    "issues": size.issues,
+                    "comments": size.comments,
+                    "goals": size.goals,
+                }),
+                Err(e) => serde_json::json!({ "repo": repo, "error": e.to_string() }),
+            })
+            .collect();
+        let output = serde_json::json!({
+            "fresh": fresh,
+            "synced": repos,
+            "elapsed_ms": start.elapsed().as_millis() as u64,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else if results.is_empty() {
+        println!("✓ All {} repos are fresh", fresh);
+    } else {
+        for (repo, result) in &results {
+            match result {
+                Ok(size) => println!(
+                    "✓ {}: {} issues, {} comments, {} goals",
+                    repo, size.issues, size.comments, size.goals
+                ),
+                Err(e) => println!("✗ {}: {}", repo, e),
+            }
+        }
+        eprintln!(
+            "\nSynced {} repos ({} already fresh) in {:.2}s",
+            results.len() - failed,
+            fresh,
+            start.elapsed().as_secs_f64()
+        );
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{} of {} repos failed to sync", failed, results.len());
+    }
+    Ok(())
+}
+
+/// Fetch issues, comments and goals for a linked repo and replace its cache
+async fn sync_forge_repo(
+    forge: &dyn forges::Forge,
+    conn: &rusqlite::Connection,
+    link: &db::RepoLink,
+    repo: &repo::Repo,
+    scope: SyncScope,
+) -> Result<db::CacheSize> {
+    let mut synced = db::CacheSize::default();
+
+    if scope.includes(SyncScope::Issues) {
+        let started = db::fetch_started();
+        let issues = forge.list_issues(repo).await?;
+        db::save_issues(conn, &link.forge_repo, &issues, &started)?;
+        synced.issues = issues.len() as i64;
+        // The label catalog only adds colors and descriptions, so don't fail the sync
+        match forge.list_labels(repo).await {
+            Ok(labels) => db::save_labels(conn, &link.forge_repo, &labels)?,
+            Err(e) => eprintln!("Skipping labels: {}", e),
+        }
+    }
+    if scope.includes(SyncScope::Comments) {
+        let comments = forge.list_all_comments(repo).await?;
+        db::save_comments(conn, &link.forge_repo, &comments)?;
+        synced.comments = comments.len() as i64;
+    }
+    if scope.includes(SyncScope::Goals) {
+        let goals = forge.list_goals(repo).await?;
+        db::save_goals(conn, &link.forge_repo, &goals)?;
+        synced.goals = goals.len() as i64;
+        match forge.list_objectives(repo).await {
+            Ok(Some(objectives)) => db::save_objectives(conn, &link.forge_repo, &objectives)?,
+            Ok(None) => {}
+            Err(e) => eprintln!("Skipping objectives: {}", e),
+        }
+        match forge.list_cycles(repo).await {
+            Ok(Some(cycles)) => db::save_cycles(conn, &link.forge_repo, &cycles)?,
+            Ok(None) => {}
+            Err(e) => eprintln!("Skipping cycles: {}", e),
+        }
+    }
+    if let Some(budget) = forge.complexity_budget() {
+        db::update_complexity_budget(conn, &link.forge_type, &budget)?;
+    }
+
+    Ok(synced)
+}
+
+/// The current repo's link, if it's a Linear link
+fn current_linear_link(conn: &rusqlite::Connection) -> Result<Option<(String, db::RepoLink)>> {
+    let Ok(repo_path) = repo::detect_repo_path() else {
+        return Ok(None);
+    };
+    Ok(db::get_repo_link(conn, &repo_path)?
+        .filter(|link| link.forge_type == ForgeType::Linear.as_str())
+        .map(|link| (repo_path, link)))
+}
+
+async fn cmd_team_list(json: bool) -> Result<()> {
+    let conn = db::open()?;
+    let current = current_linear_link(&conn)?.map(|(_, link)| link);
+    let client = forges::linear_client(&conn, current.as_ref().and_then(|link| link.account.as_deref()))?;
+    let teams = client.list_teams().await?;
+    let current = current.map(|link| link.forge_repo);
+
+    let mut rows = Vec::new();
+    for team in &teams {
+        let forge_repo = team.forge_repo();
+        let cached = db::cache_size(&conn, &forge_repo)?.issues;
+        rows.push((team, current.as_deref() == Some(forge_repo.as_str()), cached));
+    }
+
+    if json {
+        let output: Vec<_> = rows
+            .iter()
+            .map(|(team, linked, cached)| {
+                serde_json::json!({
+                    "key": team.key,
+                    "name": team.name,
+                    "id": team.id,
+                    "linked": linked,
+                    "cached_issues": cached,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    let key_width = teams.iter().map(|t| t.key.len()).max().unwrap_or(0);
+    for (team, linked, cached) in &rows {
+        let marker = if *linked { "*" } else { " " };
+        let cached = if *cached > 0 { format!(" ({} cached)", cached) } else { String::new() };
+        println!("{} {:width$}  {}{}", marker, team.key, team.name, cached, width = key_width);
+    }
+    if current.is_some() {
+        println!("\n* linked to this repo. Switch with `isq team switch <key>`.");
+    }
+
+    Ok(())
+}
+
+async fn cmd_team_switch(query: String, json: bool) -> Result<()> {
+    let start = Instant::now();
+    let conn = db::open()?;
+
+    let (repo_path, link) = match current_linear_link(&conn)? {
+        Some(current) => current,
+        None => {
+            let repo_path = repo::detect_repo_path()?;
+            return match db::get_repo_link(&conn, &repo_path)? {
+                Some(link) => Err(error::err(
+                    ErrorKind::Usage,
+                    format!("`isq team` only applies to Linear links (this repo is linked to {})", link.forge_type),
+                )),
+                None => Err(not_linked_error()),
+            };
+        }
+    };
+
+    let client = forges::linear_client(&conn, link.account.as_deref())?;
+    let teams = client.list_teams().await?;
+    let team = forges::find_team(&teams, &query)?;
+    let forge_repo = team.forge_repo();
+
+    if forge_repo == link.forge_repo {
+        println!("Already on {} ({})", team.name, team.key);
+        return Ok(());
+    }
+
+    // Queued writes stay keyed to the old team and replay once it's linked again
+    let stranded = db::ops_at_risk(&conn, &repo_path)?.len();
+
+    let org = client.get_organization().await?;
+    let display_name = format!("{}/{}", org.url_key, team.key);
+    db::set_repo_link(&conn, &repo_path, &link.forge_type, &forge_repo, Some(&display_name))?;
+    let link = db::get_repo_link(&conn, &repo_path)?.ok_or_else(not_linked_error)?;
+
+    eprintln!("Syncing {}...", team.name);
+    let pseudo_repo = repo::Repo {
+        owner: team.key.clone(),
+        name: team.id.clone(),
+    };
+    let synced = sync_forge_repo(&client, &conn, &link, &pseudo_repo, SyncScope::All).await?;
+    let elapsed = start.elapsed();
+
+    if json {
+        let output = serde_json::json!({
+            "team": team.key,
+            "name": team.name,
+            "forge_repo": forge_repo,
+            "issues": synced.issues,
+            "comments": synced.comments,
+            "goals": synced.goals,
+            "stranded_ops": stranded,
+            "elapsed_ms": elapsed.as_millis() as u64,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        println!(
+            "✓ Switched to {} ({}), synced {} issues ({:.0}ms)",
+            team.name, team.key, synced.issues, elapsed.as_millis()
+        );
+        if stranded > 0 {
+            println!(
+                "  {} queued operations for the previous team will sync when you switch back",
+                stranded
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// How `issue list` presents its results
+struct ListOutput {
+    columns: Vec<display::Column>,
+    watch: bool,
+    ranked: bool,
+    smart: bool,
+    json: bool,
+    fields: Vec<String>,
+    compact: bool,
+}
+
+/// `issue list` label filters: `--label` (all of), `--any-label`, `--not-label`
+struct LabelFilters {
+    all: Vec<String>,
+    any: Vec<String>,
+    not: Vec<String>,
+}
+
+/// `issue list` filters other than `--remote`
+struct ListFilters {
+    labels: LabelFilters,
+    state: Option<String>,
+    goal: Option<String>,
+    assignee: Option<String>,
+    participant: Option<String>,
+    stale: Option<Duration>,
+    issue_type: Option<String>,
+    sla_breaching: Option<Duration>,
+    unanswered: bool,
+    no_exclude: bool,
+}
+
+async fn cmd_issue_list(remote: Option<String>, filters: ListFilters, output: ListOutput) -> Result<()> {
+    let ListFilters { labels, state, goal, assignee, participant, stale, issue_type, sla_breaching, unanswered, no_exclude } = filters;
+    let start = Instant::now();
+
+    let repo_path = repo::detect_repo_path()?;
+    let cache = db::open()?;
+
+    // Check if repo is linked
+    let mut link = db::get_repo_link(&cache, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    // `--remote` filters a scratch cache holding just the other repo
+    let is_remote = remote.is_some();
+    let scratch;
+    let conn = match remote {
+        Some(name) => {
+            scratch = fetch_remote_issues(&repo_path, &link, &name, goal.is_some()).await?;
+            link.forge_repo = name;
+            &scratch
+        }
+        None => &cache,
+    };
+
+    // Auto-sync if no cached data
+    let sync_state = db::get_sync_state(conn, &link.forge_repo)?;
+    if sync_state.is_none() && !is_remote {
+        eprintln!("No cache for {}. Syncing...", link.forge_repo);
+        let (forge, _) = get_forge_for_repo(&repo_path)?;
+
+        // Parse forge_repo to create Repo struct
+        let parts: Vec<&str> = link.forge_repo.split('/').collect();
+        if parts.len() == 2 {
+            let repo = repo::Repo {
+                owner: parts[0].to_string(),
+                name: parts[1].to_string(),
+            };
+            let started = db::fetch_started();
+            let issues = forge.list_issues(&repo).await?;
+            db::save_issues(conn, &link.forge_repo, &issues, &started)?;
+            eprintln!("✓ Synced {} issues", issues.len());
+        }
+    }
+
+    // Touch repo to update last_accessed for daemon priority
+    db::touch_repo(&cache, &repo_path)?;
+
+    // Resolve goal against cached goal names (case-insensitive)
+    let goal = match goal {
+        Some(name) => Some(
+            db::resolve_goal_name(conn, &link.forge_repo, &name)?
+                .ok_or_else(|| error::err(ErrorKind::NotFound, format!("Goal '{}' not found. Run `isq sync` to refresh.", name)))?,
+        ),
+        None => None,
+    };
+
+    let assignee = match assignee {
+        Some(a) if a == ME => {
+            let (forge, _) = get_forge_for_repo(&repo_path)?;
+            Some(resolve_me(&cache, forge.as_ref(), &link.identity_key()).await?)
+        }
+        other => other,
+    };
+    let participant = match participant {
+        Some(p) if p == ME => {
+            let (forge, _) = get_forge_for_repo(&repo_path)?;
+            Some(resolve_me(&cache, forge.as_ref(), &link.identity_key()).await?)
+        }
+        other => other,
+    };
+
+    let config = config::load().unwrap_or_default();
+    let mut filter = db::IssueFilter {
+        labels: labels.all,
+        any_labels: labels.any,
+        not_labels: labels.not,
+        state,
+        goal,
+        assignee,
+        participant,
+        updated_before: stale.map(updated_before),
+        sla_breaches_before: sla_breaching.map(sla_breaches_before),
+        unanswered: unanswered.then(|| config.list.maintainers.clone()),
+        type_labels: issue_type.as_deref().map(|t| config.types.labels_for(t)).unwrap_or_default(),
+        issue_type,
+        ..Default::default()
+    };
+    if !no_exclude {
+        let list = config.list;
+        filter.exclude_authors = list.exclude_authors;
+        // Asking for a label explicitly wins over excluding it
+        filter.exclude_labels = list
+            .exclude_labels
+            .into_iter()
+            .filter(|l| !filter.labels.iter().chain(&filter.any_labels).any(|wanted| wanted.eq_ignore_ascii_case(l)))
+            .collect();
+    }
+    if output.watch {
+        return watch_issue_list(conn, &link, &filter, &config.types, &output.columns).await;
+    }
+
+    let mut issues = db::load_issues_matching(conn, &link.forge_repo, &filter)?;
+    config.types.apply(&mut issues);
+    if filter.sla_breaches_before.is_some() {
+        issues.sort_by(|a, b| a.sla_breaches_at.cmp(&b.sla_breaches_at));
+    }
+    let ranks: std::collections::HashMap<u64, usize> = if output.ranked {
+        let ranking = db::load_ranking(conn, &link.forge_repo)?;
+        ranking.into_iter().enumerate().map(|(i, n)| (n, i + 1)).collect()
+    } else {
+        Default::default()
+    };
+    // Stable, so unranked issues keep their usual order after the ranked ones
+    issues.sort_by_key(|i| ranks.get(&i.number).copied().unwrap_or(usize::MAX));
+    if output.smart {
+        let login = match db::get_identity(&cache, &link.identity_key())? {
+            Some(login) => Some(login),
+            None => match get_forge_for_repo(&repo_path) {
+                Ok((forge, _)) => resolve_me(&cache, forge.as_ref(), &link.identity_key())
+                    .await
+                    .inspect_err(|e| eprintln!("warning: sorting without your assignments: {}", e))
+                    .ok(),
+                Err(_) => None,
+            },
+        };
+        let commented = match &login {
+            Some(login) => db::issues_commented_by(conn, &link.forge_repo, login)?,
+            None => Default::default(),
+        };
+        let me = relevance::Me { login: login.as_deref(), commented };
+        relevance::sort(&mut issues, &me, chrono::Utc::now());
+    }
+    let comment_counts = db::count_comments_by_issue(conn, &link.forge_repo)?;
+    let pending = db::pending_ops_by_issue(conn, &link.forge_repo)?;
+    let elapsed = start.elapsed();
+
+    if output.json {
+        let mut values = Vec::with_capacity(issues.len());
+        for issue in &issues {
+            let mut value = serde_json::to_value(IssueJson::new(issue, &pending))?;
+            if output.ranked {
+                value["rank"] = serde_json::json!(ranks.get(&issue.number));
+            }
+            values.push(display::shape_issue_json(value, &output.fields, output.compact));
+        }
+        println!("{}", serde_json::to_string_pretty(&values)?);
+    } else {
+        print_issues(&issues, &comment_counts, &pending.keys().copied().collect(), &output.columns);
+        let hidden = if filter.has_exclusions() {
+            let unfiltered = db::IssueFilter { exclude_authors: Vec::new(), exclude_labels: Vec::new(), ..filter.clone() };
+            db::load_issues_matching(conn, &link.forge_repo, &unfiltered)?.len() - issues.len()
+        } else {
+            0
+        };
+        if output.ranked {
+            let ranked = issues.iter().filter(|i| ranks.contains_key(&i.number)).count();
+            eprintln!("\n{} ranked, then {} unranked", ranked, issues.len() - ranked);
+        }
+        if hidden > 0 {
+            eprintln!(
+                "\n{} issues in {:.0}ms ({} hidden by exclusions; --no-exclude to show)",
+                issues.len(),
+                elapsed.as_millis(),
+                hidden
+            );
+        } else {
+            eprintln!("\n{} issues in {:.0}ms", issues.len(), elapsed.as_millis());
+        }
     }
 
     Ok(())
 }
 
-async fn cmd_link(forge_name: Option<&str>, opts: Vec<String>) -> Result<()> {
+/// Fetch another repo's issues (and goals, for `--goal`) with the linked
+/// repo's forge and credentials, into a scratch cache that's never written to disk
+async fn fetch_remote_issues(
+    repo_path: &str,
+    link: &db::RepoLink,
+    name: &str,
+    with_goals: bool,
+) -> Result<rusqlite::Connection> {
+    if link.forge_type == ForgeType::Linear.as_str() {
+        return Err(error::err(
+            ErrorKind::Usage,
+            "--remote works with GitHub-style owner/name repos. For another Linear team, see `isq team list`.",
+        ));
+    }
+    let remote = match name.split_once('/') {
+        Some((owner, repo)) if !owner.is_empty() && !repo.is_empty() && !repo.contains('/') => {
+            repo::Repo { owner: owner.to_string(), name: repo.to_string() }
+        }
+        _ => return Err(error::err(ErrorKind::Usage, format!("Expected --remote owner/name, got '{}'", name))),
+    };
+
+    let (forge, _) = get_forge_for_repo(repo_path)?;
+    eprintln!("Fetching {}...", name);
+    let conn = db::open_scratch()?;
+    let started = db::fetch_started();
+    db::save_issues(&conn, name, &forge.list_issues(&remote).await?, &started)?;
+    if with_goals {
+        db::save_goals(&conn, name, &forge.list_goals(&remote).await?)?;
+    }
+    Ok(conn)
+}
+
+/// Cutoff timestamp for issues untouched for `age`
+fn updated_before(age: Duration) -> String {
+    let cutoff = chrono::Duration::from_std(age)
+        .ok()
+        .and_then(|age| chrono::Utc::now().checked_sub_signed(age))
+        .unwrap_or(chrono::DateTime::<chrono::Utc>::MIN_UTC);
+    cutoff.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// Cutoff for `--sla-breaching`: `within` from now
+fn sla_breaches_before(within: Duration) -> String {
+    let cutoff = chrono::Duration::from_std(within)
+        .ok()
+        .and_then(|within| chrono::Utc::now().checked_add_signed(within))
+        .unwrap_or(chrono::DateTime::<chrono::Utc>::MAX_UTC);
+    cutoff.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// List stale open issues; with `--comment`/`--close`, act on each of them.
+/// Once the forge is unreachable the remaining writes are queued.
+async fn cmd_issue_stale(
+    older_than: Duration,
+    labels: Vec<String>,
+    comment: Option<String>,
+    close: bool,
+    json: bool,
+) -> Result<()> {
+    let start = Instant::now();
     let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
 
-    // Require forge name
-    let forge_name = forge_name.ok_or_else(|| {
-        let forges: Vec<_> = ALL_FORGE_TYPES.iter().map(|f| format!("  isq link {}", f.as_str())).collect();
-        anyhow::anyhow!("Missing forge name.\n\nRun one of:\n{}", forges.join("\n"))
-    })?;
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
 
-    // Parse forge type
-    let forge_type = ForgeType::from_str(forge_name).ok_or_else(|| {
-        let forges: Vec<_> = ALL_FORGE_TYPES.iter().map(|f| format!("  isq link {}", f.as_str())).collect();
-        anyhow::anyhow!("Unknown forge: {}\n\nRun one of:\n{}", forge_name, forges.join("\n"))
-    })?;
+    db::touch_repo(&conn, &repo_path)?;
 
-    // Parse options
-    let args = LinkArgs::parse(&opts)?;
+    let filter = db::IssueFilter {
+        labels,
+        state: Some("open".to_string()),
+        updated_before: Some(updated_before(older_than)),
+        ..Default::default()
+    };
+    let issues = db::load_issues_matching(&conn, &link.forge_repo, &filter)?;
 
-    // Run forge-specific link flow
-    let result = forge_type.link(&repo_path, &args).await?;
+    if comment.is_none() && !close {
+        if json {
+            println!("{}", serde_json::to_string_pretty(&issues)?);
+        } else {
+            let comment_counts = db::count_comments_by_issue(&conn, &link.forge_repo)?;
+            let pending = db::pending_ops_by_issue(&conn, &link.forge_repo)?;
+            print_issues(&issues, &comment_counts, &pending.keys().copied().collect(), &[]);
+            eprintln!("\n{} stale issues in {:.0}ms", issues.len(), start.elapsed().as_millis());
+        }
+        return Ok(());
+    }
 
-    // Start background service
-    println!();
-    ensure_service_running()?;
-    println!("\n✓ Linked to {} ({})", forge_type.auth().display_name, result.display_name);
+    let (forge, _) = get_forge_for_repo(&repo_path)?;
+    let parts: Vec<&str> = link.forge_repo.split('/').collect();
+    if parts.len() != 2 {
+        anyhow::bail!("Invalid forge_repo format: {}", link.forge_repo);
+    }
+    let repo = repo::Repo {
+        owner: parts[0].to_string(),
+        name: parts[1].to_string(),
+    };
 
-    Ok(())
-}
+    let mut results = Vec::new();
+    let mut offline = false;
+    for issue in &issues {
+        let id = issue.number;
+        let mut done = Vec::new();
+
+        if let Some(body) = &comment {
+            let client_key = forges::new_client_key();
+            if !offline {
+                match network::write(forge.create_keyed_comment(&repo, id, body, &client_key)).await {
+                    Ok(()) => done.push("commented"),
+                    Err(e) if is_offline_error(&e) => offline = true,
+                    Err(e) => return Err(e),
+                }
+            }
+            if offline {
+                let payload = serde_json::json!({ "issue_number": id, "body": body, "client_key": client_key });
+                db::queue_op(&conn, &link.forge_repo, "comment", &payload.to_string())?;
+                done.push("queued comment");
+            }
+            cache_comment(&conn, &link, id, body)?;
+        }
 
-/// Ensure the system service is installed and running
-fn ensure_service_running() -> Result<()> {
-    let status = service::status()?;
+        if close {
+            if !offline {
+                match network::write(forge.close_issue(&repo, id)).await {
+                    Ok(()) => done.push("closed"),
+                    Err(e) if is_offline_error(&e) => offline = true,
+                    Err(e) => return Err(e),
+                }
+            }
+            if offline {
+                let payload = serde_json::json!({ "issue_number": id });
+                db::queue_op(&conn, &link.forge_repo, "close", &payload.to_string())?;
+                done.push("queued close");
+            }
+            db::set_issue_state(&conn, &link.forge_repo, id, "closed")?;
+        }
 
-    if !status.installed {
-        println!("Installing system service...");
-        service::install()?;
-        println!("✓ System service installed");
-    } else if !status.running {
-        service::start()?;
-        println!("✓ System service started");
-    } else if let Some(pid) = status.pid {
-        println!("System service running (PID {})", pid);
+        let message = format!("#{}: {}", id, done.join(", "));
+        if !json {
+            println!("✓ {}", message);
+        }
+        results.push(WriteResult {
+            success: true,
+            queued: offline,
+            issue_number: Some(id),
+            message,
+            elapsed_ms: start.elapsed().as_millis() as u64,
+        });
     }
 
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else {
+        eprintln!("\n{} stale issues in {:.0}ms", results.len(), start.elapsed().as_millis());
+    }
+
+    if offline {
+        return Err(queued_error());
+    }
     Ok(())
 }
 
-fn cmd_unlink() -> Result<()> {
+/// How often `issue list --watch` checks the cache for changes
+const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Redraw the issue list whenever another process (usually the daemon) writes
+/// to the cache. Runs until interrupted.
+async fn watch_issue_list(
+    conn: &rusqlite::Connection,
+    link: &db::RepoLink,
+    filter: &db::IssueFilter,
+    types: &issue_types::TypesConfig,
+    columns: &[display::Column],
+) -> Result<()> {
+    let name = link.display_name.as_deref().unwrap_or(&link.forge_repo);
+    loop {
+        let version = db::data_version(conn)?;
+
+        let mut issues = db::load_issues_matching(conn, &link.forge_repo, filter)?;
+        types.apply(&mut issues);
+        let comment_counts = db::count_comments_by_issue(conn, &link.forge_repo)?;
+        let pending = db::pending_ops_by_issue(conn, &link.forge_repo)?;
+
+        // Clear the screen and move the cursor home
+        print!("\x1b[2J\x1b[H");
+        print_issues(&issues, &comment_counts, &pending.keys().copied().collect(), columns);
+        println!(
+            "\n{} issues · {} · updated {} · Ctrl-C to exit",
+            issues.len(),
+            name,
+            timefmt::FORMAT.time_with_seconds(chrono::Utc::now())
+        );
+
+        while db::data_version(conn)? == version {
+            tokio::time::sleep(WATCH_POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// Poll the cache until issue `id` is in `state` or gets a comment, asking the
+/// daemon to sync right away so the wait doesn't start with a full interval
+async fn cmd_issue_await(id: u64, state: Option<String>, comment: bool, timeout: Duration, json: bool) -> Result<()> {
+    let start = Instant::now();
     let repo_path = repo::detect_repo_path()?;
     let conn = db::open()?;
 
-    // Check if linked
-    let link = db::get_repo_link(&conn, &repo_path)?;
-    if link.is_none() {
-        println!("This repo is not linked to any issue tracker.");
-        return Ok(());
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    db::touch_repo(&conn, &repo_path)?;
+    let not_found = || error::err(ErrorKind::NotFound, format!("Issue #{} not found in cache. Run `isq sync` to refresh.", id));
+    db::load_issue(&conn, &link.forge_repo, id)?.ok_or_else(not_found)?;
+    let (_, seen_comments) = db::load_latest_comments(&conn, &link.forge_repo, id, Some(0))?;
+
+    if !service::status().map(|s| s.running).unwrap_or(false) {
+        eprintln!("warning: the daemon isn't running, so the cache won't change (start it with `isq daemon start`)");
     }
+    db::request_sync(&db::open_shared()?, &repo_path)?;
 
-    let link = link.unwrap();
-    db::remove_repo_link(&conn, &repo_path)?;
-    db::remove_watched_repo(&conn, &repo_path)?;
+    let (issue, new_comment) = loop {
+        let version = db::data_version(&conn)?;
+        let issue = db::load_issue(&conn, &link.forge_repo, id)?.ok_or_else(not_found)?;
+        if state.as_deref().is_some_and(|s| s == issue.state) {
+            break (issue, None);
+        }
+        if comment {
+            let (latest, total) = db::load_latest_comments(&conn, &link.forge_repo, id, Some(1))?;
+            if total > seen_comments {
+                break (issue, latest.into_iter().next());
+            }
+        }
 
-    println!("✓ Unlinked from {} ({})", link.forge_type, link.forge_repo);
+        while db::data_version(&conn)? == version {
+            if start.elapsed() >= timeout {
+                let waiting_for = match (&state, comment) {
+                    (Some(state), true) => format!("to be {} or get a comment", state),
+                    (Some(state), false) => format!("to be {}", state),
+                    (None, _) => "to get a comment".to_string(),
+                };
+                return Err(error::err(
+                    ErrorKind::Timeout,
+                    format!("Timed out after {}s waiting for #{} {}", start.elapsed().as_secs(), id, waiting_for),
+                ));
+            }
+            tokio::time::sleep(WATCH_POLL_INTERVAL).await;
+        }
+    };
+    let elapsed = start.elapsed();
 
-    // Check if any repos left - if not, uninstall service
-    let remaining = db::list_watched_repos(&conn)?;
-    if remaining.is_empty() {
-        println!();
-        service::uninstall()?;
-        println!("✓ System service removed (no repos to watch)");
+    if json {
+        let result = serde_json::json!({
+            "issue_number": id,
+            "state": issue.state,
+            "matched": if new_comment.is_some() { "comment" } else { "state" },
+            "comment": new_comment.as_ref().map(|c| serde_json::json!({
+                "id": c.comment_id,
+                "body": c.body,
+                "author": c.author,
+                "created_at": c.created_at,
+            })),
+            "elapsed_ms": elapsed.as_millis() as u64,
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else if let Some(c) = new_comment {
+        println!("✓ New comment on #{} from {} ({}s):\n{}", id, c.author, elapsed.as_secs(), c.body);
+    } else {
+        println!("✓ #{} {} is {} ({}s)", id, issue.title, issue.state, elapsed.as_secs());
     }
-
     Ok(())
 }
 
-fn cmd_status() -> Result<()> {
-    // Auth status
-    println!("Authentication:");
+fn cmd_issue_show(id: u64, comment_limit: CommentLimit, json_output: bool, fields: &[String]) -> Result<()> {
+    let start = Instant::now();
 
-    for forge_type in ALL_FORGE_TYPES {
-        let auth = forge_type.auth();
-        print!("  {:10}", auth.display_name);
-        if auth.has_credentials() {
-            println!("ready");
-        } else {
-            println!("not configured (run: {})", auth.link_command);
-        }
-    }
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
 
-    // Current repo link (if in a git repo)
-    println!();
-    match repo::detect_repo_path() {
-        Ok(repo_path) => {
-            let conn = db::open()?;
-            match db::get_repo_link(&conn, &repo_path)? {
-                Some(link) => {
-                    let display = link.display_name.as_deref().unwrap_or(&link.forge_repo);
-                    println!("This repo:");
-                    println!("  Linked to {} ({})", display, link.forge_type);
+    // Check if repo is linked
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
 
-                    // Show sync state
-                    if let Some((last_sync, count)) = db::get_sync_state(&conn, &link.forge_repo)? {
-                        println!("  {} issues cached ({})", count, last_sync);
-                    }
+    // Touch repo to update last_accessed for daemon priority
+    db::touch_repo(&conn, &repo_path)?;
 
-                    // Show pending ops
-                    let pending = db::count_pending_ops(&conn, &link.forge_repo)?;
-                    if pending > 0 {
-                        println!("  {} pending operations", pending);
-                    }
+    let mut issue = db::load_issue(&conn, &link.forge_repo, id)?;
+    config::load().unwrap_or_default().types.apply(issue.as_mut_slice());
+    let (comments, total_comments) = db::load_latest_comments(&conn, &link.forge_repo, id, comment_limit.max())?;
+    let pending = db::pending_ops_by_issue(&conn, &link.forge_repo)?;
+    let backlinks = db::load_backlinks(&conn, &link.forge_repo, id)?;
+    let subscribed = db::get_subscription(&conn, &link.forge_repo, id)?;
+    let participants = db::load_participants(&conn, &link.forge_repo, id)?;
+    let elapsed = start.elapsed();
 
-                    // Show rate limit status
-                    if let Some(state) = db::get_rate_limit_state(&conn, &link.forge_type)? {
-                        if let Some(reset_at) = state.reset_at {
-                            let now = std::time::SystemTime::now()
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs() as i64;
-                            if now < reset_at {
-                                let wait_secs = reset_at - now;
-                                // Convert to local time, 12-hour format like macOS default
-                                let reset_time = chrono::DateTime::from_timestamp(reset_at, 0)
-                                    .map(|dt| {
-                                        use chrono::Local;
-                                        let local: chrono::DateTime<Local> = dt.into();
-                                        local.format("%-I:%M %p").to_string()
-                                    })
-                                    .unwrap_or_else(|| format!("{}s", wait_secs));
-                                println!("  ⚠️  Rate limited until {}", reset_time);
-                            }
+    match issue {
+        Some(issue) => {
+            if json_output {
+                let issue_json = serde_json::to_value(IssueJson::new(&issue, &pending))?;
+                let comments_json: Vec<_> = comments.iter().map(|c| {
+                    serde_json::json!({
+                        "id": c.comment_id,
+                        "body": c.body,
+                        "author": c.author,
+                        "created_at": c.created_at
+                    })
+                }).collect();
+
+                let output = if fields.is_empty() {
+                    // Include comments in JSON output
+                    serde_json::json!({
+                        "issue": issue_json,
+                        "comments": comments_json,
+                        "total_comments": total_comments,
+                        "referenced_by": backlinks,
+                        "subscribed": subscribed,
+                        "participants": participants,
+                    })
+                } else {
+                    // Projection: issue fields and comments side by side, nothing else
+                    let mut flat = display::shape_issue_json(issue_json, fields, false);
+                    if let Some(map) = flat.as_object_mut() {
+                        if fields.iter().any(|f| f == "comments") {
+                            map.insert("comments".to_string(), comments_json.into());
+                        }
+                        if fields.iter().any(|f| f == "total_comments") {
+                            map.insert("total_comments".to_string(), total_comments.into());
+                        }
+                        if fields.iter().any(|f| f == "referenced_by") {
+                            map.insert("referenced_by".to_string(), serde_json::to_value(&backlinks)?);
+                        }
+                        if fields.iter().any(|f| f == "subscribed") {
+                            map.insert("subscribed".to_string(), subscribed.into());
+                        }
+                        if fields.iter().any(|f| f == "participants") {
+                            map.insert("participants".to_string(), participants.clone().into());
                         }
                     }
-                }
-                None => {
-                    println!("This repo:");
-                    println!("  Not linked (run: isq link <forge>)");
-                }
+                    flat
+                };
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else {
+                // Use styled display
+                let issue_pending = pending.get(&id).map(Vec::as_slice).unwrap_or_default();
+                let extras = display::IssueExtras {
+                    pending: issue_pending,
+                    backlinks: &backlinks,
+                    subscribed,
+                    participants: &participants,
+                };
+                display::print_issue(&issue, &comments, total_comments, &extras, elapsed.as_millis() as u64);
             }
         }
-        Err(_) => {
-            println!("Not in a git repository");
+        None => {
+            return Err(error::err(
+                ErrorKind::NotFound,
+                format!("Issue #{} not found in cache. Run `isq sync` to refresh.", id),
+            ));
         }
     }
 
-    // Service status
-    println!();
-    print!("Service:    ");
-    let svc_status = service::status()?;
-    if !svc_status.installed {
-        println!("not installed");
-    } else if let Some(pid) = svc_status.pid {
-        println!("running (PID {})", pid);
-    } else {
-        println!("installed but not running");
-    }
-
     Ok(())
 }
 
-async fn cmd_sync() -> Result<()> {
+/// Move an issue in the repo's local ranking (`issue list --ranked`)
+fn cmd_issue_rank(id: u64, mv: db::RankMove, json: bool) -> Result<()> {
     let repo_path = repo::detect_repo_path()?;
-    let (forge, link) = get_forge_for_repo(&repo_path)?;
+    let conn = db::open()?;
 
-    // Parse forge_repo to create Repo struct
-    let parts: Vec<&str> = link.forge_repo.split('/').collect();
-    if parts.len() != 2 {
-        anyhow::bail!("Invalid forge_repo format: {}", link.forge_repo);
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    db::touch_repo(&conn, &repo_path)?;
+
+    // Removing needs no cached issue, so stale entries can be cleaned up
+    let issue = db::load_issue(&conn, &link.forge_repo, id)?;
+    if issue.is_none() && mv != db::RankMove::Remove {
+        return Err(error::err(
+            ErrorKind::NotFound,
+            format!("Issue #{} not found in cache. Run `isq sync` to refresh.", id),
+        ));
     }
-    let repo = repo::Repo {
-        owner: parts[0].to_string(),
-        name: parts[1].to_string(),
-    };
 
-    eprintln!("Syncing {}...", link.forge_repo);
-    let start = Instant::now();
+    let position = db::rank_issue(&conn, &link.forge_repo, id, mv)?;
+    let total = db::load_ranking(&conn, &link.forge_repo)?.len();
 
-    let issues = forge.list_issues(&repo).await?;
-    let comments = forge.list_all_comments(&repo).await?;
-    let goals = forge.list_goals(&repo).await?;
-    let fetch_time = start.elapsed();
+    if json {
+        let result = serde_json::json!({
+            "issue_number": id,
+            "rank": position,
+            "ranked": total,
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
+
+    match position {
+        Some(rank) => println!("✓ Ranked #{} at {} of {}", id, rank, total),
+        None => println!("✓ Removed #{} from the ranking", id),
+    }
+    Ok(())
+}
 
+fn cmd_issue_current(id: Option<u64>, clear: bool, json: bool) -> Result<()> {
+    let worktree = repo::current_worktree()?;
     let conn = db::open()?;
-    db::save_issues(&conn, &link.forge_repo, &issues)?;
-    db::save_comments(&conn, &link.forge_repo, &comments)?;
-    db::save_goals(&conn, &link.forge_repo, &goals)?;
 
-    // Touch repo to update last_accessed
-    db::touch_repo(&conn, &repo_path)?;
+    let link = db::get_repo_link(&conn, &worktree.repo_path)?
+        .ok_or_else(not_linked_error)?;
 
-    println!(
-        "✓ Synced {} issues, {} comments, and {} goals in {:.2}s",
-        issues.len(),
-        comments.len(),
-        goals.len(),
-        fetch_time.as_secs_f64()
-    );
+    db::touch_repo(&conn, &worktree.repo_path)?;
+
+    let branch = worktree.branch.as_deref().ok_or_else(|| {
+        error::err(ErrorKind::Usage, "HEAD is detached; check out a branch to track its issue")
+    })?;
+
+    if clear {
+        let cleared = db::clear_branch_issue(&conn, &link.forge_repo, branch)?;
+        if json {
+            println!("{}", serde_json::json!({ "branch": branch, "cleared": cleared }));
+        } else if cleared {
+            println!("✓ Cleared the issue for branch {}", branch);
+        } else {
+            println!("No issue set for branch {}", branch);
+        }
+        return Ok(());
+    }
+
+    // A set association wins; otherwise go by a number in the branch name
+    let (number, source) = match id {
+        Some(id) => {
+            db::set_branch_issue(&conn, &link.forge_repo, branch, id)?;
+            (Some(id), "set")
+        }
+        None => match db::get_branch_issue(&conn, &link.forge_repo, branch)? {
+            Some(id) => (Some(id), "set"),
+            None => (repo::issue_from_branch(branch), "branch_name"),
+        },
+    };
+    let issue = match number {
+        Some(n) => db::load_issue(&conn, &link.forge_repo, n)?,
+        None => None,
+    };
+
+    if json {
+        let result = serde_json::json!({
+            "branch": branch,
+            "worktree": worktree.path,
+            "issue_number": number,
+            "source": number.map(|_| source),
+            "issue": issue,
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
 
+    let Some(number) = number else {
+        println!("No issue for branch {}. Set one with `isq issue current <id>`.", branch);
+        return Ok(());
+    };
+    if id.is_some() {
+        print!("✓ ");
+    }
+    match &issue {
+        Some(issue) => println!("#{} {} [{}]", number, issue.title, issue.state),
+        None => println!("#{} (not in cache)", number),
+    }
+    let via = if source == "set" { "" } else { ", from the branch name" };
+    println!("  branch {}{}", branch, via);
     Ok(())
 }
 
-async fn cmd_issue_list(
-    label: Option<String>,
-    state: Option<String>,
-    json_output: bool,
-) -> Result<()> {
-    let start = Instant::now();
-
+fn cmd_issue_url(id: u64, copy: bool, json: bool) -> Result<()> {
     let repo_path = repo::detect_repo_path()?;
     let conn = db::open()?;
 
-    // Check if repo is linked
     let link = db::get_repo_link(&conn, &repo_path)?
         .ok_or_else(not_linked_error)?;
 
-    // Auto-sync if no cached data
-    let sync_state = db::get_sync_state(&conn, &link.forge_repo)?;
-    if sync_state.is_none() {
-        eprintln!("No cache for {}. Syncing...", link.forge_repo);
-        let (forge, _) = get_forge_for_repo(&repo_path)?;
+    db::touch_repo(&conn, &repo_path)?;
 
-        // Parse forge_repo to create Repo struct
-        let parts: Vec<&str> = link.forge_repo.split('/').collect();
-        if parts.len() == 2 {
-            let repo = repo::Repo {
-                owner: parts[0].to_string(),
-                name: parts[1].to_string(),
-            };
-            let issues = forge.list_issues(&repo).await?;
-            db::save_issues(&conn, &link.forge_repo, &issues)?;
-            eprintln!("✓ Synced {} issues", issues.len());
+    let issue = db::load_issue(&conn, &link.forge_repo, id)?.ok_or_else(|| {
+        error::err(
+            ErrorKind::NotFound,
+            format!("Issue #{} not found in cache. Run `isq sync` to refresh.", id),
+        )
+    })?;
+
+    // Older caches predate issue URLs; GitHub's are predictable
+    let url = match issue.url {
+        Some(url) => url,
+        None if link.forge_type == ForgeType::GitHub.as_str() => {
+            format!("https://github.com/{}/issues/{}", link.forge_repo, id)
         }
-    }
+        None => {
+            return Err(error::err(
+                ErrorKind::NotFound,
+                format!("No URL cached for #{}. Run `isq sync` to refresh.", id),
+            ))
+        }
+    };
+
+    print_url(&url, copy, json)
+}
+
+fn cmd_goal_url(name: String, copy: bool, json: bool) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
 
-    // Touch repo to update last_accessed for daemon priority
     db::touch_repo(&conn, &repo_path)?;
 
-    let issues = db::load_issues_filtered(
-        &conn,
-        &link.forge_repo,
-        label.as_deref(),
-        state.as_deref(),
-    )?;
-    let comment_counts = db::count_comments_by_issue(&conn, &link.forge_repo)?;
-    let elapsed = start.elapsed();
+    let goal = db::load_goal_by_name(&conn, &link.forge_repo, &name)?
+        .ok_or_else(|| error::err(ErrorKind::NotFound, format!("Goal '{}' not found. Run `isq sync` to refresh.", name)))?;
+    let url = goal.html_url.ok_or_else(|| {
+        error::err(ErrorKind::NotFound, format!("No URL cached for goal '{}'. Run `isq sync` to refresh.", goal.name))
+    })?;
+
+    print_url(&url, copy, json)
+}
+
+/// Print a URL, or copy it to the clipboard with `--copy`
+fn print_url(url: &str, copy: bool, json: bool) -> Result<()> {
+    if copy {
+        copy_to_clipboard(url)?;
+    }
 
-    if json_output {
-        println!("{}", serde_json::to_string_pretty(&issues)?);
+    if json {
+        let json = serde_json::json!({ "url": url, "copied": copy });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+    } else if copy {
+        println!("✓ Copied {}", url);
     } else {
-        print_issues(&issues, &comment_counts);
-        eprintln!("\n{} issues in {:.0}ms", issues.len(), elapsed.as_millis());
+        println!("{}", url);
     }
 
     Ok(())
 }
 
-fn cmd_issue_show(id: u64, json_output: bool) -> Result<()> {
-    let start = Instant::now();
+/// Put `text` on the clipboard
+#[cfg(not(target_os = "linux"))]
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|e| anyhow::anyhow!("Couldn't copy to the clipboard: {}", e))
+}
+
+/// Put `text` on the clipboard. On X11 and Wayland the clipboard is served by
+/// whoever set it, for as long as they run, so this hands it to a tool that
+/// stays behind to do that after isq exits.
+#[cfg(target_os = "linux")]
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let tools: [(&str, &[&str]); 3] = [
+        ("wl-copy", &[]),
+        ("xclip", &["-selection", "clipboard"]),
+        ("xsel", &["--clipboard", "--input"]),
+    ];
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    for (tool, args) in tools {
+        // X tools still work under Wayland through XWayland
+        if tool == "wl-copy" && !wayland {
+            continue;
+        }
+        // The tool forks to keep serving the clipboard; don't let it hold our output open
+        let child = Command::new(tool)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => anyhow::bail!("Couldn't copy to the clipboard: {}: {}", tool, e),
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        let status = child.wait()?;
+        if !status.success() {
+            anyhow::bail!("Couldn't copy to the clipboard: {} exited with {}", tool, status);
+        }
+        return Ok(());
+    }
+    let tools = if wayland { "wl-clipboard, xclip or xsel" } else { "xclip or xsel" };
+    anyhow::bail!("Couldn't copy to the clipboard: install {}", tools)
+}
 
+fn cmd_issue_context(id: u64, max_tokens: Option<usize>) -> Result<()> {
     let repo_path = repo::detect_repo_path()?;
     let conn = db::open()?;
 
@@ -600,58 +3711,151 @@ fn cmd_issue_show(id: u64, json_output: bool) -> Result<()> {
     // Touch repo to update last_accessed for daemon priority
     db::touch_repo(&conn, &repo_path)?;
 
-    let issue = db::load_issue(&conn, &link.forge_repo, id)?;
+    let issue = db::load_issue(&conn, &link.forge_repo, id)?.ok_or_else(|| {
+        error::err(
+            ErrorKind::NotFound,
+            format!("Issue #{} not found in cache. Run `isq sync` to refresh.", id),
+        )
+    })?;
     let comments = db::load_comments(&conn, &link.forge_repo, id)?;
-    let elapsed = start.elapsed();
 
-    match issue {
-        Some(issue) => {
-            if json_output {
-                // Include comments in JSON output
-                let output = serde_json::json!({
-                    "issue": issue,
-                    "comments": comments.iter().map(|c| {
-                        serde_json::json!({
-                            "id": c.comment_id,
-                            "body": c.body,
-                            "author": c.author,
-                            "created_at": c.created_at
-                        })
-                    }).collect::<Vec<_>>()
-                });
-                println!("{}", serde_json::to_string_pretty(&output)?);
-            } else {
-                // Use styled display
-                display::print_issue(&issue, &comments, elapsed.as_millis() as u64);
-            }
+    print!("{}", context::render_context(&issue, &comments, max_tokens));
+    Ok(())
+}
+
+/// Pick a cached issue: interactively on a terminal, else the query's best match
+fn cmd_issue_pick(query: Option<String>, state: String, labels: Vec<String>) -> Result<u64> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    db::touch_repo(&conn, &repo_path)?;
+
+    let filter = db::IssueFilter {
+        labels,
+        state: (state != "all").then_some(state),
+        ..Default::default()
+    };
+    let issues = db::load_issues_matching(&conn, &link.forge_repo, &filter)?;
+    if issues.is_empty() {
+        return Err(error::err(ErrorKind::NotFound, "No cached issues to pick from. Run `isq sync` to refresh."));
+    }
+
+    let query = query.unwrap_or_default();
+    if !std::io::stderr().is_terminal() {
+        if query.is_empty() {
+            return Err(error::err(ErrorKind::Usage, "`isq issue pick` needs a terminal, or a query to take the best match of"));
         }
-        None => {
-            anyhow::bail!(
-                "Issue #{} not found in cache. Run `isq sync` to refresh.",
-                id
-            );
+        return picker::rank(&query, &issues)
+            .first()
+            .map(|issue| issue.number)
+            .ok_or_else(|| error::err(ErrorKind::NotFound, format!("No issue matches '{}'", query)));
+    }
+
+    picker::pick(&issues, &query)?.ok_or_else(|| error::err(ErrorKind::General, "No issue picked"))
+}
+
+/// Stop before creating a likely duplicate of a cached open issue: ask on a
+/// terminal, otherwise fail, listing the candidates (in the JSON too, for agents)
+fn check_duplicates(conn: &rusqlite::Connection, forge_repo: &str, title: &str, json: bool) -> Result<()> {
+    let filter = db::IssueFilter { state: Some("open".to_string()), ..Default::default() };
+    let candidates = similar::find(title, &db::load_issues_matching(conn, forge_repo, &filter)?);
+    if candidates.is_empty() {
+        return Ok(());
+    }
+
+    if json {
+        let result = serde_json::json!({
+            "success": false,
+            "queued": false,
+            "message": "Similar open issues exist; use --force to create anyway",
+            "duplicates": candidates,
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        for candidate in &candidates {
+            eprintln!("Similar: {} '{}'", candidate.display_id(), candidate.title);
+        }
+        if std::io::stdin().is_terminal() && std::io::stderr().is_terminal() {
+            eprint!("Create anyway? [y/N] ");
+            let mut answer = String::new();
+            {
+                let _waiting = network::interactive();
+                std::io::stdin().read_line(&mut answer)?;
+            }
+            if matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+                return Ok(());
+            }
         }
     }
+    Err(error::err(
+        ErrorKind::Conflict,
+        "Not created: similar open issues exist (use --force to skip the check)",
+    ))
+}
 
-    Ok(())
+/// What `issue create` (or `draft submit`) asks for
+struct NewIssue {
+    title: String,
+    body: Option<String>,
+    labels: Vec<String>,
+    goal: Option<String>,
+    issue_type: Option<String>,
 }
 
-async fn cmd_issue_create(title: String, body: Option<String>, labels: Vec<String>, goal: Option<String>, json: bool) -> Result<()> {
+async fn cmd_issue_create(new: NewIssue, queue: bool, json: bool, quiet: bool, force: bool) -> Result<()> {
     let start = Instant::now();
+    let NewIssue { title, body, mut labels, goal, issue_type } = new;
 
     let repo_path = repo::detect_repo_path()?;
     let (forge, link) = get_forge_for_repo(&repo_path)?;
     let conn = db::open()?;
 
+    // A broken config shouldn't block creating the issue
+    let config = config::load().unwrap_or_default();
+    let team = team_config::load(&repo_path).unwrap_or_default();
+    team.add_default_labels(&mut labels);
+
+    // [create] defaults and keyword rules; a goal given here wins
+    let routing = config.create.route(&title, body.as_deref());
+    for label in routing.labels {
+        if !labels.iter().any(|l| l.eq_ignore_ascii_case(&label)) {
+            labels.push(label);
+        }
+    }
+    if !quiet && !json && !routing.reasons.is_empty() {
+        eprintln!("Routed by [create] rules: {}", routing.reasons.join(", "));
+    }
+
     // Resolve goal name to goal_id if provided
     let goal_id = if let Some(goal_name) = &goal {
         let g = db::load_goal_by_name(&conn, &link.forge_repo, goal_name)?
-            .ok_or_else(|| anyhow::anyhow!("Goal '{}' not found. Run `isq sync` to refresh.", goal_name))?;
+            .ok_or_else(|| error::err(ErrorKind::NotFound, format!("Goal '{}' not found. Run `isq sync` to refresh.", goal_name)))?;
         Some(g.id)
+    } else if let Some(goal_name) = &routing.goal {
+        // A stale rule shouldn't block creating the issue
+        let g = db::load_goal_by_name(&conn, &link.forge_repo, goal_name)?;
+        if g.is_none() {
+            eprintln!("warning: [create] rule goal '{}' not found; run `isq sync` to refresh", goal_name);
+        }
+        g.map(|g| g.id)
     } else {
         None
     };
 
+    let assignee = match routing.assignee {
+        Some(user) if user == ME => match resolve_me(&conn, forge.as_ref(), &link.identity_key()).await {
+            Ok(login) => Some(login),
+            Err(e) => {
+                eprintln!("warning: not assigning to you: {}", e);
+                None
+            }
+        },
+        other => other,
+    };
+
     // Parse forge_repo to create Repo struct
     let parts: Vec<&str> = link.forge_repo.split('/').collect();
     if parts.len() != 2 {
@@ -662,16 +3866,49 @@ async fn cmd_issue_create(title: String, body: Option<String>, labels: Vec<Strin
         name: parts[1].to_string(),
     };
 
+    // A type that labels stand for is set with its first label; any other
+    // type is the forge's own
+    let issue_type = issue_type.filter(|t| {
+        let mapped = config.types.labels_for(t);
+        if let Some(first) = mapped.first()
+            && !labels.iter().any(|l| mapped.iter().any(|m| m.eq_ignore_ascii_case(l)))
+        {
+            labels.push(first.clone());
+        }
+        mapped.is_empty()
+    });
+
+    for warning in taxonomy::check_labels(team.taxonomy(&config.labels), &labels) {
+        eprintln!("warning: {}", warning);
+    }
+
+    if !force {
+        check_duplicates(&conn, &link.forge_repo, &title, json)?;
+    }
+
+    // Queued with the op if this times out, so replay can tell whether it landed
+    let client_key = forges::new_client_key();
     let req = CreateIssueRequest {
         title: title.clone(),
         body: body.clone(),
         labels: labels.clone(),
         goal_id: goal_id.clone(),
+        client_key: Some(client_key.clone()),
+        issue_type: issue_type.clone(),
+        assignee: assignee.clone(),
     };
 
-    match forge.create_issue(&repo, req).await {
+    warn_low_budget(&conn, &link.forge_type, queue)?;
+    match unless_queued(queue, forge.create_issue(&repo, req)).await {
         Ok(issue) => {
             let elapsed = start.elapsed();
+            db::upsert_issue(&conn, &link.forge_repo, &issue)?;
+            // The daemon won't see it as new now that it's cached, so fire the hook here
+            hooks::fire(
+                &config.hooks,
+                hooks::HookEvent::IssueCreated,
+                serde_json::json!({ "repo": link.forge_repo, "issue": issue }),
+            );
             if json {
                 let result = WriteResult {
                     success: true,
@@ -681,6 +3918,8 @@ async fn cmd_issue_create(title: String, body: Option<String>, labels: Vec<Strin
                     elapsed_ms: elapsed.as_millis() as u64,
                 };
                 println!("{}", serde_json::to_string_pretty(&result)?);
+            } else if quiet {
+                println!("{}", issue.number);
             } else {
                 println!(
                     "✓ Created #{} {} ({:.0}ms)",
@@ -695,6 +3934,9 @@ async fn cmd_issue_create(title: String, body: Option<String>, labels: Vec<Strin
                 "body": body,
                 "labels": labels,
                 "goal_id": goal_id,
+                "client_key": client_key,
+                "type": issue_type,
+                "assignee": assignee,
             });
             db::queue_op(&conn, &link.forge_repo, "create", &payload.to_string())?;
             if json {
@@ -706,12 +3948,13 @@ async fn cmd_issue_create(title: String, body: Option<String>, labels: Vec<Strin
                     elapsed_ms: elapsed.as_millis() as u64,
                 };
                 println!("{}", serde_json::to_string_pretty(&result)?);
-            } else {
+            } else if !quiet {
                 println!(
                     "✓ Queued: {} (offline, {:.0}ms)",
                     title, elapsed.as_millis()
                 );
             }
+            return Err(queued_error());
         }
         Err(e) => return Err(e),
     }
@@ -719,7 +3962,9 @@ async fn cmd_issue_create(title: String, body: Option<String>, labels: Vec<Strin
     Ok(())
 }
 
-async fn cmd_issue_comment(id: u64, message: String, json: bool) -> Result<()> {
+/// Post a comment, keeping it as a comment draft until the forge or the
+/// offline queue has it. `draft` is an existing comment draft being submitted.
+async fn cmd_issue_comment(id: u64, message: String, queue: bool, json: bool, draft: Option<i64>) -> Result<()> {
     let start = Instant::now();
 
     let repo_path = repo::detect_repo_path()?;
@@ -735,9 +3980,18 @@ async fn cmd_issue_comment(id: u64, message: String, json: bool) -> Result<()> {
         name: parts[1].to_string(),
     };
 
-    match forge.create_comment(&repo, id, &message).await {
+    let conn = db::open()?;
+    let draft = match draft {
+        Some(draft) => draft,
+        None => db::create_comment_draft(&conn, &link.forge_repo, id, &message)?,
+    };
+    warn_low_budget(&conn, &link.forge_type, queue)?;
+    let client_key = forges::new_client_key();
+    match unless_queued(queue, forge.create_keyed_comment(&repo, id, &message, &client_key)).await {
         Ok(()) => {
             let elapsed = start.elapsed();
+            db::delete_draft(&conn, &link.forge_repo, draft)?;
+            cache_comment(&conn, &link, id, &message)?;
             if json {
                 let result = WriteResult {
                     success: true,
@@ -756,9 +4010,11 @@ async fn cmd_issue_comment(id: u64, message: String, json: bool) -> Result<()> {
             let payload = serde_json::json!({
                 "issue_number": id,
                 "body": message,
+                "client_key": client_key,
             });
-            let conn = db::open()?;
             db::queue_op(&conn, &link.forge_repo, "comment", &payload.to_string())?;
+            db::delete_draft(&conn, &link.forge_repo, draft)?;
+            cache_comment(&conn, &link, id, &message)?;
             if json {
                 let result = WriteResult {
                     success: true,
@@ -774,15 +4030,42 @@ async fn cmd_issue_comment(id: u64, message: String, json: bool) -> Result<()> {
                     id, elapsed.as_millis()
                 );
             }
+            return Err(queued_error());
         }
-        Err(e) => return Err(e),
+        Err(e) => return Err(comment_draft_kept(e, draft)),
     }
 
     Ok(())
 }
 
-async fn cmd_issue_close(id: u64, json: bool) -> Result<()> {
+/// A comment write failed for good: point at the draft it's kept in
+fn comment_draft_kept(e: anyhow::Error, draft: i64) -> anyhow::Error {
+    eprintln!(
+        "Comment kept as draft {}: see `isq draft list --comments`, then `isq draft submit {}` to retry",
+        draft, draft
+    );
+    e
+}
+
+/// Cache a comment we just posted (or queued) so `issue show` includes it before the next sync
+fn cache_comment(conn: &rusqlite::Connection, link: &db::RepoLink, id: u64, body: &str) -> Result<()> {
+    let author = db::get_identity(conn, &link.identity_key())?.unwrap_or_else(|| "you".to_string());
+    let now = chrono::Utc::now();
+    let comment = db::Comment {
+        comment_id: format!("local-{}", now.timestamp_nanos_opt().unwrap_or_default()),
+        issue_number: id,
+        body: body.to_string(),
+        author,
+        created_at: now.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+    };
+    db::add_comment(conn, &link.forge_repo, &comment)
+}
+
+/// Close (`close = true`) or reopen an issue, optionally commenting first.
+/// Offline, the comment and the state change are queued in that order.
+async fn cmd_issue_set_state(id: u64, close: bool, comment: Option<String>, queue: bool, json: bool) -> Result<()> {
     let start = Instant::now();
+    let (op, done, state) = if close { ("close", "Closed", "closed") } else { ("reopen", "Reopened", "open") };
 
     let repo_path = repo::detect_repo_path()?;
     let (forge, link) = get_forge_for_repo(&repo_path)?;
@@ -797,53 +4080,489 @@ async fn cmd_issue_close(id: u64, json: bool) -> Result<()> {
         name: parts[1].to_string(),
     };
 
-    match forge.close_issue(&repo, id).await {
-        Ok(()) => {
-            let elapsed = start.elapsed();
-            if json {
-                let result = WriteResult {
-                    success: true,
-                    queued: false,
-                    issue_number: Some(id),
-                    message: format!("Closed #{}", id),
-                    elapsed_ms: elapsed.as_millis() as u64,
-                };
-                println!("{}", serde_json::to_string_pretty(&result)?);
-            } else {
-                println!("✓ Closed #{} ({:.0}ms)", id, elapsed.as_millis());
+    let conn = db::open()?;
+    warn_low_budget(&conn, &link.forge_type, queue)?;
+    let mut offline = false;
+    if let Some(message) = &comment {
+        let draft = db::create_comment_draft(&conn, &link.forge_repo, id, message)?;
+        let client_key = forges::new_client_key();
+        match unless_queued(queue, forge.create_keyed_comment(&repo, id, message, &client_key)).await {
+            Ok(()) => {}
+            Err(e) if is_offline_error(&e) => {
+                let payload = serde_json::json!({ "issue_number": id, "body": message, "client_key": client_key });
+                db::queue_op(&conn, &link.forge_repo, "comment", &payload.to_string())?;
+                offline = true;
             }
+            Err(e) => return Err(comment_draft_kept(e, draft)),
+        }
+        db::delete_draft(&conn, &link.forge_repo, draft)?;
+        cache_comment(&conn, &link, id, message)?;
+    }
+
+    if !offline {
+        let write = async { if close { forge.close_issue(&repo, id).await } else { forge.reopen_issue(&repo, id).await } };
+        match unless_queued(queue, write).await {
+            Ok(()) => {}
+            Err(e) if is_offline_error(&e) => offline = true,
+            Err(e) => return Err(e),
         }
+    }
+    if offline {
+        let payload = serde_json::json!({ "issue_number": id });
+        db::queue_op(&conn, &link.forge_repo, op, &payload.to_string())?;
+    }
+    db::set_issue_state(&conn, &link.forge_repo, id, state)?;
+
+    let elapsed = start.elapsed();
+    let with_comment = if comment.is_some() { " with comment" } else { "" };
+    let message = if offline {
+        format!("Queued: {} #{}{}", op, id, with_comment)
+    } else {
+        format!("{} #{}{}", done, id, with_comment)
+    };
+    if json {
+        let result = WriteResult {
+            success: true,
+            queued: offline,
+            issue_number: Some(id),
+            message,
+            elapsed_ms: elapsed.as_millis() as u64,
+        };
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else if offline {
+        println!("✓ {} (offline, {:.0}ms)", message, elapsed.as_millis());
+    } else {
+        println!("✓ {} ({:.0}ms)", message, elapsed.as_millis());
+    }
+
+    if offline {
+        return Err(queued_error());
+    }
+    Ok(())
+}
+
+async fn cmd_issue_convert_to_goal(
+    id: u64,
+    name: Option<String>,
+    target: Option<String>,
+    tasks: bool,
+    close: bool,
+    json: bool,
+) -> Result<()> {
+    let start = Instant::now();
+    let repo_path = repo::detect_repo_path()?;
+    let (forge, link) = get_forge_for_repo(&repo_path)?;
+    let conn = db::open()?;
+
+    let issue = db::load_issue(&conn, &link.forge_repo, id)?.ok_or_else(|| {
+        error::err(
+            ErrorKind::NotFound,
+            format!("Issue #{} not found in cache. Run `isq sync` to refresh.", id),
+        )
+    })?;
+
+    let parts: Vec<&str> = link.forge_repo.split('/').collect();
+    if parts.len() != 2 {
+        anyhow::bail!("Invalid forge_repo format: {}", link.forge_repo);
+    }
+    let repo = repo::Repo {
+        owner: parts[0].to_string(),
+        name: parts[1].to_string(),
+    };
+
+    let name = name.unwrap_or_else(|| issue.title.clone());
+    team_config::load(&repo_path)?
+        .check_goal_name(&name)
+        .map_err(|e| error::err(ErrorKind::Usage, e))?;
+
+    let body = issue.body.clone().unwrap_or_default();
+    let description = format!("{}\n\nConverted from #{}", body.trim(), id).trim().to_string();
+    let req = CreateGoalRequest {
+        name,
+        description: Some(description),
+        target_date: target,
+    };
+
+    // Everything after this needs the goal's ID, which only exists once it's created
+    let goal = match network::write(forge.create_goal(&repo, req)).await {
+        Ok(goal) => goal,
         Err(e) if is_offline_error(&e) => {
-            let elapsed = start.elapsed();
+            return Err(anyhow::anyhow!("Converting an issue to a goal needs a connection: {}", e));
+        }
+        Err(e) => return Err(e),
+    };
+    db::save_goal(&conn, &link.forge_repo, &goal)?;
+    if !json {
+        println!("✓ Created goal: {}", goal.name);
+        if let Some(url) = &goal.html_url {
+            println!("  {}", url);
+        }
+    }
+
+    // Once a request fails offline, queue the rest for the daemon
+    let mut offline = false;
+    let mut steps = Vec::new();
+    let open_tasks: Vec<_> = if tasks {
+        tasks::parse(&body).into_iter().filter(|t| !t.done).collect()
+    } else {
+        Vec::new()
+    };
+
+    for task in &open_tasks {
+        let client_key = forges::new_client_key();
+        if !offline {
+            let req = CreateIssueRequest {
+                title: task.text.clone(),
+                body: Some(format!("From #{}", id)),
+                labels: Vec::new(),
+                goal_id: Some(goal.id.clone()),
+                client_key: Some(client_key.clone()),
+                issue_type: None,
+                assignee: None,
+            };
+            match network::write(forge.create_issue(&repo, req)).await {
+                Ok(created) => {
+                    db::upsert_issue(&conn, &link.forge_repo, &created)?;
+                    steps.push(format!("#{} {}", created.number, created.title));
+                    continue;
+                }
+                Err(e) if is_offline_error(&e) => offline = true,
+                Err(e) => return Err(e),
+            }
+        }
+        let payload = serde_json::json!({
+            "title": task.text,
+            "body": format!("From #{}", id),
+            "labels": Vec::<String>::new(),
+            "goal_id": goal.id,
+            "client_key": client_key,
+        });
+        db::queue_op(&conn, &link.forge_repo, "create", &payload.to_string())?;
+        steps.push(format!("Queued: {}", task.text));
+    }
+
+    if !offline {
+        match network::write(forge.assign_to_goal(&repo, id, &goal.id)).await {
+            Ok(()) => steps.push(format!("Added #{} to the goal", id)),
+            Err(e) if is_offline_error(&e) => offline = true,
+            Err(e) => return Err(e),
+        }
+    }
+    if offline {
+        let payload = serde_json::json!({ "issue_number": id, "goal_id": goal.id });
+        db::queue_op(&conn, &link.forge_repo, "assign_goal", &payload.to_string())?;
+        steps.push(format!("Queued: add #{} to the goal", id));
+    }
+    db::set_issue_goal(&conn, &link.forge_repo, id, &goal.name)?;
+
+    let mut comment = format!("Converted to goal **{}**", goal.name);
+    if let Some(url) = &goal.html_url {
+        comment.push_str(&format!(": {}", url));
+    }
+    let client_key = forges::new_client_key();
+    if !offline {
+        match network::write(forge.create_keyed_comment(&repo, id, &comment, &client_key)).await {
+            Ok(()) => steps.push(format!("Commented on #{}", id)),
+            Err(e) if is_offline_error(&e) => offline = true,
+            Err(e) => return Err(e),
+        }
+    }
+    if offline {
+        let payload = serde_json::json!({ "issue_number": id, "body": comment, "client_key": client_key });
+        db::queue_op(&conn, &link.forge_repo, "comment", &payload.to_string())?;
+        steps.push(format!("Queued: comment on #{}", id));
+    }
+    cache_comment(&conn, &link, id, &comment)?;
+
+    if close {
+        if !offline {
+            match network::write(forge.close_issue(&repo, id)).await {
+                Ok(()) => steps.push(format!("Closed #{}", id)),
+                Err(e) if is_offline_error(&e) => offline = true,
+                Err(e) => return Err(e),
+            }
+        }
+        if offline {
             let payload = serde_json::json!({ "issue_number": id });
-            let conn = db::open()?;
             db::queue_op(&conn, &link.forge_repo, "close", &payload.to_string())?;
-            if json {
-                let result = WriteResult {
-                    success: true,
-                    queued: true,
-                    issue_number: Some(id),
-                    message: format!("Queued: close #{}", id),
-                    elapsed_ms: elapsed.as_millis() as u64,
-                };
-                println!("{}", serde_json::to_string_pretty(&result)?);
-            } else {
-                println!("✓ Queued: close #{} (offline, {:.0}ms)", id, elapsed.as_millis());
+            steps.push(format!("Queued: close #{}", id));
+        }
+        db::set_issue_state(&conn, &link.forge_repo, id, "closed")?;
+    }
+
+    let elapsed = start.elapsed();
+    if json {
+        let mut message = format!("Converted #{} to goal: {}", id, goal.name);
+        if !open_tasks.is_empty() {
+            message.push_str(&format!(" with {} issues", open_tasks.len()));
+        }
+        let result = WriteResult {
+            success: true,
+            queued: offline,
+            issue_number: Some(id),
+            message,
+            elapsed_ms: elapsed.as_millis() as u64,
+        };
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        for step in &steps {
+            println!("  ✓ {}", step);
+        }
+        eprintln!("\nConverted #{} in {:.0}ms", id, elapsed.as_millis());
+    }
+
+    if offline {
+        return Err(queued_error());
+    }
+    Ok(())
+}
+
+async fn cmd_issue_split(id: u64, same_goal: bool, link_parent: bool, json: bool) -> Result<()> {
+    let start = Instant::now();
+    let repo_path = repo::detect_repo_path()?;
+    let (forge, link) = get_forge_for_repo(&repo_path)?;
+    let conn = db::open()?;
+
+    let issue = db::load_issue(&conn, &link.forge_repo, id)?.ok_or_else(|| {
+        error::err(
+            ErrorKind::NotFound,
+            format!("Issue #{} not found in cache. Run `isq sync` to refresh.", id),
+        )
+    })?;
+
+    let parts: Vec<&str> = link.forge_repo.split('/').collect();
+    if parts.len() != 2 {
+        anyhow::bail!("Invalid forge_repo format: {}", link.forge_repo);
+    }
+    let repo = repo::Repo {
+        owner: parts[0].to_string(),
+        name: parts[1].to_string(),
+    };
+
+    let open_tasks: Vec<_> = tasks::parse(issue.body.as_deref().unwrap_or_default())
+        .into_iter()
+        .filter(|t| !t.done)
+        .collect();
+    if open_tasks.is_empty() {
+        return Err(error::err(
+            ErrorKind::Usage,
+            format!("{} has no unchecked task-list items to split", issue.display_id()),
+        ));
+    }
+
+    let goal_id = match issue.milestone.as_deref().filter(|_| same_goal) {
+        Some(name) => {
+            let goal = db::load_goal_by_name(&conn, &link.forge_repo, name)?;
+            if goal.is_none() {
+                eprintln!("warning: goal '{}' not found; run `isq sync` to refresh", name);
+            }
+            goal.map(|g| g.id)
+        }
+        None => None,
+    };
+    let body = link_parent.then(|| format!("Split from {}", issue.display_id()));
+
+    // Once a request fails offline, queue the rest for the daemon
+    let mut offline = false;
+    let mut steps = Vec::new();
+    let mut created_lines = Vec::new();
+
+    for task in &open_tasks {
+        let client_key = forges::new_client_key();
+        if !offline {
+            let req = CreateIssueRequest {
+                title: task.text.clone(),
+                body: body.clone(),
+                labels: Vec::new(),
+                goal_id: goal_id.clone(),
+                client_key: Some(client_key.clone()),
+                issue_type: None,
+                assignee: None,
+            };
+            match network::write(forge.create_issue(&repo, req)).await {
+                Ok(created) => {
+                    db::upsert_issue(&conn, &link.forge_repo, &created)?;
+                    steps.push(format!("{} {}", created.display_id(), created.title));
+                    created_lines.push(format!("- {} {}", created.display_id(), created.title));
+                    continue;
+                }
+                Err(e) if is_offline_error(&e) => offline = true,
+                Err(e) => return Err(e),
+            }
+        }
+        let payload = serde_json::json!({
+            "title": task.text,
+            "body": body,
+            "labels": Vec::<String>::new(),
+            "goal_id": goal_id,
+            "client_key": client_key,
+        });
+        db::queue_op(&conn, &link.forge_repo, "create", &payload.to_string())?;
+        steps.push(format!("Queued: {}", task.text));
+        created_lines.push(format!("- {}", task.text));
+    }
+
+    let comment = format!("Split into:\n{}", created_lines.join("\n"));
+    let client_key = forges::new_client_key();
+    if !offline {
+        match network::write(forge.create_keyed_comment(&repo, id, &comment, &client_key)).await {
+            Ok(()) => steps.push(format!("Commented on {}", issue.display_id())),
+            Err(e) if is_offline_error(&e) => offline = true,
+            Err(e) => return Err(e),
+        }
+    }
+    if offline {
+        let payload = serde_json::json!({ "issue_number": id, "body": comment, "client_key": client_key });
+        db::queue_op(&conn, &link.forge_repo, "comment", &payload.to_string())?;
+        steps.push(format!("Queued: comment on {}", issue.display_id()));
+    }
+    cache_comment(&conn, &link, id, &comment)?;
+
+    let elapsed = start.elapsed();
+    if json {
+        let result = WriteResult {
+            success: true,
+            queued: offline,
+            issue_number: Some(id),
+            message: format!("Split {} into {} issues", issue.display_id(), open_tasks.len()),
+            elapsed_ms: elapsed.as_millis() as u64,
+        };
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        for step in &steps {
+            println!("  ✓ {}", step);
+        }
+        eprintln!("\nSplit {} in {:.0}ms", issue.display_id(), elapsed.as_millis());
+    }
+
+    if offline {
+        return Err(queued_error());
+    }
+    Ok(())
+}
+
+async fn cmd_issue_suggest_labels(id: u64, apply: bool, json: bool) -> Result<()> {
+    let start = Instant::now();
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    db::touch_repo(&conn, &repo_path)?;
+
+    let issue = db::load_issue(&conn, &link.forge_repo, id)?.ok_or_else(|| {
+        error::err(
+            ErrorKind::NotFound,
+            format!("Issue #{} not found in cache. Run `isq sync` to refresh.", id),
+        )
+    })?;
+
+    let labels = config::load().unwrap_or_default().labels;
+    let mut suggestions = suggest::suggest(&labels.rules, &issue);
+    if let Some(command) = &labels.classifier {
+        // The classifier is optional help; keyword suggestions still stand without it
+        match suggest::classify(command, &issue) {
+            Ok(names) => {
+                for name in names {
+                    let known = issue.labels.iter().any(|l| l.name.eq_ignore_ascii_case(&name))
+                        || suggestions.iter().any(|s| s.label.eq_ignore_ascii_case(&name));
+                    if !known {
+                        suggestions.push(suggest::Suggestion { label: name, reason: "classifier".to_string() });
+                    }
+                }
+            }
+            Err(e) => eprintln!("warning: {}", e),
+        }
+    }
+
+    if !apply {
+        if json {
+            println!("{}", serde_json::to_string_pretty(&suggestions)?);
+        } else if suggestions.is_empty() {
+            println!("No label suggestions for #{}", id);
+        } else {
+            for s in &suggestions {
+                println!("{}  ({})", s.label, s.reason);
+            }
+        }
+        return Ok(());
+    }
+
+    let (forge, _) = get_forge_for_repo(&repo_path)?;
+    let parts: Vec<&str> = link.forge_repo.split('/').collect();
+    if parts.len() != 2 {
+        anyhow::bail!("Invalid forge_repo format: {}", link.forge_repo);
+    }
+    let repo = repo::Repo {
+        owner: parts[0].to_string(),
+        name: parts[1].to_string(),
+    };
+
+    let mut results = Vec::new();
+    let mut offline = false;
+    for s in &suggestions {
+        if !offline {
+            match network::write(forge.add_label(&repo, id, &s.label)).await {
+                Ok(()) => {}
+                Err(e) if is_offline_error(&e) => offline = true,
+                Err(e) => return Err(e),
             }
         }
-        Err(e) => return Err(e),
+        if offline {
+            let payload = serde_json::json!({ "issue_number": id, "label": s.label });
+            db::queue_op(&conn, &link.forge_repo, "label_add", &payload.to_string())?;
+        }
+        db::add_issue_label(&conn, &link.forge_repo, id, &s.label)?;
+
+        let message = if offline {
+            format!("Queued: add label '{}' to #{}", s.label, id)
+        } else {
+            format!("Added label '{}' to #{}", s.label, id)
+        };
+        if !json {
+            println!("✓ {}", message);
+        }
+        results.push(WriteResult {
+            success: true,
+            queued: offline,
+            issue_number: Some(id),
+            message,
+            elapsed_ms: start.elapsed().as_millis() as u64,
+        });
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else if results.is_empty() {
+        println!("No label suggestions for #{}", id);
     }
 
+    if offline {
+        return Err(queued_error());
+    }
     Ok(())
 }
 
-async fn cmd_issue_reopen(id: u64, json: bool) -> Result<()> {
+async fn cmd_issue_check(id: u64, index: usize, json: bool) -> Result<()> {
     let start = Instant::now();
-
     let repo_path = repo::detect_repo_path()?;
     let (forge, link) = get_forge_for_repo(&repo_path)?;
+    let conn = db::open()?;
+
+    // Edits the cached body, so a sync first avoids overwriting newer changes
+    let issue = db::load_issue(&conn, &link.forge_repo, id)?.ok_or_else(|| {
+        error::err(
+            ErrorKind::NotFound,
+            format!("Issue #{} not found in cache. Run `isq sync` to refresh.", id),
+        )
+    })?;
+    let body = issue.body.unwrap_or_default();
+    let (body, task) = tasks::toggle(&body, index).ok_or_else(|| {
+        let count = tasks::parse(&body).len();
+        error::err(ErrorKind::NotFound, format!("#{} has no task {} ({} tasks)", id, index, count))
+    })?;
 
-    // Parse forge_repo to create Repo struct
     let parts: Vec<&str> = link.forge_repo.split('/').collect();
     if parts.len() != 2 {
         anyhow::bail!("Invalid forge_repo format: {}", link.forge_repo);
@@ -853,47 +4572,43 @@ async fn cmd_issue_reopen(id: u64, json: bool) -> Result<()> {
         name: parts[1].to_string(),
     };
 
-    match forge.reopen_issue(&repo, id).await {
-        Ok(()) => {
-            let elapsed = start.elapsed();
-            if json {
-                let result = WriteResult {
-                    success: true,
-                    queued: false,
-                    issue_number: Some(id),
-                    message: format!("Reopened #{}", id),
-                    elapsed_ms: elapsed.as_millis() as u64,
-                };
-                println!("{}", serde_json::to_string_pretty(&result)?);
-            } else {
-                println!("✓ Reopened #{} ({:.0}ms)", id, elapsed.as_millis());
-            }
-        }
+    let verb = if task.done { "Checked" } else { "Unchecked" };
+    let queued = match network::write(forge.update_issue_body(&repo, id, &body)).await {
+        Ok(()) => false,
         Err(e) if is_offline_error(&e) => {
-            let elapsed = start.elapsed();
-            let payload = serde_json::json!({ "issue_number": id });
-            let conn = db::open()?;
-            db::queue_op(&conn, &link.forge_repo, "reopen", &payload.to_string())?;
-            if json {
-                let result = WriteResult {
-                    success: true,
-                    queued: true,
-                    issue_number: Some(id),
-                    message: format!("Queued: reopen #{}", id),
-                    elapsed_ms: elapsed.as_millis() as u64,
-                };
-                println!("{}", serde_json::to_string_pretty(&result)?);
-            } else {
-                println!("✓ Queued: reopen #{} (offline, {:.0}ms)", id, elapsed.as_millis());
-            }
+            let payload = serde_json::json!({ "issue_number": id, "body": body });
+            db::queue_op(&conn, &link.forge_repo, "edit_body", &payload.to_string())?;
+            true
         }
         Err(e) => return Err(e),
+    };
+    db::set_issue_body(&conn, &link.forge_repo, id, &body)?;
+
+    let elapsed = start.elapsed();
+    let (done, total) = tasks::progress(&body).unwrap_or_default();
+    let message = format!("{} '{}' on #{} ({}/{} tasks)", verb, task.text, id, done, total);
+    if json {
+        let result = WriteResult {
+            success: true,
+            queued,
+            issue_number: Some(id),
+            message: if queued { format!("Queued: {}", message) } else { message },
+            elapsed_ms: elapsed.as_millis() as u64,
+        };
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else if queued {
+        println!("✓ Queued: {} (offline, {:.0}ms)", message, elapsed.as_millis());
+    } else {
+        println!("✓ {} ({:.0}ms)", message, elapsed.as_millis());
     }
 
+    if queued {
+        return Err(queued_error());
+    }
     Ok(())
 }
 
-async fn cmd_issue_label(id: u64, action: String, label: String, json: bool) -> Result<()> {
+async fn cmd_issue_label(id: u64, action: String, label: String, queue: bool, json: bool) -> Result<()> {
     let start = Instant::now();
 
     let repo_path = repo::detect_repo_path()?;
@@ -909,11 +4624,13 @@ async fn cmd_issue_label(id: u64, action: String, label: String, json: bool) ->
         name: parts[1].to_string(),
     };
 
+    warn_low_budget(&db::open()?, &link.forge_type, queue)?;
     match action.as_str() {
         "add" => {
-            match forge.add_label(&repo, id, &label).await {
+            match unless_queued(queue, forge.add_label(&repo, id, &label)).await {
                 Ok(()) => {
                     let elapsed = start.elapsed();
+                    db::add_issue_label(&db::open()?, &link.forge_repo, id, &label)?;
                     if json {
                         let result = WriteResult {
                             success: true,
@@ -935,6 +4652,7 @@ async fn cmd_issue_label(id: u64, action: String, label: String, json: bool) ->
                     });
                     let conn = db::open()?;
                     db::queue_op(&conn, &link.forge_repo, "label_add", &payload.to_string())?;
+                    db::add_issue_label(&conn, &link.forge_repo, id, &label)?;
                     if json {
                         let result = WriteResult {
                             success: true,
@@ -950,14 +4668,16 @@ async fn cmd_issue_label(id: u64, action: String, label: String, json: bool) ->
                             label, id, elapsed.as_millis()
                         );
                     }
+                    return Err(queued_error());
                 }
                 Err(e) => return Err(e),
             }
         }
         "remove" => {
-            match forge.remove_label(&repo, id, &label).await {
+            match unless_queued(queue, forge.remove_label(&repo, id, &label)).await {
                 Ok(()) => {
                     let elapsed = start.elapsed();
+                    db::remove_issue_label(&db::open()?, &link.forge_repo, id, &label)?;
                     if json {
                         let result = WriteResult {
                             success: true,
@@ -979,6 +4699,7 @@ async fn cmd_issue_label(id: u64, action: String, label: String, json: bool) ->
                     });
                     let conn = db::open()?;
                     db::queue_op(&conn, &link.forge_repo, "label_remove", &payload.to_string())?;
+                    db::remove_issue_label(&conn, &link.forge_repo, id, &label)?;
                     if json {
                         let result = WriteResult {
                             success: true,
@@ -994,6 +4715,7 @@ async fn cmd_issue_label(id: u64, action: String, label: String, json: bool) ->
                             label, id, elapsed.as_millis()
                         );
                     }
+                    return Err(queued_error());
                 }
                 Err(e) => return Err(e),
             }
@@ -1006,12 +4728,32 @@ async fn cmd_issue_label(id: u64, action: String, label: String, json: bool) ->
     Ok(())
 }
 
-async fn cmd_issue_assign(id: u64, user: String, json: bool) -> Result<()> {
+/// Placeholder for the authenticated user in `assign` and `--assignee`
+const ME: &str = "@me";
+
+/// Resolve `@me` to the authenticated user's username for this forge.
+/// Asks the forge once, then uses the identity cached in the db.
+async fn resolve_me(conn: &rusqlite::Connection, forge: &dyn forges::Forge, identity_key: &str) -> Result<String> {
+    if let Some(login) = db::get_identity(conn, identity_key)? {
+        return Ok(login);
+    }
+    let login = forge.get_user().await?;
+    db::set_identity(conn, identity_key, &login)?;
+    Ok(login)
+}
+
+async fn cmd_issue_assign(id: u64, user: String, queue: bool, json: bool) -> Result<()> {
     let start = Instant::now();
 
     let repo_path = repo::detect_repo_path()?;
     let (forge, link) = get_forge_for_repo(&repo_path)?;
 
+    let user = if user == ME {
+        resolve_me(&db::open()?, forge.as_ref(), &link.identity_key()).await?
+    } else {
+        user
+    };
+
     // Parse forge_repo to create Repo struct
     let parts: Vec<&str> = link.forge_repo.split('/').collect();
     if parts.len() != 2 {
@@ -1022,9 +4764,14 @@ async fn cmd_issue_assign(id: u64, user: String, json: bool) -> Result<()> {
         name: parts[1].to_string(),
     };
 
-    match forge.assign_issue(&repo, id, &user).await {
+    // Linear issues have a single assignee, so assigning replaces it
+    let exclusive = link.forge_type == "linear";
+
+    warn_low_budget(&db::open()?, &link.forge_type, queue)?;
+    match unless_queued(queue, forge.assign_issue(&repo, id, &user)).await {
         Ok(()) => {
             let elapsed = start.elapsed();
+            db::add_issue_assignee(&db::open()?, &link.forge_repo, id, &user, exclusive)?;
             if json {
                 let result = WriteResult {
                     success: true,
@@ -1043,9 +4790,11 @@ async fn cmd_issue_assign(id: u64, user: String, json: bool) -> Result<()> {
             let payload = serde_json::json!({
                 "issue_number": id,
                 "assignee": user,
+                "exclusive": exclusive,
             });
             let conn = db::open()?;
             db::queue_op(&conn, &link.forge_repo, "assign", &payload.to_string())?;
+            db::add_issue_assignee(&conn, &link.forge_repo, id, &user, exclusive)?;
             if json {
                 let result = WriteResult {
                     success: true,
@@ -1061,6 +4810,69 @@ async fn cmd_issue_assign(id: u64, user: String, json: bool) -> Result<()> {
                     user, id, elapsed.as_millis()
                 );
             }
+            return Err(queued_error());
+        }
+        Err(e) => return Err(e),
+    }
+
+    Ok(())
+}
+
+async fn cmd_issue_subscribe(id: u64, subscribed: bool, queue: bool, json: bool) -> Result<()> {
+    let start = Instant::now();
+
+    let repo_path = repo::detect_repo_path()?;
+    let (forge, link) = get_forge_for_repo(&repo_path)?;
+
+    // Parse forge_repo to create Repo struct
+    let parts: Vec<&str> = link.forge_repo.split('/').collect();
+    if parts.len() != 2 {
+        anyhow::bail!("Invalid forge_repo format: {}", link.forge_repo);
+    }
+    let repo = repo::Repo {
+        owner: parts[0].to_string(),
+        name: parts[1].to_string(),
+    };
+
+    let (op_type, verb) = if subscribed { ("subscribe", "Subscribed to") } else { ("unsubscribe", "Unsubscribed from") };
+
+    warn_low_budget(&db::open()?, &link.forge_type, queue)?;
+    match unless_queued(queue, forge.set_subscribed(&repo, id, subscribed)).await {
+        Ok(()) => {
+            let elapsed = start.elapsed();
+            db::set_subscription(&db::open()?, &link.forge_repo, id, subscribed)?;
+            if json {
+                let result = WriteResult {
+                    success: true,
+                    queued: false,
+                    issue_number: Some(id),
+                    message: format!("{} #{}", verb, id),
+                    elapsed_ms: elapsed.as_millis() as u64,
+                };
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            } else {
+                println!("✓ {} #{} ({:.0}ms)", verb, id, elapsed.as_millis());
+            }
+        }
+        Err(e) if is_offline_error(&e) => {
+            let elapsed = start.elapsed();
+            let payload = serde_json::json!({ "issue_number": id });
+            let conn = db::open()?;
+            db::queue_op(&conn, &link.forge_repo, op_type, &payload.to_string())?;
+            db::set_subscription(&conn, &link.forge_repo, id, subscribed)?;
+            if json {
+                let result = WriteResult {
+                    success: true,
+                    queued: true,
+                    issue_number: Some(id),
+                    message: format!("Queued: {} #{}", op_type, id),
+                    elapsed_ms: elapsed.as_millis() as u64,
+                };
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            } else {
+                println!("✓ Queued: {} #{} (offline, {:.0}ms)", op_type, id, elapsed.as_millis());
+            }
+            return Err(queued_error());
         }
         Err(e) => return Err(e),
     }
@@ -1068,6 +4880,259 @@ async fn cmd_issue_assign(id: u64, user: String, json: bool) -> Result<()> {
     Ok(())
 }
 
+fn draft_not_found(id: i64) -> anyhow::Error {
+    error::err(ErrorKind::NotFound, format!("Draft {} not found. See `isq draft list`.", id))
+}
+
+fn print_draft_saved(draft: &db::Draft, verb: &str, json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(draft)?);
+    } else {
+        match draft.issue_number {
+            Some(number) => println!("✓ {} draft {}: comment on #{}", verb, draft.id, number),
+            None => println!("✓ {} draft {}: {}", verb, draft.id, draft.title),
+        }
+    }
+    Ok(())
+}
+
+fn cmd_search(query: &str, scopes: &[String], limit: usize, json: bool) -> Result<()> {
+    let start = Instant::now();
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    db::touch_repo(&conn, &repo_path)?;
+
+    let wants = |scope: &str| scopes.is_empty() || scopes.iter().any(|s| s == scope);
+    let mut hits = Vec::new();
+    if wants("issues") {
+        let issues = db::search_issues_text(&conn, &link.forge_repo, query, limit)?;
+        hits.extend(issues.into_iter().map(db::SearchHit::Issue));
+    }
+    if wants("comments") {
+        if !db::has_encrypted_comments(&conn, &link.forge_repo)? {
+            let comments = db::search_comments(&conn, &link.forge_repo, query, None, None, limit)?;
+            hits.extend(comments.into_iter().map(db::SearchHit::Comment));
+        } else if scopes.is_empty() {
+            eprintln!("Skipping comments: they can't be searched while the cache is encrypted");
+        } else {
+            return Err(error::err(
+                ErrorKind::Usage,
+                "Comment search isn't available while the cache is encrypted (see `isq cache decrypt`)",
+            ));
+        }
+    }
+    if wants("goals") {
+        let goals = db::search_goals(&conn, &link.forge_repo, query, limit)?;
+        hits.extend(goals.into_iter().map(db::SearchHit::Goal));
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&hits)?);
+        return Ok(());
+    }
+    if hits.is_empty() {
+        eprintln!("Nothing cached matches '{}'.", query);
+        return Ok(());
+    }
+    display::print_search_hits(&hits);
+    eprintln!("\n{} results in {:.0}ms", hits.len(), start.elapsed().as_millis());
+    Ok(())
+}
+
+fn cmd_comment_search(query: &str, author: Option<String>, issue: Option<u64>, limit: usize, json: bool) -> Result<()> {
+    let start = Instant::now();
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    db::touch_repo(&conn, &repo_path)?;
+
+    if db::has_encrypted_comments(&conn, &link.forge_repo)? {
+        return Err(error::err(
+            ErrorKind::Usage,
+            "Comment search isn't available while the cache is encrypted (see `isq cache decrypt`)",
+        ));
+    }
+
+    let hits = db::search_comments(&conn, &link.forge_repo, query, author.as_deref(), issue, limit)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&hits)?);
+        return Ok(());
+    }
+    if hits.is_empty() {
+        eprintln!("No cached comments match '{}'.", query);
+        return Ok(());
+    }
+    display::print_comment_hits(&hits);
+    eprintln!("\n{} comments in {:.0}ms", hits.len(), start.elapsed().as_millis());
+    Ok(())
+}
+
+async fn cmd_comment_quote(id: u64, comment: &str, message: Option<String>, queue: bool, json: bool) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    let comments = db::load_comments(&conn, &link.forge_repo, id)?;
+    let position = comment.parse::<usize>().ok().filter(|n| (1..=comments.len()).contains(n));
+    let quoted = comments
+        .iter()
+        .find(|c| c.comment_id == comment)
+        .or_else(|| position.and_then(|n| comments.get(n - 1)))
+        .ok_or_else(|| {
+            error::err(
+                ErrorKind::NotFound,
+                format!("No cached comment {} on #{} (try `isq sync`; #{} has {} cached)", comment, id, id, comments.len()),
+            )
+        })?;
+    let quote = drafts::quote(&quoted.author, &quoted.body);
+
+    let reply = match message {
+        Some(message) => format!("{}{}", quote, message),
+        None => drafts::edit_text("reply", &quote)?,
+    };
+    // Nothing written below the quote: don't post it
+    if reply.trim() == quote.trim() || reply.trim().is_empty() {
+        return Err(error::err(ErrorKind::Usage, "Reply is empty; nothing posted"));
+    }
+    cmd_issue_comment(id, reply.trim_end().to_string(), queue, json, None).await
+}
+
+fn cmd_draft_new(
+    title: Option<String>,
+    body: Option<String>,
+    labels: Vec<String>,
+    goal: Option<String>,
+    json: bool,
+) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    let text = drafts::DraftText { title: title.clone().unwrap_or_default(), body, labels, goal };
+    // Without --title, start from the flags in the editor
+    let text = if title.is_some() { text } else { drafts::edit(&text)? };
+
+    let id = db::create_draft(
+        &conn,
+        &link.forge_repo,
+        &text.title,
+        text.body.as_deref(),
+        &text.labels,
+        text.goal.as_deref(),
+    )?;
+    let draft = db::load_draft(&conn, &link.forge_repo, id)?.ok_or_else(|| draft_not_found(id))?;
+    print_draft_saved(&draft, "Saved", json)
+}
+
+fn cmd_draft_edit(id: i64, json: bool) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    let draft = db::load_draft(&conn, &link.forge_repo, id)?.ok_or_else(|| draft_not_found(id))?;
+    if draft.issue_number.is_some() {
+        let body = drafts::edit_text("comment", draft.body.as_deref().unwrap_or_default())?;
+        let body = body.trim();
+        if body.is_empty() {
+            return Err(error::err(ErrorKind::Usage, "Comment is empty; draft left unchanged"));
+        }
+        db::update_draft(&conn, &link.forge_repo, &db::Draft { body: Some(body.to_string()), ..draft })?;
+        let draft = db::load_draft(&conn, &link.forge_repo, id)?.ok_or_else(|| draft_not_found(id))?;
+        return print_draft_saved(&draft, "Updated", json);
+    }
+    let text = drafts::edit(&drafts::DraftText {
+        title: draft.title.clone(),
+        body: draft.body.clone(),
+        labels: draft.labels.clone(),
+        goal: draft.goal.clone(),
+    })?;
+
+    let edited = db::Draft { title: text.title, body: text.body, labels: text.labels, goal: text.goal, ..draft };
+    db::update_draft(&conn, &link.forge_repo, &edited)?;
+    let draft = db::load_draft(&conn, &link.forge_repo, id)?.ok_or_else(|| draft_not_found(id))?;
+    print_draft_saved(&draft, "Updated", json)
+}
+
+fn cmd_draft_list(comments: bool, json: bool) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    let drafts = db::list_drafts(&conn, &link.forge_repo, comments)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&drafts)?);
+        return Ok(());
+    }
+    if comments {
+        if drafts.is_empty() {
+            println!("No comment drafts. Comments are kept here when posting them fails.");
+        }
+        for draft in &drafts {
+            let number = draft.issue_number.unwrap_or_default();
+            let issue = db::load_issue(&conn, &link.forge_repo, number)?
+                .map(|i| i.display_id())
+                .unwrap_or_else(|| format!("#{}", number));
+            let first_line = draft.body.as_deref().unwrap_or_default().lines().next().unwrap_or_default();
+            println!("{:>4}  {}  {}  (updated {})", draft.id, issue, first_line, draft.updated_at);
+        }
+        return Ok(());
+    }
+    let kept = db::list_drafts(&conn, &link.forge_repo, true)?.len();
+    if kept > 0 {
+        eprintln!("{} comment drafts kept from failed posts: see `isq draft list --comments`", kept);
+    }
+    if drafts.is_empty() {
+        println!("No drafts. Start one with `isq draft new`.");
+        return Ok(());
+    }
+    for draft in &drafts {
+        let mut extra = Vec::new();
+        if !draft.labels.is_empty() {
+            extra.push(draft.labels.join(", "));
+        }
+        if let Some(goal) = &draft.goal {
+            extra.push(format!("goal {}", goal));
+        }
+        let extra = if extra.is_empty() { String::new() } else { format!(" [{}]", extra.join("; ")) };
+        println!("{:>4}  {}{}  (updated {})", draft.id, draft.title, extra, draft.updated_at);
+    }
+    Ok(())
+}
+
+async fn cmd_draft_submit(id: i64, force: bool, json: bool) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    let draft = db::load_draft(&conn, &link.forge_repo, id)?.ok_or_else(|| draft_not_found(id))?;
+    // Comment drafts are deleted once posted or queued
+    if let Some(number) = draft.issue_number {
+        return cmd_issue_comment(number, draft.body.unwrap_or_default(), false, json, Some(id)).await;
+    }
+    let new = NewIssue { title: draft.title, body: draft.body, labels: draft.labels, goal: draft.goal, issue_type: None };
+    let result = cmd_issue_create(new, false, json, false, force).await;
+
+    // A queued create will still reach the forge, so the draft is done either way
+    let submitted = match &result {
+        Ok(()) => true,
+        Err(e) => error::classify(e) == ErrorKind::OfflineQueued,
+    };
+    if submitted {
+        db::delete_draft(&conn, &link.forge_repo, id)?;
+    }
+    result
+}
+
 fn cmd_daemon_status() -> Result<()> {
     // Check service status
     let status = service::status()?;
@@ -1085,23 +5150,61 @@ fn cmd_daemon_status() -> Result<()> {
 
     // Clean up stale repo entries before displaying
     let conn = db::open()?;
-    let removed = db::cleanup_stale_repos(&conn)?;
-    if removed > 0 {
-        println!("\n(Cleaned up {} stale entries)", removed);
+
+    if let Some(safe_mode) = daemon::load_health().safe_mode {
+        println!("Sync:    ⚠️  safe mode since {}: {}", timefmt::local_time(safe_mode.since), safe_mode.reason);
+        println!("         Fix the cause, then run `isq daemon resume`");
+    }
+    if let Some(pause) = db::get_daemon_pause(&db::open_shared()?, daemon::unix_now())? {
+        match pause.until {
+            Some(ts) => println!("Sync:    paused until {}", timefmt::local_time(ts)),
+            None => println!("Sync:    paused (run `isq daemon resume`)"),
+        }
+    }
+
+    let sizes = storage::FileSizes::of(&db::shared_db_path()?);
+    println!("Cache:   {} (write-ahead log {})", storage::megabytes(sizes.db), storage::megabytes(sizes.wal));
+
+    let cleanup = db::cleanup_stale_repos(&conn)?;
+    if cleanup.removed > 0 {
+        println!("\n(Cleaned up {} stale entries)", cleanup.removed);
+    }
+    for (path, pending) in &cleanup.kept {
+        println!(
+            "\n⚠️  {} is missing but has {} unsynced operations. Run `isq link --repair` from its new location.",
+            path, pending
+        );
     }
 
     // Show rate limit budget per forge
     let mut shown_rate_limits = false;
     for forge_type in ALL_FORGE_TYPES {
-        if let Some(state) = db::get_rate_limit_state(&conn, forge_type.as_str())? {
-            if let (Some(limit), Some(_remaining)) = (state.limit, state.remaining) {
-                if !shown_rate_limits {
-                    println!();
-                    shown_rate_limits = true;
-                }
-                let used = state.used().unwrap_or(0);
-                println!("Rate limit budget ({}): {} req/hr", forge_type.auth().display_name, limit);
-                println!("  Used this hour: {}", used);
+        if let Some(state) = db::get_rate_limit_state(&conn, forge_type.as_str())?
+            && let (Some(limit), Some(_remaining)) = (state.limit, state.remaining)
+        {
+            if !shown_rate_limits {
+                println!();
+                shown_rate_limits = true;
+            }
+            let used = state.used().unwrap_or(0);
+            println!("Rate limit budget ({}): {} req/hr", forge_type.display_name(), limit);
+            println!("  Used this hour: {}", used);
+        }
+        if let Some(budget) = db::get_complexity_budget(&conn, forge_type.as_str())? {
+            if !shown_rate_limits {
+                println!();
+                shown_rate_limits = true;
+            }
+            let now = daemon::unix_now();
+            println!(
+                "Complexity budget ({}): {} of {} left (last query cost {})",
+                forge_type.display_name(),
+                budget.remaining_at(now),
+                budget.limit,
+                budget.last_cost
+            );
+            if budget.is_low(now) {
+                println!("  Low: page sizes reduced, comment sync deferred until reset");
             }
         }
     }
@@ -1149,11 +5252,7 @@ fn cmd_daemon_status() -> Result<()> {
                         .as_secs() as i64;
                     if now < reset_at && state.last_error.is_some() {
                         let reset_time = chrono::DateTime::from_timestamp(reset_at, 0)
-                            .map(|dt| {
-                                use chrono::Local;
-                                let local: chrono::DateTime<Local> = dt.into();
-                                local.format("%-I:%M %p").to_string()
-                            })
+                            .map(|at| timefmt::FORMAT.time(at))
                             .unwrap_or_else(|| format!("{}s", reset_at - now));
                         format!(" ⚠️  rate limited until {}", reset_time)
                     } else {
@@ -1166,7 +5265,8 @@ fn cmd_daemon_status() -> Result<()> {
                 String::new()
             };
 
-            println!("  {} [{}]", display, forge_type);
+            let paused_info = if watched_repo.paused { " [paused]" } else { "" };
+            println!("  {} [{}]{}", display, forge_type, paused_info);
             println!("    {}{}{}", sync_info, pending_info, rate_limit_warning);
         }
     }
@@ -1186,16 +5286,70 @@ fn cmd_daemon_stop() -> Result<()> {
     Ok(())
 }
 
-fn cmd_daemon_watch() -> Result<()> {
-    let repo_path = repo::detect_repo_path()?;
-    let conn = db::open()?;
+/// Resolve a repo argument (linked name or local path), defaulting to the current repo
+fn resolve_repo_arg(conn: &rusqlite::Connection, arg: Option<&str>) -> Result<String> {
+    let Some(arg) = arg else {
+        return repo::detect_repo_path();
+    };
+
+    if let Some(path) = db::find_repo_path(conn, arg)? {
+        return Ok(path);
+    }
+
+    let path = std::path::Path::new(arg);
+    if path.is_dir() {
+        return Ok(std::fs::canonicalize(path)?.to_string_lossy().into_owned());
+    }
+
+    Err(error::err(
+        ErrorKind::NotFound,
+        format!("No linked repo matches '{}'. Pass a repo path or linked name (e.g. owner/repo).", arg),
+    ))
+}
+
+fn cmd_daemon_watch(repo: Option<String>, paused: bool) -> Result<()> {
+    let conn = db::open()?;
+    let repo_path = resolve_repo_arg(&conn, repo.as_deref())?;
+
+    // Check if repo is linked
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    db::set_repo_paused(&conn, &repo_path, paused)?;
+    if paused {
+        println!("✓ Paused sync for {} ({})", link.forge_repo, repo_path);
+    } else {
+        println!("✓ Watching {} ({})", link.forge_repo, repo_path);
+    }
+    Ok(())
+}
+
+fn cmd_daemon_pause(duration: Option<Duration>) -> Result<()> {
+    let conn = db::open_shared()?;
+    let until = duration.map(|d| daemon::unix_now() + d.as_secs() as i64);
+    db::set_daemon_pause(&conn, until)?;
+
+    match until {
+        Some(ts) => println!("✓ Daemon paused until {}", timefmt::local_time(ts)),
+        None => println!("✓ Daemon paused. Run `isq daemon resume` to resume."),
+    }
+    Ok(())
+}
+
+fn cmd_daemon_resume() -> Result<()> {
+    // Before opening the cache, which may be what put the daemon in safe mode
+    if daemon::clear_safe_mode()? {
+        println!("✓ Left safe mode; the daemon re-checks the cache and resumes syncing if it passes");
+    }
 
-    // Check if repo is linked
-    let link = db::get_repo_link(&conn, &repo_path)?
-        .ok_or_else(not_linked_error)?;
+    let conn = db::open_shared()?;
+    if db::get_daemon_pause(&conn, daemon::unix_now())?.is_none() {
+        println!("Daemon is not paused.");
+        return Ok(());
+    }
 
-    db::add_watched_repo(&conn, &repo_path)?;
-    println!("✓ Watching {} ({})", link.forge_repo, repo_path);
+    db::clear_daemon_pause(&conn)?;
+    println!("✓ Daemon resumed");
     Ok(())
 }
 
@@ -1207,23 +5361,178 @@ fn cmd_daemon_unwatch() -> Result<()> {
     Ok(())
 }
 
-fn print_issues(issues: &[Issue], comment_counts: &std::collections::HashMap<u64, usize>) {
+fn print_issues(
+    issues: &[Issue],
+    comment_counts: &std::collections::HashMap<u64, usize>,
+    pending: &std::collections::HashSet<u64>,
+    columns: &[display::Column],
+) {
     if issues.is_empty() {
         println!("No open issues.");
         return;
     }
 
-    for issue in issues {
-        let count = comment_counts.get(&issue.number).copied();
-        display::print_issue_row(issue, count);
+    display::print_issue_table(issues, comment_counts, pending, columns);
+}
+
+// ============================================================================
+// WIP Commands
+// ============================================================================
+
+/// Open issues assigned to you in every linked repo (each forge repo once),
+/// across the caches from `db::open_all`
+async fn load_my_issues(conns: &[rusqlite::Connection]) -> Result<Vec<wip::WipIssue>> {
+    let mut seen = HashSet::new();
+    let mut mine = Vec::new();
+    // Search workspaces go last, so issues in linked repos keep their rank
+    let mut workspaces = Vec::new();
+    let mut listed = HashSet::new();
+    for conn in conns {
+        for (path, link) in db::list_repo_links(conn)? {
+            if !seen.insert(link.forge_repo.clone()) {
+                continue;
+            }
+            let name = link.display_name.clone().unwrap_or_else(|| link.forge_repo.clone());
+
+            let login = match db::get_identity(conn, &link.identity_key())? {
+                Some(login) => login,
+                None => {
+                    let resolved = match forges::get_forge_in(conn, &path) {
+                        Ok((forge, _)) => resolve_me(conn, forge.as_ref(), &link.identity_key()).await,
+                        Err(e) => Err(e),
+                    };
+                    match resolved {
+                        Ok(login) => login,
+                        Err(e) => {
+                            eprintln!("warning: skipping {} (can't tell who you are there: {})", name, e);
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            if workspace::is_workspace(&link.forge_repo) {
+                workspaces.push((conn, link.forge_repo, login));
+                continue;
+            }
+
+            let filter = db::IssueFilter {
+                state: Some("open".to_string()),
+                assignee: Some(login),
+                ..Default::default()
+            };
+            let mut branches = db::load_branch_issues(conn, &link.forge_repo)?;
+            let ranking = db::load_ranking(conn, &link.forge_repo)?;
+            for issue in db::load_issues_matching(conn, &link.forge_repo, &filter)? {
+                listed.insert((link.forge_repo.clone(), issue.number));
+                mine.push(wip::WipIssue {
+                    repo: name.clone(),
+                    branch: branches.remove(&issue.number),
+                    rank: ranking.iter().position(|&n| n == issue.number).map(|i| i + 1),
+                    issue,
+                });
+            }
+        }
+    }
+
+    for (conn, workspace, login) in workspaces {
+        for (forge_repo, issue) in db::load_workspace_issues(conn, &workspace)? {
+            let assigned = issue.assignees.iter().any(|a| a.eq_ignore_ascii_case(&login));
+            if issue.state != "open" || !assigned || !listed.insert((forge_repo.clone(), issue.number)) {
+                continue;
+            }
+            mine.push(wip::WipIssue {
+                branch: db::load_branch_issues(conn, &forge_repo)?.remove(&issue.number),
+                repo: forge_repo,
+                rank: None,
+                issue,
+            });
+        }
+    }
+    Ok(mine)
+}
+
+fn print_wip_issue(item: &wip::WipIssue, repo_width: usize) {
+    let branch = item.branch.as_ref().map(|b| format!("  ({})", b)).unwrap_or_default();
+    println!("  {:repo_width$}  {:>8}  {}{}", item.repo, item.issue.display_id(), item.issue.title, branch);
+}
+
+fn warn_over_wip_limit(config: &wip::WipConfig, in_progress: usize) {
+    if config.is_over(in_progress) {
+        eprintln!(
+            "warning: {} issues in progress, over your WIP limit of {}. Finish one before starting another.",
+            in_progress, config.limit
+        );
+    }
+}
+
+async fn cmd_wip(json: bool) -> Result<()> {
+    let conns = db::open_all()?;
+    let config = config::load().unwrap_or_default().wip;
+    let mine = load_my_issues(&conns).await?;
+    let (in_progress, unstarted): (Vec<_>, Vec<_>) = mine.iter().partition(|w| w.is_in_progress(&config));
+
+    if json {
+        let output = serde_json::json!({
+            "in_progress": in_progress,
+            "limit": config.limit,
+            "over_limit": config.is_over(in_progress.len()),
+            "unstarted": unstarted.len(),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if in_progress.is_empty() {
+        println!("Nothing in progress.");
+    } else {
+        let width = in_progress.iter().map(|w| w.repo.chars().count()).max().unwrap_or(0);
+        for item in &in_progress {
+            print_wip_issue(item, width);
+        }
+        let limit = if config.limit > 0 { format!(" (limit {})", config.limit) } else { String::new() };
+        println!("\n{} in progress{}", in_progress.len(), limit);
+    }
+    if !unstarted.is_empty() {
+        println!("{} more assigned to you; `isq wip next` suggests one", unstarted.len());
+    }
+    warn_over_wip_limit(&config, in_progress.len());
+    Ok(())
+}
+
+async fn cmd_wip_next(json: bool) -> Result<()> {
+    let conns = db::open_all()?;
+    let config = config::load().unwrap_or_default().wip;
+    let mine = load_my_issues(&conns).await?;
+    let (in_progress, unstarted): (Vec<_>, Vec<_>) = mine.iter().partition(|w| w.is_in_progress(&config));
+    let next = wip::next(&unstarted);
+
+    if json {
+        let output = serde_json::json!({
+            "next": next,
+            "in_progress": in_progress.len(),
+            "over_limit": config.is_over(in_progress.len()),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    match next {
+        Some(item) => {
+            print_wip_issue(item, item.repo.chars().count());
+            println!("\nStart it with `isq issue current {}` on its branch", item.issue.display_id());
+        }
+        None => println!("No unstarted issues assigned to you."),
     }
+    warn_over_wip_limit(&config, in_progress.len());
+    Ok(())
 }
 
 // ============================================================================
 // Goal Commands
 // ============================================================================
 
-async fn cmd_goal_list(state: String, json_output: bool) -> Result<()> {
+async fn cmd_goal_list(state: String, sort: Option<String>, json_output: bool) -> Result<()> {
     let start = Instant::now();
     let repo_path = repo::detect_repo_path()?;
     let conn = db::open()?;
@@ -1258,12 +5567,32 @@ async fn cmd_goal_list(state: String, json_output: bool) -> Result<()> {
     }
 
     db::touch_repo(&conn, &repo_path)?;
+    if let Some(by) = &sort {
+        display::sort_goals(&mut goals, by);
+    }
+    let rule = config::load().unwrap_or_default().reminders.rule(&link.forge_repo);
+    let today = chrono::Local::now().date_naive();
+    let at_risk: HashSet<&str> = goals
+        .iter()
+        .filter(|goal| reminders::is_at_risk(goal, today, rule))
+        .map(|goal| goal.id.as_str())
+        .collect();
     let elapsed = start.elapsed();
 
     if json_output {
-        println!("{}", serde_json::to_string_pretty(&goals)?);
+        let mut values = Vec::with_capacity(goals.len());
+        for goal in &goals {
+            let mut value = serde_json::to_value(goal)?;
+            let days_left = reminders::days_left(goal, today);
+            value["percent"] = serde_json::json!((goal.progress * 100.0).round() as u32);
+            value["days_left"] = serde_json::json!(days_left);
+            value["overdue"] = serde_json::json!(goal.state == GoalState::Open && days_left.is_some_and(|d| d < 0));
+            value["at_risk"] = serde_json::json!(at_risk.contains(goal.id.as_str()));
+            values.push(value);
+        }
+        println!("{}", serde_json::to_string_pretty(&values)?);
     } else {
-        display::print_goals(&goals);
+        display::print_goals(&goals, &at_risk, today);
         eprintln!("\n{} goals in {:.0}ms", goals.len(), elapsed.as_millis());
     }
 
@@ -1281,7 +5610,7 @@ fn cmd_goal_show(name: String, json_output: bool) -> Result<()> {
     db::touch_repo(&conn, &repo_path)?;
 
     let goal = db::load_goal_by_name(&conn, &link.forge_repo, &name)?
-        .ok_or_else(|| anyhow::anyhow!("Goal '{}' not found. Run `isq sync` to refresh.", name))?;
+        .ok_or_else(|| error::err(ErrorKind::NotFound, format!("Goal '{}' not found. Run `isq sync` to refresh.", name)))?;
 
     let elapsed = start.elapsed();
 
@@ -1294,11 +5623,57 @@ fn cmd_goal_show(name: String, json_output: bool) -> Result<()> {
     Ok(())
 }
 
-async fn cmd_goal_create(name: String, target: Option<String>, body: Option<String>, json: bool) -> Result<()> {
+fn cmd_goal_notes(name: String, format: String) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    db::touch_repo(&conn, &repo_path)?;
+
+    let goal = db::load_goal_by_name(&conn, &link.forge_repo, &name)?
+        .ok_or_else(|| error::err(ErrorKind::NotFound, format!("Goal '{}' not found. Run `isq sync` to refresh.", name)))?;
+
+    let filter = db::IssueFilter {
+        goal: Some(goal.name.clone()),
+        state: Some("closed".to_string()),
+        ..Default::default()
+    };
+    let issues = db::load_issues_matching(&conn, &link.forge_repo, &filter)?;
+
+    let sections = config::load().unwrap_or_default().notes.sections();
+    let groups = notes::group(&sections, &issues);
+
+    if format == "json" {
+        println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "goal": goal.name, "sections": groups }))?);
+    } else {
+        print!("{}", notes::render_markdown(&goal.name, &groups));
+    }
+    Ok(())
+}
+
+async fn cmd_goal_create(
+    name: String,
+    target: Option<String>,
+    body: Option<String>,
+    template: Option<String>,
+    json: bool,
+) -> Result<()> {
     let start = Instant::now();
     let repo_path = repo::detect_repo_path()?;
+    team_config::load(&repo_path)?
+        .check_goal_name(&name)
+        .map_err(|e| error::err(ErrorKind::Usage, e))?;
     let (forge, link) = get_forge_for_repo(&repo_path)?;
 
+    // Load the template up front so a typo fails before anything is created
+    let template = match &template {
+        Some(t) => Some(template::load(t)?.render(&name, target.as_deref())),
+        None => None,
+    };
+    let body = body.or_else(|| template.as_ref().and_then(|t| t.description.clone()));
+
     let parts: Vec<&str> = link.forge_repo.split('/').collect();
     if parts.len() != 2 {
         anyhow::bail!("Invalid forge_repo format: {}", link.forge_repo);
@@ -1314,19 +5689,64 @@ async fn cmd_goal_create(name: String, target: Option<String>, body: Option<Stri
         target_date: target.clone(),
     };
 
-    match forge.create_goal(&repo, req).await {
+    match network::write(forge.create_goal(&repo, req)).await {
         Ok(goal) => {
-            let elapsed = start.elapsed();
             // Save to local cache
             let conn = db::open()?;
             db::save_goal(&conn, &link.forge_repo, &goal)?;
 
+            // Create the template's issues under the new goal; once offline, queue the rest
+            let issues = template.map(|t| t.issues).unwrap_or_default();
+            let total = issues.len();
+            let keyed: Vec<_> = issues.into_iter().map(|issue| (issue, forges::new_client_key())).collect();
+            let run = template::create_issues(
+                keyed,
+                |(issue, client_key)| {
+                    let req = CreateIssueRequest {
+                        title: issue.title.clone(),
+                        body: issue.body.clone(),
+                        labels: issue.labels.clone(),
+                        goal_id: Some(goal.id.clone()),
+                        client_key: Some(client_key.clone()),
+                        issue_type: None,
+                        assignee: None,
+                    };
+                    network::write(forge.create_issue(&repo, req))
+                },
+                is_offline_error,
+            )
+            .await;
+
+            for issue in &run.created {
+                db::upsert_issue(&conn, &link.forge_repo, issue)?;
+            }
+            // A create that timed out keeps its key, so replay can tell whether it landed
+            for (issue, client_key) in &run.queued {
+                let payload = serde_json::json!({
+                    "title": issue.title,
+                    "body": issue.body,
+                    "labels": issue.labels,
+                    "goal_id": goal.id,
+                    "client_key": client_key,
+                });
+                db::queue_op(&conn, &link.forge_repo, "create", &payload.to_string())?;
+            }
+            let created = run.created;
+            let queued = run.queued.len();
+            let elapsed = start.elapsed();
+
             if json {
+                let mut message = format!("Created goal: {}", goal.name);
+                if run.failed.is_some() {
+                    message.push_str(&format!(" with {} of {} issues", created.len(), total));
+                } else if total > 0 {
+                    message.push_str(&format!(" with {} issues", total));
+                }
                 let result = WriteResult {
-                    success: true,
-                    queued: false,
+                    success: run.failed.is_none(),
+                    queued: queued > 0,
                     issue_number: None,
-                    message: format!("Created goal: {}", goal.name),
+                    message,
                     elapsed_ms: elapsed.as_millis() as u64,
                 };
                 println!("{}", serde_json::to_string_pretty(&result)?);
@@ -1335,8 +5755,36 @@ async fn cmd_goal_create(name: String, target: Option<String>, body: Option<Stri
                 if let Some(url) = &goal.html_url {
                     println!("  {}", url);
                 }
+                for issue in &created {
+                    println!("  ✓ #{} {}", issue.number, issue.title);
+                }
+                if queued > 0 {
+                    println!("  ✓ Queued {} more issues (offline)", queued);
+                }
+                if let Some((_, rest)) = &run.failed {
+                    for (issue, _) in rest {
+                        println!("  ✗ {}", issue.title);
+                    }
+                }
+            }
+
+            if let Some((e, rest)) = run.failed {
+                return Err(e.context(format!(
+                    "Created goal {} and {} of its {} issues; {} not created",
+                    goal.name,
+                    created.len(),
+                    total,
+                    rest.len()
+                )));
+            }
+            if queued > 0 {
+                return Err(queued_error());
             }
         }
+        Err(e) if is_offline_error(&e) && template.is_some() => {
+            // Template issues need the goal's ID, which only exists once it's created
+            return Err(anyhow::anyhow!("Creating a goal from a template needs a connection: {}", e));
+        }
         Err(e) if is_offline_error(&e) => {
             let elapsed = start.elapsed();
             let payload = serde_json::json!({
@@ -1359,6 +5807,7 @@ async fn cmd_goal_create(name: String, target: Option<String>, body: Option<Stri
             } else {
                 println!("✓ Queued: create goal {} (offline, {:.0}ms)", name, elapsed.as_millis());
             }
+            return Err(queued_error());
         }
         Err(e) => return Err(e),
     }
@@ -1374,7 +5823,7 @@ async fn cmd_goal_assign(issue: u64, goal_name: String, json: bool) -> Result<()
 
     // Resolve goal name to ID
     let goal = db::load_goal_by_name(&conn, &link.forge_repo, &goal_name)?
-        .ok_or_else(|| anyhow::anyhow!("Goal '{}' not found. Run `isq sync` to refresh.", goal_name))?;
+        .ok_or_else(|| error::err(ErrorKind::NotFound, format!("Goal '{}' not found. Run `isq sync` to refresh.", goal_name)))?;
 
     let parts: Vec<&str> = link.forge_repo.split('/').collect();
     if parts.len() != 2 {
@@ -1385,9 +5834,10 @@ async fn cmd_goal_assign(issue: u64, goal_name: String, json: bool) -> Result<()
         name: parts[1].to_string(),
     };
 
-    match forge.assign_to_goal(&repo, issue, &goal.id).await {
+    match network::write(forge.assign_to_goal(&repo, issue, &goal.id)).await {
         Ok(()) => {
             let elapsed = start.elapsed();
+            db::set_issue_goal(&conn, &link.forge_repo, issue, &goal.name)?;
             if json {
                 let result = WriteResult {
                     success: true,
@@ -1398,29 +5848,259 @@ async fn cmd_goal_assign(issue: u64, goal_name: String, json: bool) -> Result<()
                 };
                 println!("{}", serde_json::to_string_pretty(&result)?);
             } else {
-                println!("✓ Assigned #{} to goal '{}' ({:.0}ms)", issue, goal.name, elapsed.as_millis());
+                println!("✓ Assigned #{} to goal '{}' ({:.0}ms)", issue, goal.name, elapsed.as_millis());
+            }
+        }
+        Err(e) if is_offline_error(&e) => {
+            let elapsed = start.elapsed();
+            let payload = serde_json::json!({
+                "issue_number": issue,
+                "goal_id": goal.id,
+            });
+            db::queue_op(&conn, &link.forge_repo, "assign_goal", &payload.to_string())?;
+            db::set_issue_goal(&conn, &link.forge_repo, issue, &goal.name)?;
+
+            if json {
+                let result = WriteResult {
+                    success: true,
+                    queued: true,
+                    issue_number: Some(issue),
+                    message: format!("Queued: assign #{} to '{}'", issue, goal.name),
+                    elapsed_ms: elapsed.as_millis() as u64,
+                };
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            } else {
+                println!("✓ Queued: assign #{} to '{}' (offline, {:.0}ms)", issue, goal.name, elapsed.as_millis());
+            }
+            return Err(queued_error());
+        }
+        Err(e) => return Err(e),
+    }
+
+    Ok(())
+}
+
+/// An objective's goals, in goal list order
+fn objective_goals(conn: &rusqlite::Connection, forge_repo: &str, objective: &forges::Objective) -> Result<Vec<forges::Goal>> {
+    Ok(db::load_goals(conn, forge_repo, None)?
+        .into_iter()
+        .filter(|goal| objective.goal_ids.contains(&goal.id))
+        .collect())
+}
+
+fn cmd_objective_list(json_output: bool) -> Result<()> {
+    let start = Instant::now();
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    db::touch_repo(&conn, &repo_path)?;
+
+    let mut rows = Vec::new();
+    for objective in db::load_objectives(&conn, &link.forge_repo)? {
+        let progress = ObjectiveProgress::from_goals(&objective_goals(&conn, &link.forge_repo, &objective)?);
+        rows.push((objective, progress));
+    }
+    let elapsed = start.elapsed();
+
+    if json_output {
+        let json: Vec<_> = rows
+            .iter()
+            .map(|(objective, progress)| serde_json::json!({ "objective": objective, "progress": progress }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+    } else {
+        display::print_objectives(&rows);
+        eprintln!("\n{} objectives in {:.0}ms", rows.len(), elapsed.as_millis());
+    }
+
+    Ok(())
+}
+
+fn cmd_objective_show(name: String, json_output: bool) -> Result<()> {
+    let start = Instant::now();
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    db::touch_repo(&conn, &repo_path)?;
+
+    let objective = db::load_objective_by_name(&conn, &link.forge_repo, &name)?
+        .ok_or_else(|| error::err(ErrorKind::NotFound, format!("Objective '{}' not found. Run `isq sync` to refresh.", name)))?;
+    let goals = objective_goals(&conn, &link.forge_repo, &objective)?;
+    let progress = ObjectiveProgress::from_goals(&goals);
+
+    let elapsed = start.elapsed();
+
+    if json_output {
+        let json = serde_json::json!({
+            "objective": objective,
+            "progress": progress,
+            "goals": goals,
+        });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+    } else {
+        display::print_objective_detail(&objective, &goals, &progress, elapsed.as_millis() as u64);
+    }
+
+    Ok(())
+}
+
+async fn cmd_objective_create(name: String, target: Option<String>, body: Option<String>, json: bool) -> Result<()> {
+    let start = Instant::now();
+    let repo_path = repo::detect_repo_path()?;
+    let (forge, link) = get_forge_for_repo(&repo_path)?;
+    let conn = db::open()?;
+
+    let parts: Vec<&str> = link.forge_repo.split('/').collect();
+    if parts.len() != 2 {
+        anyhow::bail!("Invalid forge_repo format: {}", link.forge_repo);
+    }
+    let repo = repo::Repo {
+        owner: parts[0].to_string(),
+        name: parts[1].to_string(),
+    };
+
+    let req = CreateGoalRequest {
+        name: name.clone(),
+        description: body.clone(),
+        target_date: target.clone(),
+    };
+
+    match network::write(forge.create_objective(&repo, req)).await {
+        Ok(created) => {
+            // Forges without objectives keep them in the cache only
+            let local = created.is_none();
+            let objective = created.unwrap_or_else(|| {
+                let now = chrono::Utc::now();
+                forges::Objective {
+                    id: format!("local-{}", now.timestamp_nanos_opt().unwrap_or_default()),
+                    name: name.clone(),
+                    description: body,
+                    target_date: target,
+                    state: GoalState::Open,
+                    goal_ids: Vec::new(),
+                    updated_at: now.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+                    html_url: None,
+                }
+            });
+            db::save_objective(&conn, &link.forge_repo, &objective)?;
+            let elapsed = start.elapsed();
+
+            let message = if local {
+                format!("Created objective: {} (local)", objective.name)
+            } else {
+                format!("Created objective: {}", objective.name)
+            };
+            if json {
+                let result = WriteResult {
+                    success: true,
+                    queued: false,
+                    issue_number: None,
+                    message,
+                    elapsed_ms: elapsed.as_millis() as u64,
+                };
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            } else {
+                println!("✓ {} ({:.0}ms)", message, elapsed.as_millis());
+                if let Some(url) = &objective.html_url {
+                    println!("  {}", url);
+                }
+            }
+        }
+        Err(e) if is_offline_error(&e) => {
+            let elapsed = start.elapsed();
+            let payload = serde_json::json!({
+                "name": name,
+                "target_date": target,
+                "description": body,
+            });
+            db::queue_op(&conn, &link.forge_repo, "create_objective", &payload.to_string())?;
+
+            if json {
+                let result = WriteResult {
+                    success: true,
+                    queued: true,
+                    issue_number: None,
+                    message: format!("Queued: create objective {}", name),
+                    elapsed_ms: elapsed.as_millis() as u64,
+                };
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            } else {
+                println!("✓ Queued: create objective {} (offline, {:.0}ms)", name, elapsed.as_millis());
+            }
+            return Err(queued_error());
+        }
+        Err(e) => return Err(e),
+    }
+
+    Ok(())
+}
+
+async fn cmd_objective_link(objective_name: String, goal_name: String, json: bool) -> Result<()> {
+    let start = Instant::now();
+    let repo_path = repo::detect_repo_path()?;
+    let (forge, link) = get_forge_for_repo(&repo_path)?;
+    let conn = db::open()?;
+
+    let objective = db::load_objective_by_name(&conn, &link.forge_repo, &objective_name)?
+        .ok_or_else(|| error::err(ErrorKind::NotFound, format!("Objective '{}' not found. Run `isq sync` to refresh.", objective_name)))?;
+    let goal = db::load_goal_by_name(&conn, &link.forge_repo, &goal_name)?
+        .ok_or_else(|| error::err(ErrorKind::NotFound, format!("Goal '{}' not found. Run `isq sync` to refresh.", goal_name)))?;
+
+    let parts: Vec<&str> = link.forge_repo.split('/').collect();
+    if parts.len() != 2 {
+        anyhow::bail!("Invalid forge_repo format: {}", link.forge_repo);
+    }
+    let repo = repo::Repo {
+        owner: parts[0].to_string(),
+        name: parts[1].to_string(),
+    };
+
+    match network::write(forge.link_goal_to_objective(&repo, &objective.id, &goal.id)).await {
+        Ok(()) => {
+            let elapsed = start.elapsed();
+            db::link_objective_goal(&conn, &link.forge_repo, &objective.id, &goal.id)?;
+            if json {
+                let result = WriteResult {
+                    success: true,
+                    queued: false,
+                    issue_number: None,
+                    message: format!("Linked goal '{}' to objective '{}'", goal.name, objective.name),
+                    elapsed_ms: elapsed.as_millis() as u64,
+                };
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            } else {
+                println!("✓ Linked goal '{}' to objective '{}' ({:.0}ms)", goal.name, objective.name, elapsed.as_millis());
             }
         }
         Err(e) if is_offline_error(&e) => {
             let elapsed = start.elapsed();
             let payload = serde_json::json!({
-                "issue_number": issue,
+                "objective_id": objective.id,
                 "goal_id": goal.id,
+                "name": goal.name,
             });
-            db::queue_op(&conn, &link.forge_repo, "assign_goal", &payload.to_string())?;
+            db::queue_op(&conn, &link.forge_repo, "link_objective", &payload.to_string())?;
+            db::link_objective_goal(&conn, &link.forge_repo, &objective.id, &goal.id)?;
 
             if json {
                 let result = WriteResult {
                     success: true,
                     queued: true,
-                    issue_number: Some(issue),
-                    message: format!("Queued: assign #{} to '{}'", issue, goal.name),
+                    issue_number: None,
+                    message: format!("Queued: link goal '{}' to '{}'", goal.name, objective.name),
                     elapsed_ms: elapsed.as_millis() as u64,
                 };
                 println!("{}", serde_json::to_string_pretty(&result)?);
             } else {
-                println!("✓ Queued: assign #{} to '{}' (offline, {:.0}ms)", issue, goal.name, elapsed.as_millis());
+                println!("✓ Queued: link goal '{}' to '{}' (offline, {:.0}ms)", goal.name, objective.name, elapsed.as_millis());
             }
+            return Err(queued_error());
         }
         Err(e) => return Err(e),
     }
@@ -1428,7 +6108,8 @@ async fn cmd_goal_assign(issue: u64, goal_name: String, json: bool) -> Result<()
     Ok(())
 }
 
-async fn cmd_goal_close(name: String, json: bool) -> Result<()> {
+/// Close or reopen a goal
+async fn cmd_goal_set_state(name: String, target: GoalState, json: bool) -> Result<()> {
     let start = Instant::now();
     let repo_path = repo::detect_repo_path()?;
     let (forge, link) = get_forge_for_repo(&repo_path)?;
@@ -1436,7 +6117,29 @@ async fn cmd_goal_close(name: String, json: bool) -> Result<()> {
 
     // Resolve goal name to ID
     let goal = db::load_goal_by_name(&conn, &link.forge_repo, &name)?
-        .ok_or_else(|| anyhow::anyhow!("Goal '{}' not found. Run `isq sync` to refresh.", name))?;
+        .ok_or_else(|| error::err(ErrorKind::NotFound, format!("Goal '{}' not found. Run `isq sync` to refresh.", name)))?;
+
+    if goal.state == target {
+        let message = format!("Goal '{}' is already {}", goal.name, target.as_str());
+        if json {
+            let result = serde_json::json!({
+                "success": true,
+                "queued": false,
+                "unchanged": true,
+                "message": message,
+                "elapsed_ms": start.elapsed().as_millis() as u64,
+            });
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        } else {
+            println!("{}", message);
+        }
+        return Ok(());
+    }
+
+    let (verb, op_type) = match target {
+        GoalState::Closed => ("Closed", "close_goal"),
+        GoalState::Open => ("Reopened", "reopen_goal"),
+    };
 
     let parts: Vec<&str> = link.forge_repo.split('/').collect();
     if parts.len() != 2 {
@@ -1447,41 +6150,51 @@ async fn cmd_goal_close(name: String, json: bool) -> Result<()> {
         name: parts[1].to_string(),
     };
 
-    match forge.close_goal(&repo, &goal.id).await {
+    let result = match target {
+        GoalState::Closed => network::write(forge.close_goal(&repo, &goal.id)).await,
+        GoalState::Open => network::write(forge.reopen_goal(&repo, &goal.id)).await,
+    };
+
+    match result {
         Ok(()) => {
             let elapsed = start.elapsed();
+            db::set_goal_state(&conn, &link.forge_repo, &goal.id, target)?;
             if json {
                 let result = WriteResult {
                     success: true,
                     queued: false,
                     issue_number: None,
-                    message: format!("Closed goal '{}'", goal.name),
+                    message: format!("{} goal '{}'", verb, goal.name),
                     elapsed_ms: elapsed.as_millis() as u64,
                 };
                 println!("{}", serde_json::to_string_pretty(&result)?);
             } else {
-                println!("✓ Closed goal '{}' ({:.0}ms)", goal.name, elapsed.as_millis());
+                println!("✓ {} goal '{}' ({:.0}ms)", verb, goal.name, elapsed.as_millis());
             }
         }
         Err(e) if is_offline_error(&e) => {
             let elapsed = start.elapsed();
             let payload = serde_json::json!({
                 "goal_id": goal.id,
+                "name": goal.name,
             });
-            db::queue_op(&conn, &link.forge_repo, "close_goal", &payload.to_string())?;
+            db::queue_op(&conn, &link.forge_repo, op_type, &payload.to_string())?;
+            db::set_goal_state(&conn, &link.forge_repo, &goal.id, target)?;
 
+            let action = if target == GoalState::Closed { "close" } else { "reopen" };
             if json {
                 let result = WriteResult {
                     success: true,
                     queued: true,
                     issue_number: None,
-                    message: format!("Queued: close goal '{}'", goal.name),
+                    message: format!("Queued: {} goal '{}'", action, goal.name),
                     elapsed_ms: elapsed.as_millis() as u64,
                 };
                 println!("{}", serde_json::to_string_pretty(&result)?);
             } else {
-                println!("✓ Queued: close goal '{}' (offline, {:.0}ms)", goal.name, elapsed.as_millis());
+                println!("✓ Queued: {} goal '{}' (offline, {:.0}ms)", action, goal.name, elapsed.as_millis());
             }
+            return Err(queued_error());
         }
         Err(e) => return Err(e),
     }
@@ -1489,3 +6202,527 @@ async fn cmd_goal_close(name: String, json: bool) -> Result<()> {
     Ok(())
 }
 
+// ============================================================================
+// Backup Commands
+// ============================================================================
+
+fn cmd_label_list(names: bool, json: bool) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    let labels = db::load_labels(&conn, &link.forge_repo)?;
+    let open_counts = db::count_open_issues_by_label(&conn, &link.forge_repo)?;
+    if labels.is_empty() && !names {
+        eprintln!("No labels cached yet. Run `isq sync` first.");
+    }
+
+    if names {
+        for label in &labels {
+            println!("{}", label.name);
+        }
+    } else if json {
+        let output: Vec<_> = labels
+            .iter()
+            .map(|label| {
+                serde_json::json!({
+                    "name": label.name,
+                    "color": label.color,
+                    "description": label.description,
+                    "open_issues": open_counts.get(&label.name.to_lowercase()).copied().unwrap_or(0),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        display::print_label_catalog(&labels, &open_counts);
+    }
+
+    Ok(())
+}
+
+async fn cmd_label_sync(apply: bool, json: bool) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let labels = config::load()?.labels;
+    let team = team_config::load(&repo_path)?;
+    let taxonomy = team.taxonomy(&labels);
+    if taxonomy.is_empty() {
+        return Err(error::err(
+            ErrorKind::Usage,
+            format!(
+                "No label taxonomy configured. Add [[labels.taxonomy]] entries to {} (or the repo's {})",
+                config::config_path()?.display(),
+                team_config::path(&repo_path).display()
+            ),
+        ));
+    }
+
+    let (forge, link) = get_forge_for_repo(&repo_path)?;
+
+    let parts: Vec<&str> = link.forge_repo.split('/').collect();
+    if parts.len() != 2 {
+        anyhow::bail!("Invalid forge_repo format: {}", link.forge_repo);
+    }
+    let repo = repo::Repo {
+        owner: parts[0].to_string(),
+        name: parts[1].to_string(),
+    };
+
+    let existing = forge.list_labels(&repo).await?;
+    let conn = db::open()?;
+    db::save_labels(&conn, &link.forge_repo, &existing)?;
+    let changes = taxonomy::plan(taxonomy, &existing);
+    let unmanaged = taxonomy::unmanaged(taxonomy, &existing);
+
+    if apply {
+        for change in &changes {
+            match change {
+                taxonomy::LabelChange::Create(label) => forge.create_label(&repo, label).await?,
+                taxonomy::LabelChange::Update { from, to, .. } => forge.update_label(&repo, from, to).await?,
+            }
+            if !json {
+                println!("✓ {}", change.describe());
+            }
+        }
+        if !changes.is_empty() {
+            db::save_labels(&conn, &link.forge_repo, &forge.list_labels(&repo).await?)?;
+        }
+    }
+
+    if json {
+        let result = serde_json::json!({
+            "applied": apply,
+            "changes": changes.iter().map(|c| c.describe()).collect::<Vec<_>>(),
+            "unmanaged": unmanaged,
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
+
+    if changes.is_empty() {
+        println!("Labels match the taxonomy");
+    } else if !apply {
+        for change in &changes {
+            println!("  {}", change.describe());
+        }
+        println!("\n{} changes. Run with --apply to make them.", changes.len());
+    }
+    if !unmanaged.is_empty() {
+        eprintln!("Not in the taxonomy (left alone): {}", unmanaged.join(", "));
+    }
+
+    Ok(())
+}
+
+fn cmd_stats(weeks: u16, json: bool) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    let issues = db::load_issues(&conn, &link.forge_repo)?;
+    if issues.is_empty() {
+        eprintln!("No cached issues yet. Run `isq sync` first.");
+    }
+    let comments: Vec<_> = db::load_all_comments(&conn, &link.forge_repo)?.into_values().flatten().collect();
+    let today = chrono::Local::now().date_naive();
+    let stats = stats::compute(&issues, &comments, today, weeks as usize);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        print!("{}", stats::render(&stats));
+    }
+
+    Ok(())
+}
+
+fn cmd_report_goals(since: Option<String>, goal: Option<String>, format: &str) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    let snapshots = db::load_goal_snapshots(&conn, &link.forge_repo, since.as_deref(), goal.as_deref())?;
+    if snapshots.is_empty() {
+        if let Some(goal) = &goal
+            && db::load_goal_by_name(&conn, &link.forge_repo, goal)?.is_none()
+        {
+            return Err(error::err(ErrorKind::NotFound, format!("Goal '{}' not found. Run `isq sync` to refresh.", goal)));
+        }
+        eprintln!("No goal history yet. It's recorded each time goals sync (`isq sync` or the daemon).");
+    }
+
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&snapshots)?),
+        _ => print!("{}", report::goal_snapshots_csv(&snapshots)),
+    }
+
+    Ok(())
+}
+
+fn cmd_report_aging(maintainers: &[String], format: &str) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    let issues = db::load_issues(&conn, &link.forge_repo)?;
+    if issues.is_empty() {
+        eprintln!("No cached issues yet. Run `isq sync` first.");
+    }
+    let comments = db::load_all_comments(&conn, &link.forge_repo)?;
+    let maintainers: Vec<String> = if maintainers.is_empty() {
+        config::load().unwrap_or_default().list.maintainers
+    } else {
+        maintainers.iter().map(|m| m.trim_start_matches('@').to_string()).collect()
+    };
+    let report = report::aging(&issues, &comments, &maintainers, chrono::Utc::now());
+
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+        _ => {
+            let name = link.display_name.as_deref().unwrap_or(&link.forge_repo);
+            print!("{}", report::aging_markdown(name, &report));
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_digest(week: bool, days: u16, out: Option<&std::path::Path>, json: bool) -> Result<()> {
+    let conns = db::open_all()?;
+    let now = chrono::Utc::now();
+    let (since, until) = if week {
+        digest::last_week(now)
+    } else {
+        (now - chrono::Duration::days(days as i64), now)
+    };
+    let digest = digest::collect(&conns, since, until)?;
+
+    let contents = if json {
+        serde_json::to_string_pretty(&digest)? + "\n"
+    } else {
+        digest::markdown(&digest)
+    };
+    match out {
+        Some(path) => {
+            std::fs::write(path, contents)
+                .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+            eprintln!("✓ Wrote digest of {} repos to {}", digest.repos.len(), path.display());
+        }
+        None => print!("{}", contents),
+    }
+
+    Ok(())
+}
+
+fn cmd_estimate_report(by: &str, cycles: u16, format: &str) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    let issues = db::load_issues(&conn, &link.forge_repo)?;
+    if !issues.iter().any(|i| i.estimate.is_some()) {
+        eprintln!("No estimates cached. They come from Linear; run `isq sync` to fetch them.");
+    }
+    let grouping = match by {
+        "cycle" => report::EstimateGrouping::Cycle,
+        _ => report::EstimateGrouping::Goal,
+    };
+    let report = report::estimates(
+        &issues,
+        &db::load_cycles(&conn, &link.forge_repo)?,
+        grouping,
+        cycles as usize,
+        chrono::Utc::now(),
+    );
+
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+        _ => {
+            let name = link.display_name.as_deref().unwrap_or(&link.forge_repo);
+            print!("{}", report::estimates_markdown(name, grouping, &report));
+        }
+    }
+
+    Ok(())
+}
+
+fn cmd_export(out: &std::path::Path, json: bool) -> Result<()> {
+    let start = Instant::now();
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    let issues = db::load_issues(&conn, &link.forge_repo)?;
+    let comments = db::load_all_comments(&conn, &link.forge_repo)?;
+    let goals = db::load_goals(&conn, &link.forge_repo, None)?;
+    let name = link.display_name.as_deref().unwrap_or(&link.forge_repo);
+
+    let files = export::render_site(name, &issues, &comments, &goals);
+    std::fs::create_dir_all(out.join("issues"))
+        .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", out.display(), e))?;
+    for (path, contents) in &files {
+        let path = out.join(path);
+        std::fs::write(&path, contents).map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+    }
+    let elapsed = start.elapsed();
+
+    if json {
+        let result = serde_json::json!({
+            "path": out.join("index.html"),
+            "files": files.len(),
+            "issues": issues.len(),
+            "goals": goals.len(),
+            "elapsed_ms": elapsed.as_millis() as u64,
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        println!(
+            "✓ Exported {} issues and {} goals to {} ({:.0}ms)",
+            issues.len(),
+            goals.len(),
+            out.join("index.html").display(),
+            elapsed.as_millis()
+        );
+    }
+
+    Ok(())
+}
+
+fn cmd_backup_create(file: &std::path::Path, force: bool) -> Result<()> {
+    if file.exists() && !force {
+        return Err(error::err(
+            ErrorKind::Conflict,
+            format!("{} already exists. Use --force to overwrite.", file.display()),
+        ));
+    }
+
+    let conn = db::open()?;
+    db::backup_to(&conn, file)?;
+    let summary = db::inspect_backup(file)?;
+
+    println!(
+        "✓ Backed up {} linked repos, {} issues and {} pending operations to {}",
+        summary.repo_links,
+        summary.issues,
+        summary.pending_ops,
+        file.display()
+    );
+    println!("  Credentials aren't included; run `isq auth login` on the new machine.");
+    if db::is_cache_encrypted(&conn)? {
+        println!("  Bodies are encrypted: the new machine also needs the cache key (ISQ_CACHE_KEY).");
+    }
+    Ok(())
+}
+
+fn cmd_backup_restore(file: &std::path::Path) -> Result<()> {
+    if !file.exists() {
+        return Err(error::err(ErrorKind::NotFound, format!("{} not found", file.display())));
+    }
+    let summary = db::inspect_backup(file)?;
+
+    // Keep the current state so a bad restore can itself be undone
+    let mut conn = db::open()?;
+    let previous = db::db_path()?.with_extension("db.pre-restore");
+    if previous.exists() {
+        std::fs::remove_file(&previous)?;
+    }
+    db::backup_to(&conn, &previous)?;
+
+    db::restore_from(&mut conn, file)?;
+
+    println!(
+        "✓ Restored {} linked repos, {} watched, {} issues and {} pending operations",
+        summary.repo_links, summary.watched_repos, summary.issues, summary.pending_ops
+    );
+    println!("  Previous state saved to {}", previous.display());
+    Ok(())
+}
+
+// ============================================================================
+// Queue Commands
+// ============================================================================
+
+fn cmd_inbox(all: bool, json: bool) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    let events = db::load_inbox_events(&conn, &link.forge_repo, all)?;
+    db::mark_inbox_read(&conn, &link.forge_repo)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&events)?);
+        return Ok(());
+    }
+    if events.is_empty() {
+        println!("Nothing new.");
+        return Ok(());
+    }
+    for event in &events {
+        let new = if event.read_at.is_none() { "new  " } else { "     " };
+        println!("  {}{}  ({})", new, event.message, event.created_at);
+    }
+    Ok(())
+}
+
+fn cmd_queue_list(failed: bool, json: bool) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    let ops: Vec<(db::PendingOp, Option<String>)> = if failed {
+        db::load_failed_ops(&conn, &link.forge_repo)?.into_iter().map(|f| (f.op, Some(f.failed_at))).collect()
+    } else {
+        db::load_pending_ops(&conn, &link.forge_repo)?.into_iter().map(|op| (op, None)).collect()
+    };
+
+    if json {
+        let values: Vec<_> = ops
+            .iter()
+            .map(|(op, failed_at)| {
+                let mut value = op.to_json();
+                value["summary"] = serde_json::json!(op.summary());
+                if let Some(failed_at) = failed_at {
+                    value["failed_at"] = serde_json::json!(failed_at);
+                }
+                value
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&values)?);
+        return Ok(());
+    }
+
+    if ops.is_empty() {
+        if failed {
+            println!("No failed writes.");
+        } else {
+            println!("Nothing queued.");
+        }
+        return Ok(());
+    }
+    for (op, failed_at) in &ops {
+        match failed_at {
+            Some(at) => println!("  {}  (queued {}, gave up {})", op.summary(), op.created_at, at),
+            None => println!("  {}  (queued {})", op.summary(), op.created_at),
+        }
+        if let Some(error) = &op.last_error {
+            println!("      last error: {}", error);
+        }
+    }
+    if failed {
+        eprintln!("\n{} failed. Run `isq queue retry --failed` to queue them again.", ops.len());
+    }
+    Ok(())
+}
+
+fn cmd_queue_retry(failed: bool, json: bool) -> Result<()> {
+    let repo_path = repo::detect_repo_path()?;
+    let conn = db::open()?;
+    let link = db::get_repo_link(&conn, &repo_path)?
+        .ok_or_else(not_linked_error)?;
+
+    let requeued = if failed { db::retry_failed_ops(&conn, &link.forge_repo)? } else { 0 };
+    let pending = db::count_pending_ops(&conn, &link.forge_repo)?;
+    if pending > 0 {
+        db::request_sync(&db::open_shared()?, &repo_path)?;
+    }
+    let running = service::status().map(|s| s.running).unwrap_or(false);
+
+    if json {
+        let result = serde_json::json!({
+            "requeued": requeued,
+            "pending": pending,
+            "daemon_running": running,
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
+
+    if failed {
+        println!("✓ Queued {} failed writes again", requeued);
+    }
+    if pending == 0 {
+        println!("Nothing queued.");
+    } else if running {
+        println!("✓ Asked the daemon to replay {} queued writes", pending);
+    } else {
+        eprintln!("warning: the daemon isn't running, so nothing will sync (start it with `isq daemon start`)");
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Cache Commands
+// ============================================================================
+
+/// `isq cache encrypt` / `isq cache decrypt`: convert every cached body and
+/// record the choice so later writes follow it
+fn cmd_cache_convert(encrypt: bool, json: bool) -> Result<()> {
+    let start = Instant::now();
+    let conn = db::open()?;
+    let configured = config::load()?.cache.encrypt;
+    if !encrypt && configured {
+        return Err(error::err(
+            ErrorKind::Usage,
+            format!("[cache] encrypt = true is set in {}. Remove it first.", config::config_path()?.display()),
+        ));
+    }
+
+    let cipher = if encrypt {
+        cipher::cache_cipher(true)?
+    } else if db::is_cache_encrypted(&conn)? {
+        cipher::cache_cipher(false)?
+    } else {
+        // Nothing was encrypted unless a key exists; without one there's nothing to undo
+        match cipher::cache_cipher(false) {
+            Ok(cipher) => cipher,
+            Err(_) => {
+                let message = "The cache isn't encrypted".to_string();
+                return print_cache_result(json, 0, &message, start);
+            }
+        }
+    };
+
+    let changed = db::convert_bodies(&conn, cipher, encrypt)?;
+    db::scrub(&conn)?;
+
+    let message = if encrypt {
+        format!("Encrypted {} cached bodies", changed)
+    } else {
+        format!("Decrypted {} cached bodies", changed)
+    };
+    print_cache_result(json, changed, &message, start)?;
+    if encrypt && !json && !configured {
+        eprintln!("  New bodies are encrypted too. Add `[cache] encrypt = true` to the config to make it explicit.");
+    }
+    Ok(())
+}
+
+fn print_cache_result(json: bool, changed: usize, message: &str, start: Instant) -> Result<()> {
+    if json {
+        let result = serde_json::json!({
+            "success": true,
+            "changed": changed,
+            "message": message,
+            "elapsed_ms": start.elapsed().as_millis() as u64,
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        println!("✓ {}", message);
+    }
+    Ok(())
+}
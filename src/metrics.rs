@@ -0,0 +1,196 @@
+//! Prometheus metrics for the daemon (`GET /metrics`)
+//!
+//! Off unless an address is configured; the daemon reads it at startup.
+//!
+//! ```toml
+//! [daemon]
+//! metrics_addr = "127.0.0.1:9464"
+//! ```
+//!
+//! Sync counts and durations are kept in memory since the daemon started.
+//! Queue depth and rate-limit budgets are read from the cache on each scrape,
+//! so they also reflect writes queued by the CLI.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+use crate::db::{self, ForgeBudget};
+
+/// Sync outcomes for one watched repo
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RepoSyncMetrics {
+    pub syncs: u64,
+    pub errors: u64,
+    pub last_duration_secs: f64,
+    pub total_duration_secs: f64,
+}
+
+/// Sync outcomes per watched repo (by path), shared with the metrics server
+#[derive(Debug, Default, Clone)]
+pub struct SyncMetrics {
+    repos: Arc<Mutex<BTreeMap<String, RepoSyncMetrics>>>,
+}
+
+impl SyncMetrics {
+    /// Record one sync attempt of a repo
+    pub fn record(&self, repo_path: &str, duration: Duration, ok: bool) {
+        let mut repos = self.repos.lock().unwrap_or_else(|e| e.into_inner());
+        let repo = repos.entry(repo_path.to_string()).or_default();
+        repo.syncs += 1;
+        if !ok {
+            repo.errors += 1;
+        }
+        repo.last_duration_secs = duration.as_secs_f64();
+        repo.total_duration_secs += duration.as_secs_f64();
+    }
+
+    fn snapshot(&self) -> BTreeMap<String, RepoSyncMetrics> {
+        self.repos.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+/// Escape a label value for the text exposition format
+fn label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// A per-repo metric family: name, type, help, and its value for a repo
+type RepoFamily = (&'static str, &'static str, &'static str, fn(&RepoSyncMetrics) -> String);
+
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Render metrics in the Prometheus text format
+pub fn render(syncs: &BTreeMap<String, RepoSyncMetrics>, pending: &[(String, i64)], budgets: &[ForgeBudget]) -> String {
+    let mut out = String::new();
+
+    let per_repo: [RepoFamily; 4] = [
+        ("isq_syncs_total", "counter", "Sync attempts since the daemon started", |m| m.syncs.to_string()),
+        ("isq_sync_errors_total", "counter", "Failed sync attempts since the daemon started", |m| m.errors.to_string()),
+        ("isq_sync_duration_seconds_total", "counter", "Time spent syncing", |m| m.total_duration_secs.to_string()),
+        ("isq_last_sync_duration_seconds", "gauge", "Duration of the latest sync attempt", |m| m.last_duration_secs.to_string()),
+    ];
+    for (name, kind, help, value) in per_repo {
+        family(&mut out, name, kind, help);
+        for (repo, metrics) in syncs {
+            let _ = writeln!(out, "{}{{repo=\"{}\"}} {}", name, label(repo), value(metrics));
+        }
+    }
+
+    family(&mut out, "isq_pending_ops", "gauge", "Writes queued for sync");
+    for (forge_repo, count) in pending {
+        let _ = writeln!(out, "isq_pending_ops{{forge_repo=\"{}\"}} {}", label(forge_repo), count);
+    }
+
+    family(&mut out, "isq_rate_limit_remaining", "gauge", "Requests left in the forge's rate-limit window");
+    for budget in budgets {
+        if let Some(remaining) = budget.remaining {
+            let _ = writeln!(out, "isq_rate_limit_remaining{{forge=\"{}\"}} {}", label(&budget.forge), remaining);
+        }
+    }
+    family(&mut out, "isq_complexity_remaining", "gauge", "Query-complexity points left (Linear)");
+    for budget in budgets {
+        if let Some(remaining) = budget.complexity_remaining {
+            let _ = writeln!(out, "isq_complexity_remaining{{forge=\"{}\"}} {}", label(&budget.forge), remaining);
+        }
+    }
+    out
+}
+
+/// Current metrics, reading queue depth and budgets from every cache
+fn scrape(metrics: &SyncMetrics) -> Result<String> {
+    let conns = db::open_all()?;
+    let mut pending = Vec::new();
+    for conn in &conns {
+        pending.extend(db::pending_op_counts(conn)?);
+    }
+    pending.sort();
+    Ok(render(&metrics.snapshot(), &pending, &db::forge_budgets(&conns)?))
+}
+
+/// Serve `GET /metrics` on `addr` until the daemon exits
+pub async fn serve(addr: &str, metrics: SyncMetrics) -> Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    eprintln!("[daemon] Serving metrics on http://{}/metrics", addr);
+
+    loop {
+        // Accept errors (a reset connection, running out of file descriptors) are
+        // per-connection; back off briefly rather than taking the endpoint down
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                eprintln!("[daemon] Metrics accept failed: {}", e);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                continue;
+            }
+        };
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            let (read, mut write) = stream.into_split();
+            let mut request_line = String::new();
+            if BufReader::new(read).read_line(&mut request_line).await.is_err() {
+                return;
+            }
+
+            let mut parts = request_line.split_whitespace();
+            let response = match (parts.next(), parts.next()) {
+                (Some("GET"), Some("/metrics")) => match scrape(&metrics) {
+                    Ok(body) => response("200 OK", "text/plain; version=0.0.4", &body),
+                    Err(e) => response("500 Internal Server Error", "text/plain", &format!("{}\n", e)),
+                },
+                _ => response("404 Not Found", "text/plain", "Not found; try /metrics\n"),
+            };
+            let _ = write.write_all(response.as_bytes()).await;
+        });
+    }
+}
+
+fn response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let metrics = SyncMetrics::default();
+        metrics.record("/src/api", Duration::from_millis(1500), true);
+        metrics.record("/src/api", Duration::from_millis(500), false);
+
+        let budgets = vec![
+            ForgeBudget { forge: "github".to_string(), remaining: Some(4200), complexity_remaining: None },
+            ForgeBudget { forge: "linear".to_string(), remaining: None, complexity_remaining: Some(90) },
+        ];
+        let text = render(&metrics.snapshot(), &[("acme/\"api\"".to_string(), 3)], &budgets);
+
+        for line in [
+            "# TYPE isq_syncs_total counter",
+            "isq_syncs_total{repo=\"/src/api\"} 2",
+            "isq_sync_errors_total{repo=\"/src/api\"} 1",
+            "isq_sync_duration_seconds_total{repo=\"/src/api\"} 2",
+            "isq_last_sync_duration_seconds{repo=\"/src/api\"} 0.5",
+            "isq_pending_ops{forge_repo=\"acme/\\\"api\\\"\"} 3",
+            "isq_rate_limit_remaining{forge=\"github\"} 4200",
+            "isq_complexity_remaining{forge=\"linear\"} 90",
+        ] {
+            assert!(text.lines().any(|l| l == line), "missing {:?} in\n{}", line, text);
+        }
+        assert!(!text.contains("isq_rate_limit_remaining{forge=\"linear\"}"));
+    }
+}
@@ -0,0 +1,180 @@
+//! HTTP timeouts and command deadlines
+//!
+//! ```toml
+//! [network]
+//! connect_timeout_secs = 10   # opening a connection
+//! read_timeout_secs = 30      # waiting for the next bytes of a response
+//! command_timeout_secs = 600  # a whole command, or one repo's daemon sync (default: none)
+//! ```
+//!
+//! 0 turns a limit off. Forge clients come from `client()`, so a hung
+//! connection errors instead of stalling. A command that runs past its
+//! deadline is dropped, which cancels its in-flight requests (every page of a
+//! parallel fetch included). Time spent in `$EDITOR` or the picker doesn't
+//! count towards the deadline, and a write in flight (`write()`) is let finish
+//! or time out on its own, so the command can queue it rather than lose it.
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::config;
+use crate::error::{self, ErrorKind};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Give up opening a connection after this long
+    pub connect_timeout_secs: u64,
+    /// Give up on a response that sends nothing for this long
+    pub read_timeout_secs: u64,
+    /// Cancel a command (or one repo's daemon sync) that runs this long
+    pub command_timeout_secs: u64,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        NetworkConfig { connect_timeout_secs: 10, read_timeout_secs: 30, command_timeout_secs: 0 }
+    }
+}
+
+impl NetworkConfig {
+    pub fn command_timeout(&self) -> Option<Duration> {
+        limit(self.command_timeout_secs)
+    }
+}
+
+fn limit(secs: u64) -> Option<Duration> {
+    (secs > 0).then(|| Duration::from_secs(secs))
+}
+
+/// HTTP client with the configured connect and read timeouts
+pub fn client() -> reqwest::Client {
+    let config = config::load().map(|c| c.network).unwrap_or_default();
+    let mut builder = reqwest::Client::builder();
+    if let Some(timeout) = limit(config.connect_timeout_secs) {
+        builder = builder.connect_timeout(timeout);
+    }
+    if let Some(timeout) = limit(config.read_timeout_secs) {
+        builder = builder.read_timeout(timeout);
+    }
+    builder.build().unwrap_or_else(|_| reqwest::Client::new())
+}
+
+/// Milliseconds spent waiting on the user, which deadlines don't count
+static INTERACTIVE_MS: AtomicU64 = AtomicU64::new(0);
+/// Waits on the user in progress
+static WAITING: AtomicUsize = AtomicUsize::new(0);
+
+/// How often an expired deadline checks whether the user is done
+const WAITING_POLL: Duration = Duration::from_secs(1);
+
+/// Until dropped, the clock on any running deadline is stopped
+pub struct Interactive(Instant);
+
+/// Stop the deadline clock while waiting on the user (editor, picker,
+/// prompts, browser sign-in)
+pub fn interactive() -> Interactive {
+    WAITING.fetch_add(1, Ordering::Relaxed);
+    Interactive(Instant::now())
+}
+
+impl Drop for Interactive {
+    fn drop(&mut self) {
+        INTERACTIVE_MS.fetch_add(self.0.elapsed().as_millis() as u64, Ordering::Relaxed);
+        WAITING.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn interactive_time() -> Duration {
+    Duration::from_millis(INTERACTIVE_MS.load(Ordering::Relaxed))
+}
+
+/// Forge writes in flight, which an expired deadline waits for
+static WRITING: AtomicUsize = AtomicUsize::new(0);
+
+struct Writing;
+
+impl Drop for Writing {
+    fn drop(&mut self) {
+        WRITING.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Run a forge write that a deadline mustn't cut off mid-request, where it
+/// may or may not land. Its request timeouts still apply; the caller queues
+/// it when one fires.
+pub async fn write<T>(fut: impl Future<Output = Result<T>>) -> Result<T> {
+    WRITING.fetch_add(1, Ordering::Relaxed);
+    let _writing = Writing;
+    fut.await
+}
+
+/// Run `fut`, giving up on it after `timeout` (None: no deadline). Giving up
+/// drops it, cancelling whatever requests it has in flight.
+pub async fn deadline<T>(timeout: Option<Duration>, what: &str, fut: impl Future<Output = Result<T>>) -> Result<T> {
+    let Some(timeout) = timeout else {
+        return fut.await;
+    };
+    let start = Instant::now();
+    let waited_before = interactive_time();
+    let expired = async {
+        loop {
+            let end = start + timeout + (interactive_time() - waited_before);
+            let past_end = Instant::now() >= end;
+            // A write past the end is let land or fail before stopping
+            if WAITING.load(Ordering::Relaxed) > 0 || (past_end && WRITING.load(Ordering::Relaxed) > 0) {
+                tokio::time::sleep(WAITING_POLL).await;
+            } else if past_end {
+                break;
+            } else {
+                tokio::time::sleep_until(end.into()).await;
+            }
+        }
+    };
+
+    tokio::select! {
+        result = fut => result,
+        _ = expired => Err(error::err(
+            ErrorKind::Timeout,
+            format!(
+                "{} took longer than {}s and was cancelled (raise [network] command_timeout_secs in {})",
+                what,
+                timeout.as_secs(),
+                config::config_path().map(|p| p.display().to_string()).unwrap_or_else(|_| "config.toml".to_string()),
+            ),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_deadline() {
+        let done = deadline(Some(Duration::from_secs(5)), "Test", async { Ok(1) }).await.unwrap();
+        assert_eq!(done, 1);
+
+        let hung = deadline(Some(Duration::from_millis(20)), "Test", std::future::pending::<Result<()>>()).await;
+        let e = hung.unwrap_err();
+        assert_eq!(error::classify(&e), ErrorKind::Timeout);
+        assert!(e.to_string().starts_with("Test took longer than"));
+
+        // Opt-in only
+        assert!(NetworkConfig::default().command_timeout().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_deadline_lets_writes_finish() {
+        let slow_write = write(async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(2)
+        });
+        let done = deadline(Some(Duration::from_millis(10)), "Test", slow_write).await.unwrap();
+        assert_eq!(done, 2);
+    }
+}
@@ -0,0 +1,160 @@
+//! Release notes from a goal's closed issues (`isq goal notes`)
+//!
+//! Issues are grouped into sections by label; the first section with a
+//! matching label wins and anything unmatched lands under "Other".
+//!
+//! ```toml
+//! [[notes.sections]]
+//! title = "Features"
+//! labels = ["enhancement", "feature"]
+//!
+//! [[notes.sections]]
+//! title = "Fixes"
+//! labels = ["bug"]
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::forges::Issue;
+
+/// Section for issues no configured section claims
+const OTHER_SECTION: &str = "Other";
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct NotesConfig {
+    /// Sections in output order (defaults to Features/Fixes/Chores)
+    pub sections: Vec<NotesSection>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotesSection {
+    pub title: String,
+    /// Labels (case-insensitive) that put an issue in this section
+    pub labels: Vec<String>,
+}
+
+impl NotesConfig {
+    /// Configured sections, or the defaults if none are set
+    pub fn sections(&self) -> Vec<NotesSection> {
+        if !self.sections.is_empty() {
+            return self.sections.clone();
+        }
+        let section = |title: &str, labels: &[&str]| NotesSection {
+            title: title.to_string(),
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+        };
+        vec![
+            section("Features", &["enhancement", "feature"]),
+            section("Fixes", &["bug", "fix"]),
+            section("Chores", &["chore", "maintenance", "dependencies"]),
+        ]
+    }
+}
+
+/// One section of the notes and its issues
+#[derive(Debug, Clone, Serialize)]
+pub struct NotesGroup<'a> {
+    pub title: String,
+    pub issues: Vec<&'a Issue>,
+}
+
+/// Group closed issues into sections, dropping empty ones. Issues keep
+/// ascending number order within a section.
+pub fn group<'a>(sections: &[NotesSection], issues: &'a [Issue]) -> Vec<NotesGroup<'a>> {
+    let mut groups: Vec<NotesGroup> = sections
+        .iter()
+        .map(|s| NotesGroup { title: s.title.clone(), issues: Vec::new() })
+        .chain(std::iter::once(NotesGroup { title: OTHER_SECTION.to_string(), issues: Vec::new() }))
+        .collect();
+
+    let mut closed: Vec<&Issue> = issues.iter().filter(|i| i.state == "closed").collect();
+    closed.sort_by_key(|i| i.number);
+
+    for issue in closed {
+        let index = sections
+            .iter()
+            .position(|s| {
+                s.labels
+                    .iter()
+                    .any(|wanted| issue.labels.iter().any(|l| l.name.eq_ignore_ascii_case(wanted)))
+            })
+            .unwrap_or(sections.len());
+        groups[index].issues.push(issue);
+    }
+
+    groups.retain(|g| !g.issues.is_empty());
+    groups
+}
+
+/// Render grouped issues as markdown release notes
+pub fn render_markdown(goal: &str, groups: &[NotesGroup]) -> String {
+    let mut out = format!("# {}\n", goal);
+    if groups.is_empty() {
+        out.push_str("\nNo closed issues.\n");
+    }
+    for group in groups {
+        out.push_str(&format!("\n## {}\n\n", group.title));
+        for issue in &group.issues {
+            out.push_str(&format!("- {} ({})\n", issue.title.trim(), issue.display_id()));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forges::Label;
+
+    fn issue(number: u64, title: &str, state: &str, labels: &[&str]) -> Issue {
+        Issue {
+            number,
+            title: title.to_string(),
+            state: state.to_string(),
+            author: "alice".to_string(),
+            labels: labels.iter().map(|l| Label::name_only(l.to_string())).collect(),
+            milestone: Some("v1.0".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let issues = vec![
+            issue(4, "Fix crash on save", "closed", &["Bug"]),
+            issue(2, "Add export", "closed", &["enhancement", "bug"]),
+            issue(3, "Still open", "open", &["bug"]),
+            issue(5, "Tidy README", "closed", &[]),
+            issue(1, "Fix typo", "closed", &["bug"]),
+        ];
+        let groups = group(&NotesConfig::default().sections(), &issues);
+
+        assert_eq!(
+            render_markdown("v1.0", &groups),
+            "# v1.0\n\n\
+             ## Features\n\n- Add export (#2)\n\n\
+             ## Fixes\n\n- Fix typo (#1)\n- Fix crash on save (#4)\n\n\
+             ## Other\n\n- Tidy README (#5)\n"
+        );
+        assert_eq!(render_markdown("v2.0", &[]), "# v2.0\n\nNo closed issues.\n");
+    }
+
+    #[test]
+    fn test_configured_sections() {
+        let config = crate::config::parse(
+            r#"
+            [[notes.sections]]
+            title = "Docs"
+            labels = ["documentation"]
+            "#,
+        )
+        .unwrap();
+        let sections = config.notes.sections();
+        assert_eq!(sections.len(), 1);
+
+        let issues = vec![issue(1, "Guide", "closed", &["Documentation"]), issue(2, "Fix", "closed", &["bug"])];
+        let titles: Vec<_> = group(&sections, &issues).into_iter().map(|g| g.title).collect();
+        assert_eq!(titles, vec!["Docs", "Other"]);
+    }
+}
@@ -0,0 +1,172 @@
+//! Fuzzy issue picker (`isq issue pick`)
+//!
+//! Matching works like fzf: each word of the query must appear in the issue's
+//! id, title or labels with its letters in order, and runs of consecutive
+//! letters and word starts score higher, so "lgn tmo" finds "Login times
+//! out". The picker draws on stderr and reads keys from the terminal, which
+//! leaves stdout for the picked number (`isq issue show $(isq issue pick)`).
+
+use std::io;
+
+use anyhow::Result;
+use colored::Colorize;
+use console::{Key, Term};
+
+use crate::forges::Issue;
+use crate::network;
+
+/// How many matches are on screen at once
+const HEIGHT: usize = 10;
+
+const CONSECUTIVE_BONUS: i64 = 4;
+const WORD_START_BONUS: i64 = 3;
+
+/// Score one query word against `text` (both lowercase), or `None` if its
+/// letters don't all appear in order
+fn score_word(word: &[char], text: &[char]) -> Option<i64> {
+    let mut score = 0;
+    let mut last: Option<usize> = None;
+    let mut from = 0;
+    for &c in word {
+        let at = from + text[from..].iter().position(|&t| t == c)?;
+        score += 1;
+        if last.is_some_and(|l| l + 1 == at) {
+            score += CONSECUTIVE_BONUS;
+        }
+        if at == 0 || !text[at - 1].is_alphanumeric() {
+            score += WORD_START_BONUS;
+        }
+        last = Some(at);
+        from = at + 1;
+    }
+    Some(score)
+}
+
+/// Score a query against `text`: every whitespace-separated word must match
+pub fn score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    query.split_whitespace().try_fold(0, |total, word| {
+        let word: Vec<char> = word.to_lowercase().chars().collect();
+        Some(total + score_word(&word, &text)?)
+    })
+}
+
+/// What the query is matched against
+fn haystack(issue: &Issue) -> String {
+    let labels: Vec<&str> = issue.labels.iter().map(|l| l.name.as_str()).collect();
+    format!("{} {} {}", issue.display_id(), issue.title, labels.join(" "))
+}
+
+/// Issues matching `query`, best first (ties keep their order)
+pub fn rank<'a>(query: &str, issues: &'a [Issue]) -> Vec<&'a Issue> {
+    let mut scored: Vec<(i64, &Issue)> = issues
+        .iter()
+        .filter_map(|issue| score(query, &haystack(issue)).map(|s| (s, issue)))
+        .collect();
+    scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+    scored.into_iter().map(|(_, issue)| issue).collect()
+}
+
+fn render(term: &Term, query: &str, matches: &[&Issue], total: usize, selected: usize) -> io::Result<usize> {
+    let width = term.size().1 as usize;
+    let top = selected.saturating_sub(HEIGHT - 1);
+    let mut lines = 0;
+    for (i, issue) in matches.iter().enumerate().skip(top).take(HEIGHT) {
+        let line: String = format!("{:>8}  {}", issue.display_id(), issue.title)
+            .chars()
+            .take(width.saturating_sub(3))
+            .collect();
+        if i == selected {
+            term.write_line(&format!("{} {}", ">".cyan().bold(), line.bold()))?;
+        } else {
+            term.write_line(&format!("  {}", line))?;
+        }
+        lines += 1;
+    }
+    term.write_line(&format!("  {}", format!("{}/{}", matches.len(), total).dimmed()))?;
+    term.write_line(&format!("{} {}", ">".cyan().bold(), query))?;
+    Ok(lines + 2)
+}
+
+/// Let the user pick one of `issues`, starting from `query`. `None` if they
+/// cancel (Esc or Ctrl-C).
+pub fn pick(issues: &[Issue], query: &str) -> Result<Option<u64>> {
+    let _waiting = network::interactive();
+    let term = Term::stderr();
+    let mut query = query.to_string();
+    let mut selected = 0;
+    let mut drawn = 0;
+
+    term.hide_cursor()?;
+    let picked = loop {
+        let matches = rank(&query, issues);
+        selected = selected.min(matches.len().saturating_sub(1));
+        term.clear_last_lines(drawn)?;
+        drawn = render(&term, &query, &matches, issues.len(), selected)?;
+
+        match term.read_key_raw()? {
+            Key::Enter => break matches.get(selected).map(|issue| issue.number),
+            Key::Escape | Key::CtrlC => break None,
+            Key::ArrowUp | Key::BackTab | Key::Char('\x10') => selected = selected.saturating_sub(1),
+            Key::ArrowDown | Key::Tab | Key::Char('\x0e') => {
+                selected = (selected + 1).min(matches.len().saturating_sub(1))
+            }
+            Key::Backspace => {
+                query.pop();
+                selected = 0;
+            }
+            // Ctrl-U clears the query
+            Key::Char('\x15') => {
+                query.clear();
+                selected = 0;
+            }
+            Key::Char(c) if !c.is_control() => {
+                query.push(c);
+                selected = 0;
+            }
+            _ => {}
+        }
+    };
+    term.clear_last_lines(drawn)?;
+    term.show_cursor()?;
+    Ok(picked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(number: u64, title: &str) -> Issue {
+        Issue {
+            number,
+            title: title.to_string(),
+            state: "open".to_string(),
+            author: "alice".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_score_needs_letters_in_order() {
+        assert!(score("lgn", "Login times out").is_some());
+        assert!(score("lgn tmo", "Login times out").is_some());
+        assert!(score("ngl", "Login times out").is_none());
+        assert!(score("login crash", "Login times out").is_none());
+        assert_eq!(score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn test_rank_prefers_tight_matches() {
+        let issues = vec![
+            issue(3, "Create a shortcut"),
+            issue(2, "Crash on startup"),
+            issue(1, "Docs typo"),
+        ];
+        let ranked: Vec<u64> = rank("crash", &issues).iter().map(|i| i.number).collect();
+        assert_eq!(ranked, vec![2, 3]);
+
+        // Numbers match too, and an empty query keeps the cache's order
+        assert_eq!(rank("#1", &issues)[0].number, 1);
+        assert_eq!(rank("", &issues).len(), 3);
+    }
+}
@@ -0,0 +1,27 @@
+//! Progress bars for long fetches (large initial syncs)
+//!
+//! Drawn on stderr and hidden when it isn't a terminal, so piped output and
+//! the daemon's log stay clean.
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// A bar over a known number of steps, e.g. pages of issues
+pub fn bar(len: u64, message: &str) -> ProgressBar {
+    let pb = ProgressBar::new(len);
+    pb.set_style(
+        ProgressStyle::with_template("{msg} [{bar:30}] {pos}/{len} pages ({eta})")
+            .expect("valid template")
+            .progress_chars("=> "),
+    );
+    pb.set_message(message.to_string());
+    pb
+}
+
+/// A running count when the total isn't known up front
+pub fn counter(message: &str) -> ProgressBar {
+    let pb = ProgressBar::new_spinner();
+    pb.set_style(ProgressStyle::with_template("{spinner} {msg} {pos} issues").expect("valid template"));
+    pb.set_message(message.to_string());
+    pb.enable_steady_tick(std::time::Duration::from_millis(120));
+    pb
+}
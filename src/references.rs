@@ -0,0 +1,107 @@
+//! Issue references (`#123`, `ABC-123`) in bodies and comments, for backlinks
+
+/// A reference to another issue. `key` is the team key of an identifier
+/// (`ABC` in `ABC-123`); `#123` references have none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub key: Option<String>,
+    pub number: u64,
+}
+
+/// Characters that make a `#` or key part of a larger token (`foo#1`,
+/// `owner/repo#1`, `&#123;`, `x-ABC-1`)
+fn joins_token(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '/' | '&' | '-' | '_' | '.')
+}
+
+/// References in markdown text, in order of first appearance. Fenced code
+/// blocks and `owner/repo#123` (another repo) are skipped.
+pub fn extract(text: &str) -> Vec<Reference> {
+    let mut refs = Vec::new();
+    let mut in_code = false;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if !in_code {
+            extract_line(line, &mut refs);
+        }
+    }
+    refs
+}
+
+fn extract_line(line: &str, refs: &mut Vec<Reference>) {
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        if i > 0 && joins_token(chars[i - 1]) {
+            i += 1;
+            continue;
+        }
+
+        let reference = if chars[i] == '#' {
+            let digits = take_digits(&chars, i + 1);
+            (digits > 0).then(|| (None, i + 1, digits))
+        } else if chars[i].is_ascii_uppercase() {
+            let key_len = chars[i..].iter().take_while(|c| c.is_ascii_uppercase() || c.is_ascii_digit()).count();
+            let dash = i + key_len;
+            let digits = if chars.get(dash) == Some(&'-') { take_digits(&chars, dash + 1) } else { 0 };
+            (digits > 0).then(|| (Some(chars[i..dash].iter().collect::<String>()), dash + 1, digits))
+        } else {
+            None
+        };
+
+        match reference {
+            Some((key, start, len)) if !chars.get(start + len).is_some_and(|&c| joins_token(c) && c != '.') => {
+                let number: String = chars[start..start + len].iter().collect();
+                if let Ok(number) = number.parse() {
+                    let reference = Reference { key, number };
+                    if !refs.contains(&reference) {
+                        refs.push(reference);
+                    }
+                }
+                i = start + len;
+            }
+            _ => i += 1,
+        }
+    }
+}
+
+fn take_digits(chars: &[char], start: usize) -> usize {
+    chars.get(start..).map_or(0, |rest| rest.iter().take_while(|c| c.is_ascii_digit()).count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(n: u64) -> Reference {
+        Reference { key: None, number: n }
+    }
+
+    fn ident(key: &str, n: u64) -> Reference {
+        Reference { key: Some(key.to_string()), number: n }
+    }
+
+    #[test]
+    fn test_extract_numbers_and_identifiers() {
+        let refs = extract("Dup of #12, see ENG-7 and (#3). Also #12 again.");
+        assert_eq!(refs, vec![number(12), ident("ENG", 7), number(3)]);
+    }
+
+    #[test]
+    fn test_extract_skips_other_repos_urls_and_code() {
+        let text = "acme/api#4 and foo#5 and &#123; and https://x.io/#6\n```\n#7\n```\nv1-ENG-8 `#9`";
+        // Inline code isn't special-cased; only fenced blocks are skipped
+        assert_eq!(extract(text), vec![number(9)]);
+    }
+
+    #[test]
+    fn test_extract_requires_a_token_boundary_after() {
+        assert_eq!(extract("#12abc ENG-3x #4."), vec![number(4)]);
+        // Any KEY-N looks like an identifier; indexing keeps only known team keys
+        assert_eq!(extract("UTF-8"), vec![ident("UTF", 8)]);
+    }
+}
@@ -0,0 +1,94 @@
+//! "Relevance to me" order for `isq issue list --smart`
+//!
+//! Computed from the cache alone: issues assigned to you come first, then
+//! ones you've commented on, with urgent and recently updated issues pulled
+//! up within each. Recency halves every week, so an issue untouched for a
+//! month barely counts for it.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+
+use crate::forges::Issue;
+
+const ASSIGNED: f64 = 100.0;
+const COMMENTED: f64 = 40.0;
+/// Points for priority 1 (urgent) through 4 (low)
+const PRIORITY: [f64; 4] = [30.0, 20.0, 10.0, 0.0];
+/// Points for an issue updated just now
+const RECENT: f64 = 30.0;
+const RECENCY_HALF_LIFE_DAYS: f64 = 7.0;
+
+/// What's known about you when scoring
+#[derive(Debug, Default)]
+pub struct Me<'a> {
+    /// Your login, if isq knows it
+    pub login: Option<&'a str>,
+    /// Issues you've commented on
+    pub commented: HashSet<u64>,
+}
+
+/// How relevant an issue is to you (higher first)
+pub fn score(issue: &Issue, me: &Me, now: DateTime<Utc>) -> f64 {
+    let mut score = 0.0;
+    if me.login.is_some_and(|login| issue.assignees.iter().any(|a| a.eq_ignore_ascii_case(login))) {
+        score += ASSIGNED;
+    }
+    if me.commented.contains(&issue.number) {
+        score += COMMENTED;
+    }
+    if let Some(points) = issue.priority.and_then(|p| PRIORITY.get((p as usize).checked_sub(1)?)) {
+        score += points;
+    }
+    if let Ok(updated) = DateTime::parse_from_rfc3339(&issue.updated_at) {
+        let age_days = (now - updated.with_timezone(&Utc)).num_seconds().max(0) as f64 / 86400.0;
+        score += RECENT * 0.5_f64.powf(age_days / RECENCY_HALF_LIFE_DAYS);
+    }
+    score
+}
+
+/// Sort issues most relevant first (ties keep their order)
+pub fn sort(issues: &mut [Issue], me: &Me, now: DateTime<Utc>) {
+    issues.sort_by(|a, b| score(b, me, now).total_cmp(&score(a, me, now)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(number: u64, updated_at: &str) -> Issue {
+        Issue {
+            number,
+            title: format!("Issue {}", number),
+            state: "open".to_string(),
+            author: "bob".to_string(),
+            created_at: updated_at.to_string(),
+            updated_at: updated_at.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sort_by_relevance() {
+        let now = DateTime::parse_from_rfc3339("2025-06-30T00:00:00Z").unwrap().with_timezone(&Utc);
+        let old = "2025-01-01T00:00:00Z";
+
+        let mut assigned = issue(1, old);
+        assigned.assignees = vec!["Alice".to_string()];
+        let commented = issue(2, old);
+        let mut urgent = issue(3, old);
+        urgent.priority = Some(1);
+        let fresh = issue(4, "2025-06-29T00:00:00Z");
+        let stale = issue(5, old);
+
+        let me = Me { login: Some("alice"), commented: HashSet::from([2]) };
+        let mut issues = vec![stale, fresh, urgent, commented, assigned];
+        sort(&mut issues, &me, now);
+        let order: Vec<u64> = issues.iter().map(|i| i.number).collect();
+        assert_eq!(order, vec![1, 2, 3, 4, 5]);
+
+        // Without a login, assignment can't count
+        let anonymous = Me::default();
+        assert_eq!(score(&issues[0], &anonymous, now), score(&issues[4], &anonymous, now));
+    }
+}
@@ -0,0 +1,153 @@
+//! Goal target-date reminders
+//!
+//! An open goal is at risk when its target date is near (or past) and it's
+//! less far along than it should be by then. The daemon leaves an inbox event
+//! and fires the `on_goal_at_risk` hook once per goal and target date;
+//! `isq goal list` flags at-risk goals.
+//!
+//! ```toml
+//! [reminders]
+//! days = 7             # target date within this many days (0 turns reminders off)
+//! min_progress = 0.5   # and less than half done
+//!
+//! [reminders.repos."acme/api"]   # per forge repo, overriding the above
+//! days = 14
+//! ```
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::forges::{Goal, GoalState};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RemindersConfig {
+    pub days: u32,
+    pub min_progress: f64,
+    /// Overrides keyed by forge repo (`owner/repo`, or the team for Linear)
+    pub repos: HashMap<String, ReminderOverride>,
+}
+
+impl Default for RemindersConfig {
+    fn default() -> Self {
+        RemindersConfig { days: 7, min_progress: 0.5, repos: HashMap::new() }
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct ReminderOverride {
+    pub days: Option<u32>,
+    pub min_progress: Option<f64>,
+}
+
+/// When a repo's goals count as at risk
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReminderRule {
+    pub days: u32,
+    pub min_progress: f64,
+}
+
+impl RemindersConfig {
+    /// The rule for a forge repo: its overrides on top of the defaults
+    pub fn rule(&self, forge_repo: &str) -> ReminderRule {
+        let repo = self.repos.get(forge_repo);
+        ReminderRule {
+            days: repo.and_then(|r| r.days).unwrap_or(self.days),
+            min_progress: repo.and_then(|r| r.min_progress).unwrap_or(self.min_progress),
+        }
+    }
+}
+
+/// Days from `today` to the goal's target date (negative once it's past).
+/// Target dates are `YYYY-MM-DD`, optionally followed by a time (GitHub).
+pub fn days_left(goal: &Goal, today: NaiveDate) -> Option<i64> {
+    let target = goal.target_date.as_deref()?;
+    let date = NaiveDate::parse_from_str(target.get(..10)?, "%Y-%m-%d").ok()?;
+    Some((date - today).num_days())
+}
+
+/// Whether an open goal is due within the rule's window and behind
+pub fn is_at_risk(goal: &Goal, today: NaiveDate, rule: ReminderRule) -> bool {
+    rule.days > 0
+        && goal.state == GoalState::Open
+        && goal.progress < rule.min_progress
+        && days_left(goal, today).is_some_and(|days| days <= rule.days as i64)
+}
+
+/// Inbox message for an at-risk goal
+pub fn message(goal: &Goal, today: NaiveDate) -> String {
+    let due = match days_left(goal, today) {
+        Some(0) => "is due today".to_string(),
+        Some(1) => "is due tomorrow".to_string(),
+        Some(days) if days > 0 => format!("is due in {} days", days),
+        Some(-1) => "was due yesterday".to_string(),
+        Some(days) => format!("was due {} days ago", -days),
+        None => "has no target date".to_string(),
+    };
+    format!("Goal '{}' {} and is {}% done", goal.name, due, (goal.progress * 100.0).round() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn goal(target: Option<&str>, progress: f64, state: GoalState) -> Goal {
+        Goal {
+            id: "g1".to_string(),
+            name: "v1.0".to_string(),
+            description: None,
+            target_date: target.map(String::from),
+            state,
+            progress,
+            open_count: None,
+            closed_count: None,
+            created_at: String::new(),
+            updated_at: String::new(),
+            html_url: None,
+        }
+    }
+
+    fn day(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_is_at_risk() {
+        let rule = ReminderRule { days: 7, min_progress: 0.5 };
+        let today = day("2024-03-01");
+
+        assert!(is_at_risk(&goal(Some("2024-03-05"), 0.2, GoalState::Open), today, rule));
+        // GitHub due dates carry a time
+        assert!(is_at_risk(&goal(Some("2024-03-08T07:00:00Z"), 0.2, GoalState::Open), today, rule));
+        // Overdue goals stay at risk
+        assert!(is_at_risk(&goal(Some("2024-02-20"), 0.2, GoalState::Open), today, rule));
+
+        assert!(!is_at_risk(&goal(Some("2024-03-09"), 0.2, GoalState::Open), today, rule));
+        assert!(!is_at_risk(&goal(Some("2024-03-05"), 0.5, GoalState::Open), today, rule));
+        assert!(!is_at_risk(&goal(Some("2024-03-05"), 0.2, GoalState::Closed), today, rule));
+        assert!(!is_at_risk(&goal(None, 0.2, GoalState::Open), today, rule));
+        assert!(!is_at_risk(&goal(Some("2024-03-05"), 0.2, GoalState::Open), today, ReminderRule { days: 0, ..rule }));
+    }
+
+    #[test]
+    fn test_rule_overrides_per_repo() {
+        let config: RemindersConfig = toml::from_str(
+            "min_progress = 0.8\n[repos.\"acme/api\"]\ndays = 14\n",
+        )
+        .unwrap();
+        assert_eq!(config.rule("acme/api"), ReminderRule { days: 14, min_progress: 0.8 });
+        assert_eq!(config.rule("acme/web"), ReminderRule { days: 7, min_progress: 0.8 });
+    }
+
+    #[test]
+    fn test_message() {
+        let today = day("2024-03-01");
+        let g = goal(Some("2024-03-04"), 0.25, GoalState::Open);
+        assert_eq!(message(&g, today), "Goal 'v1.0' is due in 3 days and is 25% done");
+        let g = goal(Some("2024-02-28"), 0.25, GoalState::Open);
+        assert_eq!(message(&g, today), "Goal 'v1.0' was due 2 days ago and is 25% done");
+    }
+}
@@ -1,4 +1,5 @@
 use anyhow::{anyhow, Result};
+use std::path::Path;
 use std::process::Command;
 
 /// Repository identifier (owner/name)
@@ -29,10 +30,29 @@ pub fn detect_repo() -> Result<Repo> {
     parse_repo_url(&url)
 }
 
-/// Get the absolute path to the git repository root
+/// Get the absolute path to the git repository root.
+///
+/// Inside a linked worktree this is the main checkout, so every worktree of a
+/// repo shares its link and cache.
 pub fn detect_repo_path() -> Result<String> {
+    Ok(current_worktree()?.repo_path)
+}
+
+/// The checkout the current directory is in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Worktree {
+    /// Root of this worktree
+    pub path: String,
+    /// Root of the main checkout (same as `path` outside linked worktrees)
+    pub repo_path: String,
+    /// Checked-out branch, None when HEAD is detached
+    pub branch: Option<String>,
+}
+
+/// Detect the current worktree and its branch
+pub fn current_worktree() -> Result<Worktree> {
     let output = Command::new("git")
-        .args(["rev-parse", "--show-toplevel"])
+        .args(["rev-parse", "--show-toplevel", "--git-common-dir"])
         .output()
         .map_err(|_| anyhow!("git not found"))?;
 
@@ -40,8 +60,177 @@ pub fn detect_repo_path() -> Result<String> {
         return Err(anyhow!("Not a git repository"));
     }
 
-    let path = String::from_utf8(output.stdout)?.trim().to_string();
-    Ok(path)
+    let stdout = String::from_utf8(output.stdout)?;
+    let mut lines = stdout.lines().map(str::trim);
+    let path = lines.next().unwrap_or_default().to_string();
+    let repo_path = lines
+        .next()
+        .and_then(|dir| main_checkout(&path, dir))
+        .unwrap_or_else(|| path.clone());
+
+    let branch = Command::new("git")
+        .args(["symbolic-ref", "--quiet", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8(o.stdout).ok())
+        .map(|b| b.trim().to_string())
+        .filter(|b| !b.is_empty());
+
+    Ok(Worktree { path, repo_path, branch })
+}
+
+/// Root of the main checkout given a worktree root and git's common dir
+/// (which git may print relative to the current directory).
+fn main_checkout(worktree: &str, common_dir: &str) -> Option<String> {
+    let common_dir = Path::new(common_dir);
+    let common_dir = if common_dir.is_absolute() {
+        common_dir.to_path_buf()
+    } else {
+        std::env::current_dir().ok()?.join(common_dir)
+    };
+    let common_dir = common_dir.canonicalize().unwrap_or(common_dir);
+
+    // Bare repos and custom layouts have no main checkout; stay where we are
+    if common_dir.file_name()? != ".git" {
+        return Some(worktree.to_string());
+    }
+    Some(common_dir.parent()?.to_string_lossy().into_owned())
+}
+
+/// Issue number named by a branch, e.g. `123-fix-login`, `fix/123-login`, `isq-123`
+pub fn issue_from_branch(branch: &str) -> Option<u64> {
+    let name = branch.rsplit('/').next().unwrap_or(branch);
+    name.split(['-', '_'])
+        .find(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+        .and_then(|part| part.parse().ok())
+}
+
+/// A commit's short hash and message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitInfo {
+    pub sha: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Commits and diffstat for a revision (`HEAD`) or range (`main..HEAD`),
+/// newest first
+pub fn read_commits(rev: &str) -> Result<(Vec<CommitInfo>, String)> {
+    let git = |args: &[&str]| -> Result<String> {
+        let output = Command::new("git").args(args).output().map_err(|_| anyhow!("git not found"))?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "git {} failed: {}",
+                args[0],
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8(output.stdout)?)
+    };
+
+    let is_range = rev.contains("..");
+    let mut log_args = vec!["log", "--format=%h%x1f%s%x1f%b%x1e"];
+    if !is_range {
+        log_args.push("-n1");
+    }
+    log_args.extend(["--end-of-options", rev]);
+
+    let commits = git(&log_args)?
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(3, '\x1f');
+            Some(CommitInfo {
+                sha: fields.next().filter(|s| !s.is_empty())?.to_string(),
+                subject: fields.next()?.to_string(),
+                body: fields.next().unwrap_or_default().trim().to_string(),
+            })
+        })
+        .collect::<Vec<_>>();
+    if commits.is_empty() {
+        return Err(anyhow!("No commits in {}", rev));
+    }
+
+    let stat = if is_range {
+        git(&["diff", "--stat", rev])?
+    } else {
+        git(&["show", "--stat", "--format=", rev])?
+    };
+    Ok((commits, stat.trim_matches('\n').to_string()))
+}
+
+/// Format commits and their diffstat as a markdown comment
+pub fn commits_markdown(commits: &[CommitInfo], stat: &str) -> String {
+    let mut out = String::new();
+    match commits {
+        [commit] => {
+            out.push_str(&format!("**{}** {}\n", commit.sha, commit.subject));
+            if !commit.body.is_empty() {
+                out.push_str(&format!("\n{}\n", commit.body));
+            }
+        }
+        _ => {
+            out.push_str(&format!("{} commits:\n\n", commits.len()));
+            for commit in commits {
+                out.push_str(&format!("- **{}** {}\n", commit.sha, commit.subject));
+            }
+        }
+    }
+    if !stat.is_empty() {
+        out.push_str(&format!("\n```\n{}\n```\n", stat));
+    }
+    out
+}
+
+/// Identity of a checkout that survives moving its directory
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepoFingerprint {
+    /// URL of the `origin` remote
+    pub remote_url: Option<String>,
+    /// Hash of the root commit (stable across clones and moves)
+    pub root_commit: Option<String>,
+}
+
+impl RepoFingerprint {
+    pub fn is_empty(&self) -> bool {
+        self.remote_url.is_none() && self.root_commit.is_none()
+    }
+
+    /// Whether a stored fingerprint refers to the same repository
+    pub fn matches(&self, remote_url: Option<&str>, root_commit: Option<&str>) -> bool {
+        if let (Some(a), Some(b)) = (self.root_commit.as_deref(), root_commit) {
+            return a == b;
+        }
+        match (self.remote_url.as_deref(), remote_url) {
+            (Some(a), Some(b)) => normalize_remote_url(a) == normalize_remote_url(b),
+            _ => false,
+        }
+    }
+}
+
+/// Fingerprint the checkout at `path`
+pub fn fingerprint(path: &str) -> RepoFingerprint {
+    let git = |args: &[&str]| -> Option<String> {
+        let output = Command::new("git").arg("-C").arg(path).args(args).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        // Repos with several roots (merged histories) list more than one; pick deterministically
+        let stdout = String::from_utf8(output.stdout).ok()?;
+        let mut lines: Vec<&str> = stdout.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+        lines.sort_unstable();
+        lines.first().map(|l| l.to_string())
+    };
+
+    RepoFingerprint {
+        remote_url: git(&["remote", "get-url", "origin"]),
+        root_commit: git(&["rev-list", "--max-parents=0", "HEAD"]),
+    }
+}
+
+/// Normalize a remote URL for comparison (case, trailing slash, `.git` suffix)
+fn normalize_remote_url(url: &str) -> String {
+    url.trim().trim_end_matches('/').trim_end_matches(".git").to_lowercase()
 }
 
 /// Parse owner/name from various git URL formats
@@ -82,3 +271,77 @@ fn parse_owner_name(path: &str) -> Result<Repo> {
         name: parts[1].to_string(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fp(remote_url: Option<&str>, root_commit: Option<&str>) -> RepoFingerprint {
+        RepoFingerprint {
+            remote_url: remote_url.map(String::from),
+            root_commit: root_commit.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_matches_root_commit() {
+        let current = fp(Some("git@github.com:acme/new-name.git"), Some("abc123"));
+        assert!(current.matches(Some("git@github.com:acme/old-name.git"), Some("abc123")));
+        assert!(!current.matches(Some("git@github.com:acme/new-name.git"), Some("def456")));
+    }
+
+    #[test]
+    fn test_fingerprint_falls_back_to_remote_url() {
+        let current = fp(Some("https://github.com/acme/api.git"), None);
+        assert!(current.matches(Some("https://github.com/Acme/api/"), Some("abc123")));
+        assert!(!current.matches(Some("https://github.com/acme/web"), None));
+        assert!(!fp(None, None).matches(None, None));
+    }
+
+    #[test]
+    fn test_main_checkout() {
+        let dir = std::env::temp_dir().join(format!("isq-worktree-test-{}", std::process::id()));
+        let git_dir = dir.join("main").join(".git");
+        std::fs::create_dir_all(&git_dir).unwrap();
+        let main = dir.join("main").canonicalize().unwrap();
+
+        let found = main_checkout("/tmp/elsewhere", git_dir.to_str().unwrap());
+        assert_eq!(found.as_deref(), main.to_str());
+
+        // Bare repo: no checkout to go to
+        let bare = dir.join("bare.git");
+        std::fs::create_dir_all(&bare).unwrap();
+        assert_eq!(main_checkout("/tmp/wt", bare.to_str().unwrap()).as_deref(), Some("/tmp/wt"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_issue_from_branch() {
+        assert_eq!(issue_from_branch("123-fix-login"), Some(123));
+        assert_eq!(issue_from_branch("fix/42-login"), Some(42));
+        assert_eq!(issue_from_branch("alice/isq-7"), Some(7));
+        assert_eq!(issue_from_branch("issue_9"), Some(9));
+        assert_eq!(issue_from_branch("release/2.0"), None);
+        assert_eq!(issue_from_branch("main"), None);
+    }
+
+    #[test]
+    fn test_commits_markdown() {
+        let commit = |sha: &str, subject: &str, body: &str| CommitInfo {
+            sha: sha.to_string(),
+            subject: subject.to_string(),
+            body: body.to_string(),
+        };
+        let stat = " src/main.rs | 2 +-\n 1 file changed, 1 insertion(+), 1 deletion(-)";
+
+        assert_eq!(
+            commits_markdown(&[commit("abc1234", "Fix login", "Handle expired tokens.")], stat),
+            "**abc1234** Fix login\n\nHandle expired tokens.\n\n```\n src/main.rs | 2 +-\n 1 file changed, 1 insertion(+), 1 deletion(-)\n```\n"
+        );
+        assert_eq!(
+            commits_markdown(&[commit("def5678", "Add tests", ""), commit("abc1234", "Fix login", "")], ""),
+            "2 commits:\n\n- **def5678** Add tests\n- **abc1234** Fix login\n"
+        );
+    }
+}
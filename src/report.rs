@@ -0,0 +1,518 @@
+//! Reports over cached history (`isq report ...`)
+//!
+//! Goal progress is snapshotted once per day whenever goals are synced, so
+//! `isq report goals` can show how each goal moved over time. `isq report
+//! aging` needs no history: it buckets the open issues in the cache by age.
+//! `isq issue estimate report` rolls cached estimates up per goal or cycle
+//! and forecasts from the velocity of recent cycles.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::db::{Comment, GoalSnapshot};
+use crate::forges::{Cycle, Issue};
+
+/// Columns of `isq report goals --format csv`
+const GOAL_CSV_HEADER: &str = "day,goal_id,goal,state,progress,open,closed";
+
+/// Quote a CSV field if it needs it (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Render goal snapshots as CSV, one row per goal per day
+pub fn goal_snapshots_csv(snapshots: &[GoalSnapshot]) -> String {
+    let mut out = String::from(GOAL_CSV_HEADER);
+    out.push('\n');
+    for s in snapshots {
+        let count = |c: Option<u64>| c.map(|c| c.to_string()).unwrap_or_default();
+        let row = [
+            s.day.clone(),
+            csv_field(&s.goal_id),
+            csv_field(&s.name),
+            s.state.as_str().to_string(),
+            format!("{:.3}", s.progress),
+            count(s.open_count),
+            count(s.closed_count),
+        ];
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Age buckets of `isq report aging`: a name and the age in days it runs up
+/// to (exclusive)
+const AGE_BUCKETS: [(&str, i64); 4] = [("0-7d", 7), ("7-30d", 30), ("30-90d", 90), ("90d+", i64::MAX)];
+
+/// Row for issues without labels in the per-label breakdown
+const NO_LABEL: &str = "(no label)";
+
+/// Open issues in one age bucket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AgeBucket {
+    pub age: &'static str,
+    pub issues: usize,
+}
+
+/// Open issues with a label, per age bucket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LabelAging {
+    pub label: String,
+    /// Counts in the order of `AgingReport::buckets`
+    pub issues: Vec<usize>,
+    pub total: usize,
+}
+
+/// An open issue nobody but its author has answered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Unanswered {
+    pub number: u64,
+    pub id: String,
+    pub title: String,
+    pub author: String,
+    pub age_days: i64,
+}
+
+/// Everything `isq report aging` shows
+#[derive(Debug, Clone, Serialize)]
+pub struct AgingReport {
+    pub open: usize,
+    pub buckets: Vec<AgeBucket>,
+    /// Most issues first
+    pub labels: Vec<LabelAging>,
+    /// Oldest first
+    pub unanswered: Vec<Unanswered>,
+}
+
+fn bucket_of(age_days: i64) -> usize {
+    AGE_BUCKETS.iter().position(|&(_, below)| age_days < below).unwrap_or(AGE_BUCKETS.len() - 1)
+}
+
+/// Whether anyone counting as a maintainer commented on an issue. Without a
+/// maintainer list, anyone but the issue's author counts.
+fn answered(issue: &Issue, comments: &[Comment], maintainers: &[String]) -> bool {
+    comments.iter().any(|c| {
+        if maintainers.is_empty() {
+            !c.author.eq_ignore_ascii_case(&issue.author)
+        } else {
+            maintainers.iter().any(|m| m.eq_ignore_ascii_case(&c.author))
+        }
+    })
+}
+
+/// Bucket the open issues by age (from creation), overall and per label, and
+/// find the ones without a response from `maintainers`
+pub fn aging(
+    issues: &[Issue],
+    comments: &HashMap<u64, Vec<Comment>>,
+    maintainers: &[String],
+    now: DateTime<Utc>,
+) -> AgingReport {
+    let mut totals = [0; AGE_BUCKETS.len()];
+    let mut labels: BTreeMap<&str, [usize; AGE_BUCKETS.len()]> = BTreeMap::new();
+    let mut unanswered = Vec::new();
+
+    for issue in issues.iter().filter(|i| i.state == "open") {
+        let age_days = DateTime::parse_from_rfc3339(&issue.created_at)
+            .map(|created| (now - created.with_timezone(&Utc)).num_days().max(0))
+            .unwrap_or(0);
+        let bucket = bucket_of(age_days);
+        totals[bucket] += 1;
+        if issue.labels.is_empty() {
+            labels.entry(NO_LABEL).or_default()[bucket] += 1;
+        }
+        for label in &issue.labels {
+            labels.entry(&label.name).or_default()[bucket] += 1;
+        }
+        if !answered(issue, comments.get(&issue.number).map(Vec::as_slice).unwrap_or_default(), maintainers) {
+            unanswered.push(Unanswered {
+                number: issue.number,
+                id: issue.display_id(),
+                title: issue.title.clone(),
+                author: issue.author.clone(),
+                age_days,
+            });
+        }
+    }
+
+    let mut labels: Vec<LabelAging> = labels
+        .into_iter()
+        .map(|(label, counts)| LabelAging { label: label.to_string(), issues: counts.to_vec(), total: counts.iter().sum() })
+        .collect();
+    labels.sort_by_key(|l| std::cmp::Reverse(l.total));
+    unanswered.sort_by_key(|u| std::cmp::Reverse(u.age_days));
+
+    AgingReport {
+        open: totals.iter().sum(),
+        buckets: AGE_BUCKETS.iter().zip(totals).map(|(&(age, _), issues)| AgeBucket { age, issues }).collect(),
+        labels,
+        unanswered,
+    }
+}
+
+/// Escape text for a markdown table cell
+fn md_cell(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+/// Render an aging report as markdown (for pasting into an issue or doc)
+pub fn aging_markdown(repo: &str, report: &AgingReport) -> String {
+    let ages: Vec<&str> = report.buckets.iter().map(|b| b.age).collect();
+    let mut out = format!("# Issue aging: {}\n\n{} open issues\n\n", repo, report.open);
+
+    out.push_str("| Age | Open |\n| --- | ---: |\n");
+    for bucket in &report.buckets {
+        out.push_str(&format!("| {} | {} |\n", bucket.age, bucket.issues));
+    }
+
+    if !report.labels.is_empty() {
+        out.push_str(&format!("\n## By label\n\n| Label | {} | Total |\n", ages.join(" | ")));
+        out.push_str(&format!("| --- |{} ---: |\n", " ---: |".repeat(ages.len())));
+        for label in &report.labels {
+            let counts: Vec<String> = label.issues.iter().map(|n| n.to_string()).collect();
+            out.push_str(&format!("| {} | {} | {} |\n", md_cell(&label.label), counts.join(" | "), label.total));
+        }
+    }
+
+    out.push_str(&format!("\n## No response from maintainers ({})\n\n", report.unanswered.len()));
+    if report.unanswered.is_empty() {
+        out.push_str("Every open issue has a response.\n");
+    }
+    for issue in &report.unanswered {
+        out.push_str(&format!("- {} {} ({}d, @{})\n", issue.id, issue.title, issue.age_days, issue.author));
+    }
+    out
+}
+
+/// Row for issues without a goal or cycle in the estimate roll-up
+const UNPLANNED: &str = "(none)";
+
+/// Cycle length to forecast with when no finished cycle says otherwise
+const DEFAULT_CYCLE_DAYS: f64 = 14.0;
+
+/// What `isq issue estimate report` groups issues by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EstimateGrouping {
+    Goal,
+    Cycle,
+}
+
+/// Points in one goal or cycle
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PointsGroup {
+    pub name: String,
+    pub open_points: f64,
+    pub closed_points: f64,
+    /// Issues without an estimate (not counted in the points)
+    pub unestimated: usize,
+    /// When the open points should be done at the recent velocity
+    /// (YYYY-MM-DD; goals only)
+    pub forecast: Option<String>,
+}
+
+/// Points closed in a finished cycle
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CycleVelocity {
+    pub number: u32,
+    pub name: Option<String>,
+    pub points: f64,
+}
+
+/// Everything `isq issue estimate report` shows
+#[derive(Debug, Clone, Serialize)]
+pub struct EstimateReport {
+    pub groups: Vec<PointsGroup>,
+    /// The last finished cycles, oldest first
+    pub velocity: Vec<CycleVelocity>,
+    /// Mean points closed per cycle over `velocity`
+    pub average_velocity: Option<f64>,
+}
+
+fn parse_time(at: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(at).ok().map(|at| at.with_timezone(&Utc))
+}
+
+/// "Cycle 12", with the cycle's name if it has one
+fn cycle_label(number: u32, name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("Cycle {} ({})", number, name),
+        None => format!("Cycle {}", number),
+    }
+}
+
+/// Roll estimates up per goal or cycle. Velocity is the points closed in each
+/// of the last `recent` cycles that have ended; goals get a forecast from the
+/// average, one cycle length per cycle of work left.
+pub fn estimates(
+    issues: &[Issue],
+    cycles: &[Cycle],
+    grouping: EstimateGrouping,
+    recent: usize,
+    now: DateTime<Utc>,
+) -> EstimateReport {
+    let mut finished: Vec<&Cycle> = cycles.iter().filter(|c| parse_time(&c.ends_at).is_some_and(|end| end <= now)).collect();
+    finished.sort_by_key(|c| c.number);
+    let finished = &finished[finished.len().saturating_sub(recent)..];
+
+    let velocity: Vec<CycleVelocity> = finished
+        .iter()
+        .map(|c| CycleVelocity {
+            number: c.number,
+            name: c.name.clone(),
+            points: issues
+                .iter()
+                .filter(|i| i.state == "closed" && i.cycle == Some(c.number))
+                .filter_map(|i| i.estimate)
+                .sum(),
+        })
+        .collect();
+    let average_velocity =
+        (!velocity.is_empty()).then(|| velocity.iter().map(|v| v.points).sum::<f64>() / velocity.len() as f64);
+    let cycle_days = {
+        let lengths: Vec<f64> = finished
+            .iter()
+            .filter_map(|c| Some((parse_time(&c.ends_at)? - parse_time(&c.starts_at)?).num_hours() as f64 / 24.0))
+            .collect();
+        if lengths.is_empty() { DEFAULT_CYCLE_DAYS } else { lengths.iter().sum::<f64>() / lengths.len() as f64 }
+    };
+
+    // Sorted by cycle number or goal name, issues with neither last
+    let mut groups: BTreeMap<(bool, u32, String), PointsGroup> = BTreeMap::new();
+    for issue in issues {
+        let key = match grouping {
+            EstimateGrouping::Goal => match &issue.milestone {
+                Some(goal) => (false, 0, goal.clone()),
+                None => (true, 0, UNPLANNED.to_string()),
+            },
+            EstimateGrouping::Cycle => match issue.cycle {
+                Some(number) => {
+                    let name = cycles.iter().find(|c| c.number == number).and_then(|c| c.name.as_deref());
+                    (false, number, cycle_label(number, name))
+                }
+                None => (true, 0, UNPLANNED.to_string()),
+            },
+        };
+        let group = groups.entry(key.clone()).or_insert_with(|| PointsGroup {
+            name: key.2,
+            open_points: 0.0,
+            closed_points: 0.0,
+            unestimated: 0,
+            forecast: None,
+        });
+        match (issue.estimate, issue.state == "closed") {
+            (None, _) => group.unestimated += 1,
+            (Some(points), true) => group.closed_points += points,
+            (Some(points), false) => group.open_points += points,
+        }
+    }
+    let mut groups: Vec<PointsGroup> = groups.into_values().collect();
+
+    if grouping == EstimateGrouping::Goal
+        && let Some(velocity) = average_velocity.filter(|v| *v > 0.0)
+    {
+        for group in groups.iter_mut().filter(|g| g.name != UNPLANNED && g.open_points > 0.0) {
+            let days = (group.open_points / velocity * cycle_days).ceil() as i64;
+            group.forecast = Some((now + chrono::Duration::days(days)).format("%Y-%m-%d").to_string());
+        }
+    }
+
+    EstimateReport { groups, velocity, average_velocity }
+}
+
+fn points(value: f64) -> String {
+    if value.fract() == 0.0 { format!("{:.0}", value) } else { format!("{:.1}", value) }
+}
+
+/// Render an estimate report as markdown
+pub fn estimates_markdown(repo: &str, grouping: EstimateGrouping, report: &EstimateReport) -> String {
+    let column = match grouping {
+        EstimateGrouping::Goal => "Goal",
+        EstimateGrouping::Cycle => "Cycle",
+    };
+    let mut out = format!("# Estimates: {}\n\n", repo);
+
+    out.push_str(&format!("| {} | Open | Closed | Unestimated | Forecast |\n| --- | ---: | ---: | ---: | --- |\n", column));
+    for group in &report.groups {
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            md_cell(&group.name),
+            points(group.open_points),
+            points(group.closed_points),
+            group.unestimated,
+            group.forecast.as_deref().unwrap_or("-"),
+        ));
+    }
+
+    out.push_str("\n## Velocity\n\n");
+    match report.average_velocity {
+        Some(average) => {
+            out.push_str(&format!("{} points per cycle over the last {} cycles\n\n", points(average), report.velocity.len()));
+            for cycle in &report.velocity {
+                out.push_str(&format!("- {}: {} points\n", cycle_label(cycle.number, cycle.name.as_deref()), points(cycle.points)));
+            }
+        }
+        None => out.push_str("No finished cycles cached, so no forecast.\n"),
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forges::GoalState;
+
+    #[test]
+    fn test_goal_snapshots_csv() {
+        let snapshots = vec![
+            GoalSnapshot {
+                day: "2024-01-01".to_string(),
+                goal_id: "1".to_string(),
+                name: "v1, \"final\"".to_string(),
+                state: GoalState::Open,
+                progress: 0.5,
+                open_count: Some(2),
+                closed_count: Some(2),
+            },
+            GoalSnapshot {
+                day: "2024-01-02".to_string(),
+                goal_id: "abc".to_string(),
+                name: "Launch".to_string(),
+                state: GoalState::Closed,
+                progress: 1.0,
+                open_count: None,
+                closed_count: None,
+            },
+        ];
+
+        assert_eq!(
+            goal_snapshots_csv(&snapshots),
+            "day,goal_id,goal,state,progress,open,closed\n\
+             2024-01-01,1,\"v1, \"\"final\"\"\",open,0.500,2,2\n\
+             2024-01-02,abc,Launch,closed,1.000,,\n"
+        );
+    }
+
+    fn issue(number: u64, created_at: &str, labels: &[&str]) -> Issue {
+        Issue {
+            number,
+            title: format!("Issue {}", number),
+            state: "open".to_string(),
+            author: "reporter".to_string(),
+            labels: labels.iter().map(|name| crate::forges::Label { name: name.to_string(), color: None }).collect(),
+            created_at: created_at.to_string(),
+            updated_at: created_at.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn comment(issue_number: u64, author: &str) -> Comment {
+        Comment {
+            comment_id: format!("{}-{}", issue_number, author),
+            issue_number,
+            body: "Looking into it".to_string(),
+            author: author.to_string(),
+            created_at: "2025-06-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_aging_buckets_and_responses() {
+        let now = DateTime::parse_from_rfc3339("2025-06-30T00:00:00Z").unwrap().with_timezone(&Utc);
+        let mut closed = issue(5, "2020-01-01T00:00:00Z", &["bug"]);
+        closed.state = "closed".to_string();
+        let issues = vec![
+            issue(1, "2025-06-28T00:00:00Z", &["bug"]),
+            issue(2, "2025-06-10T00:00:00Z", &["bug", "ui"]),
+            issue(3, "2025-01-01T00:00:00Z", &[]),
+            issue(4, "2025-04-15T00:00:00Z", &["ui"]),
+            closed,
+        ];
+        let comments = HashMap::from([
+            (1, vec![comment(1, "reporter")]),
+            (2, vec![comment(2, "maintainer")]),
+            (4, vec![comment(4, "passerby")]),
+        ]);
+
+        let report = aging(&issues, &comments, &[], now);
+        assert_eq!(report.open, 4);
+        let counts: Vec<usize> = report.buckets.iter().map(|b| b.issues).collect();
+        assert_eq!(counts, vec![1, 1, 1, 1]);
+        assert_eq!(report.labels[0].label, "bug");
+        assert_eq!(report.labels[0].issues, vec![1, 1, 0, 0]);
+        assert!(report.labels.iter().any(|l| l.label == NO_LABEL && l.issues == vec![0, 0, 0, 1]));
+        // The author's own comment isn't a response
+        let unanswered: Vec<u64> = report.unanswered.iter().map(|u| u.number).collect();
+        assert_eq!(unanswered, vec![3, 1]);
+
+        // With a maintainer list, only their comments count
+        let report = aging(&issues, &comments, &["Maintainer".to_string()], now);
+        let unanswered: Vec<u64> = report.unanswered.iter().map(|u| u.number).collect();
+        assert_eq!(unanswered, vec![3, 4, 1]);
+
+        let markdown = aging_markdown("acme/app", &report);
+        assert!(markdown.contains("| Label | 0-7d | 7-30d | 30-90d | 90d+ | Total |\n"));
+        assert!(markdown.contains("| bug | 1 | 1 | 0 | 0 | 2 |\n"));
+        assert!(markdown.contains("## No response from maintainers (3)\n\n- #3 Issue 3 (180d, @reporter)\n"));
+    }
+
+    #[test]
+    fn test_estimates_by_goal_and_cycle() {
+        let now = DateTime::parse_from_rfc3339("2025-06-30T00:00:00Z").unwrap().with_timezone(&Utc);
+        let cycle = |number: u32, starts_at: &str, ends_at: &str| Cycle {
+            number,
+            name: (number == 3).then(|| "Polish".to_string()),
+            starts_at: starts_at.to_string(),
+            ends_at: ends_at.to_string(),
+        };
+        let cycles = vec![
+            cycle(1, "2025-06-02T00:00:00Z", "2025-06-09T00:00:00Z"),
+            cycle(2, "2025-06-09T00:00:00Z", "2025-06-16T00:00:00Z"),
+            cycle(3, "2025-06-16T00:00:00Z", "2025-06-23T00:00:00Z"),
+            // Still running
+            cycle(4, "2025-06-23T00:00:00Z", "2025-07-07T00:00:00Z"),
+        ];
+        let planned = |number: u64, state: &str, estimate: Option<f64>, cycle: u32, goal: Option<&str>| {
+            let mut i = issue(number, "2025-06-01T00:00:00Z", &[]);
+            i.state = state.to_string();
+            i.estimate = estimate;
+            i.cycle = Some(cycle);
+            i.milestone = goal.map(String::from);
+            i
+        };
+        let issues = vec![
+            planned(1, "closed", Some(5.0), 1, Some("v1")),
+            planned(2, "closed", Some(2.0), 2, Some("v1")),
+            planned(3, "closed", Some(3.0), 3, Some("v2")),
+            planned(4, "open", Some(6.0), 4, Some("v1")),
+            planned(5, "open", None, 4, None),
+        ];
+
+        // Over the last two finished cycles: (2 + 3) / 2 points per 7-day cycle
+        let report = estimates(&issues, &cycles, EstimateGrouping::Goal, 2, now);
+        let velocity: Vec<(u32, f64)> = report.velocity.iter().map(|v| (v.number, v.points)).collect();
+        assert_eq!(velocity, vec![(2, 2.0), (3, 3.0)]);
+        assert_eq!(report.average_velocity, Some(2.5));
+        let v1 = &report.groups[0];
+        assert_eq!((v1.name.as_str(), v1.open_points, v1.closed_points), ("v1", 6.0, 7.0));
+        // 6 points at 2.5 a week: 2.4 cycles, 17 days
+        assert_eq!(v1.forecast.as_deref(), Some("2025-07-17"));
+        assert_eq!(report.groups[1].forecast, None);
+        assert_eq!((report.groups[2].name.as_str(), report.groups[2].unestimated), (UNPLANNED, 1));
+
+        let report = estimates(&issues, &cycles, EstimateGrouping::Cycle, 3, now);
+        let names: Vec<&str> = report.groups.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, vec!["Cycle 1", "Cycle 2", "Cycle 3 (Polish)", "Cycle 4"]);
+        assert!(report.groups.iter().all(|g| g.forecast.is_none()));
+
+        let markdown = estimates_markdown("acme", EstimateGrouping::Cycle, &report);
+        assert!(markdown.contains("| Cycle 4 | 6 | 0 | 1 | - |\n"));
+        assert!(markdown.contains("3.3 points per cycle over the last 3 cycles\n\n- Cycle 1: 5 points\n"));
+    }
+}
@@ -13,11 +13,12 @@ pub struct ServiceStatus {
 
 /// Get the log file path for the service (shared across platforms)
 fn log_path() -> Result<PathBuf> {
-    let dirs = directories::ProjectDirs::from("", "", "isq")
-        .ok_or_else(|| anyhow!("Could not determine cache directory"))?;
-    let cache_dir = dirs.cache_dir();
-    fs::create_dir_all(cache_dir)?;
-    Ok(cache_dir.join("daemon.log"))
+    Ok(crate::db::data_dir()?.join("daemon.log"))
+}
+
+/// `ISQ_DATA_DIR` at install time, so the service uses the same cache
+fn data_dir_env() -> Option<String> {
+    std::env::var(crate::db::DATA_DIR_ENV).ok().filter(|v| !v.is_empty())
 }
 
 // ============================================================================
@@ -44,6 +45,15 @@ mod platform {
         let exe_path = exe.to_string_lossy();
         let log = log_path()?;
         let log_path_str = log.to_string_lossy();
+        let env = data_dir_env()
+            .map(|dir| {
+                format!(
+                    "    <key>EnvironmentVariables</key>\n    <dict>\n        <key>{}</key>\n        <string>{}</string>\n    </dict>\n",
+                    crate::db::DATA_DIR_ENV,
+                    dir
+                )
+            })
+            .unwrap_or_default();
 
         Ok(format!(
             r#"<?xml version="1.0" encoding="UTF-8"?>
@@ -66,10 +76,10 @@ mod platform {
     <string>{}</string>
     <key>StandardErrorPath</key>
     <string>{}</string>
-</dict>
+{}</dict>
 </plist>
 "#,
-            SERVICE_LABEL, exe_path, log_path_str, log_path_str
+            SERVICE_LABEL, exe_path, log_path_str, log_path_str, env
         ))
     }
 
@@ -226,6 +236,9 @@ mod platform {
         let exe_path = exe.to_string_lossy();
         let log = log_path()?;
         let log_path_str = log.to_string_lossy();
+        let env = data_dir_env()
+            .map(|dir| format!("Environment={}={}\n", crate::db::DATA_DIR_ENV, dir))
+            .unwrap_or_default();
 
         Ok(format!(
             r#"[Unit]
@@ -239,11 +252,11 @@ Restart=always
 RestartSec=5
 StandardOutput=append:{}
 StandardError=append:{}
-
+{}
 [Install]
 WantedBy=default.target
 "#,
-            exe_path, log_path_str, log_path_str
+            exe_path, log_path_str, log_path_str, env
         ))
     }
 
@@ -378,12 +391,11 @@ WantedBy=default.target
             if let Some(value) = line.strip_prefix("ActiveState=") {
                 running = value == "active";
             }
-            if let Some(value) = line.strip_prefix("MainPID=") {
-                if let Ok(p) = value.parse::<u32>() {
-                    if p > 0 {
-                        pid = Some(p);
-                    }
-                }
+            if let Some(value) = line.strip_prefix("MainPID=")
+                && let Ok(p) = value.parse::<u32>()
+                && p > 0
+            {
+                pid = Some(p);
             }
         }
 
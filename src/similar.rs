@@ -0,0 +1,116 @@
+//! Near-duplicate issue titles, checked by `isq issue create`
+//!
+//! Titles are compared by word trigrams (like Postgres' pg_trgm), so plurals,
+//! word order and small typos still match: "App crashes on startup" is close
+//! to "Crash on startup", "Crash on shutdown" isn't.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::forges::Issue;
+
+/// Titles at least this similar (0 to 1) count as possible duplicates
+pub const THRESHOLD: f64 = 0.5;
+
+/// How many candidates to report
+pub const MAX_CANDIDATES: usize = 3;
+
+/// An open issue whose title looks like the new one
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimilarIssue {
+    pub number: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub similarity: f64,
+}
+
+impl SimilarIssue {
+    /// `#123`, or the identifier for Linear
+    pub fn display_id(&self) -> String {
+        self.identifier.clone().unwrap_or_else(|| format!("#{}", self.number))
+    }
+}
+
+/// Trigrams of each lowercased word, padded so word starts and ends count
+fn trigrams(text: &str) -> HashSet<[char; 3]> {
+    let mut grams = HashSet::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+        let padded: Vec<char> = "  ".chars().chain(word.chars().flat_map(char::to_lowercase)).chain([' ']).collect();
+        for window in padded.windows(3) {
+            grams.insert([window[0], window[1], window[2]]);
+        }
+    }
+    grams
+}
+
+/// Shared trigrams over all trigrams of both titles
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (trigrams(a), trigrams(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// Open issues whose titles look like `title`, most similar first
+pub fn find(title: &str, issues: &[Issue]) -> Vec<SimilarIssue> {
+    let mut similar: Vec<SimilarIssue> = issues
+        .iter()
+        .filter(|issue| issue.state == "open")
+        .filter_map(|issue| {
+            let score = similarity(title, &issue.title);
+            (score >= THRESHOLD).then(|| SimilarIssue {
+                number: issue.number,
+                identifier: issue.identifier.clone(),
+                title: issue.title.clone(),
+                url: issue.url.clone(),
+                similarity: (score * 100.0).round() / 100.0,
+            })
+        })
+        .collect();
+    similar.sort_by(|a, b| b.similarity.total_cmp(&a.similarity).then(a.number.cmp(&b.number)));
+    similar.truncate(MAX_CANDIDATES);
+    similar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(number: u64, title: &str, state: &str) -> Issue {
+        Issue {
+            number,
+            title: title.to_string(),
+            state: state.to_string(),
+            author: "alice".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(similarity("Crash on startup", "crash on STARTUP!"), 1.0);
+        assert!(similarity("Crash on startup", "App crashes on startup") >= THRESHOLD);
+        assert!(similarity("Crash on startup", "Crash on shutdown") < THRESHOLD);
+        assert!(similarity("Add dark mode", "Fix login redirect") < 0.1);
+        assert_eq!(similarity("", "anything"), 0.0);
+    }
+
+    #[test]
+    fn test_find_skips_closed_and_ranks_by_similarity() {
+        let issues = vec![
+            issue(1, "App crashes on startup", "open"),
+            issue(2, "Crash on startup", "closed"),
+            issue(3, "Crash on startup", "open"),
+            issue(4, "Dark mode", "open"),
+        ];
+        let numbers: Vec<u64> = find("Crash on startup", &issues).iter().map(|s| s.number).collect();
+        assert_eq!(numbers, vec![3, 1]);
+        assert!(find("Dark mode toggle in settings page", &issues).is_empty());
+    }
+}
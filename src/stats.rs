@@ -0,0 +1,296 @@
+//! Repository analytics computed from the cache (`isq stats`)
+//!
+//! The cache doesn't keep close times, so a closed issue counts as closed
+//! when it was last updated. That's exact for issues nobody touches after
+//! closing, which is most of them.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::Serialize;
+
+use crate::db::Comment;
+use crate::display;
+use crate::forges::Issue;
+
+/// How many labels and authors `isq stats` lists
+const TOP_N: usize = 5;
+
+/// Width of the longest bar in the label/author charts
+const BAR_WIDTH: usize = 20;
+
+/// Activity in one week (Monday to Sunday)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WeekStats {
+    /// Monday of the week, YYYY-MM-DD
+    pub week: String,
+    pub opened: usize,
+    pub closed: usize,
+    pub comments: usize,
+}
+
+/// A label and how many issues carry it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LabelCount {
+    pub label: String,
+    pub issues: usize,
+}
+
+/// An author's issues and comments
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuthorActivity {
+    pub author: String,
+    pub issues: usize,
+    pub comments: usize,
+}
+
+/// Everything `isq stats` shows
+#[derive(Debug, Clone, Serialize)]
+pub struct RepoStats {
+    pub open: usize,
+    pub closed: usize,
+    pub comments: usize,
+    /// Median days from open to close, over closed issues
+    pub median_days_to_close: Option<f64>,
+    /// Oldest week first, ending with the current week
+    pub weeks: Vec<WeekStats>,
+    pub top_labels: Vec<LabelCount>,
+    pub top_authors: Vec<AuthorActivity>,
+}
+
+fn parse_time(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s).ok().map(|t| t.with_timezone(&Utc))
+}
+
+fn week_start(day: NaiveDate) -> NaiveDate {
+    day - Duration::days(day.weekday().num_days_from_monday() as i64)
+}
+
+/// Compute stats over `issues` and `comments`, with `weeks` weekly buckets
+/// ending at the week containing `today`
+pub fn compute(issues: &[Issue], comments: &[Comment], today: NaiveDate, weeks: usize) -> RepoStats {
+    let current = week_start(today);
+    let mut buckets: Vec<WeekStats> = (0..weeks)
+        .rev()
+        .map(|i| WeekStats {
+            week: (current - Duration::weeks(i as i64)).format("%Y-%m-%d").to_string(),
+            opened: 0,
+            closed: 0,
+            comments: 0,
+        })
+        .collect();
+    let first = current - Duration::weeks(weeks as i64 - 1);
+    let bucket = |time: Option<DateTime<Utc>>| -> Option<usize> {
+        let day = week_start(time?.date_naive());
+        (day >= first && day <= current).then(|| ((day - first).num_days() / 7) as usize)
+    };
+
+    let mut closed = 0;
+    let mut days_to_close = Vec::new();
+    let mut labels: HashMap<&str, usize> = HashMap::new();
+    let mut authors: HashMap<&str, AuthorActivity> = HashMap::new();
+
+    for issue in issues {
+        let created = parse_time(&issue.created_at);
+        if let Some(i) = bucket(created) {
+            buckets[i].opened += 1;
+        }
+        if issue.state == "closed" {
+            closed += 1;
+            let closed_at = parse_time(&issue.updated_at);
+            if let Some(i) = bucket(closed_at) {
+                buckets[i].closed += 1;
+            }
+            if let (Some(created), Some(closed_at)) = (created, closed_at) {
+                days_to_close.push((closed_at - created).num_seconds().max(0) as f64 / 86_400.0);
+            }
+        }
+        for label in &issue.labels {
+            *labels.entry(&label.name).or_default() += 1;
+        }
+        author_entry(&mut authors, &issue.author).issues += 1;
+    }
+
+    for comment in comments {
+        if let Some(i) = bucket(parse_time(&comment.created_at)) {
+            buckets[i].comments += 1;
+        }
+        author_entry(&mut authors, &comment.author).comments += 1;
+    }
+
+    let mut top_labels: Vec<_> = labels
+        .into_iter()
+        .map(|(label, issues)| LabelCount { label: label.to_string(), issues })
+        .collect();
+    top_labels.sort_by(|a, b| b.issues.cmp(&a.issues).then_with(|| a.label.cmp(&b.label)));
+    top_labels.truncate(TOP_N);
+
+    let mut top_authors: Vec<_> = authors.into_values().collect();
+    top_authors.sort_by(|a, b| {
+        (b.issues + b.comments)
+            .cmp(&(a.issues + a.comments))
+            .then_with(|| a.author.cmp(&b.author))
+    });
+    top_authors.truncate(TOP_N);
+
+    RepoStats {
+        open: issues.len() - closed,
+        closed,
+        comments: comments.len(),
+        median_days_to_close: median(&mut days_to_close),
+        weeks: buckets,
+        top_labels,
+        top_authors,
+    }
+}
+
+fn author_entry<'a, 'm>(authors: &'m mut HashMap<&'a str, AuthorActivity>, author: &'a str) -> &'m mut AuthorActivity {
+    authors.entry(author).or_insert_with(|| AuthorActivity { author: author.to_string(), issues: 0, comments: 0 })
+}
+
+fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) { (values[mid - 1] + values[mid]) / 2.0 } else { values[mid] })
+}
+
+/// One glyph per value from `levels` (lowest first), scaled to the largest
+pub fn sparkline(values: &[usize], levels: &[&str]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|&v| levels[(v * (levels.len() - 1)).checked_div(max).unwrap_or(0)])
+        .collect()
+}
+
+fn bar(value: usize, max: usize) -> String {
+    let width = if max == 0 { 0 } else { (value * BAR_WIDTH).div_ceil(max) };
+    display::bar_glyph().repeat(width)
+}
+
+/// Human-readable report
+pub fn render(stats: &RepoStats) -> String {
+    let mut out = String::new();
+    let series = |f: fn(&WeekStats) -> usize| stats.weeks.iter().map(f).collect::<Vec<_>>();
+    let opened = series(|w| w.opened);
+    let closed = series(|w| w.closed);
+    let comments = series(|w| w.comments);
+    let sparks = display::spark_glyphs();
+
+    out.push_str(&format!(
+        "{} open, {} closed, {} comments\n",
+        stats.open, stats.closed, stats.comments
+    ));
+    match stats.median_days_to_close {
+        Some(days) => out.push_str(&format!("Median time to close: {:.1} days\n", days)),
+        None => out.push_str("Median time to close: -\n"),
+    }
+
+    if let (Some(first), Some(last)) = (stats.weeks.first(), stats.weeks.last()) {
+        out.push_str(&format!("\nPer week ({} to {}):\n", first.week, last.week));
+        out.push_str(&format!("  Opened    {}  {}\n", sparkline(&opened, sparks), opened.iter().sum::<usize>()));
+        out.push_str(&format!("  Closed    {}  {}\n", sparkline(&closed, sparks), closed.iter().sum::<usize>()));
+        out.push_str(&format!("  Comments  {}  {}\n", sparkline(&comments, sparks), comments.iter().sum::<usize>()));
+    }
+
+    if !stats.top_labels.is_empty() {
+        let width = stats.top_labels.iter().map(|l| l.label.chars().count()).max().unwrap_or(0);
+        let max = stats.top_labels[0].issues;
+        out.push_str("\nTop labels:\n");
+        for l in &stats.top_labels {
+            out.push_str(&format!("  {:width$}  {:>4}  {}\n", l.label, l.issues, bar(l.issues, max)));
+        }
+    }
+
+    if !stats.top_authors.is_empty() {
+        let width = stats.top_authors.iter().map(|a| a.author.chars().count()).max().unwrap_or(0);
+        let max = stats.top_authors.iter().map(|a| a.issues + a.comments).max().unwrap_or(0);
+        out.push_str("\nMost active:\n");
+        for a in &stats.top_authors {
+            out.push_str(&format!(
+                "  {:width$}  {:>4} issues {:>4} comments  {}\n",
+                a.author,
+                a.issues,
+                a.comments,
+                bar(a.issues + a.comments, max)
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forges::Label;
+
+    fn issue(number: u64, state: &str, author: &str, labels: &[&str], created: &str, updated: &str) -> Issue {
+        Issue {
+            number,
+            title: format!("Issue {}", number),
+            state: state.to_string(),
+            author: author.to_string(),
+            labels: labels.iter().map(|l| Label::new(l.to_string(), None)).collect(),
+            created_at: created.to_string(),
+            updated_at: updated.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn comment(author: &str, created: &str) -> Comment {
+        Comment {
+            comment_id: created.to_string(),
+            issue_number: 1,
+            body: "ok".to_string(),
+            author: author.to_string(),
+            created_at: created.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_compute() {
+        // 2024-01-17 is a Wednesday; the last three weeks start Jan 1, 8 and 15
+        let today = NaiveDate::from_ymd_opt(2024, 1, 17).unwrap();
+        let issues = vec![
+            issue(1, "closed", "alice", &["bug"], "2024-01-01T00:00:00Z", "2024-01-03T00:00:00Z"),
+            issue(2, "closed", "bob", &["bug", "ui"], "2024-01-08T00:00:00Z", "2024-01-16T00:00:00Z"),
+            issue(3, "open", "alice", &["ui", "bug"], "2024-01-15T12:00:00Z", "2024-01-15T12:00:00Z"),
+            issue(4, "open", "carol", &[], "2023-06-01T00:00:00Z", "2023-06-01T00:00:00Z"),
+        ];
+        let comments = vec![comment("bob", "2024-01-09T00:00:00Z"), comment("bob", "2024-01-16T00:00:00Z")];
+
+        let stats = compute(&issues, &comments, today, 3);
+        assert_eq!((stats.open, stats.closed, stats.comments), (2, 2, 2));
+        // 2 and 8 days to close
+        assert_eq!(stats.median_days_to_close, Some(5.0));
+
+        let weeks: Vec<_> = stats.weeks.iter().map(|w| (w.week.as_str(), w.opened, w.closed, w.comments)).collect();
+        assert_eq!(weeks, vec![("2024-01-01", 1, 1, 0), ("2024-01-08", 1, 0, 1), ("2024-01-15", 1, 1, 1)]);
+
+        let labels: Vec<_> = stats.top_labels.iter().map(|l| (l.label.as_str(), l.issues)).collect();
+        assert_eq!(labels, vec![("bug", 3), ("ui", 2)]);
+
+        let authors: Vec<_> = stats.top_authors.iter().map(|a| (a.author.as_str(), a.issues, a.comments)).collect();
+        assert_eq!(authors, vec![("bob", 1, 2), ("alice", 2, 0), ("carol", 1, 0)]);
+    }
+
+    #[test]
+    fn test_compute_empty() {
+        let stats = compute(&[], &[], NaiveDate::from_ymd_opt(2024, 1, 17).unwrap(), 2);
+        assert_eq!(stats.median_days_to_close, None);
+        assert_eq!(stats.weeks.len(), 2);
+        assert!(stats.top_labels.is_empty() && stats.top_authors.is_empty());
+    }
+
+    #[test]
+    fn test_sparkline() {
+        let unicode = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+        assert_eq!(sparkline(&[0, 1, 7, 14], &unicode), "▁▁▄█");
+        assert_eq!(sparkline(&[0, 0], &unicode), "▁▁");
+        assert_eq!(sparkline(&[], &unicode), "");
+        assert_eq!(sparkline(&[0, 1, 7, 14], &[" ", ".", ":", "-", "=", "+", "*", "#"]), "  -#");
+    }
+}
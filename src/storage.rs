@@ -0,0 +1,184 @@
+//! Cache file sizes, kept in check by the daemon
+//!
+//! SQLite's write-ahead log only shrinks when a checkpoint truncates it, and
+//! with the daemon always connected that can take a long time on some
+//! filesystems. So the daemon checkpoints every few minutes, or as soon as the
+//! log passes a size, and watches how big each database grows:
+//!
+//! ```toml
+//! [daemon.storage]
+//! checkpoint_minutes = 5   # wal_checkpoint(TRUNCATE) this often (0 = only by size)
+//! max_wal_mb = 64          # checkpoint as soon as the log is bigger (0 = no limit)
+//! max_db_mb = 1024         # log a warning above this (0 = no limit)
+//! on_large_db = "warn"     # or "vacuum" to reclaim free pages
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::db;
+
+const MB: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct StorageConfig {
+    pub checkpoint_minutes: u64,
+    pub max_wal_mb: u64,
+    pub max_db_mb: u64,
+    pub on_large_db: LargeDbAction,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        StorageConfig { checkpoint_minutes: 5, max_wal_mb: 64, max_db_mb: 1024, on_large_db: LargeDbAction::Warn }
+    }
+}
+
+/// What the daemon does when a database passes `max_db_mb`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LargeDbAction {
+    #[default]
+    Warn,
+    Vacuum,
+}
+
+/// Sizes of a database and its write-ahead log, in bytes
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FileSizes {
+    pub db: u64,
+    pub wal: u64,
+}
+
+impl FileSizes {
+    /// Current sizes (0 for a file that doesn't exist)
+    pub fn of(db_path: &Path) -> FileSizes {
+        let len = |path: &Path| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        FileSizes { db: len(db_path), wal: len(&wal_path(db_path)) }
+    }
+}
+
+/// The write-ahead log next to a database (`cache.db-wal`)
+pub fn wal_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.as_os_str().to_owned();
+    name.push("-wal");
+    PathBuf::from(name)
+}
+
+/// Size in megabytes, for messages
+pub fn megabytes(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / MB as f64)
+}
+
+/// What's due for one database
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Due {
+    pub checkpoint: bool,
+    pub over_limit: bool,
+}
+
+impl StorageConfig {
+    fn wal_too_big(&self, sizes: FileSizes) -> bool {
+        self.max_wal_mb > 0 && sizes.wal > self.max_wal_mb * MB
+    }
+
+    /// What to do given a database's sizes and the time since its last
+    /// checkpoint (`None` if the daemon hasn't checkpointed it yet)
+    pub fn due(&self, sizes: FileSizes, since_checkpoint: Option<Duration>) -> Due {
+        let interval = Duration::from_secs(self.checkpoint_minutes * 60);
+        let interval_passed = self.checkpoint_minutes > 0 && since_checkpoint.is_none_or(|since| since >= interval);
+        Due {
+            checkpoint: interval_passed || self.wal_too_big(sizes),
+            over_limit: self.max_db_mb > 0 && sizes.db > self.max_db_mb * MB,
+        }
+    }
+}
+
+/// Per-database state the daemon keeps between cycles
+#[derive(Debug, Default)]
+pub struct Maintenance {
+    last_checkpoint: HashMap<PathBuf, Instant>,
+    /// Databases already reported over `max_db_mb`, so each crossing is
+    /// handled once rather than every cycle
+    over_limit: HashSet<PathBuf>,
+}
+
+impl Maintenance {
+    /// Checkpoint the database at `path` if due, and act on its size
+    pub fn run(&mut self, config: &StorageConfig, path: &Path) -> Result<()> {
+        let sizes = FileSizes::of(path);
+        let due = config.due(sizes, self.last_checkpoint.get(path).map(Instant::elapsed));
+
+        if due.checkpoint {
+            let complete = db::checkpoint(&db::open_at(path)?)?;
+            self.last_checkpoint.insert(path.to_path_buf(), Instant::now());
+            if config.wal_too_big(sizes) {
+                eprintln!(
+                    "[daemon] Checkpointed {} (write-ahead log was {}{})",
+                    path.display(),
+                    megabytes(sizes.wal),
+                    if complete { "" } else { "; readers kept part of it" }
+                );
+            }
+        }
+
+        if !due.over_limit {
+            self.over_limit.remove(path);
+            return Ok(());
+        }
+        if !self.over_limit.insert(path.to_path_buf()) {
+            return Ok(());
+        }
+        eprintln!(
+            "[daemon] {} is {}, over max_db_mb = {}",
+            path.display(),
+            megabytes(sizes.db),
+            config.max_db_mb
+        );
+        if config.on_large_db == LargeDbAction::Vacuum {
+            db::vacuum(&db::open_at(path)?)?;
+            eprintln!("[daemon] Vacuumed {}: now {}", path.display(), megabytes(FileSizes::of(path).db));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_due_by_interval_and_size() {
+        let config = StorageConfig::default();
+        let small = FileSizes { db: MB, wal: MB };
+        let minute = Duration::from_secs(60);
+
+        assert!(config.due(small, None).checkpoint);
+        assert!(!config.due(small, Some(minute)).checkpoint);
+        assert!(config.due(small, Some(5 * minute)).checkpoint);
+
+        let big_wal = FileSizes { db: MB, wal: 65 * MB };
+        assert!(config.due(big_wal, Some(minute)).checkpoint);
+
+        let only_by_size = StorageConfig { checkpoint_minutes: 0, ..StorageConfig::default() };
+        assert!(!only_by_size.due(small, None).checkpoint);
+        assert!(only_by_size.due(big_wal, None).checkpoint);
+    }
+
+    #[test]
+    fn test_over_limit_and_config() {
+        let config: StorageConfig = toml::from_str("max_db_mb = 10\non_large_db = \"vacuum\"\n").unwrap();
+        assert_eq!(config.on_large_db, LargeDbAction::Vacuum);
+        assert_eq!(config.checkpoint_minutes, 5);
+        assert!(config.due(FileSizes { db: 11 * MB, wal: 0 }, None).over_limit);
+        assert!(!config.due(FileSizes { db: 9 * MB, wal: 0 }, None).over_limit);
+
+        let unlimited = StorageConfig { max_db_mb: 0, ..config };
+        assert!(!unlimited.due(FileSizes { db: 100 * MB, wal: 0 }, None).over_limit);
+    }
+}
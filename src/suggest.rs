@@ -0,0 +1,164 @@
+//! Label suggestions from issue content (`isq issue suggest-labels`)
+//!
+//! Keyword rules come from config; a classifier command (e.g. an LLM wrapper)
+//! can add more. It gets the issue as JSON on stdin and prints label names,
+//! one per line or as a JSON array.
+//!
+//! ```toml
+//! [labels]
+//! classifier = "~/bin/classify-issue"
+//!
+//! [[labels.rules]]
+//! label = "bug"
+//! keywords = ["crash", "panic", "stack trace"]
+//! ```
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::forges::Issue;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LabelRule {
+    pub label: String,
+    /// Suggest `label` if the title or body contains any of these (case-insensitive)
+    pub keywords: Vec<String>,
+}
+
+/// A suggested label and why
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Suggestion {
+    pub label: String,
+    pub reason: String,
+}
+
+/// Whether `text` contains `keyword` as a whole word or phrase
+pub fn contains_word(text: &str, keyword: &str) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(keyword).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + keyword.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+/// Labels the keyword rules suggest for `issue`, skipping ones it already has
+pub fn suggest(rules: &[LabelRule], issue: &Issue) -> Vec<Suggestion> {
+    let text = format!("{}\n{}", issue.title, issue.body.as_deref().unwrap_or("")).to_lowercase();
+
+    let mut suggestions: Vec<Suggestion> = Vec::new();
+    for rule in rules {
+        let already = issue.labels.iter().any(|l| l.name.eq_ignore_ascii_case(&rule.label))
+            || suggestions.iter().any(|s| s.label.eq_ignore_ascii_case(&rule.label));
+        if already {
+            continue;
+        }
+        if let Some(keyword) = rule.keywords.iter().find(|k| contains_word(&text, &k.to_lowercase())) {
+            suggestions.push(Suggestion {
+                label: rule.label.clone(),
+                reason: format!("mentions \"{}\"", keyword),
+            });
+        }
+    }
+    suggestions
+}
+
+/// Parse classifier output: a JSON array of names, or one name per line
+pub fn parse_classifier_output(output: &str) -> Vec<String> {
+    if let Ok(labels) = serde_json::from_str::<Vec<String>>(output.trim()) {
+        return labels;
+    }
+    output
+        .lines()
+        .map(|l| l.trim().trim_start_matches("- ").trim())
+        .filter(|l| !l.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Run the classifier command on an issue and return the labels it prints
+pub fn classify(command: &str, issue: &Issue) -> Result<Vec<String>> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to run classifier: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // A classifier that ignores stdin closes the pipe early; that's fine
+        let _ = stdin.write_all(serde_json::to_string(issue)?.as_bytes());
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(anyhow!("Classifier exited with {}", output.status));
+    }
+    Ok(parse_classifier_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forges::Label;
+
+    fn issue(title: &str, body: &str, labels: &[&str]) -> Issue {
+        Issue {
+            number: 1,
+            title: title.to_string(),
+            body: Some(body.to_string()),
+            state: "open".to_string(),
+            author: "alice".to_string(),
+            labels: labels.iter().map(|l| Label::name_only(l.to_string())).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn rules() -> Vec<LabelRule> {
+        crate::config::parse(
+            r#"
+            [[labels.rules]]
+            label = "bug"
+            keywords = ["crash", "stack trace"]
+
+            [[labels.rules]]
+            label = "docs"
+            keywords = ["readme", "typo"]
+
+            [[labels.rules]]
+            label = "perf"
+            keywords = ["slow"]
+            "#,
+        )
+        .unwrap()
+        .labels
+        .rules
+    }
+
+    #[test]
+    fn test_suggest() {
+        let found = suggest(&rules(), &issue("App CRASHES on save", "Here is the Stack Trace", &[]));
+        assert_eq!(
+            found,
+            vec![Suggestion {
+                label: "bug".to_string(),
+                reason: "mentions \"stack trace\"".to_string(),
+            }]
+        );
+
+        // Whole words only, existing labels skipped
+        let found = suggest(&rules(), &issue("Typo in README", "slowly", &["Docs"]));
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn test_parse_classifier_output() {
+        assert_eq!(parse_classifier_output("[\"bug\", \"ui\"]\n"), vec!["bug", "ui"]);
+        assert_eq!(parse_classifier_output("bug\n- ui\n\n"), vec!["bug", "ui"]);
+        assert!(parse_classifier_output("").is_empty());
+    }
+}
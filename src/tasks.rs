@@ -0,0 +1,130 @@
+//! Markdown task lists (`- [ ] thing`, `- [x] done`) in issue bodies
+
+use serde::Serialize;
+
+/// One checkbox item
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Task {
+    pub text: String,
+    pub done: bool,
+}
+
+/// A task-list line: its checkbox's byte offset within the line, and the task
+fn parse_line(line: &str) -> Option<(usize, Task)> {
+    let trimmed = line.trim_start();
+    let rest = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+        .or_else(|| trimmed.strip_prefix("+ "))?;
+    let (done, text) = if let Some(text) = rest.strip_prefix("[ ]") {
+        (false, text)
+    } else if let Some(text) = rest.strip_prefix("[x]").or_else(|| rest.strip_prefix("[X]")) {
+        (true, text)
+    } else {
+        return None;
+    };
+
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let offset = line.len() - rest.len();
+    Some((offset, Task { text: text.to_string(), done }))
+}
+
+/// Task-list items with the index of their line. Items inside fenced code
+/// blocks are ignored.
+fn task_lines(body: &str) -> Vec<(usize, usize, Task)> {
+    let mut tasks = Vec::new();
+    let mut in_code = false;
+    for (i, line) in body.split('\n').enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        if let Some((offset, task)) = parse_line(line) {
+            tasks.push((i, offset, task));
+        }
+    }
+    tasks
+}
+
+/// Task-list items in a markdown body, in order
+pub fn parse(body: &str) -> Vec<Task> {
+    task_lines(body).into_iter().map(|(_, _, task)| task).collect()
+}
+
+/// (done, total) for a body's task list, None if it has none
+pub fn progress(body: &str) -> Option<(usize, usize)> {
+    let tasks = parse(body);
+    if tasks.is_empty() {
+        return None;
+    }
+    Some((tasks.iter().filter(|t| t.done).count(), tasks.len()))
+}
+
+/// Flip the checkbox of task `index` (1-based). Returns the new body and the
+/// task as it is now, or None if there is no such task.
+pub fn toggle(body: &str, index: usize) -> Option<(String, Task)> {
+    let (line_index, offset, mut task) = task_lines(body).into_iter().nth(index.checked_sub(1)?)?;
+    task.done = !task.done;
+
+    let mut lines: Vec<String> = body.split('\n').map(String::from).collect();
+    let line = &mut lines[line_index];
+    line.replace_range(offset..offset + 3, if task.done { "[x]" } else { "[ ]" });
+    Some((lines.join("\n"), task))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let body = "Plan:\n\
+                    - [ ] Design schema\n\
+                    \x20 * [x] Write migration\n\
+                    - [X] Review\n\
+                    - [ ]\n\
+                    - plain item\n\
+                    ```\n\
+                    - [ ] not a task\n\
+                    ```\n\
+                    + [ ] Ship it";
+        let task = |text: &str, done| Task { text: text.to_string(), done };
+        assert_eq!(
+            parse(body),
+            vec![
+                task("Design schema", false),
+                task("Write migration", true),
+                task("Review", true),
+                task("Ship it", false),
+            ]
+        );
+        assert!(parse("").is_empty());
+    }
+
+    #[test]
+    fn test_progress_and_toggle() {
+        let body = "Steps\r\n- [ ] One\r\n  - [x] Two\r\n```\n- [ ] code\n```\n- [ ] Three";
+        assert_eq!(progress(body), Some((1, 3)));
+        assert_eq!(progress("no tasks"), None);
+
+        let (body, task) = toggle(body, 3).unwrap();
+        assert_eq!(task, Task { text: "Three".to_string(), done: true });
+        assert!(body.ends_with("```\n- [x] Three"));
+        assert!(body.contains("- [ ] code"));
+
+        let (body, task) = toggle(&body, 2).unwrap();
+        assert!(!task.done);
+        assert_eq!(body, "Steps\r\n- [ ] One\r\n  - [ ] Two\r\n```\n- [ ] code\n```\n- [x] Three");
+        assert_eq!(progress(&body), Some((1, 3)));
+
+        assert!(toggle(&body, 0).is_none());
+        assert!(toggle(&body, 4).is_none());
+    }
+}
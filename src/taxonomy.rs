@@ -0,0 +1,212 @@
+//! Canonical label set (`[[labels.taxonomy]]` in config.toml)
+//!
+//! `isq label sync` compares the forge's labels against the taxonomy and
+//! creates, renames (from `aliases`) or recolors them to match; `issue create`
+//! warns about labels outside it.
+//!
+//! ```toml
+//! [[labels.taxonomy]]
+//! name = "bug"
+//! color = "d73a4a"
+//! description = "Something isn't working"
+//! aliases = ["defect", "Bug Report"]
+//! ```
+
+use serde::Deserialize;
+
+use crate::forges::LabelDef;
+use crate::suggest::LabelRule;
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct LabelsConfig {
+    pub taxonomy: Vec<TaxonomyLabel>,
+    /// Keyword rules for `isq issue suggest-labels`
+    pub rules: Vec<LabelRule>,
+    /// Command that prints suggested labels for an issue given as JSON on stdin
+    pub classifier: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TaxonomyLabel {
+    pub name: String,
+    /// Hex color, with or without #
+    pub color: Option<String>,
+    pub description: Option<String>,
+    /// Old names; a forge label with one of these is renamed
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+impl TaxonomyLabel {
+    fn matches(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name) || self.aliases.iter().any(|a| a.eq_ignore_ascii_case(name))
+    }
+
+    fn to_def(&self) -> LabelDef {
+        LabelDef {
+            name: self.name.clone(),
+            color: self.color.as_deref().map(normalize_color),
+            description: self.description.clone(),
+        }
+    }
+}
+
+/// One change needed to bring the forge in line with the taxonomy
+#[derive(Debug, Clone, PartialEq)]
+pub enum LabelChange {
+    Create(LabelDef),
+    /// Update the label currently named `from`
+    Update { from: String, to: LabelDef, fields: Vec<&'static str> },
+}
+
+impl LabelChange {
+    pub fn describe(&self) -> String {
+        match self {
+            LabelChange::Create(label) => format!("create '{}'", label.name),
+            LabelChange::Update { from, to, fields } if from != &to.name => {
+                let mut text = format!("rename '{}' to '{}'", from, to.name);
+                let rest: Vec<_> = fields.iter().filter(|f| **f != "name").copied().collect();
+                if !rest.is_empty() {
+                    text.push_str(&format!(" and update {}", rest.join(", ")));
+                }
+                text
+            }
+            LabelChange::Update { to, fields, .. } => format!("update {} of '{}'", fields.join(", "), to.name),
+        }
+    }
+}
+
+fn normalize_color(color: &str) -> String {
+    color.trim().trim_start_matches('#').to_lowercase()
+}
+
+/// Changes that make `existing` match `taxonomy`. Labels outside the taxonomy
+/// are left alone; colors and descriptions are only compared when the
+/// taxonomy sets them.
+pub fn plan(taxonomy: &[TaxonomyLabel], existing: &[LabelDef]) -> Vec<LabelChange> {
+    let mut changes = Vec::new();
+    for wanted in taxonomy {
+        // Prefer the canonical name over an alias if both exist
+        let current = existing
+            .iter()
+            .find(|l| l.name.eq_ignore_ascii_case(&wanted.name))
+            .or_else(|| existing.iter().find(|l| wanted.matches(&l.name)));
+
+        let to = wanted.to_def();
+        let Some(current) = current else {
+            changes.push(LabelChange::Create(to));
+            continue;
+        };
+
+        let mut fields = Vec::new();
+        if current.name != to.name {
+            fields.push("name");
+        }
+        if to.color.is_some() && current.color.as_deref().map(normalize_color) != to.color {
+            fields.push("color");
+        }
+        if to.description.is_some() && current.description != to.description {
+            fields.push("description");
+        }
+        if !fields.is_empty() {
+            changes.push(LabelChange::Update { from: current.name.clone(), to, fields });
+        }
+    }
+    changes
+}
+
+/// Forge labels the taxonomy doesn't mention (neither as a name nor an alias)
+pub fn unmanaged<'a>(taxonomy: &[TaxonomyLabel], existing: &'a [LabelDef]) -> Vec<&'a str> {
+    existing
+        .iter()
+        .filter(|l| !taxonomy.iter().any(|t| t.matches(&l.name)))
+        .map(|l| l.name.as_str())
+        .collect()
+}
+
+/// Warnings for labels outside the taxonomy (none if no taxonomy is configured)
+pub fn check_labels(taxonomy: &[TaxonomyLabel], labels: &[String]) -> Vec<String> {
+    if taxonomy.is_empty() {
+        return Vec::new();
+    }
+    labels
+        .iter()
+        .filter(|label| !taxonomy.iter().any(|t| t.name.eq_ignore_ascii_case(label)))
+        .map(|label| match taxonomy.iter().find(|t| t.matches(label)) {
+            Some(canonical) => format!("Label '{}' is deprecated; use '{}'", label, canonical.name),
+            None => format!("Label '{}' is not in the label taxonomy", label),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn taxonomy() -> Vec<TaxonomyLabel> {
+        crate::config::parse(
+            r##"
+            [[labels.taxonomy]]
+            name = "bug"
+            color = "#D73A4A"
+            aliases = ["defect"]
+
+            [[labels.taxonomy]]
+            name = "docs"
+            description = "Documentation"
+
+            [[labels.taxonomy]]
+            name = "perf"
+            "##,
+        )
+        .unwrap()
+        .labels
+        .taxonomy
+    }
+
+    fn label(name: &str, color: &str, description: Option<&str>) -> LabelDef {
+        LabelDef {
+            name: name.to_string(),
+            color: Some(color.to_string()),
+            description: description.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_plan() {
+        let existing = vec![
+            label("Defect", "ffffff", None),
+            label("docs", "0075ca", Some("Documentation")),
+            label("wontfix", "ffffff", None),
+        ];
+
+        let changes = plan(&taxonomy(), &existing);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].describe(), "rename 'Defect' to 'bug' and update color");
+        assert_eq!(changes[1].describe(), "create 'perf'");
+        match &changes[0] {
+            LabelChange::Update { to, .. } => assert_eq!(to.color.as_deref(), Some("d73a4a")),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        assert_eq!(unmanaged(&taxonomy(), &existing), vec!["wontfix"]);
+
+        // Already in line: nothing to do
+        let synced = vec![label("bug", "d73a4a", None), label("docs", "000000", Some("Documentation")), label("perf", "x", None)];
+        assert!(plan(&taxonomy(), &synced).is_empty());
+    }
+
+    #[test]
+    fn test_check_labels() {
+        let labels = vec!["Bug".to_string(), "defect".to_string(), "random".to_string()];
+        assert_eq!(
+            check_labels(&taxonomy(), &labels),
+            vec![
+                "Label 'defect' is deprecated; use 'bug'",
+                "Label 'random' is not in the label taxonomy",
+            ]
+        );
+        assert!(check_labels(&[], &labels).is_empty());
+    }
+}
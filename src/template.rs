@@ -0,0 +1,236 @@
+//! Goal templates (~/.config/isq/templates/<name>.toml)
+//!
+//! `isq goal create <name> --template release` creates the goal and then one
+//! issue per `[[issues]]` entry, assigned to the new goal. `{goal}` and
+//! `{target}` in titles and bodies are replaced with the goal's name and
+//! target date.
+//!
+//! ```toml
+//! description = "Release checklist for {goal}"
+//!
+//! [[issues]]
+//! title = "Update CHANGELOG for {goal}"
+//! labels = ["release"]
+//!
+//! [[issues]]
+//! title = "Tag and publish {goal}"
+//! body = "Due {target}"
+//! ```
+
+use std::future::Future;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::config;
+use crate::error::{self, ErrorKind};
+use crate::forges::Issue;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct GoalTemplate {
+    /// Goal description (used when `--body` isn't given)
+    pub description: Option<String>,
+    pub issues: Vec<TemplateIssue>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TemplateIssue {
+    pub title: String,
+    pub body: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
+impl GoalTemplate {
+    /// Substitute `{goal}` and `{target}` throughout the template
+    pub fn render(&self, goal: &str, target: Option<&str>) -> GoalTemplate {
+        let fill = |text: &str| text.replace("{goal}", goal).replace("{target}", target.unwrap_or("TBD"));
+        GoalTemplate {
+            description: self.description.as_deref().map(fill),
+            issues: self
+                .issues
+                .iter()
+                .map(|issue| TemplateIssue {
+                    title: fill(&issue.title),
+                    body: issue.body.as_deref().map(fill),
+                    labels: issue.labels.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Directory holding named templates
+pub fn templates_dir() -> Result<PathBuf> {
+    Ok(config::config_dir()?.join("templates"))
+}
+
+/// Names of the available templates
+pub fn list() -> Result<Vec<String>> {
+    let mut names: Vec<String> = match std::fs::read_dir(templates_dir()?) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+            .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    names.sort();
+    Ok(names)
+}
+
+/// Load a template by name, or by path if it looks like a file
+pub fn load(name: &str) -> Result<GoalTemplate> {
+    let path = if name.ends_with(".toml") || name.contains(std::path::MAIN_SEPARATOR) {
+        PathBuf::from(name)
+    } else {
+        templates_dir()?.join(format!("{}.toml", name))
+    };
+
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let available = list()?;
+            let hint = if available.is_empty() {
+                format!("Create one at {}", templates_dir()?.join(format!("{}.toml", name)).display())
+            } else {
+                format!("Available: {}", available.join(", "))
+            };
+            return Err(error::err(ErrorKind::NotFound, format!("Template '{}' not found. {}", name, hint)));
+        }
+        Err(e) => return Err(anyhow!("Failed to read {}: {}", path.display(), e)),
+    };
+
+    parse(&contents).map_err(|e| anyhow!("Invalid template {}: {}", path.display(), e))
+}
+
+/// What became of a template's issues
+pub struct IssuesRun<T> {
+    pub created: Vec<Issue>,
+    /// Left for the queue once the forge was unreachable
+    pub queued: Vec<T>,
+    /// The error that stopped the run, with the issues it left uncreated
+    pub failed: Option<(anyhow::Error, Vec<T>)>,
+}
+
+/// Create `issues` in order. After an error `is_offline` accepts, the rest
+/// are returned for queueing; any other error stops the run, so the caller
+/// can say which issues exist before reporting it.
+pub async fn create_issues<T, F, Fut>(issues: Vec<T>, mut create: F, is_offline: fn(&anyhow::Error) -> bool) -> IssuesRun<T>
+where
+    F: FnMut(&T) -> Fut,
+    Fut: Future<Output = Result<Issue>>,
+{
+    let mut run = IssuesRun { created: Vec::new(), queued: Vec::new(), failed: None };
+    let mut issues = issues.into_iter();
+    while let Some(issue) = issues.next() {
+        match create(&issue).await {
+            Ok(created) => run.created.push(created),
+            Err(e) if is_offline(&e) => {
+                run.queued.push(issue);
+                run.queued.extend(issues);
+                break;
+            }
+            Err(e) => {
+                run.failed = Some((e, std::iter::once(issue).chain(issues).collect()));
+                break;
+            }
+        }
+    }
+    run
+}
+
+/// Parse a template from TOML
+pub fn parse(contents: &str) -> Result<GoalTemplate> {
+    let template: GoalTemplate = toml::from_str(contents)?;
+    if template.issues.is_empty() {
+        return Err(anyhow!("template has no [[issues]]"));
+    }
+    Ok(template)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RELEASE: &str = r#"
+        description = "Release checklist for {goal}"
+
+        [[issues]]
+        title = "Update CHANGELOG for {goal}"
+        labels = ["release", "docs"]
+
+        [[issues]]
+        title = "Publish {goal}"
+        body = "Ship by {target}"
+    "#;
+
+    #[test]
+    fn test_parse_template() {
+        let template = parse(RELEASE).unwrap();
+        assert_eq!(template.issues.len(), 2);
+        assert_eq!(template.issues[0].labels, vec!["release", "docs"]);
+        assert!(template.issues[1].labels.is_empty());
+    }
+
+    #[test]
+    fn test_parse_template_requires_issues() {
+        assert!(parse("description = \"empty\"").is_err());
+        assert!(parse("[[issues]]\nbody = \"no title\"").is_err());
+    }
+
+    #[test]
+    fn test_render_substitutes_placeholders() {
+        let rendered = parse(RELEASE).unwrap().render("v2.0", Some("2025-03-01"));
+        assert_eq!(rendered.description.as_deref(), Some("Release checklist for v2.0"));
+        assert_eq!(rendered.issues[0].title, "Update CHANGELOG for v2.0");
+        assert_eq!(rendered.issues[1].body.as_deref(), Some("Ship by 2025-03-01"));
+
+        let undated = parse(RELEASE).unwrap().render("v2.0", None);
+        assert_eq!(undated.issues[1].body.as_deref(), Some("Ship by TBD"));
+    }
+
+    fn offline(e: &anyhow::Error) -> bool {
+        error::classify(e) == ErrorKind::OfflineQueued
+    }
+
+    /// Creates issues until the one titled `fail_at`, which fails with `kind`
+    async fn run(titles: &[&str], fail_at: &str, kind: ErrorKind) -> IssuesRun<String> {
+        let titles = titles.iter().map(|t| t.to_string()).collect();
+        create_issues(
+            titles,
+            |title: &String| {
+                let result = if title == fail_at {
+                    Err(error::err(kind, "nope"))
+                } else {
+                    Ok(Issue { title: title.clone(), ..Default::default() })
+                };
+                async move { result }
+            },
+            offline,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_create_issues_stops_at_failure() {
+        let run = run(&["a", "b", "c", "d"], "b", ErrorKind::Conflict).await;
+        let created: Vec<_> = run.created.iter().map(|i| i.title.as_str()).collect();
+        assert_eq!(created, vec!["a"]);
+        assert!(run.queued.is_empty());
+        let (e, rest) = run.failed.unwrap();
+        assert_eq!(error::classify(&e), ErrorKind::Conflict);
+        assert_eq!(rest, vec!["b", "c", "d"]);
+    }
+
+    #[tokio::test]
+    async fn test_create_issues_queues_rest_when_offline() {
+        let run = run(&["a", "b", "c"], "b", ErrorKind::OfflineQueued).await;
+        assert_eq!(run.created.len(), 1);
+        assert_eq!(run.queued, vec!["b", "c"]);
+        assert!(run.failed.is_none());
+    }
+}
@@ -0,0 +1,181 @@
+//! How times are shown: 12- or 24-hour clock, which timezone, and whether
+//! issue and comment timestamps are relative ("5d ago") or absolute
+//!
+//! ```toml
+//! [display]
+//! clock = "24h"             # 12h (default) or 24h
+//! timezone = "UTC"          # local (default), UTC, or an offset like "+05:30"
+//! timestamps = "absolute"   # relative (default) or absolute
+//! ```
+//!
+//! Only human-readable output changes; JSON keeps the forge's RFC 3339 times.
+
+use chrono::{DateTime, FixedOffset, Local, Offset, Utc};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum Clock {
+    #[default]
+    #[serde(rename = "12h")]
+    TwelveHour,
+    #[serde(rename = "24h")]
+    TwentyFourHour,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Timestamps {
+    #[default]
+    Relative,
+    Absolute,
+}
+
+/// The timezone times are shown in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Zone {
+    /// The system's timezone
+    #[default]
+    Local,
+    Fixed(FixedOffset),
+}
+
+impl TryFrom<String> for Zone {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Zone, String> {
+        Zone::parse(&s).ok_or_else(|| format!("invalid timezone '{}' (expected local, UTC or an offset like +05:30)", s))
+    }
+}
+
+impl Zone {
+    /// Parse "local", "UTC" (or "Z") or an offset: "+05:30", "-0800", "+2"
+    pub fn parse(s: &str) -> Option<Zone> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("local") {
+            return Some(Zone::Local);
+        }
+        if s.eq_ignore_ascii_case("utc") || s.eq_ignore_ascii_case("z") {
+            return Some(Zone::Fixed(Utc.fix()));
+        }
+        let sign = match s.chars().next()? {
+            '+' => 1,
+            '-' => -1,
+            _ => return None,
+        };
+        let digits = s[1..].replace(':', "");
+        if digits.is_empty() || digits.len() > 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let (hours, minutes) = if digits.len() > 2 { digits.split_at(digits.len() - 2) } else { (digits.as_str(), "0") };
+        let (hours, minutes): (i32, i32) = (hours.parse().ok()?, minutes.parse().ok()?);
+        if hours > 14 || minutes > 59 {
+            return None;
+        }
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).map(Zone::Fixed)
+    }
+
+    fn convert(self, at: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            Zone::Local => at.with_timezone(&Local).fixed_offset(),
+            Zone::Fixed(offset) => at.with_timezone(&offset),
+        }
+    }
+}
+
+/// Time settings from `[display]`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeFormat {
+    pub clock: Clock,
+    pub zone: Zone,
+    pub timestamps: Timestamps,
+}
+
+impl TimeFormat {
+    fn clock_pattern(&self) -> &'static str {
+        match self.clock {
+            Clock::TwelveHour => "%-I:%M %p",
+            Clock::TwentyFourHour => "%H:%M",
+        }
+    }
+
+    /// Time of day ("2:05 PM" or "14:05")
+    pub fn time(&self, at: DateTime<Utc>) -> String {
+        self.zone.convert(at).format(self.clock_pattern()).to_string()
+    }
+
+    /// Time of day with seconds, for views that refresh ("2:05:09 PM")
+    pub fn time_with_seconds(&self, at: DateTime<Utc>) -> String {
+        let pattern = match self.clock {
+            Clock::TwelveHour => "%-I:%M:%S %p",
+            Clock::TwentyFourHour => "%H:%M:%S",
+        };
+        self.zone.convert(at).format(pattern).to_string()
+    }
+
+    /// Time of day, with the date in front unless it's the same day as `now`
+    /// ("2:05 PM", "Mar 4 2:05 PM")
+    pub fn moment(&self, at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+        let (at, now) = (self.zone.convert(at), self.zone.convert(now));
+        if at.date_naive() == now.date_naive() {
+            at.format(self.clock_pattern()).to_string()
+        } else {
+            at.format(&format!("%b %-d {}", self.clock_pattern())).to_string()
+        }
+    }
+
+    /// Full date and time ("2025-03-04 14:05")
+    pub fn absolute(&self, at: DateTime<Utc>) -> String {
+        self.zone.convert(at).format(&format!("%Y-%m-%d {}", self.clock_pattern())).to_string()
+    }
+}
+
+/// The configured format, read once per run
+pub static FORMAT: Lazy<TimeFormat> = Lazy::new(|| {
+    let display = crate::config::load().map(|c| c.display).unwrap_or_default();
+    TimeFormat { clock: display.clock, zone: display.timezone, timestamps: display.timestamps }
+});
+
+/// A unix timestamp as `moment` in the configured format
+pub fn local_time(ts: i64) -> String {
+    match DateTime::from_timestamp(ts, 0) {
+        Some(at) => FORMAT.moment(at, Utc::now()),
+        None => ts.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_zone() {
+        assert_eq!(Zone::parse("local"), Some(Zone::Local));
+        assert_eq!(Zone::parse("UTC"), Some(Zone::Fixed(Utc.fix())));
+        assert_eq!(Zone::parse("+05:30"), Some(Zone::Fixed(FixedOffset::east_opt(19800).unwrap())));
+        assert_eq!(Zone::parse("-0800"), Some(Zone::Fixed(FixedOffset::west_opt(28800).unwrap())));
+        assert_eq!(Zone::parse("+2"), Some(Zone::Fixed(FixedOffset::east_opt(7200).unwrap())));
+        assert_eq!(Zone::parse("Europe/Paris"), None);
+        assert_eq!(Zone::parse("+25:00"), None);
+    }
+
+    #[test]
+    fn test_clock_and_zone() {
+        let utc = TimeFormat { zone: Zone::parse("UTC").unwrap(), ..TimeFormat::default() };
+        let now = at("2025-03-04T14:05:09Z");
+        assert_eq!(utc.time(now), "2:05 PM");
+        assert_eq!(utc.time_with_seconds(now), "2:05:09 PM");
+        assert_eq!(utc.moment(at("2025-03-03T09:00:00Z"), now), "Mar 3 9:00 AM");
+
+        let tokyo = TimeFormat { clock: Clock::TwentyFourHour, zone: Zone::parse("+09:00").unwrap(), ..utc };
+        assert_eq!(tokyo.time(now), "23:05");
+        assert_eq!(tokyo.absolute(now), "2025-03-04 23:05");
+        // Past midnight in Tokyo, so no longer the same day there
+        assert_eq!(tokyo.moment(now, at("2025-03-04T16:00:00Z")), "Mar 4 23:05");
+    }
+}
@@ -0,0 +1,134 @@
+//! Your work in progress across linked repos (`isq wip`)
+//!
+//! An open issue assigned to you is in progress when a branch is tied to it
+//! (`isq issue current <id>`) or it carries one of the WIP labels. The rest
+//! of your assigned issues are the queue `isq wip next` picks from.
+//!
+//! ```toml
+//! [wip]
+//! limit = 3                          # warn above this many (0 = no limit)
+//! labels = ["in progress", "doing"]  # case-insensitive
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::forges::Issue;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WipConfig {
+    pub limit: usize,
+    pub labels: Vec<String>,
+}
+
+impl Default for WipConfig {
+    fn default() -> Self {
+        WipConfig {
+            limit: 3,
+            labels: ["in progress", "in-progress", "wip", "doing"].map(String::from).to_vec(),
+        }
+    }
+}
+
+impl WipConfig {
+    /// Whether the number of issues in progress is above the limit
+    pub fn is_over(&self, in_progress: usize) -> bool {
+        self.limit > 0 && in_progress > self.limit
+    }
+}
+
+/// An issue assigned to you, with where it lives
+#[derive(Debug, Clone, Serialize)]
+pub struct WipIssue {
+    /// Linked repo name (display name, or forge repo)
+    pub repo: String,
+    #[serde(flatten)]
+    pub issue: Issue,
+    /// Branch tied to the issue with `isq issue current`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Position in the repo's `isq issue rank` order
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rank: Option<usize>,
+}
+
+impl WipIssue {
+    pub fn is_in_progress(&self, config: &WipConfig) -> bool {
+        self.branch.is_some()
+            || self
+                .issue
+                .labels
+                .iter()
+                .any(|l| config.labels.iter().any(|wip| wip.eq_ignore_ascii_case(&l.name)))
+    }
+}
+
+/// The unstarted issue to pick up next: ranked issues first (by rank), then
+/// by forge priority (urgent first, none last), then the oldest
+pub fn next<'a>(unstarted: &[&'a WipIssue]) -> Option<&'a WipIssue> {
+    unstarted.iter().copied().min_by_key(|w| {
+        (
+            w.rank.is_none(),
+            w.rank,
+            w.issue.priority.filter(|&p| p > 0).unwrap_or(u8::MAX),
+            w.issue.created_at.clone(),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::forges::Label;
+
+    fn wip(number: u64, labels: &[&str], branch: Option<&str>, rank: Option<usize>, priority: Option<u8>) -> WipIssue {
+        WipIssue {
+            repo: "acme/api".to_string(),
+            issue: Issue {
+                number,
+                title: format!("Issue {}", number),
+                state: "open".to_string(),
+                author: "alice".to_string(),
+                labels: labels.iter().map(|l| Label::new(l.to_string(), None)).collect(),
+                created_at: format!("2024-01-{:02}T00:00:00Z", number),
+                assignees: vec!["me".to_string()],
+                priority,
+                ..Default::default()
+            },
+            branch: branch.map(String::from),
+            rank,
+        }
+    }
+
+    #[test]
+    fn test_is_in_progress() {
+        let config = WipConfig::default();
+        assert!(wip(1, &[], Some("fix-1"), None, None).is_in_progress(&config));
+        assert!(wip(2, &["In Progress"], None, None, None).is_in_progress(&config));
+        assert!(!wip(3, &["bug"], None, None, None).is_in_progress(&config));
+    }
+
+    #[test]
+    fn test_is_over() {
+        let config = WipConfig { limit: 2, ..Default::default() };
+        assert!(!config.is_over(2));
+        assert!(config.is_over(3));
+        assert!(!WipConfig { limit: 0, ..Default::default() }.is_over(10));
+    }
+
+    #[test]
+    fn test_next_prefers_rank_then_priority_then_age() {
+        let ranked = wip(1, &[], None, Some(2), None);
+        let urgent = wip(2, &[], None, None, Some(1));
+        let low = wip(3, &[], None, None, Some(4));
+        // Higher number = created later (see `wip`)
+        let older = wip(4, &[], None, None, None);
+        let newer = wip(5, &[], None, None, None);
+
+        assert_eq!(next(&[&urgent, &ranked, &low]).unwrap().issue.number, 1);
+        assert_eq!(next(&[&low, &older, &urgent]).unwrap().issue.number, 2);
+        assert_eq!(next(&[&newer, &older, &low]).unwrap().issue.number, 3);
+        assert_eq!(next(&[&newer, &older]).unwrap().issue.number, 4);
+        assert!(next(&[]).is_none());
+    }
+}
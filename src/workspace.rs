@@ -0,0 +1,50 @@
+//! Search workspaces: a GitHub org or user linked through a search query
+//! (`isq link github --org acme --query 'assignee:@me'`)
+//!
+//! A workspace has no checkout and doesn't sync whole repos. The daemon
+//! re-runs the search and caches each issue found under its own repo, so
+//! repos you haven't cloned still show up in `isq wip`. The link's
+//! forge_repo is the search itself (`search:user:acme assignee:@me`), and the
+//! issues it last found are recorded under that key.
+
+use anyhow::Result;
+
+use crate::db;
+use crate::error::{self, ErrorKind};
+use crate::forges::Forge;
+
+const PREFIX: &str = "search:";
+
+/// The forge_repo of a workspace searching `owner`'s repos
+pub fn forge_repo(owner: &str, query: &str) -> String {
+    format!("{}user:{} {}", PREFIX, owner, query.trim())
+}
+
+/// Whether a link's forge_repo is a search workspace rather than a repo
+pub fn is_workspace(forge_repo: &str) -> bool {
+    forge_repo.starts_with(PREFIX)
+}
+
+/// Re-run a workspace's search and record what it found. Returns the number
+/// of issues.
+pub async fn sync(conn: &rusqlite::Connection, forge: &dyn Forge, forge_repo: &str) -> Result<usize> {
+    let query = forge_repo
+        .strip_prefix(PREFIX)
+        .ok_or_else(|| error::err(ErrorKind::General, format!("Not a search workspace: {}", forge_repo)))?;
+    let hits = forge.search_issues(query).await?;
+    db::save_workspace_issues(conn, forge_repo, &hits)?;
+    Ok(hits.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forge_repo() {
+        let key = forge_repo("acme", " assignee:@me ");
+        assert_eq!(key, "search:user:acme assignee:@me");
+        assert!(is_workspace(&key));
+        assert!(!is_workspace("acme/api"));
+    }
+}